@@ -0,0 +1,207 @@
+//! Benchmarks of the BCS and JSON encodings of large values, to catch performance regressions in
+//! the hand-written `Serialize` and `Deserialize` implementations.
+//!
+//! ```text
+//! cargo bench --bench serde --features json
+//! ```
+
+use std::sync::Arc;
+
+use base64ct::Base64;
+use base64ct::Encoding;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sui_sdk::types::Address;
+use sui_sdk::types::Argument;
+use sui_sdk::types::Bls12381Signature;
+use sui_sdk::types::CheckpointContents;
+use sui_sdk::types::CheckpointData;
+use sui_sdk::types::CheckpointSummary;
+use sui_sdk::types::CheckpointTransaction;
+use sui_sdk::types::CheckpointTransactionInfo;
+use sui_sdk::types::Command;
+use sui_sdk::types::Ed25519PublicKey;
+use sui_sdk::types::Ed25519Signature;
+use sui_sdk::types::EpochId;
+use sui_sdk::types::GasCostSummary;
+use sui_sdk::types::Identifier;
+use sui_sdk::types::InputArgument;
+use sui_sdk::types::MoveCall;
+use sui_sdk::types::ObjectId;
+use sui_sdk::types::ProgrammableTransaction;
+use sui_sdk::types::Publish;
+use sui_sdk::types::SignedCheckpointSummary;
+use sui_sdk::types::SignedTransaction;
+use sui_sdk::types::SimpleSignature;
+use sui_sdk::types::SplitCoins;
+use sui_sdk::types::Transaction;
+use sui_sdk::types::TransactionEffects;
+use sui_sdk::types::TransactionKind;
+use sui_sdk::types::TypeTag;
+use sui_sdk::types::UserSignature;
+use sui_sdk::types::ValidatorAggregatedSignature;
+
+fn decode<T: DeserializeOwned>(b64: &str) -> T {
+    bcs::from_bytes(&Base64::decode_vec(b64.trim()).unwrap()).unwrap()
+}
+
+fn fixture_transaction() -> Transaction {
+    decode(include_str!(
+        "../src/types/transaction/fixtures/wormhole-pyth-transaction"
+    ))
+}
+
+fn fixture_effects() -> TransactionEffects {
+    decode(include_str!(
+        "../src/types/effects/fixtures/pyth-wormhole-v2"
+    ))
+}
+
+fn with_kind(kind: TransactionKind) -> Transaction {
+    Transaction {
+        kind,
+        ..fixture_transaction()
+    }
+}
+
+/// A programmable transaction at the protocol's limit of 1024 commands, alternating between
+/// splitting coins and calling a generic function.
+fn large_ptb() -> Transaction {
+    let inputs = (0..512u64)
+        .map(|i| InputArgument::Pure {
+            value: i.to_le_bytes().to_vec(),
+        })
+        .collect();
+    let commands = (0..1024u16)
+        .map(|i| {
+            if i % 2 == 0 {
+                Command::SplitCoins(SplitCoins {
+                    coin: Argument::GasCoin,
+                    amounts: vec![Argument::Input(i / 2)],
+                })
+            } else {
+                Command::MoveCall(Arc::new(MoveCall {
+                    package: ObjectId::from(Address::TWO),
+                    module: Identifier::new("coin").unwrap(),
+                    function: Identifier::new("join").unwrap(),
+                    type_arguments: vec!["0x2::sui::SUI".parse::<TypeTag>().unwrap()],
+                    arguments: vec![Argument::GasCoin, Argument::NestedResult(i - 1, 0)],
+                }))
+            }
+        })
+        .collect();
+    with_kind(TransactionKind::ProgrammableTransaction(
+        ProgrammableTransaction { inputs, commands },
+    ))
+}
+
+/// A publish of 16 modules of 32KiB each.
+fn publish() -> Transaction {
+    let command = Command::Publish(Arc::new(Publish {
+        modules: (0..16u8).map(|i| vec![i; 32 * 1024]).collect(),
+        dependencies: vec![ObjectId::from(Address::ZERO), ObjectId::from(Address::TWO)],
+    }));
+    with_kind(TransactionKind::ProgrammableTransaction(
+        ProgrammableTransaction {
+            inputs: vec![],
+            commands: vec![command],
+        },
+    ))
+}
+
+/// A checkpoint of 256 signed transactions along with their effects.
+fn checkpoint() -> CheckpointData {
+    let signature = UserSignature::Simple(SimpleSignature::Ed25519 {
+        signature: Ed25519Signature::new([0xab; Ed25519Signature::LENGTH]),
+        public_key: Ed25519PublicKey::new([0xcd; Ed25519PublicKey::LENGTH]),
+    });
+    let transaction = SignedTransaction {
+        transaction: fixture_transaction(),
+        signatures: vec![signature],
+    };
+    let effects = fixture_effects();
+    let transactions: Vec<_> = (0..256)
+        .map(|_| CheckpointTransaction {
+            transaction: transaction.clone(),
+            effects: effects.clone(),
+            events: None,
+            input_objects: vec![],
+            output_objects: vec![],
+        })
+        .collect();
+
+    let contents = CheckpointContents::new(
+        transactions
+            .iter()
+            .map(|transaction| CheckpointTransactionInfo {
+                transaction: *transaction.effects.transaction_digest(),
+                effects: Default::default(),
+                signatures: transaction.transaction.signatures.clone(),
+            })
+            .collect(),
+    );
+    let checkpoint = CheckpointSummary {
+        epoch: EpochId::new(1),
+        sequence_number: 42,
+        network_total_transactions: 100_000,
+        content_digest: Default::default(),
+        previous_digest: Some(Default::default()),
+        epoch_rolling_gas_cost_summary: GasCostSummary::new(1_000_000, 2_000, 1_000, 10),
+        timestamp_ms: 1_700_000_000_000,
+        checkpoint_commitments: vec![],
+        end_of_epoch_data: None,
+        version_specific_data: vec![],
+    };
+    CheckpointData {
+        checkpoint_summary: SignedCheckpointSummary {
+            checkpoint,
+            signature: ValidatorAggregatedSignature {
+                epoch: EpochId::new(1),
+                signature: Bls12381Signature::new([0; Bls12381Signature::LENGTH]),
+                bitmap: (0..100).collect(),
+            },
+        },
+        checkpoint_contents: contents,
+        transactions,
+    }
+}
+
+/// Benchmark serializing and deserializing `value` with both encodings.
+fn roundtrip<T>(c: &mut Criterion, name: &str, value: &T)
+where
+    T: Serialize + DeserializeOwned,
+{
+    let bcs = bcs::to_bytes(value).unwrap();
+    let json = serde_json::to_vec(value).unwrap();
+
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(bcs.len() as u64));
+    group.bench_function(BenchmarkId::new("bcs", "serialize"), |b| {
+        b.iter(|| bcs::to_bytes(value).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("bcs", "deserialize"), |b| {
+        b.iter(|| bcs::from_bytes::<T>(&bcs).unwrap())
+    });
+    group.throughput(Throughput::Bytes(json.len() as u64));
+    group.bench_function(BenchmarkId::new("json", "serialize"), |b| {
+        b.iter(|| serde_json::to_vec(value).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("json", "deserialize"), |b| {
+        b.iter(|| serde_json::from_slice::<T>(&json).unwrap())
+    });
+    group.finish();
+}
+
+fn benchmarks(c: &mut Criterion) {
+    roundtrip(c, "large_ptb", &large_ptb());
+    roundtrip(c, "publish", &publish());
+    roundtrip(c, "checkpoint", &checkpoint());
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,10 @@
+//! Print the corpus of test vectors as JSON.
+//!
+//! ```text
+//! cargo run --example test-vectors --features test-vectors > test-vectors.json
+//! ```
+
+fn main() {
+    let vectors = sui_sdk::test_vectors::test_vectors();
+    println!("{}", serde_json::to_string_pretty(&vectors).unwrap());
+}
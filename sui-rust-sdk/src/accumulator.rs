@@ -0,0 +1,288 @@
+//! The accumulator of the live object set, committed to by the last checkpoint of each epoch.
+//!
+//! Validators accumulate the digests of the live objects in an elliptic curve multiset hash over
+//! Ristretto255: each item is hashed to a point with SHA-512, and the points of the set are
+//! summed. The sum doesn't depend on the order of the items, items are removed by subtracting
+//! their point, and the digest of the set is the SHA-256 of the compressed sum. The last
+//! checkpoint of an epoch commits to the digest of the objects live at its end in
+//! [`CheckpointCommitment::EcmhLiveObjectSet`].
+//!
+//! Accumulating the objects of a formal snapshot, and checking the digest against a certified
+//! end-of-epoch checkpoint, proves the snapshot holds exactly the live objects of the network:
+//!
+//! ```ignore
+//! let accumulator = Accumulator::from_snapshot(&mut reader)?;
+//! accumulator.verify_end_of_epoch(&end_of_epoch_checkpoint.checkpoint)?;
+//! ```
+
+use crate::archive::ArchiveError;
+use crate::archive::ArchiveStore;
+use crate::snapshot::LiveObject;
+use crate::snapshot::SnapshotReader;
+use crate::snapshot::WRAPPED_OBJECT_DIGEST;
+use crate::types::CheckpointCommitment;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::CheckpointSummary;
+use crate::types::Digest;
+use crate::types::ObjectId;
+use crate::types::Version;
+
+use curve25519_dalek::ristretto::RistrettoPoint;
+
+/// A multiset of byte strings, accumulated as the sum of their points on Ristretto255.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Accumulator {
+    point: RistrettoPoint,
+}
+
+impl Accumulator {
+    /// The empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The accumulator of the live objects of the snapshot read by `reader`.
+    pub fn from_snapshot<S: ArchiveStore>(
+        reader: &mut SnapshotReader<S>,
+    ) -> Result<Self, ArchiveError<S::Error>> {
+        let mut accumulator = Self::new();
+        for object in reader.objects() {
+            accumulator.insert_live_object(&object?);
+        }
+        Ok(accumulator)
+    }
+
+    pub fn insert<T: AsRef<[u8]>>(&mut self, item: T) {
+        self.point += hash_to_point(item.as_ref());
+    }
+
+    pub fn remove<T: AsRef<[u8]>>(&mut self, item: T) {
+        self.point -= hash_to_point(item.as_ref());
+    }
+
+    pub fn insert_all<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        for item in items {
+            self.insert(item);
+        }
+    }
+
+    pub fn remove_all<I>(&mut self, items: I)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        for item in items {
+            self.remove(item);
+        }
+    }
+
+    /// Add the items of `other` to this set.
+    pub fn union(&mut self, other: &Self) {
+        self.point += other.point;
+    }
+
+    /// Insert a live object as validators do: the digest of a normal object, and its id and
+    /// version for a wrapped one, whose digest is the same for all wrapped objects.
+    pub fn insert_live_object(&mut self, object: &LiveObject) {
+        match object {
+            LiveObject::Normal(object) => self.insert(object.digest().inner()),
+            LiveObject::Wrapped { object_id, version } => {
+                self.insert(wrapped_object_bytes(*object_id, *version))
+            }
+        }
+    }
+
+    pub fn digest(&self) -> Digest {
+        use sha2::Digest as _;
+
+        Digest::new(sha2::Sha256::digest(self.point.compress().as_bytes()).into())
+    }
+
+    /// Verify that this set is the live object set committed to by `checkpoint`, the last
+    /// checkpoint of an epoch.
+    ///
+    /// `checkpoint` is trusted: verify its signature, e.g. with a
+    /// [`LightClient`](crate::light_client::LightClient), beforehand.
+    pub fn verify_end_of_epoch(
+        &self,
+        checkpoint: &CheckpointSummary,
+    ) -> Result<(), AccumulatorError> {
+        let end_of_epoch_data =
+            checkpoint
+                .end_of_epoch_data
+                .as_ref()
+                .ok_or(AccumulatorError::NotEndOfEpoch {
+                    sequence_number: checkpoint.sequence_number,
+                })?;
+        let expected = end_of_epoch_data
+            .epoch_commitments
+            .iter()
+            .find_map(|commitment| match commitment {
+                CheckpointCommitment::EcmhLiveObjectSet { digest } => Some(*digest),
+            })
+            .ok_or(AccumulatorError::MissingCommitment {
+                sequence_number: checkpoint.sequence_number,
+            })?;
+        let actual = self.digest();
+        if actual != expected {
+            return Err(AccumulatorError::DigestMismatch { expected, actual });
+        }
+        Ok(())
+    }
+}
+
+fn hash_to_point(item: &[u8]) -> RistrettoPoint {
+    RistrettoPoint::hash_from_bytes::<sha2::Sha512>(item)
+}
+
+/// The item accumulated for a wrapped object: the BCS of its reference with the wrapped digest.
+fn wrapped_object_bytes(object_id: ObjectId, version: Version) -> Vec<u8> {
+    bcs::to_bytes(&(object_id, version, WRAPPED_OBJECT_DIGEST))
+        .expect("serialization of a reference cannot fail")
+}
+
+#[derive(Debug)]
+pub enum AccumulatorError {
+    /// The checkpoint isn't the last one of its epoch.
+    NotEndOfEpoch {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    /// The checkpoint doesn't commit to the live object set.
+    MissingCommitment {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    DigestMismatch {
+        expected: Digest,
+        actual: Digest,
+    },
+}
+
+impl std::fmt::Display for AccumulatorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotEndOfEpoch { sequence_number } => write!(
+                f,
+                "checkpoint {sequence_number} isn't the last checkpoint of its epoch"
+            ),
+            Self::MissingCommitment { sequence_number } => write!(
+                f,
+                "checkpoint {sequence_number} doesn't commit to the live object set"
+            ),
+            Self::DigestMismatch { expected, actual } => write!(
+                f,
+                "live object set digest mismatch: expected {expected}, found {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AccumulatorError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::EndOfEpochData;
+    use crate::types::EpochId;
+    use crate::types::GasCostSummary;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn checkpoint(epoch_commitments: Option<Vec<CheckpointCommitment>>) -> CheckpointSummary {
+        CheckpointSummary {
+            epoch: EpochId::new(3),
+            sequence_number: 42,
+            network_total_transactions: 0,
+            content_digest: Default::default(),
+            previous_digest: None,
+            epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+            timestamp_ms: 0,
+            checkpoint_commitments: vec![],
+            end_of_epoch_data: epoch_commitments.map(|epoch_commitments| EndOfEpochData {
+                next_epoch_committee: vec![],
+                next_epoch_protocol_version: 1,
+                epoch_commitments,
+            }),
+            version_specific_data: vec![],
+        }
+    }
+
+    #[test]
+    fn multiset() {
+        let mut a = Accumulator::new();
+        a.insert_all([b"one", b"two", b"two"]);
+        let mut b = Accumulator::new();
+        b.insert_all([b"two", b"one", b"two"]);
+        assert_eq!(a.digest(), b.digest());
+
+        b.remove(b"two");
+        assert_ne!(a.digest(), b.digest());
+        let mut c = Accumulator::new();
+        c.insert(b"two");
+        b.union(&c);
+        assert_eq!(a, b);
+
+        a.remove_all([b"one", b"two", b"two"]);
+        assert_eq!(a, Accumulator::new());
+        // The SHA-256 of the compressed identity, 32 zero bytes
+        assert_eq!(
+            a.digest().to_string(),
+            "7tkzFg8RHBmMw1ncRJZCCZAizgq4rwCftTKYLce8RU8t"
+        );
+    }
+
+    #[test]
+    fn wrapped_objects() {
+        let object_id = ObjectId::new([9; 32]);
+        let mut wrapped = Accumulator::new();
+        wrapped.insert_live_object(&LiveObject::Wrapped {
+            object_id,
+            version: Version::new(9),
+        });
+        let mut expected = Accumulator::new();
+        let mut bytes = object_id.as_ref().to_vec();
+        bytes.extend(9u64.to_le_bytes());
+        bytes.push(32);
+        bytes.extend([88; 32]);
+        expected.insert(bytes);
+        assert_eq!(wrapped, expected);
+
+        let mut other = Accumulator::new();
+        other.insert_live_object(&LiveObject::Wrapped {
+            object_id,
+            version: Version::new(10),
+        });
+        assert_ne!(wrapped, other);
+    }
+
+    #[test]
+    fn verify_end_of_epoch() {
+        let mut accumulator = Accumulator::new();
+        accumulator.insert(b"object");
+        let commitment = CheckpointCommitment::EcmhLiveObjectSet {
+            digest: accumulator.digest(),
+        };
+        accumulator
+            .verify_end_of_epoch(&checkpoint(Some(vec![commitment.clone()])))
+            .unwrap();
+
+        assert!(matches!(
+            Accumulator::new().verify_end_of_epoch(&checkpoint(Some(vec![commitment]))),
+            Err(AccumulatorError::DigestMismatch { expected, .. }) if expected == accumulator.digest()
+        ));
+        assert!(matches!(
+            accumulator.verify_end_of_epoch(&checkpoint(None)),
+            Err(AccumulatorError::NotEndOfEpoch {
+                sequence_number: 42
+            })
+        ));
+        assert!(matches!(
+            accumulator.verify_end_of_epoch(&checkpoint(Some(vec![]))),
+            Err(AccumulatorError::MissingCommitment { .. })
+        ));
+    }
+}
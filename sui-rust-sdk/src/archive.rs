@@ -0,0 +1,752 @@
+//! A reader of the checkpoint archives of Sui, for backfilling the full history of a network
+//! without the pruning limits of fullnodes.
+//!
+//! Archives are buckets laid out by epoch, with a `MANIFEST` indexing their files:
+//!
+//! ```text
+//! MANIFEST
+//! epoch_0/0.chk
+//! epoch_0/0.sum
+//! epoch_0/1000.chk
+//! epoch_0/1000.sum
+//! epoch_1/4200.chk
+//! ...
+//! ```
+//!
+//! Each `.sum` file holds the certified summaries of a range of checkpoints, and the `.chk` file
+//! starting at the same checkpoint holds their contents. The [`Manifest`] records the range and
+//! the SHA3-256 checksum of each file. An [`ArchiveReader`] fetches files through an
+//! [`ArchiveStore`], e.g. an HTTP client of the bucket, and checks them against the manifest, the
+//! contents against the digests of the summaries, and each summary against its predecessor.
+//!
+//! The signatures of the summaries aren't verified: feeding them to a
+//! [`LightClientState`](crate::light_client::LightClientState) verifies the archive back to a
+//! trusted committee.
+//!
+//! Files compressed with zstd, as those of the public archives, are only read with the
+//! `archive-zstd` feature.
+
+use std::ops::Range;
+
+use crate::types::CheckpointContents;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::CheckpointVerificationError;
+use crate::types::EpochId;
+use crate::types::ObjectReference;
+use crate::types::SignedCheckpointSummary;
+
+/// The magic number starting checkpoint contents files.
+pub const CHECKPOINT_FILE_MAGIC: u32 = 0x0000DEAD;
+/// The magic number starting checkpoint summary files.
+pub const SUMMARY_FILE_MAGIC: u32 = 0x0000CAFE;
+/// The magic number starting the manifest.
+pub const MANIFEST_FILE_MAGIC: u32 = 0x00C0FFEE;
+/// The path of the manifest in an archive.
+pub const MANIFEST_PATH: &str = "MANIFEST";
+
+const SHA3_LENGTH: usize = 32;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum FileType {
+    CheckpointContent,
+    CheckpointSummary,
+}
+
+/// A file of an archive, as recorded in its manifest.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct FileMetadata {
+    pub file_type: FileType,
+    pub epoch_num: u64,
+    pub checkpoint_seq_range: Range<CheckpointSequenceNumber>,
+    /// The SHA3-256 checksum of the file.
+    pub sha3_digest: [u8; SHA3_LENGTH],
+}
+
+impl FileMetadata {
+    /// The path of the file in the archive.
+    pub fn path(&self) -> String {
+        let suffix = match self.file_type {
+            FileType::CheckpointContent => "chk",
+            FileType::CheckpointSummary => "sum",
+        };
+        format!(
+            "epoch_{}/{}.{suffix}",
+            self.epoch_num, self.checkpoint_seq_range.start
+        )
+    }
+}
+
+/// The index of the files of an archive.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum Manifest {
+    V1(ManifestV1),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ManifestV1 {
+    pub archive_version: u8,
+    /// The checkpoint after the last one archived.
+    pub next_checkpoint_seq_num: CheckpointSequenceNumber,
+    pub file_metadata: Vec<FileMetadata>,
+    /// The epoch of the last checkpoint archived.
+    pub epoch: EpochId,
+}
+
+/// The summary and contents files of a range of checkpoints.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchiveFiles {
+    pub summaries: FileMetadata,
+    pub contents: FileMetadata,
+}
+
+impl ArchiveFiles {
+    pub fn checkpoint_range(&self) -> Range<CheckpointSequenceNumber> {
+        self.summaries.checkpoint_seq_range.clone()
+    }
+}
+
+impl Manifest {
+    /// Parse the manifest file of an archive, checking its magic number and checksum.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ArchiveFormatError> {
+        let contents = check_magic(bytes, MANIFEST_FILE_MAGIC)?;
+        if contents.len() < SHA3_LENGTH {
+            return Err(ArchiveFormatError::Truncated);
+        }
+        let (signed, checksum) = bytes.split_at(bytes.len() - SHA3_LENGTH);
+        if sha3_256(signed) != checksum {
+            return Err(ArchiveFormatError::ChecksumMismatch {
+                path: MANIFEST_PATH.to_owned(),
+            });
+        }
+
+        let mut blobs = &contents[..contents.len() - SHA3_LENGTH];
+        read_blob(&mut blobs)?.ok_or(ArchiveFormatError::Truncated)
+    }
+
+    pub fn files(&self) -> &[FileMetadata] {
+        match self {
+            Self::V1(manifest) => &manifest.file_metadata,
+        }
+    }
+
+    /// The epoch of the last checkpoint archived.
+    pub fn epoch(&self) -> EpochId {
+        match self {
+            Self::V1(manifest) => manifest.epoch,
+        }
+    }
+
+    /// The checkpoint after the last one archived.
+    pub fn next_checkpoint_seq_num(&self) -> CheckpointSequenceNumber {
+        match self {
+            Self::V1(manifest) => manifest.next_checkpoint_seq_num,
+        }
+    }
+
+    /// The summary and contents files of the archive, by checkpoint.
+    ///
+    /// Fails unless the files cover the checkpoints from genesis without gaps or overlaps, each
+    /// range with both a summary and a contents file.
+    pub fn file_pairs(&self) -> Result<Vec<ArchiveFiles>, ArchiveFormatError> {
+        let mut summaries: Vec<_> = self
+            .files()
+            .iter()
+            .filter(|file| file.file_type == FileType::CheckpointSummary)
+            .collect();
+        let mut contents: Vec<_> = self
+            .files()
+            .iter()
+            .filter(|file| file.file_type == FileType::CheckpointContent)
+            .collect();
+        summaries.sort_by_key(|file| file.checkpoint_seq_range.start);
+        contents.sort_by_key(|file| file.checkpoint_seq_range.start);
+        if summaries.len() != contents.len() {
+            return Err(ArchiveFormatError::InvalidManifest(
+                "summary and contents files don't match",
+            ));
+        }
+
+        let mut next = 0;
+        let mut pairs = Vec::with_capacity(summaries.len());
+        for (summaries, contents) in summaries.into_iter().zip(contents) {
+            let range = &summaries.checkpoint_seq_range;
+            if range != &contents.checkpoint_seq_range {
+                return Err(ArchiveFormatError::InvalidManifest(
+                    "summary and contents files don't match",
+                ));
+            }
+            if range.start != next || range.is_empty() {
+                return Err(ArchiveFormatError::InvalidManifest(
+                    "files don't cover contiguous checkpoints from genesis",
+                ));
+            }
+            next = range.end;
+            pairs.push(ArchiveFiles {
+                summaries: summaries.clone(),
+                contents: contents.clone(),
+            });
+        }
+        Ok(pairs)
+    }
+
+    /// The files holding the checkpoints of `range`.
+    pub fn files_for(
+        &self,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<ArchiveFiles>, ArchiveFormatError> {
+        Ok(self
+            .file_pairs()?
+            .into_iter()
+            .filter(|files| {
+                let files = files.checkpoint_range();
+                files.start < range.end && range.start < files.end
+            })
+            .collect())
+    }
+}
+
+/// Where the files of an archive are read from, e.g. an HTTP client of the archive's bucket.
+pub trait ArchiveStore {
+    type Error;
+
+    /// Fetch the file at `path`, relative to the root of the archive.
+    fn get(&mut self, path: &str) -> Result<Vec<u8>, Self::Error>;
+}
+
+/// A checkpoint read from an archive, whose contents match its summary.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ArchivedCheckpoint {
+    pub summary: SignedCheckpointSummary,
+    pub contents: CheckpointContents,
+}
+
+/// Reads checkpoints from an archive through an [`ArchiveStore`].
+pub struct ArchiveReader<S> {
+    store: S,
+}
+
+impl<S: ArchiveStore> ArchiveReader<S> {
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+
+    /// Fetch the manifest of the archive.
+    pub fn manifest(&mut self) -> Result<Manifest, ArchiveError<S::Error>> {
+        let bytes = self.store.get(MANIFEST_PATH).map_err(ArchiveError::Store)?;
+        Ok(Manifest::from_bytes(&bytes)?)
+    }
+
+    /// Fetch the checkpoints of `range`, as listed by `manifest`, in order.
+    pub fn read_checkpoints(
+        &mut self,
+        manifest: &Manifest,
+        range: Range<CheckpointSequenceNumber>,
+    ) -> Result<Vec<ArchivedCheckpoint>, ArchiveError<S::Error>> {
+        if range.end > manifest.next_checkpoint_seq_num() {
+            return Err(ArchiveError::NotArchived {
+                next_checkpoint_seq_num: manifest.next_checkpoint_seq_num(),
+            });
+        }
+
+        let mut checkpoints = Vec::new();
+        for files in manifest.files_for(range.clone())? {
+            let read = self.read_files(&files)?;
+            if let (Some(last), Some(first)) = (checkpoints.last(), read.first()) {
+                check_previous(last, first)?;
+            }
+            checkpoints.extend(read.into_iter().filter(|checkpoint| {
+                range.contains(&checkpoint.summary.checkpoint.sequence_number)
+            }));
+        }
+        Ok(checkpoints)
+    }
+
+    /// Fetch the checkpoints of a pair of files of the archive, checking them against their
+    /// checksums and the contents against the summaries.
+    pub fn read_files(
+        &mut self,
+        files: &ArchiveFiles,
+    ) -> Result<Vec<ArchivedCheckpoint>, ArchiveError<S::Error>> {
+        let summaries: Vec<SignedCheckpointSummary> =
+            read_blobs(&self.get(&files.summaries)?, SUMMARY_FILE_MAGIC)?;
+        let contents: Vec<CheckpointContents> =
+            read_blobs(&self.get(&files.contents)?, CHECKPOINT_FILE_MAGIC)?;
+
+        let range = files.checkpoint_range();
+        let expected = (range.end - range.start) as usize;
+        if summaries.len() != expected || contents.len() != expected {
+            return Err(ArchiveFormatError::InvalidFile {
+                path: files.summaries.path(),
+            }
+            .into());
+        }
+
+        let mut checkpoints: Vec<ArchivedCheckpoint> = Vec::with_capacity(expected);
+        for ((sequence_number, summary), contents) in range.zip(summaries).zip(contents) {
+            if summary.checkpoint.sequence_number != sequence_number {
+                return Err(ArchiveFormatError::InvalidFile {
+                    path: files.summaries.path(),
+                }
+                .into());
+            }
+            summary
+                .checkpoint
+                .verify_contents(&contents)
+                .map_err(ArchiveError::Verification)?;
+            let checkpoint = ArchivedCheckpoint { summary, contents };
+            if let Some(previous) = checkpoints.last() {
+                check_previous(previous, &checkpoint)?;
+            }
+            checkpoints.push(checkpoint);
+        }
+        Ok(checkpoints)
+    }
+
+    /// Fetch `file`, checking it against its checksum.
+    fn get(&mut self, file: &FileMetadata) -> Result<Vec<u8>, ArchiveError<S::Error>> {
+        let path = file.path();
+        let bytes = self.store.get(&path).map_err(ArchiveError::Store)?;
+        if sha3_256(&bytes) != file.sha3_digest {
+            return Err(ArchiveFormatError::ChecksumMismatch { path }.into());
+        }
+        Ok(bytes)
+    }
+}
+
+/// Check that `checkpoint` follows `previous`.
+fn check_previous<E>(
+    previous: &ArchivedCheckpoint,
+    checkpoint: &ArchivedCheckpoint,
+) -> Result<(), ArchiveError<E>> {
+    let summary = &checkpoint.summary.checkpoint;
+    if summary.previous_digest != Some(previous.summary.checkpoint.digest()) {
+        return Err(ArchiveError::PreviousDigestMismatch {
+            sequence_number: summary.sequence_number,
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn sha3_256(bytes: &[u8]) -> [u8; SHA3_LENGTH] {
+    use sha3::Digest;
+
+    sha3::Sha3_256::digest(bytes).into()
+}
+
+/// The bytes of a file after its magic number.
+pub(crate) fn check_magic(bytes: &[u8], magic: u32) -> Result<&[u8], ArchiveFormatError> {
+    let (actual, rest) = bytes
+        .split_first_chunk::<4>()
+        .ok_or(ArchiveFormatError::Truncated)?;
+    let actual = u32::from_be_bytes(*actual);
+    if actual != magic {
+        return Err(ArchiveFormatError::InvalidMagic {
+            expected: magic,
+            actual,
+        });
+    }
+    Ok(rest)
+}
+
+/// Decode the blobs of a summary or contents file: its magic number, storage format and
+/// compression, followed by the BCS encoded values.
+fn read_blobs<T: serde::de::DeserializeOwned>(
+    bytes: &[u8],
+    magic: u32,
+) -> Result<Vec<T>, ArchiveFormatError> {
+    let [format, compression, rest @ ..] = check_magic(bytes, magic)? else {
+        return Err(ArchiveFormatError::Truncated);
+    };
+    // Blobs are the only storage format
+    if *format != 0 {
+        return Err(ArchiveFormatError::UnsupportedFormat(*format));
+    }
+    read_all_blobs(&decompress(*compression, rest)?)
+}
+
+/// Decompress `bytes`, compressed as a file with the given compression: none or zstd.
+pub(crate) fn decompress(
+    compression: u8,
+    bytes: &[u8],
+) -> Result<std::borrow::Cow<'_, [u8]>, ArchiveFormatError> {
+    match compression {
+        0 => Ok(bytes.into()),
+        #[cfg(feature = "archive-zstd")]
+        1 => zstd::stream::decode_all(bytes)
+            .map(Into::into)
+            .map_err(ArchiveFormatError::Zstd),
+        _ => Err(ArchiveFormatError::UnsupportedCompression(compression)),
+    }
+}
+
+/// Decode the blobs of `bytes`, up to its end.
+pub(crate) fn read_all_blobs<T: serde::de::DeserializeOwned>(
+    mut bytes: &[u8],
+) -> Result<Vec<T>, ArchiveFormatError> {
+    let mut values = Vec::new();
+    while let Some(value) = read_blob(&mut bytes)? {
+        values.push(value);
+    }
+    Ok(values)
+}
+
+/// Decode the blob at the start of `bytes`, its length as a ULEB128 followed by its encoding and
+/// data, advancing past it.
+fn read_blob<T: serde::de::DeserializeOwned>(
+    bytes: &mut &[u8],
+) -> Result<Option<T>, ArchiveFormatError> {
+    if bytes.is_empty() {
+        return Ok(None);
+    }
+
+    let mut len = 0u64;
+    let mut shift = 0;
+    loop {
+        let (&byte, rest) = bytes.split_first().ok_or(ArchiveFormatError::Truncated)?;
+        *bytes = rest;
+        if shift >= 64 {
+            return Err(ArchiveFormatError::Truncated);
+        }
+        len |= u64::from(byte & 0x7f) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    let (&encoding, rest) = bytes.split_first().ok_or(ArchiveFormatError::Truncated)?;
+    // BCS is the only encoding
+    if encoding != 1 {
+        return Err(ArchiveFormatError::UnsupportedEncoding(encoding));
+    }
+    let len = usize::try_from(len)
+        .ok()
+        .filter(|len| *len > 0 && *len <= rest.len())
+        .ok_or(ArchiveFormatError::Truncated)?;
+    let (data, rest) = rest.split_at(len);
+    *bytes = rest;
+    bcs::from_bytes(data)
+        .map(Some)
+        .map_err(ArchiveFormatError::Bcs)
+}
+
+#[derive(Debug)]
+pub enum ArchiveFormatError {
+    InvalidMagic {
+        expected: u32,
+        actual: u32,
+    },
+    Truncated,
+    ChecksumMismatch {
+        path: String,
+    },
+    /// The files of the manifest don't form a valid archive.
+    InvalidManifest(&'static str),
+    /// A file doesn't hold what the manifest lists for it, e.g. the checkpoints of its range.
+    InvalidFile {
+        path: String,
+    },
+    UnsupportedFormat(u8),
+    UnsupportedCompression(u8),
+    UnsupportedEncoding(u8),
+    Bcs(bcs::Error),
+    #[cfg(feature = "archive-zstd")]
+    Zstd(std::io::Error),
+}
+
+impl std::fmt::Display for ArchiveFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidMagic { expected, actual } => write!(
+                f,
+                "unexpected magic number {actual:#010x}, expected {expected:#010x}"
+            ),
+            Self::Truncated => write!(f, "truncated file"),
+            Self::ChecksumMismatch { path } => write!(f, "checksum mismatch for {path}"),
+            Self::InvalidManifest(reason) => write!(f, "invalid manifest: {reason}"),
+            Self::InvalidFile { path } => write!(f, "{path} doesn't match the manifest"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported storage format {format}"),
+            Self::UnsupportedCompression(compression) => {
+                write!(f, "unsupported compression {compression}")
+            }
+            Self::UnsupportedEncoding(encoding) => write!(f, "unsupported encoding {encoding}"),
+            Self::Bcs(e) => write!(f, "invalid bcs: {e}"),
+            #[cfg(feature = "archive-zstd")]
+            Self::Zstd(e) => write!(f, "invalid zstd stream: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveFormatError {}
+
+#[derive(Debug)]
+pub enum ArchiveError<E> {
+    /// The store failed to serve a file.
+    Store(E),
+    Format(ArchiveFormatError),
+    Verification(CheckpointVerificationError),
+    /// A checkpoint doesn't commit to the one before it as its predecessor.
+    PreviousDigestMismatch {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    /// The requested checkpoints aren't all archived yet.
+    NotArchived {
+        next_checkpoint_seq_num: CheckpointSequenceNumber,
+    },
+    /// An object of a snapshot doesn't match its reference.
+    ReferenceMismatch {
+        expected: ObjectReference,
+        actual: ObjectReference,
+    },
+}
+
+impl<E> From<ArchiveFormatError> for ArchiveError<E> {
+    fn from(e: ArchiveFormatError) -> Self {
+        Self::Format(e)
+    }
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ArchiveError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Store(e) => write!(f, "archive store error: {e}"),
+            Self::Format(e) => write!(f, "{e}"),
+            Self::Verification(e) => write!(f, "{e}"),
+            Self::PreviousDigestMismatch { sequence_number } => write!(
+                f,
+                "checkpoint {sequence_number} doesn't follow the previous checkpoint"
+            ),
+            Self::NotArchived {
+                next_checkpoint_seq_num,
+            } => write!(
+                f,
+                "checkpoints from {next_checkpoint_seq_num} aren't archived yet"
+            ),
+            Self::ReferenceMismatch { expected, actual } => write!(
+                f,
+                "expected object {} at version {}, found {} at version {}",
+                expected.object_id(),
+                expected.version(),
+                actual.object_id(),
+                actual.version()
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ArchiveError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Store(e) => Some(e),
+            Self::Format(e) => Some(e),
+            Self::Verification(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Bls12381Signature;
+    use crate::types::CheckpointSummary;
+    use crate::types::CheckpointTransactionInfo;
+    use crate::types::GasCostSummary;
+    use crate::types::TransactionDigest;
+    use crate::types::TransactionEffectsDigest;
+    use crate::types::ValidatorAggregatedSignature;
+    use std::collections::BTreeMap;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn blob<T: serde::Serialize>(value: &T) -> Vec<u8> {
+        let data = bcs::to_bytes(value).unwrap();
+        assert!(data.len() < 0x4000);
+        let mut blob = if data.len() < 0x80 {
+            vec![data.len() as u8]
+        } else {
+            vec![data.len() as u8 | 0x80, (data.len() >> 7) as u8]
+        };
+        blob.push(1);
+        blob.extend(data);
+        blob
+    }
+
+    fn file<T: serde::Serialize>(magic: u32, values: &[T]) -> Vec<u8> {
+        let mut file = magic.to_be_bytes().to_vec();
+        file.extend([0, 0]);
+        for value in values {
+            file.extend(blob(value));
+        }
+        file
+    }
+
+    fn checkpoints(len: u64) -> Vec<ArchivedCheckpoint> {
+        let mut checkpoints: Vec<ArchivedCheckpoint> = vec![];
+        for sequence_number in 0..len {
+            let contents = CheckpointContents::new(vec![]);
+            let checkpoint = CheckpointSummary {
+                epoch: EpochId::new(0),
+                sequence_number,
+                network_total_transactions: 0,
+                content_digest: contents.digest(),
+                previous_digest: checkpoints
+                    .last()
+                    .map(|previous| previous.summary.checkpoint.digest()),
+                epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+                timestamp_ms: sequence_number,
+                checkpoint_commitments: vec![],
+                end_of_epoch_data: None,
+                version_specific_data: vec![],
+            };
+            checkpoints.push(ArchivedCheckpoint {
+                summary: SignedCheckpointSummary {
+                    checkpoint,
+                    signature: ValidatorAggregatedSignature {
+                        epoch: EpochId::new(0),
+                        signature: Bls12381Signature::new([0; 48]),
+                        bitmap: Default::default(),
+                    },
+                },
+                contents,
+            });
+        }
+        checkpoints
+    }
+
+    /// An archive of `checkpoints`, in files of `per_file` checkpoints.
+    fn archive(checkpoints: &[ArchivedCheckpoint], per_file: usize) -> BTreeMap<String, Vec<u8>> {
+        let mut archive = BTreeMap::new();
+        let mut file_metadata = vec![];
+        for (i, chunk) in checkpoints.chunks(per_file).enumerate() {
+            let start = (i * per_file) as u64;
+            let range = start..start + chunk.len() as u64;
+            let summaries: Vec<_> = chunk.iter().map(|c| c.summary.clone()).collect();
+            let contents: Vec<_> = chunk.iter().map(|c| c.contents.clone()).collect();
+            for (file_type, bytes) in [
+                (
+                    FileType::CheckpointSummary,
+                    file(SUMMARY_FILE_MAGIC, &summaries),
+                ),
+                (
+                    FileType::CheckpointContent,
+                    file(CHECKPOINT_FILE_MAGIC, &contents),
+                ),
+            ] {
+                let metadata = FileMetadata {
+                    file_type,
+                    epoch_num: 0,
+                    checkpoint_seq_range: range.clone(),
+                    sha3_digest: sha3_256(&bytes),
+                };
+                archive.insert(metadata.path(), bytes);
+                file_metadata.push(metadata);
+            }
+        }
+
+        let manifest = Manifest::V1(ManifestV1 {
+            archive_version: 1,
+            next_checkpoint_seq_num: checkpoints.len() as u64,
+            file_metadata,
+            epoch: EpochId::new(0),
+        });
+        let mut bytes = MANIFEST_FILE_MAGIC.to_be_bytes().to_vec();
+        bytes.extend(blob(&manifest));
+        bytes.extend(sha3_256(&bytes));
+        archive.insert(MANIFEST_PATH.to_owned(), bytes);
+        archive
+    }
+
+    struct Bucket(BTreeMap<String, Vec<u8>>);
+
+    impl ArchiveStore for Bucket {
+        type Error = String;
+
+        fn get(&mut self, path: &str) -> Result<Vec<u8>, Self::Error> {
+            self.0.get(path).cloned().ok_or_else(|| path.to_owned())
+        }
+    }
+
+    #[test]
+    fn read_range() {
+        let checkpoints = checkpoints(7);
+        let mut reader = ArchiveReader::new(Bucket(archive(&checkpoints, 3)));
+        let manifest = reader.manifest().unwrap();
+        assert_eq!(manifest.next_checkpoint_seq_num(), 7);
+        assert_eq!(manifest.file_pairs().unwrap().len(), 3);
+        assert_eq!(manifest.files()[0].path(), "epoch_0/0.sum");
+        assert_eq!(manifest.files()[3].path(), "epoch_0/3.chk");
+
+        assert_eq!(manifest.files_for(2..4).unwrap().len(), 2);
+        assert_eq!(
+            reader.read_checkpoints(&manifest, 2..7).unwrap(),
+            checkpoints[2..]
+        );
+        assert!(matches!(
+            reader.read_checkpoints(&manifest, 5..8),
+            Err(ArchiveError::NotArchived {
+                next_checkpoint_seq_num: 7
+            })
+        ));
+    }
+
+    #[test]
+    fn integrity() {
+        let checkpoints = checkpoints(4);
+
+        let mut corrupted = archive(&checkpoints, 2);
+        corrupted.get_mut("epoch_0/2.sum").unwrap()[8] ^= 1;
+        let mut reader = ArchiveReader::new(Bucket(corrupted));
+        let manifest = reader.manifest().unwrap();
+        assert!(matches!(
+            reader.read_checkpoints(&manifest, 0..4),
+            Err(ArchiveError::Format(ArchiveFormatError::ChecksumMismatch { path }))
+                if path == "epoch_0/2.sum"
+        ));
+
+        let mut corrupted = archive(&checkpoints, 2);
+        corrupted.get_mut(MANIFEST_PATH).unwrap()[5] ^= 1;
+        assert!(matches!(
+            ArchiveReader::new(Bucket(corrupted)).manifest(),
+            Err(ArchiveError::Format(
+                ArchiveFormatError::ChecksumMismatch { .. }
+            ))
+        ));
+
+        // Contents which aren't those of the summary
+        let mut swapped = checkpoints.clone();
+        swapped[1].contents = CheckpointContents::new(vec![CheckpointTransactionInfo {
+            transaction: TransactionDigest::ZERO,
+            effects: TransactionEffectsDigest::ZERO,
+            signatures: vec![],
+        }]);
+        let mut reader = ArchiveReader::new(Bucket(archive(&swapped, 2)));
+        let manifest = reader.manifest().unwrap();
+        assert!(matches!(
+            reader.read_checkpoints(&manifest, 0..2),
+            Err(ArchiveError::Verification(
+                CheckpointVerificationError::ContentsDigestMismatch
+            ))
+        ));
+
+        // A summary which doesn't follow its predecessor, across files
+        let mut forked = checkpoints.clone();
+        forked[2].summary.checkpoint.previous_digest = None;
+        forked[3].summary.checkpoint.previous_digest = Some(forked[2].summary.checkpoint.digest());
+        let mut reader = ArchiveReader::new(Bucket(archive(&forked, 2)));
+        let manifest = reader.manifest().unwrap();
+        assert!(matches!(
+            reader.read_checkpoints(&manifest, 0..4),
+            Err(ArchiveError::PreviousDigestMismatch { sequence_number: 2 })
+        ));
+    }
+}
@@ -0,0 +1,240 @@
+//! The BLS12-381 protocol keys of validators, for operator tooling like key rotation scripts and
+//! monitoring.
+//!
+//! An [`AuthorityKeyPair`] signs as a validator does, and proves possession of its key to the
+//! address of the validator with [`AuthorityKeyPair::proof_of_possession`], as the system state
+//! requires to register or rotate a protocol key. Key pairs are stored in the authority key file
+//! format of Sui nodes: the Base64 encoding of the 32-byte private key.
+
+use crate::types::verify_validator_signature;
+use crate::types::Address;
+use crate::types::Bls12381PrivateKey;
+use crate::types::Bls12381PublicKey;
+use crate::types::Bls12381Signature;
+use crate::types::CheckpointVerificationError;
+use crate::types::EpochId;
+use crate::types::Intent;
+use crate::types::IntentMessage;
+use crate::types::IntentScope;
+use crate::types::ValidatorSignature;
+use crate::types::VALIDATOR_SIGNATURE_DST;
+
+/// The protocol key pair of a validator.
+pub struct AuthorityKeyPair {
+    key: blst::min_sig::SecretKey,
+    public_key: Bls12381PublicKey,
+}
+
+impl AuthorityKeyPair {
+    /// `None` if `private_key` isn't a valid BLS12-381 scalar.
+    pub fn new(private_key: &Bls12381PrivateKey) -> Option<Self> {
+        let key = blst::min_sig::SecretKey::from_bytes(private_key.inner()).ok()?;
+        Some(Self::from_secret_key(key))
+    }
+
+    /// The key pair derived from the input keying material `ikm`, of at least 32 bytes, as
+    /// specified by the BLS signature draft's `KeyGen`.
+    pub fn from_ikm(ikm: &[u8]) -> Option<Self> {
+        let key = blst::min_sig::SecretKey::key_gen(ikm, &[]).ok()?;
+        Some(Self::from_secret_key(key))
+    }
+
+    /// A key pair with a new random key.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn generate<R>(mut rng: R) -> Self
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        let mut ikm = [0; 32];
+        rng.fill_bytes(&mut ikm);
+        Self::from_ikm(&ikm).expect("32 bytes of keying material are enough")
+    }
+
+    fn from_secret_key(key: blst::min_sig::SecretKey) -> Self {
+        let public_key = Bls12381PublicKey::new(key.sk_to_pk().to_bytes());
+        Self { key, public_key }
+    }
+
+    pub fn private_key(&self) -> Bls12381PrivateKey {
+        Bls12381PrivateKey::new(self.key.to_bytes())
+    }
+
+    pub fn public_key(&self) -> Bls12381PublicKey {
+        self.public_key
+    }
+
+    /// Sign `message` with the domain separation tag of validators.
+    pub fn sign(&self, message: &[u8]) -> Bls12381Signature {
+        Bls12381Signature::new(
+            self.key
+                .sign(message, VALIDATOR_SIGNATURE_DST, &[])
+                .to_bytes(),
+        )
+    }
+
+    /// Sign `message` in `epoch`, as validators sign checkpoints and effects, see
+    /// [`IntentMessage::to_authority_message`].
+    pub fn sign_intent_message<T: serde::Serialize>(
+        &self,
+        message: &IntentMessage<T>,
+        epoch: EpochId,
+    ) -> ValidatorSignature {
+        ValidatorSignature {
+            epoch,
+            public_key: self.public_key,
+            signature: self.sign(&message.to_authority_message(epoch)),
+        }
+    }
+
+    /// The proof that the owner of `address` holds this key: its signature, in epoch 0, of the
+    /// public key followed by the address.
+    pub fn proof_of_possession(&self, address: Address) -> Bls12381Signature {
+        self.sign(&proof_of_possession_message(&self.public_key, address))
+    }
+
+    /// The key pair in the authority key file format: the Base64 of the private key.
+    pub fn to_base64(&self) -> String {
+        use base64ct::Encoding;
+
+        base64ct::Base64::encode_string(&self.key.to_bytes())
+    }
+
+    pub fn from_base64(s: &str) -> Result<Self, AuthorityKeyError> {
+        use base64ct::Encoding;
+
+        let bytes = base64ct::Base64::decode_vec(s.trim()).map_err(AuthorityKeyError::Base64)?;
+        let bytes = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| AuthorityKeyError::InvalidLength(bytes.len()))?;
+        Self::new(&Bls12381PrivateKey::new(bytes)).ok_or(AuthorityKeyError::InvalidKey)
+    }
+
+    /// Write the key pair to the authority key file at `path`.
+    pub fn write_to_file<P: AsRef<std::path::Path>>(&self, path: P) -> std::io::Result<()> {
+        std::fs::write(path, self.to_base64())
+    }
+
+    /// Read the key pair from the authority key file at `path`.
+    pub fn read_from_file<P: AsRef<std::path::Path>>(path: P) -> std::io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Self::from_base64(&contents)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Verify that `proof` proves the owner of `address` holds the private key of `public_key`, see
+/// [`AuthorityKeyPair::proof_of_possession`].
+pub fn verify_proof_of_possession(
+    public_key: &Bls12381PublicKey,
+    address: Address,
+    proof: &Bls12381Signature,
+) -> Result<(), AuthorityKeyError> {
+    let message = proof_of_possession_message(public_key, address);
+    verify_validator_signature(public_key, &message, proof).map_err(|e| match e {
+        CheckpointVerificationError::InvalidPublicKey(_) => AuthorityKeyError::InvalidKey,
+        _ => AuthorityKeyError::InvalidSignature,
+    })
+}
+
+/// The message signed to prove possession of a key: the public key followed by the address, as a
+/// byte vector, with the proof of possession intent and in epoch 0.
+fn proof_of_possession_message(public_key: &Bls12381PublicKey, address: Address) -> Vec<u8> {
+    let mut bytes = public_key.as_bytes().to_vec();
+    bytes.extend_from_slice(address.as_ref());
+    IntentMessage::new(Intent::sui_app(IntentScope::ProofOfPossession), bytes)
+        .to_authority_message(EpochId::new(0))
+}
+
+#[derive(Debug)]
+pub enum AuthorityKeyError {
+    Base64(base64ct::Error),
+    /// A private key of another length than 32 bytes.
+    InvalidLength(usize),
+    InvalidKey,
+    InvalidSignature,
+}
+
+impl std::fmt::Display for AuthorityKeyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Base64(e) => write!(f, "invalid base64: {e}"),
+            Self::InvalidLength(len) => write!(
+                f,
+                "private key has {len} bytes, expected {}",
+                Bls12381PrivateKey::LENGTH
+            ),
+            Self::InvalidKey => write!(f, "invalid bls12381 key"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for AuthorityKeyError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::ValidatorCommittee;
+    use crate::types::ValidatorCommitteeMember;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn proof_of_possession() {
+        let key = AuthorityKeyPair::from_ikm(&[7; 32]).unwrap();
+        let address = Address::new([1; 32]);
+        let proof = key.proof_of_possession(address);
+
+        verify_proof_of_possession(&key.public_key(), address, &proof).unwrap();
+        assert!(matches!(
+            verify_proof_of_possession(&key.public_key(), Address::new([2; 32]), &proof),
+            Err(AuthorityKeyError::InvalidSignature)
+        ));
+        let other = AuthorityKeyPair::from_ikm(&[8; 32]).unwrap();
+        assert!(matches!(
+            verify_proof_of_possession(&other.public_key(), address, &proof),
+            Err(AuthorityKeyError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn key_file() {
+        let key = AuthorityKeyPair::from_ikm(&[7; 32]).unwrap();
+        let encoded = key.to_base64();
+        let decoded = AuthorityKeyPair::from_base64(&format!("{encoded}\n")).unwrap();
+        assert_eq!(decoded.public_key(), key.public_key());
+        assert!(decoded.private_key() == key.private_key());
+
+        assert!(matches!(
+            AuthorityKeyPair::from_base64("AAAA"),
+            Err(AuthorityKeyError::InvalidLength(3))
+        ));
+        // Larger than the order of the group
+        assert!(matches!(
+            AuthorityKeyPair::from_base64(&Base64::encode_string(&[0xff; 32])),
+            Err(AuthorityKeyError::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn signatures_verify_against_committee() {
+        let key = AuthorityKeyPair::from_ikm(&[7; 32]).unwrap();
+        let committee = ValidatorCommittee {
+            epoch: EpochId::new(3),
+            members: vec![ValidatorCommitteeMember {
+                public_key: key.public_key(),
+                stake: 10_000,
+            }],
+        };
+        let message = IntentMessage::new(Intent::CHECKPOINT_SUMMARY, 42u64);
+        let signature = key.sign_intent_message(&message, committee.epoch);
+        let index = committee
+            .verify_signature(&message.to_authority_message(committee.epoch), &signature)
+            .unwrap();
+        assert_eq!(index, 0);
+    }
+}
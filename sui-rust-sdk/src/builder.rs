@@ -0,0 +1,927 @@
+//! Building programmable transactions.
+//!
+//! A [`TransactionBuilder`] collects the inputs and commands of a programmable transaction,
+//! returning the [`Argument`] referring to each, so that commands can use the inputs and the
+//! results of earlier commands without their indices being counted by hand.
+//!
+//! The builder keeps track of the Move types of the arguments it knows them for, the pure inputs
+//! added with [`typed_pure`](TransactionBuilder::typed_pure), the inputs added with
+//! [`input_with_type`](TransactionBuilder::input_with_type) and the vectors it made, so that
+//! [`make_move_vec_of`](TransactionBuilder::make_move_vec_of) can infer the type of the elements
+//! of a vector and check that they all have the same type, rather than the transaction failing on
+//! execution.
+//!
+//! Identical inputs are only added once, so that a value or an object used by several commands is
+//! a single input of the transaction. [`finish_canonical`](TransactionBuilder::finish_canonical)
+//! also orders the inputs canonically, so that the same inputs and commands build the same
+//! transaction whatever order the inputs were added in, e.g. for the transaction to be cached.
+//!
+//! Multi-command patterns of the framework are built by a single method, like
+//! `upgrade_package`, with the `hash` feature, for the authorize, upgrade and commit steps of a
+//! package upgrade.
+
+use std::sync::Arc;
+
+use crate::types::well_known::MOVE_STDLIB_ADDRESS;
+use crate::types::Address;
+use crate::types::Argument;
+use crate::types::Command;
+use crate::types::Identifier;
+use crate::types::InputArgument;
+use crate::types::MakeMoveVector;
+use crate::types::ObjectId;
+use crate::types::ProgrammableTransaction;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// A programmable transaction being built.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionBuilder {
+    inputs: Vec<InputArgument>,
+    commands: Vec<Command>,
+    /// The type of each input, if known.
+    input_types: Vec<Option<TypeTag>>,
+    /// The type of the result of each command, if known.
+    result_types: Vec<Option<TypeTag>>,
+}
+
+impl TransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an input, returning the argument referring to it.
+    ///
+    /// An input identical to one added before isn't added again, the argument referring to the
+    /// existing input is returned instead. A shared object added both by immutable and mutable
+    /// reference is a single input, by mutable reference. Pure inputs are only identical if their
+    /// types are too, known or not, so that a pure input keeps its type.
+    ///
+    /// # Panics
+    ///
+    /// If the transaction already has `u16::MAX` inputs, far more than the protocol accepts.
+    pub fn input(&mut self, input: InputArgument) -> Argument {
+        self.push_input(input, None)
+    }
+
+    /// Add an input of a known type, e.g. an object of the type given by its
+    /// [`object_type`](crate::types::Object::object_type), returning the argument referring to
+    /// it.
+    pub fn input_with_type(&mut self, input: InputArgument, type_: TypeTag) -> Argument {
+        self.push_input(input, Some(type_))
+    }
+
+    fn push_input(&mut self, input: InputArgument, type_: Option<TypeTag>) -> Argument {
+        if let Some(index) = self.duplicate_input(&input, type_.as_ref()) {
+            if let (
+                InputArgument::Shared { mutable, .. },
+                InputArgument::Shared {
+                    mutable: also_mutable,
+                    ..
+                },
+            ) = (&mut self.inputs[index], &input)
+            {
+                *mutable |= *also_mutable;
+            }
+            if self.input_types[index].is_none() {
+                self.input_types[index] = type_;
+            }
+            return Argument::Input(index as u16);
+        }
+
+        let index = u16::try_from(self.inputs.len()).expect("too many inputs");
+        self.inputs.push(input);
+        self.input_types.push(type_);
+        Argument::Input(index)
+    }
+
+    /// The index of an input `input` is a duplicate of, whose type, if known, is `type_`.
+    ///
+    /// Pure inputs with the same bytes but of different types are kept apart, for the types of
+    /// both to be known, while an object is the same input whether its type is known or not.
+    fn duplicate_input(&self, input: &InputArgument, type_: Option<&TypeTag>) -> Option<usize> {
+        self.inputs
+            .iter()
+            .zip(&self.input_types)
+            .position(|(existing, existing_type)| {
+                let is_duplicate = match (existing, input) {
+                    (
+                        InputArgument::Shared {
+                            object_id,
+                            initial_shared_version,
+                            ..
+                        },
+                        InputArgument::Shared {
+                            object_id: other_id,
+                            initial_shared_version: other_version,
+                            ..
+                        },
+                    ) => object_id == other_id && initial_shared_version == other_version,
+                    (existing, input) => existing == input,
+                };
+                let types_agree = match (existing_type, type_) {
+                    (Some(existing_type), Some(type_)) => existing_type == type_,
+                    (None, None) => true,
+                    // An object is the same input whether its type is known or not
+                    _ => !matches!(input, InputArgument::Pure { .. }),
+                };
+                is_duplicate && types_agree
+            })
+    }
+
+    /// Add the BCS of `value` as a pure input, returning the argument referring to it.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn pure<T: serde::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<Argument, bcs::Error> {
+        Ok(self.input(InputArgument::Pure {
+            value: bcs::to_bytes(value)?,
+        }))
+    }
+
+    /// Add the BCS of `value` as a pure input of the Move type of `T`, returning the argument
+    /// referring to it.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn typed_pure<T: MoveType + serde::Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<Argument, bcs::Error> {
+        let value = bcs::to_bytes(value)?;
+        Ok(self.push_input(InputArgument::Pure { value }, Some(T::type_tag())))
+    }
+
+    /// Add a command, returning the argument referring to its result.
+    ///
+    /// # Panics
+    ///
+    /// If the transaction already has `u16::MAX` commands, far more than the protocol accepts.
+    pub fn command(&mut self, command: Command) -> Argument {
+        self.push_command(command, None)
+    }
+
+    fn push_command(&mut self, command: Command, type_: Option<TypeTag>) -> Argument {
+        let index = u16::try_from(self.commands.len()).expect("too many commands");
+        self.commands.push(command);
+        self.result_types.push(type_);
+        Argument::Result(index)
+    }
+
+    /// The Move type of `argument`, if known.
+    pub fn type_of(&self, argument: Argument) -> Option<TypeTag> {
+        match argument {
+            Argument::GasCoin => Some(TypeTag::Struct(Box::new(StructTag::gas_coin()))),
+            Argument::Input(index) => self.input_types.get(index as usize)?.clone(),
+            Argument::Result(index) => self.result_types.get(index as usize)?.clone(),
+            Argument::NestedResult(_, _) => None,
+        }
+    }
+
+    /// Make a `vector<T>` of `elements`, returning the argument referring to it.
+    ///
+    /// Fails if the type of any of the elements is known to be another type than `T`.
+    pub fn make_move_vec<T: MoveType + ?Sized>(
+        &mut self,
+        elements: Vec<Argument>,
+    ) -> Result<Argument, MakeMoveVecError> {
+        self.make_move_vec_of(Some(T::type_tag()), elements)
+    }
+
+    /// Make a vector of `elements` of type `type_`, returning the argument referring to it.
+    ///
+    /// Without a type, the type of the elements is inferred from those whose type is known. A type
+    /// is needed for an empty vector and, as the protocol requires it, for a vector of pure inputs.
+    /// Fails if any two elements are known to have different types.
+    pub fn make_move_vec_of(
+        &mut self,
+        type_: Option<TypeTag>,
+        elements: Vec<Argument>,
+    ) -> Result<Argument, MakeMoveVecError> {
+        let mut element_type = type_;
+        for &element in &elements {
+            let Some(found) = self.type_of(element) else {
+                continue;
+            };
+            match &element_type {
+                Some(expected) if *expected != found => {
+                    return Err(MakeMoveVecError::TypeMismatch {
+                        element,
+                        expected: expected.clone(),
+                        found,
+                    })
+                }
+                Some(_) => {}
+                None => element_type = Some(found),
+            }
+        }
+
+        if element_type.is_none() {
+            if elements.is_empty() {
+                return Err(MakeMoveVecError::MissingType);
+            }
+            let pure = elements.iter().find(|element| match element {
+                Argument::Input(index) => matches!(
+                    self.inputs.get(*index as usize),
+                    Some(InputArgument::Pure { .. })
+                ),
+                _ => false,
+            });
+            if let Some(&element) = pure {
+                return Err(MakeMoveVecError::UntypedPure(element));
+            }
+        }
+
+        let vector_type = element_type
+            .clone()
+            .map(|element_type| TypeTag::Vector(Box::new(element_type)));
+        Ok(self.push_command(
+            Command::from(MakeMoveVector {
+                type_: element_type,
+                elements,
+            }),
+            vector_type,
+        ))
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        ProgrammableTransaction {
+            inputs: self.inputs,
+            commands: self.commands,
+        }
+    }
+
+    /// Finish the transaction with its inputs in canonical order: owned and immutable objects,
+    /// shared objects and objects to receive, each by id, then pure inputs by their bytes and,
+    /// for the same bytes, by their types, untyped first.
+    ///
+    /// The arguments of the commands are updated to refer to the reordered inputs. Fails if a
+    /// command added by [`command`](Self::command) refers to an input the transaction doesn't
+    /// have.
+    pub fn finish_canonical(mut self) -> Result<ProgrammableTransaction, UnknownInputError> {
+        let mut inputs = self
+            .inputs
+            .into_iter()
+            .zip(self.input_types)
+            .enumerate()
+            .collect::<Vec<_>>();
+        inputs.sort_by(|(_, a), (_, b)| input_order(a).cmp(&input_order(b)));
+
+        let mut new_indices = vec![0; inputs.len()];
+        for (new_index, (index, _)) in inputs.iter().enumerate() {
+            new_indices[*index] = new_index as u16;
+        }
+        for (command_index, command) in self.commands.iter_mut().enumerate() {
+            for argument in arguments_mut(command) {
+                if let Argument::Input(index) = argument {
+                    *index = *new_indices.get(*index as usize).ok_or(UnknownInputError {
+                        command: command_index,
+                        input: *index,
+                    })?;
+                }
+            }
+        }
+
+        Ok(ProgrammableTransaction {
+            inputs: inputs.into_iter().map(|(_, (input, _))| input).collect(),
+            commands: self.commands,
+        })
+    }
+}
+
+/// The key ordering inputs, of their types, canonically.
+fn input_order((input, type_): &(InputArgument, Option<TypeTag>)) -> (u8, &[u8], Option<&TypeTag>) {
+    let (rank, bytes) = match input {
+        InputArgument::ImmutableOrOwned(reference) => (0, reference.object_id().as_bytes()),
+        InputArgument::Shared { object_id, .. } => (1, object_id.as_bytes()),
+        InputArgument::Receiving(reference) => (2, reference.object_id().as_bytes()),
+        InputArgument::Pure { value } => (3, value.as_slice()),
+    };
+    (rank, bytes, type_.as_ref())
+}
+
+/// The arguments of `command`.
+fn arguments_mut(command: &mut Command) -> Vec<&mut Argument> {
+    match command {
+        Command::MoveCall(call) => Arc::make_mut(call).arguments.iter_mut().collect(),
+        Command::TransferObjects(transfer) => transfer
+            .objects
+            .iter_mut()
+            .chain([&mut transfer.address])
+            .collect(),
+        Command::SplitCoins(split) => [&mut split.coin]
+            .into_iter()
+            .chain(&mut split.amounts)
+            .collect(),
+        Command::MergeCoins(merge) => [&mut merge.coin]
+            .into_iter()
+            .chain(&mut merge.coins_to_merge)
+            .collect(),
+        Command::Publish(_) => vec![],
+        Command::MakeMoveVector(vector) => Arc::make_mut(vector).elements.iter_mut().collect(),
+        Command::Upgrade(upgrade) => vec![&mut Arc::make_mut(upgrade).ticket],
+    }
+}
+
+/// The upgrades an `UpgradeCap` authorizes, from the most to the least permissive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UpgradePolicy {
+    /// Any change keeping the public signatures and the layouts of types.
+    #[default]
+    Compatible,
+    /// Only new functions and types.
+    Additive,
+    /// Only changes of dependencies.
+    DependencyOnly,
+}
+
+impl UpgradePolicy {
+    /// The `u8` of the policy in `0x2::package`.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Self::Compatible => 0,
+            Self::Additive => 128,
+            Self::DependencyOnly => 192,
+        }
+    }
+}
+
+/// The Rust types of Move values, whose type tags make typed pure inputs and vectors.
+pub trait MoveType {
+    fn type_tag() -> TypeTag;
+}
+
+macro_rules! impl_move_type {
+    ($($type:ty => $tag:ident),* $(,)?) => {
+        $(
+            impl MoveType for $type {
+                fn type_tag() -> TypeTag {
+                    TypeTag::$tag
+                }
+            }
+        )*
+    };
+}
+
+impl_move_type!(
+    bool => Bool,
+    u8 => U8,
+    u16 => U16,
+    u32 => U32,
+    u64 => U64,
+    u128 => U128,
+    Address => Address,
+);
+
+/// `0x2::object::ID`, which has the BCS of an address.
+impl MoveType for ObjectId {
+    fn type_tag() -> TypeTag {
+        std_struct(Address::TWO, "object", "ID", vec![])
+    }
+}
+
+/// `0x1::string::String`, which has the BCS of its UTF-8 bytes.
+impl MoveType for str {
+    fn type_tag() -> TypeTag {
+        std_struct(MOVE_STDLIB_ADDRESS, "string", "String", vec![])
+    }
+}
+
+impl MoveType for String {
+    fn type_tag() -> TypeTag {
+        str::type_tag()
+    }
+}
+
+impl<T: MoveType> MoveType for Vec<T> {
+    fn type_tag() -> TypeTag {
+        TypeTag::Vector(Box::new(T::type_tag()))
+    }
+}
+
+impl<T: MoveType> MoveType for [T] {
+    fn type_tag() -> TypeTag {
+        Vec::<T>::type_tag()
+    }
+}
+
+/// `0x1::option::Option`, which has the BCS of a vector of none or one element, the same as
+/// that of a Rust `Option`.
+impl<T: MoveType> MoveType for Option<T> {
+    fn type_tag() -> TypeTag {
+        std_struct(MOVE_STDLIB_ADDRESS, "option", "Option", vec![T::type_tag()])
+    }
+}
+
+impl<T: MoveType + ?Sized> MoveType for &T {
+    fn type_tag() -> TypeTag {
+        T::type_tag()
+    }
+}
+
+fn std_struct(address: Address, module: &str, name: &str, type_params: Vec<TypeTag>) -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address,
+        module: Identifier::new(module).unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params,
+    }))
+}
+
+/// A vector can't be made of the elements given to
+/// [`make_move_vec`](TransactionBuilder::make_move_vec).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MakeMoveVecError {
+    /// An empty vector needs the type of its elements.
+    MissingType,
+    /// A vector of pure inputs needs the type of its elements, which isn't known for the pure
+    /// input.
+    UntypedPure(Argument),
+    /// An element has another type than the type of the vector, or of the elements before it.
+    TypeMismatch {
+        element: Argument,
+        expected: TypeTag,
+        found: TypeTag,
+    },
+}
+
+impl std::fmt::Display for MakeMoveVecError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::MissingType => write!(f, "an empty vector needs the type of its elements"),
+            Self::UntypedPure(element) => write!(
+                f,
+                "a vector of pure inputs needs the type of its elements, unknown for {element:?}"
+            ),
+            Self::TypeMismatch {
+                element,
+                expected,
+                found,
+            } => write!(
+                f,
+                "element {element:?} of a vector of {expected} has type {found}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MakeMoveVecError {}
+
+/// A command refers to an input the transaction doesn't have, so that
+/// [`finish_canonical`](TransactionBuilder::finish_canonical) can't reorder the inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownInputError {
+    /// The index of the command.
+    pub command: usize,
+    /// The index of the input the command refers to.
+    pub input: u16,
+}
+
+impl std::fmt::Display for UnknownInputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "command {} refers to input {}, which the transaction doesn't have",
+            self.command, self.input
+        )
+    }
+}
+
+impl std::error::Error for UnknownInputError {}
+
+#[cfg(feature = "hash")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+mod upgrade {
+    use super::TransactionBuilder;
+    use super::UpgradePolicy;
+    use crate::hash::Hasher;
+    use crate::types::Address;
+    use crate::types::Argument;
+    use crate::types::Command;
+    use crate::types::Digest;
+    use crate::types::Identifier;
+    use crate::types::InputArgument;
+    use crate::types::MoveCall;
+    use crate::types::ObjectId;
+    use crate::types::Upgrade;
+
+    impl TransactionBuilder {
+        /// Upgrade `package` to `modules`, linked against `dependencies`, with the `UpgradeCap` of
+        /// the package given by `upgrade_cap`, by reference to an owned object input.
+        ///
+        /// Adds the three commands of an upgrade: `0x2::package::authorize_upgrade` issues a
+        /// ticket for the digest of the new package under `policy`, the `Upgrade` command spends
+        /// the ticket to publish the new package, and `0x2::package::commit_upgrade` hands its
+        /// receipt back to the cap, which then refers to the new package.
+        pub fn upgrade_package(
+            &mut self,
+            upgrade_cap: Argument,
+            policy: UpgradePolicy,
+            package: ObjectId,
+            modules: Vec<Vec<u8>>,
+            dependencies: Vec<ObjectId>,
+        ) {
+            let digest = package_digest(&modules, &dependencies);
+            let policy = self.input(InputArgument::Pure {
+                value: vec![policy.as_u8()],
+            });
+            // The BCS of the digest as a `vector<u8>`, prefixed by its length
+            let mut value = vec![32];
+            value.extend_from_slice(digest.inner());
+            let digest = self.input(InputArgument::Pure { value });
+
+            let ticket = self.command(package_call(
+                "authorize_upgrade",
+                vec![upgrade_cap, policy, digest],
+            ));
+            let receipt = self.command(Command::from(Upgrade {
+                modules,
+                dependencies,
+                package,
+                ticket,
+            }));
+            self.command(package_call("commit_upgrade", vec![upgrade_cap, receipt]));
+        }
+    }
+
+    /// A call to a function of the `0x2::package` module.
+    fn package_call(function: &str, arguments: Vec<Argument>) -> Command {
+        Command::from(MoveCall {
+            package: ObjectId::from(Address::TWO),
+            module: Identifier::new("package").unwrap(),
+            function: Identifier::new(function).unwrap(),
+            type_arguments: vec![],
+            arguments,
+        })
+    }
+
+    /// The digest of a package with `modules`, linked against `dependencies`, which authorizing
+    /// its upgrade commits to.
+    ///
+    /// The digest doesn't depend on the order of the modules or of the dependencies.
+    pub fn package_digest(modules: &[Vec<u8>], dependencies: &[ObjectId]) -> Digest {
+        let module_digests = Hasher::digest_many(modules);
+        let mut components = module_digests
+            .iter()
+            .map(AsRef::<[u8]>::as_ref)
+            .chain(dependencies.iter().map(AsRef::<[u8]>::as_ref))
+            .collect::<Vec<_>>();
+        components.sort();
+
+        let mut hasher = Hasher::new();
+        for component in components {
+            hasher.update(component);
+        }
+        hasher.finalize()
+    }
+}
+
+#[cfg(feature = "hash")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+pub use upgrade::package_digest;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::MergeCoins;
+    use crate::types::ObjectDigest;
+    use crate::types::ObjectReference;
+    use crate::types::SplitCoins;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn arguments() {
+        let mut builder = TransactionBuilder::new();
+        let first = builder.input(InputArgument::Pure { value: vec![1] });
+        let second = builder.input(InputArgument::Pure { value: vec![2] });
+        assert_eq!((first, second), (Argument::Input(0), Argument::Input(1)));
+
+        let split = builder.command(Command::SplitCoins(SplitCoins {
+            coin: Argument::GasCoin,
+            amounts: vec![first],
+        }));
+        assert_eq!(split, Argument::Result(0));
+
+        let ptb = builder.finish();
+        assert_eq!(ptb.inputs.len(), 2);
+        assert_eq!(ptb.commands.len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn pure() {
+        let mut builder = TransactionBuilder::new();
+        builder.pure(&1000u64).unwrap();
+        builder.pure("sui").unwrap();
+        assert_eq!(
+            builder.finish().inputs,
+            [
+                InputArgument::Pure {
+                    value: 1000u64.to_le_bytes().to_vec()
+                },
+                InputArgument::Pure {
+                    value: vec![3, b's', b'u', b'i']
+                },
+            ]
+        );
+    }
+
+    fn object(byte: u8) -> ObjectReference {
+        ObjectReference::new(
+            ObjectId::new([byte; 32]),
+            Version::new(1),
+            ObjectDigest::new([byte; 32]),
+        )
+    }
+
+    #[test]
+    fn duplicate_inputs() {
+        let shared = |mutable| InputArgument::Shared {
+            object_id: ObjectId::new([7; 32]),
+            initial_shared_version: Version::new(3),
+            mutable,
+        };
+
+        let mut builder = TransactionBuilder::new();
+        let owned = builder.input(InputArgument::ImmutableOrOwned(object(1)));
+        let amount = builder.input(InputArgument::Pure { value: vec![5] });
+        let by_reference = builder.input(shared(false));
+        assert_eq!(
+            builder.input(InputArgument::ImmutableOrOwned(object(1))),
+            owned
+        );
+        assert_eq!(
+            builder.input(InputArgument::Pure { value: vec![5] }),
+            amount
+        );
+        assert_eq!(builder.input(shared(true)), by_reference);
+        assert_eq!(builder.input(shared(false)), by_reference);
+
+        // An object is the same input once its type is known
+        let coin = TypeTag::Struct(Box::new(StructTag::gas_coin()));
+        assert_eq!(
+            builder.input_with_type(InputArgument::ImmutableOrOwned(object(1)), coin.clone()),
+            owned
+        );
+        assert_eq!(builder.type_of(owned), Some(coin));
+
+        // Pure inputs of the same bytes but of different types, or of a type and of none
+        let flag = builder.input_with_type(InputArgument::Pure { value: vec![1] }, TypeTag::Bool);
+        let byte = builder.input_with_type(InputArgument::Pure { value: vec![1] }, TypeTag::U8);
+        let untyped = builder.input(InputArgument::Pure { value: vec![1] });
+        assert_ne!(flag, byte);
+        assert_ne!(untyped, flag);
+        assert_ne!(untyped, byte);
+        assert_eq!(
+            builder.input_with_type(InputArgument::Pure { value: vec![1] }, TypeTag::U8),
+            byte
+        );
+        assert_eq!(
+            builder.input(InputArgument::Pure { value: vec![1] }),
+            untyped
+        );
+        assert_eq!(builder.type_of(untyped), None);
+
+        let ptb = builder.finish();
+        assert_eq!(ptb.inputs.len(), 6);
+        assert_eq!(ptb.inputs[2], shared(true));
+    }
+
+    #[test]
+    fn canonical_inputs() {
+        let build = |reversed: bool| {
+            let mut inputs = vec![
+                InputArgument::Pure { value: vec![9] },
+                InputArgument::ImmutableOrOwned(object(2)),
+                InputArgument::Pure { value: vec![1] },
+                InputArgument::ImmutableOrOwned(object(1)),
+            ];
+            if reversed {
+                inputs.reverse();
+            }
+            let mut builder = TransactionBuilder::new();
+            for input in inputs {
+                builder.input(input);
+            }
+            let coin = builder.input(InputArgument::ImmutableOrOwned(object(1)));
+            let amounts = [
+                builder.input(InputArgument::Pure { value: vec![9] }),
+                builder.input(InputArgument::Pure { value: vec![1] }),
+            ];
+            builder.command(Command::SplitCoins(SplitCoins {
+                coin,
+                amounts: amounts.to_vec(),
+            }));
+            builder.finish_canonical().unwrap()
+        };
+
+        let ptb = build(false);
+        assert_eq!(ptb, build(true));
+        assert_eq!(
+            ptb.inputs,
+            [
+                InputArgument::ImmutableOrOwned(object(1)),
+                InputArgument::ImmutableOrOwned(object(2)),
+                InputArgument::Pure { value: vec![1] },
+                InputArgument::Pure { value: vec![9] },
+            ]
+        );
+        assert_eq!(
+            ptb.commands,
+            [Command::SplitCoins(SplitCoins {
+                coin: Argument::Input(0),
+                amounts: vec![Argument::Input(3), Argument::Input(2)],
+            })]
+        );
+    }
+
+    #[test]
+    fn canonical_inputs_of_the_same_bytes() {
+        let build = |types: [Option<TypeTag>; 3]| {
+            let mut builder = TransactionBuilder::new();
+            let arguments = types
+                .iter()
+                .map(|type_| {
+                    builder.push_input(InputArgument::Pure { value: vec![1] }, type_.clone())
+                })
+                .collect::<Vec<_>>();
+            builder.command(Command::SplitCoins(SplitCoins {
+                coin: Argument::GasCoin,
+                amounts: arguments,
+            }));
+            builder.finish_canonical().unwrap()
+        };
+
+        let ptb = build([Some(TypeTag::U8), None, Some(TypeTag::Bool)]);
+        assert_eq!(
+            ptb.commands,
+            [Command::SplitCoins(SplitCoins {
+                coin: Argument::GasCoin,
+                amounts: vec![Argument::Input(1), Argument::Input(0), Argument::Input(2)],
+            })]
+        );
+        // Built the other way around, each pure input has the same index
+        let reversed = build([Some(TypeTag::Bool), None, Some(TypeTag::U8)]);
+        assert_eq!(
+            reversed.commands,
+            [Command::SplitCoins(SplitCoins {
+                coin: Argument::GasCoin,
+                amounts: vec![Argument::Input(2), Argument::Input(0), Argument::Input(1)],
+            })]
+        );
+    }
+
+    #[test]
+    fn canonical_inputs_unknown_input() {
+        let mut builder = TransactionBuilder::new();
+        let coin = builder.input(InputArgument::ImmutableOrOwned(object(1)));
+        builder.command(Command::MergeCoins(MergeCoins {
+            coin,
+            coins_to_merge: vec![Argument::Input(1)],
+        }));
+        assert_eq!(
+            builder.finish_canonical(),
+            Err(UnknownInputError {
+                command: 0,
+                input: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn move_types() {
+        assert_eq!(u64::type_tag(), TypeTag::U64);
+        let std = MOVE_STDLIB_ADDRESS;
+        assert_eq!(
+            Vec::<Option<String>>::type_tag().to_string(),
+            format!("vector<{std}::option::Option<{std}::string::String>>")
+        );
+        assert_eq!(<&[u8]>::type_tag(), Vec::<u8>::type_tag());
+    }
+
+    #[test]
+    fn make_move_vec() {
+        let coin = TypeTag::Struct(Box::new(StructTag::gas_coin()));
+        let mut builder = TransactionBuilder::new();
+        let coin_input =
+            builder.input_with_type(InputArgument::Pure { value: vec![] }, coin.clone());
+        let amount = builder.input_with_type(InputArgument::Pure { value: vec![] }, TypeTag::U64);
+        let untyped = builder.input(InputArgument::Pure { value: vec![] });
+
+        // The type is inferred from the elements whose type is known
+        let coins = builder
+            .make_move_vec_of(None, vec![Argument::GasCoin, coin_input])
+            .unwrap();
+        assert_eq!(
+            builder.type_of(coins),
+            Some(TypeTag::Vector(Box::new(coin.clone())))
+        );
+        let amounts = builder.make_move_vec::<u64>(vec![amount, amount]).unwrap();
+        assert_eq!(builder.type_of(amounts), Some(Vec::<u64>::type_tag()));
+
+        assert_eq!(
+            builder.make_move_vec::<u64>(vec![amount, coin_input]),
+            Err(MakeMoveVecError::TypeMismatch {
+                element: coin_input,
+                expected: TypeTag::U64,
+                found: coin.clone(),
+            })
+        );
+        assert_eq!(
+            builder.make_move_vec_of(None, vec![]),
+            Err(MakeMoveVecError::MissingType)
+        );
+        assert_eq!(
+            builder.make_move_vec_of(None, vec![untyped]),
+            Err(MakeMoveVecError::UntypedPure(untyped))
+        );
+        let empty = builder.make_move_vec::<u8>(vec![]).unwrap();
+
+        let ptb = builder.finish();
+        assert_eq!(ptb.commands.len(), 3);
+        let Command::MakeMoveVector(vector) = &ptb.commands[0] else {
+            panic!("not a vector");
+        };
+        assert_eq!(vector.type_, Some(coin));
+        let Command::MakeMoveVector(vector) = &ptb.commands[2] else {
+            panic!("not a vector");
+        };
+        assert_eq!(empty, Argument::Result(2));
+        assert_eq!(vector.type_, Some(TypeTag::U8));
+        assert!(vector.elements.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn typed_pure() {
+        let mut builder = TransactionBuilder::new();
+        let name = builder.typed_pure("sui").unwrap();
+        let other = builder.typed_pure(&String::from("move")).unwrap();
+        let names = builder.make_move_vec_of(None, vec![name, other]).unwrap();
+        assert_eq!(builder.type_of(names), Some(Vec::<String>::type_tag()));
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn upgrade() {
+        let package = ObjectId::from(Address::from_static_hex("0x42"));
+        let modules = vec![vec![1, 2, 3], vec![4, 5]];
+        let dependencies = vec![ObjectId::from(Address::TWO), ObjectId::from(Address::ZERO)];
+
+        let mut builder = TransactionBuilder::new();
+        let cap = builder.input(InputArgument::Pure { value: vec![] });
+        builder.upgrade_package(
+            cap,
+            UpgradePolicy::Additive,
+            package,
+            modules.clone(),
+            dependencies.clone(),
+        );
+
+        let ptb = builder.finish();
+        let digest = package_digest(&modules, &dependencies);
+        assert_eq!(ptb.inputs[1], InputArgument::Pure { value: vec![128] });
+        let InputArgument::Pure { value } = &ptb.inputs[2] else {
+            panic!("digest is not pure");
+        };
+        assert_eq!(value[0], 32);
+        assert_eq!(&value[1..], digest.inner());
+
+        let [Command::MoveCall(authorize), Command::Upgrade(upgrade), Command::MoveCall(commit)] =
+            &ptb.commands[..]
+        else {
+            panic!("unexpected commands {:?}", ptb.commands);
+        };
+        assert_eq!(authorize.function.as_str(), "authorize_upgrade");
+        assert_eq!(
+            authorize.arguments,
+            [cap, Argument::Input(1), Argument::Input(2)]
+        );
+        assert_eq!(upgrade.ticket, Argument::Result(0));
+        assert_eq!(upgrade.package, package);
+        assert_eq!(commit.function.as_str(), "commit_upgrade");
+        assert_eq!(commit.arguments, [cap, Argument::Result(1)]);
+    }
+
+    #[cfg(feature = "hash")]
+    #[test]
+    fn package_digest_is_unordered() {
+        let modules = [vec![1, 2, 3], vec![4, 5]];
+        let dependencies = [ObjectId::from(Address::TWO), ObjectId::from(Address::ZERO)];
+        let digest = package_digest(&modules, &dependencies);
+
+        let mut reversed_modules = modules.clone();
+        reversed_modules.reverse();
+        let mut reversed_dependencies = dependencies;
+        reversed_dependencies.reverse();
+        assert_eq!(
+            package_digest(&reversed_modules, &reversed_dependencies),
+            digest
+        );
+        assert_ne!(package_digest(&modules[..1], &dependencies), digest);
+    }
+}
@@ -0,0 +1,854 @@
+//! Reading of compiled Move modules, as published in packages.
+//!
+//! A [`CompiledModule`] holds the parts of a module's bytecode which describe its types and the
+//! signatures of its functions, enough for a [`LayoutResolver`] to derive the
+//! [`MoveTypeLayout`](crate::types::MoveTypeLayout) of any type defined by a set of packages, and
+//! so decode the contents of objects and events without asking a node, and for a [`PackageAbi`]
+//! to describe the functions of a package which transactions can call.
+
+use crate::types::Address;
+use crate::types::Identifier;
+
+mod abi;
+mod resolver;
+
+pub use abi::Ability;
+pub use abi::FunctionAbi;
+pub use abi::ModuleAbi;
+pub use abi::PackageAbi;
+pub use abi::ParameterAbi;
+pub use abi::ParameterKind;
+pub use abi::Reference;
+pub use abi::TypeParameterAbi;
+pub use resolver::LayoutError;
+pub use resolver::LayoutResolver;
+
+const MAGIC: [u8; 4] = [0xa1, 0x1c, 0xeb, 0x0b];
+const MIN_VERSION: u32 = 5;
+const MAX_VERSION: u32 = 7;
+/// Versions from 7 on carry the flavor of Move in their high byte.
+const SUI_FLAVOR: u32 = 0x05;
+
+/// The maximum nesting of a type in a signature.
+const MAX_SIGNATURE_DEPTH: usize = 256;
+
+mod table {
+    pub const MODULE_HANDLES: u8 = 0x1;
+    pub const DATATYPE_HANDLES: u8 = 0x2;
+    pub const FUNCTION_HANDLES: u8 = 0x3;
+    pub const SIGNATURES: u8 = 0x5;
+    pub const IDENTIFIERS: u8 = 0x7;
+    pub const ADDRESS_IDENTIFIERS: u8 = 0x8;
+    pub const STRUCT_DEFS: u8 = 0xa;
+    pub const FUNCTION_DEFS: u8 = 0xc;
+    pub const ENUM_DEFS: u8 = 0x11;
+}
+
+/// The flags of a function definition, after its visibility.
+mod function_flags {
+    pub const NATIVE: u8 = 0x2;
+    pub const ENTRY: u8 = 0x4;
+}
+
+/// The parts of a compiled Move module describing its types and functions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompiledModule {
+    version: u32,
+    self_handle: u16,
+    module_handles: Vec<ModuleHandle>,
+    datatype_handles: Vec<DatatypeHandle>,
+    function_handles: Vec<FunctionHandle>,
+    signatures: Vec<Vec<SignatureToken>>,
+    identifiers: Vec<Identifier>,
+    address_identifiers: Vec<Address>,
+    struct_defs: Vec<StructDefinition>,
+    function_defs: Vec<FunctionDefinition>,
+    enum_defs: Vec<EnumDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct ModuleHandle {
+    address: u16,
+    name: u16,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DatatypeHandle {
+    module: u16,
+    name: u16,
+    abilities: u8,
+    type_parameters: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FunctionHandle {
+    name: u16,
+    /// The signature of the parameters.
+    parameters: u16,
+    /// The signature of the return values.
+    returns: u16,
+    /// The abilities each type parameter is constrained to have.
+    type_parameters: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct StructDefinition {
+    handle: u16,
+    /// The fields of the struct, `None` for a native struct.
+    fields: Option<Vec<FieldDefinition>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FunctionDefinition {
+    handle: u16,
+    visibility: Visibility,
+    is_entry: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Visibility {
+    Private,
+    Public,
+    Friend,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct EnumDefinition {
+    handle: u16,
+    variants: Vec<VariantDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct VariantDefinition {
+    name: u16,
+    fields: Vec<FieldDefinition>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FieldDefinition {
+    name: u16,
+    type_: SignatureToken,
+}
+
+/// A type in a module, referring to the datatypes and type parameters of the module by index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum SignatureToken {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<SignatureToken>),
+    Datatype(u16),
+    DatatypeInstantiation(u16, Vec<SignatureToken>),
+    Reference(Box<SignatureToken>),
+    MutableReference(Box<SignatureToken>),
+    TypeParameter(u16),
+}
+
+impl CompiledModule {
+    /// Read the types of a module from its bytecode.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, BytecodeError> {
+        let mut reader = Reader(bytes);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(BytecodeError::BadMagic);
+        }
+        let flavored_version = u32::from_le_bytes(reader.take(4)?.try_into().unwrap());
+        let version = if flavored_version <= 6 {
+            flavored_version
+        } else if flavored_version >> 24 == SUI_FLAVOR {
+            flavored_version & 0x00ff_ffff
+        } else {
+            return Err(BytecodeError::UnsupportedVersion(flavored_version));
+        };
+        if !(MIN_VERSION..=MAX_VERSION).contains(&version) {
+            return Err(BytecodeError::UnsupportedVersion(version));
+        }
+
+        let table_count = reader.uleb128(255)?;
+        let mut tables = Vec::with_capacity(table_count as usize);
+        for _ in 0..table_count {
+            let [kind] = reader.array()?;
+            let offset = reader.uleb128(u32::MAX.into())?;
+            let len = reader.uleb128(u32::MAX.into())?;
+            tables.push((kind, offset as usize, (offset + len) as usize));
+        }
+        let data = reader.0;
+        let data_len = tables.iter().map(|(_, _, end)| *end).max().unwrap_or(0);
+        if data_len > data.len() {
+            return Err(BytecodeError::UnexpectedEnd);
+        }
+        let mut trailer = Reader(&data[data_len..]);
+        let self_handle = trailer.index()?;
+
+        let mut module = Self {
+            version,
+            self_handle,
+            module_handles: vec![],
+            datatype_handles: vec![],
+            function_handles: vec![],
+            signatures: vec![],
+            identifiers: vec![],
+            address_identifiers: vec![],
+            struct_defs: vec![],
+            function_defs: vec![],
+            enum_defs: vec![],
+        };
+        for (kind, start, end) in tables {
+            let mut reader = Reader(data.get(start..end).ok_or(BytecodeError::UnexpectedEnd)?);
+            while !reader.0.is_empty() {
+                match kind {
+                    table::MODULE_HANDLES => module.module_handles.push(ModuleHandle {
+                        address: reader.index()?,
+                        name: reader.index()?,
+                    }),
+                    table::DATATYPE_HANDLES => {
+                        module.datatype_handles.push(reader.datatype_handle()?)
+                    }
+                    table::FUNCTION_HANDLES => {
+                        module.function_handles.push(reader.function_handle()?)
+                    }
+                    table::SIGNATURES => module.signatures.push(reader.signature()?),
+                    table::IDENTIFIERS => module.identifiers.push(reader.identifier()?),
+                    table::ADDRESS_IDENTIFIERS => module
+                        .address_identifiers
+                        .push(Address::new(reader.array()?)),
+                    table::STRUCT_DEFS => module.struct_defs.push(reader.struct_def()?),
+                    table::FUNCTION_DEFS => {
+                        module.function_defs.push(reader.function_def(version)?)
+                    }
+                    table::ENUM_DEFS => module.enum_defs.push(reader.enum_def()?),
+                    // The other tables describe the code of functions, and constants
+                    _ => break,
+                }
+            }
+        }
+
+        module.handle(module.self_handle)?;
+        Ok(module)
+    }
+
+    /// The version of the bytecode format.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The address of the module, which is the original id of its package.
+    pub fn address(&self) -> Address {
+        self.handle(self.self_handle)
+            .expect("checked when deserialized")
+            .0
+    }
+
+    pub fn name(&self) -> &Identifier {
+        self.handle(self.self_handle)
+            .expect("checked when deserialized")
+            .1
+    }
+
+    /// The address and name of the module of the handle at `index`.
+    fn handle(&self, index: u16) -> Result<(Address, &Identifier), BytecodeError> {
+        let handle = self
+            .module_handles
+            .get(index as usize)
+            .ok_or(BytecodeError::IndexOutOfBounds)?;
+        Ok((
+            *self
+                .address_identifiers
+                .get(handle.address as usize)
+                .ok_or(BytecodeError::IndexOutOfBounds)?,
+            self.identifier(handle.name)?,
+        ))
+    }
+
+    fn identifier(&self, index: u16) -> Result<&Identifier, BytecodeError> {
+        self.identifiers
+            .get(index as usize)
+            .ok_or(BytecodeError::IndexOutOfBounds)
+    }
+
+    fn datatype_handle(&self, index: u16) -> Result<&DatatypeHandle, BytecodeError> {
+        self.datatype_handles
+            .get(index as usize)
+            .ok_or(BytecodeError::IndexOutOfBounds)
+    }
+
+    fn function_handle(&self, index: u16) -> Result<&FunctionHandle, BytecodeError> {
+        self.function_handles
+            .get(index as usize)
+            .ok_or(BytecodeError::IndexOutOfBounds)
+    }
+
+    fn signature(&self, index: u16) -> Result<&[SignatureToken], BytecodeError> {
+        self.signatures
+            .get(index as usize)
+            .map(Vec::as_slice)
+            .ok_or(BytecodeError::IndexOutOfBounds)
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], BytecodeError> {
+        if self.0.len() < len {
+            return Err(BytecodeError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], BytecodeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    fn uleb128(&mut self, max: u64) -> Result<u64, BytecodeError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let [byte] = self.array()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return if value <= max {
+                    Ok(value)
+                } else {
+                    Err(BytecodeError::Malformed("value out of range"))
+                };
+            }
+        }
+        Err(BytecodeError::Malformed("invalid ULEB128"))
+    }
+
+    /// The index of an entry of a table.
+    fn index(&mut self) -> Result<u16, BytecodeError> {
+        Ok(self.uleb128(u16::MAX.into())? as u16)
+    }
+
+    fn identifier(&mut self) -> Result<Identifier, BytecodeError> {
+        let len = self.uleb128(u16::MAX.into())? as usize;
+        std::str::from_utf8(self.take(len)?)
+            .ok()
+            .and_then(|name| Identifier::new(name).ok())
+            .ok_or(BytecodeError::Malformed("invalid identifier"))
+    }
+
+    fn datatype_handle(&mut self) -> Result<DatatypeHandle, BytecodeError> {
+        let module = self.index()?;
+        let name = self.index()?;
+        let abilities = self.uleb128(0xf)? as u8;
+        let type_parameters = self.uleb128(255)? as usize;
+        for _ in 0..type_parameters {
+            let _constraints = self.uleb128(0xf)?;
+            let _is_phantom = self.uleb128(1)?;
+        }
+        Ok(DatatypeHandle {
+            module,
+            name,
+            abilities,
+            type_parameters,
+        })
+    }
+
+    fn function_handle(&mut self) -> Result<FunctionHandle, BytecodeError> {
+        let _module = self.index()?;
+        let name = self.index()?;
+        let parameters = self.index()?;
+        let returns = self.index()?;
+        let count = self.uleb128(255)?;
+        let type_parameters = (0..count)
+            .map(|_| Ok(self.uleb128(0xf)? as u8))
+            .collect::<Result<_, _>>()?;
+        Ok(FunctionHandle {
+            name,
+            parameters,
+            returns,
+            type_parameters,
+        })
+    }
+
+    fn signature(&mut self) -> Result<Vec<SignatureToken>, BytecodeError> {
+        let count = self.uleb128(255)?;
+        (0..count).map(|_| self.signature_token(0)).collect()
+    }
+
+    fn function_def(&mut self, version: u32) -> Result<FunctionDefinition, BytecodeError> {
+        let handle = self.index()?;
+        let visibility = match self.array()? {
+            [0x0] => Visibility::Private,
+            [0x1] => Visibility::Public,
+            [0x3] => Visibility::Friend,
+            _ => return Err(BytecodeError::Malformed("invalid visibility")),
+        };
+        let [flags] = self.array()?;
+        if flags & !(function_flags::NATIVE | function_flags::ENTRY) != 0 {
+            return Err(BytecodeError::Malformed("invalid function flags"));
+        }
+        for _ in 0..self.uleb128(255)? {
+            let _acquired = self.index()?;
+        }
+        if flags & function_flags::NATIVE == 0 {
+            self.skip_code_unit(version)?;
+        }
+        Ok(FunctionDefinition {
+            handle,
+            visibility,
+            is_entry: flags & function_flags::ENTRY != 0,
+        })
+    }
+
+    /// Skip over the locals, instructions and jump tables of a function.
+    fn skip_code_unit(&mut self, version: u32) -> Result<(), BytecodeError> {
+        let _locals = self.index()?;
+        for _ in 0..self.uleb128(u16::MAX.into())? {
+            self.skip_instruction()?;
+        }
+        if version >= 7 {
+            for _ in 0..self.uleb128(127)? {
+                let _enum_def = self.index()?;
+                let branches = self.uleb128(127)?;
+                if self.array()? != [0x1] {
+                    return Err(BytecodeError::Malformed("invalid jump table"));
+                }
+                for _ in 0..branches {
+                    let _offset = self.index()?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn skip_instruction(&mut self) -> Result<(), BytecodeError> {
+        let [opcode] = self.array()?;
+        match opcode {
+            // Instructions without operands
+            0x01 | 0x02 | 0x08 | 0x09 | 0x14..=0x28 | 0x2e..=0x30 | 0x33..=0x35 | 0x4b..=0x4d => {}
+            // Instructions taking an index into a table, a local or an offset in the code
+            0x03..=0x05
+            | 0x07
+            | 0x0a..=0x13
+            | 0x29..=0x2d
+            | 0x36..=0x3f
+            | 0x41..=0x45
+            | 0x47
+            | 0x4e..=0x56 => {
+                self.index()?;
+            }
+            // Loads of constant integers
+            0x31 => {
+                self.take(1)?;
+            }
+            0x48 => {
+                self.take(2)?;
+            }
+            0x49 => {
+                self.take(4)?;
+            }
+            0x06 => {
+                self.take(8)?;
+            }
+            0x32 => {
+                self.take(16)?;
+            }
+            0x4a => {
+                self.take(32)?;
+            }
+            // Vector packs and unpacks, taking a signature and a length
+            0x40 | 0x46 => {
+                self.index()?;
+                self.take(8)?;
+            }
+            _ => return Err(BytecodeError::Malformed("invalid opcode")),
+        }
+        Ok(())
+    }
+
+    fn struct_def(&mut self) -> Result<StructDefinition, BytecodeError> {
+        let handle = self.index()?;
+        let fields = match self.array()? {
+            [0x1] => None,
+            [0x2] => Some(self.fields()?),
+            _ => return Err(BytecodeError::Malformed("invalid struct flag")),
+        };
+        Ok(StructDefinition { handle, fields })
+    }
+
+    fn enum_def(&mut self) -> Result<EnumDefinition, BytecodeError> {
+        let handle = self.index()?;
+        if self.array()? != [0x2] {
+            return Err(BytecodeError::Malformed("invalid enum flag"));
+        }
+        let count = self.uleb128(127)?;
+        let variants = (0..count)
+            .map(|_| {
+                Ok(VariantDefinition {
+                    name: self.index()?,
+                    fields: self.fields()?,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(EnumDefinition { handle, variants })
+    }
+
+    fn fields(&mut self) -> Result<Vec<FieldDefinition>, BytecodeError> {
+        let count = self.uleb128(255)?;
+        (0..count)
+            .map(|_| {
+                Ok(FieldDefinition {
+                    name: self.index()?,
+                    type_: self.signature_token(0)?,
+                })
+            })
+            .collect()
+    }
+
+    fn signature_token(&mut self, depth: usize) -> Result<SignatureToken, BytecodeError> {
+        if depth > MAX_SIGNATURE_DEPTH {
+            return Err(BytecodeError::Malformed("type nested too deeply"));
+        }
+        let [tag] = self.array()?;
+        Ok(match tag {
+            0x1 => SignatureToken::Bool,
+            0x2 => SignatureToken::U8,
+            0x3 => SignatureToken::U64,
+            0x4 => SignatureToken::U128,
+            0x5 => SignatureToken::Address,
+            0x6 => SignatureToken::Reference(Box::new(self.signature_token(depth + 1)?)),
+            0x7 => SignatureToken::MutableReference(Box::new(self.signature_token(depth + 1)?)),
+            0x8 => SignatureToken::Datatype(self.index()?),
+            0x9 => SignatureToken::TypeParameter(self.index()?),
+            0xa => SignatureToken::Vector(Box::new(self.signature_token(depth + 1)?)),
+            0xb => {
+                let handle = self.index()?;
+                let arity = self.uleb128(255)?;
+                if arity == 0 {
+                    return Err(BytecodeError::Malformed("instantiation without types"));
+                }
+                let type_arguments = (0..arity)
+                    .map(|_| self.signature_token(depth + 1))
+                    .collect::<Result<_, _>>()?;
+                SignatureToken::DatatypeInstantiation(handle, type_arguments)
+            }
+            0xc => SignatureToken::Signer,
+            0xd => SignatureToken::U16,
+            0xe => SignatureToken::U32,
+            0xf => SignatureToken::U256,
+            _ => return Err(BytecodeError::Malformed("invalid type")),
+        })
+    }
+}
+
+/// The bytecode of a module can't be read.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BytecodeError {
+    /// The bytes are not Move bytecode.
+    BadMagic,
+    UnsupportedVersion(u32),
+    UnexpectedEnd,
+    Malformed(&'static str),
+    /// An entry refers to an entry of a table which doesn't exist.
+    IndexOutOfBounds,
+}
+
+impl std::fmt::Display for BytecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not Move bytecode"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported bytecode version {version}")
+            }
+            Self::UnexpectedEnd => write!(f, "unexpected end of bytecode"),
+            Self::Malformed(reason) => write!(f, "malformed bytecode: {reason}"),
+            Self::IndexOutOfBounds => write!(f, "table index out of bounds"),
+        }
+    }
+}
+
+impl std::error::Error for BytecodeError {}
+
+/// Hand assembled modules, for tests.
+#[cfg(test)]
+pub(crate) mod test_modules {
+    use super::table;
+
+    /// Append the ULEB128 encoding of `value` to `bytes`.
+    pub fn uleb128(bytes: &mut Vec<u8>, mut value: u64) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            if value == 0 {
+                bytes.push(byte);
+                return;
+            }
+            bytes.push(byte | 0x80);
+        }
+    }
+
+    /// A module of version 7 with the given tables, whose self handle is the first module handle.
+    pub fn module(tables: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        let mut bytes = super::MAGIC.to_vec();
+        bytes.extend(((super::SUI_FLAVOR << 24) | 7).to_le_bytes());
+        uleb128(&mut bytes, tables.len() as u64);
+        let mut offset = 0;
+        for (kind, contents) in tables {
+            bytes.push(*kind);
+            uleb128(&mut bytes, offset);
+            uleb128(&mut bytes, contents.len() as u64);
+            offset += contents.len() as u64;
+        }
+        for (_, contents) in tables {
+            bytes.extend(contents);
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    fn identifiers(names: &[&str]) -> Vec<u8> {
+        let mut bytes = vec![];
+        for name in names {
+            uleb128(&mut bytes, name.len() as u64);
+            bytes.extend(name.as_bytes());
+        }
+        bytes
+    }
+
+    fn addresses(addresses: &[u8]) -> Vec<u8> {
+        addresses
+            .iter()
+            .flat_map(|address| {
+                let mut bytes = [0; 32];
+                bytes[31] = *address;
+                bytes
+            })
+            .collect()
+    }
+
+    /// `0x1::string`, defining `struct String { bytes: vector<u8> }`.
+    pub fn string() -> Vec<u8> {
+        module(&[
+            (table::MODULE_HANDLES, vec![0, 0]),
+            // String, with the abilities copy, drop and store
+            (table::DATATYPE_HANDLES, vec![0, 1, 0x7, 0]),
+            (
+                table::IDENTIFIERS,
+                identifiers(&["string", "String", "bytes"]),
+            ),
+            (table::ADDRESS_IDENTIFIERS, addresses(&[0x1])),
+            (table::STRUCT_DEFS, vec![0, 0x2, 1, 2, 0xa, 0x2]),
+        ])
+    }
+
+    /// `0x42::example`, defining:
+    ///
+    /// ```text
+    /// struct Pair<T> { first: T, second: u64 }
+    /// struct Record { pair: Pair<bool>, names: vector<String>, status: Status }
+    /// enum Status { Active, Closed { at: u64 } }
+    /// ```
+    pub fn example() -> Vec<u8> {
+        module(&[
+            (table::MODULE_HANDLES, vec![0, 0, 1, 1]),
+            (
+                table::DATATYPE_HANDLES,
+                vec![
+                    // Pair<T>
+                    0, 2, 0x7, 1, 0x7, 0, //
+                    // Record
+                    0, 3, 0x7, 0, //
+                    // Status
+                    0, 4, 0x7, 0, //
+                    // 0x1::string::String
+                    1, 10, 0x7, 0,
+                ],
+            ),
+            (
+                table::IDENTIFIERS,
+                identifiers(&[
+                    "example", "string", "Pair", "Record", "Status", "first", "second", "pair",
+                    "names", "status", "String", "Active", "Closed", "at",
+                ]),
+            ),
+            (table::ADDRESS_IDENTIFIERS, addresses(&[0x42, 0x1])),
+            (
+                table::STRUCT_DEFS,
+                vec![
+                    // Pair { first: T, second: u64 }
+                    0, 0x2, 2, 5, 0x9, 0, 6, 0x3, //
+                    // Record { pair: Pair<bool>, names: vector<String>, status: Status }
+                    1, 0x2, 3, 7, 0xb, 0, 1, 0x1, 8, 0xa, 0x8, 3, 9, 0x8, 2,
+                ],
+            ),
+            (
+                table::ENUM_DEFS,
+                // Status { Active, Closed { at: u64 } }
+                vec![2, 0x2, 2, 11, 0, 12, 1, 13, 0x3],
+            ),
+        ])
+    }
+
+    /// `0x42::shop`, declaring:
+    ///
+    /// ```text
+    /// public entry fun buy<T: key + store>(
+    ///     item: &mut Item,
+    ///     payment: T,
+    ///     names: vector<String>,
+    ///     amount: Option<u64>,
+    ///     items: vector<Item>,
+    ///     ctx: &mut TxContext,
+    /// )
+    /// public fun price(item: &Item): u64
+    /// fun helper(x: u64)
+    /// ```
+    ///
+    /// where `Item` has the abilities key and store, with code exercising the decoding of
+    /// instructions.
+    pub fn shop() -> Vec<u8> {
+        module(&[
+            (table::MODULE_HANDLES, vec![0, 0, 1, 1, 2, 2, 2, 3]),
+            (
+                table::DATATYPE_HANDLES,
+                vec![
+                    // Item
+                    0, 4, 0xc, 0, //
+                    // 0x2::tx_context::TxContext
+                    1, 5, 0x2, 0, //
+                    // 0x1::string::String
+                    2, 6, 0x7, 0, //
+                    // 0x1::option::Option<T>
+                    3, 7, 0x7, 1, 0, 0,
+                ],
+            ),
+            (
+                table::FUNCTION_HANDLES,
+                vec![
+                    // buy<T: key + store>
+                    0, 8, 1, 0, 1, 0xc, //
+                    // price
+                    0, 9, 2, 3, 0, //
+                    // helper
+                    0, 10, 4, 0, 0,
+                ],
+            ),
+            (
+                table::SIGNATURES,
+                vec![
+                    // ()
+                    0, //
+                    // (&mut Item, T, vector<String>, Option<u64>, vector<Item>, &mut TxContext)
+                    6, 0x7, 0x8, 0, 0x9, 0, 0xa, 0x8, 2, 0xb, 3, 1, 0x3, 0xa, 0x8, 0, 0x7, 0x8, 1,
+                    // (&Item)
+                    1, 0x6, 0x8, 0, //
+                    // (u64)
+                    1, 0x3, //
+                    // (u64)
+                    1, 0x3,
+                ],
+            ),
+            (
+                table::IDENTIFIERS,
+                identifiers(&[
+                    "shop",
+                    "tx_context",
+                    "string",
+                    "option",
+                    "Item",
+                    "TxContext",
+                    "String",
+                    "Option",
+                    "buy",
+                    "price",
+                    "helper",
+                ]),
+            ),
+            (table::ADDRESS_IDENTIFIERS, addresses(&[0x42, 0x2, 0x1])),
+            (
+                table::FUNCTION_DEFS,
+                vec![
+                    // buy: public entry, LdU64(1), Pop, Ret
+                    0, 0x1, 0x4, 0, 0, 3, 0x06, 1, 0, 0, 0, 0, 0, 0, 0, 0x01, 0x02, 0, //
+                    // price: public, MoveLoc(0), Pop, LdU8(7), CastU64, Ret
+                    1, 0x1, 0, 0, 0, 5, 0x0b, 0, 0x01, 0x31, 7, 0x34, 0x02, 0, //
+                    // helper: private, VariantSwitch(0), Ret, with a jump table of two branches
+                    2, 0, 0, 0, 0, 2, 0x56, 0, 0x02, 1, 0, 2, 0x1, 0, 1,
+                ],
+            ),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn deserialize() {
+        let module = CompiledModule::deserialize(&test_modules::example()).unwrap();
+        assert_eq!(module.version(), 7);
+        assert_eq!(module.address(), Address::from_static_hex("0x42"));
+        assert_eq!(module.name().as_str(), "example");
+        assert_eq!(module.struct_defs.len(), 2);
+        assert_eq!(
+            module.struct_defs[1].fields.as_ref().unwrap()[0].type_,
+            SignatureToken::DatatypeInstantiation(0, vec![SignatureToken::Bool])
+        );
+        assert_eq!(module.enum_defs[0].variants.len(), 2);
+
+        let string = CompiledModule::deserialize(&test_modules::string()).unwrap();
+        assert_eq!(string.address(), Address::from_static_hex("0x1"));
+    }
+
+    #[test]
+    fn functions() {
+        let module = CompiledModule::deserialize(&test_modules::shop()).unwrap();
+        assert_eq!(module.function_defs.len(), 3);
+        assert_eq!(
+            module.function_defs[0],
+            FunctionDefinition {
+                handle: 0,
+                visibility: Visibility::Public,
+                is_entry: true,
+            }
+        );
+        assert_eq!(module.function_defs[2].visibility, Visibility::Private);
+        assert_eq!(module.function_handles[0].type_parameters, [0xc]);
+        assert_eq!(module.signature(1).unwrap().len(), 6);
+
+        let mut bytes = test_modules::shop();
+        // The opcode of the last instruction of `helper`
+        let ret = bytes.len() - 8;
+        assert_eq!(bytes[ret], 0x02);
+        bytes[ret] = 0xff;
+        assert_eq!(
+            CompiledModule::deserialize(&bytes),
+            Err(BytecodeError::Malformed("invalid opcode"))
+        );
+    }
+
+    #[test]
+    fn invalid_bytecode() {
+        assert_eq!(
+            CompiledModule::deserialize(&[0; 8]),
+            Err(BytecodeError::BadMagic)
+        );
+
+        let mut bytes = test_modules::example();
+        bytes[4] = 8;
+        assert_eq!(
+            CompiledModule::deserialize(&bytes),
+            Err(BytecodeError::UnsupportedVersion(8))
+        );
+
+        let bytes = test_modules::example();
+        assert_eq!(
+            CompiledModule::deserialize(&bytes[..bytes.len() - 10]),
+            Err(BytecodeError::UnexpectedEnd)
+        );
+    }
+}
@@ -0,0 +1,470 @@
+use super::BytecodeError;
+use super::CompiledModule;
+use super::SignatureToken;
+use super::Visibility;
+use crate::types::well_known::MOVE_STDLIB_ADDRESS;
+use crate::types::well_known::SUI_FRAMEWORK_ADDRESS;
+use crate::types::Address;
+use crate::types::Identifier;
+use crate::types::MovePackage;
+use crate::types::ObjectId;
+
+/// The functions of a package which transactions can call, with the kinds of their parameters,
+/// for frontends to build the forms of calls to them.
+///
+/// Its readable serialization is the JSON ABI of the package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct PackageAbi {
+    pub package: ObjectId,
+    pub modules: Vec<ModuleAbi>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct ModuleAbi {
+    pub name: Identifier,
+    pub functions: Vec<FunctionAbi>,
+}
+
+/// A public or entry function.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct FunctionAbi {
+    pub name: Identifier,
+    /// Entry functions can only be given inputs of the transaction, not results of other
+    /// commands.
+    pub is_entry: bool,
+    pub type_parameters: Vec<TypeParameterAbi>,
+    /// The parameters, but for the `TxContext` which ends the parameters of functions using it,
+    /// which is provided by the chain.
+    pub parameters: Vec<ParameterAbi>,
+    /// The types of the return values.
+    pub returns: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct TypeParameterAbi {
+    /// The abilities the type argument must have.
+    pub constraints: Vec<Ability>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Ability {
+    Copy,
+    Drop,
+    Store,
+    Key,
+}
+
+impl Ability {
+    const ALL: [Self; 4] = [Self::Copy, Self::Drop, Self::Store, Self::Key];
+
+    fn bit(self) -> u8 {
+        match self {
+            Self::Copy => 0x1,
+            Self::Drop => 0x2,
+            Self::Store => 0x4,
+            Self::Key => 0x8,
+        }
+    }
+
+    fn set(abilities: u8) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|ability| abilities & ability.bit() != 0)
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct ParameterAbi {
+    /// The type of the parameter, in Move syntax, naming type parameters `T0`, `T1` and so on.
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: String,
+    pub kind: ParameterKind,
+    /// Whether the parameter is a reference, if it is one.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub reference: Option<Reference>,
+}
+
+/// How the argument of a parameter is given to a transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ParameterKind {
+    /// A pure input, the BCS of a primitive, a string, an `ID`, an `Option` or a vector of them.
+    ///
+    /// Type parameters without the `key` ability are taken to be pure.
+    Pure,
+    /// An object input.
+    Object,
+    /// A vector of objects, made from object inputs with `MakeMoveVec`.
+    ObjectVector,
+    /// An object input for an object sent to the object of another argument.
+    Receiving,
+    /// A value which can only be the result of another command.
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Reference {
+    Immutable,
+    Mutable,
+}
+
+impl PackageAbi {
+    /// The ABI of the functions of `package` which transactions can call.
+    pub fn new(package: &MovePackage) -> Result<Self, BytecodeError> {
+        Ok(Self {
+            package: package.id(),
+            modules: package
+                .modules()
+                .values()
+                .map(|bytes| ModuleAbi::new(&CompiledModule::deserialize(bytes)?))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl ModuleAbi {
+    pub fn new(module: &CompiledModule) -> Result<Self, BytecodeError> {
+        let mut functions = vec![];
+        for definition in &module.function_defs {
+            if !definition.is_entry && definition.visibility != Visibility::Public {
+                continue;
+            }
+            functions.push(function(module, definition.handle, definition.is_entry)?);
+        }
+        Ok(Self {
+            name: module.name().clone(),
+            functions,
+        })
+    }
+}
+
+fn function(
+    module: &CompiledModule,
+    handle: u16,
+    is_entry: bool,
+) -> Result<FunctionAbi, BytecodeError> {
+    let handle = module.function_handle(handle)?;
+    let type_parameters = &handle.type_parameters;
+
+    let mut parameters = module.signature(handle.parameters)?;
+    if let Some((last, rest)) = parameters.split_last() {
+        if is_tx_context(module, last)? {
+            parameters = rest;
+        }
+    }
+    let parameters = parameters
+        .iter()
+        .map(|token| {
+            let (reference, inner) = match token {
+                SignatureToken::Reference(inner) => (Some(Reference::Immutable), inner.as_ref()),
+                SignatureToken::MutableReference(inner) => {
+                    (Some(Reference::Mutable), inner.as_ref())
+                }
+                token => (None, token),
+            };
+            let kind = if reference.is_none() && is_pure(module, type_parameters, inner)? {
+                ParameterKind::Pure
+            } else if is_object(module, type_parameters, inner)? {
+                ParameterKind::Object
+            } else if reference.is_none() && is_receiving(module, inner)? {
+                ParameterKind::Receiving
+            } else if reference.is_none() && is_object_vector(module, type_parameters, inner)? {
+                ParameterKind::ObjectVector
+            } else {
+                ParameterKind::Other
+            };
+            Ok(ParameterAbi {
+                type_: type_name(module, token)?,
+                kind,
+                reference,
+            })
+        })
+        .collect::<Result<_, BytecodeError>>()?;
+
+    Ok(FunctionAbi {
+        name: module.identifier(handle.name)?.clone(),
+        is_entry,
+        type_parameters: type_parameters
+            .iter()
+            .map(|abilities| TypeParameterAbi {
+                constraints: Ability::set(*abilities),
+            })
+            .collect(),
+        parameters,
+        returns: module
+            .signature(handle.returns)?
+            .iter()
+            .map(|token| type_name(module, token))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+/// The address, module and name of the datatype of `token`, if it is one.
+fn datatype(
+    module: &CompiledModule,
+    token: &SignatureToken,
+) -> Result<Option<(Address, &str, &str)>, BytecodeError> {
+    let handle = match token {
+        SignatureToken::Datatype(handle) | SignatureToken::DatatypeInstantiation(handle, _) => {
+            module.datatype_handle(*handle)?
+        }
+        _ => return Ok(None),
+    };
+    let (address, module_name) = module.handle(handle.module)?;
+    Ok(Some((
+        address,
+        module_name.as_str(),
+        module.identifier(handle.name)?.as_str(),
+    )))
+}
+
+fn is_tx_context(module: &CompiledModule, token: &SignatureToken) -> Result<bool, BytecodeError> {
+    match token {
+        SignatureToken::Reference(inner) | SignatureToken::MutableReference(inner) => Ok(datatype(
+            module, inner,
+        )?
+            == Some((SUI_FRAMEWORK_ADDRESS, "tx_context", "TxContext"))),
+        _ => Ok(false),
+    }
+}
+
+fn is_receiving(module: &CompiledModule, token: &SignatureToken) -> Result<bool, BytecodeError> {
+    Ok(datatype(module, token)? == Some((SUI_FRAMEWORK_ADDRESS, "transfer", "Receiving")))
+}
+
+fn is_pure(
+    module: &CompiledModule,
+    type_parameters: &[u8],
+    token: &SignatureToken,
+) -> Result<bool, BytecodeError> {
+    Ok(match token {
+        SignatureToken::Bool
+        | SignatureToken::U8
+        | SignatureToken::U16
+        | SignatureToken::U32
+        | SignatureToken::U64
+        | SignatureToken::U128
+        | SignatureToken::U256
+        | SignatureToken::Address => true,
+        SignatureToken::Signer
+        | SignatureToken::Reference(_)
+        | SignatureToken::MutableReference(_) => false,
+        SignatureToken::TypeParameter(index) => {
+            type_parameter(type_parameters, *index)? & Ability::Key.bit() == 0
+        }
+        SignatureToken::Vector(element) => is_pure(module, type_parameters, element)?,
+        SignatureToken::Datatype(_) => {
+            let datatype = datatype(module, token)?;
+            [
+                (MOVE_STDLIB_ADDRESS, "string", "String"),
+                (MOVE_STDLIB_ADDRESS, "ascii", "String"),
+                (SUI_FRAMEWORK_ADDRESS, "object", "ID"),
+            ]
+            .into_iter()
+            .any(|pure| datatype == Some(pure))
+        }
+        SignatureToken::DatatypeInstantiation(_, type_arguments) => {
+            datatype(module, token)? == Some((MOVE_STDLIB_ADDRESS, "option", "Option"))
+                && type_arguments.len() == 1
+                && is_pure(module, type_parameters, &type_arguments[0])?
+        }
+    })
+}
+
+fn is_object(
+    module: &CompiledModule,
+    type_parameters: &[u8],
+    token: &SignatureToken,
+) -> Result<bool, BytecodeError> {
+    let abilities = match token {
+        SignatureToken::TypeParameter(index) => type_parameter(type_parameters, *index)?,
+        SignatureToken::Datatype(handle) | SignatureToken::DatatypeInstantiation(handle, _) => {
+            module.datatype_handle(*handle)?.abilities
+        }
+        _ => return Ok(false),
+    };
+    Ok(abilities & Ability::Key.bit() != 0)
+}
+
+fn is_object_vector(
+    module: &CompiledModule,
+    type_parameters: &[u8],
+    token: &SignatureToken,
+) -> Result<bool, BytecodeError> {
+    match token {
+        SignatureToken::Vector(element) => is_object(module, type_parameters, element),
+        _ => Ok(false),
+    }
+}
+
+fn type_parameter(type_parameters: &[u8], index: u16) -> Result<u8, BytecodeError> {
+    type_parameters
+        .get(index as usize)
+        .copied()
+        .ok_or(BytecodeError::IndexOutOfBounds)
+}
+
+/// The Move syntax of the type of `token`.
+fn type_name(module: &CompiledModule, token: &SignatureToken) -> Result<String, BytecodeError> {
+    Ok(match token {
+        SignatureToken::Bool => "bool".to_owned(),
+        SignatureToken::U8 => "u8".to_owned(),
+        SignatureToken::U16 => "u16".to_owned(),
+        SignatureToken::U32 => "u32".to_owned(),
+        SignatureToken::U64 => "u64".to_owned(),
+        SignatureToken::U128 => "u128".to_owned(),
+        SignatureToken::U256 => "u256".to_owned(),
+        SignatureToken::Address => "address".to_owned(),
+        SignatureToken::Signer => "signer".to_owned(),
+        SignatureToken::Vector(element) => format!("vector<{}>", type_name(module, element)?),
+        SignatureToken::Reference(inner) => format!("&{}", type_name(module, inner)?),
+        SignatureToken::MutableReference(inner) => format!("&mut {}", type_name(module, inner)?),
+        SignatureToken::TypeParameter(index) => format!("T{index}"),
+        SignatureToken::Datatype(_) | SignatureToken::DatatypeInstantiation(_, _) => {
+            let (address, module_name, name) =
+                datatype(module, token)?.expect("token is a datatype");
+            let path = format!("{address}::{module_name}::{name}");
+            match token {
+                SignatureToken::DatatypeInstantiation(_, type_arguments) => {
+                    let type_arguments = type_arguments
+                        .iter()
+                        .map(|token| type_name(module, token))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    format!("{path}<{}>", type_arguments.join(", "))
+                }
+                _ => path,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_modules;
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn shop() -> ModuleAbi {
+        ModuleAbi::new(&CompiledModule::deserialize(&test_modules::shop()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn callable_functions() {
+        let abi = shop();
+        assert_eq!(abi.name.as_str(), "shop");
+        let names = abi
+            .functions
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["buy", "price"]);
+    }
+
+    #[test]
+    fn parameters() {
+        let item = format!("{}::shop::Item", Address::from_static_hex("0x42"));
+        let abi = shop();
+
+        let buy = &abi.functions[0];
+        assert!(buy.is_entry);
+        assert_eq!(
+            buy.type_parameters,
+            [TypeParameterAbi {
+                constraints: vec![Ability::Store, Ability::Key]
+            }]
+        );
+        let parameters = buy
+            .parameters
+            .iter()
+            .map(|parameter| (parameter.type_.clone(), parameter.kind, parameter.reference))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            parameters,
+            [
+                (
+                    format!("&mut {item}"),
+                    ParameterKind::Object,
+                    Some(Reference::Mutable)
+                ),
+                ("T0".to_owned(), ParameterKind::Object, None),
+                (
+                    format!("vector<{MOVE_STDLIB_ADDRESS}::string::String>"),
+                    ParameterKind::Pure,
+                    None
+                ),
+                (
+                    format!("{MOVE_STDLIB_ADDRESS}::option::Option<u64>"),
+                    ParameterKind::Pure,
+                    None
+                ),
+                (format!("vector<{item}>"), ParameterKind::ObjectVector, None),
+            ]
+        );
+        assert!(buy.returns.is_empty());
+
+        let price = &abi.functions[1];
+        assert!(!price.is_entry);
+        assert_eq!(price.parameters[0].type_, format!("&{item}"));
+        assert_eq!(price.parameters[0].kind, ParameterKind::Object);
+        assert_eq!(price.parameters[0].reference, Some(Reference::Immutable));
+        assert_eq!(price.returns, ["u64"]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json() {
+        let abi = serde_json::to_value(&shop()).unwrap();
+        assert_eq!(
+            abi["functions"][0]["type_parameters"][0]["constraints"],
+            serde_json::json!(["store", "key"])
+        );
+        assert_eq!(
+            abi["functions"][0]["parameters"][0]["reference"],
+            serde_json::json!("mutable")
+        );
+        assert_eq!(
+            abi["functions"][0]["parameters"][1],
+            serde_json::json!({ "type": "T0", "kind": "object" })
+        );
+    }
+}
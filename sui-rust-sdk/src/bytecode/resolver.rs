@@ -0,0 +1,457 @@
+use std::collections::BTreeMap;
+
+use super::BytecodeError;
+use super::CompiledModule;
+use super::FieldDefinition;
+use super::SignatureToken;
+use crate::types::Address;
+use crate::types::Identifier;
+use crate::types::MoveEnumLayout;
+use crate::types::MoveFieldLayout;
+use crate::types::MovePackage;
+use crate::types::MoveStructLayout;
+use crate::types::MoveTypeLayout;
+use crate::types::MoveVariantLayout;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// The maximum nesting of a layout, well above that of any type which can be published.
+const MAX_DEPTH: usize = 128;
+
+/// Derives the layouts of the types defined by a set of modules, without any RPC.
+///
+/// Modules are found by their address, the original id of their package, while type tags name
+/// types by the id of the package which first defined them, which differ for types added by an
+/// upgrade. Packages added with [`add_package`](Self::add_package) record where each of their
+/// types was defined, so that both are resolved, and layouts are tagged with the defining ids
+/// like the types of objects on chain.
+#[derive(Clone, Debug, Default)]
+pub struct LayoutResolver {
+    modules: BTreeMap<(Address, Identifier), CompiledModule>,
+    /// The original id of the package of each type, by its defining id, module and name.
+    original_ids: BTreeMap<(Address, Identifier, Identifier), Address>,
+    /// The defining id of each type, by the original id of its package, its module and name.
+    defining_ids: BTreeMap<(Address, Identifier, Identifier), Address>,
+}
+
+impl LayoutResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a module, replacing any module of the same address and name.
+    pub fn add_module(&mut self, module: CompiledModule) {
+        self.modules
+            .insert((module.address(), module.name().clone()), module);
+    }
+
+    /// Add the modules of a package, and where its types were defined.
+    ///
+    /// The modules of a package replace those of previous versions of the package, so that the
+    /// latest version of a package is to be added last.
+    pub fn add_package(&mut self, package: &MovePackage) -> Result<(), BytecodeError> {
+        let modules = package
+            .modules()
+            .values()
+            .map(|bytes| CompiledModule::deserialize(bytes))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(original_id) = modules.first().map(CompiledModule::address) else {
+            return Ok(());
+        };
+        for origin in package.type_origin_table() {
+            let defining_id = Address::from(origin.package);
+            let key = (origin.module_name.clone(), origin.struct_name.clone());
+            self.original_ids
+                .insert((defining_id, key.0.clone(), key.1.clone()), original_id);
+            self.defining_ids
+                .insert((original_id, key.0, key.1), defining_id);
+        }
+        for module in modules {
+            self.add_module(module);
+        }
+        Ok(())
+    }
+
+    /// The layout of `type_`, instantiating the type parameters of the datatypes it names.
+    pub fn type_layout(&self, type_: &TypeTag) -> Result<MoveTypeLayout, LayoutError> {
+        self.layout(type_, 0)
+    }
+
+    pub fn struct_layout(&self, struct_tag: &StructTag) -> Result<MoveStructLayout, LayoutError> {
+        match self.datatype_layout(struct_tag, 0)? {
+            MoveTypeLayout::Struct(layout) => Ok(*layout),
+            _ => Err(LayoutError::NotAStruct(struct_tag.clone())),
+        }
+    }
+
+    fn layout(&self, type_: &TypeTag, depth: usize) -> Result<MoveTypeLayout, LayoutError> {
+        if depth > MAX_DEPTH {
+            return Err(LayoutError::TooDeep);
+        }
+        Ok(match type_ {
+            TypeTag::Bool => MoveTypeLayout::Bool,
+            TypeTag::U8 => MoveTypeLayout::U8,
+            TypeTag::U16 => MoveTypeLayout::U16,
+            TypeTag::U32 => MoveTypeLayout::U32,
+            TypeTag::U64 => MoveTypeLayout::U64,
+            TypeTag::U128 => MoveTypeLayout::U128,
+            TypeTag::U256 => MoveTypeLayout::U256,
+            TypeTag::Address => MoveTypeLayout::Address,
+            TypeTag::Signer => return Err(LayoutError::Signer),
+            TypeTag::Vector(element) => {
+                MoveTypeLayout::Vector(Box::new(self.layout(element, depth + 1)?))
+            }
+            TypeTag::Struct(struct_tag) => self.datatype_layout(struct_tag, depth)?,
+        })
+    }
+
+    fn datatype_layout(
+        &self,
+        struct_tag: &StructTag,
+        depth: usize,
+    ) -> Result<MoveTypeLayout, LayoutError> {
+        let original_id = self
+            .original_ids
+            .get(&(
+                struct_tag.address,
+                struct_tag.module.clone(),
+                struct_tag.name.clone(),
+            ))
+            .copied()
+            .unwrap_or(struct_tag.address);
+        let module = self
+            .modules
+            .get(&(original_id, struct_tag.module.clone()))
+            .ok_or_else(|| LayoutError::ModuleNotFound {
+                address: original_id,
+                module: struct_tag.module.clone(),
+            })?;
+
+        let is_named = |handle: u16| -> Result<bool, BytecodeError> {
+            let handle = module.datatype_handle(handle)?;
+            Ok(handle.module == module.self_handle
+                && *module.identifier(handle.name)? == struct_tag.name)
+        };
+        let check_arity = |handle: u16| -> Result<(), LayoutError> {
+            let expected = module.datatype_handle(handle)?.type_parameters;
+            if expected == struct_tag.type_params.len() {
+                Ok(())
+            } else {
+                Err(LayoutError::TypeArgumentMismatch {
+                    struct_tag: struct_tag.clone(),
+                    expected,
+                })
+            }
+        };
+
+        for definition in &module.struct_defs {
+            if !is_named(definition.handle)? {
+                continue;
+            }
+            check_arity(definition.handle)?;
+            let fields = definition
+                .fields
+                .as_ref()
+                .ok_or_else(|| LayoutError::Native(struct_tag.clone()))?;
+            return Ok(MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+                type_: self.defining_tag(struct_tag),
+                fields: self.fields(module, fields, &struct_tag.type_params, depth)?,
+            })));
+        }
+
+        for definition in &module.enum_defs {
+            if !is_named(definition.handle)? {
+                continue;
+            }
+            check_arity(definition.handle)?;
+            let variants = definition
+                .variants
+                .iter()
+                .enumerate()
+                .map(|(tag, variant)| {
+                    Ok(MoveVariantLayout {
+                        name: module.identifier(variant.name)?.clone(),
+                        tag: tag as u16,
+                        fields: self.fields(
+                            module,
+                            &variant.fields,
+                            &struct_tag.type_params,
+                            depth,
+                        )?,
+                    })
+                })
+                .collect::<Result<_, LayoutError>>()?;
+            return Ok(MoveTypeLayout::Enum(Box::new(MoveEnumLayout {
+                type_: self.defining_tag(struct_tag),
+                variants,
+            })));
+        }
+
+        Err(LayoutError::DatatypeNotFound(struct_tag.clone()))
+    }
+
+    fn fields(
+        &self,
+        module: &CompiledModule,
+        fields: &[FieldDefinition],
+        type_arguments: &[TypeTag],
+        depth: usize,
+    ) -> Result<Vec<MoveFieldLayout>, LayoutError> {
+        fields
+            .iter()
+            .map(|field| {
+                let type_ = self.type_tag(module, &field.type_, type_arguments)?;
+                Ok(MoveFieldLayout {
+                    name: module.identifier(field.name)?.clone(),
+                    layout: self.layout(&type_, depth + 1)?,
+                })
+            })
+            .collect()
+    }
+
+    /// The type of `token` in `module`, with its type parameters set to `type_arguments`.
+    fn type_tag(
+        &self,
+        module: &CompiledModule,
+        token: &SignatureToken,
+        type_arguments: &[TypeTag],
+    ) -> Result<TypeTag, LayoutError> {
+        Ok(match token {
+            SignatureToken::Bool => TypeTag::Bool,
+            SignatureToken::U8 => TypeTag::U8,
+            SignatureToken::U16 => TypeTag::U16,
+            SignatureToken::U32 => TypeTag::U32,
+            SignatureToken::U64 => TypeTag::U64,
+            SignatureToken::U128 => TypeTag::U128,
+            SignatureToken::U256 => TypeTag::U256,
+            SignatureToken::Address => TypeTag::Address,
+            SignatureToken::Signer => TypeTag::Signer,
+            SignatureToken::Vector(element) => {
+                TypeTag::Vector(Box::new(self.type_tag(module, element, type_arguments)?))
+            }
+            SignatureToken::Datatype(handle) => {
+                TypeTag::Struct(Box::new(self.datatype_tag(module, *handle, vec![])?))
+            }
+            SignatureToken::DatatypeInstantiation(handle, tokens) => {
+                let type_params = tokens
+                    .iter()
+                    .map(|token| self.type_tag(module, token, type_arguments))
+                    .collect::<Result<_, _>>()?;
+                TypeTag::Struct(Box::new(self.datatype_tag(module, *handle, type_params)?))
+            }
+            SignatureToken::TypeParameter(index) => type_arguments
+                .get(*index as usize)
+                .cloned()
+                .ok_or(BytecodeError::IndexOutOfBounds)?,
+            // Fields can't hold references
+            SignatureToken::Reference(_) | SignatureToken::MutableReference(_) => {
+                return Err(BytecodeError::Malformed("reference in a field").into())
+            }
+        })
+    }
+
+    fn datatype_tag(
+        &self,
+        module: &CompiledModule,
+        handle: u16,
+        type_params: Vec<TypeTag>,
+    ) -> Result<StructTag, LayoutError> {
+        let handle = module.datatype_handle(handle)?;
+        let (address, module_name) = module.handle(handle.module)?;
+        Ok(self.defining_tag(&StructTag {
+            address,
+            module: module_name.clone(),
+            name: module.identifier(handle.name)?.clone(),
+            type_params,
+        }))
+    }
+
+    /// `struct_tag`, with the address of its package replaced by the id of the package which
+    /// defined it, if known.
+    fn defining_tag(&self, struct_tag: &StructTag) -> StructTag {
+        let address = self
+            .defining_ids
+            .get(&(
+                struct_tag.address,
+                struct_tag.module.clone(),
+                struct_tag.name.clone(),
+            ))
+            .copied()
+            .unwrap_or(struct_tag.address);
+        StructTag {
+            address,
+            ..struct_tag.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LayoutError {
+    ModuleNotFound {
+        address: Address,
+        module: Identifier,
+    },
+    /// The module defines no struct or enum of the name.
+    DatatypeNotFound(StructTag),
+    NotAStruct(StructTag),
+    TypeArgumentMismatch {
+        struct_tag: StructTag,
+        expected: usize,
+    },
+    /// Native structs have no fields to lay out.
+    Native(StructTag),
+    /// Signers only exist during execution.
+    Signer,
+    TooDeep,
+    Bytecode(BytecodeError),
+}
+
+impl std::fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::ModuleNotFound { address, module } => {
+                write!(f, "module {address}::{module} not found")
+            }
+            Self::DatatypeNotFound(struct_tag) => write!(f, "type {struct_tag} not found"),
+            Self::NotAStruct(struct_tag) => write!(f, "{struct_tag} is not a struct"),
+            Self::TypeArgumentMismatch {
+                struct_tag,
+                expected,
+            } => write!(f, "{struct_tag} expects {expected} type arguments"),
+            Self::Native(struct_tag) => write!(f, "{struct_tag} is native"),
+            Self::Signer => write!(f, "signers have no layout"),
+            Self::TooDeep => write!(f, "type nested too deeply"),
+            Self::Bytecode(e) => write!(f, "invalid module: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Bytecode(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<BytecodeError> for LayoutError {
+    fn from(e: BytecodeError) -> Self {
+        Self::Bytecode(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test_modules;
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn resolver() -> LayoutResolver {
+        let mut resolver = LayoutResolver::new();
+        for bytes in [test_modules::string(), test_modules::example()] {
+            resolver.add_module(CompiledModule::deserialize(&bytes).unwrap());
+        }
+        resolver
+    }
+
+    #[test]
+    fn generic_struct() {
+        let layout = resolver()
+            .struct_layout(&"0x42::example::Pair<u16>".parse().unwrap())
+            .unwrap();
+        assert_eq!(layout.type_, "0x42::example::Pair<u16>".parse().unwrap());
+        assert_eq!(layout.fields[0].name.as_str(), "first");
+        assert_eq!(layout.fields[0].layout, MoveTypeLayout::U16);
+        assert_eq!(layout.fields[1].layout, MoveTypeLayout::U64);
+
+        assert_eq!(
+            resolver().struct_layout(&"0x42::example::Pair".parse().unwrap()),
+            Err(LayoutError::TypeArgumentMismatch {
+                struct_tag: "0x42::example::Pair".parse().unwrap(),
+                expected: 1,
+            })
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn decode() {
+        let layout = resolver()
+            .type_layout(&"0x42::example::Pair<bool>".parse().unwrap())
+            .unwrap();
+        assert_eq!(
+            layout.to_json(&[1, 5, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+            serde_json::json!({ "first": true, "second": "5" })
+        );
+    }
+
+    #[test]
+    fn nested_types() {
+        let layout = resolver()
+            .type_layout(&"vector<0x42::example::Record>".parse().unwrap())
+            .unwrap();
+        let MoveTypeLayout::Vector(record) = layout else {
+            panic!("not a vector");
+        };
+        let MoveTypeLayout::Struct(record) = *record else {
+            panic!("not a struct");
+        };
+
+        let MoveTypeLayout::Struct(pair) = &record.fields[0].layout else {
+            panic!("not a struct");
+        };
+        assert_eq!(pair.type_, "0x42::example::Pair<bool>".parse().unwrap());
+        assert_eq!(pair.fields[0].layout, MoveTypeLayout::Bool);
+
+        let MoveTypeLayout::Vector(name) = &record.fields[1].layout else {
+            panic!("not a vector");
+        };
+        let MoveTypeLayout::Struct(name) = name.as_ref() else {
+            panic!("not a struct");
+        };
+        assert_eq!(name.type_, "0x1::string::String".parse().unwrap());
+
+        let MoveTypeLayout::Enum(status) = &record.fields[2].layout else {
+            panic!("not an enum");
+        };
+        assert_eq!(status.variants.len(), 2);
+        assert_eq!(status.variant(1).unwrap().name.as_str(), "Closed");
+        assert_eq!(
+            status.variant(1).unwrap().fields[0].layout,
+            MoveTypeLayout::U64
+        );
+    }
+
+    #[test]
+    fn missing_types() {
+        assert_eq!(
+            resolver().type_layout(&"0x42::other::Thing".parse().unwrap()),
+            Err(LayoutError::ModuleNotFound {
+                address: Address::from_static_hex("0x42"),
+                module: Identifier::new("other").unwrap(),
+            })
+        );
+        assert_eq!(
+            resolver().type_layout(&"0x42::example::Thing".parse().unwrap()),
+            Err(LayoutError::DatatypeNotFound(
+                "0x42::example::Thing".parse().unwrap()
+            ))
+        );
+        assert_eq!(
+            resolver().struct_layout(&"0x42::example::Status".parse().unwrap()),
+            Err(LayoutError::NotAStruct(
+                "0x42::example::Status".parse().unwrap()
+            ))
+        );
+
+        let mut resolver = LayoutResolver::new();
+        resolver.add_module(CompiledModule::deserialize(&test_modules::example()).unwrap());
+        assert!(matches!(
+            resolver.type_layout(&"0x42::example::Record".parse().unwrap()),
+            Err(LayoutError::ModuleNotFound { .. })
+        ));
+    }
+}
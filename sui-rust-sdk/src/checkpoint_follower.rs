@@ -0,0 +1,464 @@
+//! Following the checkpoints of a network across epochs, verifying each of them.
+//!
+//! A [`CheckpointFollower`] wraps a [`Stream`] of [`CheckpointData`], e.g. a checkpoint
+//! subscription, and yields only the checkpoints it verified: certified by the committee of their
+//! epoch, following the previous checkpoint without gaps, and with contents and transactions
+//! matching the summary. It rotates its [`LightClientState`] to the next committee at the end of
+//! each epoch, and saves the state to a [`WatermarkStore`] after each checkpoint, so that a
+//! follower resumed from the store picks up right after the last verified checkpoint:
+//!
+//! ```ignore
+//! let follower = CheckpointFollower::resume(store, genesis_committee)?;
+//! let checkpoints = client.subscribe_checkpoints(follower.next_sequence_number());
+//! let mut follower = follower.follow(checkpoints);
+//! while let Some(checkpoint) = follower.try_next().await? {
+//!     index(checkpoint);
+//! }
+//! ```
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures_core::Stream;
+
+use crate::light_client::LightClientState;
+use crate::types::CheckpointData;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::CheckpointVerificationError;
+use crate::types::ValidatorCommittee;
+
+/// Where a [`CheckpointFollower`] persists its state, i.e. its committee and the watermark of the
+/// last checkpoint it verified.
+pub trait WatermarkStore {
+    type Error;
+
+    /// The state last saved, if any.
+    fn load(&mut self) -> Result<Option<LightClientState>, Self::Error>;
+
+    /// Save `state`, called after each verified checkpoint.
+    fn save(&mut self, state: &LightClientState) -> Result<(), Self::Error>;
+}
+
+/// A [`WatermarkStore`] keeping the state in memory, e.g. for tests or short-lived followers.
+#[derive(Clone, Debug, Default)]
+pub struct InMemoryWatermarkStore {
+    state: Option<LightClientState>,
+}
+
+impl InMemoryWatermarkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn state(&self) -> Option<&LightClientState> {
+        self.state.as_ref()
+    }
+}
+
+impl WatermarkStore for InMemoryWatermarkStore {
+    type Error = std::convert::Infallible;
+
+    fn load(&mut self) -> Result<Option<LightClientState>, Self::Error> {
+        Ok(self.state.clone())
+    }
+
+    fn save(&mut self, state: &LightClientState) -> Result<(), Self::Error> {
+        self.state = Some(state.clone());
+        Ok(())
+    }
+}
+
+/// A follower of the checkpoints of a network, verifying them from epoch to epoch.
+///
+/// The follower is created from a [`WatermarkStore`] and then given the stream of checkpoints to
+/// follow with [`CheckpointFollower::follow`], which should start at
+/// [`CheckpointFollower::next_sequence_number`]. Checkpoints at or below the watermark, e.g.
+/// replayed by a subscription after a reconnection, are skipped.
+///
+/// The follower stops after the first error, whether of the stream, of the store or of the
+/// verification of a checkpoint. The store holds the state of the last checkpoint verified, so a
+/// follower resumed from it continues where this one stopped.
+pub struct CheckpointFollower<W, S = ()> {
+    state: LightClientState,
+    store: W,
+    checkpoints: S,
+    done: bool,
+}
+
+impl<W: WatermarkStore> CheckpointFollower<W> {
+    /// Resume following from the state saved in `store`, or start trusting `committee` if the
+    /// store is empty.
+    pub fn resume(mut store: W, committee: ValidatorCommittee) -> Result<Self, W::Error> {
+        let state = store
+            .load()?
+            .unwrap_or_else(|| LightClientState::new(committee));
+        Ok(Self::new(state, store))
+    }
+
+    /// Follow from `state`, saving it to `store` as checkpoints get verified.
+    pub fn new(state: LightClientState, store: W) -> Self {
+        Self {
+            state,
+            store,
+            checkpoints: (),
+            done: false,
+        }
+    }
+
+    /// Follow the checkpoints of `checkpoints`.
+    pub fn follow<S>(self, checkpoints: S) -> CheckpointFollower<W, S> {
+        CheckpointFollower {
+            state: self.state,
+            store: self.store,
+            checkpoints,
+            done: self.done,
+        }
+    }
+}
+
+impl<W, S> CheckpointFollower<W, S> {
+    /// The state of the follower, as of the last checkpoint verified.
+    pub fn state(&self) -> &LightClientState {
+        &self.state
+    }
+
+    pub fn store(&self) -> &W {
+        &self.store
+    }
+
+    /// The sequence number of the next checkpoint to follow, or `None` if no checkpoint was
+    /// verified yet, in which case any checkpoint of the current epoch can be followed from.
+    pub fn next_sequence_number(&self) -> Option<CheckpointSequenceNumber> {
+        self.state
+            .latest_checkpoint()
+            .map(|latest| latest.sequence_number + 1)
+    }
+
+    /// Stop following, returning the state and the store.
+    pub fn into_parts(self) -> (LightClientState, W) {
+        (self.state, self.store)
+    }
+}
+
+impl<W: WatermarkStore, S> CheckpointFollower<W, S> {
+    /// Verify `checkpoint` and save the resulting state.
+    ///
+    /// Returns `false` if the checkpoint was already verified, and is to be skipped.
+    fn verify<E>(
+        &mut self,
+        checkpoint: &CheckpointData,
+    ) -> Result<bool, FollowerError<E, W::Error>> {
+        let summary = &checkpoint.checkpoint_summary.checkpoint;
+        if let Some(latest) = self.state.latest_checkpoint() {
+            if summary.sequence_number <= latest.sequence_number {
+                return Ok(false);
+            }
+            if summary.sequence_number != latest.sequence_number + 1 {
+                return Err(FollowerError::Gap {
+                    expected: latest.sequence_number + 1,
+                    actual: summary.sequence_number,
+                });
+            }
+            if summary.previous_digest != Some(latest.digest) {
+                return Err(FollowerError::PreviousDigestMismatch {
+                    sequence_number: summary.sequence_number,
+                });
+            }
+        }
+
+        // The contents are only hashed, so they are checked before the signature of the summary
+        summary
+            .verify_contents(&checkpoint.checkpoint_contents)
+            .map_err(FollowerError::Verification)?;
+        checkpoint
+            .verify_transactions()
+            .map_err(FollowerError::Verification)?;
+        self.state
+            .update(&checkpoint.checkpoint_summary)
+            .map_err(FollowerError::Verification)?;
+
+        self.store.save(&self.state).map_err(FollowerError::Store)?;
+        Ok(true)
+    }
+}
+
+impl<W, S> Unpin for CheckpointFollower<W, S> {}
+
+impl<W, S, E> Stream for CheckpointFollower<W, S>
+where
+    W: WatermarkStore,
+    S: Stream<Item = Result<CheckpointData, E>> + Unpin,
+{
+    type Item = Result<CheckpointData, FollowerError<E, W::Error>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let checkpoint = match Pin::new(&mut this.checkpoints).poll_next(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => {
+                    this.done = true;
+                    return Poll::Ready(None);
+                }
+                Poll::Ready(Some(Err(e))) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(FollowerError::Stream(e))));
+                }
+                Poll::Ready(Some(Ok(checkpoint))) => checkpoint,
+            };
+
+            match this.verify(&checkpoint) {
+                Ok(true) => return Poll::Ready(Some(Ok(checkpoint))),
+                Ok(false) => continue,
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FollowerError<E, W> {
+    /// The stream of checkpoints failed.
+    Stream(E),
+    /// The state couldn't be saved.
+    Store(W),
+    /// A checkpoint doesn't follow the last one verified.
+    Gap {
+        expected: CheckpointSequenceNumber,
+        actual: CheckpointSequenceNumber,
+    },
+    /// A checkpoint doesn't commit to the last one verified as its predecessor.
+    PreviousDigestMismatch {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    Verification(CheckpointVerificationError),
+}
+
+impl<E: std::fmt::Display, W: std::fmt::Display> std::fmt::Display for FollowerError<E, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Stream(e) => write!(f, "checkpoint stream failed: {e}"),
+            Self::Store(e) => write!(f, "failed to save the watermark: {e}"),
+            Self::Gap { expected, actual } => {
+                write!(f, "expected checkpoint {expected}, got {actual}")
+            }
+            Self::PreviousDigestMismatch { sequence_number } => write!(
+                f,
+                "checkpoint {sequence_number} doesn't follow the last verified checkpoint"
+            ),
+            Self::Verification(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E, W> std::error::Error for FollowerError<E, W>
+where
+    E: std::fmt::Debug + std::fmt::Display,
+    W: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::test_committee;
+    use crate::types::CheckpointContents;
+    use crate::types::CheckpointDigest;
+    use crate::types::CheckpointSummary;
+    use crate::types::EndOfEpochData;
+    use crate::types::EpochId;
+    use crate::types::GasCostSummary;
+    use crate::types::SignedCheckpointSummary;
+    use blst::min_sig::SecretKey;
+    use std::task::Waker;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn secret_keys(seed: u8) -> Vec<SecretKey> {
+        test_committee::secret_keys(seed, 4)
+    }
+
+    fn committee(epoch: u64, keys: &[SecretKey]) -> ValidatorCommittee {
+        test_committee::committee(EpochId::new(epoch), keys)
+    }
+
+    /// An empty checkpoint following `previous`, signed by the first three of `keys`.
+    fn checkpoint(
+        keys: &[SecretKey],
+        epoch: u64,
+        sequence_number: CheckpointSequenceNumber,
+        previous: Option<CheckpointDigest>,
+        next_committee: Option<&[SecretKey]>,
+    ) -> CheckpointData {
+        let contents = CheckpointContents::new(vec![]);
+        let checkpoint = CheckpointSummary {
+            epoch: EpochId::new(epoch),
+            sequence_number,
+            network_total_transactions: 0,
+            content_digest: contents.digest(),
+            previous_digest: previous,
+            epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+            timestamp_ms: 0,
+            checkpoint_commitments: vec![],
+            end_of_epoch_data: next_committee.map(|keys| EndOfEpochData {
+                next_epoch_committee: test_committee::members(keys),
+                next_epoch_protocol_version: 1,
+                epoch_commitments: vec![],
+            }),
+            version_specific_data: vec![],
+        };
+
+        let mut message = vec![2, 0, 0];
+        message.extend(bcs::to_bytes(&checkpoint).unwrap());
+        message.extend(epoch.to_le_bytes());
+
+        CheckpointData {
+            checkpoint_summary: SignedCheckpointSummary {
+                signature: test_committee::sign_aggregated(
+                    keys,
+                    &[0, 1, 2],
+                    checkpoint.epoch,
+                    &message,
+                ),
+                checkpoint,
+            },
+            checkpoint_contents: contents,
+            transactions: vec![],
+        }
+    }
+
+    /// Checkpoints 0 to 3, crossing from epoch 0 to epoch 1 after checkpoint 1.
+    fn chain(epoch_0: &[SecretKey], epoch_1: &[SecretKey]) -> Vec<CheckpointData> {
+        let mut checkpoints: Vec<CheckpointData> = vec![];
+        for sequence_number in 0..4 {
+            let previous = checkpoints
+                .last()
+                .map(|checkpoint| checkpoint.checkpoint_summary.checkpoint.digest());
+            checkpoints.push(match sequence_number {
+                0 => checkpoint(epoch_0, 0, 0, previous, None),
+                1 => checkpoint(epoch_0, 0, 1, previous, Some(epoch_1)),
+                _ => checkpoint(epoch_1, 1, sequence_number, previous, None),
+            });
+        }
+        checkpoints
+    }
+
+    struct Checkpoints(std::vec::IntoIter<Result<CheckpointData, ()>>);
+
+    impl Stream for Checkpoints {
+        type Item = Result<CheckpointData, ()>;
+
+        fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Ready(self.0.next())
+        }
+    }
+
+    fn checkpoints(checkpoints: &[CheckpointData]) -> Checkpoints {
+        Checkpoints(
+            checkpoints
+                .iter()
+                .cloned()
+                .map(Ok)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )
+    }
+
+    /// Poll `stream` to completion, the checkpoints of the tests being ready immediately.
+    fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = vec![];
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return items,
+                Poll::Pending => panic!("checkpoint not ready"),
+            }
+        }
+    }
+
+    fn sequence_numbers<E>(
+        items: Vec<Result<CheckpointData, E>>,
+    ) -> Vec<Result<CheckpointSequenceNumber, E>> {
+        items
+            .into_iter()
+            .map(|item| {
+                item.map(|checkpoint| checkpoint.checkpoint_summary.checkpoint.sequence_number)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn follow_across_epochs() {
+        let (epoch_0, epoch_1) = (secret_keys(0), secret_keys(10));
+        let chain = chain(&epoch_0, &epoch_1);
+
+        let follower =
+            CheckpointFollower::resume(InMemoryWatermarkStore::new(), committee(0, &epoch_0))
+                .unwrap();
+        assert_eq!(follower.next_sequence_number(), None);
+        let mut follower = follower.follow(checkpoints(&chain[..3]));
+        assert_eq!(
+            sequence_numbers(collect(&mut follower)),
+            [Ok(0), Ok(1), Ok(2)]
+        );
+        assert_eq!(follower.state().epoch(), EpochId::new(1));
+        assert_eq!(follower.next_sequence_number(), Some(3));
+
+        // Resume from the store, the subscription replaying the last checkpoint
+        let (_, store) = follower.into_parts();
+        assert_eq!(store.state().unwrap().epoch(), EpochId::new(1));
+        let follower = CheckpointFollower::resume(store, committee(0, &epoch_0)).unwrap();
+        assert_eq!(follower.next_sequence_number(), Some(3));
+        let follower = follower.follow(checkpoints(&chain[2..]));
+        assert_eq!(sequence_numbers(collect(follower)), [Ok(3)]);
+    }
+
+    #[test]
+    fn reject_discontinuities() {
+        let (epoch_0, epoch_1) = (secret_keys(0), secret_keys(10));
+        let chain = chain(&epoch_0, &epoch_1);
+        let start = || {
+            let mut state = LightClientState::new(committee(0, &epoch_0));
+            state.update(&chain[0].checkpoint_summary).unwrap();
+            CheckpointFollower::new(state, InMemoryWatermarkStore::new())
+        };
+
+        // Skipping the end of epoch 0 would skip the rotation of the committee
+        assert_eq!(
+            sequence_numbers(collect(start().follow(checkpoints(&chain[2..])))),
+            [Err(FollowerError::Gap {
+                expected: 1,
+                actual: 2
+            })]
+        );
+
+        let fork = checkpoint(&epoch_0, 0, 1, None, None);
+        assert_eq!(
+            sequence_numbers(collect(
+                start().follow(checkpoints(&[fork, chain[2].clone()]))
+            )),
+            [Err(FollowerError::PreviousDigestMismatch {
+                sequence_number: 1
+            })]
+        );
+
+        let mut tampered = chain[1].clone();
+        tampered.checkpoint_summary.checkpoint.timestamp_ms = 1;
+        assert_eq!(
+            sequence_numbers(collect(start().follow(checkpoints(&[tampered])))),
+            [Err(FollowerError::Verification(
+                CheckpointVerificationError::InvalidSignature
+            ))]
+        );
+    }
+}
@@ -0,0 +1,661 @@
+//! Trading on DeepBook, the central limit order book of the `0xdee9` package.
+//!
+//! [`ClobV2`] builds the `clob_v2` calls used by market makers, placing and canceling orders and
+//! moving funds in and out of a pool's custody, for a pool of a given pair of assets. The state of
+//! pools, their open orders and the events of their trades decode from their BCS contents into
+//! [`PoolState`], [`Order`], [`OrderPlaced`], [`OrderCanceled`] and [`OrderFilled`].
+//!
+//! Calls placing orders read the shared `0x2::clock::Clock` object, by immutable reference, and
+//! all calls on an account need its `AccountCap`, created with [`ClobV2::create_account`].
+
+use std::sync::Arc;
+
+use crate::types::well_known::DEEPBOOK_ADDRESS;
+use crate::types::Address;
+use crate::types::Argument;
+use crate::types::Command;
+use crate::types::Event;
+use crate::types::Identifier;
+use crate::types::InputArgument;
+use crate::types::MoveCall;
+use crate::types::Object;
+use crate::types::ObjectData;
+use crate::types::ObjectId;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// The `clob_v2` calls on the pools trading `base_asset` for `quote_asset`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClobV2 {
+    base_asset: TypeTag,
+    quote_asset: TypeTag,
+}
+
+/// How a limit order is matched against the orders already in the book.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Restriction {
+    /// Fill what can be filled, and place the rest in the book.
+    #[default]
+    None,
+    /// Fill what can be filled immediately, and cancel the rest.
+    ImmediateOrCancel,
+    /// Fill the whole order immediately, or abort.
+    FillOrKill,
+    /// Place the whole order in the book, or abort if any of it would be filled immediately.
+    PostOrAbort,
+}
+
+impl Restriction {
+    /// The `u8` encoding of the restriction, as `restriction` argument of `place_limit_order`.
+    pub fn input(self) -> InputArgument {
+        let restriction: u8 = match self {
+            Self::None => 0,
+            Self::ImmediateOrCancel => 1,
+            Self::FillOrKill => 2,
+            Self::PostOrAbort => 3,
+        };
+        InputArgument::Pure {
+            value: vec![restriction],
+        }
+    }
+}
+
+/// The arguments of `place_limit_order`, besides the pool, clock and account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitOrder {
+    /// A `u64` chosen by the client to identify the order in events.
+    pub client_order_id: Argument,
+    /// A `u64` price, in quote asset units per base asset unit scaled by 10^9, and a multiple of
+    /// the pool's tick size.
+    pub price: Argument,
+    /// A `u64` quantity of base asset, a multiple of the pool's lot size.
+    pub quantity: Argument,
+    /// A `u8`, of which only `0`, canceling the oldest of the crossing orders, is supported.
+    pub self_matching_prevention: Argument,
+    /// A `bool`, whether the order buys the base asset.
+    pub is_bid: Argument,
+    /// A `u64` timestamp in milliseconds after which the order expires.
+    pub expire_timestamp: Argument,
+    /// A `u8` [`Restriction`].
+    pub restriction: Argument,
+}
+
+/// The arguments of `place_market_order`, besides the pool, clock and account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MarketOrder {
+    /// A `u64` chosen by the client to identify the order in events.
+    pub client_order_id: Argument,
+    /// A `u64` quantity of base asset, a multiple of the pool's lot size.
+    pub quantity: Argument,
+    /// A `bool`, whether the order buys the base asset.
+    pub is_bid: Argument,
+    /// A `Coin<BaseAsset>` paying for asks, which is returned with the base asset bought by bids.
+    pub base_coin: Argument,
+    /// A `Coin<QuoteAsset>` paying for bids, which is returned with the quote asset bought by
+    /// asks.
+    pub quote_coin: Argument,
+}
+
+impl ClobV2 {
+    /// The id of the DeepBook package.
+    pub const PACKAGE_ID: ObjectId = ObjectId::new(DEEPBOOK_ADDRESS.into_inner());
+
+    pub fn new(base_asset: TypeTag, quote_asset: TypeTag) -> Self {
+        Self {
+            base_asset,
+            quote_asset,
+        }
+    }
+
+    pub fn base_asset(&self) -> &TypeTag {
+        &self.base_asset
+    }
+
+    pub fn quote_asset(&self) -> &TypeTag {
+        &self.quote_asset
+    }
+
+    /// The type of the pools, `0xdee9::clob_v2::Pool<BaseAsset, QuoteAsset>`.
+    pub fn pool_type(&self) -> StructTag {
+        clob_v2_type(
+            "Pool",
+            vec![self.base_asset.clone(), self.quote_asset.clone()],
+        )
+    }
+
+    /// `create_account(&mut TxContext): AccountCap`
+    pub fn create_account() -> Command {
+        call("create_account", vec![], vec![])
+    }
+
+    /// `deposit_base<BaseAsset, QuoteAsset>(&mut Pool, Coin<BaseAsset>, &AccountCap)`
+    pub fn deposit_base(&self, pool: Argument, coin: Argument, account_cap: Argument) -> Command {
+        self.call("deposit_base", vec![pool, coin, account_cap])
+    }
+
+    /// `deposit_quote<BaseAsset, QuoteAsset>(&mut Pool, Coin<QuoteAsset>, &AccountCap)`
+    pub fn deposit_quote(&self, pool: Argument, coin: Argument, account_cap: Argument) -> Command {
+        self.call("deposit_quote", vec![pool, coin, account_cap])
+    }
+
+    /// `withdraw_base<BaseAsset, QuoteAsset>(&mut Pool, u64, &AccountCap, &mut TxContext):
+    /// Coin<BaseAsset>`
+    pub fn withdraw_base(
+        &self,
+        pool: Argument,
+        quantity: Argument,
+        account_cap: Argument,
+    ) -> Command {
+        self.call("withdraw_base", vec![pool, quantity, account_cap])
+    }
+
+    /// `withdraw_quote<BaseAsset, QuoteAsset>(&mut Pool, u64, &AccountCap, &mut TxContext):
+    /// Coin<QuoteAsset>`
+    pub fn withdraw_quote(
+        &self,
+        pool: Argument,
+        quantity: Argument,
+        account_cap: Argument,
+    ) -> Command {
+        self.call("withdraw_quote", vec![pool, quantity, account_cap])
+    }
+
+    /// `place_limit_order<BaseAsset, QuoteAsset>(&mut Pool, u64, u64, u64, u8, bool, u64, u8,
+    /// &Clock, &AccountCap, &mut TxContext): (u64, u64, bool, u64)`, returning the base and quote
+    /// quantities filled, whether the rest was placed in the book, and the order id.
+    pub fn place_limit_order(
+        &self,
+        pool: Argument,
+        order: LimitOrder,
+        clock: Argument,
+        account_cap: Argument,
+    ) -> Command {
+        self.call(
+            "place_limit_order",
+            vec![
+                pool,
+                order.client_order_id,
+                order.price,
+                order.quantity,
+                order.self_matching_prevention,
+                order.is_bid,
+                order.expire_timestamp,
+                order.restriction,
+                clock,
+                account_cap,
+            ],
+        )
+    }
+
+    /// `place_market_order<BaseAsset, QuoteAsset>(&mut Pool, &AccountCap, u64, u64, bool,
+    /// Coin<BaseAsset>, Coin<QuoteAsset>, &Clock, &mut TxContext): (Coin<BaseAsset>,
+    /// Coin<QuoteAsset>)`
+    pub fn place_market_order(
+        &self,
+        pool: Argument,
+        account_cap: Argument,
+        order: MarketOrder,
+        clock: Argument,
+    ) -> Command {
+        self.call(
+            "place_market_order",
+            vec![
+                pool,
+                account_cap,
+                order.client_order_id,
+                order.quantity,
+                order.is_bid,
+                order.base_coin,
+                order.quote_coin,
+                clock,
+            ],
+        )
+    }
+
+    /// `cancel_order<BaseAsset, QuoteAsset>(&mut Pool, u64, &AccountCap)`
+    pub fn cancel_order(
+        &self,
+        pool: Argument,
+        order_id: Argument,
+        account_cap: Argument,
+    ) -> Command {
+        self.call("cancel_order", vec![pool, order_id, account_cap])
+    }
+
+    /// `batch_cancel_order<BaseAsset, QuoteAsset>(&mut Pool, vector<u64>, &AccountCap)`
+    pub fn batch_cancel_order(
+        &self,
+        pool: Argument,
+        order_ids: Argument,
+        account_cap: Argument,
+    ) -> Command {
+        self.call("batch_cancel_order", vec![pool, order_ids, account_cap])
+    }
+
+    /// `cancel_all_orders<BaseAsset, QuoteAsset>(&mut Pool, &AccountCap)`
+    pub fn cancel_all_orders(&self, pool: Argument, account_cap: Argument) -> Command {
+        self.call("cancel_all_orders", vec![pool, account_cap])
+    }
+
+    fn call(&self, function: &str, arguments: Vec<Argument>) -> Command {
+        call(
+            function,
+            vec![self.base_asset.clone(), self.quote_asset.clone()],
+            arguments,
+        )
+    }
+}
+
+fn call(function: &str, type_arguments: Vec<TypeTag>, arguments: Vec<Argument>) -> Command {
+    Command::MoveCall(Arc::new(MoveCall {
+        package: ClobV2::PACKAGE_ID,
+        module: Identifier::new("clob_v2").unwrap(),
+        function: Identifier::new(function).unwrap(),
+        type_arguments,
+        arguments,
+    }))
+}
+
+fn clob_v2_type(name: &str, type_params: Vec<TypeTag>) -> StructTag {
+    StructTag {
+        address: DEEPBOOK_ADDRESS,
+        module: Identifier::new("clob_v2").unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params,
+    }
+}
+
+/// The base and quote assets of `type_`, if it is the `clob_v2` type `name`.
+fn clob_v2_assets<'a>(type_: &'a StructTag, name: &str) -> Option<(&'a TypeTag, &'a TypeTag)> {
+    match type_.type_params.as_slice() {
+        [base_asset, quote_asset]
+            if type_.address == DEEPBOOK_ADDRESS
+                && type_.module == *"clob_v2"
+                && type_.name == *name =>
+        {
+            Some((base_asset, quote_asset))
+        }
+        _ => None,
+    }
+}
+
+/// An open order, as stored in the book of a pool.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct Order {
+    pub order_id: u64,
+    pub client_order_id: u64,
+    pub price: u64,
+    /// The quantity of base asset when the order was placed.
+    pub original_quantity: u64,
+    /// The quantity of base asset still open.
+    pub quantity: u64,
+    pub is_bid: bool,
+    /// The owner of the `AccountCap` which placed the order.
+    pub owner: Address,
+    pub expire_timestamp: u64,
+    pub self_matching_prevention: u8,
+}
+
+impl Order {
+    /// Whether `order_id` is the id of a bid, rather than an ask, as its highest bit is unset.
+    pub fn is_bid_order_id(order_id: u64) -> bool {
+        order_id < 1 << 63
+    }
+
+    /// Read an order from the node holding it in the open orders of a price level, a dynamic field
+    /// of type `0x2::dynamic_field::Field<u64, 0x2::linked_table::Node<u64, Order>>`.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field {
+            _id: ObjectId,
+            _name: u64,
+            value: Node,
+        }
+
+        #[derive(serde_derive::Deserialize)]
+        struct Node {
+            _prev: Option<u64>,
+            _next: Option<u64>,
+            value: Order,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let type_ = &move_struct.type_;
+        let is_field = type_.address == Address::TWO
+            && type_.module == *"dynamic_field"
+            && type_.name == *"Field";
+        let is_order_node = matches!(
+            type_.type_params.as_slice(),
+            [TypeTag::U64, TypeTag::Struct(node)]
+                if node.address == Address::TWO
+                    && node.module == *"linked_table"
+                    && node.name == *"Node"
+                    && node.type_params.get(1) == Some(&TypeTag::Struct(Box::new(clob_v2_type("Order", vec![]))))
+        );
+        if !is_field || !is_order_node {
+            return None;
+        }
+        let field: Field = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some(field.value.value)
+    }
+}
+
+/// The parameters and counters of a pool, without its book and custody, which are held in
+/// dynamic fields.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PoolState {
+    pub id: ObjectId,
+    pub base_asset: TypeTag,
+    pub quote_asset: TypeTag,
+    /// The id the next bid placed will get.
+    pub next_bid_order_id: u64,
+    /// The id the next ask placed will get.
+    pub next_ask_order_id: u64,
+    /// The fee charged to takers, scaled by 10^9.
+    pub taker_fee_rate: u64,
+    /// The rebate paid to makers, scaled by 10^9.
+    pub maker_rebate_rate: u64,
+    pub tick_size: u64,
+    pub lot_size: u64,
+    /// The trading fees collected so far, in quote asset.
+    pub quote_asset_trading_fees: u64,
+}
+
+/// The BCS layout of a `0x2::table::Table`, whose entries are dynamic fields.
+#[derive(serde_derive::Deserialize)]
+struct Table {
+    _id: ObjectId,
+    _size: u64,
+}
+
+/// The BCS layout of a `0xdee9::critbit::CritbitTree`.
+#[derive(serde_derive::Deserialize)]
+struct CritbitTree {
+    _root: u64,
+    _internal_nodes: Table,
+    _leaves: Table,
+    _min_leaf: u64,
+    _max_leaf: u64,
+    _next_internal_node_index: u64,
+    _next_leaf_index: u64,
+}
+
+/// The BCS layout of a `0xdee9::custodian_v2::Custodian`.
+#[derive(serde_derive::Deserialize)]
+struct Custodian {
+    _id: ObjectId,
+    _account_balances: Table,
+}
+
+/// The BCS layout of a `0xdee9::clob_v2::Pool`.
+#[derive(serde_derive::Deserialize)]
+struct BinaryPool {
+    id: ObjectId,
+    _bids: CritbitTree,
+    _asks: CritbitTree,
+    next_bid_order_id: u64,
+    next_ask_order_id: u64,
+    _usr_open_orders: Table,
+    taker_fee_rate: u64,
+    maker_rebate_rate: u64,
+    tick_size: u64,
+    lot_size: u64,
+    _base_custodian: Custodian,
+    _quote_custodian: Custodian,
+    _creation_fee: u64,
+    _base_asset_trading_fees: u64,
+    quote_asset_trading_fees: u64,
+}
+
+impl PoolState {
+    /// Read the state of a `0xdee9::clob_v2::Pool<BaseAsset, QuoteAsset>` object.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let (base_asset, quote_asset) = clob_v2_assets(&move_struct.type_, "Pool")?;
+        let pool: BinaryPool = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some(Self {
+            id: pool.id,
+            base_asset: base_asset.clone(),
+            quote_asset: quote_asset.clone(),
+            next_bid_order_id: pool.next_bid_order_id,
+            next_ask_order_id: pool.next_ask_order_id,
+            taker_fee_rate: pool.taker_fee_rate,
+            maker_rebate_rate: pool.maker_rebate_rate,
+            tick_size: pool.tick_size,
+            lot_size: pool.lot_size,
+            quote_asset_trading_fees: pool.quote_asset_trading_fees,
+        })
+    }
+}
+
+/// Emitted when an order is placed in the book, as `OrderPlaced<BaseAsset, QuoteAsset>`.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct OrderPlaced {
+    pub pool_id: ObjectId,
+    pub order_id: u64,
+    pub client_order_id: u64,
+    pub is_bid: bool,
+    pub owner: Address,
+    pub original_quantity: u64,
+    pub base_asset_quantity_placed: u64,
+    pub price: u64,
+    pub expire_timestamp: u64,
+}
+
+/// Emitted when an order in the book is canceled, as `OrderCanceled<BaseAsset, QuoteAsset>`.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct OrderCanceled {
+    pub pool_id: ObjectId,
+    pub order_id: u64,
+    pub client_order_id: u64,
+    pub is_bid: bool,
+    pub owner: Address,
+    pub original_quantity: u64,
+    pub base_asset_quantity_canceled: u64,
+    pub price: u64,
+}
+
+/// Emitted when an order in the book is filled, entirely or partially, by a taker, as
+/// `OrderFilled<BaseAsset, QuoteAsset>`.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct OrderFilled {
+    pub pool_id: ObjectId,
+    /// The id of the maker's order.
+    pub order_id: u64,
+    pub taker_client_order_id: u64,
+    pub maker_client_order_id: u64,
+    /// Whether the maker's order is a bid.
+    pub is_bid: bool,
+    pub taker_address: Address,
+    pub maker_address: Address,
+    pub original_quantity: u64,
+    pub base_asset_quantity_filled: u64,
+    pub base_asset_quantity_remaining: u64,
+    pub price: u64,
+    pub taker_commission: u64,
+    pub maker_rebates: u64,
+}
+
+macro_rules! impl_try_from_event {
+    ($($event:ident),*) => {
+        $(
+            impl $event {
+                #[doc = concat!("Read a `0xdee9::clob_v2::", stringify!($event), "` event.")]
+                pub fn try_from_event(event: &Event) -> Option<Self> {
+                    clob_v2_assets(&event.type_, stringify!($event))?;
+                    bcs::from_bytes(&event.contents).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_try_from_event!(OrderPlaced, OrderCanceled, OrderFilled);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn sui_usdc() -> ClobV2 {
+        ClobV2::new(
+            "0x2::sui::SUI".parse().unwrap(),
+            "0x5d4b302506645c37ff133b98c4b50a5ae14841659738d6d733d59d0d217a93bf::coin::COIN"
+                .parse()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn cancel_order_call() {
+        let clob = sui_usdc();
+        let Command::MoveCall(call) =
+            clob.cancel_order(Argument::Input(0), Argument::Input(1), Argument::Input(2))
+        else {
+            panic!("expected a move call");
+        };
+        assert_eq!(
+            call.package.to_string(),
+            "0x000000000000000000000000000000000000000000000000000000000000dee9"
+        );
+        assert_eq!(call.module.as_str(), "clob_v2");
+        assert_eq!(call.function.as_str(), "cancel_order");
+        assert_eq!(
+            call.type_arguments,
+            [clob.base_asset().clone(), clob.quote_asset().clone()]
+        );
+        assert_eq!(call.arguments.len(), 3);
+    }
+
+    #[test]
+    fn place_limit_order_call() {
+        let order = LimitOrder {
+            client_order_id: Argument::Input(2),
+            price: Argument::Input(3),
+            quantity: Argument::Input(4),
+            self_matching_prevention: Argument::Input(5),
+            is_bid: Argument::Input(6),
+            expire_timestamp: Argument::Input(7),
+            restriction: Argument::Input(8),
+        };
+        let Command::MoveCall(call) = sui_usdc().place_limit_order(
+            Argument::Input(0),
+            order,
+            Argument::Input(9),
+            Argument::Input(1),
+        ) else {
+            panic!("expected a move call");
+        };
+        // The arguments in the order of the Move signature.
+        assert_eq!(
+            call.arguments,
+            [0, 2, 3, 4, 5, 6, 7, 8, 9, 1].map(Argument::Input)
+        );
+        assert_eq!(
+            Restriction::PostOrAbort.input(),
+            InputArgument::Pure { value: vec![3] }
+        );
+    }
+
+    #[test]
+    fn order_filled_event() {
+        let clob = sui_usdc();
+        let filled = OrderFilled {
+            pool_id: ObjectId::from(Address::TWO),
+            order_id: 1 << 63 | 5,
+            taker_client_order_id: 1,
+            maker_client_order_id: 2,
+            is_bid: false,
+            taker_address: Address::TWO,
+            maker_address: Address::THREE,
+            original_quantity: 1000,
+            base_asset_quantity_filled: 400,
+            base_asset_quantity_remaining: 600,
+            price: 1_500_000_000,
+            taker_commission: 3,
+            maker_rebates: 1,
+        };
+        let contents = bcs::to_bytes(&(
+            filled.pool_id,
+            filled.order_id,
+            filled.taker_client_order_id,
+            filled.maker_client_order_id,
+            filled.is_bid,
+            filled.taker_address,
+            filled.maker_address,
+            filled.original_quantity,
+            (
+                filled.base_asset_quantity_filled,
+                filled.base_asset_quantity_remaining,
+                filled.price,
+                filled.taker_commission,
+                filled.maker_rebates,
+            ),
+        ))
+        .unwrap();
+        let type_params = vec![clob.base_asset().clone(), clob.quote_asset().clone()];
+        let event = Event {
+            package_id: ClobV2::PACKAGE_ID,
+            module: Identifier::new("clob_v2").unwrap(),
+            sender: Address::TWO,
+            type_: clob_v2_type("OrderFilled", type_params.clone()),
+            contents,
+        };
+
+        assert_eq!(OrderFilled::try_from_event(&event), Some(filled));
+        assert!(!Order::is_bid_order_id(1 << 63 | 5));
+
+        // The layout of another event doesn't decode as a fill.
+        let placed = Event {
+            type_: clob_v2_type("OrderPlaced", type_params),
+            ..event
+        };
+        assert_eq!(OrderFilled::try_from_event(&placed), None);
+    }
+
+    #[test]
+    fn pool_state() {
+        use crate::types::Owner;
+        use crate::types::Version;
+
+        let table = (ObjectId::ZERO, 0u64);
+        let critbit = (0u64, table, table, 0u64, 0u64, 0u64, 0u64);
+        let custodian = (ObjectId::ZERO, table);
+        let contents = bcs::to_bytes(&(
+            (
+                ObjectId::from(Address::TWO),
+                critbit,
+                critbit,
+                7u64,
+                1u64 << 63 | 3,
+                table,
+            ),
+            (2_500_000u64, 1_500_000u64, 100u64, 1000u64),
+            (custodian, custodian, 100u64, 0u64, 42u64),
+        ))
+        .unwrap();
+        let object = Object::new_move_object(
+            sui_usdc().pool_type(),
+            Version::new(1),
+            contents,
+            Owner::Shared {
+                initial_shared_version: Version::new(1),
+            },
+        );
+
+        let pool = PoolState::try_from_object(&object).unwrap();
+        assert_eq!(pool.id, ObjectId::from(Address::TWO));
+        assert_eq!(&pool.quote_asset, sui_usdc().quote_asset());
+        assert_eq!(pool.next_bid_order_id, 7);
+        assert_eq!(pool.taker_fee_rate, 2_500_000);
+        assert_eq!(pool.lot_size, 1000);
+        assert_eq!(pool.quote_asset_trading_fees, 42);
+        assert_eq!(Order::try_from_object(&object), None);
+    }
+}
@@ -0,0 +1,748 @@
+//! The coin deny list, the `0x403` shared object through which the issuers of regulated coins deny
+//! addresses the use of their coins.
+//!
+//! The denied addresses aren't held in the [`DenyList`] object itself but in dynamic fields, in
+//! one of two layouts depending on how the coin was created:
+//!
+//! - v1: its `lists` bag holds the [`PerTypeList`] of coins at [`COIN_INDEX`], whose
+//!   `denied_addresses` table maps the type name of each regulated coin to the set of addresses
+//!   denied it.
+//! - v2: a dynamic object field of the [`DenyList`] holds the [`CoinConfig`] of each regulated
+//!   coin, a `0x2::config::Config` whose own dynamic fields hold a setting per denied address and
+//!   whether the coin is globally paused.
+//!
+//! With the `hash` feature, the ids of these fields are derived by [`DenyList::coin_list_id`],
+//! [`PerTypeList::denied_addresses_id`], [`DenyList::coin_config_field_id`],
+//! [`CoinConfig::address_setting_id`] and [`CoinConfig::global_pause_setting_id`], to fetch them
+//! from a node.
+//!
+//! The entries collected in a [`CoinDenyList`] check a transaction before it is submitted, so
+//! that a wallet can warn of a transfer which would fail on-chain, e.g. from the
+//! [`TransactionPreview`] of a dry run:
+//!
+//! ```ignore
+//! let mut deny_list = CoinDenyList::new();
+//! for entry in &fetched_entries {
+//!     deny_list.insert_object(entry);
+//! }
+//! for denied in deny_list.check(&preview) {
+//!     warn(denied.address, &denied.coin_type);
+//! }
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::json_rpc::TransactionPreview;
+use crate::types::Address;
+use crate::types::Identifier;
+use crate::types::Object;
+use crate::types::ObjectData;
+use crate::types::ObjectId;
+use crate::types::Owner;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// The index of the deny list of coins in the `lists` bag of the [`DenyList`].
+pub const COIN_INDEX: u64 = 0;
+
+/// The addresses which can't be denied, those of system packages and objects.
+const RESERVED: [Address; 18] = [
+    Address::from_static_hex("0x0"),
+    Address::from_static_hex("0x1"),
+    Address::from_static_hex("0x2"),
+    Address::from_static_hex("0x3"),
+    Address::from_static_hex("0x4"),
+    Address::from_static_hex("0x5"),
+    Address::from_static_hex("0x6"),
+    Address::from_static_hex("0x7"),
+    Address::from_static_hex("0x8"),
+    Address::from_static_hex("0x9"),
+    Address::from_static_hex("0xa"),
+    Address::from_static_hex("0xb"),
+    Address::from_static_hex("0xc"),
+    Address::from_static_hex("0xd"),
+    Address::from_static_hex("0xe"),
+    Address::from_static_hex("0xf"),
+    Address::from_static_hex("0x403"),
+    Address::from_static_hex("0xdee9"),
+];
+
+/// The `0x2::deny_list::DenyList` shared object.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DenyList {
+    pub id: ObjectId,
+    /// The id of the bag of the lists, by the index of the type they deny.
+    pub lists_id: ObjectId,
+    pub lists_size: u64,
+}
+
+/// The deny list of a type, e.g. of coins, held in a dynamic field of the `lists` of the
+/// [`DenyList`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PerTypeList {
+    pub index: u64,
+    pub id: ObjectId,
+    /// The id of the table of the number of types each denied address is denied.
+    pub denied_count_id: ObjectId,
+    /// The id of the table of the addresses denied each type, by type name.
+    pub denied_addresses_id: ObjectId,
+}
+
+/// The `0x2::config::Config` holding the v2 deny list of a regulated coin, read from the dynamic
+/// object field of the [`DenyList`] pointing to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CoinConfig {
+    /// The id of the config object, which owns the dynamic fields of its settings.
+    pub id: ObjectId,
+    /// The type name of the coin, as given by `std::type_name`.
+    pub type_name: String,
+}
+
+/// The BCS layout of a `0x2::table::Table` or `0x2::bag::Bag`, whose entries are dynamic fields.
+#[derive(serde_derive::Deserialize)]
+struct Table {
+    id: ObjectId,
+    size: u64,
+}
+
+impl DenyList {
+    /// Read the `0x2::deny_list::DenyList` object.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        #[derive(serde_derive::Deserialize)]
+        struct BinaryDenyList {
+            id: ObjectId,
+            lists: Table,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        if !is_deny_list_type(&move_struct.type_, "DenyList") {
+            return None;
+        }
+        let deny_list: BinaryDenyList = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some(Self {
+            id: deny_list.id,
+            lists_id: deny_list.lists.id,
+            lists_size: deny_list.lists.size,
+        })
+    }
+
+    /// The id of the dynamic field holding the [`PerTypeList`] of coins.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn coin_list_id(&self) -> ObjectId {
+        self.lists_id
+            .derive_dynamic_child_id(&TypeTag::U64, &COIN_INDEX.to_le_bytes())
+    }
+
+    /// The id of the dynamic object field pointing to the [`CoinConfig`] of `coin_type`, which
+    /// only exists if the coin is regulated with a v2 deny list.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn coin_config_field_id(&self, coin_type: &TypeTag) -> ObjectId {
+        let key = bcs::to_bytes(&(COIN_INDEX, type_name(coin_type).as_bytes()))
+            .expect("serialization of a config key cannot fail");
+        let wrapper = StructTag {
+            address: Address::TWO,
+            module: Identifier::new("dynamic_object_field").unwrap(),
+            name: Identifier::new("Wrapper").unwrap(),
+            type_params: vec![deny_list_type("ConfigKey")],
+        };
+        self.id
+            .derive_dynamic_child_id(&TypeTag::Struct(Box::new(wrapper)), &key)
+    }
+}
+
+impl CoinConfig {
+    /// Read the config from the dynamic object field pointing to it, of type
+    /// `0x2::dynamic_field::Field<0x2::dynamic_object_field::Wrapper<0x2::deny_list::ConfigKey>,
+    /// 0x2::object::ID>`, keyed by the coin's type name at [`COIN_INDEX`].
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field {
+            _id: ObjectId,
+            name: ConfigKey,
+            value: ObjectId,
+        }
+
+        #[derive(serde_derive::Deserialize)]
+        struct ConfigKey {
+            per_type_index: u64,
+            per_type_key: Vec<u8>,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let is_config_field = is_field_type(&move_struct.type_)
+            && matches!(
+                move_struct.type_.type_params.as_slice(),
+                [TypeTag::Struct(name), TypeTag::Struct(value)]
+                    if name.address == Address::TWO
+                        && name.module == *"dynamic_object_field"
+                        && name.name == *"Wrapper"
+                        && name.type_params == [deny_list_type("ConfigKey")]
+                        && value.address == Address::TWO
+                        && value.module == *"object"
+                        && value.name == *"ID"
+            );
+        if !is_config_field {
+            return None;
+        }
+        let field: Field = bcs::from_bytes(&move_struct.contents).ok()?;
+        if field.name.per_type_index != COIN_INDEX {
+            return None;
+        }
+        Some(Self {
+            id: field.value,
+            type_name: String::from_utf8(field.name.per_type_key).ok()?,
+        })
+    }
+
+    /// The id of the dynamic field holding the setting of whether `address` is denied the coin.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn address_setting_id(&self, address: Address) -> ObjectId {
+        let key = bcs::to_bytes(&address).expect("serialization of an address cannot fail");
+        self.id
+            .derive_dynamic_child_id(&deny_list_type("AddressKey"), &key)
+    }
+
+    /// The id of the dynamic field holding the setting of whether the coin is globally paused.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn global_pause_setting_id(&self) -> ObjectId {
+        // The BCS of the empty `GlobalPauseKey`, which Move gives a `dummy_field: bool`
+        self.id
+            .derive_dynamic_child_id(&deny_list_type("GlobalPauseKey"), &[0])
+    }
+}
+
+impl PerTypeList {
+    /// Read a per type list from the dynamic field holding it, of type
+    /// `0x2::dynamic_field::Field<u64, 0x2::deny_list::PerTypeList>`.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field {
+            _id: ObjectId,
+            name: u64,
+            value: BinaryPerTypeList,
+        }
+
+        #[derive(serde_derive::Deserialize)]
+        struct BinaryPerTypeList {
+            id: ObjectId,
+            denied_count: Table,
+            denied_addresses: Table,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let is_per_type_list = is_field_type(&move_struct.type_)
+            && matches!(
+                move_struct.type_.type_params.as_slice(),
+                [TypeTag::U64, TypeTag::Struct(value)] if is_deny_list_type(value, "PerTypeList")
+            );
+        if !is_per_type_list {
+            return None;
+        }
+        let field: Field = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some(Self {
+            index: field.name,
+            id: field.value.id,
+            denied_count_id: field.value.denied_count.id,
+            denied_addresses_id: field.value.denied_addresses.id,
+        })
+    }
+
+    /// The id of the dynamic field holding the addresses denied `coin_type`, which only exists
+    /// if the coin is regulated and some addresses were ever denied it.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn denied_addresses_id(&self, coin_type: &TypeTag) -> ObjectId {
+        let key = bcs::to_bytes(type_name(coin_type).as_bytes())
+            .expect("serialization of a type name cannot fail");
+        self.denied_addresses_id
+            .derive_dynamic_child_id(&TypeTag::Vector(Box::new(TypeTag::U8)), &key)
+    }
+}
+
+/// The addresses denied the use of regulated coins, by coin type.
+///
+/// A v2 setting changed in the current epoch holds both its value for this epoch and the next,
+/// and an address or coin is taken as denied or paused if either is, erring on the side of a
+/// warning for a transaction which may still succeed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CoinDenyList {
+    /// The denied addresses of v1 deny lists, by the type name of the coin.
+    denied: BTreeMap<String, BTreeSet<Address>>,
+    /// The type names of the coins of the v2 deny lists, by the id of their config.
+    configs: BTreeMap<ObjectId, String>,
+    /// The denied addresses of v2 deny lists, by the type name of the coin.
+    denied_v2: BTreeMap<String, BTreeSet<Address>>,
+    /// The type names of the globally paused coins.
+    paused: BTreeSet<String>,
+}
+
+/// A coin which a transaction would send or receive, from or to an address denied it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeniedTransfer {
+    pub address: Address,
+    pub coin_type: TypeTag,
+    /// Whether the address would send the coin, rather than receive it.
+    pub is_sender: bool,
+}
+
+impl CoinDenyList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an object of either layout of the deny list:
+    ///
+    /// - v1: an entry of the `denied_addresses` table of the [`PerTypeList`] of coins, of type
+    ///   `0x2::dynamic_field::Field<vector<u8>, 0x2::vec_set::VecSet<address>>`, replacing the
+    ///   addresses previously known for its coin type.
+    /// - v2: the dynamic object field pointing to a [`CoinConfig`], see
+    ///   [`CoinDenyList::insert_config`], or a setting of a config inserted before, of type
+    ///   `0x2::dynamic_field::Field<0x2::deny_list::AddressKey, 0x2::config::Setting<bool>>` or
+    ///   `0x2::dynamic_field::Field<0x2::deny_list::GlobalPauseKey, 0x2::config::Setting<bool>>`.
+    ///
+    /// Returns `false` if the object isn't such an entry, or is the setting of an unknown config.
+    pub fn insert_object(&mut self, object: &Object) -> bool {
+        if let Some(config) = CoinConfig::try_from_object(object) {
+            self.insert_config(config);
+            return true;
+        }
+        if let Some(inserted) = self.insert_setting(object) {
+            return inserted;
+        }
+        self.insert_v1_entry(object)
+    }
+
+    /// Add the config of a coin, whose settings can then be inserted.
+    pub fn insert_config(&mut self, config: CoinConfig) {
+        self.configs.insert(config.id, config.type_name);
+    }
+
+    /// Add a setting of a v2 deny list, `None` if the object isn't one.
+    fn insert_setting(&mut self, object: &Object) -> Option<bool> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field<K> {
+            _id: ObjectId,
+            name: K,
+            value: Option<SettingData>,
+        }
+
+        #[derive(serde_derive::Deserialize)]
+        struct SettingData {
+            _newer_value_epoch: u64,
+            newer_value: Option<bool>,
+            older_value_opt: Option<bool>,
+        }
+
+        impl SettingData {
+            fn is_set(setting: Option<Self>) -> bool {
+                setting.is_some_and(|data| {
+                    data.newer_value == Some(true) || data.older_value_opt == Some(true)
+                })
+            }
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        if !is_field_type(&move_struct.type_) {
+            return None;
+        }
+        let [TypeTag::Struct(name), TypeTag::Struct(value)] =
+            move_struct.type_.type_params.as_slice()
+        else {
+            return None;
+        };
+        let is_setting = value.address == Address::TWO
+            && value.module == *"config"
+            && value.name == *"Setting"
+            && value.type_params == [TypeTag::Bool];
+        if !is_setting {
+            return None;
+        }
+        let Owner::Object(config_id) = object.owner() else {
+            return Some(false);
+        };
+        let Some(type_name) = self.configs.get(config_id) else {
+            return Some(false);
+        };
+        if is_deny_list_type(name, "AddressKey") {
+            let Ok(field) = bcs::from_bytes::<Field<Address>>(&move_struct.contents) else {
+                return Some(false);
+            };
+            let denied = self.denied_v2.entry(type_name.clone()).or_default();
+            if SettingData::is_set(field.value) {
+                denied.insert(field.name);
+            } else {
+                denied.remove(&field.name);
+            }
+            Some(true)
+        } else if is_deny_list_type(name, "GlobalPauseKey") {
+            let Ok(field) = bcs::from_bytes::<Field<bool>>(&move_struct.contents) else {
+                return Some(false);
+            };
+            if SettingData::is_set(field.value) {
+                self.paused.insert(type_name.clone());
+            } else {
+                self.paused.remove(type_name);
+            }
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    fn insert_v1_entry(&mut self, object: &Object) -> bool {
+        #[derive(serde_derive::Deserialize)]
+        struct Field {
+            _id: ObjectId,
+            name: Vec<u8>,
+            value: Vec<Address>,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return false;
+        };
+        let is_entry = is_field_type(&move_struct.type_)
+            && matches!(
+                move_struct.type_.type_params.as_slice(),
+                [TypeTag::Vector(name), TypeTag::Struct(value)]
+                    if **name == TypeTag::U8
+                        && value.address == Address::TWO
+                        && value.module == *"vec_set"
+                        && value.name == *"VecSet"
+                        && value.type_params == [TypeTag::Address]
+            );
+        if !is_entry {
+            return false;
+        }
+        let Ok(field) = bcs::from_bytes::<Field>(&move_struct.contents) else {
+            return false;
+        };
+        let Ok(type_name) = String::from_utf8(field.name) else {
+            return false;
+        };
+        self.denied
+            .insert(type_name, field.value.into_iter().collect());
+        true
+    }
+
+    /// Deny `address` the use of `coin_type`.
+    pub fn deny(&mut self, coin_type: &TypeTag, address: Address) {
+        self.denied
+            .entry(type_name(coin_type))
+            .or_default()
+            .insert(address);
+    }
+
+    /// Whether `address` is denied the use of `coin_type`, by either deny list or because the
+    /// coin is globally paused. Reserved system addresses are never denied by address.
+    pub fn is_denied(&self, coin_type: &TypeTag, address: &Address) -> bool {
+        let type_name = type_name(coin_type);
+        let is_listed = |denied: &BTreeMap<String, BTreeSet<Address>>| {
+            denied
+                .get(&type_name)
+                .is_some_and(|addresses| addresses.contains(address))
+        };
+        self.paused.contains(&type_name)
+            || (!RESERVED.contains(address)
+                && (is_listed(&self.denied) || is_listed(&self.denied_v2)))
+    }
+
+    /// Whether `coin_type` is globally paused by its v2 deny list.
+    pub fn is_paused(&self, coin_type: &TypeTag) -> bool {
+        self.paused.contains(&type_name(coin_type))
+    }
+
+    /// The transfers of regulated coins a transaction would make from or to denied addresses,
+    /// found from the balance changes of its preview.
+    pub fn check(&self, preview: &TransactionPreview) -> Vec<DeniedTransfer> {
+        let mut denied = Vec::new();
+        for (address, address_preview) in &preview.addresses {
+            for (coin_type, amount) in &address_preview.balances {
+                if *amount != 0 && self.is_denied(coin_type, address) {
+                    denied.push(DeniedTransfer {
+                        address: *address,
+                        coin_type: coin_type.clone(),
+                        is_sender: *amount < 0,
+                    });
+                }
+            }
+        }
+        denied
+    }
+
+    /// The denied addresses of a transfer of `coin_type` from `sender` to `recipient`, for a
+    /// check before the transaction is built.
+    pub fn check_transfer(
+        &self,
+        coin_type: &TypeTag,
+        sender: Address,
+        recipient: Address,
+    ) -> Vec<DeniedTransfer> {
+        [(sender, true), (recipient, false)]
+            .into_iter()
+            .filter(|(address, _)| self.is_denied(coin_type, address))
+            .map(|(address, is_sender)| DeniedTransfer {
+                address,
+                coin_type: coin_type.clone(),
+                is_sender,
+            })
+            .collect()
+    }
+}
+
+fn is_deny_list_type(type_: &StructTag, name: &str) -> bool {
+    type_.address == Address::TWO
+        && type_.module == *"deny_list"
+        && type_.name == *name
+        && type_.type_params.is_empty()
+}
+
+fn deny_list_type(name: &str) -> TypeTag {
+    TypeTag::Struct(Box::new(StructTag {
+        address: Address::TWO,
+        module: Identifier::new("deny_list").unwrap(),
+        name: Identifier::new(name).unwrap(),
+        type_params: vec![],
+    }))
+}
+
+fn is_field_type(type_: &StructTag) -> bool {
+    type_.address == Address::TWO && type_.module == *"dynamic_field" && type_.name == *"Field"
+}
+
+/// The name of `type_tag` as given by `std::type_name`, which keys the deny lists: addresses are
+/// written in full without a `0x` prefix, and type parameters are separated by commas alone.
+fn type_name(type_tag: &TypeTag) -> String {
+    fn write(type_tag: &TypeTag, name: &mut String) {
+        match type_tag {
+            TypeTag::Vector(element) => {
+                name.push_str("vector<");
+                write(element, name);
+                name.push('>');
+            }
+            TypeTag::Struct(struct_tag) => {
+                let address = struct_tag.address.to_string();
+                name.push_str(&address[2..]);
+                name.push_str("::");
+                name.push_str(struct_tag.module.as_str());
+                name.push_str("::");
+                name.push_str(struct_tag.name.as_str());
+                if !struct_tag.type_params.is_empty() {
+                    name.push('<');
+                    for (i, type_param) in struct_tag.type_params.iter().enumerate() {
+                        if i > 0 {
+                            name.push(',');
+                        }
+                        write(type_param, name);
+                    }
+                    name.push('>');
+                }
+            }
+            primitive => name.push_str(&primitive.to_string()),
+        }
+    }
+
+    let mut name = String::new();
+    write(type_tag, &mut name);
+    name
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn regulated_coin() -> TypeTag {
+        "0x123::regulated::REGULATED".parse().unwrap()
+    }
+
+    fn object(type_: StructTag, contents: Vec<u8>) -> Object {
+        Object::new_move_object(
+            type_,
+            Version::new(1),
+            contents,
+            Owner::Object(ObjectId::ZERO),
+        )
+    }
+
+    #[test]
+    fn type_names() {
+        assert_eq!(
+            type_name(&regulated_coin()),
+            "0000000000000000000000000000000000000000000000000000000000000123::regulated::REGULATED"
+        );
+        let pair: TypeTag = "0x2::pair::Pair<vector<u8>, 0x2::sui::SUI>"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            type_name(&pair),
+            "0000000000000000000000000000000000000000000000000000000000000002::pair::Pair<\
+             vector<u8>,0000000000000000000000000000000000000000000000000000000000000002::sui::SUI>"
+        );
+    }
+
+    #[test]
+    fn decode_objects() {
+        let id = |i: u8| ObjectId::new([i; 32]);
+        let deny_list = object(
+            "0x2::deny_list::DenyList".parse().unwrap(),
+            bcs::to_bytes(&(id(1), id(2), 1u64)).unwrap(),
+        );
+        assert_eq!(
+            DenyList::try_from_object(&deny_list),
+            Some(DenyList {
+                id: id(1),
+                lists_id: id(2),
+                lists_size: 1,
+            })
+        );
+        assert_eq!(PerTypeList::try_from_object(&deny_list), None);
+
+        let per_type_list = object(
+            "0x2::dynamic_field::Field<u64, 0x2::deny_list::PerTypeList>"
+                .parse()
+                .unwrap(),
+            bcs::to_bytes(&(id(3), COIN_INDEX, id(4), (id(5), 2u64), (id(6), 1u64))).unwrap(),
+        );
+        let list = PerTypeList::try_from_object(&per_type_list).unwrap();
+        assert_eq!(list.index, COIN_INDEX);
+        assert_eq!(list.denied_addresses_id, id(6));
+
+        let denied = Address::new([7; 32]);
+        let entry = object(
+            "0x2::dynamic_field::Field<vector<u8>, 0x2::vec_set::VecSet<address>>"
+                .parse()
+                .unwrap(),
+            bcs::to_bytes(&(id(8), type_name(&regulated_coin()), vec![denied])).unwrap(),
+        );
+        let mut coin_deny_list = CoinDenyList::new();
+        assert!(!coin_deny_list.insert_object(&per_type_list));
+        assert!(coin_deny_list.insert_object(&entry));
+        assert!(coin_deny_list.is_denied(&regulated_coin(), &denied));
+        assert!(!coin_deny_list.is_denied(&regulated_coin(), &Address::new([8; 32])));
+        assert!(!coin_deny_list.is_denied(&"0x2::sui::SUI".parse().unwrap(), &denied));
+    }
+
+    #[test]
+    fn decode_v2_objects() {
+        let id = |i: u8| ObjectId::new([i; 32]);
+        let config_field = object(
+            "0x2::dynamic_field::Field<\
+             0x2::dynamic_object_field::Wrapper<0x2::deny_list::ConfigKey>, 0x2::object::ID>"
+                .parse()
+                .unwrap(),
+            bcs::to_bytes(&(id(1), COIN_INDEX, type_name(&regulated_coin()), id(2))).unwrap(),
+        );
+        let config = CoinConfig::try_from_object(&config_field).unwrap();
+        assert_eq!(
+            config,
+            CoinConfig {
+                id: id(2),
+                type_name: type_name(&regulated_coin()),
+            }
+        );
+
+        let setting = |key: &str, name: Vec<u8>, newer: Option<bool>, older: Option<bool>| {
+            Object::new_move_object(
+                format!("0x2::dynamic_field::Field<{key}, 0x2::config::Setting<bool>>")
+                    .parse()
+                    .unwrap(),
+                Version::new(1),
+                [
+                    bcs::to_bytes(&id(3)).unwrap(),
+                    name,
+                    bcs::to_bytes(&Some((5u64, newer, older))).unwrap(),
+                ]
+                .concat(),
+                Owner::Object(config.id),
+            )
+        };
+        let denied = Address::new([7; 32]);
+        let address_setting = |newer, older| {
+            setting(
+                "0x2::deny_list::AddressKey",
+                bcs::to_bytes(&denied).unwrap(),
+                newer,
+                older,
+            )
+        };
+        let pause_setting = |newer| setting("0x2::deny_list::GlobalPauseKey", vec![0], newer, None);
+
+        let mut deny_list = CoinDenyList::new();
+        assert!(!deny_list.insert_object(&address_setting(Some(true), None)));
+        assert!(deny_list.insert_object(&config_field));
+
+        // Denied from the next epoch
+        assert!(deny_list.insert_object(&address_setting(Some(true), None)));
+        assert!(deny_list.is_denied(&regulated_coin(), &denied));
+        assert!(!deny_list.is_denied(&regulated_coin(), &Address::new([8; 32])));
+        // Still denied in the current epoch
+        assert!(deny_list.insert_object(&address_setting(Some(false), Some(true))));
+        assert!(deny_list.is_denied(&regulated_coin(), &denied));
+        assert!(deny_list.insert_object(&address_setting(Some(false), None)));
+        assert!(!deny_list.is_denied(&regulated_coin(), &denied));
+
+        assert!(deny_list.insert_object(&pause_setting(Some(true))));
+        assert!(deny_list.is_paused(&regulated_coin()));
+        assert!(deny_list.is_denied(&regulated_coin(), &Address::new([8; 32])));
+        assert!(!deny_list.is_paused(&"0x2::sui::SUI".parse().unwrap()));
+        assert!(deny_list.insert_object(&pause_setting(None)));
+        assert!(!deny_list.is_paused(&regulated_coin()));
+    }
+
+    #[test]
+    fn check_transfers() {
+        use crate::json_rpc::AddressPreview;
+        use crate::json_rpc::SuiExecutionStatus;
+        use crate::types::GasCostSummary;
+
+        let denied = Address::new([7; 32]);
+        let allowed = Address::new([8; 32]);
+        let mut deny_list = CoinDenyList::new();
+        deny_list.deny(&regulated_coin(), denied);
+        deny_list.deny(&regulated_coin(), Address::TWO);
+        assert!(!deny_list.is_denied(&regulated_coin(), &Address::TWO));
+
+        let transfers = deny_list.check_transfer(&regulated_coin(), allowed, denied);
+        assert_eq!(
+            transfers,
+            [DeniedTransfer {
+                address: denied,
+                coin_type: regulated_coin(),
+                is_sender: false,
+            }]
+        );
+
+        let balances = |amount: i128| AddressPreview {
+            balances: [(regulated_coin(), amount)].into_iter().collect(),
+            ..Default::default()
+        };
+        let preview = TransactionPreview {
+            status: SuiExecutionStatus::Success,
+            gas_owner: denied,
+            gas_used: GasCostSummary::new(0, 0, 0, 0),
+            addresses: [(denied, balances(-10)), (allowed, balances(10))]
+                .into_iter()
+                .collect(),
+        };
+        let transfers = deny_list.check(&preview);
+        assert_eq!(transfers.len(), 1);
+        assert_eq!(transfers[0].address, denied);
+        assert!(transfers[0].is_sender);
+    }
+}
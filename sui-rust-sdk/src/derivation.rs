@@ -0,0 +1,369 @@
+//! Deriving the [`Address`] of an account from the public keys which authenticate it.
+//!
+//! The address of a single key is the Blake2b-256 hash of its flag, the byte of its
+//! [`SignatureScheme`](crate::types::SignatureScheme), followed by the bytes of the key:
+//! `flag || pk`. A zkLogin identifier hashes the length of its issuer, the issuer and its address
+//! seed in place of the key, and the address of a multisig committee is the hash of the multisig
+//! flag, its threshold and every member with its weight.
+//!
+//! The `write_into_hasher` methods write the part each key contributes to the hash, so that
+//! addresses of other compositions of keys can be derived the same way.
+
+use crate::hash::Hasher;
+use crate::types::Address;
+use crate::types::Ed25519PublicKey;
+use crate::types::MultisigCommittee;
+use crate::types::MultisigMemberPublicKey;
+use crate::types::Secp256k1PublicKey;
+use crate::types::Secp256r1PublicKey;
+use crate::types::ZkLoginPublicIdentifier;
+
+impl Ed25519PublicKey {
+    pub fn to_address(&self) -> Address {
+        let mut hasher = Hasher::new();
+        self.write_into_hasher(&mut hasher);
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+
+    /// Write `flag || pk` into `hasher`.
+    pub fn write_into_hasher(&self, hasher: &mut Hasher) {
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update(self.inner());
+    }
+}
+
+impl Secp256k1PublicKey {
+    pub fn to_address(&self) -> Address {
+        let mut hasher = Hasher::new();
+        self.write_into_hasher(&mut hasher);
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+
+    /// Write `flag || pk` into `hasher`, `pk` being the compressed SEC1 encoding of the key.
+    pub fn write_into_hasher(&self, hasher: &mut Hasher) {
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update(self.inner());
+    }
+}
+
+impl Secp256r1PublicKey {
+    pub fn to_address(&self) -> Address {
+        let mut hasher = Hasher::new();
+        self.write_into_hasher(&mut hasher);
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+
+    /// Write `flag || pk` into `hasher`, `pk` being the compressed SEC1 encoding of the key.
+    pub fn write_into_hasher(&self, hasher: &mut Hasher) {
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update(self.inner());
+    }
+}
+
+impl ZkLoginPublicIdentifier {
+    /// Define as iss_bytes_len || iss_bytes || padded_32_byte_address_seed.
+    pub fn to_address_padded(&self) -> Address {
+        let mut hasher = Hasher::new();
+        self.write_into_hasher_padded(&mut hasher);
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+
+    /// Write `flag || iss_bytes_len || iss_bytes || padded_32_byte_address_seed` into `hasher`,
+    /// as the identifier contributes to the address of a multisig committee.
+    pub fn write_into_hasher_padded(&self, hasher: &mut Hasher) {
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update([self.iss().len() as u8]);
+        hasher.update(self.iss());
+        hasher.update(self.address_seed().padded());
+    }
+
+    /// Define as iss_bytes_len || iss_bytes || unpadded_32_byte_address_seed.
+    pub fn to_address_unpadded(&self) -> Address {
+        let mut hasher = Hasher::new();
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update([self.iss().len() as u8]);
+        hasher.update(self.iss());
+        hasher.update(self.address_seed().unpadded());
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+}
+
+impl MultisigMemberPublicKey {
+    /// Write the key into `hasher` as it contributes to the address of a multisig committee.
+    pub fn write_into_hasher(&self, hasher: &mut Hasher) {
+        match self {
+            Self::Ed25519(p) => p.write_into_hasher(hasher),
+            Self::Secp256k1(p) => p.write_into_hasher(hasher),
+            Self::Secp256r1(p) => p.write_into_hasher(hasher),
+            Self::ZkLogin(p) => p.write_into_hasher_padded(hasher),
+        }
+    }
+}
+
+impl MultisigCommittee {
+    /// Derive an Address from a MultisigCommittee. A MultiSig address
+    /// is defined as the 32-byte Blake2b hash of serializing the flag, the
+    /// threshold, concatenation of all n flag, public keys and
+    /// its weight. `flag_MultiSig || threshold || flag_1 || pk_1 || weight_1
+    /// || ... || flag_n || pk_n || weight_n`.
+    ///
+    /// When flag_i is ZkLogin, pk_i refers to [struct ZkLoginPublicIdentifier]
+    /// derived from padded address seed in bytes and iss.
+    pub fn to_address(&self) -> Address {
+        let mut hasher = Hasher::new();
+        hasher.update([self.scheme().to_u8()]);
+        hasher.update(self.threshold().to_le_bytes());
+
+        for member in self.members() {
+            member.public_key().write_into_hasher(&mut hasher);
+            hasher.update(member.weight().to_le_bytes());
+        }
+
+        let digest = hasher.finalize();
+        Address::new(digest.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Bn254FieldElement;
+    use crate::types::MultisigMember;
+    use crate::types::SignatureScheme;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const TWITCH: &str = "https://id.twitch.tv/oauth2";
+
+    fn address(s: &str) -> Address {
+        s.parse().unwrap()
+    }
+
+    fn zklogin(address_seed: &str) -> ZkLoginPublicIdentifier {
+        ZkLoginPublicIdentifier::new(TWITCH.to_owned(), address_seed.parse().unwrap()).unwrap()
+    }
+
+    // Vectors from the reference typescript sdk, sdk/typescript/test/unit/cryptography
+    #[test]
+    fn single_key_vectors() {
+        for (key, expected) in [
+            (
+                "UdGRWooy48vGTs0HBokIis5NK+DUjiWc9ENUlcfCCBE=",
+                "0xd77a6cd55073e98d4029b1b0b8bd8d88f45f343dad2732fc9a7965094e635c55",
+            ),
+            (
+                "0PTAfQmNiabgbak9U/stWZzKc5nsRqokda2qnV2DTfg=",
+                "0x7e8fd489c3d3cd9cc7cbcc577dc5d6de831e654edd9997d95c412d013e6eea23",
+            ),
+            (
+                "6L/l0uhGt//9cf6nLQ0+24Uv2qanX/R6tn7lWUJX1Xk=",
+                "0x3a1b4410ebe9c3386a429c349ba7929aafab739c277f97f32622b971972a14a2",
+            ),
+        ] {
+            let key = Ed25519PublicKey::from_bytes(Base64::decode_vec(key).unwrap()).unwrap();
+            assert_eq!(key.to_address(), address(expected));
+        }
+
+        for (key, expected) in [
+            (
+                "AwTC3jVFRxXc3RJIFgoQcv486QdqwYa8vBp4bgSq0gsI",
+                "0xcdce00b4326fb908fdac83c35bcfbda323bfcc0618b47c66ccafbdced850efaa",
+            ),
+            (
+                "A1F2CtldIGolO92Pm9yuxWXs5E07aX+6ZEHAnSuKOhii",
+                "0xb588e58ed8967b6a6f9dbce76386283d374cf7389fb164189551257e32b023b2",
+            ),
+            (
+                "Ak5rsa5Od4T6YFN/V3VIhZ/azMMYPkUilKQwc+RiaId+",
+                "0x694dd74af1e82b968822a82fb5e315f6d20e8697d5d03c0b15e0178c1a1fcfa0",
+            ),
+            (
+                "A4XbJ3fLvV/8ONsnLHAW1nORKsoCYsHaXv9FK1beMtvY",
+                "0x78acc6ca0003457737d755ade25a6f3a144e5e44ed6f8e6af4982c5cc75e55e7",
+            ),
+        ] {
+            let key = Secp256k1PublicKey::from_bytes(Base64::decode_vec(key).unwrap()).unwrap();
+            assert_eq!(key.to_address(), address(expected));
+        }
+
+        let key = Secp256r1PublicKey::from_bytes(
+            Base64::decode_vec("A8Ju2r5X3EZ3aYuZzH+Ofs6cd1j2WOwY7lhoJQenulBl").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            key.to_address(),
+            address("0xafd0f5a4f41c5770c201879518740b83743164ed2445016fbba9ae98e04af8a5")
+        );
+    }
+
+    // Vectors from sui-types' multisig tests and the typescript sdk's
+    #[test]
+    fn multisig_vectors() {
+        let ed25519 = MultisigMemberPublicKey::Ed25519(Ed25519PublicKey::new(
+            hex::decode("5ae220b4b2f65e977c12ede61579ff5170b6c22c006168c37b5e7c61af018083")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        ));
+        let secp256k1 = MultisigMemberPublicKey::Secp256k1(Secp256k1PublicKey::new(
+            hex::decode("021d152307c6b72b0ed0418b0e70cd80e7f5295b8d86f5722d3f5213fbd2394f36")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        ));
+        let secp256r1 = MultisigMemberPublicKey::Secp256r1(Secp256r1PublicKey::new(
+            hex::decode("0227322b3a891a0a280d6bc1fb2cbb23d28f54906fd6407f5f741f6def5762609a")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        ));
+        let committee = MultisigCommittee::new(
+            vec![
+                MultisigMember::new(ed25519, 1),
+                MultisigMember::new(secp256k1, 2),
+                MultisigMember::new(secp256r1, 3),
+            ],
+            3,
+        );
+        assert_eq!(
+            committee.to_address(),
+            address("0x8ee027fe556a3f6c0a23df64f090d2429fec0bb21f55594783476e81de2dec27")
+        );
+
+        // The second seed is shorter than 32 bytes, and padded with leading zeros
+        let long_seed = zklogin(
+            "20794788559620669596206457022966176986688727876128223628113916380927502737911",
+        );
+        let short_seed =
+            zklogin("380704556853533152350240698167704405529973457670972223618755249929828551006");
+        assert!(short_seed.address_seed().unpadded().len() < 32);
+        let committee = MultisigCommittee::new(
+            vec![
+                MultisigMember::new(MultisigMemberPublicKey::ZkLogin(long_seed.clone()), 1),
+                MultisigMember::new(MultisigMemberPublicKey::ZkLogin(short_seed), 1),
+            ],
+            1,
+        );
+        assert_eq!(
+            committee.to_address(),
+            address("0x77a9fbf3c695d78dd83449a81a9e70aa79a77dbfd6fb72037bf09201c12052cd")
+        );
+
+        let ed25519 = Ed25519PublicKey::new(
+            hex::decode("0d7dab358c8dadaa4efa0049a75b07436555b10a368219bb680f70571349d775")
+                .unwrap()
+                .try_into()
+                .unwrap(),
+        );
+        let committee = MultisigCommittee::new(
+            vec![
+                MultisigMember::new(MultisigMemberPublicKey::Ed25519(ed25519), 1),
+                MultisigMember::new(MultisigMemberPublicKey::ZkLogin(long_seed), 1),
+            ],
+            1,
+        );
+        assert_eq!(
+            committee.to_address(),
+            address("0xb9c0780a3943cde13a2409bf1a6f06ae60b0dff2b2f373260cf627aa4f43a588")
+        );
+    }
+
+    fn flagged(scheme: SignatureScheme, bytes: &[u8]) -> Address {
+        let mut hasher = Hasher::new();
+        hasher.update([scheme.to_u8()]);
+        hasher.update(bytes);
+        Address::new(hasher.finalize().into_inner())
+    }
+
+    #[proptest]
+    fn ed25519_address_is_hash_of_flag_and_key(key: Ed25519PublicKey) {
+        assert_eq!(
+            key.to_address(),
+            flagged(SignatureScheme::Ed25519, key.inner())
+        );
+    }
+
+    #[proptest]
+    fn secp256k1_address_is_hash_of_flag_and_key(key: Secp256k1PublicKey) {
+        assert_eq!(
+            key.to_address(),
+            flagged(SignatureScheme::Secp256k1, key.inner())
+        );
+    }
+
+    #[proptest]
+    fn secp256r1_address_is_hash_of_flag_and_key(key: Secp256r1PublicKey) {
+        assert_eq!(
+            key.to_address(),
+            flagged(SignatureScheme::Secp256r1, key.inner())
+        );
+    }
+
+    #[proptest]
+    fn flag_separates_schemes_of_same_length(key: Secp256k1PublicKey) {
+        assert_ne!(
+            key.to_address(),
+            Secp256r1PublicKey::new(*key.inner()).to_address()
+        );
+    }
+
+    #[proptest]
+    fn zklogin_padding_only_matters_with_leading_zeros(seed: [u8; 32]) {
+        let identifier =
+            ZkLoginPublicIdentifier::new(TWITCH.to_owned(), Bn254FieldElement::new(seed)).unwrap();
+        assert_eq!(
+            identifier.to_address_padded() == identifier.to_address_unpadded(),
+            seed[0] != 0
+        );
+    }
+
+    #[proptest]
+    fn multisig_address_is_hash_of_members(committee: MultisigCommittee) {
+        let mut bytes = vec![SignatureScheme::Multisig.to_u8()];
+        bytes.extend(committee.threshold().to_le_bytes());
+        for member in committee.members() {
+            match member.public_key() {
+                MultisigMemberPublicKey::Ed25519(p) => {
+                    bytes.push(SignatureScheme::Ed25519.to_u8());
+                    bytes.extend(p.inner());
+                }
+                MultisigMemberPublicKey::Secp256k1(p) => {
+                    bytes.push(SignatureScheme::Secp256k1.to_u8());
+                    bytes.extend(p.inner());
+                }
+                MultisigMemberPublicKey::Secp256r1(p) => {
+                    bytes.push(SignatureScheme::Secp256r1.to_u8());
+                    bytes.extend(p.inner());
+                }
+                MultisigMemberPublicKey::ZkLogin(p) => {
+                    bytes.push(SignatureScheme::ZkLogin.to_u8());
+                    bytes.push(p.iss().len() as u8);
+                    bytes.extend(p.iss().as_bytes());
+                    bytes.extend(p.address_seed().padded());
+                }
+            }
+            bytes.push(member.weight());
+        }
+        assert_eq!(
+            committee.to_address(),
+            Address::new(Hasher::digest(&bytes).into_inner())
+        );
+    }
+
+    #[proptest]
+    fn iss_longer_than_a_byte_is_rejected(#[strategy(256usize..1024)] len: usize) {
+        assert!(
+            ZkLoginPublicIdentifier::new("a".repeat(len), Bn254FieldElement::new([1; 32]))
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,446 @@
+//! Rendering of objects following the Sui Object Display standard.
+//!
+//! The publisher of a type `T` describes how its objects are displayed with a
+//! `0x2::display::Display<T>` object: a template for each of the fields shown by wallets and
+//! explorers, e.g. `name`, `description` or `image_url`, whose `{field.path}` placeholders are
+//! replaced with the values of the fields of each object.
+//!
+//! The current template of a type is carried by the last `0x2::display::VersionUpdated<T>` event
+//! emitted for it, which can be queried with an
+//! [`EventFilter::event_type`](crate::filter::EventFilter::event_type) for
+//! [`DisplayTemplate::version_updated_event_type`], then read with
+//! [`DisplayTemplate::try_from_event`]. Objects are rendered from the `fields` of their JSON-RPC
+//! `content`:
+//!
+//! ```
+//! use sui_sdk::display::DisplayTemplate;
+//!
+//! let template = DisplayTemplate::new(
+//!     "0x2::example::Hero".parse().unwrap(),
+//!     vec![
+//!         ("name".to_owned(), "{name} the hero".to_owned()),
+//!         ("image_url".to_owned(), "https://heroes.example/{id.id}.png".to_owned()),
+//!     ],
+//! );
+//! let rendered = template.render(&serde_json::json!({
+//!     "id": { "id": "0x42" },
+//!     "name": "Arthur",
+//! }));
+//! assert_eq!(rendered.name(), Some("Arthur the hero"));
+//! assert_eq!(rendered.image_url(), Some("https://heroes.example/0x42.png"));
+//! ```
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::types::Address;
+use crate::types::Event;
+use crate::types::Identifier;
+use crate::types::Object;
+use crate::types::ObjectData;
+use crate::types::ObjectId;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// The templates of a `0x2::display::Display<T>`, rendering objects of type `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisplayTemplate {
+    object_type: TypeTag,
+    version: u16,
+    fields: Vec<(String, String)>,
+}
+
+/// The BCS layout of a `0x2::display::Display<T>`.
+#[derive(serde_derive::Deserialize)]
+struct BinaryDisplay {
+    _id: ObjectId,
+    fields: Vec<(String, String)>,
+    version: u16,
+}
+
+/// The BCS layout of a `0x2::display::VersionUpdated<T>` event.
+#[derive(serde_derive::Deserialize)]
+struct BinaryVersionUpdated {
+    _id: ObjectId,
+    version: u16,
+    fields: Vec<(String, String)>,
+}
+
+impl DisplayTemplate {
+    /// Templates for objects of `object_type`, by field name.
+    pub fn new(object_type: TypeTag, fields: Vec<(String, String)>) -> Self {
+        Self {
+            object_type,
+            version: 0,
+            fields,
+        }
+    }
+
+    /// The type of the object holding the templates for objects of `object_type`,
+    /// `0x2::display::Display<T>`.
+    pub fn display_type(object_type: TypeTag) -> StructTag {
+        Self::framework_type("Display", object_type)
+    }
+
+    /// The type of the event emitted when the templates for objects of `object_type` are
+    /// updated, `0x2::display::VersionUpdated<T>`.
+    pub fn version_updated_event_type(object_type: TypeTag) -> StructTag {
+        Self::framework_type("VersionUpdated", object_type)
+    }
+
+    fn framework_type(name: &str, object_type: TypeTag) -> StructTag {
+        StructTag {
+            address: Address::TWO,
+            module: Identifier::new("display").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![object_type],
+        }
+    }
+
+    /// The type rendered by a `0x2::display::<name>` type, if `type_` is one.
+    fn rendered_type<'a>(type_: &'a StructTag, name: &str) -> Option<&'a TypeTag> {
+        let is_display = type_.address == Address::TWO
+            && type_.module == *"display"
+            && type_.name == *name
+            && type_.type_params.len() == 1;
+        is_display.then(|| &type_.type_params[0])
+    }
+
+    /// Read the templates of a `0x2::display::Display<T>` object.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let object_type = Self::rendered_type(&move_struct.type_, "Display")?;
+        let display: BinaryDisplay = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some(Self {
+            object_type: object_type.clone(),
+            version: display.version,
+            fields: display.fields,
+        })
+    }
+
+    /// Read the templates of a `0x2::display::VersionUpdated<T>` event.
+    pub fn try_from_event(event: &Event) -> Option<Self> {
+        let object_type = Self::rendered_type(&event.type_, "VersionUpdated")?;
+        let updated: BinaryVersionUpdated = bcs::from_bytes(&event.contents).ok()?;
+        Some(Self {
+            object_type: object_type.clone(),
+            version: updated.version,
+            fields: updated.fields,
+        })
+    }
+
+    /// The type of the objects rendered by these templates.
+    pub fn object_type(&self) -> &TypeTag {
+        &self.object_type
+    }
+
+    /// The version of the templates, incremented by each update of the `Display<T>` object.
+    pub fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// The template of each displayed field, by field name.
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// Render each field for an object whose Move fields are `contents`, as the `fields` of the
+    /// object's `content` in JSON-RPC.
+    pub fn render(&self, contents: &Value) -> RenderedDisplay {
+        let mut rendered = RenderedDisplay::default();
+        for (name, template) in &self.fields {
+            match render(template, contents) {
+                Ok(value) => {
+                    rendered.fields.insert(name.clone(), value);
+                }
+                Err(e) => {
+                    rendered.errors.insert(name.clone(), e);
+                }
+            }
+        }
+        rendered
+    }
+}
+
+/// Replace the placeholders of `template` with the fields of `contents` they name.
+fn render(template: &str, contents: &Value) -> Result<String, DisplayError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some(escaped @ ('{' | '}' | '\\')) => rendered.push(escaped),
+                Some(other) => {
+                    rendered.push('\\');
+                    rendered.push(other);
+                }
+                None => rendered.push('\\'),
+            },
+            '{' => {
+                let mut path = String::new();
+                let closed = chars.by_ref().any(|c| {
+                    let closing = c == '}';
+                    if !closing {
+                        path.push(c);
+                    }
+                    closing
+                });
+                if !closed || path.contains('{') || path.split('.').any(|s| s.trim().is_empty()) {
+                    return Err(DisplayError::InvalidTemplate(template.to_owned()));
+                }
+                rendered.push_str(&field(contents, &path)?);
+            }
+            '}' => return Err(DisplayError::InvalidTemplate(template.to_owned())),
+            c => rendered.push(c),
+        }
+    }
+    Ok(rendered)
+}
+
+/// The value of the field at the dotted `path` of `contents`, rendered as a string.
+fn field(contents: &Value, path: &str) -> Result<String, DisplayError> {
+    let mut value = contents;
+    for segment in path.split('.') {
+        value = fields_of(value)
+            .get(segment.trim())
+            .ok_or_else(|| DisplayError::MissingField(path.to_owned()))?;
+    }
+
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        // A `UID`, rendered as the id it wraps.
+        Value::Object(uid) if uid.len() == 1 && uid.get("id").is_some_and(Value::is_string) => {
+            Ok(uid["id"].as_str().unwrap().to_owned())
+        }
+        Value::Null | Value::Array(_) | Value::Object(_) => {
+            Err(DisplayError::UnsupportedValue(path.to_owned()))
+        }
+    }
+}
+
+/// The fields of a struct value, which JSON-RPC renders either directly as an object, or within
+/// the `fields` of an object along with its `type`.
+fn fields_of(value: &Value) -> &serde_json::Map<String, Value> {
+    static EMPTY: std::sync::OnceLock<serde_json::Map<String, Value>> = std::sync::OnceLock::new();
+
+    match value {
+        Value::Object(object) => match object.get("fields") {
+            Some(Value::Object(fields)) if object.contains_key("type") => fields,
+            _ => object,
+        },
+        _ => EMPTY.get_or_init(serde_json::Map::new),
+    }
+}
+
+/// The fields of an object rendered with a [`DisplayTemplate`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RenderedDisplay {
+    fields: BTreeMap<String, String>,
+    errors: BTreeMap<String, DisplayError>,
+}
+
+impl RenderedDisplay {
+    /// The rendered value of the field `name`, unless it failed to render.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+
+    /// The fields rendered successfully, by name.
+    pub fn fields(&self) -> &BTreeMap<String, String> {
+        &self.fields
+    }
+
+    /// The fields which failed to render, by name.
+    pub fn errors(&self) -> &BTreeMap<String, DisplayError> {
+        &self.errors
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.get("name")
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.get("description")
+    }
+
+    pub fn image_url(&self) -> Option<&str> {
+        self.get("image_url")
+    }
+
+    pub fn link(&self) -> Option<&str> {
+        self.get("link")
+    }
+
+    pub fn project_url(&self) -> Option<&str> {
+        self.get("project_url")
+    }
+
+    pub fn creator(&self) -> Option<&str> {
+        self.get("creator")
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DisplayError {
+    /// The template has an unbalanced brace or an empty placeholder.
+    InvalidTemplate(String),
+    /// A placeholder names a field the object doesn't have.
+    MissingField(String),
+    /// A placeholder names a field which isn't a string, number, boolean or id.
+    UnsupportedValue(String),
+}
+
+impl std::fmt::Display for DisplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidTemplate(template) => write!(f, "invalid display template {template:?}"),
+            Self::MissingField(path) => write!(f, "missing field {path}"),
+            Self::UnsupportedValue(path) => write!(f, "field {path} can't be displayed"),
+        }
+    }
+}
+
+impl std::error::Error for DisplayError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn hero() -> TypeTag {
+        "0x2::example::Hero".parse().unwrap()
+    }
+
+    fn template(fields: &[(&str, &str)]) -> DisplayTemplate {
+        DisplayTemplate::new(
+            hero(),
+            fields
+                .iter()
+                .map(|(name, template)| (name.to_string(), template.to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn render_fields() {
+        let contents = json!({
+            "id": { "id": "0x42" },
+            "name": "Arthur",
+            "level": 3,
+            "sword": {
+                "type": "0x2::example::Sword",
+                "fields": { "name": "Excalibur", "magic": true }
+            },
+        });
+        let rendered = template(&[
+            ("name", "{name}"),
+            ("description", "Level {level} hero wielding {sword.name}"),
+            (
+                "link",
+                "https://heroes.example/{ id.id }?magic={sword.magic}",
+            ),
+            ("creator", "\\{unknown\\}"),
+        ])
+        .render(&contents);
+
+        assert!(rendered.errors().is_empty());
+        assert_eq!(rendered.name(), Some("Arthur"));
+        assert_eq!(
+            rendered.description(),
+            Some("Level 3 hero wielding Excalibur")
+        );
+        assert_eq!(
+            rendered.link(),
+            Some("https://heroes.example/0x42?magic=true")
+        );
+        assert_eq!(rendered.creator(), Some("{unknown}"));
+        assert_eq!(rendered.image_url(), None);
+    }
+
+    #[test]
+    fn render_errors() {
+        let contents = json!({ "name": "Arthur", "sword": { "name": "Excalibur" }, "tags": [] });
+        let rendered = template(&[
+            ("name", "{name}"),
+            ("missing", "{shield.name}"),
+            ("nested", "{sword}"),
+            ("vector", "{tags}"),
+            ("unclosed", "{name"),
+            ("empty", "{}"),
+            ("unopened", "name}"),
+        ])
+        .render(&contents);
+
+        assert_eq!(rendered.name(), Some("Arthur"));
+        assert_eq!(
+            rendered.errors()["missing"],
+            DisplayError::MissingField("shield.name".to_owned())
+        );
+        assert_eq!(
+            rendered.errors()["nested"],
+            DisplayError::UnsupportedValue("sword".to_owned())
+        );
+        assert_eq!(
+            rendered.errors()["vector"],
+            DisplayError::UnsupportedValue("tags".to_owned())
+        );
+        for invalid in ["unclosed", "empty", "unopened"] {
+            assert!(matches!(
+                rendered.errors()[invalid],
+                DisplayError::InvalidTemplate(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn version_updated_event() {
+        let fields = vec![("name".to_owned(), "{name}".to_owned())];
+        let contents = bcs::to_bytes(&(ObjectId::ZERO, 2u16, &fields)).unwrap();
+        let event = Event {
+            package_id: ObjectId::from(Address::TWO),
+            module: Identifier::new("display").unwrap(),
+            sender: Address::ZERO,
+            type_: DisplayTemplate::version_updated_event_type(hero()),
+            contents,
+        };
+
+        let template = DisplayTemplate::try_from_event(&event).unwrap();
+        assert_eq!(template.object_type(), &hero());
+        assert_eq!(template.version(), 2);
+        assert_eq!(template.fields(), fields);
+
+        let other = Event {
+            type_: DisplayTemplate::display_type(hero()),
+            ..event
+        };
+        assert_eq!(DisplayTemplate::try_from_event(&other), None);
+    }
+
+    #[test]
+    fn display_object() {
+        use crate::types::Owner;
+        use crate::types::Version;
+
+        let fields = vec![("name".to_owned(), "{name}".to_owned())];
+        let contents = bcs::to_bytes(&(ObjectId::ZERO, &fields, 1u16)).unwrap();
+        let object = Object::new_move_object(
+            DisplayTemplate::display_type(hero()),
+            Version::new(1),
+            contents,
+            Owner::Immutable,
+        );
+
+        let template = DisplayTemplate::try_from_object(&object).unwrap();
+        assert_eq!(template.object_type(), &hero());
+        assert_eq!(template.version(), 1);
+        assert_eq!(template.fields(), fields);
+    }
+}
@@ -0,0 +1,1220 @@
+//! Conversions between the binary and human-readable encodings of Sui types.
+//!
+//! Every type is serialized differently depending on whether the serializer is human-readable:
+//! BCS gets the compact binary form that is hashed and signed, while JSON gets strings for
+//! addresses, digests and large integers. The helpers in this module pick the encoding explicitly
+//! so callers don't have to.
+//!
+//! CBOR and MessagePack are binary too, so they get the same form as BCS, with the fields of
+//! structs and names of variants added where the format describes them.
+
+use base64ct::Base64;
+use base64ct::Encoding;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::types::UnknownVariantError;
+
+/// Serialize `value` to BCS, encoded as a Base64 string.
+pub fn to_bcs_base64<T: Serialize + ?Sized>(value: &T) -> Result<String, bcs::Error> {
+    bcs::to_bytes(value).map(|bytes| Base64::encode_string(&bytes))
+}
+
+/// Deserialize a value from a Base64 encoded BCS string.
+pub fn from_bcs_base64<T: DeserializeOwned>(b64: &str) -> Result<T, BcsBase64Error> {
+    let bytes = Base64::decode_vec(b64).map_err(BcsBase64Error::Base64)?;
+    bcs::from_bytes(&bytes).map_err(BcsBase64Error::Bcs)
+}
+
+/// Deserialize a value from BCS, failing with [`BcsDecodeError::UnknownVariant`] on an enum
+/// variant added by a newer protocol version, see [`UnknownVariantError`].
+pub fn from_bcs<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, BcsDecodeError> {
+    // Forget a variant left by a failure this thread didn't ask about
+    UnknownVariantError::take_last();
+    bcs::from_bytes(bytes).map_err(|e| match UnknownVariantError::take_last() {
+        Some(unknown) => BcsDecodeError::UnknownVariant(unknown),
+        None => BcsDecodeError::Bcs(e),
+    })
+}
+
+/// The alphabet of Base45, that of the alphanumeric mode of QR codes.
+pub const BASE45_ALPHABET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// Encode `bytes` in the Base45 of RFC 9285, which QR codes store in their alphanumeric mode at
+/// 5.5 bits per character, more compactly than Base64 in their byte mode.
+pub fn to_base45(bytes: &[u8]) -> String {
+    let alphabet = BASE45_ALPHABET.as_bytes();
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(2) * 3);
+    for chunk in bytes.chunks(2) {
+        let (mut n, digits) = match chunk {
+            [a, b] => (usize::from(*a) * 256 + usize::from(*b), 3),
+            [a] => (usize::from(*a), 2),
+            _ => unreachable!(),
+        };
+        for _ in 0..digits {
+            encoded.push(alphabet[n % 45] as char);
+            n /= 45;
+        }
+    }
+    encoded
+}
+
+/// Decode a Base45 string, see [`to_base45`].
+pub fn from_base45(s: &str) -> Result<Vec<u8>, Base45Error> {
+    let digits = s
+        .chars()
+        .map(|c| {
+            BASE45_ALPHABET
+                .find(c)
+                .ok_or(Base45Error::InvalidCharacter(c))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut bytes = Vec::with_capacity(digits.len() / 3 * 2 + 1);
+    for chunk in digits.chunks(3) {
+        let n = chunk.iter().rev().fold(0, |n, digit| n * 45 + digit);
+        match chunk.len() {
+            3 if n <= 0xffff => bytes.extend((n as u16).to_be_bytes()),
+            2 if n <= 0xff => bytes.push(n as u8),
+            3 | 2 => return Err(Base45Error::InvalidValue),
+            _ => return Err(Base45Error::InvalidLength),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Serialize `value` to CBOR, using the binary encoding.
+#[cfg(feature = "cbor")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "cbor")))]
+pub fn to_cbor<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<Vec<u8>, ciborium::ser::Error<std::io::Error>> {
+    let mut bytes = vec![];
+    ciborium::into_writer(value, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Deserialize a value from CBOR, using the binary encoding.
+#[cfg(feature = "cbor")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "cbor")))]
+pub fn from_cbor<T: DeserializeOwned>(
+    bytes: &[u8],
+) -> Result<T, ciborium::de::Error<std::io::Error>> {
+    // Byte strings are read in one piece, so the buffer must be able to hold the largest of them
+    ciborium::de::from_reader_with_buffer(bytes, &mut vec![0; bytes.len()])
+}
+
+/// Serialize `value` to MessagePack, using the binary encoding, with structs as arrays of their
+/// fields.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "msgpack")))]
+pub fn to_msgpack<T: Serialize + ?Sized>(value: &T) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+    rmp_serde::to_vec(value)
+}
+
+/// Deserialize a value from MessagePack, using the binary encoding.
+#[cfg(feature = "msgpack")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "msgpack")))]
+pub fn from_msgpack<T: DeserializeOwned>(bytes: &[u8]) -> Result<T, rmp_serde::decode::Error> {
+    rmp_serde::from_slice(bytes)
+}
+
+/// Serialize `value` to a JSON value, using the human-readable encoding.
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub fn to_json_value<T: Serialize + ?Sized>(
+    value: &T,
+) -> Result<serde_json::Value, serde_json::Error> {
+    value.serialize(serde_json::value::Serializer)
+}
+
+/// Deserialize a value from a JSON value, using the human-readable encoding.
+///
+/// Errors report the path of the value which failed to deserialize, see [`JsonError`].
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub fn from_json_value<T: DeserializeOwned>(value: serde_json::Value) -> Result<T, JsonError> {
+    serde_path_to_error::deserialize(value).map_err(JsonError::new)
+}
+
+/// Deserialize a value from a JSON string, using the human-readable encoding.
+///
+/// Errors report the path of the value which failed to deserialize, see [`JsonError`].
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub fn from_json_str<T: DeserializeOwned>(json: &str) -> Result<T, JsonError> {
+    serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(json))
+        .map_err(JsonError::new)
+}
+
+/// Serialize `value` to canonical JSON, using the human-readable encoding, so that equal values
+/// always give the same string to hash or sign.
+///
+/// The output follows the JSON Canonicalization Scheme of RFC 8785: no whitespace, the fields of
+/// objects sorted by the UTF-16 code units of their names, strings escaping only `"`, `\\` and
+/// control characters, and numbers formatted like ECMAScript does, e.g. `1e+30` or `0.002`.
+///
+/// Integers are only exact as JSON numbers up to 2<sup>53</sup>, beyond which this fails. The SDK's
+/// types encode their large integers, like balances, as strings, so this only concerns integers
+/// within user data.
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub fn to_canonical_json<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let value = to_json_value(value)?;
+    let mut json = String::new();
+    canonical::write(&mut json, &value)?;
+    Ok(json)
+}
+
+/// A wrapper serializing `T` following the conventions of the GraphQL API: camelCase field names
+/// and SCREAMING_SNAKE_CASE enum variants, e.g. `{"kind": "PROGRAMMABLE_TRANSACTION"}`.
+///
+/// This lets responses of the GraphQL API be deserialized directly into SDK types. The value is
+/// converted from and to the human-readable encoding of `T`, so the wrapper only supports
+/// self-describing formats like JSON.
+///
+/// Enum variants are only renamed when they are the value of one of the tag fields used by the
+/// SDK, like `kind` or `type`, and name one of the variants of the enums tagged by that field, so
+/// other strings held by a field of the same name are kept as is. The keys of maps which hold
+/// data rather than fields, like the `modules` of a package, are kept as is too.
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GraphQl<T>(pub T);
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<T: Serialize> Serialize for GraphQl<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let value = to_json_value(&self.0).map_err(serde::ser::Error::custom)?;
+        graphql::convert(value, graphql::Case::Camel).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<'de, T: DeserializeOwned> serde::Deserialize<'de> for GraphQl<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+        from_json_value(graphql::convert(value, graphql::Case::Snake))
+            .map(Self)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// A wrapper deserializing `T` from JSON strictly, failing on fields `T` doesn't have.
+///
+/// The SDK's types ignore unknown fields when deserialized directly, so that JSON from newer node
+/// versions, which may have added fields, can still be read. Wrapping them in `Strict` rejects
+/// such input instead, e.g. for a gateway to validate the requests it forwards.
+///
+/// Unknown fields are found by serializing the deserialized value back and looking for the fields
+/// of the input which are missing from it. Fields set to `null` or to an empty array are accepted
+/// as long as they are absent from the output, as they are usually omitted default values.
+/// Serialization is that of `T`.
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Strict<T>(pub T);
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<T: Serialize> Serialize for Strict<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<'de, T: Serialize + DeserializeOwned> serde::Deserialize<'de> for Strict<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+
+        let input = <serde_json::Value as serde::Deserialize>::deserialize(deserializer)?;
+        let value: T = from_json_value(input.clone()).map_err(D::Error::custom)?;
+        let output = to_json_value(&value).map_err(D::Error::custom)?;
+        match strict::unknown_field(&input, &output, String::new()) {
+            Some(path) => Err(D::Error::custom(format_args!("unknown field `{path}`"))),
+            None => Ok(Self(value)),
+        }
+    }
+}
+
+/// A wrapper serializing the values of the pure inputs within `T` as `0x` prefixed hex rather than
+/// Base64, e.g. `{"type": "pure", "value": "0x0102ff"}`.
+///
+/// Pure values are read from either encoding, as well as from arrays of bytes, so deserialization
+/// is that of `T`.
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PureHex<T>(pub T);
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<T: Serialize> Serialize for PureHex<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut value = to_json_value(&self.0).map_err(serde::ser::Error::custom)?;
+        pure_hex(&mut value);
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for PureHex<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+#[cfg(feature = "json")]
+fn pure_hex(value: &mut serde_json::Value) {
+    use crate::types::InputArgument;
+    use serde_json::Value;
+
+    match value {
+        Value::Array(values) => values.iter_mut().for_each(pure_hex),
+        Value::Object(fields) if fields.get("type").is_some_and(|tag| tag == "pure") => {
+            if let Ok(InputArgument::Pure { value: bytes }) =
+                serde::Deserialize::deserialize(&*value)
+            {
+                value["value"] = Value::String(format!("0x{}", hex::encode(bytes)));
+            }
+        }
+        Value::Object(fields) => fields.values_mut().for_each(pure_hex),
+        _ => {}
+    }
+}
+
+#[cfg(feature = "json")]
+mod strict {
+    use serde_json::Value;
+
+    /// The path of the first field of `input` which is missing from `output`.
+    pub(super) fn unknown_field(input: &Value, output: &Value, path: String) -> Option<String> {
+        match (input, output) {
+            (Value::Object(input), Value::Object(output)) => {
+                input.iter().find_map(|(key, value)| {
+                    let path = if path.is_empty() {
+                        key.clone()
+                    } else {
+                        format!("{path}.{key}")
+                    };
+                    match output.get(key) {
+                        Some(output) => unknown_field(value, output, path),
+                        None if is_default(value) => None,
+                        None => Some(path),
+                    }
+                })
+            }
+            (Value::Array(input), Value::Array(output)) => input
+                .iter()
+                .zip(output)
+                .enumerate()
+                .find_map(|(i, (input, output))| {
+                    unknown_field(input, output, format!("{path}[{i}]"))
+                }),
+            _ => None,
+        }
+    }
+
+    fn is_default(value: &Value) -> bool {
+        match value {
+            Value::Null => true,
+            Value::Array(values) => values.is_empty(),
+            _ => false,
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+mod canonical {
+    use serde::ser::Error;
+    use serde_json::Number;
+    use serde_json::Value;
+
+    /// The largest integer from which all smaller ones are exact as doubles.
+    const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+    pub(super) fn write(json: &mut String, value: &Value) -> Result<(), serde_json::Error> {
+        match value {
+            Value::Null => json.push_str("null"),
+            Value::Bool(b) => json.push_str(if *b { "true" } else { "false" }),
+            Value::Number(n) => write_number(json, n)?,
+            // serde_json escapes only what RFC 8785 does, using lowercase hex
+            Value::String(s) => json.push_str(&serde_json::to_string(s)?),
+            Value::Array(values) => {
+                json.push('[');
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    write(json, value)?;
+                }
+                json.push(']');
+            }
+            Value::Object(fields) => {
+                let mut fields: Vec<_> = fields.iter().collect();
+                fields.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+                json.push('{');
+                for (i, (key, value)) in fields.into_iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    json.push_str(&serde_json::to_string(key)?);
+                    json.push(':');
+                    write(json, value)?;
+                }
+                json.push('}');
+            }
+        }
+        Ok(())
+    }
+
+    fn write_number(json: &mut String, n: &Number) -> Result<(), serde_json::Error> {
+        if let Some(u) = n.as_u64() {
+            if u > MAX_SAFE_INTEGER {
+                return Err(serde_json::Error::custom(format_args!(
+                    "integer {u} is too large to be canonicalized"
+                )));
+            }
+            json.push_str(&u.to_string());
+        } else if let Some(i) = n.as_i64() {
+            if i.unsigned_abs() > MAX_SAFE_INTEGER {
+                return Err(serde_json::Error::custom(format_args!(
+                    "integer {i} is too large to be canonicalized"
+                )));
+            }
+            json.push_str(&i.to_string());
+        } else if let Some(f) = n.as_f64() {
+            write_double(json, f);
+        }
+        Ok(())
+    }
+
+    /// Format a finite double like ECMAScript's `Number.prototype.toString`, from the shortest
+    /// digits which round-trip, which Rust's `{:e}` gives as well.
+    fn write_double(json: &mut String, f: f64) {
+        if f == 0.0 {
+            json.push('0');
+            return;
+        }
+        if f < 0.0 {
+            json.push('-');
+        }
+        let scientific = format!("{:e}", f.abs());
+        let (mantissa, exponent) = scientific.split_once('e').unwrap();
+        let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+        let k = digits.len() as i32;
+        // The position of the decimal point relative to the first digit
+        let n = exponent.parse::<i32>().unwrap() + 1;
+        if k <= n && n <= 21 {
+            json.push_str(&digits);
+            json.extend(std::iter::repeat_n('0', (n - k) as usize));
+        } else if 0 < n && n <= 21 {
+            let (integer, fraction) = digits.split_at(n as usize);
+            json.push_str(integer);
+            json.push('.');
+            json.push_str(fraction);
+        } else if -6 < n && n <= 0 {
+            json.push_str("0.");
+            json.extend(std::iter::repeat_n('0', -n as usize));
+            json.push_str(&digits);
+        } else {
+            let (first, rest) = digits.split_at(1);
+            json.push_str(first);
+            if !rest.is_empty() {
+                json.push('.');
+                json.push_str(rest);
+            }
+            json.push('e');
+            json.push(if n > 0 { '+' } else { '-' });
+            json.push_str(&(n - 1).unsigned_abs().to_string());
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+mod graphql {
+    use serde_json::Value;
+
+    /// The fields holding the variant of an enum, along with the variants, as named by the serde
+    /// attributes of the enums tagged by each field.
+    pub(super) const VARIANTS: &[(&str, &[&str])] = &[
+        (
+            "command",
+            &[
+                "move_call",
+                "transfer_objects",
+                "split_coins",
+                "merge_coins",
+                "publish",
+                "make_move_vector",
+                "upgrade",
+            ],
+        ),
+        (
+            "error",
+            &[
+                "insufficient_gas",
+                "invalid_gas_object",
+                "invariant_violation",
+                "feature_not_yet_supported",
+                "object_too_big",
+                "package_too_big",
+                "circular_object_ownership",
+                "insufficient_coin_balance",
+                "coin_balance_overflow",
+                "publish_error_non_zero_address",
+                "sui_move_verification_error",
+                "move_primitive_runtime_error",
+                "move_abort",
+                "vm_verification_or_deserialization_error",
+                "vm_invariant_violation",
+                "function_not_found",
+                "arity_mismatch",
+                "type_arity_mismatch",
+                "non_entry_function_invoked",
+                "command_argument_error",
+                "type_argument_error",
+                "unused_value_without_drop",
+                "invalid_public_function_return_type",
+                "invalid_transfer_object",
+                "effects_too_large",
+                "publish_upgrade_missing_dependency",
+                "publish_upgrade_dependency_downgrade",
+                "package_upgrade_error",
+                "written_objects_too_large",
+                "certificate_denied",
+                "sui_move_verification_timedout",
+                "shared_object_operation_not_allowed",
+                "input_object_deleted",
+            ],
+        ),
+        (
+            "kind",
+            &[
+                // CommandArgumentError
+                "type_mismatch",
+                "invalid_bcs_bytes",
+                "invalid_usage_of_pure_argument",
+                "invalid_argument_to_private_entry_function",
+                "index_out_of_bounds",
+                "secondary_index_out_of_bounds",
+                "invalid_result_arity",
+                "invalid_gas_coin_usage",
+                "invalid_value_usage",
+                "invalid_object_by_value",
+                "invalid_object_by_mut_ref",
+                "shared_object_operation_not_allowed",
+                // PackageUpgradeError
+                "unable_to_fetch_package",
+                "not_a_package",
+                "incompatible_upgrade",
+                "digest_does_not_match",
+                "unknown_upgrade_policy",
+                "package_id_does_not_match",
+                // UnchangedSharedKind
+                "read_only_root",
+                "mutate_deleted",
+                "read_deleted",
+                // TransactionKind and EndOfEpochTransactionKind
+                "programmable_transaction",
+                "change_epoch",
+                "genesis",
+                "consensus_commit_prologue",
+                "authenticator_state_update",
+                "end_of_epoch",
+                "randomness_state_update",
+                "consensus_commit_prologue_v2",
+                "authenticator_state_create",
+                "authenticator_state_expire",
+                "randomness_state_create",
+                "deny_list_state_create",
+                "bridge_state_create",
+                "bridge_committee_init",
+            ],
+        ),
+        (
+            "owner",
+            &[
+                "address",
+                "object",
+                "shared",
+                "immutable",
+                "consensus_address_owner",
+            ],
+        ),
+        (
+            "scheme",
+            &["ed25519", "secp256k1", "secp256r1", "multisig", "zklogin"],
+        ),
+        (
+            "state",
+            &["not_exist", "exist", "object_write", "package_write"],
+        ),
+        (
+            "type",
+            &[
+                "ecmh_live_object_set",
+                "pure",
+                "immutable_or_owned",
+                "shared",
+                "receiving",
+                "gas_coin",
+                "input",
+                "result",
+                "nested_result",
+            ],
+        ),
+    ];
+
+    /// The fields holding maps keyed by data, e.g. module names, rather than by field names.
+    const MAPS: &[&str] = &["modules", "linkage_table"];
+
+    #[derive(Clone, Copy)]
+    pub(super) enum Case {
+        /// From the SDK's snake_case to GraphQL's camelCase.
+        Camel,
+        /// From GraphQL's camelCase to the SDK's snake_case.
+        Snake,
+    }
+
+    pub(super) fn convert(value: Value, case: Case) -> Value {
+        match value {
+            Value::Array(values) => values
+                .into_iter()
+                .map(|value| convert(value, case))
+                .collect(),
+            Value::Object(fields) => fields
+                .into_iter()
+                .map(|(key, value)| {
+                    let (snake, converted) = match case {
+                        Case::Camel => (key.clone(), to_camel_case(&key)),
+                        Case::Snake => (to_snake_case(&key), to_snake_case(&key)),
+                    };
+                    let variants = VARIANTS
+                        .iter()
+                        .find(|(tag, _)| *tag == snake)
+                        .map(|(_, variants)| *variants);
+                    let value = match (value, variants) {
+                        (Value::String(variant), Some(variants)) => {
+                            Value::String(convert_variant(variant, variants, case))
+                        }
+                        (Value::Object(map), _) if MAPS.contains(&snake.as_str()) => map
+                            .into_iter()
+                            .map(|(key, value)| (key, convert(value, case)))
+                            .collect(),
+                        (value, _) => convert(value, case),
+                    };
+                    (converted, value)
+                })
+                .collect(),
+            value => value,
+        }
+    }
+
+    fn to_camel_case(snake: &str) -> String {
+        let mut camel = String::with_capacity(snake.len());
+        let mut chars = snake.chars().peekable();
+        while let Some(c) = chars.next() {
+            match chars.peek() {
+                Some(next) if c == '_' && next.is_ascii_lowercase() => {
+                    camel.push(next.to_ascii_uppercase());
+                    chars.next();
+                }
+                _ => camel.push(c),
+            }
+        }
+        camel
+    }
+
+    fn to_snake_case(camel: &str) -> String {
+        let mut snake = String::with_capacity(camel.len() + 4);
+        for c in camel.chars() {
+            if c.is_ascii_uppercase() {
+                snake.push('_');
+                snake.push(c.to_ascii_lowercase());
+            } else {
+                snake.push(c);
+            }
+        }
+        snake
+    }
+
+    /// Change the case of a known enum variant, leaving any other value, e.g. a type tag or a
+    /// string held by a field which only shares its name with a tag, as is.
+    fn convert_variant(variant: String, variants: &[&str], case: Case) -> String {
+        match case {
+            Case::Camel if variants.contains(&variant.as_str()) => variant.to_ascii_uppercase(),
+            Case::Snake
+                if variants
+                    .iter()
+                    .any(|known| known.to_ascii_uppercase() == variant) =>
+            {
+                variant.to_ascii_lowercase()
+            }
+            _ => variant,
+        }
+    }
+}
+
+/// An error deserializing JSON, along with the path of the value which failed to deserialize, e.g.
+/// `inputs[1].type`.
+///
+/// The path can only be followed through values which serde reads one by one. It stops at values
+/// of enums tagged by a field, like `Command`, and at fields flattened into their parent, as those
+/// are buffered before being read: an error within a command is reported at `commands[1]`, and
+/// errors within the fields of a [`Transaction`] come without a path. Deserialize the
+/// [`ProgrammableTransaction`] of a transaction on its own to locate them.
+///
+/// [`Transaction`]: crate::types::Transaction
+/// [`ProgrammableTransaction`]: crate::types::ProgrammableTransaction
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+#[derive(Debug)]
+pub struct JsonError {
+    path: String,
+    error: serde_json::Error,
+}
+
+#[cfg(feature = "json")]
+impl JsonError {
+    fn new(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        Self {
+            path: error.path().to_string(),
+            error: error.into_inner(),
+        }
+    }
+
+    /// The path of the value which failed to deserialize, `.` if it is the root.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn inner(&self) -> &serde_json::Error {
+        &self.error
+    }
+
+    pub fn into_inner(self) -> serde_json::Error {
+        self.error
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path == "." {
+            write!(f, "{}", self.error)
+        } else {
+            write!(f, "{}: {}", self.path, self.error)
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[derive(Debug)]
+pub enum BcsBase64Error {
+    Base64(base64ct::Error),
+    Bcs(bcs::Error),
+}
+
+impl std::fmt::Display for BcsBase64Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Base64(e) => write!(f, "invalid Base64: {e}"),
+            Self::Bcs(e) => write!(f, "invalid BCS: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BcsBase64Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Base64(e) => Some(e),
+            Self::Bcs(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BcsDecodeError {
+    /// An enum holds a variant unknown to this crate.
+    UnknownVariant(UnknownVariantError),
+    Bcs(bcs::Error),
+}
+
+impl std::fmt::Display for BcsDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownVariant(e) => write!(f, "{e}"),
+            Self::Bcs(e) => write!(f, "invalid BCS: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for BcsDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::UnknownVariant(e) => Some(e),
+            Self::Bcs(e) => Some(e),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Base45Error {
+    InvalidCharacter(char),
+    /// The string ends with a single character.
+    InvalidLength,
+    /// A group of characters encodes a value too large for its bytes.
+    InvalidValue,
+}
+
+impl std::fmt::Display for Base45Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidCharacter(c) => write!(f, "invalid Base45 character {c:?}"),
+            Self::InvalidLength => write!(f, "invalid Base45 length"),
+            Self::InvalidValue => write!(f, "invalid Base45 value"),
+        }
+    }
+}
+
+impl std::error::Error for Base45Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::EpochId;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn bcs_base64() {
+        let epoch = EpochId::new(1);
+        let b64 = to_bcs_base64(&epoch).unwrap();
+        assert_eq!(b64, "AQAAAAAAAAA=");
+        assert_eq!(from_bcs_base64::<EpochId>(&b64).unwrap(), epoch);
+
+        assert!(matches!(
+            from_bcs_base64::<EpochId>("not base64!"),
+            Err(BcsBase64Error::Base64(_))
+        ));
+        assert!(matches!(
+            from_bcs_base64::<EpochId>("AQ=="),
+            Err(BcsBase64Error::Bcs(_))
+        ));
+    }
+
+    #[test]
+    fn base45() {
+        for (bytes, encoded) in [
+            (&b"AB"[..], "BB8"),
+            (b"Hello!!", "%69 VD92EX0"),
+            (b"base-45", "UJCLQE7W581"),
+            (b"ietf!", "QED8WEX0"),
+            (b"", ""),
+        ] {
+            assert_eq!(to_base45(bytes), encoded);
+            assert_eq!(from_base45(encoded).unwrap(), bytes);
+        }
+
+        assert_eq!(from_base45("GGW"), Err(Base45Error::InvalidValue));
+        assert_eq!(from_base45("BB8B"), Err(Base45Error::InvalidLength));
+        assert_eq!(from_base45("bb8"), Err(Base45Error::InvalidCharacter('b')));
+    }
+
+    #[test]
+    #[cfg(all(feature = "cbor", feature = "msgpack"))]
+    fn self_describing_binary_fixtures() {
+        use crate::types::CheckpointContents;
+        use crate::types::Transaction;
+        use crate::types::TransactionEffects;
+
+        fn assert_roundtrip<T>(b64: &str)
+        where
+            T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+        {
+            let value: T = from_bcs_base64(b64.trim()).unwrap();
+            assert_eq!(from_cbor::<T>(&to_cbor(&value).unwrap()).unwrap(), value);
+            assert_eq!(
+                from_msgpack::<T>(&to_msgpack(&value).unwrap()).unwrap(),
+                value
+            );
+        }
+
+        for transaction in [
+            include_str!("types/transaction/fixtures/genesis-transaction"),
+            include_str!("types/transaction/fixtures/authenticator_state_update"),
+            include_str!("types/transaction/fixtures/wormhole-pyth-transaction"),
+        ] {
+            assert_roundtrip::<Transaction>(transaction);
+        }
+        for effects in [
+            include_str!("types/effects/fixtures/genesis-transaction-effects"),
+            include_str!("types/effects/fixtures/pyth-wormhole-v2"),
+        ] {
+            assert_roundtrip::<TransactionEffects>(effects);
+        }
+        assert_roundtrip::<CheckpointContents>("AAEgp6oAB8Qadn8+FqtdqeDIp8ViQNOZpMKs44MN0N5y7zIgqn5dKR1+8poL0pLNwRo/2knMnodwMTEDhqYL03kdewQBAWEAgpORkfH6ewjfFQYZJhmjkYq0/B3Set4mLJX/G0wUPb/V4H41gJipYu4I6ToyixnEuPQWxHKLckhNn+0UmI+pAJ9GegzEh0q2HWABmFMpFoPw0229dCfzWNOhHW5bes4H");
+
+        // Variants are named, unlike in BCS
+        let cbor = to_cbor(&crate::types::TypeTag::U64).unwrap();
+        assert_eq!(
+            ciborium::from_reader::<ciborium::Value, _>(cbor.as_slice()).unwrap(),
+            ciborium::Value::Text("u64".to_owned())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_value() {
+        use crate::types::Address;
+
+        let json = to_json_value(&Address::TWO).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!("0x0000000000000000000000000000000000000000000000000000000000000002")
+        );
+        assert_eq!(from_json_value::<Address>(json).unwrap(), Address::TWO);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_error_path() {
+        use crate::types::GasPayment;
+        use crate::types::ProgrammableTransaction;
+
+        let json = serde_json::json!({
+            "inputs": [{"type": "pure", "value": "AQ=="}],
+            "commands": [
+                {"command": "merge_coins", "coin": {"type": "gas_coin"}, "coins_to_merge": [{"type": "input", "input": 0}]},
+                {"command": "merge_coins", "coin": {"type": "gas_coin"}, "coins_to_merge": [{"type": "output", "input": 0}]},
+            ],
+        });
+        let error = from_json_value::<ProgrammableTransaction>(json).unwrap_err();
+        assert_eq!(error.path(), "commands[1]");
+        assert!(error
+            .to_string()
+            .starts_with("commands[1]: unknown variant `output`, expected one of"));
+
+        let json = serde_json::json!({
+            "inputs": [{"type": "pure", "value": "AQ=="}, {"type": "object"}],
+            "commands": [],
+        });
+        let error = from_json_value::<ProgrammableTransaction>(json).unwrap_err();
+        assert_eq!(error.path(), "inputs[1].type");
+
+        let json = r#"{
+            "objects": [],
+            "owner": "0x2",
+            "price": "1000",
+            "budget": "-1"
+        }"#;
+        let error = from_json_str::<GasPayment>(json).unwrap_err();
+        assert_eq!(error.path(), "budget");
+
+        let error = from_json_str::<GasPayment>("[]").unwrap_err();
+        assert_eq!(error.path(), ".");
+        assert_eq!(error.to_string(), error.inner().to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn canonical_json() {
+        use crate::types::GasCostSummary;
+
+        // The examples of RFC 8785, with numbers parsed by Rust as serde_json doesn't always round
+        // them correctly
+        let mut json: serde_json::Value = serde_json::from_str(
+            r#"{
+                "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/",
+                "literals": [null, true, false]
+            }"#,
+        )
+        .unwrap();
+        #[allow(clippy::excessive_precision)]
+        let numbers = serde_json::json!([
+            333333333.33333329,
+            1E30,
+            4.50,
+            2e-3,
+            0.000000000000000000000000001
+        ]);
+        json["numbers"] = numbers;
+        assert_eq!(
+            to_canonical_json(&json).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+
+        let json: serde_json::Value = serde_json::from_str(
+            r#"{"\u20ac": 1, "\r": 2, "\ufb33": 3, "1": 4, "\ud83d\ude00": 5, "\u0080": 6, "\u00f6": 7}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            to_canonical_json(&json).unwrap(),
+            "{\"\\r\":2,\"1\":4,\"\u{80}\":6,\"ö\":7,\"€\":1,\"😀\":5,\"\u{fb33}\":3}"
+        );
+
+        let json = serde_json::json!([
+            0.0,
+            -0.0,
+            -1.5,
+            100.0,
+            1e21,
+            1e-7,
+            123e-20,
+            9007199254740991u64
+        ]);
+        assert_eq!(
+            to_canonical_json(&json).unwrap(),
+            "[0,0,-1.5,100,1e+21,1e-7,1.23e-18,9007199254740991]"
+        );
+        assert!(to_canonical_json(&9007199254740992u64).is_err());
+        assert!(to_canonical_json(&i64::MIN).is_err());
+
+        let summary = GasCostSummary::new(1000, 2000, 500, 5);
+        assert_eq!(
+            to_canonical_json(&summary).unwrap(),
+            r#"{"computation_cost":"1000","non_refundable_storage_fee":"5","storage_cost":"2000","storage_rebate":"500"}"#
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn graphql() {
+        use crate::types::Argument;
+        use crate::types::GasCostSummary;
+
+        let json = serde_json::json!({
+            "computationCost": "1000",
+            "storageCost": "2000",
+            "storageRebate": "500",
+            "nonRefundableStorageFee": "5",
+        });
+        let GraphQl(summary) =
+            serde_json::from_value::<GraphQl<GasCostSummary>>(json.clone()).unwrap();
+        assert_eq!(summary, GasCostSummary::new(1000, 2000, 500, 5));
+        assert_eq!(serde_json::to_value(GraphQl(summary)).unwrap(), json);
+
+        let json = serde_json::json!({"type": "NESTED_RESULT", "result": 3, "subresult": 4});
+        assert_eq!(
+            serde_json::to_value(GraphQl(Argument::NestedResult(3, 4))).unwrap(),
+            json
+        );
+        assert_eq!(
+            serde_json::from_value::<GraphQl<Argument>>(json).unwrap(),
+            GraphQl(Argument::NestedResult(3, 4))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn graphql_unknown_variants() {
+        use graphql::Case;
+
+        let json = serde_json::json!({
+            "kind": "change_epoch",
+            "type": "custom_type",
+            "error": {"type": "pure", "state": "0x2::coin::Coin<0x2::sui::SUI>"},
+        });
+        let converted = graphql::convert(json.clone(), Case::Camel);
+        assert_eq!(
+            converted,
+            serde_json::json!({
+                "kind": "CHANGE_EPOCH",
+                "type": "custom_type",
+                "error": {"type": "PURE", "state": "0x2::coin::Coin<0x2::sui::SUI>"},
+            })
+        );
+        assert_eq!(graphql::convert(converted, Case::Snake), json);
+
+        let json = serde_json::json!({"kind": "CUSTOM_KIND", "type": "Pure"});
+        assert_eq!(graphql::convert(json.clone(), Case::Snake), json);
+    }
+
+    /// Every variant the SDK writes under a tag field is one GraphQl knows to rename.
+    #[cfg(feature = "json")]
+    macro_rules! graphql_variants_test {
+        ($type:ident) => {
+            paste::item! {
+                #[test_strategy::proptest]
+                #[allow(non_snake_case)]
+                fn [< graphql_variants_ $type >] (instance: crate::types::$type) {
+                    let json = to_json_value(&instance).unwrap();
+                    for (tag, variants) in graphql::VARIANTS {
+                        if let Some(variant) = json.get(tag).and_then(|v| v.as_str()) {
+                            assert!(variants.contains(&variant), "{tag}: {variant}");
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    #[cfg(feature = "json")]
+    mod graphql_variants {
+        use super::*;
+
+        graphql_variants_test!(Argument);
+        graphql_variants_test!(CheckpointCommitment);
+        graphql_variants_test!(Command);
+        graphql_variants_test!(CommandArgumentError);
+        graphql_variants_test!(EndOfEpochTransactionKind);
+        graphql_variants_test!(ExecutionError);
+        graphql_variants_test!(InputArgument);
+        graphql_variants_test!(MultisigMemberPublicKey);
+        graphql_variants_test!(MultisigMemberSignature);
+        graphql_variants_test!(ObjectIn);
+        graphql_variants_test!(ObjectOut);
+        graphql_variants_test!(Owner);
+        graphql_variants_test!(PackageUpgradeError);
+        graphql_variants_test!(SimpleSignature);
+        graphql_variants_test!(TransactionKind);
+        graphql_variants_test!(UnchangedSharedKind);
+        graphql_variants_test!(UserSignature);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn strict() {
+        use crate::types::Argument;
+        use crate::types::GasCostSummary;
+
+        let json = serde_json::json!({
+            "computation_cost": "1000",
+            "storage_cost": "2000",
+            "storage_rebate": "500",
+            "non_refundable_storage_fee": "5",
+        });
+        let Strict(summary) = serde_json::from_value::<Strict<GasCostSummary>>(json).unwrap();
+        assert_eq!(summary, GasCostSummary::new(1000, 2000, 500, 5));
+
+        let json =
+            serde_json::json!({"type": "nested_result", "result": 3, "subresult": 4, "extra": 1});
+        assert_eq!(
+            serde_json::from_value::<Argument>(json.clone()).unwrap(),
+            Argument::NestedResult(3, 4)
+        );
+        let error = serde_json::from_value::<Strict<Argument>>(json).unwrap_err();
+        assert_eq!(error.to_string(), "unknown field `extra`");
+    }
+
+    #[test]
+    #[cfg(all(feature = "json", feature = "rand"))]
+    fn strict_transaction() {
+        use crate::types::Transaction;
+
+        let transaction = Transaction::random();
+        let mut json = to_json_value(&transaction).unwrap();
+        assert_eq!(
+            serde_json::from_value::<Strict<Transaction>>(json.clone()).unwrap(),
+            Strict(transaction.clone())
+        );
+
+        json["gas_payment"]["objects"][0]["extra"] = serde_json::json!(true);
+        assert_eq!(
+            serde_json::from_value::<Transaction>(json.clone()).unwrap(),
+            transaction
+        );
+        let error = serde_json::from_value::<Strict<Transaction>>(json).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unknown field `gas_payment.objects[0].extra`"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn pure_hex() {
+        use crate::types::InputArgument;
+        use crate::types::ProgrammableTransaction;
+
+        let ptb = ProgrammableTransaction {
+            inputs: vec![
+                InputArgument::Pure {
+                    value: vec![1, 2, 255],
+                },
+                InputArgument::Pure { value: vec![] },
+            ],
+            commands: vec![],
+        };
+        let json = serde_json::to_value(PureHex(&ptb)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "inputs": [
+                    {"type": "pure", "value": "0x0102ff"},
+                    {"type": "pure", "value": "0x"},
+                ],
+                "commands": [],
+            })
+        );
+        assert_eq!(
+            serde_json::from_value::<PureHex<ProgrammableTransaction>>(json).unwrap(),
+            PureHex(ptb)
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "json", feature = "rand"))]
+    fn graphql_roundtrip() {
+        use crate::types::Transaction;
+
+        let transaction = Transaction::random();
+        let json = serde_json::to_value(GraphQl(&transaction)).unwrap();
+        assert_eq!(json["kind"], "PROGRAMMABLE_TRANSACTION");
+        assert_eq!(json["inputs"][0]["type"], "IMMUTABLE_OR_OWNED");
+        assert!(json["gasPayment"]["objects"][0]["objectId"].is_string());
+        assert_eq!(
+            serde_json::from_value::<GraphQl<Transaction>>(json).unwrap(),
+            GraphQl(transaction)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn transactions() {
+        use crate::types::SignedTransaction;
+        use crate::types::Transaction;
+
+        let transaction = Transaction::random();
+        let b64 = transaction.to_bcs_base64().unwrap();
+        assert_eq!(Transaction::from_bcs_base64(&b64).unwrap(), transaction);
+
+        let signed = SignedTransaction {
+            transaction,
+            signatures: vec![],
+        };
+        let b64 = signed.to_bcs_base64().unwrap();
+        assert_eq!(SignedTransaction::from_bcs_base64(&b64).unwrap(), signed);
+    }
+}
@@ -0,0 +1,410 @@
+//! Typed filters for event and transaction queries and subscriptions.
+//!
+//! [`EventFilter`] and [`TransactionFilter`] serialize to the filters of the JSON-RPC API, e.g.
+//! the `query` parameter of `suix_queryEvents` or `suix_subscribeEvent`:
+//!
+//! ```
+//! use sui_sdk::filter::EventFilter;
+//! use sui_sdk::types::Address;
+//! use sui_sdk::types::ObjectId;
+//!
+//! let filter = EventFilter::module(ObjectId::from(Address::TWO), "coin".parse().unwrap())
+//!     .and(EventFilter::time_range(1_700_000_000_000, 1_700_000_600_000));
+//! let query = serde_json::to_value(&filter).unwrap();
+//! assert_eq!(query["And"][1]["TimeRange"]["startTime"], "1700000000000");
+//! ```
+//!
+//! The filters of the GraphQL API are less expressive: they can only be a conjunction of
+//! criteria, each given at most once. Filters within those limits convert to a
+//! [`GraphQlEventFilter`] or [`GraphQlTransactionFilter`].
+
+use crate::types::Address;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::Identifier;
+use crate::types::ObjectId;
+use crate::types::StructTag;
+use crate::types::TransactionDigest;
+
+/// A filter of the events matched by a query or subscription, as `EventFilter` in JSON-RPC.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum EventFilter {
+    /// Events emitted by transactions sent by an address.
+    Sender(Address),
+    /// Events emitted by a transaction.
+    Transaction(TransactionDigest),
+    /// Events emitted by the modules of a package.
+    Package(ObjectId),
+    /// Events emitted by a module, whatever the module defining their type.
+    MoveModule {
+        package: ObjectId,
+        module: Identifier,
+    },
+    /// Events of a type.
+    MoveEventType(StructTag),
+    /// Events of a type defined in a module, wherever they are emitted.
+    MoveEventModule {
+        package: ObjectId,
+        module: Identifier,
+    },
+    /// Events emitted within a range of timestamps, in milliseconds since the Unix epoch.
+    #[serde(rename_all = "camelCase")]
+    TimeRange {
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        start_time: u64,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        end_time: u64,
+    },
+    All(Vec<EventFilter>),
+    Any(Vec<EventFilter>),
+    And(Box<EventFilter>, Box<EventFilter>),
+    Or(Box<EventFilter>, Box<EventFilter>),
+}
+
+impl EventFilter {
+    pub fn sender(sender: Address) -> Self {
+        Self::Sender(sender)
+    }
+
+    pub fn transaction(digest: TransactionDigest) -> Self {
+        Self::Transaction(digest)
+    }
+
+    pub fn package(package: ObjectId) -> Self {
+        Self::Package(package)
+    }
+
+    pub fn module(package: ObjectId, module: Identifier) -> Self {
+        Self::MoveModule { package, module }
+    }
+
+    pub fn event_type(event_type: StructTag) -> Self {
+        Self::MoveEventType(event_type)
+    }
+
+    pub fn event_module(package: ObjectId, module: Identifier) -> Self {
+        Self::MoveEventModule { package, module }
+    }
+
+    /// Events emitted from `start_time` until `end_time`, in milliseconds since the Unix epoch.
+    pub fn time_range(start_time: u64, end_time: u64) -> Self {
+        Self::TimeRange {
+            start_time,
+            end_time,
+        }
+    }
+
+    /// Events matching both `self` and `other`.
+    pub fn and(self, other: Self) -> Self {
+        Self::And(Box::new(self), Box::new(other))
+    }
+
+    /// Events matching either `self` or `other`.
+    pub fn or(self, other: Self) -> Self {
+        Self::Or(Box::new(self), Box::new(other))
+    }
+}
+
+/// A filter of the transactions matched by a query or subscription, as `TransactionFilter` in
+/// JSON-RPC.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum TransactionFilter {
+    /// Transactions included in a checkpoint.
+    Checkpoint(#[serde(with = "crate::_serde::ReadableDisplay")] CheckpointSequenceNumber),
+    /// Transactions calling a package, or a module of it, or a function of that module.
+    MoveFunction {
+        package: ObjectId,
+        module: Option<Identifier>,
+        function: Option<Identifier>,
+    },
+    /// Transactions taking an object as input.
+    InputObject(ObjectId),
+    /// Transactions creating, mutating, wrapping, unwrapping or deleting an object.
+    ChangedObject(ObjectId),
+    /// Transactions sent by an address.
+    FromAddress(Address),
+    /// Transactions transferring objects to an address.
+    ToAddress(Address),
+    FromAndToAddress {
+        from: Address,
+        to: Address,
+    },
+    FromOrToAddress {
+        addr: Address,
+    },
+}
+
+impl TransactionFilter {
+    pub fn checkpoint(checkpoint: CheckpointSequenceNumber) -> Self {
+        Self::Checkpoint(checkpoint)
+    }
+
+    pub fn package(package: ObjectId) -> Self {
+        Self::MoveFunction {
+            package,
+            module: None,
+            function: None,
+        }
+    }
+
+    pub fn module(package: ObjectId, module: Identifier) -> Self {
+        Self::MoveFunction {
+            package,
+            module: Some(module),
+            function: None,
+        }
+    }
+
+    pub fn function(package: ObjectId, module: Identifier, function: Identifier) -> Self {
+        Self::MoveFunction {
+            package,
+            module: Some(module),
+            function: Some(function),
+        }
+    }
+
+    pub fn input_object(object_id: ObjectId) -> Self {
+        Self::InputObject(object_id)
+    }
+
+    pub fn changed_object(object_id: ObjectId) -> Self {
+        Self::ChangedObject(object_id)
+    }
+
+    pub fn sender(sender: Address) -> Self {
+        Self::FromAddress(sender)
+    }
+
+    pub fn recipient(recipient: Address) -> Self {
+        Self::ToAddress(recipient)
+    }
+}
+
+/// `EventFilter` in GraphQL.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlEventFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_digest: Option<TransactionDigest>,
+    /// A package, `0x2`, or a module, `0x2::coin`, emitting the events.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub emitting_module: Option<String>,
+    /// The type of the events, `0x2::coin::CoinMetadata<0x2::sui::SUI>`, or the package or module
+    /// defining it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+}
+
+impl TryFrom<&EventFilter> for GraphQlEventFilter {
+    type Error = UnsupportedFilterError;
+
+    fn try_from(filter: &EventFilter) -> Result<Self, Self::Error> {
+        let mut graphql = Self::default();
+        graphql.add(filter)?;
+        Ok(graphql)
+    }
+}
+
+impl GraphQlEventFilter {
+    fn add(&mut self, filter: &EventFilter) -> Result<(), UnsupportedFilterError> {
+        match filter {
+            EventFilter::Sender(sender) => set(&mut self.sender, *sender),
+            EventFilter::Transaction(digest) => set(&mut self.transaction_digest, *digest),
+            EventFilter::Package(package) => set(&mut self.emitting_module, package.to_string()),
+            EventFilter::MoveModule { package, module } => {
+                set(&mut self.emitting_module, format!("{package}::{module}"))
+            }
+            EventFilter::MoveEventType(event_type) => {
+                set(&mut self.event_type, event_type.to_string())
+            }
+            EventFilter::MoveEventModule { package, module } => {
+                set(&mut self.event_type, format!("{package}::{module}"))
+            }
+            EventFilter::All(filters) => filters.iter().try_for_each(|filter| self.add(filter)),
+            EventFilter::And(left, right) => {
+                self.add(left)?;
+                self.add(right)
+            }
+            EventFilter::TimeRange { .. } | EventFilter::Any(_) | EventFilter::Or(..) => {
+                Err(UnsupportedFilterError)
+            }
+        }
+    }
+}
+
+/// `TransactionBlockFilter` in GraphQL.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde_derive::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GraphQlTransactionFilter {
+    /// A package, `0x2`, module, `0x2::coin`, or function, `0x2::coin::split`, called by the
+    /// transactions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub function: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub at_checkpoint: Option<CheckpointSequenceNumber>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sent_address: Option<Address>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_object: Option<ObjectId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_object: Option<ObjectId>,
+}
+
+impl TryFrom<&TransactionFilter> for GraphQlTransactionFilter {
+    type Error = UnsupportedFilterError;
+
+    fn try_from(filter: &TransactionFilter) -> Result<Self, Self::Error> {
+        let mut graphql = Self::default();
+        match filter {
+            TransactionFilter::Checkpoint(checkpoint) => graphql.at_checkpoint = Some(*checkpoint),
+            TransactionFilter::MoveFunction {
+                package,
+                module,
+                function,
+            } => {
+                graphql.function = Some(match (module, function) {
+                    (None, None) => package.to_string(),
+                    (Some(module), None) => format!("{package}::{module}"),
+                    (Some(module), Some(function)) => format!("{package}::{module}::{function}"),
+                    (None, Some(_)) => return Err(UnsupportedFilterError),
+                })
+            }
+            TransactionFilter::InputObject(object_id) => graphql.input_object = Some(*object_id),
+            TransactionFilter::ChangedObject(object_id) => {
+                graphql.changed_object = Some(*object_id)
+            }
+            TransactionFilter::FromAddress(sender) => graphql.sent_address = Some(*sender),
+            TransactionFilter::ToAddress(_)
+            | TransactionFilter::FromAndToAddress { .. }
+            | TransactionFilter::FromOrToAddress { .. } => return Err(UnsupportedFilterError),
+        }
+        Ok(graphql)
+    }
+}
+
+/// Set a criterion of a GraphQL filter, which can't be given twice.
+fn set<T>(criterion: &mut Option<T>, value: T) -> Result<(), UnsupportedFilterError> {
+    if criterion.is_some() {
+        return Err(UnsupportedFilterError);
+    }
+    *criterion = Some(value);
+    Ok(())
+}
+
+/// A filter which can't be expressed in GraphQL, e.g. a disjunction or a time range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedFilterError;
+
+impl std::fmt::Display for UnsupportedFilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("filter not supported by the GraphQL API")
+    }
+}
+
+impl std::error::Error for UnsupportedFilterError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn coin() -> (ObjectId, Identifier) {
+        (ObjectId::from(Address::TWO), "coin".parse().unwrap())
+    }
+
+    #[test]
+    fn event_filter_json() {
+        let (package, module) = coin();
+        let sender = Address::TWO;
+        let event_type: StructTag = "0x2::coin::CoinMetadata".parse().unwrap();
+        let filter = EventFilter::event_type(event_type.clone())
+            .or(EventFilter::module(package, module).and(EventFilter::sender(sender)));
+
+        let json = serde_json::to_value(&filter).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "Or": [
+                    { "MoveEventType": event_type.to_string() },
+                    {
+                        "And": [
+                            { "MoveModule": { "package": package, "module": "coin" } },
+                            { "Sender": sender }
+                        ]
+                    }
+                ]
+            })
+        );
+        assert_eq!(serde_json::from_value::<EventFilter>(json).unwrap(), filter);
+    }
+
+    #[test]
+    fn transaction_filter_json() {
+        let (package, module) = coin();
+        let filter = TransactionFilter::function(package, module, "split".parse().unwrap());
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            json!({
+                "MoveFunction": { "package": package, "module": "coin", "function": "split" }
+            })
+        );
+        assert_eq!(
+            serde_json::to_value(TransactionFilter::checkpoint(42)).unwrap(),
+            json!({ "Checkpoint": "42" })
+        );
+    }
+
+    #[test]
+    fn graphql_event_filter() {
+        let (package, module) = coin();
+        let filter = EventFilter::All(vec![
+            EventFilter::module(package, module.clone()),
+            EventFilter::event_module(package, module.clone()),
+        ])
+        .and(EventFilter::sender(Address::TWO));
+
+        let graphql = GraphQlEventFilter::try_from(&filter).unwrap();
+        assert_eq!(graphql.emitting_module, Some(format!("{package}::coin")));
+        assert_eq!(graphql.event_type, Some(format!("{package}::coin")));
+        assert_eq!(
+            serde_json::to_value(&graphql).unwrap(),
+            json!({
+                "sender": Address::TWO,
+                "emittingModule": format!("{package}::coin"),
+                "eventType": format!("{package}::coin"),
+            })
+        );
+
+        // Disjunctions, time ranges and repeated criteria have no GraphQL equivalent.
+        for filter in [
+            EventFilter::package(package).or(EventFilter::sender(Address::TWO)),
+            EventFilter::time_range(0, 1),
+            EventFilter::package(package).and(EventFilter::module(package, module)),
+        ] {
+            assert_eq!(
+                GraphQlEventFilter::try_from(&filter),
+                Err(UnsupportedFilterError)
+            );
+        }
+    }
+
+    #[test]
+    fn graphql_transaction_filter() {
+        let (package, module) = coin();
+        let graphql =
+            GraphQlTransactionFilter::try_from(&TransactionFilter::module(package, module))
+                .unwrap();
+        assert_eq!(
+            serde_json::to_value(&graphql).unwrap(),
+            json!({ "function": format!("{package}::coin") })
+        );
+        assert_eq!(
+            GraphQlTransactionFilter::try_from(&TransactionFilter::recipient(Address::TWO)),
+            Err(UnsupportedFilterError)
+        );
+    }
+}
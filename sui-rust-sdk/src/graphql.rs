@@ -0,0 +1,787 @@
+//! Typed queries of objects, transactions and type layouts against the GraphQL API.
+//!
+//! A query selects the fields it needs with [`select`](ObjectQuery::select), each a type of the
+//! [`object`] or [`transaction_block`] modules, so that selecting a field of another type, or
+//! one the SDK can't decode, fails to compile. The query renders to a [`GraphQlRequest`] to post
+//! to the service, and decodes the response into a partially populated SDK type, whose selected
+//! fields are set:
+//!
+//! ```
+//! use sui_sdk::graphql::object;
+//! use sui_sdk::graphql::ObjectQuery;
+//! use sui_sdk::types::ObjectId;
+//!
+//! let query = ObjectQuery::new(ObjectId::from(sui_sdk::types::Address::TWO))
+//!     .select::<object::Version>()
+//!     .select::<object::Owner>();
+//! let request = query.request();
+//! assert!(request.query.contains("version"));
+//!
+//! let response = serde_json::json!({
+//!     "data": {
+//!         "object": {
+//!             "address": "0x2",
+//!             "version": 1,
+//!             "owner": { "__typename": "Immutable" }
+//!         }
+//!     }
+//! });
+//! let object = query.decode(response).unwrap().unwrap();
+//! assert_eq!(object.version.unwrap().into_inner(), 1);
+//! assert!(object.digest.is_none());
+//! ```
+//!
+//! Fields are checked against the type being queried:
+//!
+//! ```compile_fail
+//! use sui_sdk::graphql::transaction_block;
+//! use sui_sdk::graphql::ObjectQuery;
+//! use sui_sdk::types::ObjectId;
+//!
+//! ObjectQuery::new(ObjectId::ZERO).select::<transaction_block::Sender>();
+//! ```
+//!
+//! Paginated [`Connection`]s, such as the [`OwnedObjects`] of an address or the [`Events`] of a
+//! type, are fetched in full by a [`BulkStream`], which pages through several of them at once
+//! with a bounded number of requests in flight:
+//!
+//! ```ignore
+//! let coins = BulkStream::new(|request| client.post(request), owners.map(OwnedObjects::new))
+//!     .with_max_concurrency(8);
+//! ```
+
+use std::marker::PhantomData;
+
+use serde_json::Value;
+
+use crate::types::Address;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::MoveTypeLayout;
+use crate::types::Object;
+use crate::types::ObjectDigest;
+use crate::types::ObjectId;
+use crate::types::Owner;
+use crate::types::SignedTransaction;
+use crate::types::StructTag;
+use crate::types::TransactionDigest;
+use crate::types::TransactionEffects;
+use crate::types::TypeTag;
+use crate::types::Version;
+
+mod bulk;
+pub use bulk::BulkStream;
+pub use bulk::Connection;
+pub use bulk::Events;
+pub use bulk::FetchError;
+pub use bulk::OwnedObjects;
+pub use bulk::DEFAULT_MAX_CONCURRENCY;
+pub use bulk::DEFAULT_PAGE_SIZE;
+
+/// A set of fields selected on the type `T` is decoded into.
+///
+/// Implemented by the fields of the [`object`] and [`transaction_block`] modules, by `()` for no
+/// fields, and by pairs of selections for both of them.
+pub trait Selection<T> {
+    /// Append the GraphQL selection of the fields to `query`.
+    fn write(query: &mut String);
+
+    /// Set the fields of `partial` from those of `node` in the response.
+    fn decode(node: &Value, partial: &mut T) -> Result<(), GraphQlError>;
+}
+
+impl<T> Selection<T> for () {
+    fn write(_: &mut String) {}
+
+    fn decode(_: &Value, _: &mut T) -> Result<(), GraphQlError> {
+        Ok(())
+    }
+}
+
+impl<T, A: Selection<T>, B: Selection<T>> Selection<T> for (A, B) {
+    fn write(query: &mut String) {
+        A::write(query);
+        B::write(query);
+    }
+
+    fn decode(node: &Value, partial: &mut T) -> Result<(), GraphQlError> {
+        A::decode(node, partial)?;
+        B::decode(node, partial)
+    }
+}
+
+/// A query to post to the GraphQL service, as the JSON body of the request.
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize)]
+pub struct GraphQlRequest {
+    pub query: String,
+    pub variables: Value,
+}
+
+/// An object, with the fields selected by a query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialObject {
+    pub object_id: ObjectId,
+    pub version: Option<Version>,
+    pub digest: Option<ObjectDigest>,
+    pub owner: Option<Owner>,
+    pub previous_transaction: Option<TransactionDigest>,
+    pub storage_rebate: Option<u64>,
+    pub object: Option<Object>,
+}
+
+/// A transaction, with the fields selected by a query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartialTransactionBlock {
+    pub digest: TransactionDigest,
+    pub sender: Option<Address>,
+    pub transaction: Option<SignedTransaction>,
+    pub effects: Option<TransactionEffects>,
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+/// The query of an object, at its latest version unless [`at_version`](Self::at_version) is given.
+pub struct ObjectQuery<S = ()> {
+    object_id: ObjectId,
+    version: Option<Version>,
+    selection: PhantomData<fn() -> S>,
+}
+
+impl ObjectQuery {
+    pub fn new(object_id: ObjectId) -> Self {
+        Self {
+            object_id,
+            version: None,
+            selection: PhantomData,
+        }
+    }
+}
+
+impl<S: Selection<PartialObject>> ObjectQuery<S> {
+    pub fn at_version(mut self, version: Version) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Select the field `F` of the object, in addition to those already selected.
+    pub fn select<F: Selection<PartialObject>>(self) -> ObjectQuery<(S, F)> {
+        ObjectQuery {
+            object_id: self.object_id,
+            version: self.version,
+            selection: PhantomData,
+        }
+    }
+
+    pub fn request(&self) -> GraphQlRequest {
+        let mut query = String::from(
+            "query($address: SuiAddress!, $version: Int) { \
+             object(address: $address, version: $version) { address",
+        );
+        S::write(&mut query);
+        query.push_str(" } }");
+        GraphQlRequest {
+            query,
+            variables: serde_json::json!({
+                "address": self.object_id,
+                "version": self.version.map(Version::into_inner),
+            }),
+        }
+    }
+
+    /// Decode the response to the [`request`](Self::request) of the query, `None` if the object
+    /// doesn't exist.
+    pub fn decode(&self, response: Value) -> Result<Option<PartialObject>, GraphQlError> {
+        let node = data(response, "object")?;
+        if node.is_null() {
+            return Ok(None);
+        }
+        let mut object = PartialObject {
+            object_id: field(&node, "address")?,
+            version: None,
+            digest: None,
+            owner: None,
+            previous_transaction: None,
+            storage_rebate: None,
+            object: None,
+        };
+        S::decode(&node, &mut object)?;
+        Ok(Some(object))
+    }
+}
+
+/// The query of a transaction by digest.
+pub struct TransactionBlockQuery<S = ()> {
+    digest: TransactionDigest,
+    selection: PhantomData<fn() -> S>,
+}
+
+impl TransactionBlockQuery {
+    pub fn new(digest: TransactionDigest) -> Self {
+        Self {
+            digest,
+            selection: PhantomData,
+        }
+    }
+}
+
+impl<S: Selection<PartialTransactionBlock>> TransactionBlockQuery<S> {
+    /// Select the field `F` of the transaction, in addition to those already selected.
+    pub fn select<F: Selection<PartialTransactionBlock>>(self) -> TransactionBlockQuery<(S, F)> {
+        TransactionBlockQuery {
+            digest: self.digest,
+            selection: PhantomData,
+        }
+    }
+
+    pub fn request(&self) -> GraphQlRequest {
+        let mut query =
+            String::from("query($digest: String!) { transactionBlock(digest: $digest) { digest");
+        S::write(&mut query);
+        query.push_str(" } }");
+        GraphQlRequest {
+            query,
+            variables: serde_json::json!({ "digest": self.digest }),
+        }
+    }
+
+    /// Decode the response to the [`request`](Self::request) of the query, `None` if the
+    /// transaction doesn't exist.
+    pub fn decode(&self, response: Value) -> Result<Option<PartialTransactionBlock>, GraphQlError> {
+        let node = data(response, "transactionBlock")?;
+        if node.is_null() {
+            return Ok(None);
+        }
+        let mut transaction = PartialTransactionBlock {
+            digest: field(&node, "digest")?,
+            sender: None,
+            transaction: None,
+            effects: None,
+            checkpoint: None,
+        };
+        S::decode(&node, &mut transaction)?;
+        Ok(Some(transaction))
+    }
+}
+
+/// The query of the layout of a type, to decode the BCS of its values with
+/// [`MoveTypeLayout::to_json`].
+///
+/// The GraphQL API doesn't report the tags of the variants of enums, so the layouts of types
+/// holding enums fail to decode, and have to be derived from the modules of their packages
+/// instead.
+pub struct LayoutQuery {
+    type_: TypeTag,
+}
+
+impl LayoutQuery {
+    pub fn new(type_: TypeTag) -> Self {
+        Self { type_ }
+    }
+
+    pub fn for_struct(struct_tag: StructTag) -> Self {
+        Self::new(TypeTag::Struct(Box::new(struct_tag)))
+    }
+
+    pub fn request(&self) -> GraphQlRequest {
+        GraphQlRequest {
+            query: "query($type: String!) { type(type: $type) { layout } }".to_owned(),
+            variables: serde_json::json!({ "type": self.type_.to_string() }),
+        }
+    }
+
+    /// Decode the response to the [`request`](Self::request) of the query.
+    pub fn decode(&self, response: Value) -> Result<MoveTypeLayout, GraphQlError> {
+        let node = data(response, "type")?;
+        field(&node, "layout")
+    }
+}
+
+/// The fields of objects.
+pub mod object {
+    use super::bcs_field;
+    use super::field;
+    use super::GraphQlError;
+    use super::PartialObject;
+    use super::Selection;
+    use serde_json::Value;
+
+    pub struct Version;
+
+    impl Selection<PartialObject> for Version {
+        fn write(query: &mut String) {
+            query.push_str(" version");
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            let version: u64 = field(node, "version")?;
+            object.version = Some(crate::types::Version::new(version));
+            Ok(())
+        }
+    }
+
+    pub struct Digest;
+
+    impl Selection<PartialObject> for Digest {
+        fn write(query: &mut String) {
+            query.push_str(" digest");
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            object.digest = Some(field(node, "digest")?);
+            Ok(())
+        }
+    }
+
+    pub struct Owner;
+
+    impl Selection<PartialObject> for Owner {
+        fn write(query: &mut String) {
+            query.push_str(
+                " owner { __typename \
+                 ... on AddressOwner { owner { address } } \
+                 ... on Parent { parent { address } } \
+                 ... on Shared { initialSharedVersion } \
+                 ... on ConsensusAddressOwner { startVersion owner { address } } }",
+            );
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            use crate::types::Owner;
+
+            let owner = &node["owner"];
+            let invalid = || GraphQlError::InvalidField("owner");
+            object.owner = Some(match owner["__typename"].as_str() {
+                Some("AddressOwner") => {
+                    Owner::Address(field(&owner["owner"], "address").map_err(|_| invalid())?)
+                }
+                Some("Parent") => {
+                    Owner::Object(field(&owner["parent"], "address").map_err(|_| invalid())?)
+                }
+                Some("Shared") => Owner::Shared {
+                    initial_shared_version: crate::types::Version::new(
+                        field(owner, "initialSharedVersion").map_err(|_| invalid())?,
+                    ),
+                },
+                Some("Immutable") => Owner::Immutable,
+                Some("ConsensusAddressOwner") => Owner::ConsensusAddressOwner {
+                    start_version: crate::types::Version::new(
+                        field(owner, "startVersion").map_err(|_| invalid())?,
+                    ),
+                    owner: field(&owner["owner"], "address").map_err(|_| invalid())?,
+                },
+                _ => return Err(invalid()),
+            });
+            Ok(())
+        }
+    }
+
+    /// The digest of the transaction which last changed the object.
+    pub struct PreviousTransaction;
+
+    impl Selection<PartialObject> for PreviousTransaction {
+        fn write(query: &mut String) {
+            query.push_str(" previousTransactionBlock { digest }");
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            object.previous_transaction = Some(
+                field(&node["previousTransactionBlock"], "digest")
+                    .map_err(|_| GraphQlError::InvalidField("previousTransactionBlock"))?,
+            );
+            Ok(())
+        }
+    }
+
+    pub struct StorageRebate;
+
+    impl Selection<PartialObject> for StorageRebate {
+        fn write(query: &mut String) {
+            query.push_str(" storageRebate");
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            // A `BigInt`, as a string
+            let rebate: String = field(node, "storageRebate")?;
+            object.storage_rebate = Some(
+                rebate
+                    .parse()
+                    .map_err(|_| GraphQlError::InvalidField("storageRebate"))?,
+            );
+            Ok(())
+        }
+    }
+
+    /// The object itself, from its BCS serialization.
+    pub struct Bcs;
+
+    impl Selection<PartialObject> for Bcs {
+        fn write(query: &mut String) {
+            query.push_str(" bcs");
+        }
+
+        fn decode(node: &Value, object: &mut PartialObject) -> Result<(), GraphQlError> {
+            object.object = Some(bcs_field(node, "bcs")?);
+            Ok(())
+        }
+    }
+}
+
+/// The fields of transactions.
+pub mod transaction_block {
+    use super::bcs_field;
+    use super::field;
+    use super::GraphQlError;
+    use super::PartialTransactionBlock;
+    use super::Selection;
+    use serde_json::Value;
+
+    pub struct Sender;
+
+    impl Selection<PartialTransactionBlock> for Sender {
+        fn write(query: &mut String) {
+            query.push_str(" sender { address }");
+        }
+
+        fn decode(
+            node: &Value,
+            transaction: &mut PartialTransactionBlock,
+        ) -> Result<(), GraphQlError> {
+            transaction.sender = Some(
+                field(&node["sender"], "address")
+                    .map_err(|_| GraphQlError::InvalidField("sender"))?,
+            );
+            Ok(())
+        }
+    }
+
+    /// The signed transaction, from the BCS serialization of its `SenderSignedData`.
+    pub struct Bcs;
+
+    impl Selection<PartialTransactionBlock> for Bcs {
+        fn write(query: &mut String) {
+            query.push_str(" bcs");
+        }
+
+        fn decode(
+            node: &Value,
+            transaction: &mut PartialTransactionBlock,
+        ) -> Result<(), GraphQlError> {
+            let data: crate::types::SenderSignedData = bcs_field(node, "bcs")?;
+            transaction.transaction = Some(
+                data.into_signed_transaction()
+                    .map_err(|_| GraphQlError::InvalidField("bcs"))?,
+            );
+            Ok(())
+        }
+    }
+
+    /// The effects of the transaction, from their BCS serialization.
+    pub struct Effects;
+
+    impl Selection<PartialTransactionBlock> for Effects {
+        fn write(query: &mut String) {
+            query.push_str(" effects { bcs }");
+        }
+
+        fn decode(
+            node: &Value,
+            transaction: &mut PartialTransactionBlock,
+        ) -> Result<(), GraphQlError> {
+            transaction.effects = Some(
+                bcs_field(&node["effects"], "bcs")
+                    .map_err(|_| GraphQlError::InvalidField("effects"))?,
+            );
+            Ok(())
+        }
+    }
+
+    /// The checkpoint including the transaction.
+    pub struct Checkpoint;
+
+    impl Selection<PartialTransactionBlock> for Checkpoint {
+        fn write(query: &mut String) {
+            query.push_str(" effects { checkpoint { sequenceNumber } }");
+        }
+
+        fn decode(
+            node: &Value,
+            transaction: &mut PartialTransactionBlock,
+        ) -> Result<(), GraphQlError> {
+            transaction.checkpoint = Some(
+                field(&node["effects"]["checkpoint"], "sequenceNumber")
+                    .map_err(|_| GraphQlError::InvalidField("checkpoint"))?,
+            );
+            Ok(())
+        }
+    }
+}
+
+/// The field `name` of the data of `response`, after checking it has no errors.
+fn data(mut response: Value, name: &'static str) -> Result<Value, GraphQlError> {
+    if let Some(errors) = response.get("errors").and_then(Value::as_array) {
+        if !errors.is_empty() {
+            return Err(GraphQlError::Response(
+                errors
+                    .iter()
+                    .map(|error| match error["message"].as_str() {
+                        Some(message) => message.to_owned(),
+                        None => error.to_string(),
+                    })
+                    .collect(),
+            ));
+        }
+    }
+    match response.get_mut("data").and_then(|data| data.get_mut(name)) {
+        Some(node) => Ok(node.take()),
+        None => Err(GraphQlError::InvalidField(name)),
+    }
+}
+
+fn field<T: serde::de::DeserializeOwned>(
+    node: &Value,
+    name: &'static str,
+) -> Result<T, GraphQlError> {
+    T::deserialize(&node[name]).map_err(|_| GraphQlError::InvalidField(name))
+}
+
+/// A field holding the Base64 of the BCS serialization of a `T`.
+fn bcs_field<T: serde::de::DeserializeOwned>(
+    node: &Value,
+    name: &'static str,
+) -> Result<T, GraphQlError> {
+    let b64: &str = node[name]
+        .as_str()
+        .ok_or(GraphQlError::InvalidField(name))?;
+    crate::encoding::from_bcs_base64(b64).map_err(|_| GraphQlError::InvalidField(name))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GraphQlError {
+    /// The messages of the errors reported by the service.
+    Response(Vec<String>),
+    /// A field missing from the response, or which can't be decoded.
+    InvalidField(&'static str),
+}
+
+impl std::fmt::Display for GraphQlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Response(messages) => write!(f, "query failed: {}", messages.join("; ")),
+            Self::InvalidField(name) => write!(f, "invalid field `{name}` in response"),
+        }
+    }
+}
+
+impl std::error::Error for GraphQlError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::to_bcs_base64;
+    use serde_json::json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn object_query() {
+        let object_id = ObjectId::from(Address::TWO);
+        let query = ObjectQuery::new(object_id)
+            .at_version(Version::new(7))
+            .select::<object::Version>()
+            .select::<object::Owner>()
+            .select::<object::StorageRebate>();
+        let request = query.request();
+        assert!(request.query.starts_with(
+            "query($address: SuiAddress!, $version: Int) { \
+             object(address: $address, version: $version) { address version owner {"
+        ));
+        assert!(request.query.ends_with(" storageRebate } }"));
+        assert_eq!(request.variables["version"], 7);
+
+        let response = json!({
+            "data": {
+                "object": {
+                    "address": object_id,
+                    "version": 7,
+                    "owner": { "__typename": "Shared", "initialSharedVersion": 1 },
+                    "storageRebate": "988000"
+                }
+            }
+        });
+        let object = query.decode(response).unwrap().unwrap();
+        assert_eq!(
+            object,
+            PartialObject {
+                object_id,
+                version: Some(Version::new(7)),
+                digest: None,
+                owner: Some(Owner::Shared {
+                    initial_shared_version: Version::new(1)
+                }),
+                previous_transaction: None,
+                storage_rebate: Some(988000),
+                object: None,
+            }
+        );
+
+        assert_eq!(
+            query.decode(json!({ "data": { "object": null } })),
+            Ok(None)
+        );
+        assert_eq!(
+            query.decode(json!({ "data": { "object": { "address": object_id, "version": 7 } } })),
+            Err(GraphQlError::InvalidField("owner"))
+        );
+        assert_eq!(
+            query.decode(json!({ "data": null, "errors": [{ "message": "timeout" }] })),
+            Err(GraphQlError::Response(vec!["timeout".to_owned()]))
+        );
+    }
+
+    #[test]
+    fn consensus_address_owner() {
+        let object_id = ObjectId::from(Address::TWO);
+        let query = ObjectQuery::new(object_id).select::<object::Owner>();
+        assert!(query
+            .request()
+            .query
+            .contains("... on ConsensusAddressOwner { startVersion owner { address } }"));
+
+        let response = json!({
+            "data": {
+                "object": {
+                    "address": object_id,
+                    "owner": {
+                        "__typename": "ConsensusAddressOwner",
+                        "startVersion": 4,
+                        "owner": { "address": Address::THREE }
+                    }
+                }
+            }
+        });
+        let object = query.decode(response).unwrap().unwrap();
+        assert_eq!(
+            object.owner,
+            Some(Owner::ConsensusAddressOwner {
+                start_version: Version::new(4),
+                owner: Address::THREE,
+            })
+        );
+
+        let response = json!({
+            "data": {
+                "object": {
+                    "address": object_id,
+                    "owner": { "__typename": "ConsensusAddressOwner", "startVersion": 4 }
+                }
+            }
+        });
+        assert_eq!(
+            query.decode(response),
+            Err(GraphQlError::InvalidField("owner"))
+        );
+    }
+
+    #[test]
+    fn transaction_block_query() {
+        let digest = TransactionDigest::new([3; 32]);
+        let query = TransactionBlockQuery::new(digest)
+            .select::<transaction_block::Sender>()
+            .select::<transaction_block::Checkpoint>();
+        assert_eq!(
+            query.request().query,
+            "query($digest: String!) { transactionBlock(digest: $digest) { digest \
+             sender { address } effects { checkpoint { sequenceNumber } } } }"
+        );
+
+        let response = json!({
+            "data": {
+                "transactionBlock": {
+                    "digest": digest,
+                    "sender": { "address": Address::TWO },
+                    "effects": { "checkpoint": { "sequenceNumber": 42 } }
+                }
+            }
+        });
+        let transaction = query.decode(response).unwrap().unwrap();
+        assert_eq!(transaction.sender, Some(Address::TWO));
+        assert_eq!(transaction.checkpoint, Some(42));
+        assert!(transaction.transaction.is_none());
+    }
+
+    #[test]
+    fn layout_query() {
+        let query = LayoutQuery::for_struct(StructTag::gas_coin());
+        let request = query.request();
+        assert_eq!(
+            request.variables["type"],
+            TypeTag::Struct(Box::new(StructTag::gas_coin())).to_string()
+        );
+
+        let uid = "0x0000000000000000000000000000000000000000000000000000000000000002::object::UID";
+        let response = json!({
+            "data": {
+                "type": {
+                    "layout": {
+                        "struct": {
+                            "type": request.variables["type"],
+                            "fields": [
+                                {
+                                    "name": "id",
+                                    "layout": {
+                                        "struct": {
+                                            "type": uid,
+                                            "fields": [{ "name": "id", "layout": "address" }]
+                                        }
+                                    }
+                                },
+                                { "name": "balance", "layout": "u64" }
+                            ]
+                        }
+                    }
+                }
+            }
+        });
+        let MoveTypeLayout::Struct(layout) = query.decode(response).unwrap() else {
+            panic!("not a struct layout");
+        };
+        assert_eq!(layout.type_, StructTag::gas_coin());
+        assert_eq!(layout.fields[1].layout, MoveTypeLayout::U64);
+
+        // Enums lack the tags of their variants
+        let response = json!({
+            "data": { "type": { "layout": { "enum": { "variants": [] } } } }
+        });
+        assert_eq!(
+            query.decode(response),
+            Err(GraphQlError::InvalidField("layout"))
+        );
+    }
+
+    #[test]
+    fn bcs_fields() {
+        let bytes = bcs::to_bytes(&(
+            1u8,
+            ObjectId::new([1; 32]),
+            Version::new(1),
+            [0u8; 3],
+            Owner::Immutable,
+            TransactionDigest::ZERO,
+            0u64,
+        ))
+        .unwrap();
+        let expected: Object = bcs::from_bytes(&bytes).unwrap();
+        let query = ObjectQuery::new(ObjectId::new([1; 32])).select::<object::Bcs>();
+        let response = json!({
+            "data": {
+                "object": {
+                    "address": ObjectId::new([1; 32]),
+                    "bcs": to_bcs_base64(&expected).unwrap()
+                }
+            }
+        });
+        assert_eq!(
+            query.decode(response).unwrap().unwrap().object,
+            Some(expected)
+        );
+    }
+}
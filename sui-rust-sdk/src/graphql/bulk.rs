@@ -0,0 +1,438 @@
+//! Fetching all the items of GraphQL connections, a page at a time.
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use base64ct::Encoding;
+use futures_core::Stream;
+use serde_json::Value;
+
+use super::bcs_field;
+use super::data;
+use super::field;
+use super::GraphQlError;
+use super::GraphQlRequest;
+use crate::filter::GraphQlEventFilter;
+use crate::pagination::Page;
+use crate::types::Address;
+use crate::types::Event;
+use crate::types::Object;
+use crate::types::StructTag;
+
+/// The default number of items requested per page, the largest page the service returns by
+/// default.
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+
+/// The default number of requests a [`BulkStream`] keeps in flight.
+pub const DEFAULT_MAX_CONCURRENCY: usize = 4;
+
+/// A connection of the GraphQL API, whose items are fetched a page at a time.
+pub trait Connection {
+    type Item;
+
+    /// The request of the `first` items after the cursor `after`, or from the start if `None`.
+    fn request(&self, first: u32, after: Option<&str>) -> GraphQlRequest;
+
+    /// Decode the response to a [`request`](Self::request), with the cursor of its last item.
+    fn decode(&self, response: Value) -> Result<Page<Self::Item, String>, GraphQlError>;
+}
+
+/// The live objects owned by an address, optionally of a type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedObjects {
+    owner: Address,
+    type_: Option<String>,
+}
+
+impl OwnedObjects {
+    pub fn new(owner: Address) -> Self {
+        Self { owner, type_: None }
+    }
+
+    /// Only the objects of a type, `0x2::coin::Coin<0x2::sui::SUI>`, of any instantiation of a
+    /// generic type, `0x2::coin::Coin`, or of the types of a package or module, `0x2::coin`.
+    pub fn with_type<T: Into<String>>(self, type_: T) -> Self {
+        Self {
+            type_: Some(type_.into()),
+            ..self
+        }
+    }
+}
+
+impl Connection for OwnedObjects {
+    type Item = Object;
+
+    fn request(&self, first: u32, after: Option<&str>) -> GraphQlRequest {
+        GraphQlRequest {
+            query: "query($filter: ObjectFilter!, $first: Int, $after: String) { \
+                    objects(filter: $filter, first: $first, after: $after) { \
+                    pageInfo { hasNextPage endCursor } nodes { bcs } } }"
+                .to_owned(),
+            variables: serde_json::json!({
+                "filter": { "owner": self.owner, "type": self.type_ },
+                "first": first,
+                "after": after,
+            }),
+        }
+    }
+
+    fn decode(&self, response: Value) -> Result<Page<Object, String>, GraphQlError> {
+        page(response, "objects", |node| bcs_field(node, "bcs"))
+    }
+}
+
+/// The events matching a filter, e.g. those of a type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Events {
+    filter: GraphQlEventFilter,
+}
+
+impl Events {
+    pub fn new(filter: GraphQlEventFilter) -> Self {
+        Self { filter }
+    }
+
+    /// The events of `event_type`.
+    pub fn of_type(event_type: &StructTag) -> Self {
+        Self::new(GraphQlEventFilter {
+            event_type: Some(event_type.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+impl Connection for Events {
+    type Item = Event;
+
+    fn request(&self, first: u32, after: Option<&str>) -> GraphQlRequest {
+        GraphQlRequest {
+            query: "query($filter: EventFilter, $first: Int, $after: String) { \
+                    events(filter: $filter, first: $first, after: $after) { \
+                    pageInfo { hasNextPage endCursor } \
+                    nodes { sendingModule { package { address } name } sender { address } \
+                    type { repr } bcs } } }"
+                .to_owned(),
+            variables: serde_json::json!({
+                "filter": self.filter,
+                "first": first,
+                "after": after,
+            }),
+        }
+    }
+
+    fn decode(&self, response: Value) -> Result<Page<Event, String>, GraphQlError> {
+        page(response, "events", |node| {
+            let module = &node["sendingModule"];
+            let type_: String = field(&node["type"], "repr")?;
+            let contents: String = field(node, "bcs")?;
+            Ok(Event {
+                package_id: field(&module["package"], "address")?,
+                module: field(module, "name")?,
+                sender: field(&node["sender"], "address")?,
+                type_: type_
+                    .parse()
+                    .map_err(|_| GraphQlError::InvalidField("type"))?,
+                contents: base64ct::Base64::decode_vec(&contents)
+                    .map_err(|_| GraphQlError::InvalidField("bcs"))?,
+            })
+        })
+    }
+}
+
+/// The page of the connection `name` in `response`, decoding each of its nodes with `decode`.
+fn page<T>(
+    response: Value,
+    name: &'static str,
+    decode: impl Fn(&Value) -> Result<T, GraphQlError>,
+) -> Result<Page<T, String>, GraphQlError> {
+    let connection = data(response, name)?;
+    let page_info = &connection["pageInfo"];
+    let nodes = connection["nodes"]
+        .as_array()
+        .ok_or(GraphQlError::InvalidField("nodes"))?;
+    Ok(Page {
+        data: nodes.iter().map(decode).collect::<Result<_, _>>()?,
+        next_cursor: field(page_info, "endCursor")?,
+        has_next_page: field(page_info, "hasNextPage")?,
+    })
+}
+
+/// The items of several connections, as a single [`Stream`].
+///
+/// Requests are posted with a function sending them to the service and returning the JSON of
+/// the response. At most [`max_concurrency`](Self::with_max_concurrency) of them are in flight at
+/// once, each fetching the next page of a different connection, and no more are sent until the
+/// items already received have been consumed. The posting function is expected to wait out the
+/// rate limits of the service, e.g. responses with a `Retry-After` header, before returning.
+///
+/// The items of each connection are yielded in order, but those of different connections are
+/// interleaved as their pages arrive. The stream stops after the first error, dropping the items
+/// received but not yet yielded.
+pub struct BulkStream<C: Connection, F, Fut> {
+    post: F,
+    page_size: u32,
+    max_concurrency: usize,
+    /// The connections yet to be fetched.
+    queued: VecDeque<C>,
+    /// The connections being fetched, with the request of their next page.
+    active: Vec<(C, Pin<Box<Fut>>)>,
+    items: VecDeque<C::Item>,
+    done: bool,
+}
+
+impl<C, E, F, Fut> BulkStream<C, F, Fut>
+where
+    C: Connection,
+    F: FnMut(GraphQlRequest) -> Fut,
+    Fut: Future<Output = Result<Value, E>>,
+{
+    pub fn new<I: IntoIterator<Item = C>>(post: F, connections: I) -> Self {
+        Self {
+            post,
+            page_size: DEFAULT_PAGE_SIZE,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            queued: connections.into_iter().collect(),
+            active: Vec::new(),
+            items: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    pub fn with_page_size(self, page_size: u32) -> Self {
+        Self { page_size, ..self }
+    }
+
+    /// Keep at most `max_concurrency` requests in flight, at least one.
+    pub fn with_max_concurrency(self, max_concurrency: usize) -> Self {
+        Self {
+            max_concurrency: max_concurrency.max(1),
+            ..self
+        }
+    }
+}
+
+impl<C: Connection, F, Fut> Unpin for BulkStream<C, F, Fut> {}
+
+impl<C, E, F, Fut> Stream for BulkStream<C, F, Fut>
+where
+    C: Connection,
+    F: FnMut(GraphQlRequest) -> Fut,
+    Fut: Future<Output = Result<Value, E>>,
+{
+    type Item = Result<C::Item, FetchError<E>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(item) = this.items.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            while this.active.len() < this.max_concurrency {
+                let Some(connection) = this.queued.pop_front() else {
+                    break;
+                };
+                let request = (this.post)(connection.request(this.page_size, None));
+                this.active.push((connection, Box::pin(request)));
+            }
+            if this.active.is_empty() {
+                this.done = true;
+                return Poll::Ready(None);
+            }
+
+            let mut progress = false;
+            let mut i = 0;
+            while i < this.active.len() {
+                let (connection, pending) = &mut this.active[i];
+                let response = match pending.as_mut().poll(cx) {
+                    Poll::Pending => {
+                        i += 1;
+                        continue;
+                    }
+                    Poll::Ready(response) => response,
+                };
+                progress = true;
+
+                let page = match response {
+                    Ok(response) => connection.decode(response).map_err(FetchError::GraphQl),
+                    Err(e) => Err(FetchError::Transport(e)),
+                };
+                let page = match page {
+                    Ok(page) => page,
+                    Err(e) => {
+                        this.done = true;
+                        this.queued.clear();
+                        this.active.clear();
+                        this.items.clear();
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                };
+                this.items.extend(page.data);
+                match page.next_cursor.filter(|_| page.has_next_page) {
+                    Some(cursor) => {
+                        *pending = Box::pin((this.post)(
+                            connection.request(this.page_size, Some(&cursor)),
+                        ));
+                        i += 1;
+                    }
+                    None => {
+                        this.active.swap_remove(i);
+                    }
+                }
+            }
+            if !progress {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError<E> {
+    /// The request couldn't be posted.
+    Transport(E),
+    GraphQl(GraphQlError),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for FetchError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Transport(e) => write!(f, "transport error: {e}"),
+            Self::GraphQl(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for FetchError<E> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Identifier;
+    use std::cell::Cell;
+    use std::rc::Rc;
+    use std::task::Waker;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    /// A response pending on its first poll, counting the requests in flight.
+    struct Response {
+        value: Option<Result<Value, String>>,
+        polled: bool,
+        in_flight: Rc<Cell<usize>>,
+    }
+
+    impl Future for Response {
+        type Output = Result<Value, String>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if !self.polled {
+                self.polled = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.in_flight.set(self.in_flight.get() - 1);
+            Poll::Ready(self.value.take().unwrap())
+        }
+    }
+
+    fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = vec![];
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return items,
+                Poll::Pending => {}
+            }
+        }
+    }
+
+    /// The page of the events of `module` after `after`, 3 events per module in pages of 2.
+    fn events_page(module: &str, after: Option<&str>) -> Value {
+        let start: u8 = after.map_or(0, |after| after.parse::<u8>().unwrap() + 1);
+        let end = 3.min(start + 2);
+        let nodes: Vec<Value> = (start..end)
+            .map(|i| {
+                serde_json::json!({
+                    "sendingModule": { "package": { "address": "0x2" }, "name": module },
+                    "sender": { "address": "0x1" },
+                    "type": { "repr": format!("0x2::{module}::Event") },
+                    "bcs": base64ct::Base64::encode_string(&[i]),
+                })
+            })
+            .collect();
+        serde_json::json!({
+            "data": {
+                "events": {
+                    "pageInfo": { "hasNextPage": end < 3, "endCursor": (end - 1).to_string() },
+                    "nodes": nodes,
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn stream_connections() {
+        let in_flight = Rc::new(Cell::new(0));
+        let max_in_flight = Rc::new(Cell::new(0));
+        let post = |request: GraphQlRequest| {
+            in_flight.set(in_flight.get() + 1);
+            max_in_flight.set(max_in_flight.get().max(in_flight.get()));
+            assert_eq!(request.variables["first"], 2);
+            let event_type = request.variables["filter"]["eventType"].as_str().unwrap();
+            let module = event_type.split("::").nth(1).unwrap();
+            Response {
+                value: Some(Ok(events_page(module, request.variables["after"].as_str()))),
+                polled: false,
+                in_flight: in_flight.clone(),
+            }
+        };
+        let modules = ["a", "b", "c"];
+        let connections = modules
+            .iter()
+            .map(|module| Events::of_type(&format!("0x2::{module}::Event").parse().unwrap()));
+        let stream = BulkStream::new(post, connections)
+            .with_page_size(2)
+            .with_max_concurrency(2);
+
+        let events: Vec<Event> = collect(stream).into_iter().map(Result::unwrap).collect();
+        assert_eq!(events.len(), 9);
+        assert_eq!(max_in_flight.get(), 2);
+        for module in modules {
+            let contents: Vec<_> = events
+                .iter()
+                .filter(|event| event.module == Identifier::new(module).unwrap())
+                .map(|event| event.contents.clone())
+                .collect();
+            assert_eq!(contents, [[0], [1], [2]]);
+        }
+    }
+
+    #[test]
+    fn stop_after_error() {
+        let mut calls = 0;
+        let post = |_: GraphQlRequest| {
+            calls += 1;
+            std::future::ready(Ok::<_, String>(serde_json::json!({
+                "errors": [{ "message": "too many requests" }]
+            })))
+        };
+        let stream =
+            BulkStream::new(post, vec![OwnedObjects::new(Address::TWO); 3]).with_max_concurrency(1);
+        let results = collect(stream);
+        assert!(matches!(
+            &results[..],
+            [Err(FetchError::GraphQl(GraphQlError::Response(messages)))]
+                if messages == &["too many requests"]
+        ));
+        assert_eq!(calls, 1);
+    }
+}
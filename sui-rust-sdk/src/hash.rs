@@ -1,148 +1,131 @@
-use crate::types::Address;
 use crate::types::Digest;
 
-use blake2::Digest as DigestTrait;
+/// The portable Blake2b-256 implementation, used by default and always on wasm.
+#[cfg(not(all(feature = "simd-hash", not(target_arch = "wasm32"))))]
+mod blake2b {
+    use blake2::Digest as _;
 
-type Blake2b256 = blake2::Blake2b<blake2::digest::consts::U32>;
+    #[derive(Debug, Default)]
+    pub(super) struct Blake2b256(blake2::Blake2b<blake2::digest::consts::U32>);
 
-#[derive(Debug, Default)]
-pub struct Hasher(Blake2b256);
-
-impl Hasher {
-    pub fn new() -> Self {
-        Self(Blake2b256::new())
-    }
+    impl Blake2b256 {
+        pub(super) fn update(&mut self, data: &[u8]) {
+            self.0.update(data)
+        }
 
-    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
-        self.0.update(data)
+        pub(super) fn finalize(self) -> [u8; 32] {
+            self.0.finalize().into()
+        }
     }
 
-    /// Retrieve result and consume hasher instance.
-    pub fn finalize(self) -> Digest {
-        let mut buf = [0; Digest::LENGTH];
-        let result = self.0.finalize();
-
-        buf.copy_from_slice(result.as_slice());
-
-        Digest::new(buf)
-    }
-
-    pub fn digest<T: AsRef<[u8]>>(data: T) -> Digest {
-        let mut hasher = Self::new();
-        hasher.update(data);
-        hasher.finalize()
+    pub(super) fn hash_many<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<[u8; 32]> {
+        inputs
+            .iter()
+            .map(|input| blake2::Blake2b::<blake2::digest::consts::U32>::digest(input).into())
+            .collect()
     }
 }
 
-impl std::io::Write for Hasher {
-    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        self.0.write(buf)
+/// The SIMD accelerated Blake2b-256 implementation, which picks the widest instruction set the
+/// CPU supports at runtime.
+#[cfg(all(feature = "simd-hash", not(target_arch = "wasm32")))]
+mod blake2b {
+    fn params() -> blake2b_simd::Params {
+        let mut params = blake2b_simd::Params::new();
+        params.hash_length(32);
+        params
     }
 
-    fn flush(&mut self) -> std::io::Result<()> {
-        self.0.flush()
-    }
-}
+    #[derive(Debug)]
+    pub(super) struct Blake2b256(blake2b_simd::State);
 
-impl crate::types::Ed25519PublicKey {
-    pub fn to_address(&self) -> Address {
-        let mut hasher = Hasher::new();
-        self.write_into_hasher(&mut hasher);
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
+    impl Default for Blake2b256 {
+        fn default() -> Self {
+            Self(params().to_state())
+        }
     }
 
-    fn write_into_hasher(&self, hasher: &mut Hasher) {
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update(self.inner());
-    }
-}
+    impl Blake2b256 {
+        pub(super) fn update(&mut self, data: &[u8]) {
+            self.0.update(data);
+        }
 
-impl crate::types::Secp256k1PublicKey {
-    pub fn to_address(&self) -> Address {
-        let mut hasher = Hasher::new();
-        self.write_into_hasher(&mut hasher);
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
+        pub(super) fn finalize(self) -> [u8; 32] {
+            self.0
+                .finalize()
+                .as_bytes()
+                .try_into()
+                .expect("hash length is 32")
+        }
     }
 
-    fn write_into_hasher(&self, hasher: &mut Hasher) {
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update(self.inner());
+    /// Hash each of `inputs`, several at a time in parallel SIMD lanes.
+    pub(super) fn hash_many<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<[u8; 32]> {
+        let params = params();
+        let mut jobs: Vec<_> = inputs
+            .iter()
+            .map(|input| blake2b_simd::many::HashManyJob::new(&params, input.as_ref()))
+            .collect();
+        blake2b_simd::many::hash_many(jobs.iter_mut());
+        jobs.iter()
+            .map(|job| {
+                job.to_hash()
+                    .as_bytes()
+                    .try_into()
+                    .expect("hash length is 32")
+            })
+            .collect()
     }
 }
 
-impl crate::types::Secp256r1PublicKey {
-    pub fn to_address(&self) -> Address {
-        let mut hasher = Hasher::new();
-        self.write_into_hasher(&mut hasher);
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
-    }
+/// A Blake2b-256 hasher.
+///
+/// With the `simd-hash` feature hashing uses SIMD instructions where the CPU supports them,
+/// falling back to the portable implementation otherwise, and always on wasm.
+#[derive(Debug, Default)]
+pub struct Hasher(blake2b::Blake2b256);
 
-    fn write_into_hasher(&self, hasher: &mut Hasher) {
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update(self.inner());
+impl Hasher {
+    pub fn new() -> Self {
+        Self::default()
     }
-}
 
-impl crate::types::ZkLoginPublicIdentifier {
-    /// Define as iss_bytes_len || iss_bytes || padded_32_byte_address_seed.
-    pub fn to_address_padded(&self) -> Address {
-        let mut hasher = Hasher::new();
-        self.write_into_hasher_padded(&mut hasher);
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
+    pub fn update<T: AsRef<[u8]>>(&mut self, data: T) {
+        self.0.update(data.as_ref())
     }
 
-    fn write_into_hasher_padded(&self, hasher: &mut Hasher) {
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update([self.iss().len() as u8]); // TODO enforce iss is less than 255 bytes
-        hasher.update(self.iss());
-        hasher.update(self.address_seed().padded());
+    /// Retrieve result and consume hasher instance.
+    pub fn finalize(self) -> Digest {
+        Digest::new(self.0.finalize())
     }
 
-    /// Define as iss_bytes_len || iss_bytes || unpadded_32_byte_address_seed.
-    pub fn to_address_unpadded(&self) -> Address {
-        let mut hasher = Hasher::new();
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update([self.iss().len() as u8]); // TODO enforce iss is less than 255 bytes
-        hasher.update(self.iss());
-        hasher.update(self.address_seed().unpadded());
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
+    pub fn digest<T: AsRef<[u8]>>(data: T) -> Digest {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
     }
-}
 
-impl crate::types::MultisigCommittee {
-    /// Derive an Address from a MultisigCommittee. A MultiSig address
-    /// is defined as the 32-byte Blake2b hash of serializing the flag, the
-    /// threshold, concatenation of all n flag, public keys and
-    /// its weight. `flag_MultiSig || threshold || flag_1 || pk_1 || weight_1
-    /// || ... || flag_n || pk_n || weight_n`.
+    /// Hash each of `inputs`, returning their digests in the same order.
     ///
-    /// When flag_i is ZkLogin, pk_i refers to [struct ZkLoginPublicIdentifier]
-    /// derived from padded address seed in bytes and iss.
-    pub fn to_address(&self) -> Address {
-        use crate::types::MultisigMemberPublicKey::*;
-
-        let mut hasher = Hasher::new();
-        hasher.update([self.scheme().to_u8()]);
-        hasher.update(self.threshold().to_le_bytes());
-
-        for member in self.members() {
-            match member.public_key() {
-                Ed25519(p) => p.write_into_hasher(&mut hasher),
-                Secp256k1(p) => p.write_into_hasher(&mut hasher),
-                Secp256r1(p) => p.write_into_hasher(&mut hasher),
-                ZkLogin(p) => p.write_into_hasher_padded(&mut hasher),
-            }
+    /// With the `simd-hash` feature several inputs are hashed at once, which is considerably
+    /// faster than hashing them one by one when ingesting the many transactions, effects and
+    /// objects of a checkpoint.
+    pub fn digest_many<T: AsRef<[u8]>>(inputs: &[T]) -> Vec<Digest> {
+        blake2b::hash_many(inputs)
+            .into_iter()
+            .map(Digest::new)
+            .collect()
+    }
+}
 
-            hasher.update(member.weight().to_le_bytes());
-        }
+impl std::io::Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
 
-        let digest = hasher.finalize();
-        Address::new(digest.into_inner())
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
     }
 }
 
@@ -198,6 +181,175 @@ impl crate::types::ObjectId {
     }
 }
 
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use signing_digests::personal_message_signing_digest;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod signing_digests {
+    use super::Hasher;
+    use crate::types::ChainIdentifier;
+    use crate::types::CheckpointContents;
+    use crate::types::CheckpointContentsDigest;
+    use crate::types::CheckpointDigest;
+    use crate::types::CheckpointSummary;
+    use crate::types::Object;
+    use crate::types::ObjectDigest;
+    use crate::types::Transaction;
+    use crate::types::TransactionDigest;
+    use crate::types::TransactionEffects;
+    use crate::types::TransactionEffectsDigest;
+
+    /// Hash the BCS encoding of `value` prefixed with `"<name>::"`, the way Sui derives the
+    /// digests of its signable types.
+    fn type_prefixed_digest<T: serde::Serialize>(name: &str, value: &T) -> [u8; 32] {
+        let mut hasher = Hasher::new();
+        hasher.update(name);
+        hasher.update("::");
+        bcs::serialize_into(&mut hasher, value).expect("bcs serialization cannot fail");
+        hasher.finalize().into_inner()
+    }
+
+    /// Hash many values the way [`type_prefixed_digest`] does, in a single batch.
+    fn type_prefixed_digests<'a, T, I>(name: &str, values: I) -> Vec<[u8; 32]>
+    where
+        T: serde::Serialize + 'a,
+        I: IntoIterator<Item = &'a T>,
+    {
+        let messages: Vec<Vec<u8>> = values
+            .into_iter()
+            .map(|value| {
+                let mut message = format!("{name}::").into_bytes();
+                bcs::serialize_into(&mut message, value).expect("bcs serialization cannot fail");
+                message
+            })
+            .collect();
+        Hasher::digest_many(&messages)
+            .into_iter()
+            .map(|digest| digest.into_inner())
+            .collect()
+    }
+
+    impl Transaction {
+        pub fn digest(&self) -> TransactionDigest {
+            TransactionDigest::new(type_prefixed_digest("TransactionData", self))
+        }
+
+        /// The digests of many values at once, in the same order, using
+        /// [`Hasher::digest_many`].
+        pub fn digests<'a>(values: impl IntoIterator<Item = &'a Self>) -> Vec<TransactionDigest> {
+            type_prefixed_digests("TransactionData", values)
+                .into_iter()
+                .map(TransactionDigest::new)
+                .collect()
+        }
+
+        /// The digest signed by the sender and sponsor of the transaction: the hash of the BCS
+        /// encoding of the transaction prefixed with its intent, `[0, 0, 0]`.
+        pub fn signing_digest(&self) -> [u8; 32] {
+            let mut hasher = Hasher::new();
+            hasher.update([0, 0, 0]);
+            bcs::serialize_into(&mut hasher, self).expect("bcs serialization cannot fail");
+            hasher.finalize().into_inner()
+        }
+    }
+
+    /// The digest signed for a personal message: the hash of the BCS encoding of the message, as
+    /// a byte vector, prefixed with its intent, `[3, 0, 0]`.
+    pub fn personal_message_signing_digest(message: &[u8]) -> [u8; 32] {
+        let mut hasher = Hasher::new();
+        hasher.update([3, 0, 0]);
+        bcs::serialize_into(&mut hasher, message).expect("bcs serialization cannot fail");
+        hasher.finalize().into_inner()
+    }
+
+    impl TransactionEffects {
+        pub fn digest(&self) -> TransactionEffectsDigest {
+            TransactionEffectsDigest::new(type_prefixed_digest("TransactionEffects", self))
+        }
+
+        /// The digests of many values at once, in the same order, using
+        /// [`Hasher::digest_many`].
+        pub fn digests<'a>(
+            values: impl IntoIterator<Item = &'a Self>,
+        ) -> Vec<TransactionEffectsDigest> {
+            type_prefixed_digests("TransactionEffects", values)
+                .into_iter()
+                .map(TransactionEffectsDigest::new)
+                .collect()
+        }
+    }
+
+    impl CheckpointSummary {
+        pub fn digest(&self) -> CheckpointDigest {
+            CheckpointDigest::new(type_prefixed_digest("CheckpointSummary", self))
+        }
+    }
+
+    impl ChainIdentifier {
+        /// The identifier of the chain starting with `genesis`, `None` if it isn't a genesis
+        /// checkpoint.
+        pub fn from_genesis(genesis: &CheckpointSummary) -> Option<Self> {
+            (genesis.sequence_number == 0).then(|| genesis.digest().into())
+        }
+    }
+
+    impl CheckpointContents {
+        pub fn digest(&self) -> CheckpointContentsDigest {
+            CheckpointContentsDigest::new(type_prefixed_digest("CheckpointContents", self))
+        }
+    }
+
+    impl Object {
+        pub fn digest(&self) -> ObjectDigest {
+            ObjectDigest::new(type_prefixed_digest("Object", self))
+        }
+
+        /// The digests of many values at once, in the same order, using
+        /// [`Hasher::digest_many`].
+        pub fn digests<'a>(values: impl IntoIterator<Item = &'a Self>) -> Vec<ObjectDigest> {
+            type_prefixed_digests("Object", values)
+                .into_iter()
+                .map(ObjectDigest::new)
+                .collect()
+        }
+    }
+}
+
+impl crate::types::BridgeMessage {
+    /// The digest signed by bridge committee members: the keccak256 hash of the
+    /// `signing_message`, i.e. `keccak256("SUI_BRIDGE_MESSAGE" || message)`.
+    pub fn signing_digest(&self) -> [u8; 32] {
+        use sha3::Digest as _;
+
+        sha3::Keccak256::digest(self.signing_message()).into()
+    }
+}
+
+impl crate::types::EthAddress {
+    /// The EIP-55 mixed case checksum encoding of the address.
+    pub fn to_checksum_string(&self) -> String {
+        use sha3::Digest as _;
+
+        let lowercase = hex::encode(self.inner());
+        let hash = sha3::Keccak256::digest(lowercase.as_bytes());
+        let checksummed: String = lowercase
+            .chars()
+            .enumerate()
+            .map(|(i, c)| {
+                let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0xf;
+                if nibble >= 8 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect();
+        format!("0x{checksummed}")
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::HashingIntent;
@@ -231,4 +383,79 @@ mod test {
     fn roundtrip_hashing_intent(intent: HashingIntent) {
         assert_eq!(Ok(intent), HashingIntent::from_byte(intent as u8));
     }
+
+    #[test]
+    fn eth_address_checksum() {
+        for checksummed in [
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ] {
+            let address: crate::types::EthAddress = checksummed.parse().unwrap();
+            assert_eq!(address.to_checksum_string(), checksummed);
+        }
+    }
+
+    #[test]
+    fn eth_claim_selector() {
+        use sha3::Digest as _;
+
+        let hash = sha3::Keccak256::digest(
+            "transferBridgedTokensWithSignatures(bytes[],(uint8,uint8,uint64,uint8,bytes))",
+        );
+        assert_eq!(
+            hash[..4],
+            crate::types::CertifiedBridgeMessage::ETH_CLAIM_SELECTOR
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn transaction_digest_matches_effects() {
+        use crate::types::Transaction;
+        use crate::types::TransactionEffects;
+        use base64ct::Base64;
+        use base64ct::Encoding;
+
+        const GENESIS_TRANSACTION: &str =
+            include_str!("types/transaction/fixtures/genesis-transaction");
+        const GENESIS_EFFECTS: &str =
+            include_str!("types/effects/fixtures/genesis-transaction-effects");
+
+        let transaction: Transaction =
+            bcs::from_bytes(&Base64::decode_vec(GENESIS_TRANSACTION.trim()).unwrap()).unwrap();
+        let effects: TransactionEffects =
+            bcs::from_bytes(&Base64::decode_vec(GENESIS_EFFECTS.trim()).unwrap()).unwrap();
+        assert_eq!(
+            transaction.digest().to_string(),
+            "7CuBm1AnLgkBMB6GiEn5d3RizznF5LbawjJTs8A5dcXF"
+        );
+        assert_eq!(&transaction.digest(), effects.transaction_digest());
+        assert_eq!(
+            Transaction::digests([&transaction, &transaction]),
+            [transaction.digest(), transaction.digest()]
+        );
+        assert_eq!(TransactionEffects::digests([&effects]), [effects.digest()]);
+    }
+
+    #[test]
+    fn digest_many() {
+        use super::Hasher;
+
+        // Inputs of every length around the block size, hashed in lanes of differing lengths.
+        let inputs: Vec<Vec<u8>> = (0..300).map(|len| vec![len as u8; len]).collect();
+        let digests = Hasher::digest_many(&inputs);
+        assert_eq!(digests.len(), inputs.len());
+        for (input, digest) in inputs.iter().zip(&digests) {
+            assert_eq!(*digest, Hasher::digest(input));
+        }
+        assert!(Hasher::digest_many::<&[u8]>(&[]).is_empty());
+
+        // The Blake2b-256 of the empty message.
+        assert_eq!(
+            Hasher::digest(b"").to_hex(),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
 }
@@ -0,0 +1,581 @@
+//! The JSON shapes of the node's JSON-RPC API, with conversions to and from the SDK's types.
+//!
+//! These mirror the types of the `sui-json-rpc-types` crate, field for field, so that applications
+//! moving off of it can keep talking to JSON-RPC endpoints while using the SDK's types. Only the
+//! parts which can be converted without loss are provided: transaction kinds in particular are
+//! rendered by the node with decoded Move values, so transactions are instead recovered from the
+//! BCS `rawTransaction` of a [`SuiTransactionBlockResponse`].
+//!
+//! With the `json` feature, requests can be batched with a [`BatchRequest`], fetching many objects
+//! or transactions in a single round trip and reporting failures for each of them.
+//!
+//! A dry run of a transaction, e.g. from [`BatchRequest::dry_run_transaction`], can be summarized
+//! into a [`TransactionPreview`] of what it does to each address, for a wallet to show before
+//! asking for a signature.
+
+use crate::types::Address;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::ExecutionStatus;
+use crate::types::GasCostSummary;
+use crate::types::GasPayment;
+use crate::types::ObjectDigest;
+use crate::types::ObjectId;
+use crate::types::ObjectReference;
+use crate::types::Owner;
+use crate::types::SignedTransaction;
+use crate::types::StructTag;
+use crate::types::TransactionDigest;
+use crate::types::TypeTag;
+use crate::types::Version;
+
+mod preview;
+pub use preview::AddressPreview;
+pub use preview::ObjectChangeKind;
+pub use preview::ObjectPreview;
+pub use preview::TransactionPreview;
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+mod batch;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::BatchItemError;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::BatchRequest;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::BatchResponses;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::JsonRpcError;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::JsonRpcRequest;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::JsonRpcResponse;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::MultiGet;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::Version2;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use batch::MAX_MULTI_GET_LENGTH;
+
+/// `SuiObjectRef`, whose version is a JSON number.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiObjectRef {
+    pub object_id: ObjectId,
+    pub version: Version,
+    pub digest: ObjectDigest,
+}
+
+impl From<ObjectReference> for SuiObjectRef {
+    fn from(reference: ObjectReference) -> Self {
+        let (object_id, version, digest) = reference.into_parts();
+        Self {
+            object_id,
+            version,
+            digest,
+        }
+    }
+}
+
+impl From<SuiObjectRef> for ObjectReference {
+    fn from(reference: SuiObjectRef) -> Self {
+        Self::new(reference.object_id, reference.version, reference.digest)
+    }
+}
+
+/// `SuiGasData`, the gas payment of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SuiGasData {
+    pub payment: Vec<SuiObjectRef>,
+    pub owner: Address,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub price: u64,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub budget: u64,
+}
+
+impl From<GasPayment> for SuiGasData {
+    fn from(payment: GasPayment) -> Self {
+        Self {
+            payment: payment.objects.into_iter().map(Into::into).collect(),
+            owner: payment.owner,
+            price: payment.price,
+            budget: payment.budget,
+        }
+    }
+}
+
+impl From<SuiGasData> for GasPayment {
+    fn from(data: SuiGasData) -> Self {
+        Self {
+            objects: data.payment.into_iter().map(Into::into).collect(),
+            owner: data.owner,
+            price: data.price,
+            budget: data.budget,
+        }
+    }
+}
+
+/// `GasCostSummary` as rendered by JSON-RPC, with camelCase field names.
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiGasCostSummary {
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub computation_cost: u64,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub storage_cost: u64,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub storage_rebate: u64,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub non_refundable_storage_fee: u64,
+}
+
+impl From<GasCostSummary> for SuiGasCostSummary {
+    fn from(summary: GasCostSummary) -> Self {
+        Self {
+            computation_cost: summary.computation_cost,
+            storage_cost: summary.storage_cost,
+            storage_rebate: summary.storage_rebate,
+            non_refundable_storage_fee: summary.non_refundable_storage_fee,
+        }
+    }
+}
+
+impl From<SuiGasCostSummary> for GasCostSummary {
+    fn from(summary: SuiGasCostSummary) -> Self {
+        Self::new(
+            summary.computation_cost,
+            summary.storage_cost,
+            summary.storage_rebate,
+            summary.non_refundable_storage_fee,
+        )
+    }
+}
+
+/// `EventID`, the cursor of event queries: the transaction which emitted an event, and the
+/// index of the event among the ones it emitted.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventId {
+    pub tx_digest: TransactionDigest,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub event_seq: u64,
+}
+
+/// `SuiExecutionStatus`, where a failure is only described by a message.
+///
+/// There is no conversion back to an [`ExecutionStatus`], as the message is not meant to be
+/// parsed.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase", tag = "status")]
+pub enum SuiExecutionStatus {
+    Success,
+    Failure { error: String },
+}
+
+impl SuiExecutionStatus {
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+}
+
+impl From<&ExecutionStatus> for SuiExecutionStatus {
+    fn from(status: &ExecutionStatus) -> Self {
+        match status {
+            ExecutionStatus::Success => Self::Success,
+            ExecutionStatus::Failure {
+                error,
+                command: Some(command),
+            } => Self::Failure {
+                error: format!("{error:?} in command {command}"),
+            },
+            ExecutionStatus::Failure {
+                error,
+                command: None,
+            } => Self::Failure {
+                error: format!("{error:?}"),
+            },
+        }
+    }
+}
+
+/// The parts of a `SuiTransactionBlockResponse` which can be read without loss.
+///
+/// The transaction itself is only available if the request asked for it with `showRawInput`, in
+/// which case a [`SignedTransaction`] can be converted from the response. Other fields of the
+/// response are ignored.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiTransactionBlockResponse {
+    pub digest: TransactionDigest,
+    /// The BCS serialized `SenderSignedData` of the transaction.
+    #[serde(
+        default,
+        skip_serializing_if = "Vec::is_empty",
+        with = "::serde_with::As::<crate::_serde::Base64Encoded>"
+    )]
+    pub raw_transaction: Vec<u8>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::_serde::OptionReadableDisplay"
+    )]
+    pub timestamp_ms: Option<u64>,
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "crate::_serde::OptionReadableDisplay"
+    )]
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+}
+
+impl SuiTransactionBlockResponse {
+    /// A response for `transaction`, as returned when its raw input is requested.
+    pub fn new(
+        digest: TransactionDigest,
+        transaction: &SignedTransaction,
+    ) -> Result<Self, bcs::Error> {
+        Ok(Self {
+            digest,
+            raw_transaction: bcs::to_bytes(&SenderSignedDataRef(transaction))?,
+            timestamp_ms: None,
+            checkpoint: None,
+        })
+    }
+}
+
+impl TryFrom<&SuiTransactionBlockResponse> for SignedTransaction {
+    type Error = JsonRpcConversionError;
+
+    fn try_from(response: &SuiTransactionBlockResponse) -> Result<Self, Self::Error> {
+        if response.raw_transaction.is_empty() {
+            return Err(JsonRpcConversionError::MissingRawTransaction);
+        }
+        bcs::from_bytes::<SenderSignedData>(&response.raw_transaction)
+            .map(|data| data.0)
+            .map_err(JsonRpcConversionError::Bcs)
+    }
+}
+
+/// `Owner` as rendered by JSON-RPC, e.g. `{"AddressOwner": "0x.."}` or `"Immutable"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum SuiOwner {
+    AddressOwner(Address),
+    ObjectOwner(ObjectId),
+    Shared {
+        initial_shared_version: Version,
+    },
+    Immutable,
+    ConsensusAddressOwner {
+        start_version: Version,
+        owner: Address,
+    },
+}
+
+impl SuiOwner {
+    /// The address owning the object, if it is owned by one.
+    pub fn address(&self) -> Option<Address> {
+        match self {
+            Self::AddressOwner(address) | Self::ConsensusAddressOwner { owner: address, .. } => {
+                Some(*address)
+            }
+            Self::ObjectOwner(_) | Self::Shared { .. } | Self::Immutable => None,
+        }
+    }
+}
+
+impl From<Owner> for SuiOwner {
+    fn from(owner: Owner) -> Self {
+        match owner {
+            Owner::Address(address) => Self::AddressOwner(address),
+            Owner::Object(object_id) => Self::ObjectOwner(object_id),
+            Owner::Shared {
+                initial_shared_version,
+            } => Self::Shared {
+                initial_shared_version,
+            },
+            Owner::Immutable => Self::Immutable,
+            Owner::ConsensusAddressOwner {
+                start_version,
+                owner,
+            } => Self::ConsensusAddressOwner {
+                start_version,
+                owner,
+            },
+        }
+    }
+}
+
+impl From<SuiOwner> for Owner {
+    fn from(owner: SuiOwner) -> Self {
+        match owner {
+            SuiOwner::AddressOwner(address) => Self::Address(address),
+            SuiOwner::ObjectOwner(object_id) => Self::Object(object_id),
+            SuiOwner::Shared {
+                initial_shared_version,
+            } => Self::Shared {
+                initial_shared_version,
+            },
+            SuiOwner::Immutable => Self::Immutable,
+            SuiOwner::ConsensusAddressOwner {
+                start_version,
+                owner,
+            } => Self::ConsensusAddressOwner {
+                start_version,
+                owner,
+            },
+        }
+    }
+}
+
+/// `BalanceChange`, the change of the balance of a coin type for an owner, where a negative
+/// amount was spent.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiBalanceChange {
+    pub owner: SuiOwner,
+    pub coin_type: TypeTag,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub amount: i128,
+}
+
+/// `ObjectChange`, the change of an object by a transaction, tagged with its `type`.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum SuiObjectChange {
+    #[serde(rename_all = "camelCase")]
+    Published {
+        package_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+        digest: ObjectDigest,
+        modules: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Transferred {
+        sender: Address,
+        recipient: SuiOwner,
+        object_type: StructTag,
+        object_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+        digest: ObjectDigest,
+    },
+    #[serde(rename_all = "camelCase")]
+    Mutated {
+        sender: Address,
+        owner: SuiOwner,
+        object_type: StructTag,
+        object_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        previous_version: Version,
+        digest: ObjectDigest,
+    },
+    #[serde(rename_all = "camelCase")]
+    Deleted {
+        sender: Address,
+        object_type: StructTag,
+        object_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+    },
+    #[serde(rename_all = "camelCase")]
+    Wrapped {
+        sender: Address,
+        object_type: StructTag,
+        object_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+    },
+    #[serde(rename_all = "camelCase")]
+    Created {
+        sender: Address,
+        owner: SuiOwner,
+        object_type: StructTag,
+        object_id: ObjectId,
+        #[serde(with = "crate::_serde::ReadableDisplay")]
+        version: Version,
+        digest: ObjectDigest,
+    },
+}
+
+/// The parts of a `DryRunTransactionBlockResponse`, the result of `sui_dryRunTransactionBlock`,
+/// which summarize what a transaction would do. Other fields of the response are ignored.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunTransactionBlockResponse {
+    pub effects: DryRunEffects,
+    pub input: DryRunInput,
+    #[serde(default)]
+    pub balance_changes: Vec<SuiBalanceChange>,
+    #[serde(default)]
+    pub object_changes: Vec<SuiObjectChange>,
+}
+
+/// The status and gas cost of the effects of a dry run.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunEffects {
+    pub status: SuiExecutionStatus,
+    pub gas_used: SuiGasCostSummary,
+}
+
+/// The gas payment of the transaction of a dry run.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DryRunInput {
+    pub gas_data: SuiGasData,
+}
+
+/// The `SenderSignedData` wrapping of a signed transaction in its intent message.
+#[derive(serde_derive::Serialize)]
+#[serde(transparent)]
+struct SenderSignedDataRef<'a>(
+    #[serde(with = "::serde_with::As::<crate::_serde::SignedTransactionWithIntentMessage>")]
+    &'a SignedTransaction,
+);
+
+#[derive(serde_derive::Deserialize)]
+#[serde(transparent)]
+struct SenderSignedData(
+    #[serde(with = "::serde_with::As::<crate::_serde::SignedTransactionWithIntentMessage>")]
+    SignedTransaction,
+);
+
+#[derive(Debug)]
+pub enum JsonRpcConversionError {
+    /// The response doesn't include the raw transaction, which must be requested with
+    /// `showRawInput`.
+    MissingRawTransaction,
+    Bcs(bcs::Error),
+}
+
+impl std::fmt::Display for JsonRpcConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingRawTransaction => write!(f, "the response has no raw transaction"),
+            Self::Bcs(e) => write!(f, "invalid raw transaction: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for JsonRpcConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingRawTransaction => None,
+            Self::Bcs(e) => Some(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::ExecutionError;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn gas_data() {
+        let json = serde_json::json!({
+            "payment": [
+                {
+                    "objectId": "0xe8d8c7ce863f313da3dbd92a83ef26d128b88fe66bf26e0e0d09cdaf727d1d84",
+                    "version": 2,
+                    "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt"
+                }
+            ],
+            "owner": "0xfd00000000000000000000000000000000000000000000000000000000000000",
+            "price": "1000",
+            "budget": "100000"
+        });
+        let data: SuiGasData = serde_json::from_value(json.clone()).unwrap();
+        let payment = GasPayment::from(data.clone());
+        assert_eq!(payment.objects[0].version(), Version::new(2));
+        assert_eq!(payment.budget, 100000);
+        assert_eq!(SuiGasData::from(payment), data);
+        assert_eq!(serde_json::to_value(&data).unwrap(), json);
+    }
+
+    #[test]
+    fn gas_cost_summary() {
+        let json = serde_json::json!({
+            "computationCost": "1000000",
+            "storageCost": "2000",
+            "storageRebate": "1000",
+            "nonRefundableStorageFee": "10"
+        });
+        let summary: SuiGasCostSummary = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            GasCostSummary::from(summary),
+            GasCostSummary::new(1_000_000, 2000, 1000, 10)
+        );
+    }
+
+    #[test]
+    fn event_id() {
+        let json = serde_json::json!({
+            "txDigest": "7CuBm1AnLgkBMB6GiEn5d3RizznF5LbawjJTs8A5dcXF",
+            "eventSeq": "3"
+        });
+        let id: EventId = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(id.event_seq, 3);
+        assert_eq!(serde_json::to_value(&id).unwrap(), json);
+    }
+
+    #[test]
+    fn execution_status() {
+        assert_eq!(
+            serde_json::to_value(SuiExecutionStatus::from(&ExecutionStatus::Success)).unwrap(),
+            serde_json::json!({"status": "success"})
+        );
+
+        let status = SuiExecutionStatus::from(&ExecutionStatus::Failure {
+            error: ExecutionError::InsufficientGas,
+            command: Some(1),
+        });
+        assert!(!status.is_ok());
+        assert_eq!(
+            serde_json::to_value(status).unwrap(),
+            serde_json::json!({"status": "failure", "error": "InsufficientGas in command 1"})
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn raw_transaction() {
+        let transaction = SignedTransaction {
+            transaction: crate::types::Transaction::random(),
+            signatures: vec![],
+        };
+        let response =
+            SuiTransactionBlockResponse::new(TransactionDigest::random(), &transaction).unwrap();
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json["rawTransaction"].is_string());
+
+        let response: SuiTransactionBlockResponse = serde_json::from_value(json).unwrap();
+        assert_eq!(SignedTransaction::try_from(&response).unwrap(), transaction);
+
+        let response = SuiTransactionBlockResponse {
+            raw_transaction: vec![],
+            ..response
+        };
+        assert!(matches!(
+            SignedTransaction::try_from(&response),
+            Err(JsonRpcConversionError::MissingRawTransaction)
+        ));
+    }
+}
@@ -0,0 +1,615 @@
+//! Batches of JSON-RPC 2.0 requests, sent to a node in a single round trip.
+//!
+//! Fullnodes prune old transactions and object versions, so long-range historical queries can fail
+//! for data the network still has. Such failures are reported as
+//! [`BatchItemError::TransactionNotFound`] and [`BatchItemError::ObjectVersionNotFound`], and the
+//! requests which hit them can be sent again to an archival node, if one is available, with
+//! [`BatchRequest::pruned`] and [`BatchResponses::with_fallback`].
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::DryRunTransactionBlockResponse;
+use super::SuiObjectRef;
+use super::SuiTransactionBlockResponse;
+use super::TransactionPreview;
+use crate::types::ObjectId;
+use crate::types::Transaction;
+use crate::types::TransactionDigest;
+use crate::types::Version;
+
+/// The most ids the node accepts in a single `sui_multiGet*` request.
+pub const MAX_MULTI_GET_LENGTH: usize = 50;
+
+/// The start of the message of the errors of requests for transactions the node doesn't have.
+const TRANSACTION_NOT_FOUND_PREFIX: &str = "Could not find the referenced transaction";
+
+/// The `jsonrpc` member of requests, always `"2.0"`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Version2;
+
+impl serde::Serialize for Version2 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("2.0")
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Version2 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let version = std::borrow::Cow::<'de, str>::deserialize(deserializer)?;
+        if version == "2.0" {
+            Ok(Self)
+        } else {
+            Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&version),
+                &"2.0",
+            ))
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct JsonRpcRequest {
+    pub jsonrpc: Version2,
+    pub id: u64,
+    pub method: String,
+    pub params: Value,
+}
+
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct JsonRpcResponse {
+    /// The id of the request, which is null if the request itself couldn't be parsed.
+    pub id: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonRpcError>,
+}
+
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl std::fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rpc error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for JsonRpcError {}
+
+impl JsonRpcError {
+    /// Whether the error reports transactions the node doesn't have, either because they were
+    /// never executed or because the node pruned them.
+    pub fn is_transaction_not_found(&self) -> bool {
+        self.message.starts_with(TRANSACTION_NOT_FOUND_PREFIX)
+    }
+}
+
+/// A batch of requests, serialized as the JSON array sent to the node.
+///
+/// ```
+/// use sui_sdk::json_rpc::BatchRequest;
+/// use sui_sdk::json_rpc::BatchResponses;
+/// use sui_sdk::types::ObjectId;
+///
+/// let mut batch = BatchRequest::new();
+/// let objects = batch.multi_get_objects(&[ObjectId::ZERO]);
+/// let body = serde_json::to_string(&batch).unwrap();
+///
+/// // Send `body` to the node, then read back its response.
+/// # let response = r#"[{"jsonrpc": "2.0", "id": 0, "result": [{"error": {"code": "notExists"}}]}]"#;
+/// let responses = BatchResponses::new(serde_json::from_str(response).unwrap());
+/// assert!(objects.results(&responses)[0].is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, serde_derive::Serialize)]
+#[serde(transparent)]
+pub struct BatchRequest {
+    requests: Vec<JsonRpcRequest>,
+}
+
+impl BatchRequest {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a call of `method` to the batch, returning the id of its request.
+    pub fn push<M: Into<String>>(&mut self, method: M, params: Value) -> u64 {
+        let id = self.requests.len() as u64;
+        self.requests.push(JsonRpcRequest {
+            jsonrpc: Version2,
+            id,
+            method: method.into(),
+            params,
+        });
+        id
+    }
+
+    pub fn requests(&self) -> &[JsonRpcRequest] {
+        &self.requests
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Fetch the references of `object_ids`, split into `sui_multiGetObjects` requests of at most
+    /// [`MAX_MULTI_GET_LENGTH`] ids.
+    pub fn multi_get_objects(&mut self, object_ids: &[ObjectId]) -> MultiGet<SuiObjectRef> {
+        self.multi_get("sui_multiGetObjects", object_ids, serde_json::json!({}))
+    }
+
+    /// Fetch the transactions `digests`, including their raw input, split into
+    /// `sui_multiGetTransactionBlocks` requests of at most [`MAX_MULTI_GET_LENGTH`] digests.
+    pub fn multi_get_transactions(
+        &mut self,
+        digests: &[TransactionDigest],
+    ) -> MultiGet<SuiTransactionBlockResponse> {
+        self.multi_get(
+            "sui_multiGetTransactionBlocks",
+            digests,
+            serde_json::json!({ "showRawInput": true }),
+        )
+    }
+
+    /// Fetch the references of `objects` at the given versions, split into
+    /// `sui_tryMultiGetPastObjects` requests of at most [`MAX_MULTI_GET_LENGTH`] objects.
+    pub fn multi_get_past_objects(
+        &mut self,
+        objects: &[(ObjectId, Version)],
+    ) -> MultiGet<SuiObjectRef> {
+        let keys: Vec<_> = objects
+            .iter()
+            .map(|(object_id, version)| {
+                serde_json::json!({ "objectId": object_id, "version": version.to_string() })
+            })
+            .collect();
+        self.multi_get("sui_tryMultiGetPastObjects", &keys, serde_json::json!({}))
+    }
+
+    /// Dry run `transaction` with `sui_dryRunTransactionBlock`, returning the id of the request,
+    /// whose response is summarized by [`BatchResponses::preview`].
+    pub fn dry_run_transaction(&mut self, transaction: &Transaction) -> u64 {
+        let tx_bytes = crate::encoding::to_bcs_base64(transaction)
+            .expect("serialization of a transaction cannot fail");
+        self.push("sui_dryRunTransactionBlock", serde_json::json!([tx_bytes]))
+    }
+
+    /// The requests of the batch for which `responses` report data the node pruned, keeping their
+    /// ids, to send again to an archival node.
+    pub fn pruned(&self, responses: &BatchResponses) -> BatchRequest {
+        Self {
+            requests: self
+                .requests
+                .iter()
+                .filter(|request| responses.is_pruned(request.id))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    fn multi_get<K: serde::Serialize, T>(
+        &mut self,
+        method: &str,
+        keys: &[K],
+        options: Value,
+    ) -> MultiGet<T> {
+        let chunks = keys
+            .chunks(MAX_MULTI_GET_LENGTH)
+            .map(|chunk| {
+                let params = serde_json::json!([chunk, options]);
+                (self.push(method, params), chunk.len())
+            })
+            .collect();
+        MultiGet {
+            chunks,
+            _result: std::marker::PhantomData,
+        }
+    }
+}
+
+/// The responses to a [`BatchRequest`], by request id.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchResponses {
+    responses: std::collections::BTreeMap<u64, JsonRpcResponse>,
+}
+
+impl BatchResponses {
+    /// Index the responses to a batch, which the node may return in any order.
+    ///
+    /// Responses without an id are dropped, the requests they failed are reported as missing.
+    pub fn new(responses: Vec<JsonRpcResponse>) -> Self {
+        Self {
+            responses: responses
+                .into_iter()
+                .filter_map(|response| Some((response.id?, response)))
+                .collect(),
+        }
+    }
+
+    /// The result of request `id`.
+    pub fn get(&self, id: u64) -> Result<&Value, BatchItemError> {
+        match self.responses.get(&id) {
+            Some(JsonRpcResponse {
+                error: Some(error), ..
+            }) if error.is_transaction_not_found() => {
+                Err(BatchItemError::TransactionNotFound(error.clone()))
+            }
+            Some(JsonRpcResponse {
+                error: Some(error), ..
+            }) => Err(BatchItemError::Rpc(error.clone())),
+            Some(JsonRpcResponse {
+                result: Some(result),
+                ..
+            }) => Ok(result),
+            _ => Err(BatchItemError::MissingResponse),
+        }
+    }
+
+    /// The result of request `id`, deserialized as `T`.
+    pub fn decode<T: DeserializeOwned>(&self, id: u64) -> Result<T, BatchItemError> {
+        T::deserialize(self.get(id)?).map_err(BatchItemError::Json)
+    }
+
+    /// The preview of the transaction dry run by request `id`, from
+    /// [`BatchRequest::dry_run_transaction`].
+    pub fn preview(&self, id: u64) -> Result<TransactionPreview, BatchItemError> {
+        self.decode::<DryRunTransactionBlockResponse>(id)
+            .map(Into::into)
+    }
+
+    /// Whether the response to request `id` reports data the node pruned, for the request as a
+    /// whole or for some of its items.
+    pub fn is_pruned(&self, id: u64) -> bool {
+        match self.get(id) {
+            Ok(Value::Array(items)) => items
+                .iter()
+                .any(|item| item.get("status").and_then(Value::as_str) == Some("VersionNotFound")),
+            Ok(_) => false,
+            Err(e) => e.is_pruned(),
+        }
+    }
+
+    /// Replace the responses to the requests answered by `fallback`, e.g. the responses of an
+    /// archival node to the requests of [`BatchRequest::pruned`].
+    pub fn with_fallback(mut self, fallback: BatchResponses) -> Self {
+        self.responses.extend(fallback.responses);
+        self
+    }
+}
+
+/// A `sui_multiGet*` call added to a [`BatchRequest`], possibly split across several requests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiGet<T> {
+    /// The id of each request and the number of items it asked for.
+    chunks: Vec<(u64, usize)>,
+    _result: std::marker::PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> MultiGet<T> {
+    /// The result for each requested item, in the order they were requested.
+    ///
+    /// A request which failed as a whole fails each of its items.
+    pub fn results(&self, responses: &BatchResponses) -> Vec<Result<T, BatchItemError>> {
+        let mut results = Vec::new();
+        for &(id, len) in &self.chunks {
+            let items = match responses.get(id) {
+                Ok(Value::Array(items)) if items.len() == len => items,
+                Ok(_) => {
+                    results.extend((0..len).map(|_| Err(BatchItemError::InvalidResponse)));
+                    continue;
+                }
+                Err(BatchItemError::Rpc(e)) => {
+                    results.extend((0..len).map(|_| Err(BatchItemError::Rpc(e.clone()))));
+                    continue;
+                }
+                Err(BatchItemError::TransactionNotFound(e)) => {
+                    results.extend(
+                        (0..len).map(|_| Err(BatchItemError::TransactionNotFound(e.clone()))),
+                    );
+                    continue;
+                }
+                Err(_) => {
+                    results.extend((0..len).map(|_| Err(BatchItemError::MissingResponse)));
+                    continue;
+                }
+            };
+            results.extend(items.iter().map(decode_item));
+        }
+        results
+    }
+}
+
+/// Decode an item of a `sui_multiGet*` result, which reports per-item failures as an `error`
+/// member in place of the item, e.g. for objects which don't exist.
+fn decode_item<T: DeserializeOwned>(item: &Value) -> Result<T, BatchItemError> {
+    // Past objects are tagged with the status of the lookup of their version
+    if let Some(status) = item.get("status").and_then(Value::as_str) {
+        let details = item.get("details").unwrap_or(&Value::Null);
+        return match status {
+            "VersionFound" => T::deserialize(details).map_err(BatchItemError::Json),
+            "VersionNotFound" => {
+                let (object_id, version) =
+                    <(ObjectId, Version)>::deserialize(details).map_err(BatchItemError::Json)?;
+                Err(BatchItemError::ObjectVersionNotFound { object_id, version })
+            }
+            _ => Err(BatchItemError::Item(item.clone())),
+        };
+    }
+    if let Some(error) = item.get("error").filter(|error| !error.is_null()) {
+        return Err(BatchItemError::Item(error.clone()));
+    }
+    // Objects are wrapped in a `data` member
+    let item = item.get("data").unwrap_or(item);
+    T::deserialize(item).map_err(BatchItemError::Json)
+}
+
+#[derive(Debug)]
+pub enum BatchItemError {
+    /// The request failed as a whole.
+    Rpc(JsonRpcError),
+    /// The node didn't respond to the request.
+    MissingResponse,
+    /// The result of the request doesn't have one item for each requested item.
+    InvalidResponse,
+    /// The node reported an error for the item, e.g. an object which doesn't exist.
+    Item(Value),
+    Json(serde_json::Error),
+    /// The node doesn't have the transaction, either because it was never executed or because
+    /// the node pruned it. A single missing transaction fails a `sui_multiGetTransactionBlocks`
+    /// request as a whole, so each of its items reports this error.
+    TransactionNotFound(JsonRpcError),
+    /// The object exists but the node doesn't have this version of it, e.g. because it pruned it.
+    ObjectVersionNotFound {
+        object_id: ObjectId,
+        version: Version,
+    },
+}
+
+impl BatchItemError {
+    /// Whether the item may have been pruned by the node, and may be found on an archival node.
+    pub fn is_pruned(&self) -> bool {
+        matches!(
+            self,
+            Self::TransactionNotFound(_) | Self::ObjectVersionNotFound { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for BatchItemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rpc(e) => write!(f, "{e}"),
+            Self::MissingResponse => write!(f, "no response to the request"),
+            Self::InvalidResponse => write!(f, "the response doesn't match the request"),
+            Self::Item(e) => write!(f, "item error: {e}"),
+            Self::Json(e) => write!(f, "invalid result: {e}"),
+            Self::TransactionNotFound(e) => write!(f, "transaction not found: {e}"),
+            Self::ObjectVersionNotFound { object_id, version } => {
+                write!(f, "version {version} of object {object_id} not found")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BatchItemError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Rpc(e) | Self::TransactionNotFound(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::MissingResponse
+            | Self::InvalidResponse
+            | Self::Item(_)
+            | Self::ObjectVersionNotFound { .. } => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn object(i: u8) -> Value {
+        json!({
+            "data": {
+                "objectId": ObjectId::new([i; 32]),
+                "version": i,
+                "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt",
+                "type": "0x2::coin::Coin<0x2::sui::SUI>"
+            }
+        })
+    }
+
+    #[test]
+    fn requests() {
+        let mut batch = BatchRequest::new();
+        let object_ids: Vec<_> = (0..60).map(|i| ObjectId::new([i; 32])).collect();
+        batch.multi_get_objects(&object_ids);
+        batch.multi_get_transactions(&[TransactionDigest::ZERO]);
+        assert_eq!(batch.push("sui_getChainIdentifier", json!([])), 3);
+
+        let json = serde_json::to_value(&batch).unwrap();
+        let requests = json.as_array().unwrap();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(requests[0]["jsonrpc"], "2.0");
+        assert_eq!(requests[0]["method"], "sui_multiGetObjects");
+        assert_eq!(requests[0]["params"][0].as_array().unwrap().len(), 50);
+        assert_eq!(requests[1]["id"], 1);
+        assert_eq!(requests[1]["params"][0].as_array().unwrap().len(), 10);
+        assert_eq!(requests[2]["method"], "sui_multiGetTransactionBlocks");
+        assert_eq!(requests[2]["params"][1], json!({ "showRawInput": true }));
+
+        let requests: Vec<JsonRpcRequest> = serde_json::from_value(json).unwrap();
+        assert_eq!(requests, batch.requests());
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn dry_run() {
+        let transaction = Transaction::random();
+        let mut batch = BatchRequest::new();
+        let id = batch.dry_run_transaction(&transaction);
+        let request = &batch.requests()[0];
+        assert_eq!(request.method, "sui_dryRunTransactionBlock");
+        let tx_bytes = request.params[0].as_str().unwrap();
+        assert_eq!(
+            crate::encoding::from_bcs_base64::<Transaction>(tx_bytes).unwrap(),
+            transaction
+        );
+
+        let gas_used = json!({
+            "computationCost": "1000",
+            "storageCost": "0",
+            "storageRebate": "0",
+            "nonRefundableStorageFee": "0"
+        });
+        let gas_data = crate::json_rpc::SuiGasData::from(transaction.gas_payment.clone());
+        let responses: Vec<JsonRpcResponse> = serde_json::from_value(json!([{
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {
+                "effects": { "status": { "status": "success" }, "gasUsed": gas_used },
+                "input": { "gasData": gas_data },
+                "balanceChanges": [],
+                "objectChanges": []
+            }
+        }]))
+        .unwrap();
+        let preview = BatchResponses::new(responses).preview(id).unwrap();
+        assert_eq!(preview.gas_owner, transaction.gas_payment.owner);
+        assert_eq!(preview.address(&preview.gas_owner).unwrap().gas, 1000);
+    }
+
+    #[test]
+    fn per_item_errors() {
+        let mut batch = BatchRequest::new();
+        let object_ids: Vec<_> = (0..55).map(|i| ObjectId::new([i; 32])).collect();
+        let objects = batch.multi_get_objects(&object_ids[..52]);
+        let more_objects = batch.multi_get_objects(&object_ids[52..]);
+        let transactions = batch.multi_get_transactions(&[TransactionDigest::ZERO]);
+
+        let mut first: Vec<_> = (0..50).map(object).collect();
+        first[7] = json!({ "error": { "code": "notExists", "object_id": object_ids[7] } });
+        // Responses may come back in any order
+        let responses: Vec<JsonRpcResponse> = serde_json::from_value(json!([
+            { "jsonrpc": "2.0", "id": 1, "result": [object(50), object(51)] },
+            { "jsonrpc": "2.0", "id": 0, "result": first },
+            {
+                "jsonrpc": "2.0",
+                "id": 2,
+                "error": { "code": -32000, "message": "too many requests" }
+            },
+        ]))
+        .unwrap();
+        let responses = BatchResponses::new(responses);
+
+        let results = objects.results(&responses);
+        assert_eq!(results.len(), 52);
+        for (i, result) in results.iter().enumerate() {
+            match result {
+                Err(BatchItemError::Item(error)) => {
+                    assert_eq!(i, 7);
+                    assert_eq!(error["code"], "notExists");
+                }
+                Ok(reference) => assert_eq!(reference.object_id, object_ids[i]),
+                Err(e) => panic!("unexpected error {e}"),
+            }
+        }
+
+        let results = more_objects.results(&responses);
+        assert_eq!(results.len(), 3);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Err(BatchItemError::Rpc(e)) if e.code == -32000)));
+
+        let results = transactions.results(&responses);
+        assert!(matches!(
+            results[..],
+            [Err(BatchItemError::MissingResponse)]
+        ));
+    }
+
+    #[test]
+    fn archival_fallback() {
+        let mut batch = BatchRequest::new();
+        let objects = batch.multi_get_past_objects(&[
+            (ObjectId::new([1; 32]), Version::new(1)),
+            (ObjectId::new([2; 32]), Version::new(2)),
+        ]);
+        let transactions = batch.multi_get_transactions(&[TransactionDigest::ZERO]);
+        let latest = batch.multi_get_objects(&[ObjectId::new([3; 32])]);
+        assert_eq!(batch.requests()[0].params[0][1]["version"], "2");
+
+        let pruned_transaction = json!({
+            "code": -32602,
+            "message": "Could not find the referenced transactions [TransactionDigest(11111111111111111111111111111111)]."
+        });
+        let responses: Vec<JsonRpcResponse> = serde_json::from_value(json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": [
+                    { "status": "VersionFound", "details": object(1)["data"] },
+                    { "status": "VersionNotFound", "details": [ObjectId::new([2; 32]), 2] },
+                ]
+            },
+            { "jsonrpc": "2.0", "id": 1, "error": pruned_transaction },
+            { "jsonrpc": "2.0", "id": 2, "result": [object(3)] },
+        ]))
+        .unwrap();
+        let responses = BatchResponses::new(responses);
+
+        let results = objects.results(&responses);
+        assert_eq!(results[0].as_ref().unwrap().version, Version::new(1));
+        assert!(matches!(
+            results[1],
+            Err(BatchItemError::ObjectVersionNotFound { object_id, version })
+                if object_id == ObjectId::new([2; 32]) && version == Version::new(2)
+        ));
+        let results = transactions.results(&responses);
+        assert!(
+            matches!(&results[..], [Err(e @ BatchItemError::TransactionNotFound(_))] if e.is_pruned())
+        );
+        assert!(latest.results(&responses)[0].is_ok());
+
+        // Only the requests hitting pruned data are sent to the archival node
+        let fallback = batch.pruned(&responses);
+        let ids: Vec<_> = fallback
+            .requests()
+            .iter()
+            .map(|request| request.id)
+            .collect();
+        assert_eq!(ids, [0, 1]);
+
+        let archival: Vec<JsonRpcResponse> = serde_json::from_value(json!([
+            {
+                "jsonrpc": "2.0",
+                "id": 0,
+                "result": [
+                    { "status": "VersionFound", "details": object(1)["data"] },
+                    { "status": "VersionFound", "details": object(2)["data"] },
+                ]
+            },
+        ]))
+        .unwrap();
+        let responses = responses.with_fallback(BatchResponses::new(archival));
+        let results = objects.results(&responses);
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(results[1].as_ref().unwrap().version, Version::new(2));
+        assert!(!responses.is_pruned(0));
+        // The archival node didn't answer for the transaction
+        assert!(responses.is_pruned(1));
+    }
+}
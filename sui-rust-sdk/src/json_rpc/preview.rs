@@ -0,0 +1,316 @@
+//! Summaries of dry runs, for wallets to show what a transaction does before it is signed.
+
+use std::collections::BTreeMap;
+
+use super::DryRunTransactionBlockResponse;
+use super::SuiExecutionStatus;
+use super::SuiObjectChange;
+use crate::types::Address;
+use crate::types::GasCostSummary;
+use crate::types::ObjectId;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+/// What a transaction would do, by address, as found by a dry run.
+///
+/// Balance changes and object changes owned by objects, shared or immutable are left out, as they
+/// aren't any address's to approve.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionPreview {
+    /// Whether the transaction would succeed. A failed transaction is still charged for gas.
+    pub status: SuiExecutionStatus,
+    /// The address paying for gas.
+    pub gas_owner: Address,
+    pub gas_used: GasCostSummary,
+    pub addresses: BTreeMap<Address, AddressPreview>,
+}
+
+/// What a transaction would do to an address.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AddressPreview {
+    /// The change of the balance of each coin type, where a negative amount is spent. The change
+    /// of the gas owner's SUI balance includes the gas paid.
+    pub balances: BTreeMap<TypeTag, i128>,
+    /// The gas paid, net of storage rebates, which is only charged to the gas owner and may be
+    /// negative when the transaction frees more storage than it uses.
+    pub gas: i64,
+    pub objects: Vec<ObjectPreview>,
+}
+
+/// An object changed by a transaction, from the point of view of an address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ObjectPreview {
+    pub object_id: ObjectId,
+    pub object_type: StructTag,
+    pub kind: ObjectChangeKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ObjectChangeKind {
+    /// Created, and owned by the address.
+    Created,
+    /// Changed, and owned by the address.
+    Mutated,
+    /// Transferred to the address.
+    Received,
+    /// Deleted by a transaction of the address.
+    Deleted,
+    /// Wrapped in another object by a transaction of the address.
+    Wrapped,
+}
+
+impl TransactionPreview {
+    pub fn is_ok(&self) -> bool {
+        self.status.is_ok()
+    }
+
+    /// What the transaction would do to `address`, or `None` if it doesn't affect it.
+    pub fn address(&self, address: &Address) -> Option<&AddressPreview> {
+        self.addresses.get(address)
+    }
+
+    /// The change of the balance of `coin_type` of `address`, which is zero if unchanged.
+    pub fn balance_change(&self, address: &Address, coin_type: &TypeTag) -> i128 {
+        self.address(address)
+            .and_then(|preview| preview.balances.get(coin_type))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+impl From<DryRunTransactionBlockResponse> for TransactionPreview {
+    fn from(response: DryRunTransactionBlockResponse) -> Self {
+        let gas_owner = response.input.gas_data.owner;
+        let gas_used = GasCostSummary::from(response.effects.gas_used);
+        let mut addresses = BTreeMap::<Address, AddressPreview>::new();
+        addresses.entry(gas_owner).or_default().gas = gas_used.net_gas_usage();
+
+        for change in response.balance_changes {
+            if change.amount == 0 {
+                continue;
+            }
+            if let Some(address) = change.owner.address() {
+                *addresses
+                    .entry(address)
+                    .or_default()
+                    .balances
+                    .entry(change.coin_type)
+                    .or_default() += change.amount;
+            }
+        }
+
+        for change in response.object_changes {
+            let (address, object_id, object_type, kind) = match change {
+                // Packages have no owner, their upgrade capability is reported as created
+                SuiObjectChange::Published { .. } => continue,
+                SuiObjectChange::Created {
+                    owner,
+                    object_type,
+                    object_id,
+                    ..
+                } => (
+                    owner.address(),
+                    object_id,
+                    object_type,
+                    ObjectChangeKind::Created,
+                ),
+                SuiObjectChange::Mutated {
+                    owner,
+                    object_type,
+                    object_id,
+                    ..
+                } => (
+                    owner.address(),
+                    object_id,
+                    object_type,
+                    ObjectChangeKind::Mutated,
+                ),
+                SuiObjectChange::Transferred {
+                    recipient,
+                    object_type,
+                    object_id,
+                    ..
+                } => (
+                    recipient.address(),
+                    object_id,
+                    object_type,
+                    ObjectChangeKind::Received,
+                ),
+                SuiObjectChange::Deleted {
+                    sender,
+                    object_type,
+                    object_id,
+                    ..
+                } => (
+                    Some(sender),
+                    object_id,
+                    object_type,
+                    ObjectChangeKind::Deleted,
+                ),
+                SuiObjectChange::Wrapped {
+                    sender,
+                    object_type,
+                    object_id,
+                    ..
+                } => (
+                    Some(sender),
+                    object_id,
+                    object_type,
+                    ObjectChangeKind::Wrapped,
+                ),
+            };
+            if let Some(address) = address {
+                addresses
+                    .entry(address)
+                    .or_default()
+                    .objects
+                    .push(ObjectPreview {
+                        object_id,
+                        object_type,
+                        kind,
+                    });
+            }
+        }
+
+        Self {
+            status: response.effects.status,
+            gas_owner,
+            gas_used,
+            addresses,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const SENDER: &str = "0x0000000000000000000000000000000000000000000000000000000000000a11";
+    const RECIPIENT: &str = "0x0000000000000000000000000000000000000000000000000000000000000b0b";
+
+    fn dry_run() -> serde_json::Value {
+        serde_json::json!({
+            "effects": {
+                "status": { "status": "success" },
+                "gasUsed": {
+                    "computationCost": "1000000",
+                    "storageCost": "2000000",
+                    "storageRebate": "978120",
+                    "nonRefundableStorageFee": "9880"
+                },
+                "transactionDigest": "7CuBm1AnLgkBMB6GiEn5d3RizznF5LbawjJTs8A5dcXF"
+            },
+            "events": [],
+            "input": {
+                "messageVersion": "v1",
+                "gasData": {
+                    "payment": [],
+                    "owner": SENDER,
+                    "price": "1000",
+                    "budget": "10000000"
+                }
+            },
+            "balanceChanges": [
+                {
+                    "owner": { "AddressOwner": SENDER },
+                    "coinType": "0x2::sui::SUI",
+                    "amount": "-1002021880"
+                },
+                {
+                    "owner": { "AddressOwner": RECIPIENT },
+                    "coinType": "0x2::sui::SUI",
+                    "amount": "1000000000"
+                },
+                {
+                    "owner": { "ObjectOwner": RECIPIENT },
+                    "coinType": "0x2::sui::SUI",
+                    "amount": "5"
+                }
+            ],
+            "objectChanges": [
+                {
+                    "type": "mutated",
+                    "sender": SENDER,
+                    "owner": { "AddressOwner": SENDER },
+                    "objectType": "0x2::coin::Coin<0x2::sui::SUI>",
+                    "objectId": "0x01",
+                    "version": "3",
+                    "previousVersion": "2",
+                    "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt"
+                },
+                {
+                    "type": "created",
+                    "sender": SENDER,
+                    "owner": { "AddressOwner": RECIPIENT },
+                    "objectType": "0x2::coin::Coin<0x2::sui::SUI>",
+                    "objectId": "0x02",
+                    "version": "3",
+                    "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt"
+                },
+                {
+                    "type": "created",
+                    "sender": SENDER,
+                    "owner": { "Shared": { "initial_shared_version": 3 } },
+                    "objectType": "0x2::kiosk::Kiosk",
+                    "objectId": "0x03",
+                    "version": "3",
+                    "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt"
+                },
+                {
+                    "type": "published",
+                    "packageId": "0x04",
+                    "version": "1",
+                    "digest": "EnRQXe1hDGAJCFyF2ds2GmPHdvf9V6yxf24LisEsDkYt",
+                    "modules": ["example"]
+                }
+            ]
+        })
+    }
+
+    #[test]
+    fn preview() {
+        let response: DryRunTransactionBlockResponse = serde_json::from_value(dry_run()).unwrap();
+        let preview = TransactionPreview::from(response);
+        let sender: Address = SENDER.parse().unwrap();
+        let recipient: Address = RECIPIENT.parse().unwrap();
+        let sui: TypeTag = "0x2::sui::SUI".parse().unwrap();
+
+        assert!(preview.is_ok());
+        assert_eq!(preview.gas_owner, sender);
+        assert_eq!(preview.addresses.len(), 2);
+        assert_eq!(preview.balance_change(&sender, &sui), -1_002_021_880);
+        assert_eq!(preview.balance_change(&recipient, &sui), 1_000_000_000);
+        assert_eq!(preview.address(&sender).unwrap().gas, 2_021_880);
+        assert_eq!(preview.address(&recipient).unwrap().gas, 0);
+
+        let objects = &preview.address(&recipient).unwrap().objects;
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects[0].object_id, "0x02".parse().unwrap());
+        assert_eq!(objects[0].kind, ObjectChangeKind::Created);
+        assert_eq!(
+            preview.address(&sender).unwrap().objects[0].kind,
+            ObjectChangeKind::Mutated
+        );
+    }
+
+    #[test]
+    fn failed_preview() {
+        let mut json = dry_run();
+        json["effects"]["status"] = serde_json::json!({
+            "status": "failure",
+            "error": "InsufficientGas"
+        });
+        json["balanceChanges"] = serde_json::json!([]);
+        json["objectChanges"] = serde_json::json!([]);
+        let response: DryRunTransactionBlockResponse = serde_json::from_value(json).unwrap();
+        let preview = TransactionPreview::from(response);
+
+        assert!(!preview.is_ok());
+        // The gas owner is still charged
+        assert_eq!(preview.addresses.len(), 1);
+        assert_eq!(preview.address(&preview.gas_owner).unwrap().gas, 2_021_880);
+    }
+}
@@ -2,10 +2,132 @@
 
 pub mod types;
 
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod encoding;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod json_rpc;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod filter;
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub mod display;
+
+#[cfg(feature = "graphql")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "graphql")))]
+pub mod graphql;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod deepbook;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod deny_list;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod system_state;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub mod schema;
+
+pub mod bytecode;
+
 #[cfg(feature = "hash")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
 pub mod hash;
 
+#[cfg(feature = "hash")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+pub mod derivation;
+
+#[cfg(feature = "proto")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "proto")))]
+pub mod proto;
+
+#[cfg(feature = "test-vectors")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test-vectors")))]
+pub mod test_vectors;
+
+#[cfg(feature = "parachain")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "parachain")))]
+pub mod parachain;
+
+#[cfg(feature = "relayer")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "relayer")))]
+pub mod relayer;
+
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+pub mod light_client;
+
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+pub mod authority_key;
+
+#[cfg(feature = "checkpoint-follower")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "checkpoint-follower")))]
+pub mod checkpoint_follower;
+
+#[cfg(feature = "quorum-driver")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "quorum-driver")))]
+pub mod quorum_driver;
+
+#[cfg(feature = "offline")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "offline")))]
+pub mod offline;
+
+#[cfg(feature = "session")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "session")))]
+pub mod session;
+
+#[cfg(feature = "multisig")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "multisig")))]
+pub mod multisig;
+
+#[cfg(feature = "replay")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "replay")))]
+pub mod replay;
+
+#[cfg(feature = "pagination")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "pagination")))]
+pub mod pagination;
+
+#[cfg(feature = "archive")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "archive")))]
+pub mod archive;
+
+#[cfg(feature = "snapshot")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "snapshot")))]
+pub mod snapshot;
+
+#[cfg(feature = "accumulator")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "accumulator")))]
+pub mod accumulator;
+
+pub mod portfolio;
+
+pub mod transport;
+
+pub mod network;
+
+pub mod builder;
+
+#[cfg(all(feature = "test_utils", not(target_arch = "wasm32")))]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "test_utils")))]
+pub mod test_utils;
+
+#[cfg(feature = "observability")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "observability")))]
+pub mod observability;
+
 #[cfg(feature = "serde")]
 mod _serde {
     use base64ct::Base64;
@@ -28,6 +150,55 @@ mod _serde {
     pub(crate) type ReadableBase64Encoded =
         ::serde_with::As<::serde_with::IfIsHumanReadable<Base64Encoded, ::serde_with::Bytes>>;
 
+    /// Reads the index of an enum variant, from either its index, as BCS encodes it, or its name,
+    /// as other binary formats like CBOR may.
+    pub(crate) struct VariantIndex(pub(crate) &'static [&'static str]);
+
+    impl<'de> serde::de::DeserializeSeed<'de> for VariantIndex {
+        type Value = u32;
+
+        fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_identifier(self)
+        }
+    }
+
+    impl<'de> serde::de::Visitor<'de> for VariantIndex {
+        type Value = u32;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a variant index or name")
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            u32::try_from(v).map_err(E::custom)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            self.0
+                .iter()
+                .position(|name| *name == v)
+                .map(|index| index as u32)
+                .ok_or_else(|| E::unknown_variant(v, self.0))
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            let v = std::str::from_utf8(v).map_err(E::custom)?;
+            self.visit_str(v)
+        }
+    }
+
     pub(crate) struct Base64Encoded;
 
     impl<T: AsRef<[u8]>> SerializeAs<T> for Base64Encoded {
@@ -0,0 +1,231 @@
+//! A light client following the validator committee of Sui from epoch to epoch.
+//!
+//! The [`LightClientState`] starts from a trusted committee, e.g. the genesis committee of a
+//! network, and verifies checkpoints against it. The last checkpoint of an epoch carries the
+//! committee of the next epoch, and verifying it rotates the state to that committee, so that the
+//! state only ever trusts committees certified by their predecessor.
+//!
+//! The state is serializable so that it can be persisted and restored between runs.
+
+use crate::types::CheckpointDigest;
+use crate::types::CheckpointSequenceNumber;
+use crate::types::CheckpointVerificationError;
+use crate::types::EpochId;
+use crate::types::SignedCheckpointSummary;
+use crate::types::ValidatorCommittee;
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct LightClientState {
+    committee: ValidatorCommittee,
+    latest_checkpoint: Option<VerifiedCheckpoint>,
+}
+
+/// The latest checkpoint verified by a [`LightClientState`].
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct VerifiedCheckpoint {
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub sequence_number: CheckpointSequenceNumber,
+    pub digest: CheckpointDigest,
+}
+
+impl LightClientState {
+    /// Create a state trusting `committee`.
+    pub fn new(committee: ValidatorCommittee) -> Self {
+        Self {
+            committee,
+            latest_checkpoint: None,
+        }
+    }
+
+    /// The committee of the current epoch.
+    pub fn committee(&self) -> &ValidatorCommittee {
+        &self.committee
+    }
+
+    pub fn epoch(&self) -> EpochId {
+        self.committee.epoch
+    }
+
+    /// The verified checkpoint with the highest sequence number, if any.
+    pub fn latest_checkpoint(&self) -> Option<&VerifiedCheckpoint> {
+        self.latest_checkpoint.as_ref()
+    }
+
+    /// Verify that `summary` is a checkpoint of the current epoch certified by its committee.
+    pub fn verify(
+        &self,
+        summary: &SignedCheckpointSummary,
+    ) -> Result<(), CheckpointVerificationError> {
+        self.committee.verify_checkpoint_summary(summary)
+    }
+
+    /// Verify `summary` and record it, rotating to the committee of the next epoch if it is the
+    /// last checkpoint of the current epoch.
+    ///
+    /// Returns `true` if the committee was rotated.
+    pub fn update(
+        &mut self,
+        summary: &SignedCheckpointSummary,
+    ) -> Result<bool, CheckpointVerificationError> {
+        self.verify(summary)?;
+
+        let checkpoint = &summary.checkpoint;
+        let next_committee = match &checkpoint.end_of_epoch_data {
+            Some(end_of_epoch_data) => Some(ValidatorCommittee {
+                epoch: checkpoint
+                    .epoch
+                    .checked_add(1)
+                    .ok_or(CheckpointVerificationError::EpochOverflow)?,
+                members: end_of_epoch_data.next_epoch_committee.clone(),
+            }),
+            None => None,
+        };
+
+        if self
+            .latest_checkpoint
+            .as_ref()
+            .is_none_or(|latest| latest.sequence_number < checkpoint.sequence_number)
+        {
+            self.latest_checkpoint = Some(VerifiedCheckpoint {
+                sequence_number: checkpoint.sequence_number,
+                digest: checkpoint.digest(),
+            });
+        }
+
+        let Some(next_committee) = next_committee else {
+            return Ok(false);
+        };
+        self.committee = next_committee;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::test_committee;
+    use crate::types::CheckpointContentsDigest;
+    use crate::types::CheckpointSummary;
+    use crate::types::EndOfEpochData;
+    use crate::types::GasCostSummary;
+    use blst::min_sig::SecretKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn secret_keys(seed: u8) -> Vec<SecretKey> {
+        test_committee::secret_keys(seed, 4)
+    }
+
+    fn checkpoint(
+        epoch: u64,
+        sequence_number: CheckpointSequenceNumber,
+        next_committee: Option<&[SecretKey]>,
+    ) -> CheckpointSummary {
+        CheckpointSummary {
+            epoch: EpochId::new(epoch),
+            sequence_number,
+            network_total_transactions: sequence_number,
+            content_digest: CheckpointContentsDigest::ZERO,
+            previous_digest: None,
+            epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+            timestamp_ms: 0,
+            checkpoint_commitments: vec![],
+            end_of_epoch_data: next_committee.map(|keys| EndOfEpochData {
+                next_epoch_committee: test_committee::members(keys),
+                next_epoch_protocol_version: 1,
+                epoch_commitments: vec![],
+            }),
+            version_specific_data: vec![],
+        }
+    }
+
+    fn sign(keys: &[SecretKey], checkpoint: CheckpointSummary) -> SignedCheckpointSummary {
+        let mut message = vec![2, 0, 0];
+        message.extend(bcs::to_bytes(&checkpoint).unwrap());
+        message.extend(checkpoint.epoch.into_inner().to_le_bytes());
+
+        SignedCheckpointSummary {
+            signature: test_committee::sign_aggregated(
+                keys,
+                &[0, 1, 2],
+                checkpoint.epoch,
+                &message,
+            ),
+            checkpoint,
+        }
+    }
+
+    #[test]
+    fn rotate_committees() {
+        let epoch_0 = secret_keys(0);
+        let epoch_1 = secret_keys(10);
+        let mut state = LightClientState::new(test_committee::committee(EpochId::new(0), &epoch_0));
+
+        let first = sign(&epoch_0, checkpoint(0, 1, None));
+        assert_eq!(state.update(&first), Ok(false));
+        assert_eq!(
+            state.latest_checkpoint(),
+            Some(&VerifiedCheckpoint {
+                sequence_number: 1,
+                digest: first.checkpoint.digest(),
+            })
+        );
+
+        // The next committee can't certify checkpoints before it is rotated in
+        assert_eq!(
+            state.update(&sign(&epoch_1, checkpoint(0, 2, None))),
+            Err(CheckpointVerificationError::InvalidSignature)
+        );
+
+        let end_of_epoch = sign(&epoch_0, checkpoint(0, 2, Some(&epoch_1)));
+        assert_eq!(state.update(&end_of_epoch), Ok(true));
+        assert_eq!(state.epoch(), EpochId::new(1));
+        assert_eq!(state.committee().members, test_committee::members(&epoch_1));
+
+        // Checkpoints of the previous epoch are no longer accepted
+        assert_eq!(
+            state.update(&first),
+            Err(CheckpointVerificationError::EpochMismatch {
+                expected: EpochId::new(1),
+                actual: EpochId::new(0)
+            })
+        );
+        assert_eq!(
+            state.update(&sign(&epoch_1, checkpoint(1, 3, None))),
+            Ok(false)
+        );
+        assert_eq!(state.latest_checkpoint().unwrap().sequence_number, 3);
+    }
+
+    #[test]
+    fn last_epoch() {
+        let keys = secret_keys(0);
+        let mut state =
+            LightClientState::new(test_committee::committee(EpochId::new(u64::MAX), &keys));
+        let end_of_epoch = sign(&keys, checkpoint(u64::MAX, 1, Some(&secret_keys(10))));
+        assert_eq!(
+            state.update(&end_of_epoch),
+            Err(CheckpointVerificationError::EpochOverflow)
+        );
+        assert_eq!(state.epoch(), EpochId::new(u64::MAX));
+        assert_eq!(state.latest_checkpoint(), None);
+    }
+
+    #[test]
+    fn persist_state() {
+        let keys = secret_keys(0);
+        let mut state = LightClientState::new(test_committee::committee(EpochId::new(0), &keys));
+        state
+            .update(&sign(&keys, checkpoint(0, 1, Some(&secret_keys(10)))))
+            .unwrap();
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(
+            serde_json::from_str::<LightClientState>(&json).unwrap(),
+            state
+        );
+        let bcs = bcs::to_bytes(&state).unwrap();
+        assert_eq!(bcs::from_bytes::<LightClientState>(&bcs).unwrap(), state);
+    }
+}
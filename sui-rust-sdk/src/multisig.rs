@@ -0,0 +1,544 @@
+//! Collecting the signatures of the members of a multisig committee for a transaction.
+//!
+//! A [`SigningSession`] is opened for a transaction sent, or paid for, by the address of a
+//! committee, and handed from member to member, e.g. as an offline package, each adding the
+//! signature they made with [`OfflineSigner::sign_digest`](crate::offline::OfflineSigner). Every
+//! signature is checked against the signing digest of the transaction and the key of the member
+//! before it is recorded, and the session can be persisted with serde between signers. Once the
+//! members who signed weigh at least the threshold of the committee, the session is finalized into
+//! the [`MultisigAggregatedSignature`] of the transaction.
+//!
+//! Custody pipelines where members approve transactions through a service, e.g. a webhook, send
+//! them the [`ApprovalRequest`] of the session, with a [`TransactionPreview`] to review, and record
+//! their answers with [`SigningSession::add_approval`].
+//!
+//! Only signatures of secp256k1 members can be verified, so only they can sign in a session.
+
+use std::collections::BTreeMap;
+
+use crate::types::Address;
+use crate::types::InputArgument;
+use crate::types::MultisigAggregatedSignature;
+use crate::types::MultisigCommittee;
+use crate::types::MultisigMemberPublicKey;
+use crate::types::MultisigMemberSignature;
+use crate::types::ObjectId;
+use crate::types::Secp256k1PublicKey;
+use crate::types::Secp256k1Signature;
+use crate::types::SignatureScheme;
+use crate::types::SimpleSignature;
+use crate::types::Transaction;
+use crate::types::TransactionDigest;
+use crate::types::TransactionExpiration;
+use crate::types::TransactionKind;
+use crate::types::TransactionKindTag;
+use crate::types::UserSignature;
+
+/// The signatures collected so far for a transaction of a multisig committee.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SigningSession {
+    transaction: Transaction,
+    committee: MultisigCommittee,
+    /// The signatures of the members who signed, by their index in the committee.
+    signatures: BTreeMap<u8, MultisigMemberSignature>,
+}
+
+impl SigningSession {
+    /// A session collecting signatures for `transaction`, which the address of `committee` must
+    /// send or pay for.
+    pub fn new(
+        transaction: Transaction,
+        committee: MultisigCommittee,
+    ) -> Result<Self, SigningSessionError> {
+        if !committee.is_valid() {
+            return Err(SigningSessionError::InvalidCommittee);
+        }
+        let address = committee.to_address();
+        if address != transaction.sender && address != transaction.gas_payment.owner {
+            return Err(SigningSessionError::UnexpectedSigner(address));
+        }
+        Ok(Self {
+            transaction,
+            committee,
+            signatures: BTreeMap::new(),
+        })
+    }
+
+    pub fn transaction(&self) -> &Transaction {
+        &self.transaction
+    }
+
+    pub fn committee(&self) -> &MultisigCommittee {
+        &self.committee
+    }
+
+    /// Whether the member with `public_key` has signed.
+    pub fn has_signed(&self, public_key: &MultisigMemberPublicKey) -> bool {
+        self.member_index(public_key)
+            .is_some_and(|index| self.signatures.contains_key(&index))
+    }
+
+    /// The public keys of the members who haven't signed yet.
+    pub fn pending(&self) -> impl Iterator<Item = &MultisigMemberPublicKey> {
+        self.committee
+            .members()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.signatures.contains_key(&(*index as u8)))
+            .map(|(_, member)| member.public_key())
+    }
+
+    /// The total weight of the members who signed.
+    pub fn weight(&self) -> u16 {
+        self.signatures
+            .keys()
+            .filter_map(|index| self.committee.members().get(*index as usize))
+            .map(|member| member.weight() as u16)
+            .sum()
+    }
+
+    /// Whether the members who signed weigh at least the threshold of the committee.
+    pub fn is_complete(&self) -> bool {
+        self.weight() >= self.committee.threshold()
+    }
+
+    /// Record the signature of a member of the committee over the transaction, returning whether
+    /// the threshold is now met.
+    pub fn add_signature(
+        &mut self,
+        signature: &UserSignature,
+    ) -> Result<bool, SigningSessionError> {
+        let UserSignature::Simple(SimpleSignature::Secp256k1 {
+            signature,
+            public_key,
+        }) = signature
+        else {
+            return Err(SigningSessionError::UnsupportedSignature(
+                signature.scheme(),
+            ));
+        };
+        let index = self
+            .member_index(&MultisigMemberPublicKey::Secp256k1(*public_key))
+            .ok_or_else(|| SigningSessionError::NotAMember(public_key.to_address()))?;
+        if self.signatures.contains_key(&index) {
+            return Err(SigningSessionError::AlreadySigned(public_key.to_address()));
+        }
+        verify_secp256k1(public_key, signature, &self.transaction.signing_digest())?;
+        self.signatures
+            .insert(index, MultisigMemberSignature::Secp256k1(*signature));
+        Ok(self.is_complete())
+    }
+
+    /// The aggregated signature of the members who signed, once they meet the threshold.
+    pub fn finalize(&self) -> Result<MultisigAggregatedSignature, SigningSessionError> {
+        if !self.is_complete() {
+            return Err(SigningSessionError::BelowThreshold {
+                weight: self.weight(),
+                threshold: self.committee.threshold(),
+            });
+        }
+        let mut bitmap = 0;
+        let mut signatures = Vec::with_capacity(self.signatures.len());
+        for (index, signature) in &self.signatures {
+            bitmap |= 1 << index;
+            signatures.push(signature.clone());
+        }
+        Ok(MultisigAggregatedSignature::new(
+            self.committee.clone(),
+            signatures,
+            bitmap,
+        ))
+    }
+
+    /// The request for the members who haven't signed yet to approve the transaction.
+    pub fn approval_request(&self) -> ApprovalRequest {
+        ApprovalRequest {
+            digest: self.transaction.digest(),
+            committee: self.committee.to_address(),
+            preview: TransactionPreview::new(&self.transaction),
+            pending: self.pending().cloned().collect(),
+            weight: self.weight(),
+            threshold: self.committee.threshold(),
+        }
+    }
+
+    /// Record the signature of an approval of the transaction, returning whether the threshold is
+    /// now met.
+    pub fn add_approval(&mut self, approval: &Approval) -> Result<bool, SigningSessionError> {
+        let digest = self.transaction.digest();
+        if approval.digest != digest {
+            return Err(SigningSessionError::DigestMismatch {
+                expected: digest,
+                actual: approval.digest,
+            });
+        }
+        match &approval.decision {
+            ApprovalDecision::Approve { signature } => self.add_signature(signature),
+            ApprovalDecision::Reject { reason } => Err(SigningSessionError::Rejected {
+                approver: approval.approver.clone(),
+                reason: reason.clone(),
+            }),
+        }
+    }
+
+    fn member_index(&self, public_key: &MultisigMemberPublicKey) -> Option<u8> {
+        self.committee
+            .members()
+            .iter()
+            .position(|member| member.public_key() == public_key)
+            .map(|index| index as u8)
+    }
+}
+
+/// What an approver is shown of a transaction before approving it.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TransactionPreview {
+    pub sender: Address,
+    pub kind: TransactionKindTag,
+    pub gas_owner: Address,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub gas_price: u64,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub gas_budget: u64,
+    pub expiration: TransactionExpiration,
+    /// The objects the transaction takes as input, without its gas coins.
+    pub input_objects: Vec<ObjectId>,
+}
+
+impl TransactionPreview {
+    pub fn new(transaction: &Transaction) -> Self {
+        let input_objects = match &transaction.kind {
+            TransactionKind::ProgrammableTransaction(ptb) => ptb
+                .inputs
+                .iter()
+                .filter_map(|input| match input {
+                    InputArgument::Pure { .. } => None,
+                    InputArgument::ImmutableOrOwned(reference)
+                    | InputArgument::Receiving(reference) => Some(*reference.object_id()),
+                    InputArgument::Shared { object_id, .. } => Some(*object_id),
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        Self {
+            sender: transaction.sender,
+            kind: transaction.kind.tag(),
+            gas_owner: transaction.gas_payment.owner,
+            gas_price: transaction.gas_payment.price,
+            gas_budget: transaction.gas_payment.budget,
+            expiration: transaction.expiration,
+            input_objects,
+        }
+    }
+}
+
+/// A request for the approvers of a multisig committee to sign a transaction, e.g. the body of a
+/// webhook of a custody approval pipeline.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct ApprovalRequest {
+    pub digest: TransactionDigest,
+    /// The address of the committee.
+    pub committee: Address,
+    pub preview: TransactionPreview,
+    /// The public keys of the members who haven't signed yet.
+    pub pending: Vec<MultisigMemberPublicKey>,
+    /// The total weight of the members who signed.
+    pub weight: u16,
+    pub threshold: u16,
+}
+
+/// The answer of an approver to an [`ApprovalRequest`].
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct Approval {
+    /// The digest of the transaction approved or rejected.
+    pub digest: TransactionDigest,
+    /// The identity of the approver in the custody system, e.g. their user id.
+    pub approver: String,
+    pub decision: ApprovalDecision,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    /// The signature of the approver over the transaction, see
+    /// [`OfflineSigner::sign_digest`](crate::offline::OfflineSigner::sign_digest).
+    Approve {
+        signature: UserSignature,
+    },
+    Reject {
+        reason: String,
+    },
+}
+
+/// Verify a secp256k1 signature over the SHA-256 hash of `digest`, as Sui signs them.
+fn verify_secp256k1(
+    public_key: &Secp256k1PublicKey,
+    signature: &Secp256k1Signature,
+    digest: &[u8; 32],
+) -> Result<(), SigningSessionError> {
+    use k256::ecdsa::signature::Verifier;
+
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key.as_bytes())
+        .map_err(|_| SigningSessionError::InvalidSignature)?;
+    let signature = k256::ecdsa::Signature::from_slice(signature.as_bytes())
+        .map_err(|_| SigningSessionError::InvalidSignature)?;
+    key.verify(digest, &signature)
+        .map_err(|_| SigningSessionError::InvalidSignature)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SigningSessionError {
+    /// The committee isn't one Sui accepts, see [`MultisigCommittee::is_valid`].
+    InvalidCommittee,
+    /// The address of the committee is neither the sender nor the gas owner of the transaction.
+    UnexpectedSigner(Address),
+    /// The signature is made with a scheme which can't be verified.
+    UnsupportedSignature(SignatureScheme),
+    NotAMember(Address),
+    AlreadySigned(Address),
+    InvalidSignature,
+    /// The approval is for another transaction.
+    DigestMismatch {
+        expected: TransactionDigest,
+        actual: TransactionDigest,
+    },
+    Rejected {
+        approver: String,
+        reason: String,
+    },
+    BelowThreshold {
+        weight: u16,
+        threshold: u16,
+    },
+}
+
+impl std::fmt::Display for SigningSessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidCommittee => write!(f, "invalid multisig committee"),
+            Self::UnexpectedSigner(address) => write!(
+                f,
+                "committee {address} is neither the sender nor the gas owner"
+            ),
+            Self::UnsupportedSignature(scheme) => write!(
+                f,
+                "signatures with {} aren't supported in signing sessions",
+                scheme.name()
+            ),
+            Self::NotAMember(address) => write!(f, "{address} isn't a member of the committee"),
+            Self::AlreadySigned(address) => write!(f, "{address} has already signed"),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+            Self::DigestMismatch { expected, actual } => {
+                write!(
+                    f,
+                    "approval is for transaction {actual}, expected {expected}"
+                )
+            }
+            Self::Rejected { approver, reason } => {
+                write!(f, "{approver} rejected the transaction: {reason}")
+            }
+            Self::BelowThreshold { weight, threshold } => write!(
+                f,
+                "signatures weigh {weight}, below the threshold of {threshold}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SigningSessionError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::offline::OfflineSigner;
+    use crate::offline::Secp256k1Signer;
+    use crate::types::Argument;
+    use crate::types::Command;
+    use crate::types::EpochId;
+    use crate::types::GasPayment;
+    use crate::types::MultisigMember;
+    use crate::types::ObjectDigest;
+    use crate::types::ObjectReference;
+    use crate::types::ProgrammableTransaction;
+    use crate::types::Secp256k1PrivateKey;
+    use crate::types::TransferObjects;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn signer(seed: u8) -> Secp256k1Signer {
+        Secp256k1Signer::new(&Secp256k1PrivateKey::new([seed; 32])).unwrap()
+    }
+
+    fn committee(signers: &[Secp256k1Signer], threshold: u16) -> MultisigCommittee {
+        let members = signers
+            .iter()
+            .map(|signer| {
+                MultisigMember::new(MultisigMemberPublicKey::Secp256k1(signer.public_key()), 1)
+            })
+            .collect();
+        MultisigCommittee::new(members, threshold)
+    }
+
+    fn transaction(sender: Address) -> Transaction {
+        let reference = |byte| {
+            ObjectReference::new(
+                ObjectId::new([byte; 32]),
+                Version::new(7),
+                ObjectDigest::new([byte; 32]),
+            )
+        };
+        Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    InputArgument::ImmutableOrOwned(reference(1)),
+                    InputArgument::Pure {
+                        value: Address::ZERO.into(),
+                    },
+                ],
+                commands: vec![Command::TransferObjects(TransferObjects {
+                    objects: vec![Argument::Input(0)],
+                    address: Argument::Input(1),
+                })],
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![reference(2)],
+                owner: sender,
+                price: 1_000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::Epoch(EpochId::new(10)),
+        }
+    }
+
+    #[test]
+    fn two_of_three() {
+        let signers = [signer(1), signer(2), signer(3)];
+        let committee = committee(&signers, 2);
+        let transaction = transaction(committee.to_address());
+        let digest = transaction.signing_digest();
+
+        assert_eq!(
+            SigningSession::new(transaction.clone(), self::committee(&signers[..2], 2))
+                .unwrap_err(),
+            SigningSessionError::UnexpectedSigner(self::committee(&signers[..2], 2).to_address())
+        );
+        let mut session = SigningSession::new(transaction, committee).unwrap();
+
+        let third = signers[2].sign_digest(&digest).unwrap();
+        assert_eq!(session.add_signature(&third), Ok(false));
+        assert_eq!(
+            session.add_signature(&third),
+            Err(SigningSessionError::AlreadySigned(signers[2].address()))
+        );
+        assert_eq!(
+            session.add_signature(&signer(4).sign_digest(&digest).unwrap()),
+            Err(SigningSessionError::NotAMember(signer(4).address()))
+        );
+        assert_eq!(
+            session.add_signature(&signers[0].sign_digest(&[0; 32]).unwrap()),
+            Err(SigningSessionError::InvalidSignature)
+        );
+        assert_eq!(
+            session.finalize(),
+            Err(SigningSessionError::BelowThreshold {
+                weight: 1,
+                threshold: 2
+            })
+        );
+
+        // Handed to the next signer
+        let bytes = bcs::to_bytes(&session).unwrap();
+        let mut session: SigningSession = bcs::from_bytes(&bytes).unwrap();
+        assert!(session.has_signed(&MultisigMemberPublicKey::Secp256k1(signers[2].public_key())));
+        assert_eq!(session.pending().count(), 2);
+
+        let first = signers[0].sign_digest(&digest).unwrap();
+        assert_eq!(session.add_signature(&first), Ok(true));
+
+        let multisig = session.finalize().unwrap();
+        assert_eq!(multisig.bitmap(), 0b101);
+        let UserSignature::Simple(SimpleSignature::Secp256k1 { signature, .. }) = first else {
+            unreachable!()
+        };
+        assert_eq!(
+            multisig.signatures()[0],
+            MultisigMemberSignature::Secp256k1(signature)
+        );
+        assert_eq!(multisig.committee(), session.committee());
+    }
+
+    #[test]
+    fn invalid_committee() {
+        let transaction = transaction(Address::ZERO);
+        assert_eq!(
+            SigningSession::new(transaction.clone(), committee(&[signer(1)], 2)),
+            Err(SigningSessionError::InvalidCommittee)
+        );
+        assert_eq!(
+            SigningSession::new(transaction, committee(&[signer(1), signer(1)], 1)),
+            Err(SigningSessionError::InvalidCommittee)
+        );
+    }
+
+    #[test]
+    fn approvals() {
+        let signers = [signer(1), signer(2)];
+        let committee = committee(&signers, 2);
+        let transaction = transaction(committee.to_address());
+        let digest = transaction.digest();
+        let mut session = SigningSession::new(transaction.clone(), committee).unwrap();
+
+        let request = session.approval_request();
+        assert_eq!(request.digest, digest);
+        assert_eq!(request.preview.sender, session.committee().to_address());
+        assert_eq!(request.preview.input_objects, vec![ObjectId::new([1; 32])]);
+        assert_eq!(request.pending.len(), 2);
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ApprovalRequest>(&json).unwrap(),
+            request
+        );
+
+        let approve = |signer: &Secp256k1Signer, approver: &str| Approval {
+            digest,
+            approver: approver.to_owned(),
+            decision: ApprovalDecision::Approve {
+                signature: signer.sign_digest(&transaction.signing_digest()).unwrap(),
+            },
+        };
+        let approval = approve(&signers[0], "alice");
+        let json = serde_json::to_string(&approval).unwrap();
+        let approval: Approval = serde_json::from_str(&json).unwrap();
+        assert_eq!(session.add_approval(&approval), Ok(false));
+
+        let rejection = Approval {
+            digest,
+            approver: "bob".to_owned(),
+            decision: ApprovalDecision::Reject {
+                reason: "unknown recipient".to_owned(),
+            },
+        };
+        assert_eq!(
+            session.add_approval(&rejection),
+            Err(SigningSessionError::Rejected {
+                approver: "bob".to_owned(),
+                reason: "unknown recipient".to_owned(),
+            })
+        );
+
+        let mut stale = approve(&signers[1], "bob");
+        stale.digest = TransactionDigest::ZERO;
+        assert_eq!(
+            session.add_approval(&stale),
+            Err(SigningSessionError::DigestMismatch {
+                expected: digest,
+                actual: TransactionDigest::ZERO,
+            })
+        );
+        assert_eq!(session.add_approval(&approve(&signers[1], "bob")), Ok(true));
+        assert_eq!(session.approval_request().pending, vec![]);
+    }
+}
@@ -0,0 +1,389 @@
+//! Presets for connecting to the public Sui networks, or to a local one.
+//!
+//! A [`ClientConfig`] holds the endpoints of a network, the identifier of its chain and the
+//! lowest protocol version it is expected to run, along with the [`TransportConfig`] of the
+//! connection. Clients check what the node reports on connecting with
+//! [`ClientConfig::verify_chain_identifier`], so that transactions meant for a test network are
+//! never signed for mainnet, or the other way around:
+//!
+//! ```
+//! use sui_sdk::network::ClientConfig;
+//!
+//! let config = ClientConfig::testnet();
+//! // The result of `sui_getChainIdentifier`
+//! assert!(config.verify_chain_identifier("4c78adac").is_ok());
+//! assert!(config.verify_chain_identifier("35834a8a").is_err());
+//! ```
+//!
+//! Devnet and local networks are wiped regularly, and get a new identifier each time, so their
+//! presets don't pin one. They still refuse the identifiers of mainnet and testnet, and pin the
+//! identifier derived from the genesis checkpoint of the node with
+//! [`ClientConfig::with_genesis`].
+//!
+//! Transactions are bound to a chain while being built by setting
+//! [`UnresolvedTransaction::chain`](crate::types::UnresolvedTransaction::chain), and when signed
+//! offline by the [`SigningContext`](crate::offline::SigningContext) of their package.
+
+use crate::transport::TransportConfig;
+use crate::types::ChainIdentifier;
+#[cfg(all(feature = "hash", feature = "serde"))]
+use crate::types::CheckpointSequenceNumber;
+#[cfg(all(feature = "hash", feature = "serde"))]
+use crate::types::CheckpointSummary;
+use crate::types::ProtocolVersion;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Devnet,
+    Localnet,
+}
+
+impl Network {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Mainnet => "mainnet",
+            Self::Testnet => "testnet",
+            Self::Devnet => "devnet",
+            Self::Localnet => "localnet",
+        }
+    }
+
+    /// The identifier of the network's chain, if it is long-lived.
+    pub fn chain_identifier(self) -> Option<ChainIdentifier> {
+        match self {
+            Self::Mainnet => Some(ChainIdentifier::MAINNET),
+            Self::Testnet => Some(ChainIdentifier::TESTNET),
+            Self::Devnet | Self::Localnet => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Network {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Network {
+    type Err = NetworkError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mainnet" => Ok(Self::Mainnet),
+            "testnet" => Ok(Self::Testnet),
+            "devnet" => Ok(Self::Devnet),
+            "localnet" => Ok(Self::Localnet),
+            _ => Err(NetworkError::UnknownNetwork(s.to_owned())),
+        }
+    }
+}
+
+/// The settings of a client of a network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientConfig {
+    pub network: Network,
+    /// The JSON-RPC endpoint of a fullnode.
+    pub rpc_url: String,
+    pub graphql_url: Option<String>,
+    pub faucet_url: Option<String>,
+    /// The identifier the chain must report, if known.
+    pub chain_identifier: Option<ChainIdentifier>,
+    /// The lowest protocol version the network is expected to run. A node reporting an older
+    /// one is lagging behind, or serving another network.
+    pub min_protocol_version: Option<ProtocolVersion>,
+    pub transport: TransportConfig,
+}
+
+impl ClientConfig {
+    pub fn mainnet() -> Self {
+        Self {
+            network: Network::Mainnet,
+            rpc_url: "https://fullnode.mainnet.sui.io:443".to_owned(),
+            graphql_url: Some("https://sui-mainnet.mystenlabs.com/graphql".to_owned()),
+            faucet_url: None,
+            chain_identifier: Some(ChainIdentifier::MAINNET),
+            min_protocol_version: Some(50),
+            transport: TransportConfig::new(),
+        }
+    }
+
+    pub fn testnet() -> Self {
+        Self {
+            network: Network::Testnet,
+            rpc_url: "https://fullnode.testnet.sui.io:443".to_owned(),
+            graphql_url: Some("https://sui-testnet.mystenlabs.com/graphql".to_owned()),
+            faucet_url: Some("https://faucet.testnet.sui.io".to_owned()),
+            chain_identifier: Some(ChainIdentifier::TESTNET),
+            min_protocol_version: Some(50),
+            transport: TransportConfig::new(),
+        }
+    }
+
+    pub fn devnet() -> Self {
+        Self {
+            network: Network::Devnet,
+            rpc_url: "https://fullnode.devnet.sui.io:443".to_owned(),
+            graphql_url: Some("https://sui-devnet.mystenlabs.com/graphql".to_owned()),
+            faucet_url: Some("https://faucet.devnet.sui.io".to_owned()),
+            chain_identifier: None,
+            min_protocol_version: None,
+            transport: TransportConfig::new(),
+        }
+    }
+
+    /// A network started with `sui start`, on its default ports.
+    pub fn localnet() -> Self {
+        Self {
+            network: Network::Localnet,
+            rpc_url: "http://127.0.0.1:9000".to_owned(),
+            graphql_url: Some("http://127.0.0.1:9125/graphql".to_owned()),
+            faucet_url: Some("http://127.0.0.1:9123".to_owned()),
+            chain_identifier: None,
+            min_protocol_version: None,
+            transport: TransportConfig::new(),
+        }
+    }
+
+    pub fn for_network(network: Network) -> Self {
+        match network {
+            Network::Mainnet => Self::mainnet(),
+            Network::Testnet => Self::testnet(),
+            Network::Devnet => Self::devnet(),
+            Network::Localnet => Self::localnet(),
+        }
+    }
+
+    /// Connect through another fullnode, e.g. a private one, of the same network.
+    pub fn with_rpc_url<T: Into<String>>(self, rpc_url: T) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            ..self
+        }
+    }
+
+    pub fn with_graphql_url<T: Into<String>>(self, graphql_url: T) -> Self {
+        Self {
+            graphql_url: Some(graphql_url.into()),
+            ..self
+        }
+    }
+
+    /// Pin the identifier of the chain, e.g. that of a devnet for as long as it lives.
+    pub fn with_chain_identifier(self, chain_identifier: ChainIdentifier) -> Self {
+        Self {
+            chain_identifier: Some(chain_identifier),
+            ..self
+        }
+    }
+
+    pub fn with_transport(self, transport: TransportConfig) -> Self {
+        Self { transport, ..self }
+    }
+
+    /// Pin the identifier of the chain starting with `genesis`, the checkpoint fetched from the
+    /// node on connecting, after checking it against the identifier already pinned, if any.
+    #[cfg(all(feature = "hash", feature = "serde"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+    pub fn with_genesis(self, genesis: &CheckpointSummary) -> Result<Self, NetworkError> {
+        let chain_identifier =
+            ChainIdentifier::from_genesis(genesis).ok_or(NetworkError::NotGenesis {
+                sequence_number: genesis.sequence_number,
+            })?;
+        self.verify_chain_identifier(&chain_identifier.short_id())?;
+        Ok(self.with_chain_identifier(chain_identifier))
+    }
+
+    /// Check the identifier reported by the node, the short hex form returned by
+    /// `sui_getChainIdentifier`, against that of the network.
+    ///
+    /// Without a pinned identifier, only the identifiers of the networks other than this one
+    /// are refused.
+    pub fn verify_chain_identifier(&self, reported: &str) -> Result<(), NetworkError> {
+        let reported = reported.trim().to_ascii_lowercase();
+        let mismatch = || NetworkError::ChainMismatch {
+            network: self.network,
+            expected: self.chain_identifier.map(|id| id.short_id()),
+            actual: reported.clone(),
+        };
+        match self.chain_identifier {
+            Some(expected) if expected.short_id() != reported => Err(mismatch()),
+            Some(_) => Ok(()),
+            None => {
+                let other = [Network::Mainnet, Network::Testnet]
+                    .into_iter()
+                    .filter(|network| *network != self.network)
+                    .filter_map(Network::chain_identifier)
+                    .any(|id| id.short_id() == reported);
+                if other {
+                    Err(mismatch())
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    /// Check the protocol version reported by the node against the lowest one expected.
+    pub fn verify_protocol_version(&self, reported: ProtocolVersion) -> Result<(), NetworkError> {
+        match self.min_protocol_version {
+            Some(min) if reported < min => Err(NetworkError::ProtocolVersionTooOld {
+                network: self.network,
+                min,
+                actual: reported,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetworkError {
+    UnknownNetwork(String),
+    /// The node serves another chain than that of the network.
+    ChainMismatch {
+        network: Network,
+        expected: Option<String>,
+        actual: String,
+    },
+    ProtocolVersionTooOld {
+        network: Network,
+        min: ProtocolVersion,
+        actual: ProtocolVersion,
+    },
+    /// A checkpoint other than the first was given as the genesis checkpoint.
+    #[cfg(all(feature = "hash", feature = "serde"))]
+    #[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+    NotGenesis {
+        sequence_number: CheckpointSequenceNumber,
+    },
+}
+
+impl std::fmt::Display for NetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownNetwork(name) => write!(f, "unknown network {name}"),
+            Self::ChainMismatch {
+                network,
+                expected: Some(expected),
+                actual,
+            } => write!(
+                f,
+                "node serves chain {actual}, expected {expected} for {network}"
+            ),
+            Self::ChainMismatch {
+                network, actual, ..
+            } => write!(f, "node serves chain {actual}, which isn't {network}"),
+            Self::ProtocolVersionTooOld {
+                network,
+                min,
+                actual,
+            } => write!(
+                f,
+                "node runs protocol version {actual}, expected at least {min} for {network}"
+            ),
+            #[cfg(all(feature = "hash", feature = "serde"))]
+            Self::NotGenesis { sequence_number } => {
+                write!(f, "checkpoint {sequence_number} isn't a genesis checkpoint")
+            }
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn presets() {
+        for network in [
+            Network::Mainnet,
+            Network::Testnet,
+            Network::Devnet,
+            Network::Localnet,
+        ] {
+            let config = ClientConfig::for_network(network);
+            assert_eq!(config.network, network);
+            assert_eq!(config.chain_identifier, network.chain_identifier());
+            assert_eq!(network.name().parse::<Network>(), Ok(network));
+        }
+        assert_eq!(ClientConfig::mainnet().faucet_url, None);
+        assert_eq!(
+            "betanet".parse::<Network>(),
+            Err(NetworkError::UnknownNetwork("betanet".to_owned()))
+        );
+    }
+
+    #[test]
+    fn chain_identifiers() {
+        let mainnet = ClientConfig::mainnet();
+        mainnet.verify_chain_identifier("35834a8a").unwrap();
+        assert_eq!(
+            mainnet.verify_chain_identifier("4c78adac"),
+            Err(NetworkError::ChainMismatch {
+                network: Network::Mainnet,
+                expected: Some("35834a8a".to_owned()),
+                actual: "4c78adac".to_owned(),
+            })
+        );
+
+        // Devnet accepts any chain but those of the long-lived networks.
+        let devnet = ClientConfig::devnet();
+        devnet.verify_chain_identifier("0badf00d").unwrap();
+        assert!(devnet.verify_chain_identifier("35834A8A").is_err());
+        assert!(devnet.verify_chain_identifier("4c78adac").is_err());
+
+        let pinned = devnet.with_chain_identifier(ChainIdentifier::new([0x0b; 32]));
+        pinned.verify_chain_identifier("0b0b0b0b").unwrap();
+        assert!(pinned.verify_chain_identifier("0badf00d").is_err());
+    }
+
+    #[cfg(all(feature = "hash", feature = "serde"))]
+    #[test]
+    fn genesis() {
+        use crate::types::EpochId;
+        use crate::types::GasCostSummary;
+
+        let mut genesis = CheckpointSummary {
+            epoch: EpochId::new(0),
+            sequence_number: 0,
+            network_total_transactions: 1,
+            content_digest: Default::default(),
+            previous_digest: None,
+            epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+            timestamp_ms: 0,
+            checkpoint_commitments: vec![],
+            end_of_epoch_data: None,
+            version_specific_data: vec![],
+        };
+        let config = ClientConfig::devnet().with_genesis(&genesis).unwrap();
+        assert_eq!(
+            config.chain_identifier,
+            Some(ChainIdentifier::from(genesis.digest()))
+        );
+        assert!(ClientConfig::mainnet().with_genesis(&genesis).is_err());
+
+        genesis.sequence_number = 1;
+        assert_eq!(
+            ClientConfig::devnet().with_genesis(&genesis),
+            Err(NetworkError::NotGenesis { sequence_number: 1 })
+        );
+    }
+
+    #[test]
+    fn protocol_versions() {
+        let mainnet = ClientConfig::mainnet();
+        mainnet.verify_protocol_version(70).unwrap();
+        assert!(matches!(
+            mainnet.verify_protocol_version(2),
+            Err(NetworkError::ProtocolVersionTooOld { actual: 2, .. })
+        ));
+        ClientConfig::localnet().verify_protocol_version(1).unwrap();
+    }
+}
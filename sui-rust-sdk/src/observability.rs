@@ -0,0 +1,281 @@
+//! Tracing spans and metrics for the requests made by the SDK's clients.
+//!
+//! Wrapping a transport in [`Instrumented`] runs each of its requests in a `tracing` span named
+//! after the request method, and reports its latency and outcome to a [`ClientMetrics`]
+//! implementation, e.g. one updating Prometheus counters and histograms or OpenTelemetry
+//! instruments. The wrapped transport is then handed to the client as usual:
+//!
+//! ```ignore
+//! let transport = Instrumented::new(transport, metrics);
+//! let mut driver = QuorumDriver::new(transport, committee);
+//! ```
+//!
+//! Latencies are measured with [`std::time::Instant`], which isn't available on
+//! `wasm32-unknown-unknown`.
+
+use std::time::Duration;
+use std::time::Instant;
+
+/// A sink for the metrics of the requests made through an [`Instrumented`] transport.
+pub trait ClientMetrics {
+    /// Record a request to `method` which took `latency`, and failed with an error of class
+    /// `error_class` if it did fail.
+    fn record_request(&self, method: &'static str, latency: Duration, error_class: Option<&str>);
+}
+
+/// Record nothing, for transports which only need tracing spans.
+impl ClientMetrics for () {
+    fn record_request(&self, _: &'static str, _: Duration, _: Option<&str>) {}
+}
+
+impl<M: ClientMetrics + ?Sized> ClientMetrics for &M {
+    fn record_request(&self, method: &'static str, latency: Duration, error_class: Option<&str>) {
+        (**self).record_request(method, latency, error_class)
+    }
+}
+
+impl<M: ClientMetrics + ?Sized> ClientMetrics for std::sync::Arc<M> {
+    fn record_request(&self, method: &'static str, latency: Duration, error_class: Option<&str>) {
+        (**self).record_request(method, latency, error_class)
+    }
+}
+
+/// Errors which fall into a small, fixed set of classes, usable as metric labels.
+pub trait ErrorClass {
+    fn error_class(&self) -> &'static str;
+}
+
+impl ErrorClass for std::io::Error {
+    fn error_class(&self) -> &'static str {
+        use std::io::ErrorKind;
+
+        match self.kind() {
+            ErrorKind::TimedOut | ErrorKind::WouldBlock => "timeout",
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe
+            | ErrorKind::UnexpectedEof => "connection",
+            ErrorKind::InvalidData | ErrorKind::InvalidInput => "invalid_response",
+            _ => "io",
+        }
+    }
+}
+
+impl ErrorClass for std::convert::Infallible {
+    fn error_class(&self) -> &'static str {
+        match *self {}
+    }
+}
+
+/// A transport whose requests are traced and measured.
+#[derive(Clone, Debug)]
+pub struct Instrumented<T, M> {
+    inner: T,
+    metrics: M,
+}
+
+impl<T, M: ClientMetrics> Instrumented<T, M> {
+    pub fn new(inner: T, metrics: M) -> Self {
+        Self { inner, metrics }
+    }
+
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Run `request` to `method` within a span, recording its latency and outcome.
+    ///
+    /// The SDK's transport traits are instrumented through this already, it is exposed for the
+    /// calls of other clients sharing the same metrics.
+    pub fn instrument<R, E: ErrorClass>(
+        &mut self,
+        method: &'static str,
+        request: impl FnOnce(&mut T) -> Result<R, E>,
+    ) -> Result<R, E> {
+        let span = tracing::debug_span!("request", method);
+        let _entered = span.enter();
+
+        let start = Instant::now();
+        let result = request(&mut self.inner);
+        let latency = start.elapsed();
+
+        let error_class = result.as_ref().err().map(ErrorClass::error_class);
+        match error_class {
+            None => tracing::debug!(?latency, "request succeeded"),
+            Some(error_class) => tracing::debug!(?latency, error_class, "request failed"),
+        }
+        self.metrics.record_request(method, latency, error_class);
+        result
+    }
+}
+
+#[cfg(feature = "relayer")]
+mod relayer {
+    use super::*;
+    use crate::relayer::CheckpointSource;
+    use crate::relayer::SignatureTransport;
+    use crate::types::BridgeAuthoritySignature;
+    use crate::types::BridgeCommitteeMember;
+    use crate::types::BridgeMessage;
+    use crate::types::CheckpointData;
+
+    impl<T, M> CheckpointSource for Instrumented<T, M>
+    where
+        T: CheckpointSource,
+        T::Error: ErrorClass,
+        M: ClientMetrics,
+    {
+        type Error = T::Error;
+
+        fn next_checkpoint(&mut self) -> Result<Option<CheckpointData>, Self::Error> {
+            self.instrument("next_checkpoint", |inner| inner.next_checkpoint())
+        }
+    }
+
+    impl<T, M> SignatureTransport for Instrumented<T, M>
+    where
+        T: SignatureTransport,
+        T::Error: ErrorClass,
+        M: ClientMetrics,
+    {
+        type Error = T::Error;
+
+        fn request_signature(
+            &mut self,
+            member: &BridgeCommitteeMember,
+            message: &BridgeMessage,
+        ) -> Result<BridgeAuthoritySignature, Self::Error> {
+            self.instrument("request_signature", |inner| {
+                inner.request_signature(member, message)
+            })
+        }
+    }
+}
+
+#[cfg(feature = "quorum-driver")]
+mod quorum_driver {
+    use super::*;
+    use crate::quorum_driver::ValidatorTransport;
+    use crate::types::CertifiedTransaction;
+    use crate::types::SignedTransaction;
+    use crate::types::SignedTransactionEffects;
+    use crate::types::ValidatorCommitteeMember;
+    use crate::types::ValidatorSignature;
+
+    impl<T, M> ValidatorTransport for Instrumented<T, M>
+    where
+        T: ValidatorTransport,
+        T::Error: ErrorClass,
+        M: ClientMetrics,
+    {
+        type Error = T::Error;
+
+        fn submit_transaction(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            transaction: &SignedTransaction,
+        ) -> Result<ValidatorSignature, Self::Error> {
+            self.instrument("submit_transaction", |inner| {
+                inner.submit_transaction(validator, transaction)
+            })
+        }
+
+        fn submit_certificate(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            certificate: &CertifiedTransaction,
+        ) -> Result<SignedTransactionEffects, Self::Error> {
+            self.instrument("submit_certificate", |inner| {
+                inner.submit_certificate(validator, certificate)
+            })
+        }
+
+        fn submit_soft_bundle(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            certificates: &[CertifiedTransaction],
+        ) -> Option<Result<Vec<SignedTransactionEffects>, Self::Error>> {
+            self.instrument("submit_soft_bundle", |inner| {
+                inner
+                    .submit_soft_bundle(validator, certificates)
+                    .transpose()
+            })
+            .transpose()
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct Recorder(Mutex<Vec<(&'static str, Option<String>)>>);
+
+    impl ClientMetrics for Recorder {
+        fn record_request(&self, method: &'static str, _: Duration, error_class: Option<&str>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push((method, error_class.map(ToOwned::to_owned)));
+        }
+    }
+
+    #[test]
+    fn io_error_classes() {
+        use std::io::Error;
+        use std::io::ErrorKind;
+
+        assert_eq!(Error::from(ErrorKind::TimedOut).error_class(), "timeout");
+        assert_eq!(
+            Error::from(ErrorKind::ConnectionReset).error_class(),
+            "connection"
+        );
+        assert_eq!(Error::from(ErrorKind::PermissionDenied).error_class(), "io");
+    }
+
+    #[test]
+    #[cfg(feature = "relayer")]
+    fn record_requests() {
+        use crate::relayer::CheckpointSource;
+        use crate::types::CheckpointData;
+
+        /// Times out on every other request.
+        struct Flaky(usize);
+
+        impl CheckpointSource for Flaky {
+            type Error = std::io::Error;
+
+            fn next_checkpoint(&mut self) -> Result<Option<CheckpointData>, Self::Error> {
+                self.0 += 1;
+                if self.0.is_multiple_of(2) {
+                    Err(std::io::ErrorKind::TimedOut.into())
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+
+        let recorder = Recorder::default();
+        let mut source = Instrumented::new(Flaky(0), &recorder);
+        assert!(source.next_checkpoint().unwrap().is_none());
+        assert!(source.next_checkpoint().is_err());
+        assert_eq!(source.into_inner().0, 2);
+
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            [
+                ("next_checkpoint", None),
+                ("next_checkpoint", Some("timeout".to_owned()))
+            ]
+        );
+    }
+}
@@ -0,0 +1,1053 @@
+//! Signing transactions on an offline machine, e.g. a cold wallet.
+//!
+//! The online machine resolves the transaction and the objects it reads, and packs them with the
+//! context they were resolved in into an [`UnsignedTransactionPackage`], which it encodes with
+//! [`UnsignedTransactionPackage::to_qr_string`] to carry to the offline machine, e.g. as a QR code.
+//!
+//! The offline machine decodes the package, checks it against the chain it signs for and the
+//! signer's address, and signs it with [`sign_package`]. Only the resulting signature, encoded
+//! with [`signature_to_qr_string`], is carried back, to be attached to the transaction with
+//! [`UnsignedTransactionPackage::into_signed_transaction`].
+//!
+//! Both encodings are Base45, which QR codes store in their compact alphanumeric mode, of a tag
+//! byte followed by the BCS of the value.
+//!
+//! Packages too large for a single QR code, and large signatures like those of zkLogin, can be
+//! split into frames with [`UnsignedTransactionPackage::to_qr_frames`] and
+//! [`signature_to_qr_frames`], and reassembled with a [`QrFrameAssembler`]. Each frame is the
+//! Base45 of a CBOR array of its sequence number, the total number of frames, a checksum of the
+//! payload, and its chunk of the payload.
+
+use crate::encoding::from_base45;
+use crate::encoding::to_base45;
+use crate::encoding::Base45Error;
+use crate::types::Address;
+use crate::types::ChainIdentifier;
+use crate::types::EpochId;
+use crate::types::InputArgument;
+use crate::types::Object;
+use crate::types::ObjectId;
+use crate::types::ObjectReference;
+use crate::types::ObjectReferenceWithOwner;
+use crate::types::Owner;
+use crate::types::SignedTransaction;
+use crate::types::Transaction;
+use crate::types::TransactionExpiration;
+use crate::types::TransactionKind;
+use crate::types::UserSignature;
+
+/// The tag of an encoded [`UnsignedTransactionPackage`].
+const PACKAGE_TAG: u8 = 0;
+/// The tag of an encoded [`UserSignature`].
+const SIGNATURE_TAG: u8 = 1;
+
+/// The chain state a package was resolved against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SigningContext {
+    pub chain: ChainIdentifier,
+    /// The epoch the transaction was resolved in.
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub epoch: EpochId,
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub reference_gas_price: u64,
+}
+
+/// A transaction to sign offline, together with the objects it reads and the context they were
+/// resolved in.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct UnsignedTransactionPackage {
+    pub transaction: Transaction,
+    /// The owned, immutable and receiving inputs of the transaction, and its gas coins.
+    ///
+    /// Shared inputs are only known by their id until consensus assigns their version, so they
+    /// aren't included.
+    pub inputs: Vec<ObjectReferenceWithOwner>,
+    pub context: SigningContext,
+}
+
+impl UnsignedTransactionPackage {
+    pub fn new(
+        transaction: Transaction,
+        inputs: Vec<ObjectReferenceWithOwner>,
+        context: SigningContext,
+    ) -> Self {
+        Self {
+            transaction,
+            inputs,
+            context,
+        }
+    }
+
+    /// A package of `transaction` reading `objects`, as fetched by the online machine.
+    pub fn from_objects<'a>(
+        transaction: Transaction,
+        objects: impl IntoIterator<Item = &'a Object>,
+        context: SigningContext,
+    ) -> Self {
+        let inputs = objects
+            .into_iter()
+            .map(|object| ObjectReferenceWithOwner {
+                reference: ObjectReference::new(
+                    object.object_id(),
+                    object.version(),
+                    object.digest(),
+                ),
+                owner: *object.owner(),
+            })
+            .collect();
+        Self::new(transaction, inputs, context)
+    }
+
+    /// Check that the package is consistent and can be signed by `signer` for `chain`.
+    ///
+    /// The transaction must be a programmable transaction of `signer`, or paid for by it, that
+    /// hasn't expired in the package's epoch and pays at least the reference gas price. Every
+    /// object it references must be among the inputs of the package, at the same version and
+    /// digest, and owned by the sender, or the gas owner for gas coins, unless immutable.
+    pub fn validate(&self, chain: ChainIdentifier, signer: Address) -> Result<(), PackageError> {
+        let transaction = &self.transaction;
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            return Err(PackageError::NotUserTransaction);
+        };
+        if self.context.chain != chain {
+            return Err(PackageError::ChainMismatch {
+                expected: chain,
+                actual: self.context.chain,
+            });
+        }
+        if signer != transaction.sender && signer != transaction.gas_payment.owner {
+            return Err(PackageError::UnexpectedSigner(signer));
+        }
+        if let TransactionExpiration::ValidDuring {
+            chain: expiration_chain,
+            ..
+        } = transaction.expiration
+        {
+            if expiration_chain != chain {
+                return Err(PackageError::ChainMismatch {
+                    expected: chain,
+                    actual: expiration_chain,
+                });
+            }
+        }
+        if let Some(epoch) = transaction.expiration.max_epoch() {
+            if epoch < self.context.epoch {
+                return Err(PackageError::Expired(epoch));
+            }
+        }
+        let gas = &transaction.gas_payment;
+        if gas.price < self.context.reference_gas_price {
+            return Err(PackageError::GasPriceTooLow {
+                price: gas.price,
+                reference_gas_price: self.context.reference_gas_price,
+            });
+        }
+
+        for input in &ptb.inputs {
+            match input {
+                InputArgument::ImmutableOrOwned(reference) => match self.input(reference)?.owner {
+                    Owner::Immutable => {}
+                    Owner::Address(owner) if owner == transaction.sender => {}
+                    _ => return Err(PackageError::NotOwned(*reference.object_id())),
+                },
+                InputArgument::Receiving(reference) => {
+                    self.input(reference)?;
+                }
+                InputArgument::Pure { .. } | InputArgument::Shared { .. } => {}
+            }
+        }
+        for reference in &gas.objects {
+            if self.input(reference)?.owner != Owner::Address(gas.owner) {
+                return Err(PackageError::NotOwned(*reference.object_id()));
+            }
+        }
+        Ok(())
+    }
+
+    /// The input of the package matching `reference`.
+    fn input(
+        &self,
+        reference: &ObjectReference,
+    ) -> Result<&ObjectReferenceWithOwner, PackageError> {
+        let object_id = reference.object_id();
+        let input = self
+            .inputs
+            .iter()
+            .find(|input| input.reference.object_id() == object_id)
+            .ok_or(PackageError::MissingInput(*object_id))?;
+        if input.reference != *reference {
+            return Err(PackageError::InputMismatch(*object_id));
+        }
+        Ok(input)
+    }
+
+    /// Attach the `signatures` carried back from the offline machine to the transaction.
+    pub fn into_signed_transaction(self, signatures: Vec<UserSignature>) -> SignedTransaction {
+        SignedTransaction {
+            transaction: self.transaction,
+            signatures,
+        }
+    }
+
+    /// The Base45 encoding of the package, to carry to the offline machine.
+    pub fn to_qr_string(&self) -> String {
+        to_qr_string(PACKAGE_TAG, self)
+    }
+
+    pub fn from_qr_string(s: &str) -> Result<Self, QrDecodeError> {
+        from_qr_string(PACKAGE_TAG, s)
+    }
+}
+
+/// A signer holding a key offline.
+pub trait OfflineSigner {
+    type Error;
+
+    /// The address of the signer.
+    fn address(&self) -> Address;
+
+    /// Sign the signing digest of a transaction, see [`Transaction::signing_digest`].
+    fn sign_digest(&self, digest: &[u8; 32]) -> Result<UserSignature, Self::Error>;
+}
+
+/// Validate `package` for `chain` and sign its transaction with `signer`, returning the signature
+/// to carry back to the online machine.
+pub fn sign_package<S: OfflineSigner>(
+    package: &UnsignedTransactionPackage,
+    chain: ChainIdentifier,
+    signer: &S,
+) -> Result<UserSignature, OfflineSigningError<S::Error>> {
+    package.validate(chain, signer.address())?;
+    signer
+        .sign_digest(&package.transaction.signing_digest())
+        .map_err(OfflineSigningError::Signer)
+}
+
+/// The Base45 encoding of a signature, to carry back to the online machine.
+pub fn signature_to_qr_string(signature: &UserSignature) -> String {
+    to_qr_string(SIGNATURE_TAG, signature)
+}
+
+pub fn signature_from_qr_string(s: &str) -> Result<UserSignature, QrDecodeError> {
+    from_qr_string(SIGNATURE_TAG, s)
+}
+
+#[cfg(feature = "cbor")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "cbor")))]
+mod frames {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    /// The largest number of bytes a frame adds to its chunk of the payload, in CBOR: the array
+    /// header, the sequence number and total as `u16`s, the `u32` checksum and the header of the
+    /// chunk's byte string.
+    const FRAME_OVERHEAD: usize = 1 + 3 + 3 + 5 + 3;
+
+    /// A chunk of a payload, encoded in CBOR as an array of its fields.
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    pub(super) struct QrFrame(
+        pub(super) u16,
+        pub(super) u16,
+        pub(super) u32,
+        #[serde(with = "::serde_with::As::<::serde_with::Bytes>")] pub(super) Vec<u8>,
+    );
+
+    /// The checksum of a payload: the first 4 bytes of its Blake2b-256 hash.
+    fn checksum(payload: &[u8]) -> u32 {
+        let digest = crate::hash::Hasher::digest(payload);
+        u32::from_be_bytes(digest.inner()[..4].try_into().unwrap())
+    }
+
+    /// Split `payload` into frames of at most `max_frame_len` Base45 characters.
+    ///
+    /// # Panics
+    ///
+    /// If `max_frame_len` is too small to hold a byte of the payload, or the payload needs more
+    /// than `u16::MAX` frames.
+    fn to_qr_frames(payload: &[u8], max_frame_len: usize) -> Vec<String> {
+        let chunk_len = (max_frame_len / 3 * 2).saturating_sub(FRAME_OVERHEAD);
+        assert!(
+            chunk_len > 0,
+            "frames of {max_frame_len} characters are too small"
+        );
+        let checksum = checksum(payload);
+        let chunks: Vec<_> = payload.chunks(chunk_len).collect();
+        let total = u16::try_from(chunks.len()).expect("too many frames");
+        chunks
+            .into_iter()
+            .enumerate()
+            .map(|(sequence, chunk)| {
+                let frame = QrFrame(sequence as u16, total, checksum, chunk.to_vec());
+                let bytes =
+                    crate::encoding::to_cbor(&frame).expect("cbor serialization cannot fail");
+                to_base45(&bytes)
+            })
+            .collect()
+    }
+
+    impl UnsignedTransactionPackage {
+        /// The package as a sequence of Base45 frames of at most `max_frame_len` characters, to
+        /// carry to the offline machine as animated or successive QR codes.
+        ///
+        /// A QR code holds up to 4296 alphanumeric characters, but smaller frames are easier to
+        /// scan, e.g. a few hundred characters.
+        ///
+        /// # Panics
+        ///
+        /// If `max_frame_len` is too small to hold a byte of the package.
+        pub fn to_qr_frames(&self, max_frame_len: usize) -> Vec<String> {
+            to_qr_frames(&to_payload(PACKAGE_TAG, self), max_frame_len)
+        }
+    }
+
+    /// A signature as a sequence of Base45 frames of at most `max_frame_len` characters, see
+    /// [`UnsignedTransactionPackage::to_qr_frames`].
+    pub fn signature_to_qr_frames(signature: &UserSignature, max_frame_len: usize) -> Vec<String> {
+        to_qr_frames(&to_payload(SIGNATURE_TAG, signature), max_frame_len)
+    }
+
+    /// Reassembles a payload from its frames, scanned in any order.
+    #[derive(Debug, Default)]
+    pub struct QrFrameAssembler {
+        /// The total and checksum of the frames seen so far.
+        header: Option<(u16, u32)>,
+        chunks: BTreeMap<u16, Vec<u8>>,
+    }
+
+    impl QrFrameAssembler {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Add a scanned frame, returning whether all frames of the payload have been added.
+        ///
+        /// Frames added again, e.g. as an animated QR code loops, are ignored.
+        pub fn push(&mut self, frame: &str) -> Result<bool, QrFrameError> {
+            let bytes = from_base45(frame).map_err(QrDecodeError::Base45)?;
+            let QrFrame(sequence, total, checksum, chunk) =
+                crate::encoding::from_cbor(&bytes).map_err(QrFrameError::Cbor)?;
+            if sequence >= total {
+                return Err(QrFrameError::InvalidSequence { sequence, total });
+            }
+            if *self.header.get_or_insert((total, checksum)) != (total, checksum) {
+                return Err(QrFrameError::FrameMismatch);
+            }
+            self.chunks.entry(sequence).or_insert(chunk);
+            Ok(self.is_complete())
+        }
+
+        /// The number of distinct frames added, and the total number of frames once known.
+        pub fn progress(&self) -> (usize, Option<usize>) {
+            let total = self.header.map(|(total, _)| usize::from(total));
+            (self.chunks.len(), total)
+        }
+
+        pub fn is_complete(&self) -> bool {
+            let (received, total) = self.progress();
+            total == Some(received)
+        }
+
+        fn into_payload(self) -> Result<Vec<u8>, QrFrameError> {
+            let (received, total) = self.progress();
+            let Some((_, checksum)) = self.header.filter(|_| self.is_complete()) else {
+                return Err(QrFrameError::Incomplete { received, total });
+            };
+            let payload: Vec<u8> = self.chunks.into_values().flatten().collect();
+            if self::checksum(&payload) != checksum {
+                return Err(QrFrameError::ChecksumMismatch);
+            }
+            Ok(payload)
+        }
+
+        /// The package carried by the frames, once all of them are added.
+        pub fn into_package(self) -> Result<UnsignedTransactionPackage, QrFrameError> {
+            Ok(from_payload(PACKAGE_TAG, &self.into_payload()?)?)
+        }
+
+        /// The signature carried by the frames, once all of them are added.
+        pub fn into_signature(self) -> Result<UserSignature, QrFrameError> {
+            Ok(from_payload(SIGNATURE_TAG, &self.into_payload()?)?)
+        }
+    }
+
+    #[derive(Debug)]
+    pub enum QrFrameError {
+        Decode(QrDecodeError),
+        Cbor(ciborium::de::Error<std::io::Error>),
+        /// The sequence number of a frame isn't below the total number of frames.
+        InvalidSequence {
+            sequence: u16,
+            total: u16,
+        },
+        /// A frame belongs to another payload than the frames added before it.
+        FrameMismatch,
+        /// Not all the frames of the payload have been added.
+        Incomplete {
+            received: usize,
+            total: Option<usize>,
+        },
+        /// The reassembled payload doesn't match the checksum of its frames.
+        ChecksumMismatch,
+    }
+
+    impl std::fmt::Display for QrFrameError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                Self::Decode(e) => write!(f, "{e}"),
+                Self::Cbor(e) => write!(f, "invalid frame: {e}"),
+                Self::InvalidSequence { sequence, total } => {
+                    write!(f, "invalid frame {sequence} of {total}")
+                }
+                Self::FrameMismatch => write!(f, "frame belongs to another payload"),
+                Self::Incomplete {
+                    received,
+                    total: Some(total),
+                } => write!(f, "received {received} of {total} frames"),
+                Self::Incomplete { total: None, .. } => write!(f, "no frames received"),
+                Self::ChecksumMismatch => write!(f, "payload doesn't match its checksum"),
+            }
+        }
+    }
+
+    impl std::error::Error for QrFrameError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match self {
+                Self::Decode(e) => Some(e),
+                Self::Cbor(e) => Some(e),
+                _ => None,
+            }
+        }
+    }
+
+    impl From<QrDecodeError> for QrFrameError {
+        fn from(e: QrDecodeError) -> Self {
+            Self::Decode(e)
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+pub use frames::signature_to_qr_frames;
+#[cfg(feature = "cbor")]
+pub use frames::QrFrameAssembler;
+#[cfg(feature = "cbor")]
+pub use frames::QrFrameError;
+
+#[cfg(feature = "secp256k1")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256k1")))]
+mod secp256k1 {
+    use super::*;
+    use crate::types::Secp256k1PrivateKey;
+    use crate::types::Secp256k1PublicKey;
+    use crate::types::Secp256k1Signature;
+    use crate::types::SimpleSignature;
+
+    /// An [`OfflineSigner`] holding a secp256k1 private key.
+    pub struct Secp256k1Signer {
+        key: k256::ecdsa::SigningKey,
+        public_key: Secp256k1PublicKey,
+    }
+
+    impl Secp256k1Signer {
+        /// `None` if `private_key` isn't a valid secp256k1 scalar.
+        pub fn new(private_key: &Secp256k1PrivateKey) -> Option<Self> {
+            let key = k256::ecdsa::SigningKey::from_slice(private_key.inner()).ok()?;
+            let public_key = Secp256k1PublicKey::from_bytes(
+                key.verifying_key().to_encoded_point(true).as_bytes(),
+            )
+            .ok()?;
+            Some(Self { key, public_key })
+        }
+
+        /// A signer with a new random key.
+        #[cfg(feature = "rand")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+        pub fn generate<R>(mut rng: R) -> Self
+        where
+            R: rand_core::RngCore + rand_core::CryptoRng,
+        {
+            let key = k256::ecdsa::SigningKey::random(&mut rng);
+            Self::new(&Secp256k1PrivateKey::new(key.to_bytes().into()))
+                .expect("random keys are valid")
+        }
+
+        pub fn private_key(&self) -> Secp256k1PrivateKey {
+            Secp256k1PrivateKey::new(self.key.to_bytes().into())
+        }
+
+        pub fn public_key(&self) -> Secp256k1PublicKey {
+            self.public_key
+        }
+
+        /// Sign the SHA-256 hash of `digest`, as Sui verifies secp256k1 user signatures.
+        pub fn sign_digest_raw(&self, digest: &[u8; 32]) -> Secp256k1Signature {
+            self.sign_deterministic(digest)
+        }
+
+        /// Sign the SHA-256 hash of `message` with the nonce derived from the key and the hash
+        /// as specified by RFC 6979, so that signing the same message twice yields the same
+        /// signature, with the low `s` Sui requires.
+        pub fn sign_deterministic(&self, message: &[u8]) -> Secp256k1Signature {
+            use k256::ecdsa::signature::Signer;
+
+            let signature: k256::ecdsa::Signature = self.key.sign(message);
+            Secp256k1Signature::new(signature.to_bytes().into())
+        }
+    }
+
+    impl OfflineSigner for Secp256k1Signer {
+        type Error = std::convert::Infallible;
+
+        fn address(&self) -> Address {
+            self.public_key.to_address()
+        }
+
+        fn sign_digest(&self, digest: &[u8; 32]) -> Result<UserSignature, Self::Error> {
+            Ok(UserSignature::Simple(SimpleSignature::Secp256k1 {
+                signature: self.sign_digest_raw(digest),
+                public_key: self.public_key,
+            }))
+        }
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+pub use secp256k1::Secp256k1Signer;
+
+#[cfg(feature = "secp256r1")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256r1")))]
+mod secp256r1 {
+    use super::*;
+    use crate::types::Secp256r1PrivateKey;
+    use crate::types::Secp256r1PublicKey;
+    use crate::types::Secp256r1Signature;
+    use crate::types::SimpleSignature;
+
+    /// An [`OfflineSigner`] holding a secp256r1 private key.
+    pub struct Secp256r1Signer {
+        key: p256::ecdsa::SigningKey,
+        public_key: Secp256r1PublicKey,
+    }
+
+    impl Secp256r1Signer {
+        /// `None` if `private_key` isn't a valid secp256r1 scalar.
+        pub fn new(private_key: &Secp256r1PrivateKey) -> Option<Self> {
+            let key = p256::ecdsa::SigningKey::from_slice(private_key.inner()).ok()?;
+            let public_key = Secp256r1PublicKey::from_bytes(
+                key.verifying_key().to_encoded_point(true).as_bytes(),
+            )
+            .ok()?;
+            Some(Self { key, public_key })
+        }
+
+        /// A signer with a new random key.
+        #[cfg(feature = "rand")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+        pub fn generate<R>(mut rng: R) -> Self
+        where
+            R: rand_core::RngCore + rand_core::CryptoRng,
+        {
+            let key = p256::ecdsa::SigningKey::random(&mut rng);
+            Self::new(&Secp256r1PrivateKey::new(key.to_bytes().into()))
+                .expect("random keys are valid")
+        }
+
+        pub fn private_key(&self) -> Secp256r1PrivateKey {
+            Secp256r1PrivateKey::new(self.key.to_bytes().into())
+        }
+
+        pub fn public_key(&self) -> Secp256r1PublicKey {
+            self.public_key
+        }
+
+        /// Sign the SHA-256 hash of `message` with the nonce derived from the key and the hash
+        /// as specified by RFC 6979, so that signing the same message twice yields the same
+        /// signature.
+        ///
+        /// Unlike secp256k1 signatures, those of p256 aren't normalized, so `s` is replaced by
+        /// its negation when it is high, as Sui only accepts signatures with a low `s`.
+        pub fn sign_deterministic(&self, message: &[u8]) -> Secp256r1Signature {
+            use p256::ecdsa::signature::Signer;
+
+            let signature: p256::ecdsa::Signature = self.key.sign(message);
+            let signature = signature.normalize_s().unwrap_or(signature);
+            Secp256r1Signature::new(signature.to_bytes().into())
+        }
+    }
+
+    impl OfflineSigner for Secp256r1Signer {
+        type Error = std::convert::Infallible;
+
+        fn address(&self) -> Address {
+            self.public_key.to_address()
+        }
+
+        fn sign_digest(&self, digest: &[u8; 32]) -> Result<UserSignature, Self::Error> {
+            Ok(UserSignature::Simple(SimpleSignature::Secp256r1 {
+                signature: self.sign_deterministic(digest),
+                public_key: self.public_key,
+            }))
+        }
+    }
+}
+
+#[cfg(feature = "secp256r1")]
+pub use secp256r1::Secp256r1Signer;
+
+/// The payload of `value`: `tag` followed by the BCS of the value.
+fn to_payload<T: serde::Serialize>(tag: u8, value: &T) -> Vec<u8> {
+    let mut bytes = vec![tag];
+    bcs::serialize_into(&mut bytes, value).expect("bcs serialization cannot fail");
+    bytes
+}
+
+fn from_payload<T: serde::de::DeserializeOwned>(tag: u8, bytes: &[u8]) -> Result<T, QrDecodeError> {
+    match bytes.split_first() {
+        Some((actual, value)) if *actual == tag => {
+            bcs::from_bytes(value).map_err(QrDecodeError::Bcs)
+        }
+        Some((actual, _)) => Err(QrDecodeError::UnexpectedTag {
+            expected: tag,
+            actual: *actual,
+        }),
+        None => Err(QrDecodeError::Empty),
+    }
+}
+
+fn to_qr_string<T: serde::Serialize>(tag: u8, value: &T) -> String {
+    to_base45(&to_payload(tag, value))
+}
+
+fn from_qr_string<T: serde::de::DeserializeOwned>(tag: u8, s: &str) -> Result<T, QrDecodeError> {
+    from_payload(tag, &from_base45(s).map_err(QrDecodeError::Base45)?)
+}
+
+/// A package that can't be signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackageError {
+    /// Only programmable transactions are signed by users.
+    NotUserTransaction,
+    ChainMismatch {
+        expected: ChainIdentifier,
+        actual: ChainIdentifier,
+    },
+    /// The signer is neither the sender nor the gas owner of the transaction.
+    UnexpectedSigner(Address),
+    /// The transaction expired before the epoch of the package.
+    Expired(EpochId),
+    GasPriceTooLow {
+        price: u64,
+        reference_gas_price: u64,
+    },
+    /// An object referenced by the transaction isn't among the inputs of the package.
+    MissingInput(ObjectId),
+    /// An object referenced by the transaction has another version or digest in the package.
+    InputMismatch(ObjectId),
+    /// An owned input isn't owned by the sender, or a gas coin by the gas owner.
+    NotOwned(ObjectId),
+}
+
+impl std::fmt::Display for PackageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotUserTransaction => write!(f, "not a programmable transaction"),
+            Self::ChainMismatch { expected, actual } => write!(
+                f,
+                "package is for chain {}, expected {}",
+                actual.short_id(),
+                expected.short_id()
+            ),
+            Self::UnexpectedSigner(signer) => write!(
+                f,
+                "{signer} is neither the sender nor the gas owner of the transaction"
+            ),
+            Self::Expired(epoch) => write!(f, "transaction expired in epoch {epoch}"),
+            Self::GasPriceTooLow {
+                price,
+                reference_gas_price,
+            } => write!(
+                f,
+                "gas price {price} is below the reference gas price {reference_gas_price}"
+            ),
+            Self::MissingInput(object_id) => write!(f, "input {object_id} is missing"),
+            Self::InputMismatch(object_id) => {
+                write!(f, "input {object_id} doesn't match the transaction")
+            }
+            Self::NotOwned(object_id) => write!(f, "input {object_id} isn't owned by its user"),
+        }
+    }
+}
+
+impl std::error::Error for PackageError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OfflineSigningError<E> {
+    Package(PackageError),
+    Signer(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for OfflineSigningError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Package(e) => write!(f, "invalid package: {e}"),
+            Self::Signer(e) => write!(f, "signing failed: {e}"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for OfflineSigningError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Package(e) => Some(e),
+            Self::Signer(_) => None,
+        }
+    }
+}
+
+impl<E> From<PackageError> for OfflineSigningError<E> {
+    fn from(e: PackageError) -> Self {
+        Self::Package(e)
+    }
+}
+
+#[derive(Debug)]
+pub enum QrDecodeError {
+    Base45(Base45Error),
+    Empty,
+    /// The encoding is of another kind of value.
+    UnexpectedTag {
+        expected: u8,
+        actual: u8,
+    },
+    Bcs(bcs::Error),
+}
+
+impl std::fmt::Display for QrDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Base45(e) => write!(f, "{e}"),
+            Self::Empty => write!(f, "empty payload"),
+            Self::UnexpectedTag { expected, actual } => {
+                write!(f, "expected an encoding tagged {expected}, found {actual}")
+            }
+            Self::Bcs(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for QrDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Base45(e) => Some(e),
+            Self::Bcs(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::encoding::BASE45_ALPHABET;
+    use crate::types::Argument;
+    use crate::types::Command;
+    use crate::types::GasPayment;
+    use crate::types::ObjectDigest;
+    use crate::types::ProgrammableTransaction;
+    use crate::types::TransferObjects;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn reference(byte: u8) -> ObjectReference {
+        ObjectReference::new(
+            ObjectId::new([byte; 32]),
+            Version::new(7),
+            ObjectDigest::new([byte; 32]),
+        )
+    }
+
+    fn package(sender: Address) -> UnsignedTransactionPackage {
+        let transaction = Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    InputArgument::ImmutableOrOwned(reference(1)),
+                    InputArgument::Pure {
+                        value: Address::ZERO.into(),
+                    },
+                ],
+                commands: vec![Command::TransferObjects(TransferObjects {
+                    objects: vec![Argument::Input(0)],
+                    address: Argument::Input(1),
+                })],
+            }),
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![reference(2)],
+                owner: sender,
+                price: 1_000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::Epoch(EpochId::new(10)),
+        };
+        let inputs = [1, 2]
+            .map(|byte| ObjectReferenceWithOwner {
+                reference: reference(byte),
+                owner: Owner::Address(sender),
+            })
+            .to_vec();
+        let context = SigningContext {
+            chain: ChainIdentifier::MAINNET,
+            epoch: EpochId::new(10),
+            reference_gas_price: 750,
+        };
+        UnsignedTransactionPackage::new(transaction, inputs, context)
+    }
+
+    #[test]
+    fn invalid_packages() {
+        let sender = Address::new([3; 32]);
+        let valid = package(sender);
+        let chain = ChainIdentifier::MAINNET;
+        valid.validate(chain, sender).unwrap();
+
+        assert_eq!(
+            valid.validate(ChainIdentifier::TESTNET, sender),
+            Err(PackageError::ChainMismatch {
+                expected: ChainIdentifier::TESTNET,
+                actual: chain,
+            })
+        );
+        assert_eq!(
+            valid.validate(chain, Address::ZERO),
+            Err(PackageError::UnexpectedSigner(Address::ZERO))
+        );
+
+        let mut expired = valid.clone();
+        expired.context.epoch = EpochId::new(11);
+        assert_eq!(
+            expired.validate(chain, sender),
+            Err(PackageError::Expired(EpochId::new(10)))
+        );
+
+        let mut cheap = valid.clone();
+        cheap.transaction.gas_payment.price = 500;
+        assert_eq!(
+            cheap.validate(chain, sender),
+            Err(PackageError::GasPriceTooLow {
+                price: 500,
+                reference_gas_price: 750,
+            })
+        );
+
+        let mut missing = valid.clone();
+        missing.inputs.remove(1);
+        assert_eq!(
+            missing.validate(chain, sender),
+            Err(PackageError::MissingInput(*reference(2).object_id()))
+        );
+
+        let mut stale = valid.clone();
+        stale.inputs[0].reference = ObjectReference::new(
+            *reference(1).object_id(),
+            Version::new(6),
+            ObjectDigest::new([1; 32]),
+        );
+        assert_eq!(
+            stale.validate(chain, sender),
+            Err(PackageError::InputMismatch(*reference(1).object_id()))
+        );
+
+        let mut foreign = valid;
+        foreign.inputs[0].owner = Owner::Address(Address::ZERO);
+        assert_eq!(
+            foreign.validate(chain, sender),
+            Err(PackageError::NotOwned(*reference(1).object_id()))
+        );
+        foreign.inputs[0].owner = Owner::Immutable;
+        foreign.validate(chain, sender).unwrap();
+    }
+
+    #[test]
+    fn qr_roundtrip() {
+        let package = package(Address::new([3; 32]));
+        let encoded = package.to_qr_string();
+        assert!(encoded.chars().all(|c| BASE45_ALPHABET.contains(c)));
+        assert_eq!(
+            UnsignedTransactionPackage::from_qr_string(&encoded).unwrap(),
+            package
+        );
+        assert!(matches!(
+            signature_from_qr_string(&encoded),
+            Err(QrDecodeError::UnexpectedTag {
+                expected: SIGNATURE_TAG,
+                actual: PACKAGE_TAG,
+            })
+        ));
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn qr_frames() {
+        use crate::types::Ed25519PublicKey;
+        use crate::types::Ed25519Signature;
+        use crate::types::SimpleSignature;
+        use frames::QrFrame;
+
+        let package = package(Address::new([3; 32]));
+        let frames = package.to_qr_frames(120);
+        assert!(frames.len() > 2);
+        for frame in &frames {
+            assert!(frame.len() <= 120);
+            assert!(frame.chars().all(|c| BASE45_ALPHABET.contains(c)));
+        }
+
+        // Frames may be scanned in any order, and more than once
+        let mut assembler = QrFrameAssembler::new();
+        assert_eq!(assembler.progress(), (0, None));
+        for frame in frames.iter().rev().skip(1) {
+            assert!(!assembler.push(frame).unwrap());
+            assert!(!assembler.push(frame).unwrap());
+        }
+        assert_eq!(assembler.progress(), (frames.len() - 1, Some(frames.len())));
+        let signature = UserSignature::Simple(SimpleSignature::Ed25519 {
+            signature: Ed25519Signature::new([1; 64]),
+            public_key: Ed25519PublicKey::new([2; 32]),
+        });
+        let signature_frames = signature_to_qr_frames(&signature, 120);
+        assert!(matches!(
+            assembler.push(&signature_frames[0]),
+            Err(QrFrameError::FrameMismatch)
+        ));
+        assert!(assembler.push(frames.last().unwrap()).unwrap());
+        assert_eq!(assembler.into_package().unwrap(), package);
+
+        let mut incomplete = QrFrameAssembler::new();
+        incomplete.push(&frames[1]).unwrap();
+        assert!(matches!(
+            incomplete.into_package(),
+            Err(QrFrameError::Incomplete { received: 1, total: Some(total) }) if total == frames.len()
+        ));
+
+        // A frame whose chunk was altered, e.g. misread, fails the checksum
+        let decode = |frame: &str| -> QrFrame {
+            crate::encoding::from_cbor(&from_base45(frame).unwrap()).unwrap()
+        };
+        let mut tampered = decode(&frames[0]);
+        tampered.3[0] ^= 1;
+        let tampered = to_base45(&crate::encoding::to_cbor(&tampered).unwrap());
+        let mut assembler = QrFrameAssembler::new();
+        for frame in std::iter::once(&tampered).chain(&frames[1..]) {
+            assembler.push(frame).unwrap();
+        }
+        assert!(matches!(
+            assembler.into_signature(),
+            Err(QrFrameError::ChecksumMismatch)
+        ));
+
+        // Payloads are checked for their kind once reassembled
+        let mut assembler = QrFrameAssembler::new();
+        for frame in &frames {
+            assembler.push(frame).unwrap();
+        }
+        assert!(matches!(
+            assembler.into_signature(),
+            Err(QrFrameError::Decode(QrDecodeError::UnexpectedTag { .. }))
+        ));
+
+        let mut assembler = QrFrameAssembler::new();
+        for frame in &signature_frames {
+            assembler.push(frame).unwrap();
+        }
+        assert_eq!(assembler.into_signature().unwrap(), signature);
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn sign_offline() {
+        use crate::types::Secp256k1PrivateKey;
+        use crate::types::SimpleSignature;
+        use k256::ecdsa::signature::Verifier;
+
+        let signer = Secp256k1Signer::new(&Secp256k1PrivateKey::new([5; 32])).unwrap();
+        assert!(Secp256k1Signer::new(&Secp256k1PrivateKey::new([0; 32])).is_none());
+
+        // Online: resolve the transaction and encode it
+        let package = package(signer.address());
+        let request = package.to_qr_string();
+
+        // Offline: decode, check and sign the package, and encode only the signature
+        let decoded = UnsignedTransactionPackage::from_qr_string(&request).unwrap();
+        let signature = sign_package(&decoded, ChainIdentifier::MAINNET, &signer).unwrap();
+        let response = signature_to_qr_string(&signature);
+        assert_eq!(
+            sign_package(&decoded, ChainIdentifier::TESTNET, &signer),
+            Err(OfflineSigningError::Package(PackageError::ChainMismatch {
+                expected: ChainIdentifier::TESTNET,
+                actual: ChainIdentifier::MAINNET,
+            }))
+        );
+
+        // Online: attach the signature
+        let signature = signature_from_qr_string(&response).unwrap();
+        let signed = package.into_signed_transaction(vec![signature]);
+
+        let UserSignature::Simple(SimpleSignature::Secp256k1 {
+            signature,
+            public_key,
+        }) = &signed.signatures[0]
+        else {
+            panic!("expected a secp256k1 signature");
+        };
+        assert_eq!(*public_key, signer.public_key());
+        let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key.as_bytes()).unwrap();
+        let signature = k256::ecdsa::Signature::from_slice(signature.as_bytes()).unwrap();
+        key.verify(&signed.transaction.signing_digest(), &signature)
+            .unwrap();
+    }
+
+    // The secp256k1 vector widely used by bitcoin libraries, for the key 1
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn secp256k1_rfc6979() {
+        use crate::types::Secp256k1PrivateKey;
+
+        let mut key = [0; 32];
+        key[31] = 1;
+        let signer = Secp256k1Signer::new(&Secp256k1PrivateKey::new(key)).unwrap();
+        let signature = signer.sign_deterministic(b"Satoshi Nakamoto");
+        assert_eq!(
+            hex::encode(signature.as_bytes()),
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8\
+             2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        );
+        assert_eq!(signer.sign_deterministic(b"Satoshi Nakamoto"), signature);
+    }
+
+    // RFC 6979 A.2.5, P-256 with SHA-256 and the message "sample", with `s` negated to be low
+    #[cfg(feature = "secp256r1")]
+    #[test]
+    fn secp256r1_rfc6979() {
+        use crate::types::Secp256r1PrivateKey;
+        use p256::ecdsa::signature::Verifier;
+
+        let key = hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")
+            .unwrap();
+        let signer =
+            Secp256r1Signer::new(&Secp256r1PrivateKey::new(key.try_into().unwrap())).unwrap();
+        let signature = signer.sign_deterministic(b"sample");
+        assert_eq!(
+            hex::encode(signature.as_bytes()),
+            "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+             0834e36ad29a83bf2bc9385e491d6099c8fdf9d1ed67aa7ea5f51f93782857a9"
+        );
+        assert_eq!(signer.sign_deterministic(b"sample"), signature);
+
+        let key =
+            p256::ecdsa::VerifyingKey::from_sec1_bytes(signer.public_key().as_bytes()).unwrap();
+        let signature = p256::ecdsa::Signature::from_slice(signature.as_bytes()).unwrap();
+        key.verify(b"sample", &signature).unwrap();
+    }
+}
@@ -0,0 +1,246 @@
+//! Cursor based pagination, as used by the node's queries for owned objects, coins, events and
+//! dynamic fields.
+//!
+//! Each query returns a [`Page`] of results, along with the cursor to pass to the next query to
+//! continue after it. A [`PageStream`] turns such a query into a [`Stream`] of the results of all
+//! of its pages, fetching each page as the previous one is exhausted:
+//!
+//! ```ignore
+//! let coins = PageStream::new(|cursor| client.get_coins(owner, cursor, None));
+//! let balance = coins
+//!     .try_fold(0, |balance, coin| async move { Ok(balance + coin.balance) })
+//!     .await?;
+//! ```
+
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use futures_core::Stream;
+
+/// A page of the results of a paginated query.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct Page<T, C> {
+    pub data: Vec<T>,
+    /// The cursor to continue the query after this page from.
+    pub next_cursor: Option<C>,
+    pub has_next_page: bool,
+}
+
+impl<T, C> Page<T, C> {
+    /// The cursor of the next page, if there is one.
+    ///
+    /// Nodes return the cursor of the last result even on the last page, so the cursor alone
+    /// doesn't tell whether the query is over.
+    pub fn next_page(&self) -> Option<&C> {
+        self.next_cursor.as_ref().filter(|_| self.has_next_page)
+    }
+}
+
+/// The results of all the pages of a paginated query, as a [`Stream`].
+///
+/// The query is a function fetching the page starting after a cursor, or the first page when
+/// given `None`. It is called again once the results of the previous page have all been yielded,
+/// and stops being called after the last page, or after it fails.
+pub struct PageStream<T, C, F, Fut> {
+    fetch: F,
+    cursor: Option<C>,
+    results: VecDeque<T>,
+    pending: Option<Pin<Box<Fut>>>,
+    done: bool,
+}
+
+impl<T, C, E, F, Fut> PageStream<T, C, F, Fut>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<Page<T, C>, E>>,
+{
+    /// Stream the results of `fetch`, from its first page.
+    pub fn new(fetch: F) -> Self {
+        Self {
+            fetch,
+            cursor: None,
+            results: VecDeque::new(),
+            pending: None,
+            done: false,
+        }
+    }
+
+    /// Resume the query after `cursor`, e.g. the cursor of the last page seen by an earlier
+    /// stream.
+    pub fn with_cursor(mut self, cursor: C) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// The cursor the next page will be fetched after, which together with the results still
+    /// buffered from the current page allows resuming the stream later.
+    pub fn cursor(&self) -> Option<&C> {
+        self.cursor.as_ref()
+    }
+}
+
+impl<T, C, F, Fut> Unpin for PageStream<T, C, F, Fut> {}
+
+impl<T, C, E, F, Fut> Stream for PageStream<T, C, F, Fut>
+where
+    F: FnMut(Option<C>) -> Fut,
+    Fut: Future<Output = Result<Page<T, C>, E>>,
+{
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            if let Some(result) = this.results.pop_front() {
+                return Poll::Ready(Some(Ok(result)));
+            }
+            if this.done {
+                return Poll::Ready(None);
+            }
+
+            let pending = match &mut this.pending {
+                Some(pending) => pending,
+                pending @ None => pending.insert(Box::pin((this.fetch)(this.cursor.take()))),
+            };
+            let page = match pending.as_mut().poll(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(page) => page,
+            };
+            this.pending = None;
+
+            match page {
+                Ok(page) => {
+                    this.done = page.next_page().is_none();
+                    this.cursor = page.next_cursor;
+                    this.results = page.data.into();
+                }
+                Err(e) => {
+                    this.done = true;
+                    return Poll::Ready(Some(Err(e)));
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.results.len();
+        if self.done {
+            (buffered, Some(buffered))
+        } else {
+            (buffered, None)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::future::ready;
+    use std::future::Ready;
+    use std::task::Waker;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    /// Poll `stream` to completion, the pages of the tests being ready immediately.
+    fn collect<S: Stream + Unpin>(mut stream: S) -> Vec<S::Item> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut items = vec![];
+        loop {
+            match Pin::new(&mut stream).poll_next(&mut cx) {
+                Poll::Ready(Some(item)) => items.push(item),
+                Poll::Ready(None) => return items,
+                Poll::Pending => panic!("page not ready"),
+            }
+        }
+    }
+
+    /// Pages of `size` of the numbers below `len`, the cursor being the last number of a page.
+    fn numbers(
+        len: u32,
+        size: u32,
+        requested: &mut Vec<Option<u32>>,
+    ) -> impl FnMut(Option<u32>) -> Ready<Result<Page<u32, u32>, ()>> + '_ {
+        move |cursor| {
+            requested.push(cursor);
+            let start = cursor.map_or(0, |cursor| cursor + 1);
+            let data: Vec<u32> = (start..len.min(start + size)).collect();
+            ready(Ok(Page {
+                next_cursor: data.last().copied(),
+                has_next_page: start + size < len,
+                data,
+            }))
+        }
+    }
+
+    #[test]
+    fn stream_all_pages() {
+        let mut requested = vec![];
+        let numbers: Vec<_> = collect(PageStream::new(numbers(7, 3, &mut requested)))
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(numbers, [0, 1, 2, 3, 4, 5, 6]);
+        // The cursor of the last page isn't followed.
+        assert_eq!(requested, [None, Some(2), Some(5)]);
+    }
+
+    #[test]
+    fn resume_from_cursor() {
+        let mut requested = vec![];
+        let stream = PageStream::new(numbers(7, 3, &mut requested)).with_cursor(3);
+        assert_eq!(stream.cursor(), Some(&3));
+        let numbers: Vec<_> = collect(stream).into_iter().map(Result::unwrap).collect();
+        assert_eq!(numbers, [4, 5, 6]);
+        assert_eq!(requested, [Some(3)]);
+    }
+
+    #[test]
+    fn skip_empty_pages() {
+        let mut pages = vec![
+            Page {
+                data: vec![],
+                next_cursor: Some(0),
+                has_next_page: true,
+            },
+            Page {
+                data: vec!['a'],
+                next_cursor: Some(1),
+                has_next_page: false,
+            },
+        ]
+        .into_iter();
+        let stream = PageStream::new(|_| ready(Ok::<_, ()>(pages.next().unwrap())));
+        assert_eq!(collect(stream), [Ok('a')]);
+    }
+
+    #[test]
+    fn stop_after_error() {
+        let mut calls = 0;
+        let stream = PageStream::new(|_| {
+            calls += 1;
+            ready(Err::<Page<u32, u32>, _>("unavailable"))
+        });
+        assert_eq!(collect(stream), [Err("unavailable")]);
+        assert_eq!(calls, 1);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn page_json() {
+        let page: Page<u64, String> =
+            serde_json::from_str(r#"{"data": [1, 2], "nextCursor": "2", "hasNextPage": false}"#)
+                .unwrap();
+        assert_eq!(page.data, [1, 2]);
+        assert_eq!(page.next_cursor.as_deref(), Some("2"));
+        assert_eq!(page.next_page(), None);
+    }
+}
@@ -0,0 +1,247 @@
+//! Envelopes for transporting Sui data over a parachain bridge.
+//!
+//! An [`Envelope`] is SCALE encoded so it can be embedded in the extrinsics and XCM messages of a
+//! substrate based chain, while its payload is kept in the BCS encoding used by Sui, so that it can
+//! be hashed and verified against Sui signatures as is.
+
+use crate::types::ChainIdentifier;
+use crate::types::SignedCheckpointSummary;
+use crate::types::SignedTransaction;
+use parity_scale_codec::Compact;
+use parity_scale_codec::Decode;
+use parity_scale_codec::DecodeAll;
+use parity_scale_codec::Encode;
+
+/// A versioned envelope carrying a Sui payload across chains.
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub enum Envelope {
+    #[codec(index = 1)]
+    V1(EnvelopeV1),
+}
+
+impl Envelope {
+    /// Create an envelope of the latest version.
+    pub fn new(chain_id: ChainIdentifier, payload: EnvelopePayload) -> Self {
+        Self::V1(EnvelopeV1 { chain_id, payload })
+    }
+
+    pub fn chain_id(&self) -> &ChainIdentifier {
+        match self {
+            Self::V1(envelope) => &envelope.chain_id,
+        }
+    }
+
+    pub fn payload(&self) -> &EnvelopePayload {
+        match self {
+            Self::V1(envelope) => &envelope.payload,
+        }
+    }
+
+    pub fn into_payload(self) -> EnvelopePayload {
+        match self {
+            Self::V1(envelope) => envelope.payload,
+        }
+    }
+
+    /// The SCALE encoding of the envelope.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.encode()
+    }
+
+    /// Decode a SCALE encoded envelope, rejecting any trailing bytes.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, EnvelopeDecodeError> {
+        Self::decode_all(&mut bytes).map_err(EnvelopeDecodeError)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Encode, Decode)]
+pub struct EnvelopeV1 {
+    /// The chain the payload originates from.
+    pub chain_id: ChainIdentifier,
+    pub payload: EnvelopePayload,
+}
+
+/// The payload of an [`Envelope`].
+///
+/// Each variant is SCALE encoded as its index followed by the BCS encoding of its value, as a
+/// length prefixed byte vector.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EnvelopePayload {
+    Transaction(SignedTransaction),
+    CheckpointSummary(SignedCheckpointSummary),
+}
+
+impl EnvelopePayload {
+    const TRANSACTION: u8 = 0;
+    const CHECKPOINT_SUMMARY: u8 = 1;
+
+    fn index(&self) -> u8 {
+        match self {
+            Self::Transaction(_) => Self::TRANSACTION,
+            Self::CheckpointSummary(_) => Self::CHECKPOINT_SUMMARY,
+        }
+    }
+
+    /// The BCS encoding of the wrapped value.
+    pub fn to_bcs(&self) -> Vec<u8> {
+        match self {
+            Self::Transaction(transaction) => bcs::to_bytes(transaction),
+            Self::CheckpointSummary(summary) => bcs::to_bytes(summary),
+        }
+        .expect("bcs serialization of envelope payloads cannot fail")
+    }
+
+    /// The length of the BCS encoding of the wrapped value, counted without allocating it.
+    fn bcs_len(&self) -> usize {
+        match self {
+            Self::Transaction(transaction) => bcs::serialized_size(transaction),
+            Self::CheckpointSummary(summary) => bcs::serialized_size(summary),
+        }
+        .expect("bcs serialization of envelope payloads cannot fail")
+    }
+}
+
+impl Encode for EnvelopePayload {
+    fn size_hint(&self) -> usize {
+        // The index, then the BCS bytes prefixed by their compact length, as for a `Vec<u8>`.
+        let bcs_len = self.bcs_len();
+        self.index().size_hint() + Compact(bcs_len as u32).size_hint() + bcs_len
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        self.index().encode_to(dest);
+        self.to_bcs().encode_to(dest);
+    }
+}
+
+impl Decode for EnvelopePayload {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> Result<Self, parity_scale_codec::Error> {
+        let index = u8::decode(input)?;
+        let bcs_bytes = Vec::<u8>::decode(input)?;
+        let payload = match index {
+            Self::TRANSACTION => bcs::from_bytes(&bcs_bytes).map(Self::Transaction),
+            Self::CHECKPOINT_SUMMARY => bcs::from_bytes(&bcs_bytes).map(Self::CheckpointSummary),
+            _ => return Err("invalid envelope payload index".into()),
+        };
+        payload.map_err(|_| "invalid BCS encoding of envelope payload".into())
+    }
+}
+
+impl Encode for ChainIdentifier {
+    fn size_hint(&self) -> usize {
+        Self::LENGTH
+    }
+
+    fn encode_to<T: parity_scale_codec::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(self.inner())
+    }
+}
+
+impl Decode for ChainIdentifier {
+    fn decode<I: parity_scale_codec::Input>(
+        input: &mut I,
+    ) -> Result<Self, parity_scale_codec::Error> {
+        <[u8; Self::LENGTH]>::decode(input).map(Self::new)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EnvelopeDecodeError(parity_scale_codec::Error);
+
+impl std::fmt::Display for EnvelopeDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "invalid envelope: {}", self.0)
+    }
+}
+
+impl std::error::Error for EnvelopeDecodeError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Transaction;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    // A programmable transaction from mainnet, see `transaction_fixtures`.
+    const PTB: &str = "AAADAQFEBbUNeR/TNGdU6Bcaqra8LtJsLEbv3QM8FLMK5QesMyx96QEAAAAAAQAIVsakAAAAAAABALyyokbZ/8ynfWQer6UyP1DpeCnPU1NC7AyFNJSaTztnQF40BQAAAAAgffPXh5XuG6TWjHk6qC5w9k2a+41oTWfm0sC1FOYRqsEBAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAN7pB2Nsb2JfdjIMY2FuY2VsX29yZGVyAgcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgNzdWkDU1VJAAddSzAlBmRcN/8TO5jEtQpa4UhBZZc41tcz1Z0NIXqTvwRjb2luBENPSU4AAwEAAAEBAAECAPgh00g/x3Jeuvqlo9Ejc9SZAb384UhPIZ2qcGajDfd9ASXQjpFOD6mfycbzwD1wc+IOkCXQ8rHQo/Vi5SDOGMR/Jl40BQAAAAAgV7P1E0IMKon5uI82R/0arWLt+dc1ng/4VwKDqpTCxHT4IdNIP8dyXrr6paPRI3PUmQG9/OFITyGdqnBmow33fe4CAAAAAAAAAMqaOwAAAAAA";
+
+    #[test]
+    fn envelope_fixture() {
+        let transaction: Transaction = bcs::from_bytes(&Base64::decode_vec(PTB).unwrap()).unwrap();
+        let envelope = Envelope::new(
+            ChainIdentifier::MAINNET,
+            EnvelopePayload::Transaction(SignedTransaction {
+                transaction,
+                signatures: vec![],
+            }),
+        );
+
+        let bytes = envelope.to_bytes();
+        let bcs_bytes = envelope.payload().to_bcs();
+        // The PTB and an empty signature list
+        assert_eq!(bcs_bytes.len(), 439);
+
+        // version || chain id || payload index || compact length || bcs
+        let mut expected = vec![1];
+        expected.extend(ChainIdentifier::MAINNET.inner());
+        expected.push(0);
+        expected.extend([0xdd, 0x06]);
+        expected.extend(&bcs_bytes);
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            envelope.payload().size_hint(),
+            bytes.len() - 1 - ChainIdentifier::LENGTH
+        );
+
+        assert_eq!(Envelope::from_bytes(&bytes).unwrap(), envelope);
+    }
+
+    #[test]
+    fn invalid_envelopes() {
+        let envelope = Envelope::new(
+            ChainIdentifier::TESTNET,
+            EnvelopePayload::Transaction(SignedTransaction {
+                transaction: bcs::from_bytes(&Base64::decode_vec(PTB).unwrap()).unwrap(),
+                signatures: vec![],
+            }),
+        );
+        let bytes = envelope.to_bytes();
+
+        // Unknown version
+        let mut unknown_version = bytes.clone();
+        unknown_version[0] = 2;
+        assert!(Envelope::from_bytes(&unknown_version).is_err());
+
+        // Unknown payload
+        let mut unknown_payload = bytes.clone();
+        unknown_payload[33] = 2;
+        assert!(Envelope::from_bytes(&unknown_payload).is_err());
+
+        // The payload is not a checkpoint summary
+        let mut wrong_payload = bytes.clone();
+        wrong_payload[33] = 1;
+        assert!(Envelope::from_bytes(&wrong_payload).is_err());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(Envelope::from_bytes(&trailing).is_err());
+
+        assert!(Envelope::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[proptest]
+    fn roundtrip_checkpoint_summary(chain_id: ChainIdentifier, summary: SignedCheckpointSummary) {
+        let envelope = Envelope::new(chain_id, EnvelopePayload::CheckpointSummary(summary));
+        assert_eq!(
+            Envelope::from_bytes(&envelope.to_bytes()).unwrap(),
+            envelope
+        );
+    }
+}
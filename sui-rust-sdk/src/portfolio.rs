@@ -0,0 +1,352 @@
+//! The net changes to the objects and balances owned by an address over a span of history.
+//!
+//! An [`OwnedObjectTracker`] is fed the transactions executed between two points in time, in
+//! order, e.g. the [`CheckpointData`] of every checkpoint in between. Objects touched several
+//! times only count once, by their state before the first transaction touching them and after
+//! the last one, so that a coin received then spent, or merged into another, leaves no trace in
+//! the resulting [`OwnedObjectDiff`].
+//!
+//! Balances are read from the coin objects included in checkpoint data. Transactions known only
+//! by their effects, fed with [`OwnedObjectTracker::add_effects`], reveal which objects changed
+//! hands but not the balances of coins.
+
+use std::collections::BTreeMap;
+
+use crate::types::framework::Coin;
+use crate::types::Address;
+use crate::types::CheckpointData;
+use crate::types::CheckpointTransaction;
+use crate::types::Object;
+use crate::types::ObjectId;
+use crate::types::ObjectIn;
+use crate::types::ObjectOut;
+use crate::types::Owner;
+use crate::types::TransactionEffectsV2;
+use crate::types::TypeTag;
+use crate::types::Version;
+
+/// An object owned by the tracked address.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OwnedObject {
+    pub object_id: ObjectId,
+    pub version: Version,
+    /// The type and balance of the object, if it is a coin whose contents are known.
+    pub coin: Option<(TypeTag, u64)>,
+}
+
+impl OwnedObject {
+    /// `object`, if it is owned by `owner`.
+    fn from_object(owner: &Address, object: &Object) -> Option<Self> {
+        is_owned_by(object.owner(), owner).then(|| Self {
+            object_id: object.object_id(),
+            version: object.version(),
+            coin: Coin::try_from_object(object)
+                .map(|coin| (coin.coin_type().clone(), coin.balance())),
+        })
+    }
+}
+
+fn is_owned_by(owner: &Owner, address: &Address) -> bool {
+    match owner {
+        Owner::Address(owner) | Owner::ConsensusAddressOwner { owner, .. } => owner == address,
+        Owner::Object(_) | Owner::Shared { .. } | Owner::Immutable => false,
+    }
+}
+
+/// The state of an object before and after the tracked transactions, `None` when it isn't owned
+/// by the tracked address.
+#[derive(Clone, Debug)]
+struct Tracked {
+    before: Option<OwnedObject>,
+    after: Option<OwnedObject>,
+}
+
+/// Accumulates the changes of the objects owned by an address across transactions.
+#[derive(Clone, Debug)]
+pub struct OwnedObjectTracker {
+    owner: Address,
+    objects: BTreeMap<ObjectId, Tracked>,
+}
+
+impl OwnedObjectTracker {
+    pub fn new(owner: Address) -> Self {
+        Self {
+            owner,
+            objects: BTreeMap::new(),
+        }
+    }
+
+    pub fn owner(&self) -> &Address {
+        &self.owner
+    }
+
+    /// Record the transactions of `checkpoint`.
+    pub fn add_checkpoint(&mut self, checkpoint: &CheckpointData) {
+        for transaction in &checkpoint.transactions {
+            self.add_transaction(transaction);
+        }
+    }
+
+    /// Record `transaction`, from the objects it read and wrote.
+    pub fn add_transaction(&mut self, transaction: &CheckpointTransaction) {
+        self.add_objects(&transaction.input_objects, &transaction.output_objects);
+    }
+
+    /// Record a transaction which read the objects `inputs` and wrote the objects `outputs`. Input
+    /// objects missing from the outputs were deleted or wrapped.
+    pub fn add_objects(&mut self, inputs: &[Object], outputs: &[Object]) {
+        let changes = inputs
+            .iter()
+            .map(|input| (input.object_id(), Some(input), None))
+            .chain(
+                outputs
+                    .iter()
+                    .map(|output| (output.object_id(), None, Some(output))),
+            );
+        let mut changes_by_id: BTreeMap<ObjectId, (Option<&Object>, Option<&Object>)> =
+            BTreeMap::new();
+        for (object_id, input, output) in changes {
+            let change = changes_by_id.entry(object_id).or_default();
+            change.0 = change.0.or(input);
+            change.1 = change.1.or(output);
+        }
+
+        for (object_id, (input, output)) in changes_by_id {
+            let before = input.and_then(|input| OwnedObject::from_object(&self.owner, input));
+            let after = output.and_then(|output| OwnedObject::from_object(&self.owner, output));
+            if before.is_some() || after.is_some() {
+                self.record(object_id, before, after);
+            }
+        }
+    }
+
+    /// Record a transaction known only by its `effects`, which don't include the balances of
+    /// coins.
+    pub fn add_effects(&mut self, effects: &TransactionEffectsV2) {
+        for changed in &effects.changed_objects {
+            let object_id = changed.object_id;
+            let before = match &changed.change.input_state {
+                ObjectIn::Exist { version, owner, .. } if is_owned_by(owner, &self.owner) => {
+                    Some(OwnedObject {
+                        object_id,
+                        version: *version,
+                        coin: None,
+                    })
+                }
+                ObjectIn::Exist { .. } | ObjectIn::NotExist => None,
+            };
+            let after = match &changed.change.output_state {
+                ObjectOut::ObjectWrite { owner, .. } if is_owned_by(owner, &self.owner) => {
+                    Some(OwnedObject {
+                        object_id,
+                        version: effects.lamport_version,
+                        coin: None,
+                    })
+                }
+                ObjectOut::ObjectWrite { .. }
+                | ObjectOut::PackageWrite { .. }
+                | ObjectOut::NotExist => None,
+            };
+            if before.is_some() || after.is_some() {
+                self.record(object_id, before, after);
+            }
+        }
+    }
+
+    fn record(
+        &mut self,
+        object_id: ObjectId,
+        before: Option<OwnedObject>,
+        after: Option<OwnedObject>,
+    ) {
+        use std::collections::btree_map::Entry;
+
+        match self.objects.entry(object_id) {
+            Entry::Occupied(mut tracked) => tracked.get_mut().after = after,
+            Entry::Vacant(entry) => {
+                entry.insert(Tracked { before, after });
+            }
+        }
+    }
+
+    /// The net changes recorded so far.
+    pub fn diff(&self) -> OwnedObjectDiff {
+        let mut diff = OwnedObjectDiff::default();
+        for tracked in self.objects.values() {
+            for (object, sign) in [(&tracked.before, -1), (&tracked.after, 1)] {
+                if let Some((coin_type, balance)) = object.as_ref().and_then(|o| o.coin.as_ref()) {
+                    *diff.balances.entry(coin_type.clone()).or_default() += sign * *balance as i128;
+                }
+            }
+
+            match (&tracked.before, &tracked.after) {
+                (None, Some(after)) => diff.added.push(after.clone()),
+                (Some(before), None) => diff.removed.push(before.clone()),
+                (Some(before), Some(after)) if before.version != after.version => {
+                    diff.modified.push((before.clone(), after.clone()))
+                }
+                _ => {}
+            }
+        }
+        diff.balances.retain(|_, change| *change != 0);
+        diff
+    }
+}
+
+/// The net changes to the objects owned by an address between two points in time.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OwnedObjectDiff {
+    /// Objects owned at the end but not at the start: created, received or unwrapped.
+    pub added: Vec<OwnedObject>,
+    /// Objects owned at the start but not at the end: sent away, deleted or wrapped.
+    pub removed: Vec<OwnedObject>,
+    /// Objects owned throughout, but modified, at the start and at the end.
+    pub modified: Vec<(OwnedObject, OwnedObject)>,
+    /// The net change of the balance of each coin type, for coin types whose balance changed.
+    pub balances: BTreeMap<TypeTag, i128>,
+}
+
+impl OwnedObjectDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.balances.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod test {
+    use super::*;
+    use crate::types::ChangedObject;
+    use crate::types::EffectsObjectChange;
+    use crate::types::EpochId;
+    use crate::types::ExecutionStatus;
+    use crate::types::GasCostSummary;
+    use crate::types::IdOperation;
+    use crate::types::ObjectDigest;
+    use crate::types::StructTag;
+    use crate::types::TransactionDigest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const ALICE: Address = Address::TWO;
+    const BOB: Address = Address::THREE;
+
+    /// A SUI coin with `balance` owned by `owner`.
+    fn coin(id: u8, version: u64, owner: Address, balance: u64) -> Object {
+        let mut contents = ObjectId::new([id; 32]).into_inner().to_vec();
+        contents.extend(balance.to_le_bytes());
+        Object::new_move_object(
+            StructTag::gas_coin(),
+            Version::new(version),
+            contents,
+            Owner::Address(owner),
+        )
+    }
+
+    fn sui() -> TypeTag {
+        StructTag::gas_coin().is_coin().unwrap().clone()
+    }
+
+    #[test]
+    fn net_changes() {
+        let mut tracker = OwnedObjectTracker::new(ALICE);
+
+        // Alice pays 30 to Bob out of coin 1, paying 5 of gas.
+        tracker.add_objects(
+            &[coin(1, 1, ALICE, 100)],
+            &[coin(1, 2, ALICE, 65), coin(2, 2, BOB, 30)],
+        );
+        // Bob sends a coin of 50 to Alice, who merges it into coin 1, paying 1 of gas.
+        tracker.add_objects(&[coin(3, 1, BOB, 50)], &[coin(3, 3, ALICE, 50)]);
+        tracker.add_objects(
+            &[coin(1, 2, ALICE, 65), coin(3, 3, ALICE, 50)],
+            &[coin(1, 4, ALICE, 114)],
+        );
+        // Bob receives a coin, which doesn't concern Alice.
+        tracker.add_objects(&[], &[coin(4, 5, BOB, 10)]);
+
+        let diff = tracker.diff();
+        // Coin 3, received then merged, nets out.
+        assert!(diff.added.is_empty());
+        assert!(diff.removed.is_empty());
+        assert_eq!(diff.modified.len(), 1);
+        let (before, after) = &diff.modified[0];
+        assert_eq!(before.coin, Some((sui(), 100)));
+        assert_eq!(after.version, Version::new(4));
+        assert_eq!(diff.balances, BTreeMap::from([(sui(), 14)]));
+    }
+
+    #[test]
+    fn sent_and_received() {
+        let mut tracker = OwnedObjectTracker::new(ALICE);
+        tracker.add_objects(&[coin(1, 1, ALICE, 100)], &[coin(1, 2, BOB, 100)]);
+        tracker.add_objects(&[], &[coin(2, 3, ALICE, 7)]);
+
+        let diff = tracker.diff();
+        assert_eq!(diff.removed[0].object_id, ObjectId::new([1; 32]));
+        assert_eq!(diff.added[0].object_id, ObjectId::new([2; 32]));
+        assert_eq!(diff.balances, BTreeMap::from([(sui(), -93)]));
+
+        // Sending the coin back undoes the change.
+        tracker.add_objects(&[coin(1, 2, BOB, 100)], &[coin(1, 4, ALICE, 100)]);
+        tracker.add_objects(&[coin(2, 3, ALICE, 7)], &[coin(2, 5, BOB, 7)]);
+        let diff = tracker.diff();
+        assert!(diff.added.is_empty() && diff.removed.is_empty());
+        assert_eq!(diff.balances, BTreeMap::new());
+    }
+
+    #[test]
+    fn effects_changes() {
+        let owned = |owner| EffectsObjectChange {
+            input_state: ObjectIn::Exist {
+                version: Version::new(1),
+                digest: ObjectDigest::ZERO,
+                owner: Owner::Address(owner),
+            },
+            output_state: ObjectOut::ObjectWrite {
+                digest: ObjectDigest::ZERO,
+                owner: Owner::Address(BOB),
+            },
+            id_operation: IdOperation::None,
+        };
+        let effects = TransactionEffectsV2 {
+            status: ExecutionStatus::Success,
+            epoch: EpochId::new(0),
+            gas_used: GasCostSummary::default(),
+            transaction_digest: TransactionDigest::ZERO,
+            gas_object_index: None,
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: Version::new(2),
+            changed_objects: vec![
+                ChangedObject {
+                    object_id: ObjectId::new([1; 32]),
+                    change: owned(ALICE),
+                },
+                ChangedObject {
+                    object_id: ObjectId::new([2; 32]),
+                    change: owned(BOB),
+                },
+            ],
+            unchanged_shared_objects: vec![],
+            auxiliary_data_digest: None,
+        };
+
+        let mut tracker = OwnedObjectTracker::new(ALICE);
+        tracker.add_effects(&effects);
+        let diff = tracker.diff();
+        assert_eq!(
+            diff.removed,
+            [OwnedObject {
+                object_id: ObjectId::new([1; 32]),
+                version: Version::new(1),
+                coin: None,
+            }]
+        );
+        assert!(diff.added.is_empty() && diff.balances.is_empty());
+    }
+}
@@ -0,0 +1,306 @@
+// This file is @generated by prost-build.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ObjectReference {
+    #[prost(bytes = "vec", tag = "1")]
+    pub object_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+    #[prost(bytes = "vec", tag = "3")]
+    pub digest: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transaction {
+    #[prost(message, optional, tag = "1")]
+    pub kind: ::core::option::Option<TransactionKind>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub sender: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub gas_payment: ::core::option::Option<GasPayment>,
+    /// The epoch after which the transaction expires, if any.
+    #[prost(uint64, optional, tag = "4")]
+    pub expiration_epoch: ::core::option::Option<u64>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GasPayment {
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<ObjectReference>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub owner: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    pub price: u64,
+    #[prost(uint64, tag = "4")]
+    pub budget: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionKind {
+    #[prost(oneof = "transaction_kind::Kind", tags = "1, 2")]
+    pub kind: ::core::option::Option<transaction_kind::Kind>,
+}
+/// Nested message and enum types in `TransactionKind`.
+pub mod transaction_kind {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Kind {
+        #[prost(message, tag = "1")]
+        ProgrammableTransaction(super::ProgrammableTransaction),
+        /// Transactions run by the system, e.g. at the start of each consensus commit or the end of
+        /// each epoch, as the BCS of the whole `TransactionKind`.
+        #[prost(bytes, tag = "2")]
+        System(::prost::alloc::vec::Vec<u8>),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProgrammableTransaction {
+    #[prost(message, repeated, tag = "1")]
+    pub inputs: ::prost::alloc::vec::Vec<InputArgument>,
+    #[prost(message, repeated, tag = "2")]
+    pub commands: ::prost::alloc::vec::Vec<Command>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct InputArgument {
+    #[prost(oneof = "input_argument::Kind", tags = "1, 2, 3, 4")]
+    pub kind: ::core::option::Option<input_argument::Kind>,
+}
+/// Nested message and enum types in `InputArgument`.
+pub mod input_argument {
+    #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
+    pub enum Kind {
+        #[prost(bytes, tag = "1")]
+        Pure(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag = "2")]
+        ImmutableOrOwned(super::ObjectReference),
+        #[prost(message, tag = "3")]
+        Shared(super::SharedObjectInput),
+        #[prost(message, tag = "4")]
+        Receiving(super::ObjectReference),
+    }
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SharedObjectInput {
+    #[prost(bytes = "vec", tag = "1")]
+    pub object_id: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub initial_shared_version: u64,
+    #[prost(bool, tag = "3")]
+    pub mutable: bool,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Argument {
+    #[prost(oneof = "argument::Kind", tags = "1, 2, 3, 4")]
+    pub kind: ::core::option::Option<argument::Kind>,
+}
+/// Nested message and enum types in `Argument`.
+pub mod argument {
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Oneof)]
+    pub enum Kind {
+        #[prost(message, tag = "1")]
+        GasCoin(super::Empty),
+        #[prost(uint32, tag = "2")]
+        Input(u32),
+        #[prost(uint32, tag = "3")]
+        Result(u32),
+        #[prost(message, tag = "4")]
+        NestedResult(super::NestedResult),
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Empty {}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct NestedResult {
+    #[prost(uint32, tag = "1")]
+    pub result: u32,
+    #[prost(uint32, tag = "2")]
+    pub subresult: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Command {
+    #[prost(oneof = "command::Command", tags = "1, 2, 3, 4, 5, 6, 7")]
+    pub command: ::core::option::Option<command::Command>,
+}
+/// Nested message and enum types in `Command`.
+pub mod command {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Command {
+        #[prost(message, tag = "1")]
+        MoveCall(super::MoveCall),
+        #[prost(message, tag = "2")]
+        TransferObjects(super::TransferObjects),
+        #[prost(message, tag = "3")]
+        SplitCoins(super::SplitCoins),
+        #[prost(message, tag = "4")]
+        MergeCoins(super::MergeCoins),
+        #[prost(message, tag = "5")]
+        Publish(super::Publish),
+        #[prost(message, tag = "6")]
+        MakeMoveVector(super::MakeMoveVector),
+        #[prost(message, tag = "7")]
+        Upgrade(super::Upgrade),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MoveCall {
+    #[prost(bytes = "vec", tag = "1")]
+    pub package: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag = "2")]
+    pub module: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub function: ::prost::alloc::string::String,
+    /// Type tags in their canonical string form, e.g. `0x2::coin::Coin<0x2::sui::SUI>`.
+    #[prost(string, repeated, tag = "4")]
+    pub type_arguments: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "5")]
+    pub arguments: ::prost::alloc::vec::Vec<Argument>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferObjects {
+    #[prost(message, repeated, tag = "1")]
+    pub objects: ::prost::alloc::vec::Vec<Argument>,
+    #[prost(message, optional, tag = "2")]
+    pub address: ::core::option::Option<Argument>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplitCoins {
+    #[prost(message, optional, tag = "1")]
+    pub coin: ::core::option::Option<Argument>,
+    #[prost(message, repeated, tag = "2")]
+    pub amounts: ::prost::alloc::vec::Vec<Argument>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MergeCoins {
+    #[prost(message, optional, tag = "1")]
+    pub coin: ::core::option::Option<Argument>,
+    #[prost(message, repeated, tag = "2")]
+    pub coins_to_merge: ::prost::alloc::vec::Vec<Argument>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Publish {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub modules: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub dependencies: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MakeMoveVector {
+    #[prost(string, optional, tag = "1")]
+    pub element_type: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "2")]
+    pub elements: ::prost::alloc::vec::Vec<Argument>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct Upgrade {
+    #[prost(bytes = "vec", repeated, tag = "1")]
+    pub modules: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub dependencies: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub package: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "4")]
+    pub ticket: ::core::option::Option<Argument>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedTransaction {
+    #[prost(message, optional, tag = "1")]
+    pub transaction: ::core::option::Option<Transaction>,
+    /// Signatures in their serialized form, a flag byte followed by the signature.
+    #[prost(bytes = "vec", repeated, tag = "2")]
+    pub signatures: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+/// The BCS of `TransactionEffects`, whose execution errors mirror the internals of the Move VM.
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TransactionEffects {
+    #[prost(bytes = "vec", tag = "1")]
+    pub bcs: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct GasCostSummary {
+    #[prost(uint64, tag = "1")]
+    pub computation_cost: u64,
+    #[prost(uint64, tag = "2")]
+    pub storage_cost: u64,
+    #[prost(uint64, tag = "3")]
+    pub storage_rebate: u64,
+    #[prost(uint64, tag = "4")]
+    pub non_refundable_storage_fee: u64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CheckpointCommitment {
+    #[prost(oneof = "checkpoint_commitment::Commitment", tags = "1")]
+    pub commitment: ::core::option::Option<checkpoint_commitment::Commitment>,
+}
+/// Nested message and enum types in `CheckpointCommitment`.
+pub mod checkpoint_commitment {
+    #[derive(Clone, PartialEq, Eq, Hash, ::prost::Oneof)]
+    pub enum Commitment {
+        #[prost(bytes, tag = "1")]
+        EcmhLiveObjectSet(::prost::alloc::vec::Vec<u8>),
+    }
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ValidatorCommitteeMember {
+    #[prost(bytes = "vec", tag = "1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub stake: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EndOfEpochData {
+    #[prost(message, repeated, tag = "1")]
+    pub next_epoch_committee: ::prost::alloc::vec::Vec<ValidatorCommitteeMember>,
+    #[prost(uint64, tag = "2")]
+    pub next_epoch_protocol_version: u64,
+    #[prost(message, repeated, tag = "3")]
+    pub epoch_commitments: ::prost::alloc::vec::Vec<CheckpointCommitment>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointSummary {
+    #[prost(uint64, tag = "1")]
+    pub epoch: u64,
+    #[prost(uint64, tag = "2")]
+    pub sequence_number: u64,
+    #[prost(uint64, tag = "3")]
+    pub network_total_transactions: u64,
+    #[prost(bytes = "vec", tag = "4")]
+    pub content_digest: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", optional, tag = "5")]
+    pub previous_digest: ::core::option::Option<::prost::alloc::vec::Vec<u8>>,
+    #[prost(message, optional, tag = "6")]
+    pub epoch_rolling_gas_cost_summary: ::core::option::Option<GasCostSummary>,
+    #[prost(uint64, tag = "7")]
+    pub timestamp_ms: u64,
+    #[prost(message, repeated, tag = "8")]
+    pub checkpoint_commitments: ::prost::alloc::vec::Vec<CheckpointCommitment>,
+    #[prost(message, optional, tag = "9")]
+    pub end_of_epoch_data: ::core::option::Option<EndOfEpochData>,
+    #[prost(bytes = "vec", tag = "10")]
+    pub version_specific_data: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ValidatorAggregatedSignature {
+    #[prost(uint64, tag = "1")]
+    pub epoch: u64,
+    #[prost(bytes = "vec", tag = "2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The signers, as a serialized roaring bitmap of their indices in the committee.
+    #[prost(bytes = "vec", tag = "3")]
+    pub bitmap: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignedCheckpointSummary {
+    #[prost(message, optional, tag = "1")]
+    pub checkpoint: ::core::option::Option<CheckpointSummary>,
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<ValidatorAggregatedSignature>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct CheckpointTransactionInfo {
+    #[prost(bytes = "vec", tag = "1")]
+    pub transaction: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub effects: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", repeated, tag = "3")]
+    pub signatures: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CheckpointContents {
+    #[prost(message, repeated, tag = "1")]
+    pub transactions: ::prost::alloc::vec::Vec<CheckpointTransactionInfo>,
+}
@@ -0,0 +1,887 @@
+//! Protobuf messages for transactions, effects and checkpoints, with conversions to and from the
+//! SDK's types.
+//!
+//! The messages are defined in `proto/sui.types.proto` and generated with `prost`, so gRPC services
+//! can expose them directly. Conversions are lossless in both directions: converting a value to
+//! its message and back yields the same value.
+//!
+//! Parts whose shape follows the internals of the protocol rather than anything a client would
+//! inspect are carried as BCS: system transactions, e.g. consensus commit prologues and end of
+//! epoch transactions, and transaction effects, whose execution errors mirror those of the Move
+//! VM.
+
+mod generated {
+    include!("generated/sui.types.rs");
+}
+
+pub use generated::*;
+
+use std::sync::Arc;
+
+use crate::types;
+
+impl From<types::ObjectReference> for ObjectReference {
+    fn from(reference: types::ObjectReference) -> Self {
+        let (object_id, version, digest) = reference.into_parts();
+        Self {
+            object_id: object_id.into(),
+            version: version.into_inner(),
+            digest: digest.as_bytes().to_vec(),
+        }
+    }
+}
+
+impl TryFrom<ObjectReference> for types::ObjectReference {
+    type Error = ProtoConversionError;
+
+    fn try_from(reference: ObjectReference) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            types::ObjectId::new(array("object_id", &reference.object_id)?),
+            types::Version::new(reference.version),
+            types::ObjectDigest::new(array("digest", &reference.digest)?),
+        ))
+    }
+}
+
+impl TryFrom<types::Transaction> for Transaction {
+    type Error = ProtoConversionError;
+
+    fn try_from(transaction: types::Transaction) -> Result<Self, Self::Error> {
+        let expiration_epoch = match transaction.expiration {
+            types::TransactionExpiration::None => None,
+            types::TransactionExpiration::Epoch(epoch) => Some(epoch.into_inner()),
+            types::TransactionExpiration::ValidDuring { .. } => {
+                return Err(ProtoConversionError::invalid(
+                    "expiration_epoch",
+                    "valid during expirations aren't supported",
+                ))
+            }
+        };
+        Ok(Self {
+            kind: Some(transaction.kind.try_into()?),
+            sender: transaction.sender.into(),
+            gas_payment: Some(transaction.gas_payment.into()),
+            expiration_epoch,
+        })
+    }
+}
+
+impl TryFrom<Transaction> for types::Transaction {
+    type Error = ProtoConversionError;
+
+    fn try_from(transaction: Transaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            kind: required("kind", transaction.kind)?.try_into()?,
+            sender: types::Address::new(array("sender", &transaction.sender)?),
+            gas_payment: required("gas_payment", transaction.gas_payment)?.try_into()?,
+            expiration: match transaction.expiration_epoch {
+                None => types::TransactionExpiration::None,
+                Some(epoch) => types::TransactionExpiration::Epoch(types::EpochId::new(epoch)),
+            },
+        })
+    }
+}
+
+impl From<types::GasPayment> for GasPayment {
+    fn from(payment: types::GasPayment) -> Self {
+        Self {
+            objects: payment.objects.into_iter().map(Into::into).collect(),
+            owner: payment.owner.into(),
+            price: payment.price,
+            budget: payment.budget,
+        }
+    }
+}
+
+impl TryFrom<GasPayment> for types::GasPayment {
+    type Error = ProtoConversionError;
+
+    fn try_from(payment: GasPayment) -> Result<Self, Self::Error> {
+        Ok(Self {
+            objects: try_collect(payment.objects)?,
+            owner: types::Address::new(array("owner", &payment.owner)?),
+            price: payment.price,
+            budget: payment.budget,
+        })
+    }
+}
+
+impl TryFrom<types::TransactionKind> for TransactionKind {
+    type Error = ProtoConversionError;
+
+    fn try_from(kind: types::TransactionKind) -> Result<Self, Self::Error> {
+        let kind = match kind {
+            types::TransactionKind::ProgrammableTransaction(ptb) => {
+                transaction_kind::Kind::ProgrammableTransaction(ptb.into())
+            }
+            system => transaction_kind::Kind::System(bcs::to_bytes(&system)?),
+        };
+        Ok(Self { kind: Some(kind) })
+    }
+}
+
+impl TryFrom<TransactionKind> for types::TransactionKind {
+    type Error = ProtoConversionError;
+
+    fn try_from(kind: TransactionKind) -> Result<Self, Self::Error> {
+        match required("kind", kind.kind)? {
+            transaction_kind::Kind::ProgrammableTransaction(ptb) => {
+                Ok(Self::ProgrammableTransaction(ptb.try_into()?))
+            }
+            transaction_kind::Kind::System(bcs) => match bcs::from_bytes(&bcs)? {
+                Self::ProgrammableTransaction(_) => Err(ProtoConversionError::invalid(
+                    "system",
+                    "a programmable transaction is not a system transaction",
+                )),
+                system => Ok(system),
+            },
+        }
+    }
+}
+
+impl From<types::ProgrammableTransaction> for ProgrammableTransaction {
+    fn from(ptb: types::ProgrammableTransaction) -> Self {
+        Self {
+            inputs: ptb.inputs.into_iter().map(Into::into).collect(),
+            commands: ptb.commands.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<ProgrammableTransaction> for types::ProgrammableTransaction {
+    type Error = ProtoConversionError;
+
+    fn try_from(ptb: ProgrammableTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            inputs: try_collect(ptb.inputs)?,
+            commands: try_collect(ptb.commands)?,
+        })
+    }
+}
+
+impl From<types::InputArgument> for InputArgument {
+    fn from(input: types::InputArgument) -> Self {
+        let kind = match input {
+            types::InputArgument::Pure { value } => input_argument::Kind::Pure(value),
+            types::InputArgument::ImmutableOrOwned(reference) => {
+                input_argument::Kind::ImmutableOrOwned(reference.into())
+            }
+            types::InputArgument::Shared {
+                object_id,
+                initial_shared_version,
+                mutable,
+            } => input_argument::Kind::Shared(SharedObjectInput {
+                object_id: object_id.into(),
+                initial_shared_version: initial_shared_version.into_inner(),
+                mutable,
+            }),
+            types::InputArgument::Receiving(reference) => {
+                input_argument::Kind::Receiving(reference.into())
+            }
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<InputArgument> for types::InputArgument {
+    type Error = ProtoConversionError;
+
+    fn try_from(input: InputArgument) -> Result<Self, Self::Error> {
+        Ok(match required("kind", input.kind)? {
+            input_argument::Kind::Pure(value) => Self::Pure { value },
+            input_argument::Kind::ImmutableOrOwned(reference) => {
+                Self::ImmutableOrOwned(reference.try_into()?)
+            }
+            input_argument::Kind::Shared(shared) => Self::Shared {
+                object_id: types::ObjectId::new(array("object_id", &shared.object_id)?),
+                initial_shared_version: types::Version::new(shared.initial_shared_version),
+                mutable: shared.mutable,
+            },
+            input_argument::Kind::Receiving(reference) => Self::Receiving(reference.try_into()?),
+        })
+    }
+}
+
+impl From<types::Argument> for Argument {
+    fn from(argument: types::Argument) -> Self {
+        let kind = match argument {
+            types::Argument::GasCoin => argument::Kind::GasCoin(Empty {}),
+            types::Argument::Input(input) => argument::Kind::Input(input.into()),
+            types::Argument::Result(result) => argument::Kind::Result(result.into()),
+            types::Argument::NestedResult(result, subresult) => {
+                argument::Kind::NestedResult(NestedResult {
+                    result: result.into(),
+                    subresult: subresult.into(),
+                })
+            }
+        };
+        Self { kind: Some(kind) }
+    }
+}
+
+impl TryFrom<Argument> for types::Argument {
+    type Error = ProtoConversionError;
+
+    fn try_from(argument: Argument) -> Result<Self, Self::Error> {
+        Ok(match required("kind", argument.kind)? {
+            argument::Kind::GasCoin(Empty {}) => Self::GasCoin,
+            argument::Kind::Input(input) => Self::Input(index("input", input)?),
+            argument::Kind::Result(result) => Self::Result(index("result", result)?),
+            argument::Kind::NestedResult(NestedResult { result, subresult }) => {
+                Self::NestedResult(index("result", result)?, index("subresult", subresult)?)
+            }
+        })
+    }
+}
+
+impl From<types::Command> for Command {
+    fn from(command: types::Command) -> Self {
+        let command = match command {
+            types::Command::MoveCall(call) => {
+                let call = Arc::unwrap_or_clone(call);
+                command::Command::MoveCall(MoveCall {
+                    package: call.package.into(),
+                    module: call.module.as_str().to_owned(),
+                    function: call.function.as_str().to_owned(),
+                    type_arguments: call
+                        .type_arguments
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                    arguments: call.arguments.into_iter().map(Into::into).collect(),
+                })
+            }
+            types::Command::TransferObjects(transfer) => {
+                command::Command::TransferObjects(TransferObjects {
+                    objects: transfer.objects.into_iter().map(Into::into).collect(),
+                    address: Some(transfer.address.into()),
+                })
+            }
+            types::Command::SplitCoins(split) => command::Command::SplitCoins(SplitCoins {
+                coin: Some(split.coin.into()),
+                amounts: split.amounts.into_iter().map(Into::into).collect(),
+            }),
+            types::Command::MergeCoins(merge) => command::Command::MergeCoins(MergeCoins {
+                coin: Some(merge.coin.into()),
+                coins_to_merge: merge.coins_to_merge.into_iter().map(Into::into).collect(),
+            }),
+            types::Command::Publish(publish) => {
+                let publish = Arc::unwrap_or_clone(publish);
+                command::Command::Publish(Publish {
+                    modules: publish.modules,
+                    dependencies: publish.dependencies.into_iter().map(Into::into).collect(),
+                })
+            }
+            types::Command::MakeMoveVector(make) => {
+                let make = Arc::unwrap_or_clone(make);
+                command::Command::MakeMoveVector(MakeMoveVector {
+                    element_type: make.type_.as_ref().map(ToString::to_string),
+                    elements: make.elements.into_iter().map(Into::into).collect(),
+                })
+            }
+            types::Command::Upgrade(upgrade) => {
+                let upgrade = Arc::unwrap_or_clone(upgrade);
+                command::Command::Upgrade(Upgrade {
+                    modules: upgrade.modules,
+                    dependencies: upgrade.dependencies.into_iter().map(Into::into).collect(),
+                    package: upgrade.package.into(),
+                    ticket: Some(upgrade.ticket.into()),
+                })
+            }
+        };
+        Self {
+            command: Some(command),
+        }
+    }
+}
+
+impl TryFrom<Command> for types::Command {
+    type Error = ProtoConversionError;
+
+    fn try_from(command: Command) -> Result<Self, Self::Error> {
+        Ok(match required("command", command.command)? {
+            command::Command::MoveCall(call) => Self::MoveCall(Arc::new(types::MoveCall {
+                package: types::ObjectId::new(array("package", &call.package)?),
+                module: parse("module", &call.module)?,
+                function: parse("function", &call.function)?,
+                type_arguments: call
+                    .type_arguments
+                    .iter()
+                    .map(|type_argument| parse("type_arguments", type_argument))
+                    .collect::<Result<_, _>>()?,
+                arguments: try_collect(call.arguments)?,
+            })),
+            command::Command::TransferObjects(transfer) => {
+                Self::TransferObjects(types::TransferObjects {
+                    objects: try_collect(transfer.objects)?,
+                    address: required("address", transfer.address)?.try_into()?,
+                })
+            }
+            command::Command::SplitCoins(split) => Self::SplitCoins(types::SplitCoins {
+                coin: required("coin", split.coin)?.try_into()?,
+                amounts: try_collect(split.amounts)?,
+            }),
+            command::Command::MergeCoins(merge) => Self::MergeCoins(types::MergeCoins {
+                coin: required("coin", merge.coin)?.try_into()?,
+                coins_to_merge: try_collect(merge.coins_to_merge)?,
+            }),
+            command::Command::Publish(publish) => Self::Publish(Arc::new(types::Publish {
+                modules: publish.modules,
+                dependencies: object_ids("dependencies", &publish.dependencies)?,
+            })),
+            command::Command::MakeMoveVector(make) => {
+                Self::MakeMoveVector(Arc::new(types::MakeMoveVector {
+                    type_: make
+                        .element_type
+                        .map(|element_type| parse("element_type", &element_type))
+                        .transpose()?,
+                    elements: try_collect(make.elements)?,
+                }))
+            }
+            command::Command::Upgrade(upgrade) => Self::Upgrade(Arc::new(types::Upgrade {
+                modules: upgrade.modules,
+                dependencies: object_ids("dependencies", &upgrade.dependencies)?,
+                package: types::ObjectId::new(array("package", &upgrade.package)?),
+                ticket: required("ticket", upgrade.ticket)?.try_into()?,
+            })),
+        })
+    }
+}
+
+impl TryFrom<types::SignedTransaction> for SignedTransaction {
+    type Error = ProtoConversionError;
+
+    fn try_from(transaction: types::SignedTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction: Some(transaction.transaction.try_into()?),
+            signatures: signatures_to_bytes(&transaction.signatures)?,
+        })
+    }
+}
+
+impl TryFrom<SignedTransaction> for types::SignedTransaction {
+    type Error = ProtoConversionError;
+
+    fn try_from(transaction: SignedTransaction) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction: required("transaction", transaction.transaction)?.try_into()?,
+            signatures: signatures_from_bytes(transaction.signatures)?,
+        })
+    }
+}
+
+impl TryFrom<types::TransactionEffects> for TransactionEffects {
+    type Error = ProtoConversionError;
+
+    fn try_from(effects: types::TransactionEffects) -> Result<Self, Self::Error> {
+        Ok(Self {
+            bcs: bcs::to_bytes(&effects)?,
+        })
+    }
+}
+
+impl TryFrom<TransactionEffects> for types::TransactionEffects {
+    type Error = ProtoConversionError;
+
+    fn try_from(effects: TransactionEffects) -> Result<Self, Self::Error> {
+        Ok(bcs::from_bytes(&effects.bcs)?)
+    }
+}
+
+impl From<types::GasCostSummary> for GasCostSummary {
+    fn from(summary: types::GasCostSummary) -> Self {
+        Self {
+            computation_cost: summary.computation_cost,
+            storage_cost: summary.storage_cost,
+            storage_rebate: summary.storage_rebate,
+            non_refundable_storage_fee: summary.non_refundable_storage_fee,
+        }
+    }
+}
+
+impl From<GasCostSummary> for types::GasCostSummary {
+    fn from(summary: GasCostSummary) -> Self {
+        Self::new(
+            summary.computation_cost,
+            summary.storage_cost,
+            summary.storage_rebate,
+            summary.non_refundable_storage_fee,
+        )
+    }
+}
+
+impl From<types::CheckpointCommitment> for CheckpointCommitment {
+    fn from(commitment: types::CheckpointCommitment) -> Self {
+        let commitment = match commitment {
+            types::CheckpointCommitment::EcmhLiveObjectSet { digest } => {
+                checkpoint_commitment::Commitment::EcmhLiveObjectSet(digest.as_bytes().to_vec())
+            }
+        };
+        Self {
+            commitment: Some(commitment),
+        }
+    }
+}
+
+impl TryFrom<CheckpointCommitment> for types::CheckpointCommitment {
+    type Error = ProtoConversionError;
+
+    fn try_from(commitment: CheckpointCommitment) -> Result<Self, Self::Error> {
+        Ok(match required("commitment", commitment.commitment)? {
+            checkpoint_commitment::Commitment::EcmhLiveObjectSet(digest) => {
+                Self::EcmhLiveObjectSet {
+                    digest: types::Digest::new(array("ecmh_live_object_set", &digest)?),
+                }
+            }
+        })
+    }
+}
+
+impl From<types::EndOfEpochData> for EndOfEpochData {
+    fn from(data: types::EndOfEpochData) -> Self {
+        Self {
+            next_epoch_committee: data
+                .next_epoch_committee
+                .into_iter()
+                .map(|member| ValidatorCommitteeMember {
+                    public_key: member.public_key.as_bytes().to_vec(),
+                    stake: member.stake,
+                })
+                .collect(),
+            next_epoch_protocol_version: data.next_epoch_protocol_version,
+            epoch_commitments: data.epoch_commitments.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<EndOfEpochData> for types::EndOfEpochData {
+    type Error = ProtoConversionError;
+
+    fn try_from(data: EndOfEpochData) -> Result<Self, Self::Error> {
+        Ok(Self {
+            next_epoch_committee: data
+                .next_epoch_committee
+                .into_iter()
+                .map(|member| {
+                    Ok(types::ValidatorCommitteeMember {
+                        public_key: types::Bls12381PublicKey::new(array(
+                            "public_key",
+                            &member.public_key,
+                        )?),
+                        stake: member.stake,
+                    })
+                })
+                .collect::<Result<_, ProtoConversionError>>()?,
+            next_epoch_protocol_version: data.next_epoch_protocol_version,
+            epoch_commitments: try_collect(data.epoch_commitments)?,
+        })
+    }
+}
+
+impl From<types::CheckpointSummary> for CheckpointSummary {
+    fn from(summary: types::CheckpointSummary) -> Self {
+        Self {
+            epoch: summary.epoch.into_inner(),
+            sequence_number: summary.sequence_number,
+            network_total_transactions: summary.network_total_transactions,
+            content_digest: summary.content_digest.as_bytes().to_vec(),
+            previous_digest: summary
+                .previous_digest
+                .map(|digest| digest.as_bytes().to_vec()),
+            epoch_rolling_gas_cost_summary: Some(summary.epoch_rolling_gas_cost_summary.into()),
+            timestamp_ms: summary.timestamp_ms,
+            checkpoint_commitments: summary
+                .checkpoint_commitments
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            end_of_epoch_data: summary.end_of_epoch_data.map(Into::into),
+            version_specific_data: summary.version_specific_data,
+        }
+    }
+}
+
+impl TryFrom<CheckpointSummary> for types::CheckpointSummary {
+    type Error = ProtoConversionError;
+
+    fn try_from(summary: CheckpointSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: types::EpochId::new(summary.epoch),
+            sequence_number: summary.sequence_number,
+            network_total_transactions: summary.network_total_transactions,
+            content_digest: types::CheckpointContentsDigest::new(array(
+                "content_digest",
+                &summary.content_digest,
+            )?),
+            previous_digest: summary
+                .previous_digest
+                .map(|digest| array("previous_digest", &digest).map(types::CheckpointDigest::new))
+                .transpose()?,
+            epoch_rolling_gas_cost_summary: required(
+                "epoch_rolling_gas_cost_summary",
+                summary.epoch_rolling_gas_cost_summary,
+            )?
+            .into(),
+            timestamp_ms: summary.timestamp_ms,
+            checkpoint_commitments: try_collect(summary.checkpoint_commitments)?,
+            end_of_epoch_data: summary
+                .end_of_epoch_data
+                .map(TryInto::try_into)
+                .transpose()?,
+            version_specific_data: summary.version_specific_data,
+        })
+    }
+}
+
+impl TryFrom<types::ValidatorAggregatedSignature> for ValidatorAggregatedSignature {
+    type Error = ProtoConversionError;
+
+    fn try_from(signature: types::ValidatorAggregatedSignature) -> Result<Self, Self::Error> {
+        let mut bitmap = vec![];
+        signature
+            .bitmap
+            .serialize_into(&mut bitmap)
+            .map_err(|e| ProtoConversionError::invalid("bitmap", e))?;
+        Ok(Self {
+            epoch: signature.epoch.into_inner(),
+            signature: signature.signature.as_bytes().to_vec(),
+            bitmap,
+        })
+    }
+}
+
+impl TryFrom<ValidatorAggregatedSignature> for types::ValidatorAggregatedSignature {
+    type Error = ProtoConversionError;
+
+    fn try_from(signature: ValidatorAggregatedSignature) -> Result<Self, Self::Error> {
+        Ok(Self {
+            epoch: types::EpochId::new(signature.epoch),
+            signature: types::Bls12381Signature::new(array("signature", &signature.signature)?),
+            bitmap: roaring::RoaringBitmap::deserialize_from(signature.bitmap.as_slice())
+                .map_err(|e| ProtoConversionError::invalid("bitmap", e))?,
+        })
+    }
+}
+
+impl TryFrom<types::SignedCheckpointSummary> for SignedCheckpointSummary {
+    type Error = ProtoConversionError;
+
+    fn try_from(summary: types::SignedCheckpointSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            checkpoint: Some(summary.checkpoint.into()),
+            signature: Some(summary.signature.try_into()?),
+        })
+    }
+}
+
+impl TryFrom<SignedCheckpointSummary> for types::SignedCheckpointSummary {
+    type Error = ProtoConversionError;
+
+    fn try_from(summary: SignedCheckpointSummary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            checkpoint: required("checkpoint", summary.checkpoint)?.try_into()?,
+            signature: required("signature", summary.signature)?.try_into()?,
+        })
+    }
+}
+
+impl TryFrom<types::CheckpointContents> for CheckpointContents {
+    type Error = ProtoConversionError;
+
+    fn try_from(contents: types::CheckpointContents) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transactions: contents
+                .into_transactions()
+                .into_iter()
+                .map(|info| {
+                    Ok(CheckpointTransactionInfo {
+                        transaction: info.transaction.as_bytes().to_vec(),
+                        effects: info.effects.as_bytes().to_vec(),
+                        signatures: signatures_to_bytes(&info.signatures)?,
+                    })
+                })
+                .collect::<Result<_, ProtoConversionError>>()?,
+        })
+    }
+}
+
+impl TryFrom<CheckpointContents> for types::CheckpointContents {
+    type Error = ProtoConversionError;
+
+    fn try_from(contents: CheckpointContents) -> Result<Self, Self::Error> {
+        Ok(Self::new(
+            contents
+                .transactions
+                .into_iter()
+                .map(|info| {
+                    Ok(types::CheckpointTransactionInfo {
+                        transaction: types::TransactionDigest::new(array(
+                            "transaction",
+                            &info.transaction,
+                        )?),
+                        effects: types::TransactionEffectsDigest::new(array(
+                            "effects",
+                            &info.effects,
+                        )?),
+                        signatures: signatures_from_bytes(info.signatures)?,
+                    })
+                })
+                .collect::<Result<_, ProtoConversionError>>()?,
+        ))
+    }
+}
+
+/// A message could not be converted to or from the SDK's types.
+#[derive(Debug)]
+pub enum ProtoConversionError {
+    /// A field required by the SDK's type is not set.
+    MissingField(&'static str),
+    /// A field is set to a value the SDK's type can't hold, e.g. a digest of the wrong length.
+    InvalidField {
+        field: &'static str,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// A part carried as BCS could not be encoded or decoded.
+    Bcs(bcs::Error),
+}
+
+impl ProtoConversionError {
+    fn invalid(
+        field: &'static str,
+        error: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::InvalidField {
+            field,
+            error: error.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for ProtoConversionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingField(field) => write!(f, "missing field `{field}`"),
+            Self::InvalidField { field, error } => write!(f, "invalid field `{field}`: {error}"),
+            Self::Bcs(e) => write!(f, "invalid BCS: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ProtoConversionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MissingField(_) => None,
+            Self::InvalidField { error, .. } => Some(error.as_ref()),
+            Self::Bcs(e) => Some(e),
+        }
+    }
+}
+
+impl From<bcs::Error> for ProtoConversionError {
+    fn from(e: bcs::Error) -> Self {
+        Self::Bcs(e)
+    }
+}
+
+fn required<T>(field: &'static str, value: Option<T>) -> Result<T, ProtoConversionError> {
+    value.ok_or(ProtoConversionError::MissingField(field))
+}
+
+fn array<const N: usize>(
+    field: &'static str,
+    bytes: &[u8],
+) -> Result<[u8; N], ProtoConversionError> {
+    bytes.try_into().map_err(|_| {
+        ProtoConversionError::invalid(field, format!("expected {N} bytes, got {}", bytes.len()))
+    })
+}
+
+fn index(field: &'static str, index: u32) -> Result<u16, ProtoConversionError> {
+    index
+        .try_into()
+        .map_err(|e| ProtoConversionError::invalid(field, e))
+}
+
+fn parse<T>(field: &'static str, s: &str) -> Result<T, ProtoConversionError>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    s.parse()
+        .map_err(|e: T::Err| ProtoConversionError::invalid(field, e.to_string()))
+}
+
+fn object_ids(
+    field: &'static str,
+    ids: &[Vec<u8>],
+) -> Result<Vec<types::ObjectId>, ProtoConversionError> {
+    ids.iter()
+        .map(|id| array(field, id).map(types::ObjectId::new))
+        .collect()
+}
+
+fn try_collect<T, U>(messages: Vec<T>) -> Result<Vec<U>, ProtoConversionError>
+where
+    T: TryInto<U, Error = ProtoConversionError>,
+{
+    messages.into_iter().map(TryInto::try_into).collect()
+}
+
+/// Signatures in their serialized form, which BCS encodes as a byte vector.
+fn signatures_to_bytes(
+    signatures: &[types::UserSignature],
+) -> Result<Vec<Vec<u8>>, ProtoConversionError> {
+    signatures
+        .iter()
+        .map(|signature| Ok(bcs::from_bytes(&bcs::to_bytes(signature)?)?))
+        .collect()
+}
+
+fn signatures_from_bytes(
+    signatures: Vec<Vec<u8>>,
+) -> Result<Vec<types::UserSignature>, ProtoConversionError> {
+    signatures
+        .into_iter()
+        .map(|signature| Ok(bcs::from_bytes(&bcs::to_bytes(&signature)?)?))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use prost::Message;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const GENERATED_FILE: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/proto/generated/sui.types.rs"
+    );
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn generated_code() {
+        let proto_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/proto");
+        let out_dir = std::env::temp_dir().join(format!("sui-sdk-proto-{}", std::process::id()));
+        std::fs::create_dir_all(&out_dir).unwrap();
+        let descriptors = protox::compile(["sui.types.proto"], [proto_dir]).unwrap();
+        prost_build::Config::new()
+            .out_dir(&out_dir)
+            .compile_fds(descriptors)
+            .unwrap();
+        let actual = std::fs::read_to_string(out_dir.join("sui.types.rs")).unwrap();
+        std::fs::remove_dir_all(&out_dir).unwrap();
+
+        if std::env::var_os("UPDATE_PROTO").is_some() {
+            std::fs::write(GENERATED_FILE, &actual).unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(GENERATED_FILE).unwrap();
+        assert!(
+            actual == expected,
+            "the proto definitions changed, regenerate {GENERATED_FILE} with `make update-proto`"
+        );
+    }
+
+    fn assert_roundtrip<T, M>(value: T)
+    where
+        T: Clone + PartialEq + std::fmt::Debug + TryInto<M, Error = ProtoConversionError>,
+        M: Message + Default + TryInto<T, Error = ProtoConversionError>,
+    {
+        let message: M = value.clone().try_into().unwrap();
+        let decoded = M::decode(message.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded.try_into().unwrap(), value);
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(b64: &str) -> T {
+        crate::encoding::from_bcs_base64(b64.trim()).unwrap()
+    }
+
+    #[test]
+    fn fixtures() {
+        for transaction in [
+            include_str!("../types/transaction/fixtures/genesis-transaction"),
+            include_str!("../types/transaction/fixtures/authenticator_state_update"),
+            include_str!("../types/transaction/fixtures/wormhole-pyth-transaction"),
+        ] {
+            assert_roundtrip::<types::Transaction, Transaction>(decode(transaction));
+        }
+        assert_roundtrip::<types::TransactionEffects, TransactionEffects>(decode(include_str!(
+            "../types/effects/fixtures/pyth-wormhole-v2"
+        )));
+        assert_roundtrip::<types::CheckpointContents, CheckpointContents>(decode("AAEgp6oAB8Qadn8+FqtdqeDIp8ViQNOZpMKs44MN0N5y7zIgqn5dKR1+8poL0pLNwRo/2knMnodwMTEDhqYL03kdewQBAWEAgpORkfH6ewjfFQYZJhmjkYq0/B3Set4mLJX/G0wUPb/V4H41gJipYu4I6ToyixnEuPQWxHKLckhNn+0UmI+pAJ9GegzEh0q2HWABmFMpFoPw0229dCfzWNOhHW5bes4H"));
+    }
+
+    #[test]
+    fn programmable_transaction_fields() {
+        let transaction: types::Transaction = decode(include_str!(
+            "../types/transaction/fixtures/wormhole-pyth-transaction"
+        ));
+        let message = Transaction::try_from(transaction.clone()).unwrap();
+        assert_eq!(message.sender, transaction.sender.as_bytes());
+        let Some(transaction_kind::Kind::ProgrammableTransaction(ptb)) =
+            message.kind.as_ref().and_then(|kind| kind.kind.as_ref())
+        else {
+            panic!("expected a programmable transaction");
+        };
+        let Some(command::Command::MoveCall(call)) = &ptb.commands[0].command else {
+            panic!("expected a move call");
+        };
+        assert!(!call.module.is_empty());
+    }
+
+    #[test]
+    fn invalid_messages() {
+        let transaction: types::Transaction = decode(include_str!(
+            "../types/transaction/fixtures/wormhole-pyth-transaction"
+        ));
+        let message = Transaction::try_from(transaction).unwrap();
+
+        let err = types::Transaction::try_from(Transaction {
+            gas_payment: None,
+            ..message.clone()
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "missing field `gas_payment`");
+
+        let err = types::Transaction::try_from(Transaction {
+            sender: vec![0; 20],
+            ..message.clone()
+        })
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid field `sender`: expected 32 bytes, got 20"
+        );
+
+        let argument = Argument {
+            kind: Some(argument::Kind::Input(u32::from(u16::MAX) + 1)),
+        };
+        assert!(matches!(
+            types::Argument::try_from(argument),
+            Err(ProtoConversionError::InvalidField { field: "input", .. })
+        ));
+    }
+
+    #[proptest]
+    fn signed_transaction(transaction: types::SignedTransaction) {
+        assert_roundtrip::<_, SignedTransaction>(transaction);
+    }
+
+    #[proptest]
+    fn transaction_effects(effects: types::TransactionEffects) {
+        assert_roundtrip::<_, TransactionEffects>(effects);
+    }
+
+    #[proptest]
+    fn signed_checkpoint_summary(summary: types::SignedCheckpointSummary) {
+        assert_roundtrip::<_, SignedCheckpointSummary>(summary);
+    }
+
+    #[proptest]
+    fn checkpoint_contents(contents: types::CheckpointContents) {
+        assert_roundtrip::<_, CheckpointContents>(contents);
+    }
+}
@@ -0,0 +1,754 @@
+//! Submission of transactions directly to the validators of a committee, without relaying them
+//! through a fullnode.
+//!
+//! The [`QuorumDriver`] sends a signed transaction to the validators through a
+//! [`ValidatorTransport`] until members holding a quorum of stake have signed it, aggregates their
+//! signatures into a [`CertifiedTransaction`], then sends the certificate to the validators until
+//! a quorum of stake agrees on its effects, returned as [`CertifiedTransactionEffects`].
+//!
+//! Transactions which must be ordered together, e.g. to settle a batch or to keep others from
+//! ordering transactions between them, can be submitted as a soft bundle with
+//! [`QuorumDriver::submit_soft_bundle`], where the validators support it. Consensus then orders
+//! their certificates consecutively, as long as none of them is already sequenced.
+//!
+//! The driver must be kept up to date with the committee of the current epoch, e.g. from a
+//! [`LightClientState`](crate::light_client::LightClientState).
+
+use std::collections::BTreeMap;
+
+use crate::types::CertifiedTransaction;
+use crate::types::CertifiedTransactionEffects;
+use crate::types::CheckpointVerificationError;
+use crate::types::InputArgument;
+use crate::types::SignedTransaction;
+use crate::types::SignedTransactionEffects;
+use crate::types::StakeUnit;
+use crate::types::TransactionDigest;
+use crate::types::TransactionEffectsDigest;
+use crate::types::TransactionKind;
+use crate::types::ValidatorCommittee;
+use crate::types::ValidatorCommitteeMember;
+use crate::types::ValidatorSignature;
+
+/// A transport carrying requests to validators.
+pub trait ValidatorTransport {
+    type Error;
+
+    /// Submit `transaction` to `validator` for signing.
+    fn submit_transaction(
+        &mut self,
+        validator: &ValidatorCommitteeMember,
+        transaction: &SignedTransaction,
+    ) -> Result<ValidatorSignature, Self::Error>;
+
+    /// Submit `certificate` to `validator` for execution, returning the effects it signed.
+    fn submit_certificate(
+        &mut self,
+        validator: &ValidatorCommitteeMember,
+        certificate: &CertifiedTransaction,
+    ) -> Result<SignedTransactionEffects, Self::Error>;
+
+    /// Submit `certificates` to `validator` as a soft bundle, returning the effects it signed for
+    /// each of them, in order.
+    ///
+    /// `None` if the transport or the validator doesn't support soft bundles, the default.
+    fn submit_soft_bundle(
+        &mut self,
+        _validator: &ValidatorCommitteeMember,
+        _certificates: &[CertifiedTransaction],
+    ) -> Option<Result<Vec<SignedTransactionEffects>, Self::Error>> {
+        None
+    }
+}
+
+/// The largest number of transactions validators accept in a soft bundle.
+pub const MAX_SOFT_BUNDLE_SIZE: usize = 5;
+
+/// Check that `transactions` can be submitted as a soft bundle, returning their digests.
+///
+/// A soft bundle holds up to [`MAX_SOFT_BUNDLE_SIZE`] distinct programmable transactions, all with
+/// the same gas price and each with a shared object input, as validators only sequence
+/// transactions touching shared objects through consensus.
+pub fn check_soft_bundle<'a>(
+    transactions: impl IntoIterator<Item = &'a SignedTransaction>,
+) -> Result<Vec<TransactionDigest>, SoftBundleError> {
+    let mut digests = Vec::new();
+    let mut gas_price = None;
+    for (index, signed) in transactions.into_iter().enumerate() {
+        let transaction = &signed.transaction;
+        let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind else {
+            return Err(SoftBundleError::NotUserTransaction(index));
+        };
+        if !ptb
+            .inputs
+            .iter()
+            .any(|input| matches!(input, InputArgument::Shared { .. }))
+        {
+            return Err(SoftBundleError::NoSharedObject(index));
+        }
+        if *gas_price.get_or_insert(transaction.gas_payment.price) != transaction.gas_payment.price
+        {
+            return Err(SoftBundleError::GasPriceMismatch(index));
+        }
+        let digest = transaction.digest();
+        if digests.contains(&digest) {
+            return Err(SoftBundleError::DuplicateTransaction(digest));
+        }
+        digests.push(digest);
+    }
+
+    match digests.len() {
+        0 => Err(SoftBundleError::Empty),
+        size if size > MAX_SOFT_BUNDLE_SIZE => Err(SoftBundleError::TooLarge(size)),
+        _ => Ok(digests),
+    }
+}
+
+pub struct QuorumDriver<T> {
+    transport: T,
+    committee: ValidatorCommittee,
+}
+
+impl<T: ValidatorTransport> QuorumDriver<T> {
+    /// Create a driver submitting transactions to the members of `committee`.
+    pub fn new(transport: T, committee: ValidatorCommittee) -> Self {
+        Self {
+            transport,
+            committee,
+        }
+    }
+
+    pub fn committee(&self) -> &ValidatorCommittee {
+        &self.committee
+    }
+
+    /// Replace the committee, e.g. at the start of a new epoch.
+    pub fn set_committee(&mut self, committee: ValidatorCommittee) {
+        self.committee = committee;
+    }
+
+    /// Collect validator signatures over `transaction` until a quorum of stake has signed it.
+    ///
+    /// Validators are asked in order. Validators that fail to respond or respond with an invalid
+    /// signature are skipped.
+    pub fn certify(
+        &mut self,
+        transaction: &SignedTransaction,
+    ) -> Result<CertifiedTransaction, QuorumDriverError<T::Error>> {
+        let message = transaction.authority_message(self.committee.epoch);
+        let threshold = self.committee.quorum_threshold()?;
+
+        let mut stake = 0;
+        let mut signatures = Vec::new();
+        let mut errors = Vec::new();
+        for member in &self.committee.members {
+            if stake >= threshold {
+                break;
+            }
+            let signature = match self.transport.submit_transaction(member, transaction) {
+                Ok(signature) => signature,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            // Ignore signatures which don't come from the member being asked
+            if signature.public_key != member.public_key
+                || self
+                    .committee
+                    .verify_signature(&message, &signature)
+                    .is_err()
+            {
+                continue;
+            }
+            stake += member.stake;
+            signatures.push(signature);
+        }
+
+        if stake < threshold {
+            return Err(QuorumDriverError::NoQuorum {
+                stake,
+                threshold,
+                errors,
+            });
+        }
+        Ok(CertifiedTransaction {
+            transaction: transaction.clone(),
+            signature: self.committee.aggregate_signatures(&signatures)?,
+        })
+    }
+
+    /// Submit `certificate` for execution until a quorum of stake agrees on its effects.
+    ///
+    /// Validators are asked in order. Validators that fail to respond or respond with effects of
+    /// another transaction or with an invalid signature are skipped.
+    pub fn execute(
+        &mut self,
+        certificate: &CertifiedTransaction,
+    ) -> Result<CertifiedTransactionEffects, QuorumDriverError<T::Error>> {
+        certificate.verify(&self.committee)?;
+        let digest = certificate.transaction.transaction.digest();
+        let threshold = self.committee.quorum_threshold()?;
+
+        // Responses grouped by the digest of their effects, as validators may disagree on them
+        let mut responses: BTreeMap<TransactionEffectsDigest, (StakeUnit, Vec<_>)> =
+            BTreeMap::new();
+        let mut errors = Vec::new();
+        for member in &self.committee.members {
+            let response = match self.transport.submit_certificate(member, certificate) {
+                Ok(response) => response,
+                Err(e) => {
+                    errors.push(e);
+                    continue;
+                }
+            };
+            if response.effects.transaction_digest() != &digest
+                || response.signature.public_key != member.public_key
+                || response.verify(&self.committee).is_err()
+            {
+                continue;
+            }
+            let SignedTransactionEffects { effects, signature } = response;
+
+            let (stake, signed) = responses.entry(effects.digest()).or_default();
+            *stake += member.stake;
+            signed.push((effects, signature));
+            if *stake >= threshold {
+                let signature = self
+                    .committee
+                    .aggregate_signatures(signed.iter().map(|(_, signature)| signature))?;
+                let (effects, _) = signed.swap_remove(0);
+                return Ok(CertifiedTransactionEffects { effects, signature });
+            }
+        }
+
+        Err(QuorumDriverError::NoQuorum {
+            stake: responses
+                .values()
+                .map(|(stake, _)| *stake)
+                .max()
+                .unwrap_or(0),
+            threshold,
+            errors,
+        })
+    }
+
+    /// Certify `transaction` and execute it, returning its final effects.
+    pub fn submit(
+        &mut self,
+        transaction: &SignedTransaction,
+    ) -> Result<CertifiedTransactionEffects, QuorumDriverError<T::Error>> {
+        let certificate = self.certify(transaction)?;
+        self.execute(&certificate)
+    }
+
+    /// Submit `certificates` for execution as a soft bundle until a quorum of stake agrees on the
+    /// effects of all of them, returned in the order of `certificates`.
+    ///
+    /// Validators are asked in order. Validators that don't support soft bundles are skipped, as
+    /// are those that fail to respond or whose response doesn't match `certificates` or has an
+    /// invalid signature.
+    pub fn execute_soft_bundle(
+        &mut self,
+        certificates: &[CertifiedTransaction],
+    ) -> Result<Vec<CertifiedTransactionEffects>, QuorumDriverError<T::Error>> {
+        let digests = check_soft_bundle(certificates.iter().map(|c| &c.transaction))?;
+        for certificate in certificates {
+            certificate.verify(&self.committee)?;
+        }
+        let threshold = self.committee.quorum_threshold()?;
+
+        // Responses grouped by the digests of their effects, as validators may disagree on them
+        let mut responses: BTreeMap<Vec<TransactionEffectsDigest>, (StakeUnit, Vec<_>)> =
+            BTreeMap::new();
+        let mut errors = Vec::new();
+        let mut supported = false;
+        for member in &self.committee.members {
+            let response = match self.transport.submit_soft_bundle(member, certificates) {
+                None => continue,
+                Some(Ok(response)) => response,
+                Some(Err(e)) => {
+                    supported = true;
+                    errors.push(e);
+                    continue;
+                }
+            };
+            supported = true;
+            if response.len() != digests.len()
+                || response.iter().zip(&digests).any(|(signed, digest)| {
+                    signed.effects.transaction_digest() != digest
+                        || signed.signature.public_key != member.public_key
+                        || signed.verify(&self.committee).is_err()
+                })
+            {
+                continue;
+            }
+
+            let effects_digests = response.iter().map(|signed| signed.effects.digest());
+            let (stake, signed) = responses.entry(effects_digests.collect()).or_default();
+            *stake += member.stake;
+            signed.push(response);
+            if *stake >= threshold {
+                return (0..digests.len())
+                    .map(|index| {
+                        let signature = self.committee.aggregate_signatures(
+                            signed.iter().map(|response| &response[index].signature),
+                        )?;
+                        Ok(CertifiedTransactionEffects {
+                            effects: signed[0][index].effects.clone(),
+                            signature,
+                        })
+                    })
+                    .collect();
+            }
+        }
+
+        if !supported {
+            return Err(SoftBundleError::Unsupported.into());
+        }
+        Err(QuorumDriverError::NoQuorum {
+            stake: responses
+                .values()
+                .map(|(stake, _)| *stake)
+                .max()
+                .unwrap_or(0),
+            threshold,
+            errors,
+        })
+    }
+
+    /// Certify `transactions` and execute them as a soft bundle, returning their final effects in
+    /// order.
+    pub fn submit_soft_bundle(
+        &mut self,
+        transactions: &[SignedTransaction],
+    ) -> Result<Vec<CertifiedTransactionEffects>, QuorumDriverError<T::Error>> {
+        check_soft_bundle(transactions)?;
+        let certificates = transactions
+            .iter()
+            .map(|transaction| self.certify(transaction))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.execute_soft_bundle(&certificates)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QuorumDriverError<E> {
+    /// Validators holding a quorum of stake did not sign the same response.
+    NoQuorum {
+        /// The largest stake which signed the same response.
+        stake: StakeUnit,
+        threshold: StakeUnit,
+        /// The errors of the validators that failed to respond.
+        errors: Vec<E>,
+    },
+    Signature(CheckpointVerificationError),
+    SoftBundle(SoftBundleError),
+}
+
+impl<E> std::fmt::Display for QuorumDriverError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NoQuorum {
+                stake,
+                threshold,
+                errors,
+            } => write!(
+                f,
+                "no quorum: stake {stake} agreed, {threshold} is required, {} validators failed to respond",
+                errors.len()
+            ),
+            Self::Signature(e) => write!(f, "{e}"),
+            Self::SoftBundle(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug> std::error::Error for QuorumDriverError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NoQuorum { .. } => None,
+            Self::Signature(e) => Some(e),
+            Self::SoftBundle(e) => Some(e),
+        }
+    }
+}
+
+impl<E> From<CheckpointVerificationError> for QuorumDriverError<E> {
+    fn from(e: CheckpointVerificationError) -> Self {
+        Self::Signature(e)
+    }
+}
+
+impl<E> From<SoftBundleError> for QuorumDriverError<E> {
+    fn from(e: SoftBundleError) -> Self {
+        Self::SoftBundle(e)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SoftBundleError {
+    Empty,
+    /// The bundle holds more than [`MAX_SOFT_BUNDLE_SIZE`] transactions.
+    TooLarge(usize),
+    /// The transaction at this index isn't a programmable transaction.
+    NotUserTransaction(usize),
+    /// The transaction at this index has no shared object input.
+    NoSharedObject(usize),
+    /// The transaction at this index has another gas price than the first.
+    GasPriceMismatch(usize),
+    DuplicateTransaction(TransactionDigest),
+    /// None of the validators support soft bundles.
+    Unsupported,
+}
+
+impl std::fmt::Display for SoftBundleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => write!(f, "soft bundle is empty"),
+            Self::TooLarge(size) => write!(
+                f,
+                "soft bundle of {size} transactions exceeds the maximum of {MAX_SOFT_BUNDLE_SIZE}"
+            ),
+            Self::NotUserTransaction(index) => write!(
+                f,
+                "transaction {index} of soft bundle is not a programmable transaction"
+            ),
+            Self::NoSharedObject(index) => write!(
+                f,
+                "transaction {index} of soft bundle has no shared object input"
+            ),
+            Self::GasPriceMismatch(index) => write!(
+                f,
+                "transaction {index} of soft bundle has a different gas price"
+            ),
+            Self::DuplicateTransaction(digest) => {
+                write!(f, "transaction {digest} is in soft bundle more than once")
+            }
+            Self::Unsupported => write!(f, "no validator supports soft bundles"),
+        }
+    }
+}
+
+impl std::error::Error for SoftBundleError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::test_committee;
+    use crate::types::EpochId;
+    use crate::types::Transaction;
+    use crate::types::TransactionEffects;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use blst::min_sig::SecretKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Behavior {
+        Honest,
+        Unavailable,
+        /// Signs with the key of another validator.
+        Impersonating,
+    }
+
+    struct MockValidators {
+        epoch: EpochId,
+        keys: Vec<SecretKey>,
+        behaviors: Vec<Behavior>,
+        effects: TransactionEffects,
+        soft_bundles: bool,
+    }
+
+    impl MockValidators {
+        fn new(behaviors: Vec<Behavior>) -> Self {
+            let effects: TransactionEffects = bcs::from_bytes(
+                &Base64::decode_vec(
+                    include_str!("types/effects/fixtures/genesis-transaction-effects").trim(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            Self {
+                epoch: EpochId::new(7),
+                keys: test_committee::secret_keys(0, behaviors.len() as u8),
+                behaviors,
+                effects,
+                soft_bundles: false,
+            }
+        }
+
+        fn committee(&self) -> ValidatorCommittee {
+            test_committee::committee(self.epoch, &self.keys)
+        }
+
+        fn sign(
+            &self,
+            validator: &ValidatorCommitteeMember,
+            message: &[u8],
+        ) -> Result<ValidatorSignature, String> {
+            let index = self
+                .keys
+                .iter()
+                .position(|key| test_committee::public_key(key) == validator.public_key)
+                .unwrap();
+            let key = match self.behaviors[index] {
+                Behavior::Honest => &self.keys[index],
+                Behavior::Unavailable => return Err(format!("validator {index} is down")),
+                Behavior::Impersonating => &self.keys[(index + 1) % self.keys.len()],
+            };
+            Ok(ValidatorSignature {
+                public_key: validator.public_key,
+                ..test_committee::sign(key, self.epoch, message)
+            })
+        }
+    }
+
+    impl ValidatorTransport for MockValidators {
+        type Error = String;
+
+        fn submit_transaction(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            transaction: &SignedTransaction,
+        ) -> Result<ValidatorSignature, Self::Error> {
+            self.sign(validator, &transaction.authority_message(self.epoch))
+        }
+
+        fn submit_certificate(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            _certificate: &CertifiedTransaction,
+        ) -> Result<SignedTransactionEffects, Self::Error> {
+            Ok(SignedTransactionEffects {
+                effects: self.effects.clone(),
+                signature: self.sign(validator, &self.effects.authority_message(self.epoch))?,
+            })
+        }
+
+        fn submit_soft_bundle(
+            &mut self,
+            validator: &ValidatorCommitteeMember,
+            certificates: &[CertifiedTransaction],
+        ) -> Option<Result<Vec<SignedTransactionEffects>, Self::Error>> {
+            if !self.soft_bundles {
+                return None;
+            }
+            let effects: TransactionEffects = bcs::from_bytes(
+                &Base64::decode_vec(include_str!("types/effects/fixtures/pyth-wormhole-v2").trim())
+                    .unwrap(),
+            )
+            .unwrap();
+            let response = certificates
+                .iter()
+                .map(|certificate| {
+                    let mut effects = effects.clone();
+                    if let TransactionEffects::V2(effects) = &mut effects {
+                        effects.transaction_digest = certificate.transaction.transaction.digest();
+                    }
+                    let signature = self.sign(validator, &effects.authority_message(self.epoch))?;
+                    Ok(SignedTransactionEffects { effects, signature })
+                })
+                .collect();
+            Some(response)
+        }
+    }
+
+    fn transaction() -> SignedTransaction {
+        let transaction: Transaction = bcs::from_bytes(
+            &Base64::decode_vec(
+                include_str!("types/transaction/fixtures/genesis-transaction").trim(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        SignedTransaction {
+            transaction,
+            signatures: vec![],
+        }
+    }
+
+    /// Programmable transactions, which differ by their gas budget.
+    fn user_transactions(count: u64) -> Vec<SignedTransaction> {
+        let transaction: Transaction = bcs::from_bytes(
+            &Base64::decode_vec(
+                include_str!("types/transaction/fixtures/wormhole-pyth-transaction").trim(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        (0..count)
+            .map(|i| {
+                let mut transaction = transaction.clone();
+                transaction.gas_payment.budget += i;
+                SignedTransaction {
+                    transaction,
+                    signatures: vec![],
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn submit() {
+        let validators = MockValidators::new(vec![
+            Behavior::Unavailable,
+            Behavior::Honest,
+            Behavior::Impersonating,
+            Behavior::Honest,
+            Behavior::Honest,
+            Behavior::Honest,
+            Behavior::Honest,
+        ]);
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee.clone());
+
+        let certificate = driver.certify(&transaction()).unwrap();
+        certificate.verify(&committee).unwrap();
+        assert_eq!(certificate.signature.epoch, committee.epoch);
+        assert_eq!(
+            certificate.signature.bitmap.iter().collect::<Vec<_>>(),
+            [1, 3, 4, 5, 6]
+        );
+
+        let effects = driver.execute(&certificate).unwrap();
+        effects.verify(&committee).unwrap();
+        assert_eq!(
+            effects.effects.transaction_digest(),
+            &transaction().transaction.digest()
+        );
+
+        assert_eq!(driver.submit(&transaction()), Ok(effects));
+    }
+
+    #[test]
+    fn no_quorum() {
+        let validators = MockValidators::new(vec![
+            Behavior::Unavailable,
+            Behavior::Honest,
+            Behavior::Impersonating,
+            Behavior::Honest,
+        ]);
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee);
+
+        assert_eq!(
+            driver.certify(&transaction()),
+            Err(QuorumDriverError::NoQuorum {
+                stake: 5000,
+                threshold: 6667,
+                errors: vec!["validator 0 is down".to_owned()],
+            })
+        );
+    }
+
+    #[test]
+    fn reject_uncertified_transactions() {
+        let validators = MockValidators::new(vec![Behavior::Honest; 4]);
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee);
+
+        let mut certificate = driver.certify(&transaction()).unwrap();
+        certificate.signature.bitmap.remove(0);
+        assert!(matches!(
+            driver.execute(&certificate),
+            Err(QuorumDriverError::Signature(
+                CheckpointVerificationError::InsufficientStake { .. }
+            ))
+        ));
+    }
+
+    #[test]
+    fn soft_bundle() {
+        let mut validators = MockValidators::new(vec![
+            Behavior::Unavailable,
+            Behavior::Honest,
+            Behavior::Impersonating,
+            Behavior::Honest,
+            Behavior::Honest,
+            Behavior::Honest,
+            Behavior::Honest,
+        ]);
+        validators.soft_bundles = true;
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee.clone());
+
+        let transactions = user_transactions(3);
+        let effects = driver.submit_soft_bundle(&transactions).unwrap();
+        assert_eq!(effects.len(), 3);
+        for (effects, transaction) in effects.iter().zip(&transactions) {
+            effects.verify(&committee).unwrap();
+            assert_eq!(
+                effects.effects.transaction_digest(),
+                &transaction.transaction.digest()
+            );
+            assert_eq!(
+                effects.signature.bitmap.iter().collect::<Vec<_>>(),
+                [1, 3, 4, 5, 6]
+            );
+        }
+    }
+
+    #[test]
+    fn soft_bundle_unsupported() {
+        let validators = MockValidators::new(vec![Behavior::Honest; 4]);
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee);
+
+        assert_eq!(
+            driver.submit_soft_bundle(&user_transactions(2)),
+            Err(QuorumDriverError::SoftBundle(SoftBundleError::Unsupported))
+        );
+    }
+
+    #[test]
+    fn invalid_soft_bundles() {
+        let transactions = user_transactions(6);
+        assert_eq!(check_soft_bundle(&[]), Err(SoftBundleError::Empty));
+        assert_eq!(
+            check_soft_bundle(&transactions),
+            Err(SoftBundleError::TooLarge(6))
+        );
+        assert_eq!(
+            check_soft_bundle([&transactions[0], &transaction()]),
+            Err(SoftBundleError::NotUserTransaction(1))
+        );
+        assert_eq!(
+            check_soft_bundle([&transactions[0], &transactions[1], &transactions[0]]),
+            Err(SoftBundleError::DuplicateTransaction(
+                transactions[0].transaction.digest()
+            ))
+        );
+
+        let mut owned = transactions[1].clone();
+        let TransactionKind::ProgrammableTransaction(ptb) = &mut owned.transaction.kind else {
+            unreachable!()
+        };
+        ptb.inputs
+            .retain(|input| !matches!(input, InputArgument::Shared { .. }));
+        assert_eq!(
+            check_soft_bundle([&transactions[0], &owned]),
+            Err(SoftBundleError::NoSharedObject(1))
+        );
+
+        let mut expensive = transactions[1].clone();
+        expensive.transaction.gas_payment.price += 1;
+        assert_eq!(
+            check_soft_bundle([&transactions[0], &expensive]),
+            Err(SoftBundleError::GasPriceMismatch(1))
+        );
+
+        let mut validators = MockValidators::new(vec![Behavior::Honest; 4]);
+        validators.soft_bundles = true;
+        let committee = validators.committee();
+        let mut driver = QuorumDriver::new(validators, committee);
+        assert_eq!(
+            driver.submit_soft_bundle(&[transaction()]),
+            Err(QuorumDriverError::SoftBundle(
+                SoftBundleError::NotUserTransaction(0)
+            ))
+        );
+    }
+}
@@ -0,0 +1,520 @@
+//! A relayer carrying token transfers from Sui to ethereum through the Sui Bridge.
+//!
+//! The [`BridgeRelayer`] tails checkpoints from a [`CheckpointSource`], picks out the
+//! `TokenDepositedEvent`s emitted by the bridge package, collects signatures over the
+//! corresponding bridge messages from the committee through a [`SignatureTransport`], and builds
+//! the claim transactions to submit to the `SuiBridge` contract on the target chain.
+//!
+//! Checkpoints are trusted as returned by the source, they are expected to be verified by it.
+
+use crate::types::BridgeAuthoritySignature;
+use crate::types::BridgeChainId;
+use crate::types::BridgeCommittee;
+use crate::types::BridgeCommitteeMember;
+use crate::types::BridgeEvent;
+use crate::types::BridgeEventError;
+use crate::types::BridgeMessage;
+use crate::types::BridgeMessageError;
+use crate::types::BridgeSignatureAggregator;
+use crate::types::BridgeSignatureError;
+use crate::types::CertifiedBridgeMessage;
+use crate::types::CheckpointData;
+use crate::types::EthAddress;
+use crate::types::TokenDepositedEvent;
+
+/// A source of executed checkpoints, in order.
+pub trait CheckpointSource {
+    type Error;
+
+    /// Fetch the next checkpoint, or `None` if it isn't available yet.
+    fn next_checkpoint(&mut self) -> Result<Option<CheckpointData>, Self::Error>;
+}
+
+/// A transport requesting signatures over bridge messages from committee members.
+pub trait SignatureTransport {
+    type Error;
+
+    fn request_signature(
+        &mut self,
+        member: &BridgeCommitteeMember,
+        message: &BridgeMessage,
+    ) -> Result<BridgeAuthoritySignature, Self::Error>;
+}
+
+/// A transaction claiming a token transfer on an ethereum chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EthClaimTransaction {
+    /// The EVM chain id of the target chain.
+    pub chain_id: u64,
+    /// The address of the `SuiBridge` contract.
+    pub to: EthAddress,
+    pub calldata: Vec<u8>,
+    pub message: CertifiedBridgeMessage,
+}
+
+pub struct BridgeRelayer<S, T> {
+    source: S,
+    transport: T,
+    committee: BridgeCommittee,
+    eth_bridge: EthAddress,
+}
+
+impl<S: CheckpointSource, T: SignatureTransport> BridgeRelayer<S, T> {
+    /// Create a relayer claiming transfers on the `SuiBridge` contract at `eth_bridge`, with
+    /// signatures of `committee`.
+    pub fn new(
+        source: S,
+        transport: T,
+        committee: BridgeCommittee,
+        eth_bridge: EthAddress,
+    ) -> Self {
+        Self {
+            source,
+            transport,
+            committee,
+            eth_bridge,
+        }
+    }
+
+    pub fn committee(&self) -> &BridgeCommittee {
+        &self.committee
+    }
+
+    /// Replace the committee, e.g. after a `CommitteeUpdateEvent`.
+    pub fn set_committee(&mut self, committee: BridgeCommittee) {
+        self.committee = committee;
+    }
+
+    /// Fetch the next checkpoint and return the token deposits it contains, or `None` if it isn't
+    /// available yet.
+    pub fn next_deposits(
+        &mut self,
+    ) -> Result<Option<Vec<TokenDepositedEvent>>, RelayerError<S::Error, T::Error>> {
+        let Some(checkpoint) = self
+            .source
+            .next_checkpoint()
+            .map_err(RelayerError::Source)?
+        else {
+            return Ok(None);
+        };
+        token_deposits(&checkpoint)
+            .map(Some)
+            .map_err(RelayerError::Event)
+    }
+
+    /// Collect committee signatures over `message` until its approval threshold is reached.
+    ///
+    /// Members are asked in order. Members that fail to respond or respond with an invalid
+    /// signature are skipped, and if the threshold isn't reached, the errors of the members that
+    /// failed to respond are returned.
+    pub fn certify(
+        &mut self,
+        message: BridgeMessage,
+    ) -> Result<CertifiedBridgeMessage, RelayerError<S::Error, T::Error>> {
+        let mut aggregator = BridgeSignatureAggregator::new(&self.committee, message.clone())?;
+        let mut transport_errors = Vec::new();
+        for member in &self.committee.members {
+            if aggregator.has_quorum() {
+                break;
+            }
+            if member.blocklisted {
+                continue;
+            }
+            let signature = match self.transport.request_signature(member, &message) {
+                Ok(signature) => signature,
+                Err(e) => {
+                    transport_errors.push(e);
+                    continue;
+                }
+            };
+            // Ignore signatures which don't come from the member being asked
+            if signature.recover_public_key(&message).ok() != Some(member.public_key) {
+                continue;
+            }
+            aggregator.add_signature(signature)?;
+        }
+        aggregator.finish().map_err(|e| match e {
+            BridgeSignatureError::InsufficientVotingPower { .. }
+                if !transport_errors.is_empty() =>
+            {
+                RelayerError::Transport {
+                    signature: e,
+                    errors: transport_errors,
+                }
+            }
+            e => RelayerError::Signature(e),
+        })
+    }
+
+    /// Certify the transfer of `deposit` and build the transaction claiming it on its target
+    /// chain.
+    pub fn claim(
+        &mut self,
+        deposit: &TokenDepositedEvent,
+    ) -> Result<EthClaimTransaction, RelayerError<S::Error, T::Error>> {
+        let message = deposit.to_bridge_message()?;
+        let target_chain = BridgeChainId::from_byte(deposit.target_chain)?;
+        let chain_id = target_chain
+            .evm_chain_id()
+            .ok_or(RelayerError::UnsupportedTargetChain(target_chain))?;
+
+        let message = self.certify(message)?;
+        Ok(EthClaimTransaction {
+            chain_id,
+            to: self.eth_bridge,
+            calldata: message.to_eth_claim_calldata(),
+            message,
+        })
+    }
+
+    /// Fetch the next checkpoint and claim each of its token deposits, or return `None` if it
+    /// isn't available yet.
+    ///
+    /// Returns each deposit with the result of its claim, every deposit being claimed even if
+    /// others can't be. The checkpoint is consumed either way, so a deposit which can't be claimed
+    /// has to be retried with [`BridgeRelayer::claim`].
+    pub fn relay_next(
+        &mut self,
+    ) -> Result<Option<Vec<RelayedDeposit<S::Error, T::Error>>>, RelayerError<S::Error, T::Error>>
+    {
+        let Some(deposits) = self.next_deposits()? else {
+            return Ok(None);
+        };
+        let claims = deposits
+            .into_iter()
+            .map(|deposit| RelayedDeposit {
+                claim: self.claim(&deposit),
+                deposit,
+            })
+            .collect();
+        Ok(Some(claims))
+    }
+}
+
+/// A token deposit relayed by [`BridgeRelayer::relay_next`], with the result of its claim.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelayedDeposit<S, T> {
+    pub deposit: TokenDepositedEvent,
+    pub claim: Result<EthClaimTransaction, RelayerError<S, T>>,
+}
+
+/// The token deposits made to the bridge in `checkpoint`, in execution order.
+pub fn token_deposits(
+    checkpoint: &CheckpointData,
+) -> Result<Vec<TokenDepositedEvent>, BridgeEventError> {
+    let mut deposits = Vec::new();
+    let events = checkpoint
+        .transactions
+        .iter()
+        .filter_map(|transaction| transaction.events.as_ref())
+        .flat_map(|events| events.events());
+    for event in events {
+        match BridgeEvent::try_from_event(event) {
+            Ok(BridgeEvent::TokenDeposited(deposit)) => deposits.push(deposit),
+            // Events added by later versions of the bridge package are of no concern here
+            Ok(_)
+            | Err(BridgeEventError::NotBridgeEvent)
+            | Err(BridgeEventError::UnknownEventType { .. }) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(deposits)
+}
+
+/// An error of the relayer, whose checkpoint source fails with `S` and signature transport with
+/// `T`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RelayerError<S, T> {
+    Source(S),
+    Event(BridgeEventError),
+    Message(BridgeMessageError),
+    Signature(BridgeSignatureError),
+    /// The approval threshold wasn't reached, with some members failing to respond.
+    Transport {
+        signature: BridgeSignatureError,
+        /// The errors of the members that failed to respond, in the order they were asked.
+        errors: Vec<T>,
+    },
+    /// Transfers to the chain can't be claimed by the relayer.
+    UnsupportedTargetChain(BridgeChainId),
+}
+
+impl<S: std::fmt::Display, T: std::fmt::Display> std::fmt::Display for RelayerError<S, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "failed to fetch checkpoint: {e}"),
+            Self::Event(e) => write!(f, "{e}"),
+            Self::Message(e) => write!(f, "{e}"),
+            Self::Signature(e) => write!(f, "{e}"),
+            Self::Transport { signature, errors } => {
+                write!(f, "{signature}, {} members failed to respond", errors.len())?;
+                if let Some(e) = errors.first() {
+                    write!(f, ": {e}")?;
+                }
+                Ok(())
+            }
+            Self::UnsupportedTargetChain(chain) => {
+                write!(f, "unsupported target chain {chain:?}")
+            }
+        }
+    }
+}
+
+impl<S, T> std::error::Error for RelayerError<S, T>
+where
+    S: std::fmt::Debug + std::fmt::Display,
+    T: std::fmt::Debug + std::fmt::Display,
+{
+}
+
+impl<S, T> From<BridgeMessageError> for RelayerError<S, T> {
+    fn from(e: BridgeMessageError) -> Self {
+        Self::Message(e)
+    }
+}
+
+impl<S, T> From<BridgeSignatureError> for RelayerError<S, T> {
+    fn from(e: BridgeSignatureError) -> Self {
+        Self::Signature(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Address;
+    use crate::types::CheckpointTransaction;
+    use crate::types::Event;
+    use crate::types::Identifier;
+    use crate::types::ObjectId;
+    use crate::types::Secp256k1PublicKey;
+    use crate::types::StructTag;
+    use crate::types::TransactionEvents;
+    use crate::types::BRIDGE_COMMITTEE_TOTAL_VOTING_POWER;
+    use crate::types::BRIDGE_PACKAGE_ADDRESS;
+    use k256::ecdsa::SigningKey;
+    use std::collections::VecDeque;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    struct Checkpoints(VecDeque<CheckpointData>);
+
+    impl CheckpointSource for Checkpoints {
+        type Error = std::convert::Infallible;
+
+        fn next_checkpoint(&mut self) -> Result<Option<CheckpointData>, Self::Error> {
+            Ok(self.0.pop_front())
+        }
+    }
+
+    /// Signs with the members' keys, except for the members which are offline.
+    struct Committee {
+        keys: Vec<SigningKey>,
+        offline: Vec<usize>,
+        requests: usize,
+    }
+
+    impl SignatureTransport for Committee {
+        type Error = &'static str;
+
+        fn request_signature(
+            &mut self,
+            member: &BridgeCommitteeMember,
+            message: &BridgeMessage,
+        ) -> Result<BridgeAuthoritySignature, Self::Error> {
+            self.requests += 1;
+            let (index, key) = self
+                .keys
+                .iter()
+                .enumerate()
+                .find(|(_, key)| public_key(key) == member.public_key)
+                .unwrap();
+            if self.offline.contains(&index) {
+                return Err("offline");
+            }
+            let (signature, recovery_id) = key
+                .sign_prehash_recoverable(&message.signing_digest())
+                .unwrap();
+            let mut bytes = [0; BridgeAuthoritySignature::LENGTH];
+            bytes[..64].copy_from_slice(&signature.to_bytes());
+            bytes[64] = recovery_id.to_byte();
+            Ok(BridgeAuthoritySignature::new(bytes))
+        }
+    }
+
+    fn public_key(key: &SigningKey) -> Secp256k1PublicKey {
+        Secp256k1PublicKey::from_bytes(key.verifying_key().to_encoded_point(true).as_bytes())
+            .unwrap()
+    }
+
+    fn committee(keys: &[SigningKey]) -> BridgeCommittee {
+        BridgeCommittee::new(
+            keys.iter()
+                .map(|key| BridgeCommitteeMember {
+                    public_key: public_key(key),
+                    voting_power: BRIDGE_COMMITTEE_TOTAL_VOTING_POWER / keys.len() as u64,
+                    blocklisted: false,
+                })
+                .collect(),
+        )
+    }
+
+    fn bridge_event(name: &str, contents: Vec<u8>) -> Event {
+        Event {
+            package_id: ObjectId::from(BRIDGE_PACKAGE_ADDRESS),
+            module: Identifier::new("bridge").unwrap(),
+            sender: Address::ZERO,
+            type_: StructTag {
+                address: BRIDGE_PACKAGE_ADDRESS,
+                module: Identifier::new("bridge").unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            },
+            contents,
+        }
+    }
+
+    fn deposit(seq_num: u64) -> TokenDepositedEvent {
+        TokenDepositedEvent {
+            seq_num,
+            source_chain: BridgeChainId::SuiTestnet.to_u8(),
+            sender_address: vec![0xaa; 32],
+            target_chain: BridgeChainId::EthSepolia.to_u8(),
+            target_address: vec![0xbb; 20],
+            token_type: 2,
+            amount: 1000,
+        }
+    }
+
+    #[proptest(cases = 8)]
+    fn relay_deposits(mut checkpoint: CheckpointData, mut transaction: CheckpointTransaction) {
+        let keys: Vec<_> = (1..=4)
+            .map(|seed| SigningKey::from_slice(&[seed; 32]).unwrap())
+            .collect();
+        let committee = committee(&keys);
+
+        transaction.events = Some(TransactionEvents::new(vec![
+            bridge_event("TokenDepositedEvent", bcs::to_bytes(&deposit(1)).unwrap()),
+            bridge_event("EmergencyOpEvent", vec![0]),
+            bridge_event("TokenDepositedEvent", bcs::to_bytes(&deposit(2)).unwrap()),
+        ]));
+        checkpoint.transactions = vec![transaction];
+        assert_eq!(
+            token_deposits(&checkpoint),
+            Ok(vec![deposit(1), deposit(2)])
+        );
+
+        let eth_bridge = EthAddress::new([0xcc; EthAddress::LENGTH]);
+        let mut relayer = BridgeRelayer::new(
+            Checkpoints(VecDeque::from([checkpoint])),
+            Committee {
+                keys: keys.clone(),
+                offline: vec![0],
+                requests: 0,
+            },
+            committee.clone(),
+            eth_bridge,
+        );
+
+        let claims = relayer.relay_next().unwrap().unwrap();
+        assert_eq!(claims.len(), 2);
+        for (relayed, seq_num) in claims.into_iter().zip(1..) {
+            assert_eq!(relayed.deposit, deposit(seq_num));
+            let claim = relayed.claim.unwrap();
+            assert_eq!(claim.chain_id, 11155111);
+            assert_eq!(claim.to, eth_bridge);
+            assert_eq!(
+                claim.message.message,
+                deposit(seq_num).to_bridge_message().unwrap()
+            );
+            // The threshold of transfers is reached by two of the three online members
+            assert_eq!(claim.message.verify(&committee), Ok(5000));
+            assert_eq!(claim.calldata, claim.message.to_eth_claim_calldata());
+        }
+        assert_eq!(relayer.transport.requests, 6);
+
+        assert_eq!(relayer.relay_next(), Ok(None));
+    }
+
+    #[proptest(cases = 8)]
+    fn relay_unclaimable_deposit(
+        mut checkpoint: CheckpointData,
+        mut transaction: CheckpointTransaction,
+    ) {
+        let keys: Vec<_> = (1..=4)
+            .map(|seed| SigningKey::from_slice(&[seed; 32]).unwrap())
+            .collect();
+
+        let mut unsupported = deposit(1);
+        unsupported.target_chain = BridgeChainId::SuiMainnet.to_u8();
+        transaction.events = Some(TransactionEvents::new(vec![
+            bridge_event("TokenDepositedEvent", bcs::to_bytes(&unsupported).unwrap()),
+            bridge_event("TokenDepositedEvent", bcs::to_bytes(&deposit(2)).unwrap()),
+        ]));
+        checkpoint.transactions = vec![transaction];
+
+        let mut relayer = BridgeRelayer::new(
+            Checkpoints(VecDeque::from([checkpoint])),
+            Committee {
+                keys: keys.clone(),
+                offline: vec![],
+                requests: 0,
+            },
+            committee(&keys),
+            EthAddress::new([0xcc; EthAddress::LENGTH]),
+        );
+
+        // The deposit after the one which can't be claimed is still claimed
+        let claims = relayer.relay_next().unwrap().unwrap();
+        assert_eq!(claims.len(), 2);
+        assert_eq!(claims[0].deposit, unsupported);
+        assert_eq!(
+            claims[0].claim,
+            Err(RelayerError::UnsupportedTargetChain(
+                BridgeChainId::SuiMainnet
+            ))
+        );
+        assert_eq!(claims[1].deposit, deposit(2));
+        assert!(claims[1].claim.is_ok());
+    }
+
+    #[test]
+    fn certify_without_quorum() {
+        let keys: Vec<_> = (1..=4)
+            .map(|seed| SigningKey::from_slice(&[seed; 32]).unwrap())
+            .collect();
+        let mut relayer = BridgeRelayer::new(
+            Checkpoints(VecDeque::new()),
+            Committee {
+                keys: keys.clone(),
+                offline: vec![0, 1, 2],
+                requests: 0,
+            },
+            committee(&keys),
+            EthAddress::new([0xcc; EthAddress::LENGTH]),
+        );
+
+        let message = deposit(1).to_bridge_message().unwrap();
+        assert_eq!(
+            relayer.certify(message),
+            Err(RelayerError::Transport {
+                signature: BridgeSignatureError::InsufficientVotingPower {
+                    voting_power: 2500,
+                    threshold: 3334,
+                },
+                errors: vec!["offline"; 3],
+            })
+        );
+
+        let mut unsupported = deposit(1);
+        unsupported.target_chain = BridgeChainId::SuiMainnet.to_u8();
+        assert_eq!(
+            relayer.claim(&unsupported),
+            Err(RelayerError::UnsupportedTargetChain(
+                BridgeChainId::SuiMainnet
+            ))
+        );
+    }
+}
@@ -0,0 +1,541 @@
+//! Replay of historical transactions, for debugging why a transaction aborted or produced
+//! unexpected effects.
+//!
+//! The [`Replayer`] fetches a transaction and its recorded effects by digest from a
+//! [`ReplaySource`], fetches the objects it read at the versions they had before it executed,
+//! then re-runs it against exactly those objects and compares the resulting effects with the
+//! recorded ones in an [`EffectsDiff`].
+//!
+//! A faithful replay needs a source able to execute against historical object versions, e.g. a
+//! local executor or a fullnode forked at the transaction's checkpoint. The
+//! `sui_devInspectTransactionBlock` RPC method runs against the latest state of the network, so a
+//! source built on it only reproduces transactions whose inputs haven't changed since.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::types::ExecutionStatus;
+use crate::types::GasCostSummary;
+use crate::types::InputArgument;
+use crate::types::Object;
+use crate::types::ObjectId;
+use crate::types::ObjectReference;
+use crate::types::Transaction;
+use crate::types::TransactionDigest;
+use crate::types::TransactionEffects;
+use crate::types::TransactionKind;
+use crate::types::Version;
+
+/// Where the history of transactions and objects replayed by a [`Replayer`] is read from, and
+/// where they are re-run.
+pub trait ReplaySource {
+    type Error;
+
+    /// Fetch the transaction with `digest`, along with the effects recorded when it executed.
+    fn transaction(
+        &mut self,
+        digest: &TransactionDigest,
+    ) -> Result<(Transaction, TransactionEffects), Self::Error>;
+
+    /// Fetch `object_id` as it was at `version`.
+    fn object(&mut self, object_id: &ObjectId, version: Version) -> Result<Object, Self::Error>;
+
+    /// Execute `transaction` in dev-inspect mode, reading `objects` rather than the latest
+    /// versions of its inputs, and return its effects without committing them.
+    fn dev_inspect(
+        &mut self,
+        transaction: &Transaction,
+        objects: &[Object],
+    ) -> Result<TransactionEffects, Self::Error>;
+}
+
+/// Replays historical transactions read from a [`ReplaySource`].
+pub struct Replayer<S> {
+    source: S,
+}
+
+impl<S: ReplaySource> Replayer<S> {
+    pub fn new(source: S) -> Self {
+        Self { source }
+    }
+
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    pub fn into_inner(self) -> S {
+        self.source
+    }
+
+    /// Replay the transaction with `digest` at the versions of the objects it originally read.
+    pub fn replay(
+        &mut self,
+        digest: &TransactionDigest,
+    ) -> Result<ReplayedTransaction, ReplayError<S::Error>> {
+        let (transaction, recorded) = self
+            .source
+            .transaction(digest)
+            .map_err(ReplayError::Source)?;
+        if recorded.transaction_digest() != digest {
+            return Err(ReplayError::DigestMismatch {
+                expected: *digest,
+                actual: *recorded.transaction_digest(),
+            });
+        }
+
+        let objects = input_versions(&transaction, &recorded)
+            .into_iter()
+            .map(|(object_id, version)| {
+                let object = self
+                    .source
+                    .object(&object_id, version)
+                    .map_err(ReplayError::Source)?;
+                if object.object_id() != object_id || object.version() != version {
+                    return Err(ReplayError::UnexpectedObject {
+                        object_id,
+                        version,
+                        actual: (object.object_id(), object.version()),
+                    });
+                }
+                Ok(object)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let replayed = self
+            .source
+            .dev_inspect(&transaction, &objects)
+            .map_err(ReplayError::Source)?;
+
+        Ok(ReplayedTransaction {
+            transaction,
+            objects,
+            recorded,
+            replayed,
+        })
+    }
+}
+
+/// The version of each object `transaction` read, as referenced by its inputs and gas payment,
+/// or for shared objects and the objects it changed, as recorded in its `effects`.
+///
+/// Packages aren't included: the source loads the ones the transaction calls into itself.
+fn input_versions(
+    transaction: &Transaction,
+    effects: &TransactionEffects,
+) -> BTreeMap<ObjectId, Version> {
+    let inputs = match &transaction.kind {
+        TransactionKind::ProgrammableTransaction(programmable) => programmable.inputs.as_slice(),
+        _ => &[],
+    };
+    let referenced = inputs
+        .iter()
+        .filter_map(|input| match input {
+            InputArgument::ImmutableOrOwned(reference) | InputArgument::Receiving(reference) => {
+                Some(reference)
+            }
+            InputArgument::Pure { .. } | InputArgument::Shared { .. } => None,
+        })
+        .chain(&transaction.gas_payment.objects)
+        .map(|reference| (*reference.object_id(), reference.version()));
+
+    // The effects come last so their versions take precedence.
+    referenced.chain(effects.input_versions()).collect()
+}
+
+/// A historical transaction re-run at the versions of the objects it originally read.
+#[derive(Clone, Debug)]
+pub struct ReplayedTransaction {
+    pub transaction: Transaction,
+    /// The objects the transaction read, at the versions they had before it executed.
+    pub objects: Vec<Object>,
+    /// The effects recorded when the transaction executed.
+    pub recorded: TransactionEffects,
+    /// The effects of the replay.
+    pub replayed: TransactionEffects,
+}
+
+impl ReplayedTransaction {
+    /// The differences between the recorded and the replayed effects.
+    pub fn diff(&self) -> EffectsDiff {
+        EffectsDiff::new(&self.recorded, &self.replayed)
+    }
+}
+
+/// A value which differs between the recorded and the replayed effects of a transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Difference<T> {
+    pub recorded: T,
+    pub replayed: T,
+}
+
+impl<T: PartialEq> Difference<T> {
+    fn new(recorded: T, replayed: T) -> Option<Self> {
+        (recorded != replayed).then_some(Self { recorded, replayed })
+    }
+}
+
+/// The differences between two sets of effects of the same transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EffectsDiff {
+    pub status: Option<Difference<ExecutionStatus>>,
+    pub gas_used: Option<Difference<GasCostSummary>>,
+    /// The objects written with a different version or digest, by object id. An object which is
+    /// `None` on one side was deleted, wrapped or left untouched there.
+    pub objects: BTreeMap<ObjectId, Difference<Option<ObjectReference>>>,
+}
+
+impl EffectsDiff {
+    pub fn new(recorded: &TransactionEffects, replayed: &TransactionEffects) -> Self {
+        let object_ids: BTreeSet<&ObjectId> =
+            recorded.object_ids().chain(replayed.object_ids()).collect();
+        let objects = object_ids
+            .into_iter()
+            .filter_map(|object_id| {
+                let difference = Difference::new(
+                    recorded.written_object(object_id),
+                    replayed.written_object(object_id),
+                )?;
+                Some((*object_id, difference))
+            })
+            .collect();
+
+        Self {
+            status: Difference::new(recorded.status().clone(), replayed.status().clone()),
+            gas_used: Difference::new(recorded.gas_used().clone(), replayed.gas_used().clone()),
+            objects,
+        }
+    }
+
+    /// Whether the replay reproduced the recorded effects.
+    pub fn is_empty(&self) -> bool {
+        self.status.is_none() && self.gas_used.is_none() && self.objects.is_empty()
+    }
+}
+
+impl std::fmt::Display for EffectsDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fn written(reference: &Option<ObjectReference>) -> String {
+            match reference {
+                Some(reference) => format!("{} ({})", reference.version(), reference.digest()),
+                None => "not written".to_owned(),
+            }
+        }
+
+        if self.is_empty() {
+            return write!(f, "no differences");
+        }
+        if let Some(status) = &self.status {
+            writeln!(
+                f,
+                "status: recorded {:?}, replayed {:?}",
+                status.recorded, status.replayed
+            )?;
+        }
+        if let Some(gas_used) = &self.gas_used {
+            writeln!(
+                f,
+                "gas used: recorded {:?}, replayed {:?}",
+                gas_used.recorded, gas_used.replayed
+            )?;
+        }
+        for (object_id, difference) in &self.objects {
+            writeln!(
+                f,
+                "object {object_id}: recorded {}, replayed {}",
+                written(&difference.recorded),
+                written(&difference.replayed)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError<E> {
+    /// The source failed to serve a request.
+    Source(E),
+    /// The source returned the effects of another transaction.
+    DigestMismatch {
+        expected: TransactionDigest,
+        actual: TransactionDigest,
+    },
+    /// The source returned another object, or another version of it, than requested.
+    UnexpectedObject {
+        object_id: ObjectId,
+        version: Version,
+        actual: (ObjectId, Version),
+    },
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for ReplayError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Source(e) => write!(f, "replay source error: {e}"),
+            Self::DigestMismatch { expected, actual } => write!(
+                f,
+                "requested transaction {expected}, received the effects of {actual}"
+            ),
+            Self::UnexpectedObject {
+                object_id,
+                version,
+                actual: (actual_id, actual_version),
+            } => write!(
+                f,
+                "requested object {object_id} at version {version}, \
+                 received {actual_id} at version {actual_version}"
+            ),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ReplayError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Source(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")]
+mod test {
+    use super::*;
+    use crate::types::Address;
+    use crate::types::EpochId;
+    use crate::types::ExecutionError;
+    use crate::types::GasPayment;
+    use crate::types::ObjectDigest;
+    use crate::types::Owner;
+    use crate::types::ProgrammableTransaction;
+    use crate::types::TransactionEffectsV2;
+    use crate::types::TransactionExpiration;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    /// A package object, the simplest object to build outside of `types`.
+    fn object(object_id: ObjectId, version: Version) -> Object {
+        // The BCS layout of a package object: the `Package` variant of its data, its id,
+        // version, and empty module, type origin and linkage tables, then its owner, previous
+        // transaction and storage rebate.
+        let bytes = bcs::to_bytes(&(
+            1u8,
+            object_id,
+            version,
+            [0u8; 3],
+            Owner::Immutable,
+            TransactionDigest::ZERO,
+            0u64,
+        ))
+        .unwrap();
+        bcs::from_bytes(&bytes).unwrap()
+    }
+
+    fn reference(object_id: ObjectId, version: u64) -> ObjectReference {
+        ObjectReference::new(object_id, Version::new(version), ObjectDigest::ZERO)
+    }
+
+    fn transaction(owned: ObjectReference, gas: ObjectReference) -> Transaction {
+        Transaction {
+            kind: TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+                inputs: vec![
+                    InputArgument::ImmutableOrOwned(owned),
+                    InputArgument::Shared {
+                        object_id: ObjectId::new([3; 32]),
+                        initial_shared_version: Version::new(1),
+                        mutable: true,
+                    },
+                ],
+                commands: vec![],
+            }),
+            sender: Address::ZERO,
+            gas_payment: GasPayment {
+                objects: vec![gas],
+                owner: Address::ZERO,
+                price: 1000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    fn effects(digest: TransactionDigest, status: ExecutionStatus) -> TransactionEffects {
+        use crate::types::ChangedObject;
+        use crate::types::EffectsObjectChange;
+        use crate::types::IdOperation;
+        use crate::types::ObjectIn;
+        use crate::types::ObjectOut;
+
+        let mutated = |object_id, version| ChangedObject {
+            object_id,
+            change: EffectsObjectChange {
+                input_state: ObjectIn::Exist {
+                    version: Version::new(version),
+                    digest: ObjectDigest::ZERO,
+                    owner: Owner::Address(Address::ZERO),
+                },
+                output_state: ObjectOut::ObjectWrite {
+                    digest: ObjectDigest::new([version as u8; 32]),
+                    owner: Owner::Address(Address::ZERO),
+                },
+                id_operation: IdOperation::None,
+            },
+        };
+
+        TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+            status,
+            epoch: EpochId::new(1),
+            gas_used: GasCostSummary::default(),
+            transaction_digest: digest,
+            gas_object_index: Some(1),
+            events_digest: None,
+            dependencies: vec![],
+            lamport_version: Version::new(10),
+            changed_objects: vec![
+                mutated(ObjectId::new([3; 32]), 7),
+                mutated(ObjectId::new([2; 32]), 5),
+            ],
+            unchanged_shared_objects: vec![],
+            auxiliary_data_digest: None,
+        }))
+    }
+
+    struct History {
+        transaction: Transaction,
+        effects: TransactionEffects,
+        replayed_status: ExecutionStatus,
+        requested: Vec<(ObjectId, Version)>,
+    }
+
+    impl ReplaySource for History {
+        type Error = std::convert::Infallible;
+
+        fn transaction(
+            &mut self,
+            _: &TransactionDigest,
+        ) -> Result<(Transaction, TransactionEffects), Self::Error> {
+            Ok((self.transaction.clone(), self.effects.clone()))
+        }
+
+        fn object(
+            &mut self,
+            object_id: &ObjectId,
+            version: Version,
+        ) -> Result<Object, Self::Error> {
+            self.requested.push((*object_id, version));
+            Ok(object(*object_id, version))
+        }
+
+        fn dev_inspect(
+            &mut self,
+            transaction: &Transaction,
+            objects: &[Object],
+        ) -> Result<TransactionEffects, Self::Error> {
+            assert_eq!(transaction, &self.transaction);
+            assert_eq!(objects.len(), self.requested.len());
+            Ok(effects(
+                *self.effects.transaction_digest(),
+                self.replayed_status.clone(),
+            ))
+        }
+    }
+
+    fn history(replayed_status: ExecutionStatus) -> History {
+        let owned = reference(ObjectId::new([1; 32]), 4);
+        let gas = reference(ObjectId::new([2; 32]), 5);
+        let digest = TransactionDigest::new([9; 32]);
+        History {
+            transaction: transaction(owned, gas),
+            effects: effects(digest, ExecutionStatus::Success),
+            replayed_status,
+            requested: vec![],
+        }
+    }
+
+    #[test]
+    fn replay_at_historical_versions() {
+        let mut replayer = Replayer::new(history(ExecutionStatus::Success));
+        let replayed = replayer.replay(&TransactionDigest::new([9; 32])).unwrap();
+
+        // The owned input and the gas coin at the versions they're referenced at, the shared
+        // input at the version recorded in the effects.
+        assert_eq!(
+            replayer.source().requested,
+            [
+                (ObjectId::new([1; 32]), Version::new(4)),
+                (ObjectId::new([2; 32]), Version::new(5)),
+                (ObjectId::new([3; 32]), Version::new(7)),
+            ]
+        );
+        assert_eq!(replayed.objects.len(), 3);
+        assert!(replayed.diff().is_empty());
+        assert_eq!(replayed.diff().to_string(), "no differences");
+    }
+
+    #[test]
+    fn diff_replayed_effects() {
+        let aborted = ExecutionStatus::Failure {
+            error: ExecutionError::InsufficientGas,
+            command: Some(0),
+        };
+        let mut replayer = Replayer::new(history(aborted.clone()));
+        let diff = replayer
+            .replay(&TransactionDigest::new([9; 32]))
+            .unwrap()
+            .diff();
+
+        assert_eq!(
+            diff.status,
+            Some(Difference {
+                recorded: ExecutionStatus::Success,
+                replayed: aborted,
+            })
+        );
+        assert_eq!(diff.gas_used, None);
+        assert!(diff.objects.is_empty());
+    }
+
+    #[test]
+    fn diff_written_objects() {
+        let recorded = effects(TransactionDigest::ZERO, ExecutionStatus::Success);
+        let mut replayed = recorded.clone();
+        let TransactionEffects::V2(effects) = &mut replayed else {
+            unreachable!()
+        };
+        effects.changed_objects.truncate(1);
+        effects.lamport_version = Version::new(11);
+
+        let diff = EffectsDiff::new(&recorded, &replayed);
+        assert_eq!(diff.status, None);
+        assert_eq!(diff.objects.len(), 2);
+        assert_eq!(
+            diff.objects[&ObjectId::new([2; 32])],
+            Difference {
+                recorded: Some(ObjectReference::new(
+                    ObjectId::new([2; 32]),
+                    Version::new(10),
+                    ObjectDigest::new([5; 32])
+                )),
+                replayed: None,
+            }
+        );
+        assert_eq!(
+            diff.objects[&ObjectId::new([3; 32])].replayed,
+            Some(ObjectReference::new(
+                ObjectId::new([3; 32]),
+                Version::new(11),
+                ObjectDigest::new([7; 32])
+            ))
+        );
+    }
+
+    #[test]
+    fn reject_effects_of_another_transaction() {
+        let mut replayer = Replayer::new(history(ExecutionStatus::Success));
+        let error = replayer
+            .replay(&TransactionDigest::new([8; 32]))
+            .unwrap_err();
+        assert!(matches!(error, ReplayError::DigestMismatch { .. }));
+    }
+}
@@ -0,0 +1,67 @@
+//! The version of the BCS schema of the SDK's types, for services to detect at runtime that two
+//! deployed components disagree on the wire format.
+//!
+//! The schema is the `serde-reflection` registry checked in at `src/types/fixtures/format.yaml`,
+//! which the `format` test keeps in sync with the code. [`SCHEMA_VERSION`] is bumped, with an
+//! entry in [`SCHEMA_CHANGELOG`], whenever the registry changes, while [`schema_fingerprint`]
+//! identifies the registry itself, and so also tells apart builds which changed the schema
+//! without bumping the version.
+
+/// The version of the BCS schema.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// The changes made by each version of the schema, oldest first.
+pub const SCHEMA_CHANGELOG: &[(u32, &str)] = &[
+    (
+        1,
+        "First versioned schema, covering transactions, effects, events, checkpoints, objects \
+         and bridge messages",
+    ),
+    (
+        2,
+        "Add the `valid_during` variant of `TransactionExpiration`",
+    ),
+];
+
+/// The `serde-reflection` registry of the schema, as YAML.
+pub const SCHEMA_FORMAT: &str = include_str!("types/fixtures/format.yaml");
+
+/// The Blake2b-256 digest of the canonical form of the registry, its compact JSON serialization,
+/// identifying the exact schema the SDK was built with regardless of how [`SCHEMA_FORMAT`] is
+/// laid out.
+pub fn schema_fingerprint() -> crate::types::Digest {
+    SCHEMA_FINGERPRINT
+}
+
+/// Checked against the registry by the `fingerprint` test of the format, which fails with the
+/// new value when the registry changes.
+const SCHEMA_FINGERPRINT: crate::types::Digest = crate::types::Digest::new([
+    132, 241, 5, 144, 90, 209, 130, 147, 254, 38, 46, 226, 88, 201, 196, 113, 73, 80, 209, 206, 25,
+    138, 83, 234, 63, 66, 53, 200, 97, 170, 200, 129,
+]);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn changelog() {
+        assert!(SCHEMA_CHANGELOG
+            .windows(2)
+            .all(|versions| versions[0].0 < versions[1].0));
+        assert_eq!(SCHEMA_CHANGELOG.last().unwrap().0, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn fingerprint() {
+        // A version has a single fingerprint, so a changed registry needs a new version
+        let expected = match SCHEMA_VERSION {
+            2 => "9wwyfTEtvRpq79ttfR1hLCseB7Q645SVrD7Y13xtofDr",
+            version => panic!("no fingerprint pinned for schema version {version}"),
+        };
+        assert_eq!(schema_fingerprint().to_string(), expected);
+    }
+}
@@ -0,0 +1,310 @@
+//! Session keys, letting a dapp have an ephemeral key sign on behalf of a wallet after the wallet
+//! approved it once.
+//!
+//! The dapp generates a session key, e.g. with [`Secp256k1Signer::generate`], and has the wallet
+//! sign a [`SessionDelegation`] of its public key to the dapp, until an expiry time. The session
+//! key then signs messages with [`SessionKey::sign`], each [`SessionSignature`] carrying the
+//! signed delegation, so that its verifier can check both the delegation and the signature
+//! without asking the wallet.
+//!
+//! The wallet signs the delegation as a personal message, the BCS of the delegation, which a Move
+//! contract can also verify to record the delegation on chain.
+
+use crate::hash::personal_message_signing_digest;
+use crate::offline::OfflineSigner;
+use crate::offline::Secp256k1Signer;
+use crate::types::Address;
+use crate::types::Secp256k1PublicKey;
+use crate::types::Secp256k1Signature;
+use crate::types::SignatureScheme;
+use crate::types::SimpleSignature;
+use crate::types::UserSignature;
+
+/// The delegation of signing for a dapp to a session key.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SessionDelegation {
+    /// The address of the wallet delegating to the session key.
+    pub owner: Address,
+    pub session_key: Secp256k1PublicKey,
+    /// The dapp the session is for, e.g. its origin.
+    pub audience: String,
+    /// Unix timestamp, in milliseconds, from which the delegation is no longer valid.
+    #[serde(with = "crate::_serde::ReadableDisplay")]
+    pub expires_at_ms: u64,
+}
+
+impl SessionDelegation {
+    /// The message the owner signs, as a personal message: the BCS of the delegation.
+    pub fn signing_message(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("bcs serialization cannot fail")
+    }
+
+    /// Whether the delegation is no longer valid at `now_ms`.
+    pub fn is_expired(&self, now_ms: u64) -> bool {
+        now_ms >= self.expires_at_ms
+    }
+}
+
+/// A delegation signed by its owner.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SignedSessionDelegation {
+    pub delegation: SessionDelegation,
+    pub signature: UserSignature,
+}
+
+impl SignedSessionDelegation {
+    /// Delegate signing for `audience` to `session_key` until `expires_at_ms`, on behalf of the
+    /// address of `signer`.
+    pub fn sign<S: OfflineSigner>(
+        signer: &S,
+        session_key: Secp256k1PublicKey,
+        audience: impl Into<String>,
+        expires_at_ms: u64,
+    ) -> Result<Self, S::Error> {
+        let delegation = SessionDelegation {
+            owner: signer.address(),
+            session_key,
+            audience: audience.into(),
+            expires_at_ms,
+        };
+        let digest = personal_message_signing_digest(&delegation.signing_message());
+        Ok(Self {
+            signature: signer.sign_digest(&digest)?,
+            delegation,
+        })
+    }
+
+    /// Verify that the delegation is signed by its owner.
+    ///
+    /// Only owners signing with a single secp256k1 key are supported.
+    pub fn verify(&self) -> Result<(), SessionError> {
+        let UserSignature::Simple(SimpleSignature::Secp256k1 {
+            signature,
+            public_key,
+        }) = &self.signature
+        else {
+            return Err(SessionError::UnsupportedSignature(self.signature.scheme()));
+        };
+        if public_key.to_address() != self.delegation.owner {
+            return Err(SessionError::OwnerMismatch);
+        }
+        let digest = personal_message_signing_digest(&self.delegation.signing_message());
+        verify_secp256k1(public_key, signature, &digest)
+    }
+}
+
+/// A session key holding the delegation of its owner.
+pub struct SessionKey {
+    signer: Secp256k1Signer,
+    delegation: SignedSessionDelegation,
+}
+
+impl SessionKey {
+    /// `None` if `delegation` isn't to the key of `signer`.
+    pub fn new(signer: Secp256k1Signer, delegation: SignedSessionDelegation) -> Option<Self> {
+        (delegation.delegation.session_key == signer.public_key())
+            .then_some(Self { signer, delegation })
+    }
+
+    pub fn delegation(&self) -> &SignedSessionDelegation {
+        &self.delegation
+    }
+
+    /// Sign `message` on behalf of the owner, if the delegation hasn't expired at `now_ms`.
+    pub fn sign(&self, message: &[u8], now_ms: u64) -> Result<SessionSignature, SessionError> {
+        let delegation = &self.delegation.delegation;
+        if delegation.is_expired(now_ms) {
+            return Err(SessionError::Expired {
+                expires_at_ms: delegation.expires_at_ms,
+            });
+        }
+        let digest = session_message_digest(delegation, message);
+        let signature = self.signer.sign_digest_raw(&digest);
+        Ok(SessionSignature {
+            delegation: self.delegation.clone(),
+            signature,
+        })
+    }
+}
+
+/// A signature by a session key, tagged with the delegation it signs under.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SessionSignature {
+    pub delegation: SignedSessionDelegation,
+    pub signature: Secp256k1Signature,
+}
+
+impl SessionSignature {
+    /// Verify that `message` is signed for `audience` at `now_ms` under a delegation signed by its
+    /// owner, returning the address of the owner.
+    pub fn verify(
+        &self,
+        message: &[u8],
+        audience: &str,
+        now_ms: u64,
+    ) -> Result<Address, SessionError> {
+        let delegation = &self.delegation.delegation;
+        if delegation.audience != audience {
+            return Err(SessionError::AudienceMismatch {
+                expected: audience.to_owned(),
+                actual: delegation.audience.clone(),
+            });
+        }
+        if delegation.is_expired(now_ms) {
+            return Err(SessionError::Expired {
+                expires_at_ms: delegation.expires_at_ms,
+            });
+        }
+        self.delegation.verify()?;
+        let digest = session_message_digest(delegation, message);
+        verify_secp256k1(&delegation.session_key, &self.signature, &digest)?;
+        Ok(delegation.owner)
+    }
+}
+
+/// The digest a session key signs for `message`: that of the personal message of the BCS of the
+/// delegation followed by the message.
+fn session_message_digest(delegation: &SessionDelegation, message: &[u8]) -> [u8; 32] {
+    let bytes = bcs::to_bytes(&(delegation, message)).expect("bcs serialization cannot fail");
+    personal_message_signing_digest(&bytes)
+}
+
+/// Verify a secp256k1 signature over the SHA-256 hash of `digest`, as Sui signs them.
+fn verify_secp256k1(
+    public_key: &Secp256k1PublicKey,
+    signature: &Secp256k1Signature,
+    digest: &[u8; 32],
+) -> Result<(), SessionError> {
+    use k256::ecdsa::signature::Verifier;
+
+    let key = k256::ecdsa::VerifyingKey::from_sec1_bytes(public_key.as_bytes())
+        .map_err(|_| SessionError::InvalidSignature)?;
+    let signature = k256::ecdsa::Signature::from_slice(signature.as_bytes())
+        .map_err(|_| SessionError::InvalidSignature)?;
+    key.verify(digest, &signature)
+        .map_err(|_| SessionError::InvalidSignature)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionError {
+    Expired {
+        expires_at_ms: u64,
+    },
+    AudienceMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The key signing the delegation isn't that of its owner.
+    OwnerMismatch,
+    /// The delegation is signed with a scheme which can't be verified.
+    UnsupportedSignature(SignatureScheme),
+    InvalidSignature,
+}
+
+impl std::fmt::Display for SessionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Expired { expires_at_ms } => {
+                write!(f, "session delegation expired at {expires_at_ms}")
+            }
+            Self::AudienceMismatch { expected, actual } => write!(
+                f,
+                "session delegation is for {actual:?}, expected {expected:?}"
+            ),
+            Self::OwnerMismatch => write!(f, "session delegation isn't signed by its owner"),
+            Self::UnsupportedSignature(scheme) => write!(
+                f,
+                "session delegations signed with {} aren't supported",
+                scheme.name()
+            ),
+            Self::InvalidSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Secp256k1PrivateKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const DAPP: &str = "https://dapp.example";
+
+    fn signer(seed: u8) -> Secp256k1Signer {
+        Secp256k1Signer::new(&Secp256k1PrivateKey::new([seed; 32])).unwrap()
+    }
+
+    #[test]
+    fn approve_once_sign_many() {
+        let wallet = signer(1);
+        let session = signer(2);
+        let session_key = session.public_key();
+
+        let delegation = SignedSessionDelegation::sign(&wallet, session_key, DAPP, 10_000).unwrap();
+        assert_eq!(delegation.delegation.owner, wallet.address());
+        delegation.verify().unwrap();
+        assert!(SessionKey::new(signer(3), delegation.clone()).is_none());
+        let session = SessionKey::new(session, delegation).unwrap();
+
+        for message in [&b"swap 1 SUI"[..], b"swap 2 SUI"] {
+            let signature = session.sign(message, 1_000).unwrap();
+            assert_eq!(signature.verify(message, DAPP, 2_000), Ok(wallet.address()));
+            assert_eq!(
+                signature.verify(b"swap 3 SUI", DAPP, 2_000),
+                Err(SessionError::InvalidSignature)
+            );
+            assert_eq!(
+                signature.verify(message, "https://other.example", 2_000),
+                Err(SessionError::AudienceMismatch {
+                    expected: "https://other.example".to_owned(),
+                    actual: DAPP.to_owned(),
+                })
+            );
+            assert_eq!(
+                signature.verify(message, DAPP, 10_000),
+                Err(SessionError::Expired {
+                    expires_at_ms: 10_000
+                })
+            );
+        }
+        assert_eq!(
+            session.sign(b"late", 10_000).unwrap_err(),
+            SessionError::Expired {
+                expires_at_ms: 10_000
+            }
+        );
+
+        let bytes = bcs::to_bytes(&session.sign(b"swap", 0).unwrap()).unwrap();
+        let signature: SessionSignature = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(signature.verify(b"swap", DAPP, 0), Ok(wallet.address()));
+    }
+
+    #[test]
+    fn forged_delegations() {
+        let wallet = signer(1);
+        let session_key = signer(2).public_key();
+        let delegation = SignedSessionDelegation::sign(&wallet, session_key, DAPP, 10_000).unwrap();
+
+        let mut extended = delegation.clone();
+        extended.delegation.expires_at_ms = u64::MAX;
+        assert_eq!(extended.verify(), Err(SessionError::InvalidSignature));
+
+        // Signed by another key than the owner's
+        let mut impersonated =
+            SignedSessionDelegation::sign(&signer(3), session_key, DAPP, 10_000).unwrap();
+        impersonated.delegation.owner = wallet.address();
+        assert_eq!(impersonated.verify(), Err(SessionError::OwnerMismatch));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn generated_session_key() {
+        let session = Secp256k1Signer::generate(rand_core::OsRng);
+        let restored = Secp256k1Signer::new(&session.private_key()).unwrap();
+        assert_eq!(restored.public_key(), session.public_key());
+    }
+}
@@ -0,0 +1,497 @@
+//! A reader of the formal snapshots of Sui, the live objects of a network at the end of an epoch,
+//! for bootstrapping state without replaying transactions from genesis.
+//!
+//! Snapshots are stored alongside archives, in a directory per epoch:
+//!
+//! ```text
+//! epoch_42/MANIFEST
+//! epoch_42/1_1.obj
+//! epoch_42/1_1.ref
+//! epoch_42/1_2.obj
+//! epoch_42/1_2.ref
+//! ...
+//! ```
+//!
+//! The live objects are split into buckets, and each bucket into partitions. The `.obj` file of a
+//! partition holds its objects, and the `.ref` file their references, in the same order. The
+//! [`SnapshotManifest`] records the SHA3-256 checksum of each file. A [`SnapshotReader`] fetches
+//! files through an [`ArchiveStore`], checks them against the manifest, and each object against
+//! its reference.
+//!
+//! Files compressed with zstd are only read with the `archive-zstd` feature.
+
+use crate::archive::check_magic;
+use crate::archive::decompress;
+use crate::archive::read_all_blobs;
+use crate::archive::sha3_256;
+use crate::archive::ArchiveError;
+use crate::archive::ArchiveFormatError;
+use crate::archive::ArchiveStore;
+use crate::types::EpochId;
+use crate::types::Object;
+use crate::types::ObjectDigest;
+use crate::types::ObjectId;
+use crate::types::ObjectReference;
+use crate::types::Version;
+
+/// The magic number starting object files, once decompressed.
+pub const OBJECT_FILE_MAGIC: u32 = 0x00B7EC75;
+/// The magic number starting reference files, once decompressed.
+pub const REFERENCE_FILE_MAGIC: u32 = 0xDEADBEEF;
+/// The magic number starting the manifest.
+pub const MANIFEST_FILE_MAGIC: u32 = 0x00C0FFEE;
+
+/// The length of an object reference in a reference file: the object id, the version as a big
+/// endian `u64`, and the digest.
+const REFERENCE_LENGTH: usize = ObjectId::LENGTH + 8 + ObjectDigest::LENGTH;
+
+/// The digest standing for the contents of wrapped objects.
+pub(crate) const WRAPPED_OBJECT_DIGEST: ObjectDigest =
+    ObjectDigest::new([88; ObjectDigest::LENGTH]);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum FileType {
+    Object,
+    Reference,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum FileCompression {
+    None,
+    Zstd,
+}
+
+/// A file of a snapshot, as recorded in its manifest.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct FileMetadata {
+    pub file_type: FileType,
+    pub bucket_num: u32,
+    pub part_num: u32,
+    pub file_compression: FileCompression,
+    /// The SHA3-256 checksum of the file, as stored.
+    pub sha3_digest: [u8; 32],
+}
+
+impl FileMetadata {
+    /// The path of the file, relative to the directory of its epoch.
+    pub fn file_name(&self) -> String {
+        let suffix = match self.file_type {
+            FileType::Object => "obj",
+            FileType::Reference => "ref",
+        };
+        format!("{}_{}.{suffix}", self.bucket_num, self.part_num)
+    }
+}
+
+/// The index of the files of a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum SnapshotManifest {
+    V1(SnapshotManifestV1),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct SnapshotManifestV1 {
+    pub snapshot_version: u8,
+    pub address_length: u64,
+    pub file_metadata: Vec<FileMetadata>,
+    /// The epoch at the end of which the snapshot was taken.
+    pub epoch: EpochId,
+}
+
+impl SnapshotManifest {
+    /// Parse the manifest file of a snapshot, checking its magic number and checksum.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ArchiveFormatError> {
+        let contents = check_magic(bytes, MANIFEST_FILE_MAGIC)?;
+        if contents.len() < 32 {
+            return Err(ArchiveFormatError::Truncated);
+        }
+        let (signed, checksum) = bytes.split_at(bytes.len() - 32);
+        if sha3_256(signed) != checksum {
+            return Err(ArchiveFormatError::ChecksumMismatch {
+                path: "MANIFEST".to_owned(),
+            });
+        }
+
+        let manifest: Self =
+            bcs::from_bytes(&contents[..contents.len() - 32]).map_err(ArchiveFormatError::Bcs)?;
+        if manifest.address_length() != ObjectId::LENGTH as u64 {
+            return Err(ArchiveFormatError::InvalidManifest(
+                "unsupported address length",
+            ));
+        }
+        Ok(manifest)
+    }
+
+    pub fn files(&self) -> &[FileMetadata] {
+        match self {
+            Self::V1(manifest) => &manifest.file_metadata,
+        }
+    }
+
+    pub fn epoch(&self) -> EpochId {
+        match self {
+            Self::V1(manifest) => manifest.epoch,
+        }
+    }
+
+    pub fn address_length(&self) -> u64 {
+        match self {
+            Self::V1(manifest) => manifest.address_length,
+        }
+    }
+
+    /// The partitions of the snapshot, as their bucket and partition numbers, in order.
+    pub fn partitions(&self) -> Vec<(u32, u32)> {
+        let mut partitions: Vec<_> = self
+            .files()
+            .iter()
+            .filter(|file| file.file_type == FileType::Object)
+            .map(|file| (file.bucket_num, file.part_num))
+            .collect();
+        partitions.sort();
+        partitions
+    }
+
+    fn file(&self, file_type: FileType, bucket_num: u32, part_num: u32) -> Option<&FileMetadata> {
+        self.files().iter().find(|file| {
+            file.file_type == file_type
+                && file.bucket_num == bucket_num
+                && file.part_num == part_num
+        })
+    }
+}
+
+/// An object live at the end of the epoch of a snapshot.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub enum LiveObject {
+    Normal(Object),
+    /// An object wrapped in another one, whose contents are part of the wrapping object.
+    Wrapped {
+        object_id: ObjectId,
+        version: Version,
+    },
+}
+
+impl LiveObject {
+    pub fn object_id(&self) -> ObjectId {
+        match self {
+            Self::Normal(object) => object.object_id(),
+            Self::Wrapped { object_id, .. } => *object_id,
+        }
+    }
+
+    pub fn version(&self) -> Version {
+        match self {
+            Self::Normal(object) => object.version(),
+            Self::Wrapped { version, .. } => *version,
+        }
+    }
+
+    /// The reference of the object, whose digest is a placeholder for wrapped objects.
+    pub fn reference(&self) -> ObjectReference {
+        let digest = match self {
+            Self::Normal(object) => object.digest(),
+            Self::Wrapped { .. } => WRAPPED_OBJECT_DIGEST,
+        };
+        ObjectReference::new(self.object_id(), self.version(), digest)
+    }
+
+    /// The object, unless it is wrapped.
+    pub fn into_object(self) -> Option<Object> {
+        match self {
+            Self::Normal(object) => Some(object),
+            Self::Wrapped { .. } => None,
+        }
+    }
+}
+
+/// Reads the snapshot of an epoch through an [`ArchiveStore`].
+pub struct SnapshotReader<S> {
+    store: S,
+    manifest: SnapshotManifest,
+}
+
+impl<S: ArchiveStore> SnapshotReader<S> {
+    /// Read the snapshot taken at the end of `epoch`, fetching its manifest.
+    pub fn new(mut store: S, epoch: EpochId) -> Result<Self, ArchiveError<S::Error>> {
+        let bytes = store
+            .get(&format!("epoch_{epoch}/MANIFEST"))
+            .map_err(ArchiveError::Store)?;
+        let manifest = SnapshotManifest::from_bytes(&bytes)?;
+        if manifest.epoch() != epoch {
+            return Err(ArchiveFormatError::InvalidManifest("snapshot of another epoch").into());
+        }
+        Ok(Self { store, manifest })
+    }
+
+    pub fn manifest(&self) -> &SnapshotManifest {
+        &self.manifest
+    }
+
+    pub fn into_inner(self) -> S {
+        self.store
+    }
+
+    /// Fetch the references of the objects of a partition.
+    pub fn read_references(
+        &mut self,
+        bucket_num: u32,
+        part_num: u32,
+    ) -> Result<Vec<ObjectReference>, ArchiveError<S::Error>> {
+        let bytes = self.get(FileType::Reference, bucket_num, part_num)?;
+        let references = check_magic(&bytes, REFERENCE_FILE_MAGIC)?;
+        let chunks = references.chunks_exact(REFERENCE_LENGTH);
+        if !chunks.remainder().is_empty() {
+            return Err(ArchiveFormatError::Truncated.into());
+        }
+        Ok(chunks
+            .map(|reference| {
+                let (object_id, rest) = reference.split_at(ObjectId::LENGTH);
+                let (version, digest) = rest.split_at(8);
+                ObjectReference::new(
+                    ObjectId::new(object_id.try_into().unwrap()),
+                    Version::new(u64::from_be_bytes(version.try_into().unwrap())),
+                    ObjectDigest::new(digest.try_into().unwrap()),
+                )
+            })
+            .collect())
+    }
+
+    /// Fetch the objects of a partition, checking them against their references.
+    pub fn read_partition(
+        &mut self,
+        bucket_num: u32,
+        part_num: u32,
+    ) -> Result<Vec<LiveObject>, ArchiveError<S::Error>> {
+        let references = self.read_references(bucket_num, part_num)?;
+        let bytes = self.get(FileType::Object, bucket_num, part_num)?;
+        let objects: Vec<LiveObject> = read_all_blobs(check_magic(&bytes, OBJECT_FILE_MAGIC)?)?;
+
+        if objects.len() != references.len() {
+            return Err(ArchiveFormatError::InvalidFile {
+                path: format!("{bucket_num}_{part_num}.obj"),
+            }
+            .into());
+        }
+        for (object, expected) in objects.iter().zip(references) {
+            let actual = object.reference();
+            if actual != expected {
+                return Err(ArchiveError::ReferenceMismatch { expected, actual });
+            }
+        }
+        Ok(objects)
+    }
+
+    /// The live objects of the snapshot, fetched a partition at a time.
+    pub fn objects(&mut self) -> LiveObjects<'_, S> {
+        let mut partitions = self.manifest.partitions();
+        partitions.reverse();
+        LiveObjects {
+            reader: self,
+            partitions,
+            objects: Vec::new().into_iter(),
+        }
+    }
+
+    /// Fetch a file of the snapshot, checking it against its checksum, and decompress it.
+    fn get(
+        &mut self,
+        file_type: FileType,
+        bucket_num: u32,
+        part_num: u32,
+    ) -> Result<Vec<u8>, ArchiveError<S::Error>> {
+        let file = self
+            .manifest
+            .file(file_type, bucket_num, part_num)
+            .ok_or(ArchiveFormatError::InvalidManifest("missing file"))?;
+        let path = format!("epoch_{}/{}", self.manifest.epoch(), file.file_name());
+        let bytes = self.store.get(&path).map_err(ArchiveError::Store)?;
+        if sha3_256(&bytes) != file.sha3_digest {
+            return Err(ArchiveFormatError::ChecksumMismatch { path }.into());
+        }
+        let compression = match file.file_compression {
+            FileCompression::None => 0,
+            FileCompression::Zstd => 1,
+        };
+        Ok(decompress(compression, &bytes)?.into_owned())
+    }
+}
+
+/// The live objects of a snapshot, see [`SnapshotReader::objects`].
+///
+/// The iterator stops after the first error.
+pub struct LiveObjects<'a, S> {
+    reader: &'a mut SnapshotReader<S>,
+    /// The partitions left to read, the next one last.
+    partitions: Vec<(u32, u32)>,
+    objects: std::vec::IntoIter<LiveObject>,
+}
+
+impl<S: ArchiveStore> Iterator for LiveObjects<'_, S> {
+    type Item = Result<LiveObject, ArchiveError<S::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(object) = self.objects.next() {
+                return Some(Ok(object));
+            }
+            let (bucket_num, part_num) = self.partitions.pop()?;
+            match self.reader.read_partition(bucket_num, part_num) {
+                Ok(objects) => self.objects = objects.into_iter(),
+                Err(e) => {
+                    self.partitions.clear();
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Owner;
+    use crate::types::TransactionDigest;
+    use std::collections::BTreeMap;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    /// A package object, the simplest object to build outside of `types`.
+    fn object(i: u8) -> LiveObject {
+        let bytes = bcs::to_bytes(&(
+            1u8,
+            ObjectId::new([i; 32]),
+            Version::new(i.into()),
+            [0u8; 3],
+            Owner::Immutable,
+            TransactionDigest::ZERO,
+            0u64,
+        ))
+        .unwrap();
+        LiveObject::Normal(bcs::from_bytes(&bytes).unwrap())
+    }
+
+    /// A snapshot of `partitions` of objects in epoch 3, listing the references of `references`
+    /// in place of those of the objects when given.
+    fn snapshot(
+        partitions: &[(u32, u32, Vec<LiveObject>)],
+        references: Option<Vec<ObjectReference>>,
+    ) -> BTreeMap<String, Vec<u8>> {
+        let mut snapshot = BTreeMap::new();
+        let mut file_metadata = vec![];
+        for (bucket_num, part_num, objects) in partitions {
+            let mut obj = OBJECT_FILE_MAGIC.to_be_bytes().to_vec();
+            let mut refs = REFERENCE_FILE_MAGIC.to_be_bytes().to_vec();
+            for object in objects {
+                let data = bcs::to_bytes(object).unwrap();
+                assert!(data.len() < 0x80);
+                obj.extend([data.len() as u8, 1]);
+                obj.extend(data);
+            }
+            let references = references
+                .clone()
+                .unwrap_or_else(|| objects.iter().map(LiveObject::reference).collect());
+            for reference in references {
+                refs.extend(reference.object_id().as_ref());
+                refs.extend(reference.version().into_inner().to_be_bytes());
+                refs.extend(reference.digest().inner());
+            }
+
+            for (file_type, bytes) in [(FileType::Object, obj), (FileType::Reference, refs)] {
+                let metadata = FileMetadata {
+                    file_type,
+                    bucket_num: *bucket_num,
+                    part_num: *part_num,
+                    file_compression: FileCompression::None,
+                    sha3_digest: sha3_256(&bytes),
+                };
+                snapshot.insert(format!("epoch_3/{}", metadata.file_name()), bytes);
+                file_metadata.push(metadata);
+            }
+        }
+
+        let manifest = SnapshotManifest::V1(SnapshotManifestV1 {
+            snapshot_version: 1,
+            address_length: 32,
+            file_metadata,
+            epoch: EpochId::new(3),
+        });
+        let mut bytes = MANIFEST_FILE_MAGIC.to_be_bytes().to_vec();
+        bytes.extend(bcs::to_bytes(&manifest).unwrap());
+        bytes.extend(sha3_256(&bytes));
+        snapshot.insert("epoch_3/MANIFEST".to_owned(), bytes);
+        snapshot
+    }
+
+    struct Bucket(BTreeMap<String, Vec<u8>>);
+
+    impl ArchiveStore for Bucket {
+        type Error = String;
+
+        fn get(&mut self, path: &str) -> Result<Vec<u8>, Self::Error> {
+            self.0.get(path).cloned().ok_or_else(|| path.to_owned())
+        }
+    }
+
+    #[test]
+    fn read_live_objects() {
+        let wrapped = LiveObject::Wrapped {
+            object_id: ObjectId::new([9; 32]),
+            version: Version::new(9),
+        };
+        let partitions = [
+            (2, 1, vec![object(3)]),
+            (1, 1, vec![object(1), wrapped.clone()]),
+            (1, 2, vec![object(2)]),
+        ];
+        let mut reader =
+            SnapshotReader::new(Bucket(snapshot(&partitions, None)), EpochId::new(3)).unwrap();
+        assert_eq!(reader.manifest().partitions(), [(1, 1), (1, 2), (2, 1)]);
+        assert_eq!(
+            reader.read_references(1, 1).unwrap()[1],
+            ObjectReference::new(
+                ObjectId::new([9; 32]),
+                Version::new(9),
+                ObjectDigest::new([88; 32])
+            )
+        );
+
+        let objects: Vec<_> = reader.objects().map(Result::unwrap).collect();
+        assert_eq!(objects, [object(1), wrapped, object(2), object(3)]);
+        assert_eq!(
+            objects[0].clone().into_object().unwrap().version(),
+            Version::new(1)
+        );
+
+        assert!(matches!(
+            SnapshotReader::new(Bucket(snapshot(&partitions, None)), EpochId::new(4)),
+            Err(ArchiveError::Store(path)) if path == "epoch_4/MANIFEST"
+        ));
+    }
+
+    #[test]
+    fn objects_match_references() {
+        let partitions = [(1, 1, vec![object(1), object(2)])];
+        let references = vec![object(1).reference(), object(3).reference()];
+        let mut reader = SnapshotReader::new(
+            Bucket(snapshot(&partitions, Some(references))),
+            EpochId::new(3),
+        )
+        .unwrap();
+        let results: Vec<_> = reader.objects().collect();
+        assert!(matches!(
+            &results[..],
+            [Err(ArchiveError::ReferenceMismatch { actual, .. })] if *actual == object(2).reference()
+        ));
+
+        let mut files = snapshot(&partitions, None);
+        files.get_mut("epoch_3/1_1.obj").unwrap()[6] ^= 1;
+        let mut reader = SnapshotReader::new(Bucket(files), EpochId::new(3)).unwrap();
+        assert!(matches!(
+            reader.read_partition(1, 1),
+            Err(ArchiveError::Format(ArchiveFormatError::ChecksumMismatch { path }))
+                if path == "epoch_3/1_1.obj"
+        ));
+    }
+}
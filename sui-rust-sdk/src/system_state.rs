@@ -0,0 +1,579 @@
+//! The state of the Sui system, held by the `0x5` shared object: the validator set and their
+//! staking pools, the storage fund, the stake subsidy and the parameters of the network.
+//!
+//! The `0x3::sui_system::SuiSystemState` object is only a wrapper, recording the version of the
+//! inner state which it holds in a dynamic field keyed by that version, so that the layout of the
+//! state can change in upgrades of the system package. [`SystemStateWrapper`] reads the wrapper
+//! and, with the `hash` feature, derives the id of the field to fetch, and [`SystemState`] reads
+//! the field for every version of the inner state, `SuiSystemStateInner` and
+//! `SuiSystemStateInnerV2`, into a single representation.
+//!
+//! Tables and bags are decoded as a [`Table`], the id of the object whose dynamic fields hold
+//! their entries, e.g. the exchange rates of a staking pool, and the number of entries.
+//!
+//! The staking math of the system package is reproduced on top of the state, to compute the
+//! [`voting_power`] of validators from their stake, the [`ValidatorReward`]s of an epoch, and the
+//! yield of staking pools from their [`PoolTokenExchangeRate`]s.
+
+use crate::types::Address;
+use crate::types::EpochId;
+use crate::types::Object;
+use crate::types::ObjectData;
+use crate::types::ObjectId;
+use crate::types::StructTag;
+use crate::types::TypeTag;
+
+mod staking;
+pub use staking::voting_power;
+pub use staking::EpochRewards;
+pub use staking::PoolTokenExchangeRate;
+pub use staking::ValidatorReward;
+pub use staking::BASIS_POINT_DENOMINATOR;
+pub use staking::MAX_VOTING_POWER;
+pub use staking::QUORUM_THRESHOLD;
+pub use staking::TOTAL_VOTING_POWER;
+
+/// The `0x3::sui_system::SuiSystemState` shared object, the wrapper of the inner state.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct SystemStateWrapper {
+    pub id: ObjectId,
+    /// The version of the inner state, which keys the dynamic field holding it.
+    pub version: u64,
+}
+
+impl SystemStateWrapper {
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let type_ = &move_struct.type_;
+        let is_wrapper = type_.address == Address::THREE
+            && type_.module == *"sui_system"
+            && type_.name == *"SuiSystemState"
+            && type_.type_params.is_empty();
+        if !is_wrapper {
+            return None;
+        }
+        bcs::from_bytes(&move_struct.contents).ok()
+    }
+
+    /// The id of the dynamic field holding the inner state.
+    #[cfg(feature = "hash")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "hash")))]
+    pub fn inner_id(&self) -> ObjectId {
+        self.id
+            .derive_dynamic_child_id(&TypeTag::U64, &self.version.to_le_bytes())
+    }
+}
+
+/// A `0x2::table::Table`, `0x2::bag::Bag` or `0x2::table_vec::TableVec`, whose entries are
+/// dynamic fields of the object `id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct Table {
+    pub id: ObjectId,
+    pub size: u64,
+}
+
+/// The inner state of the Sui system, of any version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemState {
+    pub epoch: EpochId,
+    pub protocol_version: u64,
+    /// The version of the inner state, e.g. 2 for `SuiSystemStateInnerV2`.
+    pub system_state_version: u64,
+    pub validators: ValidatorSet,
+    pub storage_fund: StorageFund,
+    pub parameters: SystemParameters,
+    pub reference_gas_price: u64,
+    /// The validators reported by each validator, to withhold their rewards.
+    pub validator_report_records: Vec<(Address, Vec<Address>)>,
+    pub stake_subsidy: StakeSubsidy,
+    /// Whether the last epoch change failed, in which case the network runs on the parameters of
+    /// the previous epoch and accumulates rewards and rebates until an epoch change succeeds.
+    pub safe_mode: bool,
+    pub safe_mode_storage_rewards: u64,
+    pub safe_mode_computation_rewards: u64,
+    pub safe_mode_storage_rebates: u64,
+    pub safe_mode_non_refundable_storage_fee: u64,
+    pub epoch_start_timestamp_ms: u64,
+    pub extra_fields: Table,
+}
+
+/// The parameters of the network, which only change in upgrades of the system package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SystemParameters {
+    pub epoch_duration_ms: u64,
+    /// The epoch from which stake subsidies are paid.
+    pub stake_subsidy_start_epoch: EpochId,
+    /// The minimum number of active validators, since the second version of the state.
+    pub min_validator_count: Option<u64>,
+    pub max_validator_count: u64,
+    pub min_validator_joining_stake: u64,
+    /// The stake under which a validator is removed from the set after
+    /// `validator_low_stake_grace_period` epochs.
+    pub validator_low_stake_threshold: u64,
+    /// The stake under which a validator is removed from the set at the next epoch change.
+    pub validator_very_low_stake_threshold: u64,
+    pub validator_low_stake_grace_period: u64,
+    pub extra_fields: Table,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct ValidatorSet {
+    /// The stake of the active validators, in MIST.
+    pub total_stake: u64,
+    pub active_validators: Vec<Validator>,
+    /// The validators joining the set at the next epoch change.
+    pub pending_active_validators: Table,
+    /// The indices in `active_validators` of the validators leaving the set at the next epoch
+    /// change.
+    pub pending_removals: Vec<u64>,
+    /// The address of the validator of each staking pool, by pool id.
+    pub staking_pool_mappings: Table,
+    pub inactive_validators: Table,
+    pub validator_candidates: Table,
+    /// The number of epochs each validator under the low stake threshold has been so.
+    pub at_risk_validators: Vec<(Address, u64)>,
+    pub extra_fields: Table,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct Validator {
+    pub metadata: ValidatorMetadata,
+    /// The voting power of the validator in the committee of the epoch, out of 10,000.
+    pub voting_power: u64,
+    pub operation_cap_id: ObjectId,
+    /// The gas price the validator voted for in this epoch.
+    pub gas_price: u64,
+    pub staking_pool: StakingPool,
+    /// The commission the validator takes from staking rewards, in basis points.
+    pub commission_rate: u64,
+    pub next_epoch_stake: u64,
+    pub next_epoch_gas_price: u64,
+    pub next_epoch_commission_rate: u64,
+    pub extra_fields: Table,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct ValidatorMetadata {
+    pub sui_address: Address,
+    /// The BLS12-381 key the validator signs checkpoints with.
+    pub protocol_pubkey_bytes: Vec<u8>,
+    pub network_pubkey_bytes: Vec<u8>,
+    pub worker_pubkey_bytes: Vec<u8>,
+    pub proof_of_possession_bytes: Vec<u8>,
+    pub name: String,
+    pub description: String,
+    pub image_url: String,
+    pub project_url: String,
+    pub net_address: String,
+    pub p2p_address: String,
+    pub primary_address: String,
+    pub worker_address: String,
+    pub next_epoch_protocol_pubkey_bytes: Option<Vec<u8>>,
+    pub next_epoch_proof_of_possession: Option<Vec<u8>>,
+    pub next_epoch_network_pubkey_bytes: Option<Vec<u8>>,
+    pub next_epoch_worker_pubkey_bytes: Option<Vec<u8>>,
+    pub next_epoch_net_address: Option<String>,
+    pub next_epoch_p2p_address: Option<String>,
+    pub next_epoch_primary_address: Option<String>,
+    pub next_epoch_worker_address: Option<String>,
+    pub extra_fields: Table,
+}
+
+/// The pool of the SUI staked with a validator, which issues pool tokens to stakers at the
+/// exchange rate of each epoch.
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct StakingPool {
+    pub id: ObjectId,
+    /// The epoch the pool became active at, or `None` while its validator is a candidate.
+    pub activation_epoch: Option<EpochId>,
+    /// The epoch the pool was deactivated at, when its validator left the set.
+    pub deactivation_epoch: Option<EpochId>,
+    /// The SUI in the pool, stake and rewards, in MIST.
+    pub sui_balance: u64,
+    pub rewards_pool: u64,
+    pub pool_token_balance: u64,
+    /// The exchange rate of each epoch, by epoch.
+    pub exchange_rates: Table,
+    pub pending_stake: u64,
+    pub pending_total_sui_withdraw: u64,
+    pub pending_pool_token_withdraw: u64,
+    pub extra_fields: Table,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct StorageFund {
+    /// The storage fees paid for the objects currently stored, to be rebated when deleted.
+    pub total_object_storage_rebates: u64,
+    pub non_refundable_balance: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct StakeSubsidy {
+    /// The SUI left to distribute, in MIST.
+    pub balance: u64,
+    pub distribution_counter: u64,
+    /// The subsidy of each epoch, until it decays at the end of the period.
+    pub current_distribution_amount: u64,
+    pub stake_subsidy_period_length: u64,
+    /// The rate the distribution amount decays by at the end of each period, in basis points.
+    pub stake_subsidy_decrease_rate: u16,
+    pub extra_fields: Table,
+}
+
+/// The BCS layout of the inner state, whose versions only differ by their parameters.
+#[derive(serde_derive::Deserialize)]
+struct BinarySystemState<P> {
+    epoch: EpochId,
+    protocol_version: u64,
+    system_state_version: u64,
+    validators: ValidatorSet,
+    storage_fund: StorageFund,
+    parameters: P,
+    reference_gas_price: u64,
+    validator_report_records: Vec<(Address, Vec<Address>)>,
+    stake_subsidy: StakeSubsidy,
+    safe_mode: bool,
+    safe_mode_storage_rewards: u64,
+    safe_mode_computation_rewards: u64,
+    safe_mode_storage_rebates: u64,
+    safe_mode_non_refundable_storage_fee: u64,
+    epoch_start_timestamp_ms: u64,
+    extra_fields: Table,
+}
+
+/// The BCS layout of `SystemParameters`, of the first version of the state.
+#[derive(serde_derive::Deserialize)]
+struct SystemParametersV1 {
+    epoch_duration_ms: u64,
+    stake_subsidy_start_epoch: EpochId,
+    max_validator_count: u64,
+    min_validator_joining_stake: u64,
+    validator_low_stake_threshold: u64,
+    validator_very_low_stake_threshold: u64,
+    validator_low_stake_grace_period: u64,
+    extra_fields: Table,
+}
+
+/// The BCS layout of `SystemParametersV2`, which adds the minimum validator count.
+#[derive(serde_derive::Deserialize)]
+struct SystemParametersV2 {
+    epoch_duration_ms: u64,
+    stake_subsidy_start_epoch: EpochId,
+    min_validator_count: u64,
+    max_validator_count: u64,
+    min_validator_joining_stake: u64,
+    validator_low_stake_threshold: u64,
+    validator_very_low_stake_threshold: u64,
+    validator_low_stake_grace_period: u64,
+    extra_fields: Table,
+}
+
+impl From<SystemParametersV1> for SystemParameters {
+    fn from(parameters: SystemParametersV1) -> Self {
+        Self {
+            epoch_duration_ms: parameters.epoch_duration_ms,
+            stake_subsidy_start_epoch: parameters.stake_subsidy_start_epoch,
+            min_validator_count: None,
+            max_validator_count: parameters.max_validator_count,
+            min_validator_joining_stake: parameters.min_validator_joining_stake,
+            validator_low_stake_threshold: parameters.validator_low_stake_threshold,
+            validator_very_low_stake_threshold: parameters.validator_very_low_stake_threshold,
+            validator_low_stake_grace_period: parameters.validator_low_stake_grace_period,
+            extra_fields: parameters.extra_fields,
+        }
+    }
+}
+
+impl From<SystemParametersV2> for SystemParameters {
+    fn from(parameters: SystemParametersV2) -> Self {
+        Self {
+            epoch_duration_ms: parameters.epoch_duration_ms,
+            stake_subsidy_start_epoch: parameters.stake_subsidy_start_epoch,
+            min_validator_count: Some(parameters.min_validator_count),
+            max_validator_count: parameters.max_validator_count,
+            min_validator_joining_stake: parameters.min_validator_joining_stake,
+            validator_low_stake_threshold: parameters.validator_low_stake_threshold,
+            validator_very_low_stake_threshold: parameters.validator_very_low_stake_threshold,
+            validator_low_stake_grace_period: parameters.validator_low_stake_grace_period,
+            extra_fields: parameters.extra_fields,
+        }
+    }
+}
+
+impl<P: Into<SystemParameters>> From<BinarySystemState<P>> for SystemState {
+    fn from(state: BinarySystemState<P>) -> Self {
+        Self {
+            epoch: state.epoch,
+            protocol_version: state.protocol_version,
+            system_state_version: state.system_state_version,
+            validators: state.validators,
+            storage_fund: state.storage_fund,
+            parameters: state.parameters.into(),
+            reference_gas_price: state.reference_gas_price,
+            validator_report_records: state.validator_report_records,
+            stake_subsidy: state.stake_subsidy,
+            safe_mode: state.safe_mode,
+            safe_mode_storage_rewards: state.safe_mode_storage_rewards,
+            safe_mode_computation_rewards: state.safe_mode_computation_rewards,
+            safe_mode_storage_rebates: state.safe_mode_storage_rebates,
+            safe_mode_non_refundable_storage_fee: state.safe_mode_non_refundable_storage_fee,
+            epoch_start_timestamp_ms: state.epoch_start_timestamp_ms,
+            extra_fields: state.extra_fields,
+        }
+    }
+}
+
+impl SystemState {
+    /// Read the inner state from the dynamic field holding it, of type
+    /// `0x2::dynamic_field::Field<u64, 0x3::sui_system_state_inner::SuiSystemStateInner>` or
+    /// `SuiSystemStateInnerV2`.
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field<T> {
+            _id: ObjectId,
+            _name: u64,
+            value: T,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let type_ = &move_struct.type_;
+        let is_field = type_.address == Address::TWO
+            && type_.module == *"dynamic_field"
+            && type_.name == *"Field";
+        let inner = match type_.type_params.as_slice() {
+            [TypeTag::U64, TypeTag::Struct(inner)] if is_field && is_inner_type(inner) => inner,
+            _ => return None,
+        };
+        let contents = &move_struct.contents;
+        match inner.name.as_str() {
+            "SuiSystemStateInner" => {
+                bcs::from_bytes::<Field<BinarySystemState<SystemParametersV1>>>(contents)
+                    .ok()
+                    .map(|field| field.value.into())
+            }
+            "SuiSystemStateInnerV2" => {
+                bcs::from_bytes::<Field<BinarySystemState<SystemParametersV2>>>(contents)
+                    .ok()
+                    .map(|field| field.value.into())
+            }
+            _ => None,
+        }
+    }
+
+    /// The active validator with the address `address`.
+    pub fn validator(&self, address: &Address) -> Option<&Validator> {
+        self.validators
+            .active_validators
+            .iter()
+            .find(|validator| validator.metadata.sui_address == *address)
+    }
+
+    /// The active validator whose staking pool is `pool_id`.
+    pub fn validator_by_pool(&self, pool_id: &ObjectId) -> Option<&Validator> {
+        self.validators
+            .active_validators
+            .iter()
+            .find(|validator| validator.staking_pool.id == *pool_id)
+    }
+
+    /// Whether the validator `address` is leaving the set at the next epoch change.
+    pub fn is_pending_removal(&self, address: &Address) -> bool {
+        self.validators
+            .active_validators
+            .iter()
+            .position(|validator| validator.metadata.sui_address == *address)
+            .is_some_and(|index| self.validators.pending_removals.contains(&(index as u64)))
+    }
+}
+
+fn is_inner_type(type_: &StructTag) -> bool {
+    type_.address == Address::THREE
+        && type_.module == *"sui_system_state_inner"
+        && type_.type_params.is_empty()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::Owner;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const VALIDATOR: Address = Address::from_static_hex("0xa1");
+
+    pub(super) fn object(type_: &str, contents: Vec<u8>) -> Object {
+        Object::new_move_object(
+            type_.parse().unwrap(),
+            Version::new(1),
+            contents,
+            Owner::Object(ObjectId::ZERO),
+        )
+    }
+
+    /// The BCS of a `SuiSystemStateInner` field with one validator, with the minimum validator
+    /// count of the parameters of the second version if there is one.
+    pub(super) fn inner_state(min_validator_count: Option<u64>) -> Vec<u8> {
+        let table = (ObjectId::ZERO, 0u64);
+        let none = None::<Vec<u8>>;
+        let metadata = (
+            (
+                VALIDATOR,
+                vec![1u8; 96],
+                vec![2u8; 32],
+                vec![3u8; 32],
+                vec![4u8; 48],
+                "validator".to_owned(),
+                String::new(),
+                String::new(),
+                String::new(),
+                "/dns/validator/tcp/8080/http".to_owned(),
+                String::new(),
+                String::new(),
+                String::new(),
+            ),
+            (none.clone(), none.clone(), none.clone(), none),
+            (
+                None::<String>,
+                None::<String>,
+                None::<String>,
+                None::<String>,
+            ),
+            table,
+        );
+        let staking_pool = (
+            ObjectId::new([7; 32]),
+            Some(0u64),
+            None::<u64>,
+            1_100u64,
+            100u64,
+            1_000u64,
+            table,
+            0u64,
+            0u64,
+            0u64,
+            table,
+        );
+        let validator = (
+            metadata,
+            10_000u64,
+            ObjectId::new([8; 32]),
+            1_000u64,
+            staking_pool,
+            200u64,
+            1_100u64,
+            1_000u64,
+            200u64,
+            table,
+        );
+        let validators = (
+            1_100u64,
+            vec![validator],
+            table,
+            vec![0u64],
+            table,
+            table,
+            table,
+            Vec::<(Address, u64)>::new(),
+            table,
+        );
+        let mut parameters = bcs::to_bytes(&(86_400_000u64, 0u64)).unwrap();
+        if let Some(min_validator_count) = min_validator_count {
+            parameters.extend(min_validator_count.to_le_bytes());
+        }
+        parameters.extend(
+            bcs::to_bytes(&(
+                150u64,
+                30_000_000u64,
+                20_000_000u64,
+                15_000_000u64,
+                7u64,
+                table,
+            ))
+            .unwrap(),
+        );
+        let stake_subsidy = (1_000u64, 42u64, 100u64, 30u64, 1_000u16, table);
+
+        let mut contents =
+            bcs::to_bytes(&(ObjectId::new([5; 32]), 2u64, 42u64, 70u64, 2u64)).unwrap();
+        contents.extend(bcs::to_bytes(&(validators, (5u64, 6u64))).unwrap());
+        contents.extend(parameters);
+        contents.extend(
+            bcs::to_bytes(&(
+                750u64,
+                vec![(VALIDATOR, vec![Address::TWO])],
+                stake_subsidy,
+                false,
+                (0u64, 0u64, 0u64, 0u64),
+                1_700_000_000_000u64,
+                table,
+            ))
+            .unwrap(),
+        );
+        contents
+    }
+
+    #[test]
+    fn wrapper() {
+        let object = object(
+            "0x3::sui_system::SuiSystemState",
+            bcs::to_bytes(&(ObjectId::new([5; 32]), 2u64)).unwrap(),
+        );
+        let wrapper = SystemStateWrapper::try_from_object(&object).unwrap();
+        assert_eq!(wrapper.id, ObjectId::new([5; 32]));
+        assert_eq!(wrapper.version, 2);
+        assert_eq!(SystemState::try_from_object(&object), None);
+    }
+
+    #[test]
+    fn versions() {
+        let v2 = object(
+            "0x2::dynamic_field::Field<u64, 0x3::sui_system_state_inner::SuiSystemStateInnerV2>",
+            inner_state(Some(4)),
+        );
+        let state = SystemState::try_from_object(&v2).unwrap();
+        assert_eq!(state.epoch, EpochId::new(42));
+        assert_eq!(state.protocol_version, 70);
+        assert_eq!(state.parameters.min_validator_count, Some(4));
+        assert_eq!(state.parameters.max_validator_count, 150);
+        assert_eq!(state.reference_gas_price, 750);
+        assert_eq!(state.stake_subsidy.distribution_counter, 42);
+        assert!(!state.safe_mode);
+        assert_eq!(state.storage_fund.non_refundable_balance, 6);
+
+        let validator = state.validator(&VALIDATOR).unwrap();
+        assert_eq!(validator.metadata.name, "validator");
+        assert_eq!(validator.voting_power, 10_000);
+        assert_eq!(
+            validator.staking_pool.activation_epoch,
+            Some(EpochId::new(0))
+        );
+        assert_eq!(validator.commission_rate, 200);
+        assert_eq!(
+            state.validator_by_pool(&ObjectId::new([7; 32])),
+            Some(validator)
+        );
+        assert!(state.is_pending_removal(&VALIDATOR));
+        assert_eq!(state.validator(&Address::TWO), None);
+
+        let v1 = object(
+            "0x2::dynamic_field::Field<u64, 0x3::sui_system_state_inner::SuiSystemStateInner>",
+            inner_state(None),
+        );
+        let v1 = SystemState::try_from_object(&v1).unwrap();
+        assert_eq!(v1.parameters.min_validator_count, None);
+        assert_eq!(v1.validators, state.validators);
+
+        // The layout of a version doesn't decode as another
+        let mismatch = object(
+            "0x2::dynamic_field::Field<u64, 0x3::sui_system_state_inner::SuiSystemStateInner>",
+            inner_state(Some(4)),
+        );
+        assert_eq!(SystemState::try_from_object(&mismatch), None);
+    }
+}
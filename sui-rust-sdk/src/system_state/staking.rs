@@ -0,0 +1,347 @@
+//! The staking math of the `0x3` system package: the voting power of validators, the split of the
+//! rewards of an epoch between them and their stakers, and the yield of staking pools.
+
+use super::SystemState;
+use super::Validator;
+use crate::types::Address;
+use crate::types::Object;
+use crate::types::ObjectData;
+use crate::types::ObjectId;
+use crate::types::TypeTag;
+
+/// The total voting power of a committee.
+pub const TOTAL_VOTING_POWER: u64 = 10_000;
+
+/// The voting power of a quorum of a committee.
+pub const QUORUM_THRESHOLD: u64 = 6_667;
+
+/// The voting power a validator is capped at, unless there are too few validators to reach the
+/// total voting power under it.
+pub const MAX_VOTING_POWER: u64 = 1_000;
+
+/// The denominator of rates in basis points, e.g. the commission of validators.
+pub const BASIS_POINT_DENOMINATOR: u64 = 10_000;
+
+/// The voting power of validators with the given stakes, as set at epoch changes: proportional to
+/// stake, capped at [`MAX_VOTING_POWER`], with the power left under the cap handed out to the
+/// validators of most stake first.
+///
+/// Returns the power of each validator in the order of `stakes`, summing to
+/// [`TOTAL_VOTING_POWER`], or nothing if there are no validators or no stake.
+pub fn voting_power(stakes: &[u64]) -> Vec<u64> {
+    let total_stake: u128 = stakes.iter().map(|stake| *stake as u128).sum();
+    if total_stake == 0 {
+        return vec![];
+    }
+    let threshold = TOTAL_VOTING_POWER
+        .min(MAX_VOTING_POWER.max(TOTAL_VOTING_POWER.div_ceil(stakes.len() as u64)));
+
+    // The indices of the validators by decreasing stake, those of equal stake last first
+    let mut order: Vec<usize> = Vec::with_capacity(stakes.len());
+    let mut powers = vec![0; stakes.len()];
+    let mut remaining_power = TOTAL_VOTING_POWER;
+    for (index, stake) in stakes.iter().enumerate() {
+        let adjusted_stake = *stake as u128 * TOTAL_VOTING_POWER as u128 / total_stake;
+        powers[index] = (adjusted_stake as u64).min(threshold);
+        remaining_power -= powers[index];
+        let position = order
+            .iter()
+            .position(|other| stakes[*other] <= *stake)
+            .unwrap_or(order.len());
+        order.insert(position, index);
+    }
+
+    for (i, index) in order.iter().enumerate() {
+        if remaining_power == 0 {
+            break;
+        }
+        let planned = remaining_power.div_ceil((order.len() - i) as u64);
+        let target = threshold.min(powers[*index] + planned);
+        let actual = remaining_power.min(target - powers[*index]);
+        powers[*index] += actual;
+        remaining_power -= actual;
+    }
+    powers
+}
+
+/// The rewards of a validator for an epoch, before the adjustments withholding the rewards of
+/// validators reported by a quorum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidatorReward {
+    pub validator: Address,
+    /// The share of the staking rewards of the epoch of the validator, by voting power.
+    pub staking_reward: u64,
+    /// The part of `staking_reward` the validator takes as commission.
+    pub commission: u64,
+    /// The share of the storage fund rewards of the validator, split evenly between validators.
+    pub storage_fund_reward: u64,
+}
+
+impl ValidatorReward {
+    /// The rewards staked for the validator itself, its commission and storage fund reward.
+    pub fn validator_reward(&self) -> u64 {
+        self.commission + self.storage_fund_reward
+    }
+
+    /// The rewards added to the staking pool, for its stakers.
+    pub fn staker_reward(&self) -> u64 {
+        self.staking_reward - self.commission
+    }
+}
+
+/// The split of the rewards of an epoch at its end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EpochRewards {
+    /// The rewards of validators and their stakers, by voting power.
+    pub staking_reward: u64,
+    /// The rewards of the storage fund paid out to validators.
+    pub storage_fund_reward: u64,
+    /// The rewards of the storage fund kept in the fund.
+    pub storage_fund_reinvestment: u64,
+}
+
+impl Validator {
+    /// The SUI staked with the validator, including the rewards of its stakers, in MIST.
+    pub fn stake(&self) -> u64 {
+        self.staking_pool.sui_balance
+    }
+
+    /// The commission the validator takes from `staking_reward`.
+    pub fn commission(&self, staking_reward: u64) -> u64 {
+        (staking_reward as u128 * self.commission_rate as u128 / BASIS_POINT_DENOMINATOR as u128)
+            as u64
+    }
+}
+
+impl SystemState {
+    /// The SUI in the storage fund, which earns a share of the computation fees of each epoch.
+    pub fn storage_fund_balance(&self) -> u64 {
+        self.storage_fund.total_object_storage_rebates + self.storage_fund.non_refundable_balance
+    }
+
+    /// The voting power the active validators would get from their stake of the next epoch, by
+    /// address, ignoring validators joining or leaving the set.
+    pub fn next_epoch_voting_power(&self) -> Vec<(Address, u64)> {
+        let validators = &self.validators.active_validators;
+        let stakes: Vec<_> = validators
+            .iter()
+            .map(|validator| validator.next_epoch_stake)
+            .collect();
+        validators
+            .iter()
+            .map(|validator| validator.metadata.sui_address)
+            .zip(voting_power(&stakes))
+            .collect()
+    }
+
+    /// The split of the rewards of the epoch, given the computation fees charged during it, the
+    /// stake subsidy paid at its end and the rate of the storage fund rewards reinvested in the
+    /// fund, in basis points.
+    ///
+    /// The storage fund earns a share of the computation fees in proportion of its balance to the
+    /// total stake, and the validators get the rest with the stake subsidy.
+    pub fn epoch_rewards(
+        &self,
+        computation_charge: u64,
+        stake_subsidy: u64,
+        storage_fund_reinvest_rate: u64,
+    ) -> EpochRewards {
+        let storage_fund_balance = self.storage_fund_balance() as u128;
+        let total_stake = storage_fund_balance + self.validators.total_stake as u128;
+        let storage_fund_share = if total_stake == 0 {
+            0
+        } else {
+            (storage_fund_balance * computation_charge as u128 / total_stake) as u64
+        };
+        let storage_fund_reinvestment = (storage_fund_share as u128
+            * storage_fund_reinvest_rate as u128
+            / BASIS_POINT_DENOMINATOR as u128) as u64;
+        EpochRewards {
+            staking_reward: computation_charge + stake_subsidy - storage_fund_share,
+            storage_fund_reward: storage_fund_share - storage_fund_reinvestment,
+            storage_fund_reinvestment,
+        }
+    }
+
+    /// The rewards of each active validator from the rewards of the epoch, split by voting power
+    /// and evenly for the storage fund rewards.
+    pub fn reward_distribution(&self, rewards: &EpochRewards) -> Vec<ValidatorReward> {
+        let validators = &self.validators.active_validators;
+        if validators.is_empty() {
+            return vec![];
+        }
+        let storage_fund_reward = rewards.storage_fund_reward / validators.len() as u64;
+        validators
+            .iter()
+            .map(|validator| {
+                let staking_reward = (validator.voting_power as u128
+                    * rewards.staking_reward as u128
+                    / TOTAL_VOTING_POWER as u128) as u64;
+                ValidatorReward {
+                    validator: validator.metadata.sui_address,
+                    staking_reward,
+                    commission: validator.commission(staking_reward),
+                    storage_fund_reward,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The exchange rate of pool tokens of a staking pool at the start of an epoch, held in the
+/// `exchange_rates` table of the pool by epoch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde_derive::Deserialize)]
+pub struct PoolTokenExchangeRate {
+    pub sui_amount: u64,
+    pub pool_token_amount: u64,
+}
+
+impl PoolTokenExchangeRate {
+    /// Read the rate of an epoch from the entry of the `exchange_rates` table holding it, of type
+    /// `0x2::dynamic_field::Field<u64, 0x3::staking_pool::PoolTokenExchangeRate>`, returning the
+    /// epoch with it.
+    pub fn try_from_object(object: &Object) -> Option<(u64, Self)> {
+        #[derive(serde_derive::Deserialize)]
+        struct Field {
+            _id: ObjectId,
+            name: u64,
+            value: PoolTokenExchangeRate,
+        }
+
+        let ObjectData::Struct(move_struct) = &object.data else {
+            return None;
+        };
+        let type_ = &move_struct.type_;
+        let is_field = type_.address == Address::TWO
+            && type_.module == *"dynamic_field"
+            && type_.name == *"Field";
+        let is_rate = matches!(
+            type_.type_params.as_slice(),
+            [TypeTag::U64, TypeTag::Struct(rate)]
+                if rate.address == Address::THREE
+                    && rate.module == *"staking_pool"
+                    && rate.name == *"PoolTokenExchangeRate"
+        );
+        if !is_field || !is_rate {
+            return None;
+        }
+        let field: Field = bcs::from_bytes(&move_struct.contents).ok()?;
+        Some((field.name, field.value))
+    }
+
+    /// The pool tokens issued per SUI, which decreases as the pool earns rewards.
+    pub fn rate(&self) -> f64 {
+        if self.sui_amount == 0 {
+            1.0
+        } else {
+            self.pool_token_amount as f64 / self.sui_amount as f64
+        }
+    }
+
+    /// The annual yield of the pool between this rate and the `later` rate, `epochs` epochs
+    /// later, compounded over the epochs of a year of epochs of `epoch_duration_ms`.
+    pub fn apy(&self, later: &Self, epochs: u64, epoch_duration_ms: u64) -> f64 {
+        const YEAR_MS: f64 = 365.0 * 24.0 * 60.0 * 60.0 * 1000.0;
+
+        if epochs == 0 || epoch_duration_ms == 0 {
+            return 0.0;
+        }
+        let epochs_per_year = YEAR_MS / epoch_duration_ms as f64;
+        (self.rate() / later.rate()).powf(epochs_per_year / epochs as f64) - 1.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::test::inner_state;
+    use super::super::test::object;
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn state() -> SystemState {
+        SystemState::try_from_object(&object(
+            "0x2::dynamic_field::Field<u64, 0x3::sui_system_state_inner::SuiSystemStateInnerV2>",
+            inner_state(Some(4)),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn voting_powers() {
+        assert_eq!(voting_power(&[1, 1, 1, 1]), [2_500; 4]);
+        assert_eq!(voting_power(&[7; 20]), [500; 20]);
+        assert_eq!(voting_power(&[]), Vec::<u64>::new());
+        assert_eq!(voting_power(&[0, 0]), Vec::<u64>::new());
+
+        // The validator over the cap is capped, and the others share what's left under it
+        let mut stakes = vec![1; 11];
+        stakes[3] = 100;
+        let powers = voting_power(&stakes);
+        assert_eq!(powers[3], MAX_VOTING_POWER);
+        assert!(powers
+            .iter()
+            .enumerate()
+            .all(|(i, power)| i == 3 || *power == 900));
+
+        let powers = voting_power(&[30, 20, 20, 10, 5, 5, 5, 5]);
+        assert_eq!(powers.iter().sum::<u64>(), TOTAL_VOTING_POWER);
+        assert!(powers.iter().all(|power| *power <= 2_500));
+
+        assert_eq!(
+            state().next_epoch_voting_power(),
+            [(Address::from_static_hex("0xa1"), TOTAL_VOTING_POWER)]
+        );
+    }
+
+    #[test]
+    fn rewards() {
+        let state = state();
+        assert_eq!(state.storage_fund_balance(), 11);
+        assert_eq!(state.validators.active_validators[0].stake(), 1_100);
+
+        let rewards = state.epoch_rewards(1_000_000, 100, 5_000);
+        assert_eq!(
+            rewards,
+            EpochRewards {
+                staking_reward: 990_200,
+                storage_fund_reward: 4_950,
+                storage_fund_reinvestment: 4_950,
+            }
+        );
+        let distribution = state.reward_distribution(&rewards);
+        assert_eq!(distribution.len(), 1);
+        assert_eq!(distribution[0].staking_reward, 990_200);
+        assert_eq!(distribution[0].commission, 19_804);
+        assert_eq!(distribution[0].validator_reward(), 19_804 + 4_950);
+        assert_eq!(distribution[0].staker_reward(), 990_200 - 19_804);
+    }
+
+    #[test]
+    fn apy() {
+        let earlier = PoolTokenExchangeRate {
+            sui_amount: 1_000,
+            pool_token_amount: 1_000,
+        };
+        let later = PoolTokenExchangeRate {
+            sui_amount: 1_001,
+            pool_token_amount: 1_000,
+        };
+        let day_ms = 24 * 60 * 60 * 1000;
+        let apy = earlier.apy(&later, 1, day_ms);
+        assert!((apy - (1.001f64.powi(365) - 1.0)).abs() < 1e-9);
+        assert!((earlier.apy(&later, 2, day_ms) - (1.001f64.powf(182.5) - 1.0)).abs() < 1e-9);
+        assert_eq!(earlier.apy(&later, 0, day_ms), 0.0);
+        assert_eq!(PoolTokenExchangeRate::default().rate(), 1.0);
+
+        let field = object(
+            "0x2::dynamic_field::Field<u64, 0x3::staking_pool::PoolTokenExchangeRate>",
+            bcs::to_bytes(&(ObjectId::new([1; 32]), 42u64, 1_001u64, 1_000u64)).unwrap(),
+        );
+        assert_eq!(
+            PoolTokenExchangeRate::try_from_object(&field),
+            Some((42, later))
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! A harness for end-to-end tests against a local Sui network.
+//!
+//! [`LocalNetwork`] either starts a fresh network with the `sui` binary, or connects to one that
+//! is already running, e.g. started by CI. It talks to the network's JSON-RPC endpoint and faucet
+//! over plain HTTP, funds addresses from the faucet, and publishes fixture packages with the
+//! `sui client`.
+//!
+//! ```no_run
+//! use sui_sdk::test_utils::LocalNetwork;
+//! use sui_sdk::types::Address;
+//!
+//! let network = LocalNetwork::from_env().unwrap();
+//! network.fund(Address::ZERO).unwrap();
+//! let package = network.publish("tests/fixtures/my_package").unwrap();
+//! println!("published {}", package.package_id);
+//! ```
+//!
+//! The following environment variables are read:
+//!
+//! - `SUI_BINARY`: the `sui` binary to run, `sui` on the `PATH` by default.
+//! - `SUI_LOCAL_RPC_URL` and `SUI_LOCAL_FAUCET_URL`: the endpoints of an already running network
+//!   to connect to by [`LocalNetwork::from_env`], instead of starting one.
+
+use std::io::Read;
+use std::io::Write;
+use std::net::TcpStream;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use serde_json::Value;
+
+use crate::json_rpc::JsonRpcError;
+use crate::json_rpc::JsonRpcRequest;
+use crate::json_rpc::JsonRpcResponse;
+use crate::json_rpc::Version2;
+use crate::types::Address;
+use crate::types::ObjectId;
+use crate::types::TransactionDigest;
+
+pub const DEFAULT_RPC_URL: &str = "http://127.0.0.1:9000";
+pub const DEFAULT_FAUCET_URL: &str = "http://127.0.0.1:9123";
+
+/// How long to wait for a freshly started network to serve requests.
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long to wait for the response to a single HTTP request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A local network, stopped when dropped if it was started by the harness.
+#[derive(Debug)]
+pub struct LocalNetwork {
+    rpc_url: String,
+    faucet_url: String,
+    client_config: Option<PathBuf>,
+    process: Option<Child>,
+}
+
+impl LocalNetwork {
+    /// Connect to a network which is already running.
+    pub fn connect<R: Into<String>, F: Into<String>>(rpc_url: R, faucet_url: F) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            faucet_url: faucet_url.into(),
+            client_config: None,
+            process: None,
+        }
+    }
+
+    /// Start a fresh network with its faucet, listening on the default ports, and wait until it
+    /// serves requests.
+    pub fn start() -> Result<Self, TestNetworkError> {
+        let process = Command::new(sui_binary())
+            .args(["start", "--with-faucet", "--force-regenesis"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(TestNetworkError::Io)?;
+        let mut network = Self::connect(DEFAULT_RPC_URL, DEFAULT_FAUCET_URL);
+        network.process = Some(process);
+        network.wait_until_ready(STARTUP_TIMEOUT)?;
+        Ok(network)
+    }
+
+    /// Connect to the network at `SUI_LOCAL_RPC_URL` if it is set, or start one otherwise.
+    pub fn from_env() -> Result<Self, TestNetworkError> {
+        match std::env::var("SUI_LOCAL_RPC_URL") {
+            Ok(rpc_url) => {
+                let faucet_url = std::env::var("SUI_LOCAL_FAUCET_URL")
+                    .unwrap_or_else(|_| DEFAULT_FAUCET_URL.to_owned());
+                Ok(Self::connect(rpc_url, faucet_url))
+            }
+            Err(_) => Self::start(),
+        }
+    }
+
+    /// Use the `sui client` configuration at `path` to publish packages, instead of the client's
+    /// default configuration.
+    pub fn with_client_config<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.client_config = Some(path.into());
+        self
+    }
+
+    pub fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    pub fn faucet_url(&self) -> &str {
+        &self.faucet_url
+    }
+
+    /// Poll the network until it answers JSON-RPC requests.
+    pub fn wait_until_ready(&self, timeout: Duration) -> Result<(), TestNetworkError> {
+        let start = Instant::now();
+        loop {
+            match self.rpc("sui_getChainIdentifier", serde_json::json!([])) {
+                Ok(_) => return Ok(()),
+                Err(_) if start.elapsed() < timeout => {
+                    std::thread::sleep(Duration::from_millis(500))
+                }
+                Err(e) => return Err(TestNetworkError::NotReady(Box::new(e))),
+            }
+        }
+    }
+
+    /// Call `method` on the network's JSON-RPC endpoint.
+    pub fn rpc(&self, method: &str, params: Value) -> Result<Value, TestNetworkError> {
+        let request = JsonRpcRequest {
+            jsonrpc: Version2,
+            id: 0,
+            method: method.to_owned(),
+            params,
+        };
+        let body = http_post(&self.rpc_url, &serde_json::to_vec(&request).unwrap())?;
+        let response: JsonRpcResponse =
+            serde_json::from_slice(&body).map_err(TestNetworkError::Json)?;
+        match response {
+            JsonRpcResponse {
+                error: Some(error), ..
+            } => Err(TestNetworkError::Rpc(error)),
+            JsonRpcResponse { result, .. } => Ok(result.unwrap_or(Value::Null)),
+        }
+    }
+
+    /// Request gas coins for `address` from the faucet.
+    pub fn fund(&self, address: Address) -> Result<(), TestNetworkError> {
+        let request = serde_json::json!({ "FixedAmountRequest": { "recipient": address } });
+        let url = format!("{}/gas", self.faucet_url.trim_end_matches('/'));
+        let body = http_post(&url, &serde_json::to_vec(&request).unwrap())?;
+        let response: Value = serde_json::from_slice(&body).map_err(TestNetworkError::Json)?;
+        match response.get("error") {
+            Some(error) if !error.is_null() => Err(TestNetworkError::Faucet(error.to_string())),
+            _ => Ok(()),
+        }
+    }
+
+    /// Publish the Move package at `path` with the `sui client`, from its active address.
+    pub fn publish<P: AsRef<Path>>(&self, path: P) -> Result<PublishedPackage, TestNetworkError> {
+        let mut command = Command::new(sui_binary());
+        command.arg("client");
+        if let Some(config) = &self.client_config {
+            command.arg("--client.config").arg(config);
+        }
+        command
+            .arg("publish")
+            .arg(path.as_ref())
+            .args(["--skip-dependency-verification", "--json"]);
+
+        let output = command.output().map_err(TestNetworkError::Io)?;
+        if !output.status.success() {
+            return Err(TestNetworkError::Cli(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+        let response: Value =
+            serde_json::from_slice(&output.stdout).map_err(TestNetworkError::Json)?;
+        PublishedPackage::from_response(&response)
+    }
+}
+
+impl Drop for LocalNetwork {
+    fn drop(&mut self) {
+        if let Some(process) = &mut self.process {
+            let _ = process.kill();
+            let _ = process.wait();
+        }
+    }
+}
+
+/// A package published by [`LocalNetwork::publish`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PublishedPackage {
+    pub package_id: ObjectId,
+    /// The digest of the publish transaction.
+    pub digest: TransactionDigest,
+    /// The `UpgradeCap` of the package.
+    pub upgrade_cap: Option<ObjectId>,
+}
+
+impl PublishedPackage {
+    /// Read the package from the JSON transaction response printed by `sui client publish`.
+    fn from_response(response: &Value) -> Result<Self, TestNetworkError> {
+        let invalid = || TestNetworkError::Cli(format!("unexpected publish output {response}"));
+        fn parse<T: std::str::FromStr>(value: &Value) -> Option<T> {
+            value.as_str()?.parse().ok()
+        }
+
+        let changes = response["objectChanges"].as_array().ok_or_else(invalid)?;
+        let package_id = changes
+            .iter()
+            .find(|change| change["type"] == "published")
+            .and_then(|change| parse(&change["packageId"]))
+            .ok_or_else(invalid)?;
+        let upgrade_cap = changes
+            .iter()
+            .find(|change| {
+                change["type"] == "created" && change["objectType"] == "0x2::package::UpgradeCap"
+            })
+            .and_then(|change| parse(&change["objectId"]));
+        let digest = parse(&response["digest"]).ok_or_else(invalid)?;
+        Ok(Self {
+            package_id,
+            digest,
+            upgrade_cap,
+        })
+    }
+}
+
+fn sui_binary() -> String {
+    std::env::var("SUI_BINARY").unwrap_or_else(|_| "sui".to_owned())
+}
+
+/// POST `body` as JSON to the plain HTTP `url`, returning the body of the response.
+///
+/// This is just enough HTTP/1.1 for the local network's endpoints, which is all the harness
+/// needs, without pulling an HTTP client into the SDK's dependencies.
+fn http_post(url: &str, body: &[u8]) -> Result<Vec<u8>, TestNetworkError> {
+    let invalid_url = || TestNetworkError::InvalidUrl(url.to_owned());
+    let rest = url.strip_prefix("http://").ok_or_else(invalid_url)?;
+    let (authority, path) = match rest.find('/') {
+        Some(index) => rest.split_at(index),
+        None => (rest, "/"),
+    };
+    if authority.is_empty() {
+        return Err(invalid_url());
+    }
+    let address = if authority.contains(':') {
+        authority.to_owned()
+    } else {
+        format!("{authority}:80")
+    };
+
+    let mut stream = TcpStream::connect(address).map_err(TestNetworkError::Io)?;
+    stream
+        .set_read_timeout(Some(REQUEST_TIMEOUT))
+        .map_err(TestNetworkError::Io)?;
+    let head = format!(
+        "POST {path} HTTP/1.1\r\nHost: {authority}\r\nContent-Type: application/json\r\n\
+         Content-Length: {}\r\nConnection: close\r\n\r\n",
+        body.len()
+    );
+    stream
+        .write_all(head.as_bytes())
+        .and_then(|()| stream.write_all(body))
+        .map_err(TestNetworkError::Io)?;
+    let mut response = Vec::new();
+    stream
+        .read_to_end(&mut response)
+        .map_err(TestNetworkError::Io)?;
+
+    parse_http_response(&response)
+}
+
+fn parse_http_response(response: &[u8]) -> Result<Vec<u8>, TestNetworkError> {
+    let invalid = || TestNetworkError::Http {
+        status: 0,
+        body: String::from_utf8_lossy(response).into_owned(),
+    };
+    let split = response
+        .windows(4)
+        .position(|window| window == b"\r\n\r\n")
+        .ok_or_else(invalid)?;
+    let head = std::str::from_utf8(&response[..split]).map_err(|_| invalid())?;
+    let body = &response[split + 4..];
+
+    let mut lines = head.split("\r\n");
+    let status: u16 = lines
+        .next()
+        .and_then(|line| line.split(' ').nth(1))
+        .and_then(|status| status.parse().ok())
+        .ok_or_else(invalid)?;
+    let chunked = lines.any(|line| {
+        line.split_once(':').is_some_and(|(name, value)| {
+            name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked")
+        })
+    });
+    let body = if chunked {
+        decode_chunked(body).ok_or_else(invalid)?
+    } else {
+        body.to_vec()
+    };
+
+    if !(200..300).contains(&status) {
+        return Err(TestNetworkError::Http {
+            status,
+            body: String::from_utf8_lossy(&body).into_owned(),
+        });
+    }
+    Ok(body)
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let line_end = body.windows(2).position(|window| window == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..line_end]).ok()?;
+        // Ignore chunk extensions
+        let size = size.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        body = &body[line_end + 2..];
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+#[derive(Debug)]
+pub enum TestNetworkError {
+    Io(std::io::Error),
+    InvalidUrl(String),
+    /// The endpoint responded with a status other than success.
+    Http {
+        status: u16,
+        body: String,
+    },
+    Json(serde_json::Error),
+    Rpc(JsonRpcError),
+    Faucet(String),
+    /// The `sui` binary failed, with its error output.
+    Cli(String),
+    /// The network didn't serve requests in time after being started.
+    NotReady(Box<TestNetworkError>),
+}
+
+impl std::fmt::Display for TestNetworkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => write!(f, "{e}"),
+            Self::InvalidUrl(url) => write!(f, "unsupported url {url}, expected http://host:port"),
+            Self::Http { status, body } => write!(f, "http status {status}: {body}"),
+            Self::Json(e) => write!(f, "invalid response: {e}"),
+            Self::Rpc(e) => write!(f, "{e}"),
+            Self::Faucet(e) => write!(f, "faucet error: {e}"),
+            Self::Cli(e) => write!(f, "sui client failed: {e}"),
+            Self::NotReady(e) => write!(f, "local network is not ready: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for TestNetworkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            Self::Json(e) => Some(e),
+            Self::Rpc(e) => Some(e),
+            Self::NotReady(e) => Some(e),
+            Self::InvalidUrl(_) | Self::Http { .. } | Self::Faucet(_) | Self::Cli(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::net::TcpListener;
+
+    /// Serve a single HTTP request with `response`, returning the raw request.
+    fn serve_once(response: String) -> (String, std::thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 4096];
+            // Read until the whole body, announced by `Content-Length`, has arrived
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length: usize = head
+                        .lines()
+                        .find_map(|line| line.strip_prefix("Content-Length: "))
+                        .unwrap()
+                        .parse()
+                        .unwrap();
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+            }
+            stream.write_all(response.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (url, handle)
+    }
+
+    fn response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        )
+    }
+
+    #[test]
+    fn rpc() {
+        let chunks = [r#"{"jsonrpc":"2.0","id":0,"#, r#""result":"4c78adac"}"#];
+        let mut response = "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n".to_owned();
+        for chunk in chunks {
+            response.push_str(&format!("{:x}\r\n{chunk}\r\n", chunk.len()));
+        }
+        response.push_str("0\r\n\r\n");
+        let (url, server) = serve_once(response);
+        let network = LocalNetwork::connect(url, DEFAULT_FAUCET_URL);
+        assert_eq!(
+            network
+                .rpc("sui_getChainIdentifier", serde_json::json!([]))
+                .unwrap(),
+            "4c78adac"
+        );
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST / HTTP/1.1\r\n"));
+        assert!(request.contains(r#""method":"sui_getChainIdentifier""#));
+    }
+
+    #[test]
+    fn rpc_error() {
+        let (url, server) = serve_once(response(
+            "200 OK",
+            r#"{"jsonrpc":"2.0","id":0,"error":{"code":-32601,"message":"no method"}}"#,
+        ));
+        let network = LocalNetwork::connect(url, DEFAULT_FAUCET_URL);
+        assert!(matches!(
+            network.rpc("sui_nothing", serde_json::json!([])),
+            Err(TestNetworkError::Rpc(JsonRpcError { code: -32601, .. }))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn fund() {
+        let (url, server) = serve_once(response(
+            "201 Created",
+            r#"{"transferredGasObjects":[],"error":null}"#,
+        ));
+        let network = LocalNetwork::connect(DEFAULT_RPC_URL, url);
+        network.fund(Address::TWO).unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /gas HTTP/1.1\r\n"));
+        assert!(request.contains(&Address::TWO.to_string()));
+
+        let (url, server) = serve_once(response("429 Too Many Requests", "slow"));
+        let network = LocalNetwork::connect(DEFAULT_RPC_URL, url);
+        assert!(matches!(
+            network.fund(Address::TWO),
+            Err(TestNetworkError::Http { status: 429, body }) if body == "slow"
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn invalid_urls() {
+        for url in ["https://127.0.0.1:9000", "127.0.0.1:9000", "http://"] {
+            assert!(matches!(
+                http_post(url, b"{}"),
+                Err(TestNetworkError::InvalidUrl(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn published_package() {
+        let response = serde_json::json!({
+            "digest": "7CuBm1AnLgkBMB6GiEn5d3RizznF5LbawjJTs8A5dcXF",
+            "objectChanges": [
+                {
+                    "type": "created",
+                    "objectType": "0x2::package::UpgradeCap",
+                    "objectId": "0x0000000000000000000000000000000000000000000000000000000000000abc"
+                },
+                {
+                    "type": "published",
+                    "packageId": "0x0000000000000000000000000000000000000000000000000000000000000def"
+                }
+            ]
+        });
+        let package = PublishedPackage::from_response(&response).unwrap();
+        assert_eq!(package.package_id, "0xdef".parse().unwrap());
+        assert_eq!(package.upgrade_cap, Some("0xabc".parse().unwrap()));
+        assert_eq!(
+            package.digest.to_string(),
+            "7CuBm1AnLgkBMB6GiEn5d3RizznF5LbawjJTs8A5dcXF"
+        );
+
+        assert!(PublishedPackage::from_response(&serde_json::json!({})).is_err());
+    }
+
+    /// Runs against a real network, started with the `sui` binary or found at
+    /// `SUI_LOCAL_RPC_URL`.
+    #[test]
+    #[ignore]
+    fn local_network() {
+        let network = LocalNetwork::from_env().unwrap();
+        network.fund(Address::ZERO).unwrap();
+    }
+}
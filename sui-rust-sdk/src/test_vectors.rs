@@ -0,0 +1,220 @@
+//! A corpus of canonical test vectors, for SDKs in other languages to check their encodings and
+//! digests against this crate.
+//!
+//! Every vector holds a value in both of its encodings, BCS and readable JSON, along with the
+//! digests derived from it. Values are built deterministically, partly from transactions and
+//! effects captured on chain, so the corpus is the same every time it is generated. Run
+//! `cargo run --example test-vectors --features test-vectors` to print it as JSON.
+//!
+//! Signatures are only covered as far as their encoding and the messages which get signed: the
+//! crate doesn't sign, so the signatures in the corpus are made of arbitrary bytes.
+//!
+//! The same checks are available as a harness: [`check_vectors`] checks a corpus, this one or
+//! another SDK's, against this crate, and [`load_fixtures`] loads a directory of Base64 BCS
+//! fixtures for [`check_fixtures`]. Both report the failure of every fixture rather than stopping
+//! at the first.
+
+use base64ct::Base64;
+use base64ct::Encoding;
+
+use crate::types::CheckpointContents;
+use crate::types::CheckpointSummary;
+use crate::types::CheckpointTransactionInfo;
+use crate::types::Ed25519PublicKey;
+use crate::types::Ed25519Signature;
+use crate::types::EpochId;
+use crate::types::GasCostSummary;
+use crate::types::SignedTransaction;
+use crate::types::SimpleSignature;
+use crate::types::Transaction;
+use crate::types::TransactionEffects;
+use crate::types::UserSignature;
+
+mod conformance;
+
+pub use conformance::check_fixtures;
+pub use conformance::check_vectors;
+pub use conformance::load_fixtures;
+pub use conformance::ConformanceError;
+pub use conformance::ConformanceReport;
+pub use conformance::Fixture;
+
+const AUTHENTICATOR_STATE_UPDATE: &str =
+    include_str!("types/transaction/fixtures/authenticator_state_update");
+const WORMHOLE_PYTH_TRANSACTION: &str =
+    include_str!("types/transaction/fixtures/wormhole-pyth-transaction");
+const PYTH_WORMHOLE_EFFECTS: &str = include_str!("types/effects/fixtures/pyth-wormhole-v2");
+
+#[derive(Clone, Debug, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TestVector {
+    /// A unique name for the vector.
+    pub name: String,
+    /// The SDK type the value is an instance of, e.g. `Transaction`.
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// The BCS encoding of the value, as Base64.
+    #[serde(with = "::serde_with::As::<crate::_serde::Base64Encoded>")]
+    pub bcs: Vec<u8>,
+    /// The readable JSON encoding of the value.
+    pub json: serde_json::Value,
+    /// The Base58 digest of the value, for types which have one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub digest: Option<String>,
+    /// The hex encoded digest signed to authorize the value, for types which are signed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_digest: Option<String>,
+}
+
+impl TestVector {
+    /// A vector of `value`, checking that both of its encodings decode back to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` doesn't roundtrip, which would make it useless as a test vector.
+    fn new<T>(name: &str, value: &T) -> Self
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let bcs = bcs::to_bytes(value).unwrap();
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(&bcs::from_bytes::<T>(&bcs).unwrap(), value);
+        assert_eq!(&serde_json::from_value::<T>(json.clone()).unwrap(), value);
+
+        let type_name = std::any::type_name::<T>();
+        Self {
+            name: name.to_owned(),
+            type_name: type_name
+                .rsplit("::")
+                .next()
+                .unwrap_or(type_name)
+                .to_owned(),
+            bcs,
+            json,
+            digest: None,
+            signing_digest: None,
+        }
+    }
+
+    fn with_digest(self, digest: impl std::fmt::Display) -> Self {
+        Self {
+            digest: Some(digest.to_string()),
+            ..self
+        }
+    }
+
+    fn with_signing_digest(self, signing_digest: [u8; 32]) -> Self {
+        Self {
+            signing_digest: Some(hex::encode(signing_digest)),
+            ..self
+        }
+    }
+}
+
+/// The corpus of test vectors covering transactions, signatures, effects and checkpoints.
+pub fn test_vectors() -> Vec<TestVector> {
+    let mut vectors = vec![];
+
+    let wormhole_pyth: Transaction = decode(WORMHOLE_PYTH_TRANSACTION);
+    let authenticator_state_update: Transaction = decode(AUTHENTICATOR_STATE_UPDATE);
+    for (name, transaction) in [
+        ("transaction/programmable", &wormhole_pyth),
+        (
+            "transaction/authenticator_state_update",
+            &authenticator_state_update,
+        ),
+    ] {
+        vectors.push(
+            TestVector::new(name, transaction)
+                .with_digest(transaction.digest())
+                .with_signing_digest(transaction.signing_digest()),
+        );
+    }
+
+    let signature = UserSignature::Simple(SimpleSignature::Ed25519 {
+        signature: Ed25519Signature::new([0xab; Ed25519Signature::LENGTH]),
+        public_key: Ed25519PublicKey::new([0xcd; Ed25519PublicKey::LENGTH]),
+    });
+    vectors.push(TestVector::new("signature/ed25519", &signature));
+    let signed = SignedTransaction {
+        transaction: wormhole_pyth.clone(),
+        signatures: vec![signature.clone()],
+    };
+    vectors.push(
+        TestVector::new("signed_transaction/programmable", &signed)
+            .with_digest(signed.transaction.digest()),
+    );
+
+    let effects: TransactionEffects = decode(PYTH_WORMHOLE_EFFECTS);
+    vectors.push(TestVector::new("effects/v2", &effects).with_digest(effects.digest()));
+
+    let contents = CheckpointContents::new(vec![CheckpointTransactionInfo {
+        transaction: wormhole_pyth.digest(),
+        effects: effects.digest(),
+        signatures: vec![signature],
+    }]);
+    vectors.push(TestVector::new("checkpoint_contents", &contents).with_digest(contents.digest()));
+    let checkpoint = CheckpointSummary {
+        epoch: EpochId::new(1),
+        sequence_number: 42,
+        network_total_transactions: 100,
+        content_digest: contents.digest(),
+        previous_digest: None,
+        epoch_rolling_gas_cost_summary: GasCostSummary::new(1_000_000, 2_000, 1_000, 10),
+        timestamp_ms: 1_700_000_000_000,
+        checkpoint_commitments: vec![],
+        end_of_epoch_data: None,
+        version_specific_data: vec![],
+    };
+    vectors
+        .push(TestVector::new("checkpoint_summary", &checkpoint).with_digest(checkpoint.digest()));
+
+    vectors
+}
+
+fn decode<T: serde::de::DeserializeOwned>(b64: &str) -> T {
+    bcs::from_bytes(&Base64::decode_vec(b64.trim()).unwrap()).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn deterministic_corpus() {
+        let vectors = test_vectors();
+        assert_eq!(vectors, test_vectors());
+
+        let mut names: Vec<_> = vectors.iter().map(|vector| &vector.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), vectors.len());
+
+        let json = serde_json::to_string(&vectors).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<TestVector>>(&json).unwrap(),
+            vectors
+        );
+    }
+
+    #[test]
+    fn transaction_vectors() {
+        let vector = &test_vectors()[0];
+        assert_eq!(vector.type_name, "Transaction");
+
+        let transaction: Transaction = bcs::from_bytes(&vector.bcs).unwrap();
+        assert_eq!(
+            vector.digest.as_deref(),
+            Some(transaction.digest().to_string().as_str())
+        );
+
+        let mut message = vec![0, 0, 0];
+        message.extend(&vector.bcs);
+        assert_eq!(
+            vector.signing_digest.as_deref(),
+            Some(crate::hash::Hasher::digest(message).to_hex().as_str())
+        );
+    }
+}
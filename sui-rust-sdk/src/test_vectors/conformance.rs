@@ -0,0 +1,325 @@
+//! Checking values against their encodings and digests, reporting every failure instead of
+//! stopping at the first.
+
+use std::path::Path;
+
+use base64ct::Base64;
+use base64ct::Encoding;
+
+use super::TestVector;
+use crate::types::CheckpointContents;
+use crate::types::CheckpointSummary;
+use crate::types::SignedTransaction;
+use crate::types::Transaction;
+use crate::types::TransactionEffects;
+use crate::types::UserSignature;
+
+/// A value in its BCS encoding, without the JSON encoding or digests of a [`TestVector`], which
+/// are derived when it is checked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub name: String,
+    /// The SDK type the value is an instance of, e.g. `Transaction`.
+    pub type_name: String,
+    pub bcs: Vec<u8>,
+}
+
+impl Fixture {
+    /// Check that the fixture decodes as its type and that both encodings of the value roundtrip,
+    /// returning the test vector of the value.
+    pub fn check(&self) -> Result<TestVector, ConformanceError> {
+        derive(&self.name, &self.type_name, &self.bcs)
+    }
+}
+
+impl TestVector {
+    /// Check the vector against this crate: its BCS must decode to a value which encodes back to
+    /// the same bytes and whose JSON and digests are those of the vector.
+    pub fn check(&self) -> Result<(), ConformanceError> {
+        let derived = derive(&self.name, &self.type_name, &self.bcs)?;
+        if derived.json != self.json {
+            return Err(ConformanceError::JsonMismatch);
+        }
+        if derived.digest != self.digest {
+            return Err(ConformanceError::DigestMismatch {
+                expected: self.digest.clone(),
+                actual: derived.digest,
+            });
+        }
+        if derived.signing_digest != self.signing_digest {
+            return Err(ConformanceError::SigningDigestMismatch {
+                expected: self.signing_digest.clone(),
+                actual: derived.signing_digest,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Load every file in `dir` as a fixture of type `type_name`, in order of file name.
+///
+/// Each file holds the Base64 BCS encoding of a value, surrounded by optional whitespace, and the
+/// fixture is named after the file. Hidden files and subdirectories are skipped.
+pub fn load_fixtures(dir: impl AsRef<Path>, type_name: &str) -> std::io::Result<Vec<Fixture>> {
+    let mut fixtures = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with('.') || !entry.file_type()?.is_file() {
+            continue;
+        }
+        let contents = std::fs::read_to_string(entry.path())?;
+        let bcs = Base64::decode_vec(contents.trim()).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("fixture {name} is not valid Base64: {e}"),
+            )
+        })?;
+        fixtures.push(Fixture {
+            name,
+            type_name: type_name.to_owned(),
+            bcs,
+        });
+    }
+    fixtures.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(fixtures)
+}
+
+/// Check every fixture, see [`Fixture::check`].
+pub fn check_fixtures<'a>(fixtures: impl IntoIterator<Item = &'a Fixture>) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+    for fixture in fixtures {
+        report.record(&fixture.name, fixture.check().map(drop));
+    }
+    report
+}
+
+/// Check every test vector, see [`TestVector::check`].
+pub fn check_vectors<'a>(vectors: impl IntoIterator<Item = &'a TestVector>) -> ConformanceReport {
+    let mut report = ConformanceReport::default();
+    for vector in vectors {
+        report.record(&vector.name, vector.check());
+    }
+    report
+}
+
+/// The outcome of checking a set of fixtures or test vectors, by name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConformanceReport {
+    pub passed: Vec<String>,
+    pub failures: Vec<(String, ConformanceError)>,
+}
+
+impl ConformanceReport {
+    /// Whether every fixture passed.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    fn record(&mut self, name: &str, result: Result<(), ConformanceError>) {
+        match result {
+            Ok(()) => self.passed.push(name.to_owned()),
+            Err(e) => self.failures.push((name.to_owned(), e)),
+        }
+    }
+}
+
+impl std::fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed",
+            self.passed.len(),
+            self.failures.len()
+        )?;
+        for (name, error) in &self.failures {
+            write!(f, "\n{name}: {error}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConformanceError {
+    /// The type is not one covered by the harness.
+    UnknownType(String),
+    /// The BCS doesn't decode as the type.
+    Bcs(String),
+    /// The decoded value encodes to different BCS.
+    BcsRoundtrip,
+    /// The JSON of the value doesn't decode as the type.
+    Json(String),
+    /// The JSON of the value decodes to a different value.
+    JsonRoundtrip,
+    /// The JSON of the vector isn't the JSON of its value.
+    JsonMismatch,
+    DigestMismatch {
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+    SigningDigestMismatch {
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+}
+
+impl std::fmt::Display for ConformanceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownType(type_name) => write!(f, "unknown type {type_name}"),
+            Self::Bcs(e) => write!(f, "invalid BCS: {e}"),
+            Self::BcsRoundtrip => write!(f, "BCS does not roundtrip"),
+            Self::Json(e) => write!(f, "invalid JSON: {e}"),
+            Self::JsonRoundtrip => write!(f, "JSON does not roundtrip"),
+            Self::JsonMismatch => write!(f, "JSON does not match the BCS"),
+            Self::DigestMismatch { expected, actual } => {
+                write!(f, "digest {actual:?} does not match {expected:?}")
+            }
+            Self::SigningDigestMismatch { expected, actual } => {
+                write!(f, "signing digest {actual:?} does not match {expected:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConformanceError {}
+
+/// A type covered by the harness, along with its digests.
+trait Conformance: serde::Serialize + serde::de::DeserializeOwned + PartialEq {
+    fn digest(&self) -> Option<String> {
+        None
+    }
+
+    fn signing_digest(&self) -> Option<[u8; 32]> {
+        None
+    }
+}
+
+impl Conformance for Transaction {
+    fn digest(&self) -> Option<String> {
+        Some(self.digest().to_string())
+    }
+
+    fn signing_digest(&self) -> Option<[u8; 32]> {
+        Some(self.signing_digest())
+    }
+}
+
+impl Conformance for SignedTransaction {
+    fn digest(&self) -> Option<String> {
+        Some(self.transaction.digest().to_string())
+    }
+}
+
+impl Conformance for UserSignature {}
+
+impl Conformance for TransactionEffects {
+    fn digest(&self) -> Option<String> {
+        Some(self.digest().to_string())
+    }
+}
+
+impl Conformance for CheckpointContents {
+    fn digest(&self) -> Option<String> {
+        Some(self.digest().to_string())
+    }
+}
+
+impl Conformance for CheckpointSummary {
+    fn digest(&self) -> Option<String> {
+        Some(self.digest().to_string())
+    }
+}
+
+fn derive(name: &str, type_name: &str, bcs: &[u8]) -> Result<TestVector, ConformanceError> {
+    match type_name {
+        "Transaction" => derive_as::<Transaction>(name, type_name, bcs),
+        "SignedTransaction" => derive_as::<SignedTransaction>(name, type_name, bcs),
+        "UserSignature" => derive_as::<UserSignature>(name, type_name, bcs),
+        "TransactionEffects" => derive_as::<TransactionEffects>(name, type_name, bcs),
+        "CheckpointContents" => derive_as::<CheckpointContents>(name, type_name, bcs),
+        "CheckpointSummary" => derive_as::<CheckpointSummary>(name, type_name, bcs),
+        _ => Err(ConformanceError::UnknownType(type_name.to_owned())),
+    }
+}
+
+fn derive_as<T: Conformance>(
+    name: &str,
+    type_name: &str,
+    bcs: &[u8],
+) -> Result<TestVector, ConformanceError> {
+    let value: T = bcs::from_bytes(bcs).map_err(|e| ConformanceError::Bcs(e.to_string()))?;
+    if bcs::to_bytes(&value).ok().as_deref() != Some(bcs) {
+        return Err(ConformanceError::BcsRoundtrip);
+    }
+
+    let json = serde_json::to_value(&value).map_err(|e| ConformanceError::Json(e.to_string()))?;
+    let decoded: T =
+        serde_json::from_value(json.clone()).map_err(|e| ConformanceError::Json(e.to_string()))?;
+    if decoded != value {
+        return Err(ConformanceError::JsonRoundtrip);
+    }
+
+    Ok(TestVector {
+        name: name.to_owned(),
+        type_name: type_name.to_owned(),
+        bcs: bcs.to_vec(),
+        json,
+        digest: Conformance::digest(&value),
+        signing_digest: Conformance::signing_digest(&value).map(hex::encode),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn corpus_conforms() {
+        let report = check_vectors(&super::super::test_vectors());
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.passed.len(), super::super::test_vectors().len());
+    }
+
+    #[test]
+    fn reports_failures() {
+        let mut vectors = super::super::test_vectors();
+        vectors[0].digest = Some("wrong".to_owned());
+        vectors[1].type_name = "Unknown".to_owned();
+        vectors[2].bcs.push(0);
+        vectors[3].json = serde_json::Value::Null;
+
+        let report = check_vectors(&vectors);
+        assert_eq!(report.passed.len(), vectors.len() - 4);
+        let errors: Vec<_> = report.failures.iter().map(|(_, e)| e).collect();
+        assert!(matches!(
+            errors[0],
+            ConformanceError::DigestMismatch { expected: Some(digest), actual: Some(_) }
+                if digest == "wrong"
+        ));
+        assert_eq!(
+            errors[1],
+            &ConformanceError::UnknownType("Unknown".to_owned())
+        );
+        assert!(matches!(errors[2], ConformanceError::Bcs(_)));
+        assert_eq!(errors[3], &ConformanceError::JsonMismatch);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn fixture_directories() {
+        for (dir, type_name) in [
+            ("src/types/transaction/fixtures", "Transaction"),
+            ("src/types/effects/fixtures", "TransactionEffects"),
+        ] {
+            let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join(dir);
+            let fixtures = load_fixtures(dir, type_name).unwrap();
+            assert!(!fixtures.is_empty());
+            let report = check_fixtures(&fixtures);
+            assert!(report.is_ok(), "{report}");
+        }
+    }
+}
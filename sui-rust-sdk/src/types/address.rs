@@ -39,6 +39,13 @@ impl Address {
         Self::new(buf)
     }
 
+    /// Generate a random address using the operating system's random number generator.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn random() -> Self {
+        Self::generate(rand_core::OsRng)
+    }
+
     /// Return the underlying byte array of a Address.
     pub const fn into_inner(self) -> [u8; Self::LENGTH] {
         self.0
@@ -52,6 +59,41 @@ impl Address {
         &self.0
     }
 
+    /// Parse an address from a hex string, e.g. `"0x6"`, in a const context.
+    ///
+    /// The `0x` prefix is optional and short strings are padded with leading zeros, as in
+    /// [`Address::from_hex`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hex` is not a valid address, which fails compilation when used to initialize a
+    /// constant.
+    pub const fn from_static_hex(hex: &'static str) -> Self {
+        let hex = match hex.as_bytes() {
+            [b'0', b'x', rest @ ..] => rest,
+            hex => hex,
+        };
+        assert!(!hex.is_empty(), "address has no hex digits");
+        assert!(
+            hex.len() <= Self::LENGTH * 2,
+            "address has too many hex digits"
+        );
+
+        let mut bytes = [0; Self::LENGTH];
+        let mut i = 0;
+        while i < hex.len() {
+            let digit = match hex[hex.len() - 1 - i] {
+                c @ b'0'..=b'9' => c - b'0',
+                c @ b'a'..=b'f' => c - b'a' + 10,
+                c @ b'A'..=b'F' => c - b'A' + 10,
+                _ => panic!("address has an invalid hex digit"),
+            };
+            bytes[Self::LENGTH - 1 - i / 2] |= digit << (4 * (i % 2));
+            i += 1;
+        }
+        Self(bytes)
+    }
+
     pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, AddressParseError> {
         let hex = hex.as_ref();
 
@@ -243,6 +285,29 @@ mod test {
         assert_eq!(actual.to_string(), expected);
     }
 
+    #[test]
+    fn static_hex_parsing() {
+        const CLOCK: Address = Address::from_static_hex("0x6");
+        assert_eq!(CLOCK, Address::from_hex("0x6").unwrap());
+        assert_eq!(Address::from_static_hex("2"), Address::TWO);
+        assert_eq!(
+            Address::from_static_hex("0x403"),
+            Address::from_hex("0x403").unwrap()
+        );
+
+        let full = "0x00112233445566778899AABBCCDDEEFF00112233445566778899aabbccddeeff";
+        assert_eq!(
+            Address::from_static_hex(full),
+            Address::from_hex(full).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn static_hex_parsing_rejects_invalid_digits() {
+        Address::from_static_hex("0xg");
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn formats() {
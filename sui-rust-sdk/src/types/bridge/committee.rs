@@ -0,0 +1,480 @@
+use super::BridgeMessage;
+use super::BridgeMessageError;
+use super::BridgeMessagePayload;
+use super::BridgeMessageType;
+use super::EmergencyOpType;
+use crate::types::Secp256k1PublicKey;
+
+/// The total voting power of the bridge committee, which approval thresholds are relative to.
+pub const BRIDGE_COMMITTEE_TOTAL_VOTING_POWER: u64 = 10_000;
+
+/// The committee of authorities approving bridge messages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BridgeCommittee {
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=4).lift()))]
+    pub members: Vec<BridgeCommitteeMember>,
+}
+
+impl BridgeCommittee {
+    pub fn new(members: Vec<BridgeCommitteeMember>) -> Self {
+        Self { members }
+    }
+
+    pub fn member(&self, public_key: &Secp256k1PublicKey) -> Option<&BridgeCommitteeMember> {
+        self.members
+            .iter()
+            .find(|member| &member.public_key == public_key)
+    }
+
+    /// The voting power of the members that are not blocklisted, failing if it overflows.
+    pub fn active_voting_power(&self) -> Result<u64, BridgeSignatureError> {
+        self.members
+            .iter()
+            .filter(|member| !member.blocklisted)
+            .try_fold(0, |total: u64, member| {
+                total.checked_add(member.voting_power)
+            })
+            .ok_or(BridgeSignatureError::VotingPowerOverflow)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BridgeCommitteeMember {
+    pub public_key: Secp256k1PublicKey,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub voting_power: u64,
+    /// Blocklisted members can not approve bridge messages.
+    pub blocklisted: bool,
+}
+
+/// A recoverable secp256k1 signature of a committee member over the
+/// `signing_digest` of a [`BridgeMessage`], laid out as `r || s || v`.
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BridgeAuthoritySignature(
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            with = "::serde_with::As::<::serde_with::IfIsHumanReadable<crate::_serde::Base64Encoded, [::serde_with::Same; 65]>>"
+        )
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    [u8; Self::LENGTH],
+);
+
+impl BridgeAuthoritySignature {
+    /// The length of a recoverable secp256k1 signature in bytes.
+    pub const LENGTH: usize = 65;
+
+    pub const fn new(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn inner(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, std::array::TryFromSliceError> {
+        <[u8; Self::LENGTH]>::try_from(bytes.as_ref()).map(Self)
+    }
+}
+
+impl AsRef<[u8]> for BridgeAuthoritySignature {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for BridgeAuthoritySignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BridgeAuthoritySignature")
+            .field(&format_args!("\"{}\"", hex::encode(self.0)))
+            .finish()
+    }
+}
+
+/// A bridge message together with the committee signatures approving it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CertifiedBridgeMessage {
+    pub message: BridgeMessage,
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=4).lift()))]
+    pub signatures: Vec<BridgeAuthoritySignature>,
+}
+
+impl BridgeMessage {
+    /// The voting power required to approve this message, mirroring the thresholds enforced by
+    /// the `bridge::message` module.
+    pub fn approval_threshold(&self) -> Result<u64, BridgeMessageError> {
+        let threshold = match self.message_type()? {
+            BridgeMessageType::TokenTransfer => 3334,
+            BridgeMessageType::EmergencyButton => match self.payload()? {
+                BridgeMessagePayload::EmergencyOp(EmergencyOpType::Pause) => 450,
+                _ => 5001,
+            },
+            BridgeMessageType::UpdateCommitteeBlocklist
+            | BridgeMessageType::LimitUpdate
+            | BridgeMessageType::AssetPriceUpdate
+            | BridgeMessageType::EvmContractUpgrade
+            | BridgeMessageType::AddTokensOnSui
+            | BridgeMessageType::AddTokensOnEvm => 5001,
+        };
+        Ok(threshold)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BridgeSignatureError {
+    Message(BridgeMessageError),
+    InvalidSignature,
+    UnknownSigner(Secp256k1PublicKey),
+    BlocklistedSigner(Secp256k1PublicKey),
+    DuplicateSigner(Secp256k1PublicKey),
+    InsufficientVotingPower {
+        voting_power: u64,
+        threshold: u64,
+    },
+    /// The combined voting power of committee members overflows.
+    VotingPowerOverflow,
+}
+
+impl std::fmt::Display for BridgeSignatureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Message(e) => write!(f, "{e}"),
+            Self::InvalidSignature => write!(f, "invalid bridge authority signature"),
+            Self::UnknownSigner(key) => write!(f, "signer {key} is not a committee member"),
+            Self::BlocklistedSigner(key) => write!(f, "signer {key} is blocklisted"),
+            Self::DuplicateSigner(key) => write!(f, "duplicate signature from {key}"),
+            Self::InsufficientVotingPower {
+                voting_power,
+                threshold,
+            } => write!(
+                f,
+                "insufficient voting power {voting_power}, {threshold} is required"
+            ),
+            Self::VotingPowerOverflow => write!(f, "voting power of the committee overflows"),
+        }
+    }
+}
+
+impl std::error::Error for BridgeSignatureError {}
+
+impl From<BridgeMessageError> for BridgeSignatureError {
+    fn from(e: BridgeMessageError) -> Self {
+        Self::Message(e)
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+mod verification {
+    use super::*;
+    use crate::types::EthAddress;
+    use std::collections::BTreeMap;
+
+    impl BridgeAuthoritySignature {
+        /// Recover the public key of the authority that signed `message`.
+        pub fn recover_public_key(
+            &self,
+            message: &BridgeMessage,
+        ) -> Result<Secp256k1PublicKey, BridgeSignatureError> {
+            use k256::ecdsa::RecoveryId;
+            use k256::ecdsa::Signature;
+            use k256::ecdsa::VerifyingKey;
+
+            let signature = Signature::from_slice(&self.0[..64])
+                .map_err(|_| BridgeSignatureError::InvalidSignature)?;
+            let recovery_id =
+                RecoveryId::from_byte(self.0[64]).ok_or(BridgeSignatureError::InvalidSignature)?;
+            let key = VerifyingKey::recover_from_prehash(
+                &message.signing_digest(),
+                &signature,
+                recovery_id,
+            )
+            .map_err(|_| BridgeSignatureError::InvalidSignature)?;
+
+            Secp256k1PublicKey::from_bytes(key.to_encoded_point(true).as_bytes())
+                .map_err(|_| BridgeSignatureError::InvalidSignature)
+        }
+    }
+
+    impl BridgeCommitteeMember {
+        /// The ethereum address of the member, which identifies it in committee blocklist
+        /// messages and on the ethereum side of the bridge.
+        ///
+        /// Returns `None` if the member's public key is not a valid secp256k1 point.
+        pub fn eth_address(&self) -> Option<EthAddress> {
+            use sha3::Digest as _;
+
+            let key =
+                k256::ecdsa::VerifyingKey::from_sec1_bytes(self.public_key.as_bytes()).ok()?;
+            let uncompressed = key.to_encoded_point(false);
+            let hash = sha3::Keccak256::digest(&uncompressed.as_bytes()[1..]);
+            EthAddress::from_bytes(&hash[32 - EthAddress::LENGTH..]).ok()
+        }
+    }
+
+    impl BridgeCommittee {
+        /// Verify that `signatures` are signatures of distinct, non-blocklisted committee members
+        /// over `message` whose combined voting power reaches the message's approval threshold.
+        ///
+        /// Returns the voting power of the signers.
+        pub fn verify(
+            &self,
+            message: &BridgeMessage,
+            signatures: &[BridgeAuthoritySignature],
+        ) -> Result<u64, BridgeSignatureError> {
+            let mut aggregator = BridgeSignatureAggregator::new(self, message.clone())?;
+            for signature in signatures {
+                aggregator.add_signature(*signature)?;
+            }
+            let voting_power = aggregator.voting_power();
+            aggregator.finish()?;
+            Ok(voting_power)
+        }
+    }
+
+    impl CertifiedBridgeMessage {
+        pub fn verify(&self, committee: &BridgeCommittee) -> Result<u64, BridgeSignatureError> {
+            committee.verify(&self.message, &self.signatures)
+        }
+    }
+
+    /// Collects committee signatures over a bridge message until its approval threshold is
+    /// reached.
+    pub struct BridgeSignatureAggregator<'a> {
+        committee: &'a BridgeCommittee,
+        message: BridgeMessage,
+        threshold: u64,
+        signatures: BTreeMap<Secp256k1PublicKey, BridgeAuthoritySignature>,
+        voting_power: u64,
+    }
+
+    impl<'a> BridgeSignatureAggregator<'a> {
+        pub fn new(
+            committee: &'a BridgeCommittee,
+            message: BridgeMessage,
+        ) -> Result<Self, BridgeSignatureError> {
+            let threshold = message.approval_threshold()?;
+            Ok(Self {
+                committee,
+                message,
+                threshold,
+                signatures: BTreeMap::new(),
+                voting_power: 0,
+            })
+        }
+
+        /// Add a signature, returning the voting power of the signer.
+        pub fn add_signature(
+            &mut self,
+            signature: BridgeAuthoritySignature,
+        ) -> Result<u64, BridgeSignatureError> {
+            let public_key = signature.recover_public_key(&self.message)?;
+            let member = self
+                .committee
+                .member(&public_key)
+                .ok_or(BridgeSignatureError::UnknownSigner(public_key))?;
+            if member.blocklisted {
+                return Err(BridgeSignatureError::BlocklistedSigner(public_key));
+            }
+            if self.signatures.contains_key(&public_key) {
+                return Err(BridgeSignatureError::DuplicateSigner(public_key));
+            }
+            self.voting_power = self
+                .voting_power
+                .checked_add(member.voting_power)
+                .ok_or(BridgeSignatureError::VotingPowerOverflow)?;
+            self.signatures.insert(public_key, signature);
+            Ok(member.voting_power)
+        }
+
+        pub fn voting_power(&self) -> u64 {
+            self.voting_power
+        }
+
+        pub fn threshold(&self) -> u64 {
+            self.threshold
+        }
+
+        pub fn has_quorum(&self) -> bool {
+            self.voting_power >= self.threshold
+        }
+
+        pub fn finish(self) -> Result<CertifiedBridgeMessage, BridgeSignatureError> {
+            if !self.has_quorum() {
+                return Err(BridgeSignatureError::InsufficientVotingPower {
+                    voting_power: self.voting_power,
+                    threshold: self.threshold,
+                });
+            }
+            Ok(CertifiedBridgeMessage {
+                message: self.message,
+                signatures: self.signatures.into_values().collect(),
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::types::BridgeChainId;
+        use crate::types::LimitUpdatePayload;
+        use k256::ecdsa::SigningKey;
+
+        #[cfg(target_arch = "wasm32")]
+        use wasm_bindgen_test::wasm_bindgen_test as test;
+
+        fn signing_key(seed: u8) -> SigningKey {
+            SigningKey::from_slice(&[seed; 32]).unwrap()
+        }
+
+        fn member(key: &SigningKey, voting_power: u64) -> BridgeCommitteeMember {
+            let public_key = key.verifying_key().to_encoded_point(true);
+            BridgeCommitteeMember {
+                public_key: Secp256k1PublicKey::from_bytes(public_key.as_bytes()).unwrap(),
+                voting_power,
+                blocklisted: false,
+            }
+        }
+
+        fn sign(key: &SigningKey, message: &BridgeMessage) -> BridgeAuthoritySignature {
+            let (signature, recovery_id) = key
+                .sign_prehash_recoverable(&message.signing_digest())
+                .unwrap();
+            let mut bytes = [0; BridgeAuthoritySignature::LENGTH];
+            bytes[..64].copy_from_slice(&signature.to_bytes());
+            bytes[64] = recovery_id.to_byte();
+            BridgeAuthoritySignature::new(bytes)
+        }
+
+        #[test]
+        fn member_eth_address() {
+            let mut secret = [0; 32];
+            secret[31] = 1;
+            let key = SigningKey::from_slice(&secret).unwrap();
+            assert_eq!(
+                member(&key, 0).eth_address().unwrap().to_checksum_string(),
+                "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+            );
+        }
+
+        #[test]
+        fn aggregate_signatures() {
+            let keys: Vec<_> = (1..=4).map(signing_key).collect();
+            let mut committee = BridgeCommittee::new(
+                keys.iter()
+                    .map(|key| member(key, BRIDGE_COMMITTEE_TOTAL_VOTING_POWER / 4))
+                    .collect(),
+            );
+            committee.members[3].blocklisted = true;
+            let message = BridgeMessage::new(
+                1,
+                BridgeChainId::SuiTestnet,
+                &BridgeMessagePayload::LimitUpdate(LimitUpdatePayload {
+                    sending_chain: BridgeChainId::EthSepolia,
+                    limit: 1_000_000,
+                }),
+            )
+            .unwrap();
+
+            let mut aggregator =
+                BridgeSignatureAggregator::new(&committee, message.clone()).unwrap();
+            assert_eq!(aggregator.threshold(), 5001);
+            aggregator.add_signature(sign(&keys[0], &message)).unwrap();
+            assert_eq!(
+                aggregator.add_signature(sign(&keys[0], &message)),
+                Err(BridgeSignatureError::DuplicateSigner(
+                    committee.members[0].public_key
+                ))
+            );
+            assert_eq!(
+                aggregator.add_signature(sign(&keys[3], &message)),
+                Err(BridgeSignatureError::BlocklistedSigner(
+                    committee.members[3].public_key
+                ))
+            );
+            aggregator.add_signature(sign(&keys[1], &message)).unwrap();
+            assert!(!aggregator.has_quorum());
+            aggregator.add_signature(sign(&keys[2], &message)).unwrap();
+            assert!(aggregator.has_quorum());
+
+            let certified = aggregator.finish().unwrap();
+            assert_eq!(certified.verify(&committee), Ok(7500));
+
+            // Signatures over a different message recover to unknown signers.
+            let other = BridgeMessage {
+                seq_num: 2,
+                ..message.clone()
+            };
+            assert!(matches!(
+                committee.verify(&other, &certified.signatures),
+                Err(BridgeSignatureError::UnknownSigner(_))
+            ));
+            assert_eq!(
+                committee.verify(&message, &certified.signatures[..2]),
+                Err(BridgeSignatureError::InsufficientVotingPower {
+                    voting_power: 5000,
+                    threshold: 5001
+                })
+            );
+        }
+
+        #[test]
+        fn voting_power_overflow() {
+            let keys: Vec<_> = (1..=2).map(signing_key).collect();
+            let committee = BridgeCommittee::new(
+                keys.iter()
+                    .map(|key| member(key, u64::MAX / 2 + 1))
+                    .collect(),
+            );
+            assert_eq!(
+                committee.active_voting_power(),
+                Err(BridgeSignatureError::VotingPowerOverflow)
+            );
+
+            let message = BridgeMessage::new(
+                1,
+                BridgeChainId::SuiTestnet,
+                &BridgeMessagePayload::EmergencyOp(EmergencyOpType::Pause),
+            )
+            .unwrap();
+            let mut aggregator =
+                BridgeSignatureAggregator::new(&committee, message.clone()).unwrap();
+            aggregator.add_signature(sign(&keys[0], &message)).unwrap();
+            assert_eq!(
+                aggregator.add_signature(sign(&keys[1], &message)),
+                Err(BridgeSignatureError::VotingPowerOverflow)
+            );
+            assert_eq!(aggregator.voting_power(), u64::MAX / 2 + 1);
+            assert_eq!(aggregator.finish().unwrap().signatures.len(), 1);
+        }
+    }
+}
+
+#[cfg(feature = "secp256k1")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256k1")))]
+pub use verification::BridgeSignatureAggregator;
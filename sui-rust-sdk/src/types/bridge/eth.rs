@@ -0,0 +1,348 @@
+use super::BridgeChainId;
+use super::BridgeMessage;
+use super::CertifiedBridgeMessage;
+
+/// A 20-byte ethereum address.
+#[derive(Clone, Copy, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct EthAddress(
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "::serde_with::As::<::serde_with::IfIsHumanReadable<ReadableEthAddress>>")
+    )]
+    [u8; Self::LENGTH],
+);
+
+impl EthAddress {
+    pub const LENGTH: usize = 20;
+    pub const ZERO: Self = Self([0u8; Self::LENGTH]);
+
+    pub const fn new(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn into_inner(self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub const fn inner(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
+
+    pub const fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Parse a `0x` prefixed hex string of exactly 40 hex digits, in any case.
+    pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, EthAddressParseError> {
+        let hex = hex
+            .as_ref()
+            .strip_prefix(b"0x")
+            .ok_or(EthAddressParseError)?;
+
+        <[u8; Self::LENGTH] as hex::FromHex>::from_hex(hex)
+            .map(Self)
+            .map_err(|_| EthAddressParseError)
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, EthAddressParseError> {
+        <[u8; Self::LENGTH]>::try_from(bytes.as_ref())
+            .map_err(|_| EthAddressParseError)
+            .map(Self)
+    }
+}
+
+impl std::str::FromStr for EthAddress {
+    type Err = EthAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+impl AsRef<[u8]> for EthAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<EthAddress> for [u8; EthAddress::LENGTH] {
+    fn from(address: EthAddress) -> Self {
+        address.into_inner()
+    }
+}
+
+impl From<[u8; EthAddress::LENGTH]> for EthAddress {
+    fn from(address: [u8; EthAddress::LENGTH]) -> Self {
+        Self::new(address)
+    }
+}
+
+impl std::fmt::Display for EthAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl std::fmt::Debug for EthAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("EthAddress")
+            .field(&format_args!("\"{}\"", self))
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+struct ReadableEthAddress;
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl serde_with::SerializeAs<[u8; EthAddress::LENGTH]> for ReadableEthAddress {
+    fn serialize_as<S>(source: &[u8; EthAddress::LENGTH], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let address = EthAddress::new(*source);
+        serde_with::DisplayFromStr::serialize_as(&address, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl<'de> serde_with::DeserializeAs<'de, [u8; EthAddress::LENGTH]> for ReadableEthAddress {
+    fn deserialize_as<D>(deserializer: D) -> Result<[u8; EthAddress::LENGTH], D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let address: EthAddress = serde_with::DisplayFromStr::deserialize_as(deserializer)?;
+        Ok(address.into_inner())
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for EthAddress {
+    fn schema_name() -> String {
+        "EthAddress".to_owned()
+    }
+
+    fn json_schema(_: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        use schemars::schema::InstanceType;
+        use schemars::schema::Metadata;
+        use schemars::schema::SchemaObject;
+        use schemars::schema::StringValidation;
+
+        let hex_length = EthAddress::LENGTH * 2;
+        SchemaObject {
+            metadata: Some(Box::new(Metadata {
+                title: Some(Self::schema_name()),
+                description: Some(
+                    "A 20-byte ethereum address, encoded as a hex string.".to_owned(),
+                ),
+                ..Default::default()
+            })),
+            instance_type: Some(InstanceType::String.into()),
+            format: Some("hex".to_owned()),
+            string: Some(Box::new(StringValidation {
+                max_length: Some((hex_length + 2) as u32),
+                min_length: Some((hex_length + 2) as u32),
+                pattern: Some(format!("0x[a-fA-F0-9]{{{hex_length}}}")),
+            })),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EthAddressParseError;
+
+impl std::fmt::Display for EthAddressParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Unable to parse EthAddress (must be a 0x prefixed hex string of length {})",
+            EthAddress::LENGTH
+        )
+    }
+}
+
+impl std::error::Error for EthAddressParseError {}
+
+impl BridgeChainId {
+    /// The EIP-155 chain id of the ethereum network, for the well known ethereum chains.
+    ///
+    /// Returns `None` for Sui chains and for `EthCustom`, whose network is deployment specific.
+    pub fn evm_chain_id(self) -> Option<u64> {
+        match self {
+            Self::EthMainnet => Some(1),
+            Self::EthSepolia => Some(11155111),
+            Self::SuiMainnet | Self::SuiTestnet | Self::SuiCustom | Self::EthCustom => None,
+        }
+    }
+}
+
+impl BridgeMessage {
+    /// ABI encode the message as the `BridgeUtils.Message` struct of the ethereum bridge
+    /// contracts, i.e. the tuple `(uint8 messageType, uint8 version, uint64 nonce, uint8 chainID,
+    /// bytes payload)`.
+    pub fn to_eth_abi(&self) -> Vec<u8> {
+        let mut encoded = Vec::with_capacity(7 * ABI_WORD + self.payload.len());
+        encoded.extend(abi_uint(self.message_type.into()));
+        encoded.extend(abi_uint(self.message_version.into()));
+        encoded.extend(abi_uint(self.seq_num));
+        encoded.extend(abi_uint(self.source_chain.into()));
+        // Offset of the payload, following the 5 head words
+        encoded.extend(abi_uint(5 * ABI_WORD as u64));
+        encoded.extend(abi_bytes(&self.payload));
+        encoded
+    }
+}
+
+impl CertifiedBridgeMessage {
+    /// The selector of `transferBridgedTokensWithSignatures(bytes[],(uint8,uint8,uint64,uint8,bytes))`
+    /// of the `SuiBridge` ethereum contract, which claims a token transfer on ethereum.
+    pub const ETH_CLAIM_SELECTOR: [u8; 4] = [0xbe, 0xb0, 0xd5, 0x5c];
+
+    /// The calldata of a `transferBridgedTokensWithSignatures` call to the `SuiBridge` ethereum
+    /// contract, claiming the token transfer of this message with its committee signatures.
+    pub fn to_eth_claim_calldata(&self) -> Vec<u8> {
+        let signatures = abi_bytes_array(self.signatures.iter().map(|s| s.as_ref()));
+        let message = self.message.to_eth_abi();
+
+        let mut calldata = Self::ETH_CLAIM_SELECTOR.to_vec();
+        // Both arguments are dynamic so the head holds their offsets
+        calldata.extend(abi_uint(2 * ABI_WORD as u64));
+        calldata.extend(abi_uint((2 * ABI_WORD + signatures.len()) as u64));
+        calldata.extend(signatures);
+        calldata.extend(message);
+        calldata
+    }
+}
+
+const ABI_WORD: usize = 32;
+
+fn abi_uint(value: u64) -> [u8; ABI_WORD] {
+    let mut word = [0; ABI_WORD];
+    word[ABI_WORD - 8..].copy_from_slice(&value.to_be_bytes());
+    word
+}
+
+/// Encode a `bytes` value: its length followed by its content, right padded to a whole word.
+fn abi_bytes(bytes: &[u8]) -> Vec<u8> {
+    let padded_len = bytes.len().div_ceil(ABI_WORD) * ABI_WORD;
+    let mut encoded = Vec::with_capacity(ABI_WORD + padded_len);
+    encoded.extend(abi_uint(bytes.len() as u64));
+    encoded.extend_from_slice(bytes);
+    encoded.resize(ABI_WORD + padded_len, 0);
+    encoded
+}
+
+/// Encode a `bytes[]` value: its length, the offsets of the elements relative to the end of the
+/// length word, then the elements.
+fn abi_bytes_array<'a>(items: impl ExactSizeIterator<Item = &'a [u8]>) -> Vec<u8> {
+    let mut head = abi_uint(items.len() as u64).to_vec();
+    let mut tail = Vec::new();
+    let offset = items.len() * ABI_WORD;
+    for item in items {
+        head.extend(abi_uint((offset + tail.len()) as u64));
+        tail.extend(abi_bytes(item));
+    }
+    head.extend(tail);
+    head
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::BridgeAuthoritySignature;
+    use crate::types::BridgeMessagePayload;
+    use crate::types::TokenTransferPayload;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn eth_address_hex() {
+        let address: EthAddress = "0x52908400098527886E0F7030069857D2E4169EE7"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            address.to_string(),
+            "0x52908400098527886e0f7030069857d2e4169ee7"
+        );
+        assert!("52908400098527886E0F7030069857D2E4169EE7"
+            .parse::<EthAddress>()
+            .is_err());
+        assert!("0x5290".parse::<EthAddress>().is_err());
+    }
+
+    #[proptest]
+    fn roundtrip_display_fromstr(address: EthAddress) {
+        let s = address.to_string();
+        let a = s.parse::<EthAddress>().unwrap();
+        assert_eq!(address, a);
+    }
+
+    #[test]
+    fn eth_claim_calldata() {
+        let message = BridgeMessage::new(
+            7,
+            BridgeChainId::SuiTestnet,
+            &BridgeMessagePayload::TokenTransfer(TokenTransferPayload {
+                sender_address: vec![0xaa; 32],
+                target_chain: BridgeChainId::EthSepolia,
+                target_address: vec![0xbb; 20],
+                token_type: 2,
+                amount: 1000,
+            }),
+        )
+        .unwrap();
+        let certified = CertifiedBridgeMessage {
+            message: message.clone(),
+            signatures: vec![BridgeAuthoritySignature::new([0xcc; 65])],
+        };
+
+        let words = |bytes: &[u8]| bytes.chunks(ABI_WORD).map(hex::encode).collect::<Vec<_>>();
+        let uint = |value: u64| hex::encode(abi_uint(value));
+
+        let abi = message.to_eth_abi();
+        // 5 head words, the payload length, and the 64 byte payload in 3 words
+        assert_eq!(message.payload.len(), 64);
+        assert_eq!(
+            words(&abi)[..6],
+            [uint(0), uint(1), uint(7), uint(1), uint(160), uint(64)]
+        );
+
+        let calldata = certified.to_eth_claim_calldata();
+        assert_eq!(calldata[..4], CertifiedBridgeMessage::ETH_CLAIM_SELECTOR);
+        let calldata = words(&calldata[4..]);
+        // The offsets of the arguments, then the signatures: one element at offset 32 holding 65
+        // bytes padded to 3 words, followed by the message
+        assert_eq!(
+            calldata[..6],
+            [
+                uint(64),
+                uint(64 + 6 * 32),
+                uint(1),
+                uint(32),
+                uint(65),
+                hex::encode([0xcc; 32])
+            ]
+        );
+        assert_eq!(
+            calldata[7],
+            hex::encode([[0xcc].as_slice(), &[0; 31]].concat())
+        );
+        assert_eq!(calldata[8..], words(&abi));
+    }
+}
@@ -0,0 +1,438 @@
+use super::BridgeChainId;
+use super::BridgeMessage;
+use super::BridgeMessageError;
+use super::BridgeMessagePayload;
+use super::TokenTransferPayload;
+use crate::types::Address;
+
+/// The address of the Sui Bridge package.
+pub const BRIDGE_PACKAGE_ADDRESS: Address = crate::types::well_known::BRIDGE_ADDRESS;
+
+/// An event emitted by the Sui Bridge package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub enum BridgeEvent {
+    TokenDeposited(TokenDepositedEvent),
+    EmergencyOp(EmergencyOpEvent),
+    TokenTransferApproved(BridgeMessageKey),
+    TokenTransferClaimed(BridgeMessageKey),
+    TokenTransferAlreadyApproved(BridgeMessageKey),
+    TokenTransferAlreadyClaimed(BridgeMessageKey),
+    TokenTransferLimitExceed(BridgeMessageKey),
+    BlocklistValidator(BlocklistValidatorEvent),
+    CommitteeUpdate(CommitteeUpdateEvent),
+    UpdateRouteLimit(UpdateRouteLimitEvent),
+    UpdateTokenPrice(UpdateTokenPriceEvent),
+    NewToken(NewTokenEvent),
+    TokenRegistration(TokenRegistrationEvent),
+}
+
+/// Emitted by `bridge::bridge::send_token` when tokens are deposited to be bridged out of Sui.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct TokenDepositedEvent {
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub seq_num: u64,
+    pub source_chain: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub sender_address: Vec<u8>,
+    pub target_chain: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub target_address: Vec<u8>,
+    pub token_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub amount: u64,
+}
+
+impl TokenDepositedEvent {
+    /// The token transfer message the bridge committee signs for this deposit.
+    pub fn to_bridge_message(&self) -> Result<BridgeMessage, BridgeMessageError> {
+        let payload = BridgeMessagePayload::TokenTransfer(TokenTransferPayload {
+            sender_address: self.sender_address.clone(),
+            target_chain: BridgeChainId::from_byte(self.target_chain)?,
+            target_address: self.target_address.clone(),
+            token_type: self.token_type,
+            amount: self.amount,
+        });
+        BridgeMessage::new(
+            self.seq_num,
+            BridgeChainId::from_byte(self.source_chain)?,
+            &payload,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct EmergencyOpEvent {
+    pub frozen: bool,
+}
+
+/// Identifies a bridge message, as the `bridge::message::BridgeMessageKey` struct.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BridgeMessageKey {
+    pub source_chain: u8,
+    pub message_type: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub bridge_seq_num: u64,
+}
+
+impl BridgeMessage {
+    pub fn key(&self) -> BridgeMessageKey {
+        BridgeMessageKey {
+            source_chain: self.source_chain,
+            message_type: self.message_type,
+            bridge_seq_num: self.seq_num,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BlocklistValidatorEvent {
+    pub blocklisted: bool,
+    /// The compressed secp256k1 public keys of the committee members.
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
+    pub public_keys: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CommitteeUpdateEvent {
+    /// The entries of the `VecMap` of committee members, keyed by their public key.
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
+    pub members: Vec<CommitteeUpdateEntry>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub stake_participation_percentage: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CommitteeUpdateEntry {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub key: Vec<u8>,
+    pub value: CommitteeMemberInfo,
+}
+
+/// A member of the bridge committee, as the `bridge::committee::CommitteeMember` struct.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CommitteeMemberInfo {
+    pub sui_address: Address,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub bridge_pubkey_bytes: Vec<u8>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub voting_power: u64,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub http_rest_url: Vec<u8>,
+    pub blocklisted: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct UpdateRouteLimitEvent {
+    pub sending_chain: u8,
+    pub receiving_chain: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub new_limit: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct UpdateTokenPriceEvent {
+    pub token_id: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub new_price: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct NewTokenEvent {
+    pub token_id: u8,
+    /// The `std::type_name::TypeName` of the token's coin type.
+    pub type_name: String,
+    pub native_token: bool,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub decimal_multiplier: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub notional_value: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct TokenRegistrationEvent {
+    /// The `std::type_name::TypeName` of the token's coin type.
+    pub type_name: String,
+    pub decimal: u8,
+    pub native_token: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BridgeEventError {
+    /// The event is not emitted by the bridge package.
+    NotBridgeEvent,
+    UnknownEventType {
+        module: String,
+        name: String,
+    },
+    InvalidContents,
+}
+
+impl std::fmt::Display for BridgeEventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotBridgeEvent => write!(f, "not a bridge event"),
+            Self::UnknownEventType { module, name } => {
+                write!(f, "unknown bridge event type {module}::{name}")
+            }
+            Self::InvalidContents => write!(f, "invalid bridge event contents"),
+        }
+    }
+}
+
+impl std::error::Error for BridgeEventError {}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl BridgeEvent {
+    /// Decode a bridge event from the BCS contents of `event`, according to its type.
+    pub fn try_from_event(event: &crate::types::Event) -> Result<Self, BridgeEventError> {
+        fn decode<T: serde::de::DeserializeOwned>(contents: &[u8]) -> Result<T, BridgeEventError> {
+            bcs::from_bytes(contents).map_err(|_| BridgeEventError::InvalidContents)
+        }
+
+        let type_ = &event.type_;
+        if type_.address != BRIDGE_PACKAGE_ADDRESS || !type_.type_params.is_empty() {
+            return Err(BridgeEventError::NotBridgeEvent);
+        }
+
+        let contents = &event.contents;
+        let event = match (type_.module.as_str(), type_.name.as_str()) {
+            ("bridge", "TokenDepositedEvent") => Self::TokenDeposited(decode(contents)?),
+            ("bridge", "EmergencyOpEvent") => Self::EmergencyOp(decode(contents)?),
+            ("bridge", "TokenTransferApproved") => Self::TokenTransferApproved(decode(contents)?),
+            ("bridge", "TokenTransferClaimed") => Self::TokenTransferClaimed(decode(contents)?),
+            ("bridge", "TokenTransferAlreadyApproved") => {
+                Self::TokenTransferAlreadyApproved(decode(contents)?)
+            }
+            ("bridge", "TokenTransferAlreadyClaimed") => {
+                Self::TokenTransferAlreadyClaimed(decode(contents)?)
+            }
+            ("bridge", "TokenTransferLimitExceed") => {
+                Self::TokenTransferLimitExceed(decode(contents)?)
+            }
+            ("committee", "BlocklistValidatorEvent") => Self::BlocklistValidator(decode(contents)?),
+            ("committee", "CommitteeUpdateEvent") => Self::CommitteeUpdate(decode(contents)?),
+            ("limiter", "UpdateRouteLimitEvent") => Self::UpdateRouteLimit(decode(contents)?),
+            ("treasury", "UpdateTokenPriceEvent") => Self::UpdateTokenPrice(decode(contents)?),
+            ("treasury", "NewTokenEvent") => Self::NewToken(decode(contents)?),
+            ("treasury", "TokenRegistrationEvent") => Self::TokenRegistration(decode(contents)?),
+            (module, name) => {
+                return Err(BridgeEventError::UnknownEventType {
+                    module: module.to_owned(),
+                    name: name.to_owned(),
+                })
+            }
+        };
+        Ok(event)
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use super::*;
+    use crate::types::Event;
+    use crate::types::Identifier;
+    use crate::types::ObjectId;
+    use crate::types::StructTag;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn event(module: &str, name: &str, contents: Vec<u8>) -> Event {
+        Event {
+            package_id: ObjectId::from(BRIDGE_PACKAGE_ADDRESS),
+            module: Identifier::new(module).unwrap(),
+            sender: Address::ZERO,
+            type_: StructTag {
+                address: BRIDGE_PACKAGE_ADDRESS,
+                module: Identifier::new(module).unwrap(),
+                name: Identifier::new(name).unwrap(),
+                type_params: vec![],
+            },
+            contents,
+        }
+    }
+
+    #[test]
+    fn decode_token_deposited() {
+        // seq_num 3, from sui testnet to sepolia, token 2, amount 1000
+        let mut contents = vec![3, 0, 0, 0, 0, 0, 0, 0, 1, 32];
+        contents.extend([0xaa; 32]);
+        contents.extend([11, 20]);
+        contents.extend([0xbb; 20]);
+        contents.extend([2, 0xe8, 0x03, 0, 0, 0, 0, 0, 0]);
+
+        let BridgeEvent::TokenDeposited(deposited) =
+            BridgeEvent::try_from_event(&event("bridge", "TokenDepositedEvent", contents)).unwrap()
+        else {
+            panic!("expected a TokenDepositedEvent");
+        };
+        assert_eq!(
+            deposited,
+            TokenDepositedEvent {
+                seq_num: 3,
+                source_chain: 1,
+                sender_address: vec![0xaa; 32],
+                target_chain: 11,
+                target_address: vec![0xbb; 20],
+                token_type: 2,
+                amount: 1000,
+            }
+        );
+
+        let message = deposited.to_bridge_message().unwrap();
+        assert_eq!(
+            message.key(),
+            BridgeMessageKey {
+                source_chain: 1,
+                message_type: 0,
+                bridge_seq_num: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn decode_message_key_events() {
+        let contents = vec![1, 0, 7, 0, 0, 0, 0, 0, 0, 0];
+        let key = BridgeMessageKey {
+            source_chain: 1,
+            message_type: 0,
+            bridge_seq_num: 7,
+        };
+        assert_eq!(
+            BridgeEvent::try_from_event(&event("bridge", "TokenTransferClaimed", contents)),
+            Ok(BridgeEvent::TokenTransferClaimed(key))
+        );
+    }
+
+    #[test]
+    fn invalid_events() {
+        let mut not_bridge = event("bridge", "EmergencyOpEvent", vec![1]);
+        not_bridge.type_.address = Address::TWO;
+        assert_eq!(
+            BridgeEvent::try_from_event(&not_bridge),
+            Err(BridgeEventError::NotBridgeEvent)
+        );
+        assert_eq!(
+            BridgeEvent::try_from_event(&event("bridge", "Unknown", vec![])),
+            Err(BridgeEventError::UnknownEventType {
+                module: "bridge".to_owned(),
+                name: "Unknown".to_owned()
+            })
+        );
+        assert_eq!(
+            BridgeEvent::try_from_event(&event("bridge", "EmergencyOpEvent", vec![1, 0])),
+            Err(BridgeEventError::InvalidContents)
+        );
+    }
+
+    #[test_strategy::proptest]
+    fn roundtrip_bcs(deposited: TokenDepositedEvent) {
+        let contents = bcs::to_bytes(&deposited).unwrap();
+        assert_eq!(
+            BridgeEvent::try_from_event(&event("bridge", "TokenDepositedEvent", contents)),
+            Ok(BridgeEvent::TokenDeposited(deposited))
+        );
+    }
+}
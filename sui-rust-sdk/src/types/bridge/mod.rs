@@ -0,0 +1,555 @@
+mod committee;
+mod eth;
+mod events;
+
+pub use committee::BridgeAuthoritySignature;
+pub use committee::BridgeCommittee;
+pub use committee::BridgeCommitteeMember;
+pub use committee::BridgeSignatureError;
+pub use committee::CertifiedBridgeMessage;
+pub use committee::BRIDGE_COMMITTEE_TOTAL_VOTING_POWER;
+pub use eth::EthAddress;
+pub use eth::EthAddressParseError;
+pub use events::BlocklistValidatorEvent;
+pub use events::BridgeEvent;
+pub use events::BridgeEventError;
+pub use events::BridgeMessageKey;
+pub use events::CommitteeMemberInfo;
+pub use events::CommitteeUpdateEntry;
+pub use events::CommitteeUpdateEvent;
+pub use events::EmergencyOpEvent;
+pub use events::NewTokenEvent;
+pub use events::TokenDepositedEvent;
+pub use events::TokenRegistrationEvent;
+pub use events::UpdateRouteLimitEvent;
+pub use events::UpdateTokenPriceEvent;
+pub use events::BRIDGE_PACKAGE_ADDRESS;
+
+#[cfg(feature = "secp256k1")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256k1")))]
+pub use committee::BridgeSignatureAggregator;
+
+/// Prefix prepended to the encoded message bytes before they are hashed and signed by the bridge
+/// committee, acting as the bridge's signing intent.
+pub const BRIDGE_MESSAGE_PREFIX: &[u8] = b"SUI_BRIDGE_MESSAGE";
+
+/// The version of the message formats produced by this module.
+pub const BRIDGE_MESSAGE_VERSION: u8 = 1;
+
+/// Length of the ethereum style addresses used to identify bridge committee members.
+pub const BRIDGE_COMMITTEE_MEMBER_ADDRESS_LENGTH: usize = EthAddress::LENGTH;
+
+/// A message of the Sui Bridge, as stored on chain by the `bridge::message` module.
+///
+/// The payload is kept in its encoded form, use [`BridgeMessage::payload`] to decode it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct BridgeMessage {
+    pub message_type: u8,
+    pub message_version: u8,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub seq_num: u64,
+    pub source_chain: u8,
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "crate::_serde::ReadableBase64Encoded")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::Base64"))]
+    pub payload: Vec<u8>,
+}
+
+impl BridgeMessage {
+    /// Create a message originating from `source_chain` with the current message version.
+    ///
+    /// Fails if the payload can't be encoded, see [`BridgeMessagePayload::to_bytes`].
+    pub fn new(
+        seq_num: u64,
+        source_chain: BridgeChainId,
+        payload: &BridgeMessagePayload,
+    ) -> Result<Self, BridgeMessageError> {
+        Ok(Self {
+            message_type: payload.message_type().to_u8(),
+            message_version: BRIDGE_MESSAGE_VERSION,
+            seq_num,
+            source_chain: source_chain.to_u8(),
+            payload: payload.to_bytes()?,
+        })
+    }
+
+    pub fn message_type(&self) -> Result<BridgeMessageType, BridgeMessageError> {
+        BridgeMessageType::from_byte(self.message_type)
+    }
+
+    pub fn source_chain(&self) -> Result<BridgeChainId, BridgeMessageError> {
+        BridgeChainId::from_byte(self.source_chain)
+    }
+
+    /// Decode the payload of the message according to its message type.
+    pub fn payload(&self) -> Result<BridgeMessagePayload, BridgeMessageError> {
+        BridgeMessagePayload::from_bytes(self.message_type()?, &self.payload)
+    }
+
+    /// Encode the message the same way as `bridge::message::serialize_message`:
+    /// `message_type || message_version || seq_num (big endian) || source_chain || payload`.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(11 + self.payload.len());
+        bytes.push(self.message_type);
+        bytes.push(self.message_version);
+        bytes.extend_from_slice(&self.seq_num.to_be_bytes());
+        bytes.push(self.source_chain);
+        bytes.extend_from_slice(&self.payload);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BridgeMessageError> {
+        let mut reader = PayloadReader(bytes);
+        let message_type = reader.read_u8()?;
+        let message_version = reader.read_u8()?;
+        let seq_num = reader.read_u64()?;
+        let source_chain = reader.read_u8()?;
+
+        Ok(Self {
+            message_type,
+            message_version,
+            seq_num,
+            source_chain,
+            payload: reader.0.to_vec(),
+        })
+    }
+
+    /// The bytes signed by the bridge committee: the encoded message prefixed with
+    /// [`BRIDGE_MESSAGE_PREFIX`].
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut bytes = BRIDGE_MESSAGE_PREFIX.to_vec();
+        bytes.extend(self.to_bytes());
+        bytes
+    }
+}
+
+/// The chains connected by the Sui Bridge.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum BridgeChainId {
+    SuiMainnet = 0,
+    SuiTestnet = 1,
+    SuiCustom = 2,
+    EthMainnet = 10,
+    EthSepolia = 11,
+    EthCustom = 12,
+}
+
+impl BridgeChainId {
+    pub fn from_byte(byte: u8) -> Result<Self, BridgeMessageError> {
+        match byte {
+            0 => Ok(Self::SuiMainnet),
+            1 => Ok(Self::SuiTestnet),
+            2 => Ok(Self::SuiCustom),
+            10 => Ok(Self::EthMainnet),
+            11 => Ok(Self::EthSepolia),
+            12 => Ok(Self::EthCustom),
+            invalid => Err(BridgeMessageError::InvalidChainId(invalid)),
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+
+    pub fn is_sui_chain(self) -> bool {
+        matches!(self, Self::SuiMainnet | Self::SuiTestnet | Self::SuiCustom)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum BridgeMessageType {
+    TokenTransfer = 0,
+    UpdateCommitteeBlocklist = 1,
+    EmergencyButton = 2,
+    LimitUpdate = 3,
+    AssetPriceUpdate = 4,
+    EvmContractUpgrade = 5,
+    AddTokensOnSui = 6,
+    AddTokensOnEvm = 7,
+}
+
+impl BridgeMessageType {
+    pub fn from_byte(byte: u8) -> Result<Self, BridgeMessageError> {
+        match byte {
+            0 => Ok(Self::TokenTransfer),
+            1 => Ok(Self::UpdateCommitteeBlocklist),
+            2 => Ok(Self::EmergencyButton),
+            3 => Ok(Self::LimitUpdate),
+            4 => Ok(Self::AssetPriceUpdate),
+            5 => Ok(Self::EvmContractUpgrade),
+            6 => Ok(Self::AddTokensOnSui),
+            7 => Ok(Self::AddTokensOnEvm),
+            invalid => Err(BridgeMessageError::InvalidMessageType(invalid)),
+        }
+    }
+
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+/// The decoded payload of a [`BridgeMessage`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub enum BridgeMessagePayload {
+    TokenTransfer(TokenTransferPayload),
+    CommitteeBlocklist(CommitteeBlocklistPayload),
+    EmergencyOp(EmergencyOpType),
+    LimitUpdate(LimitUpdatePayload),
+}
+
+impl BridgeMessagePayload {
+    pub fn message_type(&self) -> BridgeMessageType {
+        match self {
+            Self::TokenTransfer(_) => BridgeMessageType::TokenTransfer,
+            Self::CommitteeBlocklist(_) => BridgeMessageType::UpdateCommitteeBlocklist,
+            Self::EmergencyOp(_) => BridgeMessageType::EmergencyButton,
+            Self::LimitUpdate(_) => BridgeMessageType::LimitUpdate,
+        }
+    }
+
+    /// Encode the payload the same way as the `bridge::message` module.
+    ///
+    /// Addresses and the list of blocklisted members are prefixed with their length in a single
+    /// byte, which holds for both Sui and ethereum addresses, and fails with
+    /// [`BridgeMessageError::TooLong`] for any longer.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, BridgeMessageError> {
+        fn length(field: &'static str, len: usize) -> Result<u8, BridgeMessageError> {
+            u8::try_from(len).map_err(|_| BridgeMessageError::TooLong { field, len })
+        }
+
+        let mut bytes = Vec::new();
+        match self {
+            Self::TokenTransfer(payload) => {
+                bytes.push(length("sender_address", payload.sender_address.len())?);
+                bytes.extend_from_slice(&payload.sender_address);
+                bytes.push(payload.target_chain.to_u8());
+                bytes.push(length("target_address", payload.target_address.len())?);
+                bytes.extend_from_slice(&payload.target_address);
+                bytes.push(payload.token_type);
+                bytes.extend_from_slice(&payload.amount.to_be_bytes());
+            }
+            Self::CommitteeBlocklist(payload) => {
+                bytes.push(payload.blocklist_type as u8);
+                bytes.push(length("members", payload.members.len())?);
+                for member in &payload.members {
+                    bytes.extend_from_slice(member.as_bytes());
+                }
+            }
+            Self::EmergencyOp(op) => bytes.push(*op as u8),
+            Self::LimitUpdate(payload) => {
+                bytes.push(payload.sending_chain.to_u8());
+                bytes.extend_from_slice(&payload.limit.to_be_bytes());
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Decode a payload of the given message type, following the rules of the `extract_*`
+    /// functions of the `bridge::message` module.
+    pub fn from_bytes(
+        message_type: BridgeMessageType,
+        bytes: &[u8],
+    ) -> Result<Self, BridgeMessageError> {
+        let mut reader = PayloadReader(bytes);
+        let payload = match message_type {
+            BridgeMessageType::TokenTransfer => {
+                let sender_address = reader.read_vec()?;
+                let target_chain = BridgeChainId::from_byte(reader.read_u8()?)?;
+                let target_address = reader.read_vec()?;
+                let token_type = reader.read_u8()?;
+                let amount = reader.read_u64()?;
+                Self::TokenTransfer(TokenTransferPayload {
+                    sender_address,
+                    target_chain,
+                    target_address,
+                    token_type,
+                    amount,
+                })
+            }
+            BridgeMessageType::UpdateCommitteeBlocklist => {
+                let blocklist_type = match reader.read_u8()? {
+                    0 => BlocklistType::Blocklist,
+                    1 => BlocklistType::Unblocklist,
+                    _ => return Err(BridgeMessageError::InvalidPayload),
+                };
+                let count = reader.read_u8()?;
+                if count == 0 {
+                    return Err(BridgeMessageError::InvalidPayload);
+                }
+                let members = (0..count)
+                    .map(|_| reader.read_array().map(EthAddress::new))
+                    .collect::<Result<_, _>>()?;
+                Self::CommitteeBlocklist(CommitteeBlocklistPayload {
+                    blocklist_type,
+                    members,
+                })
+            }
+            BridgeMessageType::EmergencyButton => match reader.read_u8()? {
+                0 => Self::EmergencyOp(EmergencyOpType::Pause),
+                1 => Self::EmergencyOp(EmergencyOpType::Unpause),
+                _ => return Err(BridgeMessageError::InvalidPayload),
+            },
+            BridgeMessageType::LimitUpdate => {
+                let sending_chain = BridgeChainId::from_byte(reader.read_u8()?)?;
+                let limit = reader.read_u64()?;
+                Self::LimitUpdate(LimitUpdatePayload {
+                    sending_chain,
+                    limit,
+                })
+            }
+            unsupported => return Err(BridgeMessageError::UnsupportedMessageType(unsupported)),
+        };
+
+        if !reader.0.is_empty() {
+            return Err(BridgeMessageError::TrailingBytes);
+        }
+        Ok(payload)
+    }
+}
+
+/// A transfer of tokens from one chain to another.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct TokenTransferPayload {
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=32).lift()))]
+    pub sender_address: Vec<u8>,
+    pub target_chain: BridgeChainId,
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=32).lift()))]
+    pub target_address: Vec<u8>,
+    pub token_type: u8,
+    pub amount: u64,
+}
+
+/// An update to the committee members that are blocked from signing bridge messages.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CommitteeBlocklistPayload {
+    pub blocklist_type: BlocklistType,
+    /// The ethereum style addresses derived from the members' public keys.
+    #[cfg_attr(test, any(proptest::collection::size_range(1..=3).lift()))]
+    pub members: Vec<EthAddress>,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum BlocklistType {
+    Blocklist = 0,
+    Unblocklist = 1,
+}
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum EmergencyOpType {
+    Pause = 0,
+    Unpause = 1,
+}
+
+/// An update to the limit of the route from `sending_chain` to the chain the message originates
+/// from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct LimitUpdatePayload {
+    pub sending_chain: BridgeChainId,
+    /// The new limit in USD, with 4 decimal places.
+    pub limit: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BridgeMessageError {
+    InvalidChainId(u8),
+    InvalidMessageType(u8),
+    UnsupportedMessageType(BridgeMessageType),
+    InvalidPayload,
+    UnexpectedEnd,
+    TrailingBytes,
+    /// A field of a payload is longer than its single byte length prefix can hold.
+    TooLong {
+        field: &'static str,
+        len: usize,
+    },
+}
+
+impl std::fmt::Display for BridgeMessageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidChainId(id) => write!(f, "invalid bridge chain id {id}"),
+            Self::InvalidMessageType(t) => write!(f, "invalid bridge message type {t}"),
+            Self::UnsupportedMessageType(t) => write!(f, "unsupported bridge message type {t:?}"),
+            Self::InvalidPayload => write!(f, "invalid bridge message payload"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of bridge message"),
+            Self::TrailingBytes => write!(f, "trailing bytes in bridge message"),
+            Self::TooLong { field, len } => {
+                write!(f, "bridge message {field} of length {len} exceeds 255")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BridgeMessageError {}
+
+struct PayloadReader<'a>(&'a [u8]);
+
+impl PayloadReader<'_> {
+    fn read_bytes(&mut self, len: usize) -> Result<&[u8], BridgeMessageError> {
+        if self.0.len() < len {
+            return Err(BridgeMessageError::UnexpectedEnd);
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, BridgeMessageError> {
+        self.read_bytes(1).map(|bytes| bytes[0])
+    }
+
+    fn read_u64(&mut self) -> Result<u64, BridgeMessageError> {
+        self.read_array().map(u64::from_be_bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], BridgeMessageError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    fn read_vec(&mut self) -> Result<Vec<u8>, BridgeMessageError> {
+        let len = self.read_u8()?;
+        self.read_bytes(len as usize).map(<[u8]>::to_vec)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[proptest]
+    fn roundtrip_payload(payload: BridgeMessagePayload, seq_num: u64, source: BridgeChainId) {
+        let message = BridgeMessage::new(seq_num, source, &payload).unwrap();
+        assert_eq!(Ok(payload), message.payload());
+        assert_eq!(
+            Ok(message.clone()),
+            BridgeMessage::from_bytes(&message.to_bytes())
+        );
+    }
+
+    #[test]
+    fn token_transfer_encoding() {
+        let payload = BridgeMessagePayload::TokenTransfer(TokenTransferPayload {
+            sender_address: vec![0xaa; 32],
+            target_chain: BridgeChainId::EthSepolia,
+            target_address: vec![0xbb; 20],
+            token_type: 3,
+            amount: 12345,
+        });
+        let message = BridgeMessage::new(10, BridgeChainId::SuiTestnet, &payload).unwrap();
+
+        let mut expected = vec![0, 1, 0, 0, 0, 0, 0, 0, 0, 10, 1, 32];
+        expected.extend([0xaa; 32]);
+        expected.extend([11, 20]);
+        expected.extend([0xbb; 20]);
+        expected.push(3);
+        expected.extend(12345u64.to_be_bytes());
+        assert_eq!(message.to_bytes(), expected);
+
+        let signing_message = message.signing_message();
+        assert!(signing_message.starts_with(b"SUI_BRIDGE_MESSAGE"));
+        assert_eq!(
+            &signing_message[BRIDGE_MESSAGE_PREFIX.len()..],
+            &expected[..]
+        );
+    }
+
+    #[test]
+    fn too_long_payloads() {
+        let payload = BridgeMessagePayload::TokenTransfer(TokenTransferPayload {
+            sender_address: vec![0xaa; 256],
+            target_chain: BridgeChainId::EthSepolia,
+            target_address: vec![0xbb; 20],
+            token_type: 3,
+            amount: 12345,
+        });
+        assert_eq!(
+            BridgeMessage::new(10, BridgeChainId::SuiTestnet, &payload),
+            Err(BridgeMessageError::TooLong {
+                field: "sender_address",
+                len: 256,
+            })
+        );
+
+        let payload = BridgeMessagePayload::CommitteeBlocklist(CommitteeBlocklistPayload {
+            blocklist_type: BlocklistType::Blocklist,
+            members: vec![EthAddress::new([1; 20]); 255],
+        });
+        assert!(payload.to_bytes().is_ok());
+        let payload = BridgeMessagePayload::CommitteeBlocklist(CommitteeBlocklistPayload {
+            blocklist_type: BlocklistType::Blocklist,
+            members: vec![EthAddress::new([1; 20]); 256],
+        });
+        assert_eq!(
+            payload.to_bytes(),
+            Err(BridgeMessageError::TooLong {
+                field: "members",
+                len: 256,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_payloads() {
+        let message = BridgeMessage {
+            message_type: BridgeMessageType::EmergencyButton.to_u8(),
+            message_version: BRIDGE_MESSAGE_VERSION,
+            seq_num: 0,
+            source_chain: BridgeChainId::SuiMainnet.to_u8(),
+            payload: vec![0, 0],
+        };
+        assert_eq!(message.payload(), Err(BridgeMessageError::TrailingBytes));
+
+        let message = BridgeMessage {
+            message_type: BridgeMessageType::UpdateCommitteeBlocklist.to_u8(),
+            payload: vec![0, 0],
+            ..message
+        };
+        assert_eq!(message.payload(), Err(BridgeMessageError::InvalidPayload));
+
+        let message = BridgeMessage {
+            message_type: BridgeMessageType::LimitUpdate.to_u8(),
+            payload: vec![3, 0, 0],
+            ..message
+        };
+        assert_eq!(
+            message.payload(),
+            Err(BridgeMessageError::InvalidChainId(3))
+        );
+
+        let message = BridgeMessage {
+            message_type: 42,
+            ..message
+        };
+        assert_eq!(
+            message.payload(),
+            Err(BridgeMessageError::InvalidMessageType(42))
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use super::SignedTransaction;
+use super::TransactionEffects;
+use super::ValidatorAggregatedSignature;
+use super::ValidatorSignature;
+
+/// A transaction signed by a quorum of validators, which they are bound to execute.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CertifiedTransaction {
+    #[cfg_attr(
+        feature = "serde",
+        serde(with = "::serde_with::As::<crate::_serde::SignedTransactionWithIntentMessage>")
+    )]
+    #[cfg_attr(feature = "schemars", schemars(with = "SignedTransaction"))]
+    pub transaction: SignedTransaction,
+    pub signature: ValidatorAggregatedSignature,
+}
+
+/// The effects of a transaction, as signed by a single validator which executed it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct SignedTransactionEffects {
+    pub effects: TransactionEffects,
+    pub signature: ValidatorSignature,
+}
+
+/// The effects of a transaction, as signed by a quorum of validators, which makes them final.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CertifiedTransactionEffects {
+    pub effects: TransactionEffects,
+    pub signature: ValidatorAggregatedSignature,
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod messages {
+    use super::*;
+    use crate::types::EpochId;
+    use crate::types::Intent;
+    use crate::types::IntentMessage;
+
+    type SenderSignedDataRef<'a> = serde_with::ser::SerializeAsWrap<
+        'a,
+        SignedTransaction,
+        crate::_serde::SignedTransactionWithIntentMessage,
+    >;
+
+    impl SignedTransaction {
+        /// The message validators sign to certify the transaction in `epoch`: its
+        /// `SenderSignedData` with the `SenderSignedTransaction` intent, followed by the epoch.
+        pub fn authority_message(&self, epoch: EpochId) -> Vec<u8> {
+            IntentMessage::new(
+                Intent::SENDER_SIGNED_TRANSACTION,
+                SenderSignedDataRef::new(self),
+            )
+            .to_authority_message(epoch)
+        }
+    }
+
+    impl TransactionEffects {
+        /// The message validators sign over the effects in `epoch`: the effects with the
+        /// `TransactionEffects` intent, followed by the epoch.
+        pub fn authority_message(&self, epoch: EpochId) -> Vec<u8> {
+            IntentMessage::new(Intent::TRANSACTION_EFFECTS, self).to_authority_message(epoch)
+        }
+    }
+}
+
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+mod verification {
+    use super::*;
+    use crate::types::CheckpointVerificationError;
+    use crate::types::ValidatorCommittee;
+
+    impl CertifiedTransaction {
+        /// Verify that the transaction is certified by a quorum of `committee`.
+        pub fn verify(
+            &self,
+            committee: &ValidatorCommittee,
+        ) -> Result<(), CheckpointVerificationError> {
+            let message = self.transaction.authority_message(self.signature.epoch);
+            committee.verify_aggregated_signature(&message, &self.signature)
+        }
+    }
+
+    impl SignedTransactionEffects {
+        /// Verify that the effects are signed by a member of `committee`, returning the index of
+        /// the member.
+        pub fn verify(
+            &self,
+            committee: &ValidatorCommittee,
+        ) -> Result<u32, CheckpointVerificationError> {
+            let message = self.effects.authority_message(self.signature.epoch);
+            committee.verify_signature(&message, &self.signature)
+        }
+    }
+
+    impl CertifiedTransactionEffects {
+        /// Verify that the effects are certified by a quorum of `committee`.
+        pub fn verify(
+            &self,
+            committee: &ValidatorCommittee,
+        ) -> Result<(), CheckpointVerificationError> {
+            let message = self.effects.authority_message(self.signature.epoch);
+            committee.verify_aggregated_signature(&message, &self.signature)
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bls12381"))]
+mod test {
+    use super::*;
+    use crate::types::test_committee;
+    use crate::types::CheckpointVerificationError;
+    use crate::types::EpochId;
+    use crate::types::SenderSignedData;
+    use crate::types::Transaction;
+    use crate::types::ValidatorCommittee;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use blst::min_sig::SecretKey;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn keys() -> Vec<SecretKey> {
+        test_committee::secret_keys(0, 4)
+    }
+
+    fn committee(keys: &[SecretKey]) -> ValidatorCommittee {
+        test_committee::committee(EpochId::new(3), keys)
+    }
+
+    fn transaction() -> SignedTransaction {
+        let transaction: Transaction = bcs::from_bytes(
+            &Base64::decode_vec(include_str!("transaction/fixtures/genesis-transaction").trim())
+                .unwrap(),
+        )
+        .unwrap();
+        SignedTransaction {
+            transaction,
+            signatures: vec![],
+        }
+    }
+
+    #[test]
+    fn certified_transaction() {
+        let keys = keys();
+        let committee = committee(&keys);
+        let transaction = transaction();
+
+        let message = transaction.authority_message(committee.epoch);
+        let mut expected = vec![4, 0, 0];
+        expected.extend(bcs::to_bytes(&SenderSignedData::new(transaction.clone())).unwrap());
+        expected.extend(3u64.to_le_bytes());
+        assert_eq!(message, expected);
+
+        let signatures: Vec<_> = keys[..3]
+            .iter()
+            .map(|key| test_committee::sign(key, committee.epoch, &message))
+            .collect();
+        let certificate = CertifiedTransaction {
+            transaction,
+            signature: committee.aggregate_signatures(&signatures).unwrap(),
+        };
+        certificate.verify(&committee).unwrap();
+
+        // The wire format is the `SenderSignedData` followed by the quorum signature
+        let bytes = bcs::to_bytes(&certificate).unwrap();
+        let (data, signature): (SenderSignedData, ValidatorAggregatedSignature) =
+            bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            data.into_signed_transaction().unwrap(),
+            certificate.transaction
+        );
+        assert_eq!(signature, certificate.signature);
+        assert_eq!(
+            bcs::from_bytes::<CertifiedTransaction>(&bytes).unwrap(),
+            certificate
+        );
+
+        let mut uncertified = certificate.clone();
+        uncertified.signature.bitmap.remove(0);
+        assert!(matches!(
+            uncertified.verify(&committee),
+            Err(CheckpointVerificationError::InsufficientStake { .. })
+        ));
+
+        let mut tampered = certificate;
+        tampered.transaction.signatures = vec![];
+        tampered.transaction.transaction.expiration =
+            crate::types::TransactionExpiration::Epoch(EpochId::new(9));
+        assert_eq!(
+            tampered.verify(&committee),
+            Err(CheckpointVerificationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn certified_effects() {
+        let keys = keys();
+        let committee = committee(&keys);
+        let effects: TransactionEffects = bcs::from_bytes(
+            &Base64::decode_vec(
+                include_str!("effects/fixtures/genesis-transaction-effects").trim(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+
+        let message = effects.authority_message(committee.epoch);
+        assert_eq!(message[..3], [1, 0, 0]);
+
+        let signatures: Vec<_> = keys
+            .iter()
+            .map(|key| SignedTransactionEffects {
+                effects: effects.clone(),
+                signature: test_committee::sign(key, committee.epoch, &message),
+            })
+            .collect();
+        assert_eq!(signatures[2].verify(&committee), Ok(2));
+
+        let certified = CertifiedTransactionEffects {
+            effects,
+            signature: committee
+                .aggregate_signatures(signatures.iter().map(|signed| &signed.signature))
+                .unwrap(),
+        };
+        certified.verify(&committee).unwrap();
+    }
+}
@@ -1,9 +1,14 @@
+use super::Bls12381PublicKey;
+use super::ChangeEpoch;
 use super::CheckpointContentsDigest;
 use super::CheckpointDigest;
 use super::Digest;
+use super::EpochId;
 use super::GasCostSummary;
 use super::Object;
+use super::ObjectId;
 use super::SignedTransaction;
+use super::Transaction;
 use super::TransactionDigest;
 use super::TransactionEffects;
 use super::TransactionEffectsDigest;
@@ -14,7 +19,6 @@ use super::ValidatorCommitteeMember;
 
 pub type CheckpointSequenceNumber = u64;
 pub type CheckpointTimestamp = u64;
-pub type EpochId = u64;
 pub type StakeUnit = u64;
 pub type ProtocolVersion = u64;
 
@@ -123,6 +127,20 @@ pub struct CheckpointContents(
     Vec<CheckpointTransactionInfo>,
 );
 
+impl CheckpointContents {
+    pub fn new(transactions: Vec<CheckpointTransactionInfo>) -> Self {
+        Self(transactions)
+    }
+
+    pub fn transactions(&self) -> &[CheckpointTransactionInfo] {
+        &self.0
+    }
+
+    pub fn into_transactions(self) -> Vec<CheckpointTransactionInfo> {
+        self.0
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -178,6 +196,455 @@ pub struct CheckpointTransaction {
     pub output_objects: Vec<Object>,
 }
 
+/// A proof that a transaction was executed with the given effects, checkpointed by a certified
+/// checkpoint.
+///
+/// The contents of a checkpoint are committed to by a single digest, so the proof carries the
+/// full contents of the checkpoint rather than only the entry of the transaction.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct CheckpointProof {
+    pub checkpoint_summary: SignedCheckpointSummary,
+    pub checkpoint_contents: CheckpointContents,
+    pub transaction: Transaction,
+    pub effects: TransactionEffects,
+}
+
+/// A proof of the state of an object at a version: a [`CheckpointProof`] of the transaction
+/// which wrote the object at that version, whose effects reference the digest of the object.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct ObjectProof {
+    pub checkpoint_proof: CheckpointProof,
+    pub object: Object,
+}
+
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+impl CheckpointSummary {
+    /// Verify that `contents` are the contents this summary commits to, e.g. before processing
+    /// contents downloaded separately from a certified summary.
+    pub fn verify_contents(
+        &self,
+        contents: &CheckpointContents,
+    ) -> Result<(), CheckpointVerificationError> {
+        if contents.digest() != self.content_digest {
+            return Err(CheckpointVerificationError::ContentsDigestMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+impl CheckpointContents {
+    /// Verify that `effects` are the effects checkpointed for their transaction, returning the
+    /// entry of the transaction.
+    pub fn verify_effects(
+        &self,
+        effects: &TransactionEffects,
+    ) -> Result<&CheckpointTransactionInfo, CheckpointVerificationError> {
+        let transaction_digest = effects.transaction_digest();
+        let info = self
+            .transactions()
+            .iter()
+            .find(|info| &info.transaction == transaction_digest)
+            .ok_or(CheckpointVerificationError::TransactionNotCheckpointed(
+                *transaction_digest,
+            ))?;
+        if info.effects != effects.digest() {
+            return Err(CheckpointVerificationError::EffectsDigestMismatch);
+        }
+        Ok(info)
+    }
+}
+
+#[cfg(all(feature = "hash", feature = "serde"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "hash", feature = "serde"))))]
+impl CheckpointData {
+    /// Verify that the transactions of the checkpoint, and their effects, are those of its
+    /// contents, in the same order.
+    ///
+    /// The contents themselves aren't checked against the summary, nor the summary against a
+    /// committee.
+    pub fn verify_transactions(&self) -> Result<(), CheckpointVerificationError> {
+        let contents = self.checkpoint_contents.transactions();
+        if contents.len() != self.transactions.len() {
+            return Err(CheckpointVerificationError::TransactionCountMismatch {
+                expected: contents.len(),
+                actual: self.transactions.len(),
+            });
+        }
+
+        let transaction_digests = Transaction::digests(
+            self.transactions
+                .iter()
+                .map(|transaction| &transaction.transaction.transaction),
+        );
+        let effects_digests = TransactionEffects::digests(
+            self.transactions
+                .iter()
+                .map(|transaction| &transaction.effects),
+        );
+        for (((info, transaction), transaction_digest), effects_digest) in contents
+            .iter()
+            .zip(&self.transactions)
+            .zip(transaction_digests)
+            .zip(effects_digests)
+        {
+            if info.transaction != transaction_digest {
+                return Err(CheckpointVerificationError::TransactionNotCheckpointed(
+                    transaction_digest,
+                ));
+            }
+            if info.effects != effects_digest
+                || transaction.effects.transaction_digest() != &transaction_digest
+            {
+                return Err(CheckpointVerificationError::EffectsDigestMismatch);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+impl CheckpointProof {
+    /// Verify that the checkpoint is certified by `committee`, and that it includes the
+    /// transaction with the given effects.
+    pub fn verify(
+        &self,
+        committee: &super::ValidatorCommittee,
+    ) -> Result<(), CheckpointVerificationError> {
+        committee.verify_checkpoint_summary(&self.checkpoint_summary)?;
+
+        self.checkpoint_summary
+            .checkpoint
+            .verify_contents(&self.checkpoint_contents)?;
+
+        let transaction_digest = self.transaction.digest();
+        let info = self
+            .checkpoint_contents
+            .transactions()
+            .iter()
+            .find(|info| info.transaction == transaction_digest)
+            .ok_or(CheckpointVerificationError::TransactionNotCheckpointed(
+                transaction_digest,
+            ))?;
+        if info.effects != self.effects.digest()
+            || self.effects.transaction_digest() != &transaction_digest
+        {
+            return Err(CheckpointVerificationError::EffectsDigestMismatch);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+impl ObjectProof {
+    /// Verify that the object was written by a transaction checkpointed by a checkpoint certified
+    /// by `committee`.
+    pub fn verify(
+        &self,
+        committee: &super::ValidatorCommittee,
+    ) -> Result<(), CheckpointVerificationError> {
+        self.checkpoint_proof.verify(committee)?;
+
+        let object_id = self.object.object_id();
+        let reference = self
+            .checkpoint_proof
+            .effects
+            .written_object(&object_id)
+            .ok_or(CheckpointVerificationError::ObjectNotWritten(object_id))?;
+        if reference.version() != self.object.version()
+            || reference.digest() != &self.object.digest()
+        {
+            return Err(CheckpointVerificationError::ObjectDigestMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// A summary of the transition from one epoch to the next, combining the `ChangeEpoch`
+/// transaction which ended the epoch with the last checkpoint of the epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct EpochTransition {
+    /// The epoch which ended.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub epoch: EpochId,
+    /// The sequence number of the last checkpoint of the epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub last_checkpoint: CheckpointSequenceNumber,
+    /// Total number of transactions committed since genesis until the end of the epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub network_total_transactions: u64,
+    /// The protocol version in effect in the next epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub protocol_version: ProtocolVersion,
+    /// Unix timestamp when the next epoch started.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub epoch_start_timestamp_ms: CheckpointTimestamp,
+    /// The total amount of gas charged for storage during the epoch, paid into the storage fund.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub storage_charge: u64,
+    /// The total amount of gas charged for computation during the epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub computation_charge: u64,
+    /// The amount of storage rebate refunded out of the storage fund during the epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub storage_rebate: u64,
+    /// The portion of the storage rebate kept by the storage fund.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub non_refundable_storage_fee: u64,
+    /// The total stake of the committee of the next epoch.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub next_epoch_total_stake: StakeUnit,
+}
+
+impl EpochTransition {
+    /// Summarize the transition performed by `change_epoch`, checking that it is consistent with
+    /// `checkpoint`, the last checkpoint of the epoch.
+    pub fn new(
+        change_epoch: &ChangeEpoch,
+        checkpoint: &CheckpointSummary,
+    ) -> Result<Self, EpochTransitionError> {
+        let end_of_epoch_data = checkpoint
+            .end_of_epoch_data
+            .as_ref()
+            .ok_or(EpochTransitionError::NotEndOfEpoch)?;
+
+        if checkpoint.epoch.checked_add(1) != Some(change_epoch.epoch) {
+            return Err(EpochTransitionError::EpochMismatch {
+                checkpoint: checkpoint.epoch,
+                next_epoch: change_epoch.epoch,
+            });
+        }
+        if end_of_epoch_data.next_epoch_protocol_version != change_epoch.protocol_version {
+            return Err(EpochTransitionError::ProtocolVersionMismatch {
+                checkpoint: end_of_epoch_data.next_epoch_protocol_version,
+                change_epoch: change_epoch.protocol_version,
+            });
+        }
+
+        // The epoch change is executed with the gas costs accumulated over the epoch, which are
+        // also those committed to by the last checkpoint.
+        let gas = &checkpoint.epoch_rolling_gas_cost_summary;
+        if gas.storage_cost != change_epoch.storage_charge
+            || gas.computation_cost != change_epoch.computation_charge
+            || gas.storage_rebate != change_epoch.storage_rebate
+            || gas.non_refundable_storage_fee != change_epoch.non_refundable_storage_fee
+        {
+            return Err(EpochTransitionError::GasCostMismatch);
+        }
+
+        let next_epoch_total_stake = end_of_epoch_data
+            .next_epoch_committee
+            .iter()
+            .try_fold(0, |total: StakeUnit, member| {
+                total.checked_add(member.stake)
+            })
+            .ok_or(EpochTransitionError::StakeOverflow)?;
+
+        Ok(Self {
+            epoch: checkpoint.epoch,
+            last_checkpoint: checkpoint.sequence_number,
+            network_total_transactions: checkpoint.network_total_transactions,
+            protocol_version: change_epoch.protocol_version,
+            epoch_start_timestamp_ms: change_epoch.epoch_start_timestamp_ms,
+            storage_charge: change_epoch.storage_charge,
+            computation_charge: change_epoch.computation_charge,
+            storage_rebate: change_epoch.storage_rebate,
+            non_refundable_storage_fee: change_epoch.non_refundable_storage_fee,
+            next_epoch_total_stake,
+        })
+    }
+
+    /// Summarize the transition performed by the last checkpoint of an epoch, using the
+    /// `ChangeEpoch` transaction included in it.
+    pub fn from_checkpoint_data(checkpoint: &CheckpointData) -> Result<Self, EpochTransitionError> {
+        let summary = &checkpoint.checkpoint_summary.checkpoint;
+        if summary.end_of_epoch_data.is_none() {
+            return Err(EpochTransitionError::NotEndOfEpoch);
+        }
+
+        let change_epoch = checkpoint
+            .transactions
+            .iter()
+            .find_map(|transaction| transaction.transaction.transaction.kind.change_epoch())
+            .ok_or(EpochTransitionError::ChangeEpochNotFound)?;
+        Self::new(change_epoch, summary)
+    }
+
+    /// The next epoch, started by this transition, failing if it overflows an [`EpochId`].
+    pub fn next_epoch(&self) -> Result<EpochId, EpochTransitionError> {
+        self.epoch
+            .checked_add(1)
+            .ok_or(EpochTransitionError::EpochOverflow)
+    }
+
+    /// The net amount paid into the storage fund during the epoch: storage charges minus the
+    /// storage rebates paid out of it. Negative if the storage fund shrank.
+    pub fn storage_fund_net_inflow(&self) -> i128 {
+        self.storage_charge as i128 - self.storage_rebate as i128
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EpochTransitionError {
+    /// The checkpoint is not the last checkpoint of an epoch.
+    NotEndOfEpoch,
+    /// The checkpoint does not include a `ChangeEpoch` transaction.
+    ChangeEpochNotFound,
+    /// The `ChangeEpoch` does not start the epoch following the epoch of the checkpoint.
+    EpochMismatch {
+        checkpoint: EpochId,
+        next_epoch: EpochId,
+    },
+    ProtocolVersionMismatch {
+        checkpoint: ProtocolVersion,
+        change_epoch: ProtocolVersion,
+    },
+    /// The charges of the `ChangeEpoch` do not match the gas costs of the epoch.
+    GasCostMismatch,
+    /// The total stake of the next committee overflows a `StakeUnit`.
+    StakeOverflow,
+    /// The next epoch overflows an `EpochId`.
+    EpochOverflow,
+}
+
+impl std::fmt::Display for EpochTransitionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::NotEndOfEpoch => write!(f, "not the last checkpoint of an epoch"),
+            Self::ChangeEpochNotFound => write!(f, "no ChangeEpoch transaction in the checkpoint"),
+            Self::EpochMismatch {
+                checkpoint,
+                next_epoch,
+            } => write!(
+                f,
+                "ChangeEpoch to epoch {next_epoch} does not follow checkpoint epoch {checkpoint}"
+            ),
+            Self::ProtocolVersionMismatch {
+                checkpoint,
+                change_epoch,
+            } => write!(
+                f,
+                "ChangeEpoch protocol version {change_epoch} does not match checkpoint protocol version {checkpoint}"
+            ),
+            Self::GasCostMismatch => {
+                write!(f, "ChangeEpoch charges do not match the epoch gas costs")
+            }
+            Self::StakeOverflow => write!(f, "next epoch committee stake overflows"),
+            Self::EpochOverflow => write!(f, "next epoch overflows"),
+        }
+    }
+}
+
+impl std::error::Error for EpochTransitionError {}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CheckpointVerificationError {
+    /// The signature, the checkpoint or the committee are not of the same epoch.
+    EpochMismatch {
+        expected: EpochId,
+        actual: EpochId,
+    },
+    /// The signature's bitmap refers to a member outside of the committee.
+    InvalidSignerIndex(u32),
+    InvalidPublicKey(Bls12381PublicKey),
+    InsufficientStake {
+        stake: StakeUnit,
+        threshold: StakeUnit,
+    },
+    /// The total stake of the committee overflows a `StakeUnit`.
+    StakeOverflow,
+    /// The checkpoint ends the last epoch representable by an `EpochId`.
+    EpochOverflow,
+    InvalidSignature,
+    ContentsDigestMismatch,
+    /// The checkpoint holds another number of transactions than its contents list.
+    TransactionCountMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    TransactionNotCheckpointed(TransactionDigest),
+    EffectsDigestMismatch,
+    /// The transaction did not write the object.
+    ObjectNotWritten(ObjectId),
+    ObjectDigestMismatch,
+}
+
+impl std::fmt::Display for CheckpointVerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::EpochMismatch { expected, actual } => {
+                write!(f, "expected epoch {expected}, found epoch {actual}")
+            }
+            Self::InvalidSignerIndex(index) => {
+                write!(f, "signer index {index} is not part of the committee")
+            }
+            Self::InvalidPublicKey(key) => write!(f, "invalid validator public key {key}"),
+            Self::InsufficientStake { stake, threshold } => {
+                write!(f, "insufficient stake {stake}, {threshold} is required")
+            }
+            Self::StakeOverflow => write!(f, "total stake of the committee overflows"),
+            Self::EpochOverflow => write!(f, "epoch following the checkpoint overflows"),
+            Self::InvalidSignature => write!(f, "invalid checkpoint signature"),
+            Self::ContentsDigestMismatch => {
+                write!(f, "checkpoint contents do not match the checkpoint summary")
+            }
+            Self::TransactionCountMismatch { expected, actual } => write!(
+                f,
+                "checkpoint has {actual} transactions, its contents list {expected}"
+            ),
+            Self::TransactionNotCheckpointed(digest) => {
+                write!(f, "transaction {digest} is not part of the checkpoint")
+            }
+            Self::EffectsDigestMismatch => {
+                write!(f, "effects do not match the checkpointed effects")
+            }
+            Self::ObjectNotWritten(object_id) => {
+                write!(f, "object {object_id} is not written by the transaction")
+            }
+            Self::ObjectDigestMismatch => {
+                write!(
+                    f,
+                    "object does not match the object written by the transaction"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CheckpointVerificationError {}
+
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 mod serialization {
@@ -434,15 +901,9 @@ mod serialization {
         effects: TransactionEffectsDigest,
     }
 
-    #[derive(serde_derive::Deserialize)]
-    struct BinaryContentsV1 {
-        digests: Vec<ExecutionDigests>,
-        signatures: Vec<Vec<UserSignature>>,
-    }
-
     #[derive(serde_derive::Deserialize)]
     enum BinaryContents {
-        V1(BinaryContentsV1),
+        V1(Vec<ExecutionDigests>, Vec<Vec<UserSignature>>),
     }
 
     impl<'de> Deserialize<'de> for CheckpointContents {
@@ -455,10 +916,8 @@ mod serialization {
                     Deserialize::deserialize(deserializer)?;
                 Ok(Self(contents))
             } else {
-                let BinaryContents::V1(BinaryContentsV1 {
-                    digests,
-                    signatures,
-                }) = Deserialize::deserialize(deserializer)?;
+                let BinaryContents::V1(digests, signatures) =
+                    Deserialize::deserialize(deserializer)?;
 
                 if digests.len() != signatures.len() {
                     return Err(serde::de::Error::custom(
@@ -583,5 +1042,417 @@ mod serialization {
             let json = serde_json::to_string_pretty(&contents).unwrap();
             println!("{json}");
         }
+
+        #[test]
+        fn epoch_transition() {
+            const EPOCH_CHANGE: &str = "AAUCAmkBAAAAAAAAmSrgAQAAAAAAagEAAAAAAAApAAAAAAAAALAQCoNLLwAAnNn0sywGAABsVBEfSC0AAKQnlhd1AAAAzve+vo4BAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAABAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAQAAAAAAAAAAAAAAAAAAAAA=";
+
+            let transaction: Transaction =
+                bcs::from_bytes(&Base64::decode_vec(EPOCH_CHANGE).unwrap()).unwrap();
+            let change_epoch = transaction.kind.change_epoch().unwrap();
+
+            let mut checkpoint = CheckpointSummary {
+                epoch: EpochId::new(change_epoch.epoch.into_inner() - 1),
+                sequence_number: 42,
+                network_total_transactions: 1000,
+                content_digest: CheckpointContentsDigest::ZERO,
+                previous_digest: None,
+                epoch_rolling_gas_cost_summary: GasCostSummary::new(
+                    change_epoch.computation_charge,
+                    change_epoch.storage_charge,
+                    change_epoch.storage_rebate,
+                    change_epoch.non_refundable_storage_fee,
+                ),
+                timestamp_ms: change_epoch.epoch_start_timestamp_ms,
+                checkpoint_commitments: vec![],
+                end_of_epoch_data: None,
+                version_specific_data: vec![],
+            };
+            assert_eq!(
+                EpochTransition::new(change_epoch, &checkpoint),
+                Err(EpochTransitionError::NotEndOfEpoch)
+            );
+
+            checkpoint.end_of_epoch_data = Some(EndOfEpochData {
+                next_epoch_committee: vec![
+                    ValidatorCommitteeMember {
+                        public_key: Bls12381PublicKey::new([0; 96]),
+                        stake: 6000,
+                    },
+                    ValidatorCommitteeMember {
+                        public_key: Bls12381PublicKey::new([1; 96]),
+                        stake: 4000,
+                    },
+                ],
+                next_epoch_protocol_version: change_epoch.protocol_version,
+                epoch_commitments: vec![],
+            });
+            let transition = EpochTransition::new(change_epoch, &checkpoint).unwrap();
+            assert_eq!(transition.epoch, checkpoint.epoch);
+            assert_eq!(transition.next_epoch(), Ok(change_epoch.epoch));
+            assert_eq!(transition.last_checkpoint, 42);
+            assert_eq!(transition.network_total_transactions, 1000);
+            assert_eq!(transition.protocol_version, change_epoch.protocol_version);
+            assert_eq!(
+                transition.epoch_start_timestamp_ms,
+                change_epoch.epoch_start_timestamp_ms
+            );
+            assert_eq!(transition.next_epoch_total_stake, 10000);
+            assert_eq!(
+                transition.storage_fund_net_inflow(),
+                change_epoch.storage_charge as i128 - change_epoch.storage_rebate as i128
+            );
+
+            let mut next_epoch = checkpoint.clone();
+            next_epoch.epoch = change_epoch.epoch;
+            assert_eq!(
+                EpochTransition::new(change_epoch, &next_epoch),
+                Err(EpochTransitionError::EpochMismatch {
+                    checkpoint: change_epoch.epoch,
+                    next_epoch: change_epoch.epoch,
+                })
+            );
+
+            let mut upgraded = checkpoint.clone();
+            upgraded
+                .end_of_epoch_data
+                .as_mut()
+                .unwrap()
+                .next_epoch_protocol_version += 1;
+            assert_eq!(
+                EpochTransition::new(change_epoch, &upgraded),
+                Err(EpochTransitionError::ProtocolVersionMismatch {
+                    checkpoint: change_epoch.protocol_version + 1,
+                    change_epoch: change_epoch.protocol_version,
+                })
+            );
+
+            let mut charged = checkpoint.clone();
+            charged.epoch_rolling_gas_cost_summary.storage_cost += 1;
+            assert_eq!(
+                EpochTransition::new(change_epoch, &charged),
+                Err(EpochTransitionError::GasCostMismatch)
+            );
+
+            let mut staked = checkpoint.clone();
+            staked
+                .end_of_epoch_data
+                .as_mut()
+                .unwrap()
+                .next_epoch_committee[0]
+                .stake = u64::MAX;
+            assert_eq!(
+                EpochTransition::new(change_epoch, &staked),
+                Err(EpochTransitionError::StakeOverflow)
+            );
+
+            let last = EpochTransition {
+                epoch: EpochId::new(u64::MAX),
+                ..transition
+            };
+            assert_eq!(last.next_epoch(), Err(EpochTransitionError::EpochOverflow));
+        }
+    }
+}
+
+#[cfg(all(test, feature = "bls12381"))]
+mod test {
+    use super::*;
+    use crate::types::test_committee;
+    use crate::types::ChangedObject;
+    use crate::types::EffectsObjectChange;
+    use crate::types::ExecutionStatus;
+    use crate::types::IdOperation;
+    use crate::types::ObjectDigest;
+    use crate::types::ObjectIn;
+    use crate::types::ObjectOut;
+    use crate::types::TransactionEffectsV2;
+    use crate::types::ValidatorCommittee;
+    use base64ct::Base64;
+    use base64ct::Encoding;
+    use blst::min_sig::SecretKey;
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn secret_keys() -> Vec<SecretKey> {
+        test_committee::secret_keys(0, 4)
+    }
+
+    fn committee(keys: &[SecretKey]) -> ValidatorCommittee {
+        test_committee::committee(EpochId::new(0), keys)
+    }
+
+    fn sign(
+        keys: &[SecretKey],
+        signers: &[u32],
+        checkpoint: CheckpointSummary,
+    ) -> SignedCheckpointSummary {
+        let mut message = vec![2, 0, 0];
+        message.extend(bcs::to_bytes(&checkpoint).unwrap());
+        message.extend(checkpoint.epoch.into_inner().to_le_bytes());
+
+        SignedCheckpointSummary {
+            signature: test_committee::sign_aggregated(keys, signers, checkpoint.epoch, &message),
+            checkpoint,
+        }
+    }
+
+    const GENESIS_TRANSACTION: &str = include_str!("transaction/fixtures/genesis-transaction");
+
+    fn genesis_transaction() -> Transaction {
+        bcs::from_bytes(&Base64::decode_vec(GENESIS_TRANSACTION.trim()).unwrap()).unwrap()
+    }
+
+    fn genesis_proof(keys: &[SecretKey], signers: &[u32]) -> CheckpointProof {
+        const GENESIS_EFFECTS: &str = include_str!("effects/fixtures/genesis-transaction-effects");
+
+        let effects: TransactionEffects =
+            bcs::from_bytes(&Base64::decode_vec(GENESIS_EFFECTS.trim()).unwrap()).unwrap();
+        certify(keys, signers, genesis_transaction(), effects)
+    }
+
+    /// A proof of `transaction` with `effects` checkpointed in a checkpoint signed by `signers`.
+    fn certify(
+        keys: &[SecretKey],
+        signers: &[u32],
+        transaction: Transaction,
+        effects: TransactionEffects,
+    ) -> CheckpointProof {
+        let contents = CheckpointContents::new(vec![CheckpointTransactionInfo {
+            transaction: transaction.digest(),
+            effects: effects.digest(),
+            signatures: vec![],
+        }]);
+        let checkpoint = CheckpointSummary {
+            epoch: EpochId::new(0),
+            sequence_number: 0,
+            network_total_transactions: 1,
+            content_digest: contents.digest(),
+            previous_digest: None,
+            epoch_rolling_gas_cost_summary: GasCostSummary::new(0, 0, 0, 0),
+            timestamp_ms: 0,
+            checkpoint_commitments: vec![],
+            end_of_epoch_data: None,
+            version_specific_data: vec![],
+        };
+
+        CheckpointProof {
+            checkpoint_summary: sign(keys, signers, checkpoint),
+            checkpoint_contents: contents,
+            transaction,
+            effects,
+        }
+    }
+
+    #[test]
+    fn verify_checkpoint_proof() {
+        let keys = secret_keys();
+        let committee = committee(&keys);
+        assert_eq!(committee.quorum_threshold(), Ok(6667));
+
+        genesis_proof(&keys, &[0, 1, 3]).verify(&committee).unwrap();
+
+        assert_eq!(
+            genesis_proof(&keys, &[0, 1]).verify(&committee),
+            Err(CheckpointVerificationError::InsufficientStake {
+                stake: 5000,
+                threshold: 6667
+            })
+        );
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.checkpoint_summary.signature.bitmap = [0, 1, 4].into_iter().collect();
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::InvalidSignerIndex(4))
+        );
+
+        // Signed by other validators than the bitmap claims
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.checkpoint_summary.signature.bitmap = [0, 1, 3].into_iter().collect();
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::InvalidSignature)
+        );
+
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.checkpoint_summary.checkpoint.timestamp_ms = 1;
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::InvalidSignature)
+        );
+
+        let proof = genesis_proof(&keys, &[0, 1, 2]);
+        let next_committee = ValidatorCommittee {
+            epoch: EpochId::new(1),
+            ..committee.clone()
+        };
+        assert_eq!(
+            proof.verify(&next_committee),
+            Err(CheckpointVerificationError::EpochMismatch {
+                expected: EpochId::new(1),
+                actual: EpochId::new(0)
+            })
+        );
+
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.checkpoint_contents = CheckpointContents::new(vec![]);
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::ContentsDigestMismatch)
+        );
+
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.transaction.gas_payment.budget += 1;
+        assert!(matches!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::TransactionNotCheckpointed(_))
+        ));
+
+        // The effects of another transaction
+        const PYTH_WORMHOLE_EFFECTS: &str = include_str!("effects/fixtures/pyth-wormhole-v2");
+        let mut proof = genesis_proof(&keys, &[0, 1, 2]);
+        proof.effects =
+            bcs::from_bytes(&Base64::decode_vec(PYTH_WORMHOLE_EFFECTS.trim()).unwrap()).unwrap();
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::EffectsDigestMismatch)
+        );
+    }
+
+    #[test]
+    fn verify_checkpointed_effects() {
+        let keys = secret_keys();
+        let proof = genesis_proof(&keys, &[0, 1, 2]);
+        let contents = &proof.checkpoint_contents;
+        assert_eq!(
+            contents.verify_effects(&proof.effects),
+            Ok(&contents.transactions()[0])
+        );
+
+        const PYTH_WORMHOLE_EFFECTS: &str = include_str!("effects/fixtures/pyth-wormhole-v2");
+        let effects: TransactionEffects =
+            bcs::from_bytes(&Base64::decode_vec(PYTH_WORMHOLE_EFFECTS.trim()).unwrap()).unwrap();
+        assert_eq!(
+            contents.verify_effects(&effects),
+            Err(CheckpointVerificationError::TransactionNotCheckpointed(
+                *effects.transaction_digest()
+            ))
+        );
+
+        let summary = &proof.checkpoint_summary.checkpoint;
+        summary.verify_contents(contents).unwrap();
+        assert_eq!(
+            summary.verify_contents(&CheckpointContents::new(vec![])),
+            Err(CheckpointVerificationError::ContentsDigestMismatch)
+        );
+
+        let mut claimed = contents.clone().into_transactions();
+        claimed[0].effects = TransactionEffectsDigest::ZERO;
+        assert_eq!(
+            CheckpointContents::new(claimed).verify_effects(&proof.effects),
+            Err(CheckpointVerificationError::EffectsDigestMismatch)
+        );
+
+        let mut checkpoint = CheckpointData {
+            checkpoint_summary: proof.checkpoint_summary,
+            checkpoint_contents: proof.checkpoint_contents,
+            transactions: vec![CheckpointTransaction {
+                transaction: SignedTransaction {
+                    transaction: proof.transaction,
+                    signatures: vec![],
+                },
+                effects: proof.effects,
+                events: None,
+                input_objects: vec![],
+                output_objects: vec![],
+            }],
+        };
+        checkpoint.verify_transactions().unwrap();
+
+        checkpoint.transactions[0].effects = effects;
+        assert_eq!(
+            checkpoint.verify_transactions(),
+            Err(CheckpointVerificationError::EffectsDigestMismatch)
+        );
+
+        checkpoint.transactions.clear();
+        assert_eq!(
+            checkpoint.verify_transactions(),
+            Err(CheckpointVerificationError::TransactionCountMismatch {
+                expected: 1,
+                actual: 0
+            })
+        );
+    }
+
+    #[proptest(cases = 16)]
+    fn verify_object_proof(object: Object) {
+        let keys = secret_keys();
+        let committee = committee(&keys);
+        let transaction = genesis_transaction();
+        let effects = |changed_objects| {
+            TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+                status: ExecutionStatus::Success,
+                epoch: EpochId::new(0),
+                gas_used: GasCostSummary::new(0, 0, 0, 0),
+                transaction_digest: transaction.digest(),
+                gas_object_index: None,
+                events_digest: None,
+                dependencies: vec![],
+                lamport_version: object.version(),
+                changed_objects,
+                unchanged_shared_objects: vec![],
+                auxiliary_data_digest: None,
+            }))
+        };
+        let written = |digest| ChangedObject {
+            object_id: object.object_id(),
+            change: EffectsObjectChange {
+                input_state: ObjectIn::NotExist,
+                output_state: ObjectOut::ObjectWrite {
+                    digest,
+                    owner: *object.owner(),
+                },
+                id_operation: IdOperation::Created,
+            },
+        };
+
+        let proof = ObjectProof {
+            checkpoint_proof: certify(
+                &keys,
+                &[0, 1, 2],
+                transaction.clone(),
+                effects(vec![written(object.digest())]),
+            ),
+            object: object.clone(),
+        };
+        proof.verify(&committee).unwrap();
+
+        let proof = ObjectProof {
+            checkpoint_proof: certify(
+                &keys,
+                &[0, 1, 2],
+                transaction.clone(),
+                effects(vec![written(ObjectDigest::ZERO)]),
+            ),
+            object: object.clone(),
+        };
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::ObjectDigestMismatch)
+        );
+
+        let proof = ObjectProof {
+            checkpoint_proof: certify(&keys, &[0, 1, 2], transaction.clone(), effects(vec![])),
+            object: object.clone(),
+        };
+        assert_eq!(
+            proof.verify(&committee),
+            Err(CheckpointVerificationError::ObjectNotWritten(
+                object.object_id()
+            ))
+        );
     }
 }
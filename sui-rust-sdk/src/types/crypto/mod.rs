@@ -27,16 +27,27 @@ pub use secp256r1::Secp256r1Signature;
 pub use signature::SignatureScheme;
 pub use signature::SimpleSignature;
 pub use signature::UserSignature;
+#[cfg(all(test, feature = "bls12381"))]
+pub(crate) use validator::test_committee;
+#[cfg(feature = "bls12381")]
+pub(crate) use validator::verify_validator_signature;
 pub use validator::ValidatorAggregatedSignature;
 pub use validator::ValidatorCommittee;
 pub use validator::ValidatorCommitteeMember;
 pub use validator::ValidatorSignature;
+#[cfg(feature = "bls12381")]
+pub(crate) use validator::VALIDATOR_SIGNATURE_DST;
+pub use zklogin::ActiveJwkSet;
 pub use zklogin::Bn254FieldElement;
 pub use zklogin::CircomG1;
 pub use zklogin::CircomG2;
 pub use zklogin::Claim;
+pub use zklogin::JsonWebKey;
+pub use zklogin::JsonWebKeySet;
 pub use zklogin::Jwk;
 pub use zklogin::JwkId;
+pub use zklogin::JwkParseError;
+pub use zklogin::JwkSetDiff;
 pub use zklogin::JwtDetails;
 pub use zklogin::ZkLoginAuthenticator;
 pub use zklogin::ZkLoginInputs;
@@ -57,6 +68,22 @@ const fn base64_encoded_length(len: usize) -> usize {
     ((4 * len / 3) + 3) & !3
 }
 
+/// Decode `s` into exactly `buf.len()` bytes from Base64, with or without padding, or from its
+/// URL-safe variant, which some APIs and tools emit instead.
+#[cfg(feature = "serde")]
+pub(crate) fn decode_base64_exact(s: &str, buf: &mut [u8]) -> Result<(), base64ct::Error> {
+    use base64ct::Encoding;
+
+    let len = base64ct::Base64::decode(s, buf).map(|d| d.len());
+    let len = len.or_else(|_| base64ct::Base64Unpadded::decode(s, buf).map(|d| d.len()));
+    let len = len.or_else(|_| base64ct::Base64Url::decode(s, buf).map(|d| d.len()));
+    let len = len.or_else(|_| base64ct::Base64UrlUnpadded::decode(s, buf).map(|d| d.len()))?;
+    if len != buf.len() {
+        return Err(base64ct::Error::InvalidLength);
+    }
+    Ok(())
+}
+
 macro_rules! impl_base64_helper {
     ($base:ident, $display:ident, $fromstr:ident, $test_module:ident, $array_length:literal) => {
         #[allow(unused)]
@@ -118,8 +145,12 @@ macro_rules! impl_base64_helper {
             where
                 D: serde::Deserializer<'de>,
             {
-                let array: $fromstr = serde_with::DisplayFromStr::deserialize_as(deserializer)?;
-                Ok(array.0)
+                // Unlike `FromStr`, accept the other Base64 variants
+                let s =
+                    <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+                let mut buf = [0; Self::LENGTH];
+                decode_base64_exact(&s, &mut buf).map_err(serde::de::Error::custom)?;
+                Ok(buf)
             }
         }
 
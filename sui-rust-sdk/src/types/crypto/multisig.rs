@@ -12,8 +12,8 @@ pub type WeightUnit = u8;
 pub type ThresholdUnit = u16;
 pub type BitmapUnit = u16;
 
-#[cfg(feature = "serde")]
-const MAX_COMMITTEE_SIZE: usize = 10;
+/// The maximum number of members of a committee.
+pub const MAX_COMMITTEE_SIZE: usize = 10;
 // TODO validate sigs
 // const MAX_BITMAP_VALUE: BitmapUnit = 0b1111111111;
 
@@ -36,6 +36,10 @@ pub struct MultisigMember {
 }
 
 impl MultisigMember {
+    pub fn new(public_key: MultisigMemberPublicKey, weight: WeightUnit) -> Self {
+        Self { public_key, weight }
+    }
+
     pub fn public_key(&self) -> &MultisigMemberPublicKey {
         &self.public_key
     }
@@ -61,6 +65,10 @@ pub struct MultisigCommittee {
 }
 
 impl MultisigCommittee {
+    pub fn new(members: Vec<MultisigMember>, threshold: ThresholdUnit) -> Self {
+        Self { members, threshold }
+    }
+
     pub fn members(&self) -> &[MultisigMember] {
         &self.members
     }
@@ -72,6 +80,26 @@ impl MultisigCommittee {
     pub fn scheme(&self) -> SignatureScheme {
         SignatureScheme::Multisig
     }
+
+    /// Whether the committee is one Sui accepts: between one and [`MAX_COMMITTEE_SIZE`] distinct
+    /// members, none of zero weight, with a non-zero threshold they can reach together.
+    pub fn is_valid(&self) -> bool {
+        let total_weight: ThresholdUnit = self
+            .members
+            .iter()
+            .map(|member| member.weight as ThresholdUnit)
+            .sum();
+        !self.members.is_empty()
+            && self.members.len() <= MAX_COMMITTEE_SIZE
+            && self.threshold != 0
+            && total_weight >= self.threshold
+            && self.members.iter().all(|member| member.weight != 0)
+            && self.members.iter().enumerate().all(|(i, member)| {
+                self.members[..i]
+                    .iter()
+                    .all(|other| other.public_key != member.public_key)
+            })
+    }
 }
 
 /// The struct that contains signatures and public keys necessary for authenticating a Multisig.
@@ -100,6 +128,21 @@ pub struct MultisigAggregatedSignature {
 }
 
 impl MultisigAggregatedSignature {
+    /// The signatures of the members of `committee` whose bits are set in `bitmap`, in the order
+    /// of the members.
+    pub fn new(
+        committee: MultisigCommittee,
+        signatures: Vec<MultisigMemberSignature>,
+        bitmap: BitmapUnit,
+    ) -> Self {
+        Self {
+            signatures,
+            bitmap,
+            legacy_bitmap: None,
+            committee,
+        }
+    }
+
     pub fn signatures(&self) -> &[MultisigMemberSignature] {
         &self.signatures
     }
@@ -16,6 +16,19 @@ pub struct Secp256k1PrivateKey(
 impl Secp256k1PrivateKey {
     /// The length of an secp256k1 private key in bytes.
     pub const LENGTH: usize = 32;
+
+    pub const fn new(bytes: [u8; Self::LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    /// Return the underlying byte array of an Secp256k1PrivateKey.
+    pub const fn into_inner(self) -> [u8; Self::LENGTH] {
+        self.0
+    }
+
+    pub const fn inner(&self) -> &[u8; Self::LENGTH] {
+        &self.0
+    }
 }
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
@@ -554,6 +554,30 @@ mod serialization {
             }
         }
 
+        #[test]
+        fn readable_base64_variants() {
+            use base64ct::Base64UrlUnpadded;
+
+            const FIXTURE: &str = "YQDaeO4w2ULMy5eqHBzP0oalr1YhDX/9uJS9MntKnW3d55q4aqZYYnoEloaBmXKc6FoD5bTwONdwS9CwdMQGhIcPDX2rNYyNrapO+gBJp1sHQ2VVsQo2ghm7aA9wVxNJ13U=";
+
+            let sig: UserSignature =
+                bcs::from_bytes(&Base64::decode_vec(FIXTURE).unwrap()).unwrap();
+            let canonical = serde_json::to_value(&sig).unwrap();
+
+            let mut json = canonical.clone();
+            for field in ["signature", "public_key"] {
+                let bytes = Base64::decode_vec(json[field].as_str().unwrap()).unwrap();
+                json[field] = Base64UrlUnpadded::encode_string(&bytes).into();
+            }
+            assert_ne!(json, canonical);
+            let parsed: UserSignature = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(parsed, sig);
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), canonical);
+
+            json["public_key"] = "AAEC".into();
+            assert!(serde_json::from_value::<UserSignature>(json).is_err());
+        }
+
         #[test]
         fn legacy_multisig_fixtures() {
             const FIXTURE1: &str = "rgIDAgAnwUSyrALP8m0eEPZE6aPggBELk72n1u3LU+i4nx5kqzhahcICbskEYzHJrbarvFr/RQITgDMoorqpDhN8dgsKATyrN3CD8g37D60dYiGW6sOBqIcf3E1mdMsKvX2pbOZsYQv8VNL+2Jz3vnMXcwEZF32PplKjcnmyUGRhV11M7n4UOjAAAAEAAAAAAAEAEAAAAAAAAQADLEFBMTlxeldNamEycVR2b0FTYWRiQjBObFZiRUtOb0ladTJnUGNGY1RTZGQxATBBUUlPRjgxWk9lUnJHV1pCbG96WFdaRUxvbGQrSi9wei9lT0hiYm0reGJ6ckt3PT0BMEFnTkgrNjhqOERpcnhNTUlvbkVSZWlwTS82N2R2Ri80SEhVWHZHeDBwKzIwTUE9PQECAA==";
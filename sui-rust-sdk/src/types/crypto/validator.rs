@@ -1,7 +1,8 @@
 use super::Bls12381PublicKey;
 use super::Bls12381Signature;
-use crate::types::checkpoint::EpochId;
 use crate::types::checkpoint::StakeUnit;
+use crate::types::CheckpointVerificationError;
+use crate::types::EpochId;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -17,6 +18,25 @@ pub struct ValidatorCommittee {
     pub members: Vec<ValidatorCommitteeMember>,
 }
 
+impl ValidatorCommittee {
+    /// The total stake of the members, failing if it overflows a [`StakeUnit`].
+    pub fn total_stake(&self) -> Result<StakeUnit, CheckpointVerificationError> {
+        self.members
+            .iter()
+            .try_fold(0, |total: StakeUnit, member| {
+                total.checked_add(member.stake)
+            })
+            .ok_or(CheckpointVerificationError::StakeOverflow)
+    }
+
+    /// The stake required for a quorum, more than two thirds of the total stake.
+    pub fn quorum_threshold(&self) -> Result<StakeUnit, CheckpointVerificationError> {
+        // Two thirds of a `StakeUnit` plus one always fit in one, only the doubling may not
+        let total = self.total_stake()? as u128;
+        Ok((total * 2 / 3 + 1) as StakeUnit)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -111,6 +131,251 @@ pub struct ValidatorSignature {
     pub signature: Bls12381Signature,
 }
 
+#[cfg(feature = "bls12381")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "bls12381")))]
+mod verification {
+    use super::*;
+    use crate::types::Intent;
+    use crate::types::IntentMessage;
+    use crate::types::SignedCheckpointSummary;
+
+    /// The domain separation tag of the BLS12-381 min-sig signatures of validators.
+    pub(crate) const VALIDATOR_SIGNATURE_DST: &[u8] =
+        b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_NUL_";
+
+    /// Verify that `signature` is a signature over `message` by `public_key`, with the domain
+    /// separation tag of validators.
+    pub(crate) fn verify_validator_signature(
+        public_key: &Bls12381PublicKey,
+        message: &[u8],
+        signature: &Bls12381Signature,
+    ) -> Result<(), CheckpointVerificationError> {
+        use blst::min_sig::PublicKey;
+        use blst::min_sig::Signature;
+        use blst::BLST_ERROR;
+
+        let key = PublicKey::key_validate(public_key.inner())
+            .map_err(|_| CheckpointVerificationError::InvalidPublicKey(*public_key))?;
+        let signature = Signature::from_bytes(signature.inner())
+            .map_err(|_| CheckpointVerificationError::InvalidSignature)?;
+        match signature.verify(true, message, VALIDATOR_SIGNATURE_DST, &[], &key, true) {
+            BLST_ERROR::BLST_SUCCESS => Ok(()),
+            _ => Err(CheckpointVerificationError::InvalidSignature),
+        }
+    }
+
+    impl ValidatorCommittee {
+        /// Verify that `summary` is certified by a quorum of this committee.
+        pub fn verify_checkpoint_summary(
+            &self,
+            summary: &SignedCheckpointSummary,
+        ) -> Result<(), CheckpointVerificationError> {
+            if summary.checkpoint.epoch != self.epoch {
+                return Err(CheckpointVerificationError::EpochMismatch {
+                    expected: self.epoch,
+                    actual: summary.checkpoint.epoch,
+                });
+            }
+
+            let message = IntentMessage::new(Intent::CHECKPOINT_SUMMARY, &summary.checkpoint)
+                .to_authority_message(summary.signature.epoch);
+            self.verify_aggregated_signature(&message, &summary.signature)
+        }
+
+        /// Verify that `signature` is a signature over `message` by members of this committee
+        /// holding a quorum of its stake.
+        pub fn verify_aggregated_signature(
+            &self,
+            message: &[u8],
+            signature: &ValidatorAggregatedSignature,
+        ) -> Result<(), CheckpointVerificationError> {
+            use blst::min_sig::PublicKey;
+            use blst::min_sig::Signature;
+            use blst::BLST_ERROR;
+
+            if signature.epoch != self.epoch {
+                return Err(CheckpointVerificationError::EpochMismatch {
+                    expected: self.epoch,
+                    actual: signature.epoch,
+                });
+            }
+
+            let threshold = self.quorum_threshold()?;
+            let mut stake: StakeUnit = 0;
+            let mut public_keys = Vec::with_capacity(signature.bitmap.len() as usize);
+            for index in &signature.bitmap {
+                let member = self
+                    .members
+                    .get(index as usize)
+                    .ok_or(CheckpointVerificationError::InvalidSignerIndex(index))?;
+                let public_key =
+                    PublicKey::key_validate(member.public_key.inner()).map_err(|_| {
+                        CheckpointVerificationError::InvalidPublicKey(member.public_key)
+                    })?;
+                stake = stake
+                    .checked_add(member.stake)
+                    .ok_or(CheckpointVerificationError::StakeOverflow)?;
+                public_keys.push(public_key);
+            }
+
+            if stake < threshold {
+                return Err(CheckpointVerificationError::InsufficientStake { stake, threshold });
+            }
+
+            let aggregated = Signature::from_bytes(signature.signature.inner())
+                .map_err(|_| CheckpointVerificationError::InvalidSignature)?;
+            let public_keys: Vec<&PublicKey> = public_keys.iter().collect();
+            match aggregated.fast_aggregate_verify(
+                true,
+                message,
+                VALIDATOR_SIGNATURE_DST,
+                &public_keys,
+            ) {
+                BLST_ERROR::BLST_SUCCESS => Ok(()),
+                _ => Err(CheckpointVerificationError::InvalidSignature),
+            }
+        }
+
+        /// Verify that `signature` is a signature over `message` by a member of this committee,
+        /// returning the index of the member.
+        pub fn verify_signature(
+            &self,
+            message: &[u8],
+            signature: &ValidatorSignature,
+        ) -> Result<u32, CheckpointVerificationError> {
+            let index = self.member_index(signature)?;
+            verify_validator_signature(&signature.public_key, message, &signature.signature)?;
+            Ok(index)
+        }
+
+        /// Aggregate `signatures` by members of this committee over the same message.
+        ///
+        /// The signatures themselves are not verified, see
+        /// [`ValidatorCommittee::verify_signature`].
+        pub fn aggregate_signatures<'a>(
+            &self,
+            signatures: impl IntoIterator<Item = &'a ValidatorSignature>,
+        ) -> Result<ValidatorAggregatedSignature, CheckpointVerificationError> {
+            use blst::min_sig::AggregateSignature;
+            use blst::min_sig::Signature;
+
+            let mut bitmap = roaring::RoaringBitmap::new();
+            let mut parsed = Vec::new();
+            for signature in signatures {
+                bitmap.insert(self.member_index(signature)?);
+                parsed.push(
+                    Signature::from_bytes(signature.signature.inner())
+                        .map_err(|_| CheckpointVerificationError::InvalidSignature)?,
+                );
+            }
+
+            let aggregated =
+                AggregateSignature::aggregate(&parsed.iter().collect::<Vec<_>>(), true)
+                    .map_err(|_| CheckpointVerificationError::InvalidSignature)?;
+            Ok(ValidatorAggregatedSignature {
+                epoch: self.epoch,
+                signature: Bls12381Signature::new(aggregated.to_signature().to_bytes()),
+                bitmap,
+            })
+        }
+
+        /// The index of the member which made `signature`, which must be of this committee's
+        /// epoch.
+        fn member_index(
+            &self,
+            signature: &ValidatorSignature,
+        ) -> Result<u32, CheckpointVerificationError> {
+            if signature.epoch != self.epoch {
+                return Err(CheckpointVerificationError::EpochMismatch {
+                    expected: self.epoch,
+                    actual: signature.epoch,
+                });
+            }
+            self.members
+                .iter()
+                .position(|member| member.public_key == signature.public_key)
+                .map(|index| index as u32)
+                .ok_or(CheckpointVerificationError::InvalidPublicKey(
+                    signature.public_key,
+                ))
+        }
+    }
+}
+
+#[cfg(feature = "bls12381")]
+pub(crate) use verification::verify_validator_signature;
+#[cfg(feature = "bls12381")]
+pub(crate) use verification::VALIDATOR_SIGNATURE_DST;
+
+/// Committees of validators with known secret keys, shared by the tests verifying their
+/// signatures.
+#[cfg(all(test, feature = "bls12381"))]
+pub(crate) mod test_committee {
+    use super::*;
+    use blst::min_sig::AggregateSignature;
+    use blst::min_sig::SecretKey;
+
+    /// The secret keys of `count` validators, generated from consecutive seeds from `seed`.
+    pub(crate) fn secret_keys(seed: u8, count: u8) -> Vec<SecretKey> {
+        (seed..seed + count)
+            .map(|i| SecretKey::key_gen(&[i; 32], &[]).unwrap())
+            .collect()
+    }
+
+    pub(crate) fn public_key(key: &SecretKey) -> Bls12381PublicKey {
+        Bls12381PublicKey::new(key.sk_to_pk().to_bytes())
+    }
+
+    /// The members of a committee of `keys`, each with a stake of 2500.
+    pub(crate) fn members(keys: &[SecretKey]) -> Vec<ValidatorCommitteeMember> {
+        keys.iter()
+            .map(|key| ValidatorCommitteeMember {
+                public_key: public_key(key),
+                stake: 2500,
+            })
+            .collect()
+    }
+
+    pub(crate) fn committee(epoch: EpochId, keys: &[SecretKey]) -> ValidatorCommittee {
+        ValidatorCommittee {
+            epoch,
+            members: members(keys),
+        }
+    }
+
+    /// The signature of `message` by `key` in `epoch`.
+    pub(crate) fn sign(key: &SecretKey, epoch: EpochId, message: &[u8]) -> ValidatorSignature {
+        ValidatorSignature {
+            epoch,
+            public_key: public_key(key),
+            signature: Bls12381Signature::new(
+                key.sign(message, VALIDATOR_SIGNATURE_DST, &[]).to_bytes(),
+            ),
+        }
+    }
+
+    /// The aggregated signature of `message` in `epoch` by the `signers` of `keys`, by index.
+    pub(crate) fn sign_aggregated(
+        keys: &[SecretKey],
+        signers: &[u32],
+        epoch: EpochId,
+        message: &[u8],
+    ) -> ValidatorAggregatedSignature {
+        let signatures: Vec<_> = signers
+            .iter()
+            .map(|i| keys[*i as usize].sign(message, VALIDATOR_SIGNATURE_DST, &[]))
+            .collect();
+        let signature = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+            .unwrap()
+            .to_signature();
+        ValidatorAggregatedSignature {
+            epoch,
+            signature: Bls12381Signature::new(signature.to_bytes()),
+            bitmap: signers.iter().copied().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -131,4 +396,27 @@ mod test {
         let bytes = bcs::to_bytes(&signature).unwrap();
         assert_eq!(bcs, bytes);
     }
+
+    #[test]
+    fn stake_overflow() {
+        let member = ValidatorCommitteeMember {
+            public_key: Bls12381PublicKey::new([0; 96]),
+            stake: u64::MAX / 2 + 1,
+        };
+        let mut committee = ValidatorCommittee {
+            epoch: EpochId::new(0),
+            members: vec![member.clone()],
+        };
+        assert_eq!(committee.quorum_threshold(), Ok(u64::MAX / 3 + 1));
+
+        committee.members.push(member);
+        assert_eq!(
+            committee.total_stake(),
+            Err(CheckpointVerificationError::StakeOverflow)
+        );
+        assert_eq!(
+            committee.quorum_threshold(),
+            Err(CheckpointVerificationError::StakeOverflow)
+        );
+    }
 }
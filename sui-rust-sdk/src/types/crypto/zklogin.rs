@@ -1,6 +1,8 @@
 use super::SimpleSignature;
-use crate::types::checkpoint::EpochId;
 use crate::types::u256::U256;
+use crate::types::ActiveJwk;
+use crate::types::AuthenticatorStateUpdate;
+use crate::types::EpochId;
 
 /// An zk login authenticator with all the necessary fields.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -91,13 +93,19 @@ pub struct CircomG2([[Bn254FieldElement; 2]; 3]);
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
-//TODO ensure iss is less than 255 bytes long
 pub struct ZkLoginPublicIdentifier {
+    /// At most 255 bytes long, as its length is encoded in a single byte.
+    #[cfg_attr(test, strategy("[ -~]{0,255}"))]
     iss: String,
     address_seed: Bn254FieldElement,
 }
 
 impl ZkLoginPublicIdentifier {
+    /// `None` if `iss` is longer than 255 bytes, as its length is encoded in a single byte.
+    pub fn new(iss: String, address_seed: Bn254FieldElement) -> Option<Self> {
+        (iss.len() <= u8::MAX as usize).then_some(Self { iss, address_seed })
+    }
+
     pub fn iss(&self) -> &str {
         &self.iss
     }
@@ -129,7 +137,7 @@ pub struct Jwk {
 }
 
 /// Key to identify a JWK, consists of iss and kid.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[cfg_attr(
     feature = "serde",
     derive(serde_derive::Serialize, serde_derive::Deserialize)
@@ -143,6 +151,250 @@ pub struct JwkId {
     pub kid: String,
 }
 
+/// A JSON Web Key as served by the JWK endpoint of an OIDC provider,
+/// <https://datatracker.ietf.org/doc/html/rfc7517#section-4>.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct JsonWebKey {
+    /// Key type parameter, <https://datatracker.ietf.org/doc/html/rfc7517#section-4.1>
+    pub kty: String,
+    /// Public key use parameter, <https://datatracker.ietf.org/doc/html/rfc7517#section-4.2>
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "use", default, skip_serializing_if = "Option::is_none")
+    )]
+    pub use_: Option<String>,
+    /// Key operations parameter, <https://datatracker.ietf.org/doc/html/rfc7517#section-4.3>
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub key_ops: Option<Vec<String>>,
+    /// Algorithm parameter, <https://datatracker.ietf.org/doc/html/rfc7517#section-4.4>
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub alg: Option<String>,
+    /// Key ID parameter, <https://datatracker.ietf.org/doc/html/rfc7517#section-4.5>
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kid: Option<String>,
+    /// RSA public exponent, <https://datatracker.ietf.org/doc/html/rfc7518#section-6.3.1.2>
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub e: Option<String>,
+    /// RSA modulus, <https://datatracker.ietf.org/doc/html/rfc7518#section-6.3.1.1>
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub n: Option<String>,
+}
+
+impl JsonWebKey {
+    /// Convert to the [`Jwk`] supported by zklogin, an RSA key used with RS256, along with its
+    /// `kid`.
+    pub fn to_jwk(&self) -> Result<(String, Jwk), JwkParseError> {
+        if self.kty != "RSA" {
+            return Err(JwkParseError::UnsupportedKeyType(self.kty.clone()));
+        }
+        let alg = self
+            .alg
+            .as_deref()
+            .ok_or(JwkParseError::MissingParameter("alg"))?;
+        if alg != "RS256" {
+            return Err(JwkParseError::UnsupportedAlgorithm(alg.to_owned()));
+        }
+        let kid = self
+            .kid
+            .clone()
+            .ok_or(JwkParseError::MissingParameter("kid"))?;
+        let e = self.e.clone().ok_or(JwkParseError::MissingParameter("e"))?;
+        // Some providers pad the base64url encoded modulus, which zklogin expects unpadded
+        let n = self
+            .n
+            .as_deref()
+            .ok_or(JwkParseError::MissingParameter("n"))?
+            .trim_end_matches('=')
+            .to_owned();
+
+        Ok((
+            kid,
+            Jwk {
+                kty: self.kty.clone(),
+                e,
+                n,
+                alg: alg.to_owned(),
+            },
+        ))
+    }
+}
+
+/// A JWK Set document, <https://datatracker.ietf.org/doc/html/rfc7517#section-5>.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct JsonWebKeySet {
+    pub keys: Vec<JsonWebKey>,
+}
+
+impl JsonWebKeySet {
+    /// The keys of the set supported by zklogin, identified for the provider `iss`.
+    ///
+    /// Keys which can't be used by zklogin are skipped.
+    pub fn jwks(&self, iss: &str) -> Vec<(JwkId, Jwk)> {
+        self.keys
+            .iter()
+            .filter_map(|key| key.to_jwk().ok())
+            .map(|(kid, jwk)| {
+                (
+                    JwkId {
+                        iss: iss.to_owned(),
+                        kid,
+                    },
+                    jwk,
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JwkParseError {
+    UnsupportedKeyType(String),
+    UnsupportedAlgorithm(String),
+    MissingParameter(&'static str),
+}
+
+impl std::fmt::Display for JwkParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedKeyType(kty) => write!(f, "unsupported key type {kty}"),
+            Self::UnsupportedAlgorithm(alg) => write!(f, "unsupported algorithm {alg}"),
+            Self::MissingParameter(name) => write!(f, "missing parameter {name}"),
+        }
+    }
+}
+
+impl std::error::Error for JwkParseError {}
+
+/// The set of active JWKs, as tracked by the `0x2::authenticator_state` module.
+///
+/// JWKs are kept sorted by id, the way the authenticator state object stores them: by issuer, then
+/// by key id, comparing shorter strings first and strings of the same length byte by byte.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct ActiveJwkSet {
+    jwks: Vec<ActiveJwk>,
+}
+
+impl ActiveJwkSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn jwks(&self) -> &[ActiveJwk] {
+        &self.jwks
+    }
+
+    pub fn get(&self, jwk_id: &JwkId) -> Option<&ActiveJwk> {
+        self.position(jwk_id).ok().map(|index| &self.jwks[index])
+    }
+
+    fn position(&self, jwk_id: &JwkId) -> Result<usize, usize> {
+        self.jwks
+            .binary_search_by(|active| jwk_id_cmp(&active.jwk_id, jwk_id))
+    }
+
+    /// Apply the JWKs of an `AuthenticatorStateUpdate`, returning the JWKs which were not active
+    /// before.
+    ///
+    /// JWKs which are already active have their epoch bumped. As on chain, a JWK whose id is
+    /// active with a different key is ignored.
+    pub fn apply_update(&mut self, update: &AuthenticatorStateUpdate) -> Vec<ActiveJwk> {
+        let mut added = Vec::new();
+        for new in &update.new_active_jwks {
+            match self.position(&new.jwk_id) {
+                Ok(index) => {
+                    let active = &mut self.jwks[index];
+                    if active.jwk == new.jwk {
+                        active.epoch = active.epoch.max(new.epoch);
+                    }
+                }
+                Err(index) => {
+                    self.jwks.insert(index, new.clone());
+                    added.push(new.clone());
+                }
+            }
+        }
+        added
+    }
+
+    /// Expire the JWKs last seen before `min_epoch`, returning them.
+    ///
+    /// As on chain, JWKs of a provider are only expired if the provider has a JWK seen at or
+    /// after `min_epoch`, so that a provider always keeps some keys.
+    pub fn expire(&mut self, min_epoch: EpochId) -> Vec<ActiveJwk> {
+        let mut max_epochs = std::collections::BTreeMap::<&str, EpochId>::new();
+        for active in &self.jwks {
+            let max_epoch = max_epochs.entry(&active.jwk_id.iss).or_default();
+            *max_epoch = (*max_epoch).max(active.epoch);
+        }
+        let (retained, expired) = self.jwks.iter().cloned().partition(|active| {
+            max_epochs[active.jwk_id.iss.as_str()] < min_epoch || active.epoch >= min_epoch
+        });
+        self.jwks = retained;
+        expired
+    }
+
+    /// The JWKs added and removed going from this set to `other`.
+    pub fn diff(&self, other: &ActiveJwkSet) -> JwkSetDiff {
+        let changed = |from: &ActiveJwkSet, to: &ActiveJwkSet| {
+            to.jwks
+                .iter()
+                .filter(|active| {
+                    from.get(&active.jwk_id)
+                        .is_none_or(|previous| previous.jwk != active.jwk)
+                })
+                .cloned()
+                .collect()
+        };
+        JwkSetDiff {
+            added: changed(self, other),
+            removed: changed(other, self),
+        }
+    }
+}
+
+/// The order of `jwk_lt` in `0x2::authenticator_state`, which only compares keys after ids, while
+/// ids are unique in the set.
+fn jwk_id_cmp(a: &JwkId, b: &JwkId) -> std::cmp::Ordering {
+    let string_bytes_cmp = |a: &str, b: &str| a.len().cmp(&b.len()).then(a.cmp(b));
+    string_bytes_cmp(&a.iss, &b.iss).then_with(|| string_bytes_cmp(&a.kid, &b.kid))
+}
+
+/// The difference between two [`ActiveJwkSet`]s, JWKs whose key changed are both removed and
+/// added.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JwkSetDiff {
+    pub added: Vec<ActiveJwk>,
+    pub removed: Vec<ActiveJwk>,
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
@@ -151,6 +403,11 @@ pub struct Bn254FieldElement(
 );
 
 impl Bn254FieldElement {
+    /// The element with the big-endian representation `padded`.
+    pub const fn new(padded: [u8; 32]) -> Self {
+        Self(padded)
+    }
+
     pub fn unpadded(&self) -> &[u8] {
         let mut buf = self.0.as_slice();
 
@@ -202,7 +459,8 @@ impl std::str::FromStr for Bn254FieldElement {
 
 #[cfg(test)]
 mod test {
-    use super::Bn254FieldElement;
+    use super::*;
+    use crate::types::Version;
     use num_bigint::BigUint;
     use proptest::prelude::*;
     use std::str::FromStr;
@@ -245,6 +503,163 @@ mod test {
         // Ensure unpadded doesn't crash
         seed.unpadded();
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn public_identifier_iss_length() {
+        let json = |iss: String| serde_json::json!({"iss": iss, "address_seed": "1"});
+        let identifier: ZkLoginPublicIdentifier =
+            serde_json::from_value(json("a".repeat(255))).unwrap();
+        assert_eq!(identifier.iss().len(), 255);
+        assert!(serde_json::from_value::<ZkLoginPublicIdentifier>(json("a".repeat(256))).is_err());
+        assert!(
+            ZkLoginPublicIdentifier::new("a".repeat(256), Bn254FieldElement::default()).is_none()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn parse_jwk_set() {
+        let set: JsonWebKeySet = serde_json::from_value(serde_json::json!({
+            "keys": [
+                {
+                    "kty": "RSA",
+                    "use": "sig",
+                    "alg": "RS256",
+                    "kid": "a",
+                    "e": "AQAB",
+                    "n": "sVdQ=="
+                },
+                {
+                    "kty": "EC",
+                    "use": "sig",
+                    "crv": "P-256",
+                    "kid": "b",
+                    "x": "f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU",
+                    "y": "x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"
+                },
+                {
+                    "kty": "RSA",
+                    "alg": "RS512",
+                    "kid": "c",
+                    "e": "AQAB",
+                    "n": "sVdQ"
+                }
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            set.keys[1].to_jwk(),
+            Err(JwkParseError::UnsupportedKeyType("EC".to_owned()))
+        );
+        assert_eq!(
+            set.keys[2].to_jwk(),
+            Err(JwkParseError::UnsupportedAlgorithm("RS512".to_owned()))
+        );
+        assert_eq!(
+            set.jwks("https://accounts.google.com"),
+            vec![(
+                JwkId {
+                    iss: "https://accounts.google.com".to_owned(),
+                    kid: "a".to_owned(),
+                },
+                Jwk {
+                    kty: "RSA".to_owned(),
+                    e: "AQAB".to_owned(),
+                    n: "sVdQ".to_owned(),
+                    alg: "RS256".to_owned(),
+                }
+            )]
+        );
+    }
+
+    fn active_jwk(iss: &str, kid: &str, n: &str, epoch: u64) -> ActiveJwk {
+        ActiveJwk {
+            jwk_id: JwkId {
+                iss: iss.to_owned(),
+                kid: kid.to_owned(),
+            },
+            jwk: Jwk {
+                kty: "RSA".to_owned(),
+                e: "AQAB".to_owned(),
+                n: n.to_owned(),
+                alg: "RS256".to_owned(),
+            },
+            epoch: EpochId::new(epoch),
+        }
+    }
+
+    fn update(new_active_jwks: Vec<ActiveJwk>) -> AuthenticatorStateUpdate {
+        AuthenticatorStateUpdate {
+            epoch: EpochId::new(0),
+            round: 0,
+            new_active_jwks,
+            authenticator_obj_initial_shared_version: Version::new(1),
+        }
+    }
+
+    #[test]
+    fn track_active_jwks() {
+        let mut set = ActiveJwkSet::new();
+        let added = set.apply_update(&update(vec![
+            active_jwk("google", "2", "n2", 1),
+            active_jwk("google", "1", "n1", 1),
+            active_jwk("twitch", "1", "t1", 1),
+        ]));
+        assert_eq!(added.len(), 3);
+        // Sorted by id
+        assert_eq!(set.jwks()[0], active_jwk("google", "1", "n1", 1));
+        // Shorter ids first, as on chain
+        let mut sorted = set.clone();
+        sorted.apply_update(&update(vec![
+            active_jwk("google", "10", "n10", 1),
+            active_jwk("accounts.google", "1", "a1", 1),
+        ]));
+        let ids = sorted
+            .jwks()
+            .iter()
+            .map(|active| (active.jwk_id.iss.as_str(), active.jwk_id.kid.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            [
+                ("google", "1"),
+                ("google", "2"),
+                ("google", "10"),
+                ("twitch", "1"),
+                ("accounts.google", "1"),
+            ]
+        );
+        let before = set.clone();
+
+        // A refreshed key, a new key, and a changed key which is ignored
+        let added = set.apply_update(&update(vec![
+            active_jwk("google", "1", "n1", 3),
+            active_jwk("google", "3", "n3", 3),
+            active_jwk("twitch", "1", "changed", 3),
+        ]));
+        assert_eq!(added, vec![active_jwk("google", "3", "n3", 3)]);
+        assert_eq!(set.jwks().len(), 4);
+        assert_eq!(
+            set.get(&active_jwk("google", "1", "", 0).jwk_id)
+                .unwrap()
+                .epoch,
+            EpochId::new(3)
+        );
+
+        // Only google has keys seen since epoch 2
+        let expired = set.expire(EpochId::new(2));
+        assert_eq!(expired, vec![active_jwk("google", "2", "n2", 1)]);
+
+        assert_eq!(
+            before.diff(&set),
+            JwkSetDiff {
+                added: vec![active_jwk("google", "3", "n3", 3)],
+                removed: vec![active_jwk("google", "2", "n2", 1)],
+            }
+        );
+    }
 }
 
 #[cfg(feature = "serde")]
@@ -305,7 +720,8 @@ mod serialization {
                 }
 
                 let Readable { iss, address_seed } = Deserialize::deserialize(deserializer)?;
-                Ok(Self { iss, address_seed })
+                Self::new(iss, address_seed)
+                    .ok_or_else(|| serde::de::Error::custom("zklogin iss is longer than 255 bytes"))
             } else {
                 let bytes: Cow<'de, [u8]> = Bytes::deserialize_as(deserializer)?;
                 let iss_len = *bytes
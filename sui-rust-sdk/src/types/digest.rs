@@ -31,6 +31,13 @@ impl Digest {
         Self::new(buf)
     }
 
+    /// Generate a random digest using the operating system's random number generator.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn random() -> Self {
+        Self::generate(rand_core::OsRng)
+    }
+
     pub const fn inner(&self) -> &[u8; Self::LENGTH] {
         &self.0
     }
@@ -58,18 +65,59 @@ impl Digest {
         self.to_string()
     }
 
+    /// Decode a digest from hex, with or without a `0x` prefix.
+    pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, DigestParseError> {
+        let hex = hex.as_ref();
+        let hex = hex.strip_prefix(b"0x").unwrap_or(hex);
+
+        let mut buf = [0; Self::LENGTH];
+        hex::decode_to_slice(hex, &mut buf).map_err(|_| DigestParseError)?;
+
+        Ok(Self(buf))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
     pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, DigestParseError> {
         <[u8; Self::LENGTH]>::try_from(bytes.as_ref())
             .map_err(|_| DigestParseError)
             .map(Self)
     }
+
+    /// Compare two digests in constant time, e.g. when checking a digest against a secret.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let difference = self
+            .0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0, |difference, (a, b)| difference | (a ^ b));
+        std::hint::black_box(difference) == 0
+    }
 }
 
+/// Parses the Base58 encoding of a digest, which is its canonical form, or its hex encoding.
+///
+/// The Base58 encoding of a digest is at most 44 characters long, so a hex encoding, which is 64
+/// characters long without the optional `0x` prefix, can't be mistaken for one.
 impl std::str::FromStr for Digest {
     type Err = DigestParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Self::from_base58(s)
+        if s.starts_with("0x") || s.len() == Self::LENGTH * 2 {
+            Self::from_hex(s)
+        } else {
+            Self::from_base58(s)
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for Digest {
+    type Error = DigestParseError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
     }
 }
 
@@ -162,8 +210,37 @@ impl<'de> serde_with::DeserializeAs<'de, [u8; Digest::LENGTH]> for ReadableDiges
     where
         D: serde::Deserializer<'de>,
     {
-        let digest: Digest = serde_with::DisplayFromStr::deserialize_as(deserializer)?;
-        Ok(digest.into_inner())
+        let s = <std::borrow::Cow<'de, str> as serde::Deserialize>::deserialize(deserializer)?;
+        Digest::from_readable(&s)
+            .map(Digest::into_inner)
+            .ok_or_else(|| {
+                serde::de::Error::custom(format_args!(
+                    "invalid digest `{s}`, must be Base58, hex or Base64"
+                ))
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Digest {
+    /// Parses a digest the way readable deserialization does: from its Base58 or hex encoding,
+    /// like [`FromStr`](std::str::FromStr), or else from padded Base64 or Base64Url, as emitted by
+    /// some of Sui's APIs.
+    ///
+    /// Unpadded Base64 isn't accepted: a digest's unpadded Base64 can also be valid Base58 of
+    /// another 32 bytes, while the padding of padded Base64 is never part of Base58.
+    fn from_readable(s: &str) -> Option<Self> {
+        use base64ct::Encoding;
+
+        if !s.ends_with('=') {
+            return s.parse().ok();
+        }
+        let mut buf = [0; Self::LENGTH];
+        let len = base64ct::Base64::decode(s, &mut buf)
+            .or_else(|_| base64ct::Base64Url::decode(s, &mut buf))
+            .ok()?
+            .len();
+        (len == Self::LENGTH).then_some(Self(buf))
     }
 }
 
@@ -174,7 +251,7 @@ impl std::fmt::Display for DigestParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "Unable to parse Digest (must be Base58 string of length {})",
+            "Unable to parse Digest (must be Base58 or hex string of length {})",
             Digest::LENGTH
         )
     }
@@ -214,6 +291,13 @@ macro_rules! impl_digest {
                 Self(Digest::generate(rng))
             }
 
+            /// Generate a random digest using the operating system's random number generator.
+            #[cfg(feature = "rand")]
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+            pub fn random() -> Self {
+                Self(Digest::random())
+            }
+
             pub const fn inner(&self) -> &[u8; Self::LENGTH] {
                 self.0.inner()
             }
@@ -235,16 +319,53 @@ macro_rules! impl_digest {
                 self.to_string()
             }
 
+            pub fn from_hex<T: AsRef<[u8]>>(hex: T) -> Result<Self, DigestParseError> {
+                Digest::from_hex(hex).map(Self)
+            }
+
+            pub fn to_hex(&self) -> String {
+                self.0.to_hex()
+            }
+
             pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> Result<Self, DigestParseError> {
                 Digest::from_bytes(bytes).map(Self)
             }
+
+            pub fn ct_eq(&self, other: &Self) -> bool {
+                self.0.ct_eq(&other.0)
+            }
+
+            /// Treat an untyped digest as a digest of this kind.
+            ///
+            /// Converting between kinds of digests, e.g. from an `ObjectDigest` to a
+            /// `TransactionDigest`, always goes through `into_digest` and `from_digest`, so that
+            /// the reinterpretation is spelled out.
+            pub const fn from_digest(digest: Digest) -> Self {
+                Self(digest)
+            }
+
+            pub const fn into_digest(self) -> Digest {
+                self.0
+            }
+
+            pub const fn as_digest(&self) -> &Digest {
+                &self.0
+            }
         }
 
         impl std::str::FromStr for $t {
             type Err = DigestParseError;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                Self::from_base58(s)
+                s.parse().map(Self)
+            }
+        }
+
+        impl TryFrom<&[u8]> for $t {
+            type Error = DigestParseError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                Self::from_bytes(bytes)
             }
         }
 
@@ -314,6 +435,66 @@ impl_digest!(TransactionEventsDigest);
 impl_digest!(ObjectDigest);
 impl_digest!(ConsensusCommitDigest);
 impl_digest!(EffectsAuxiliaryDataDigest);
+impl_digest!(ChainIdentifier);
+
+impl ChainIdentifier {
+    /// The identifier of Sui mainnet, the digest of its genesis checkpoint.
+    pub const MAINNET: Self = Self::new([
+        53, 131, 74, 138, 193, 124, 164, 143, 177, 74, 200, 249, 156, 23, 201, 135, 71, 233, 93,
+        208, 114, 148, 174, 65, 164, 107, 56, 34, 70, 164, 73, 155,
+    ]);
+
+    /// The identifier of Sui testnet, the digest of its genesis checkpoint.
+    pub const TESTNET: Self = Self::new([
+        76, 120, 173, 172, 242, 162, 245, 173, 128, 242, 126, 215, 213, 74, 166, 157, 58, 120, 241,
+        202, 103, 253, 239, 158, 207, 87, 84, 245, 184, 187, 119, 176,
+    ]);
+
+    /// The hex encoding of the first 4 bytes of the identifier, as reported by
+    /// `sui_getChainIdentifier`, e.g. `35834a8a` for mainnet.
+    pub fn short_id(&self) -> String {
+        hex::encode(&self.inner()[..4])
+    }
+
+    /// Check that `actual`, e.g. the chain a signed artifact is bound to, is this chain.
+    pub fn verify(&self, actual: ChainIdentifier) -> Result<(), ChainMismatchError> {
+        if *self != actual {
+            return Err(ChainMismatchError {
+                expected: *self,
+                actual,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A value bound to another chain than the one expected, e.g. a transaction built for testnet
+/// submitted to mainnet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainMismatchError {
+    pub expected: ChainIdentifier,
+    pub actual: ChainIdentifier,
+}
+
+impl std::fmt::Display for ChainMismatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "bound to chain {}, expected {}",
+            self.actual.short_id(),
+            self.expected.short_id()
+        )
+    }
+}
+
+impl std::error::Error for ChainMismatchError {}
+
+/// A chain is identified by the digest of its genesis checkpoint.
+impl From<CheckpointDigest> for ChainIdentifier {
+    fn from(genesis_checkpoint: CheckpointDigest) -> Self {
+        Self(genesis_checkpoint.0)
+    }
+}
 
 #[cfg(test)]
 mod test {
@@ -329,4 +510,138 @@ mod test {
         let d = s.parse::<Digest>().unwrap();
         assert_eq!(digest, d);
     }
+
+    #[proptest]
+    fn roundtrip_hex(digest: TransactionDigest) {
+        let hex = digest.to_hex();
+        assert_eq!(hex.parse::<TransactionDigest>().unwrap(), digest);
+        assert_eq!(
+            format!("{digest:#x}").parse::<TransactionDigest>().unwrap(),
+            digest
+        );
+        assert_eq!(TransactionDigest::from_hex(&hex).unwrap(), digest);
+    }
+
+    #[test]
+    fn parse_digests() {
+        let digest: ObjectDigest = "4btiuiMPvEENsttpZC7CZ53DruC3MAgfznDbASZ7DR6S"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            "0x35834a8ac17ca48fb14ac8f99c17c98747e95dd07294ae41a46b382246a4499b"
+                .parse::<ObjectDigest>()
+                .unwrap(),
+            digest
+        );
+        assert_eq!(
+            "35834a8ac17ca48fb14ac8f99c17c98747e95dd07294ae41a46b382246a4499b"
+                .parse::<ObjectDigest>()
+                .unwrap(),
+            digest
+        );
+        assert_eq!("0x35834a8a".parse::<ObjectDigest>(), Err(DigestParseError));
+        assert_eq!(
+            "not a digest".parse::<ObjectDigest>(),
+            Err(DigestParseError)
+        );
+
+        assert_eq!(ObjectDigest::try_from(digest.as_bytes()), Ok(digest));
+        assert_eq!(
+            ObjectDigest::try_from(&digest.as_bytes()[1..]),
+            Err(DigestParseError)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn readable_digest_encodings() {
+        use base64ct::Encoding;
+
+        let digest = ObjectDigest::new([
+            0xfb, 0xff, 0xbf, 0x3e, 0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09,
+            0x0a, 0x0b, 0x0c, 0x0d, 0x0e, 0x0f, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17,
+            0x18, 0x19, 0x1a, 0x1b,
+        ]);
+        let bytes = digest.inner();
+        for encoded in [
+            digest.to_string(),
+            digest.to_hex(),
+            base64ct::Base64::encode_string(bytes),
+            base64ct::Base64Url::encode_string(bytes),
+        ] {
+            let json = serde_json::Value::String(encoded);
+            let parsed: ObjectDigest = serde_json::from_value(json.clone()).unwrap();
+            assert_eq!(parsed, digest, "{json}");
+            // Output is always the canonical Base58
+            assert_eq!(
+                serde_json::to_value(parsed).unwrap(),
+                serde_json::Value::String(digest.to_string())
+            );
+        }
+
+        for invalid in [
+            base64ct::Base64::encode_string(&bytes[1..]),
+            base64ct::Base64Unpadded::encode_string(bytes),
+            base64ct::Base64UrlUnpadded::encode_string(bytes),
+        ] {
+            let error = serde_json::from_value::<ObjectDigest>(invalid.into()).unwrap_err();
+            assert!(error.to_string().starts_with("invalid digest"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn base58_and_unpadded_base64_collision() {
+        // The unpadded Base64 of these bytes is also the Base58 of other 32 bytes
+        let bytes = [
+            191, 175, 61, 94, 192, 16, 138, 107, 31, 126, 155, 167, 206, 125, 184, 25, 118, 148, 3,
+            100, 49, 69, 114, 188, 136, 72, 83, 116, 38, 159, 221, 224,
+        ];
+        let unpadded = "v689XsAQimsffpunzn24GXaUA2QxRXK8iEhTdCaf3eA";
+
+        let parsed: ObjectDigest = serde_json::from_value(unpadded.into()).unwrap();
+        assert_eq!(parsed, unpadded.parse().unwrap());
+        assert_ne!(parsed.into_inner(), bytes);
+
+        let padded = format!("{unpadded}=");
+        let parsed: ObjectDigest = serde_json::from_value(padded.into()).unwrap();
+        assert_eq!(parsed.into_inner(), bytes);
+    }
+
+    #[test]
+    fn compare_and_convert() {
+        let digest = TransactionDigest::new([7; 32]);
+        assert!(digest.ct_eq(&TransactionDigest::new([7; 32])));
+        let mut other = [7; 32];
+        other[31] = 8;
+        assert!(!digest.ct_eq(&TransactionDigest::new(other)));
+
+        let object_digest = ObjectDigest::from_digest(digest.into_digest());
+        assert_eq!(object_digest.as_digest(), digest.as_digest());
+    }
+
+    #[test]
+    fn known_chain_identifiers() {
+        assert_eq!(
+            ChainIdentifier::MAINNET.to_string(),
+            "4btiuiMPvEENsttpZC7CZ53DruC3MAgfznDbASZ7DR6S"
+        );
+        assert_eq!(ChainIdentifier::MAINNET.short_id(), "35834a8a");
+        assert_eq!(
+            ChainIdentifier::TESTNET.to_string(),
+            "69WiPg3DAQiwdxfncX6wYQ2siKwAe6L9BZthQea3JNMD"
+        );
+        assert_eq!(ChainIdentifier::TESTNET.short_id(), "4c78adac");
+
+        ChainIdentifier::MAINNET
+            .verify(ChainIdentifier::MAINNET)
+            .unwrap();
+        assert_eq!(
+            ChainIdentifier::MAINNET.verify(ChainIdentifier::TESTNET),
+            Err(ChainMismatchError {
+                expected: ChainIdentifier::MAINNET,
+                actual: ChainIdentifier::TESTNET,
+            })
+        );
+    }
 }
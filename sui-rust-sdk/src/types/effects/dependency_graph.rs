@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::types::CheckpointData;
+use crate::types::TransactionDigest;
+use crate::types::TransactionEffects;
+
+/// A directed acyclic graph of transactions, with an edge from each transaction to every
+/// transaction it depends on according to its effects.
+///
+/// Only dependencies between transactions of the graph are considered: dependencies on other
+/// transactions, e.g. ones included in earlier checkpoints, are assumed to have been processed
+/// already.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionDependencyGraph {
+    /// The transactions in insertion order, with their dependencies.
+    transactions: Vec<(TransactionDigest, Vec<TransactionDigest>)>,
+    index: BTreeMap<TransactionDigest, usize>,
+}
+
+impl TransactionDependencyGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_effects<'a>(effects: impl IntoIterator<Item = &'a TransactionEffects>) -> Self {
+        let mut graph = Self::new();
+        for effects in effects {
+            graph.insert(effects);
+        }
+        graph
+    }
+
+    /// The graph of the transactions of a checkpoint.
+    pub fn from_checkpoint(checkpoint: &CheckpointData) -> Self {
+        Self::from_effects(
+            checkpoint
+                .transactions
+                .iter()
+                .map(|transaction| &transaction.effects),
+        )
+    }
+
+    /// Add the transaction of `effects` to the graph.
+    ///
+    /// Returns `false` if the transaction was already part of the graph.
+    pub fn insert(&mut self, effects: &TransactionEffects) -> bool {
+        let digest = *effects.transaction_digest();
+        if self.index.contains_key(&digest) {
+            return false;
+        }
+
+        self.index.insert(digest, self.transactions.len());
+        self.transactions
+            .push((digest, effects.dependencies().to_vec()));
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transactions.is_empty()
+    }
+
+    pub fn contains(&self, transaction: &TransactionDigest) -> bool {
+        self.index.contains_key(transaction)
+    }
+
+    /// The transactions of the graph, in insertion order.
+    pub fn transactions(&self) -> impl Iterator<Item = &TransactionDigest> {
+        self.transactions.iter().map(|(digest, _)| digest)
+    }
+
+    /// The transactions of the graph that `transaction` depends on.
+    pub fn dependencies(
+        &self,
+        transaction: &TransactionDigest,
+    ) -> impl Iterator<Item = &TransactionDigest> {
+        self.index
+            .get(transaction)
+            .map(|i| self.transactions[*i].1.as_slice())
+            .unwrap_or_default()
+            .iter()
+            .filter(|dependency| self.contains(dependency))
+    }
+
+    /// The transactions of the graph that depend on `transaction`.
+    pub fn dependents<'a>(
+        &'a self,
+        transaction: &'a TransactionDigest,
+    ) -> impl Iterator<Item = &'a TransactionDigest> {
+        self.transactions
+            .iter()
+            .filter(move |(_, dependencies)| dependencies.contains(transaction))
+            .map(|(digest, _)| digest)
+    }
+
+    /// The transactions of the graph ordered such that every transaction comes after the
+    /// transactions it depends on.
+    ///
+    /// Transactions which don't depend on each other are kept in insertion order, so the
+    /// transactions of a checkpoint, which are already causally ordered, keep their order.
+    pub fn topological_order(&self) -> Result<Vec<TransactionDigest>, DependencyCycleError> {
+        let mut remaining_dependencies = Vec::with_capacity(self.len());
+        let mut dependents = vec![Vec::new(); self.len()];
+        for (i, (_, dependencies)) in self.transactions.iter().enumerate() {
+            let dependencies: BTreeSet<usize> = dependencies
+                .iter()
+                .filter_map(|dependency| self.index.get(dependency).copied())
+                .collect();
+            for dependency in &dependencies {
+                dependents[*dependency].push(i);
+            }
+            remaining_dependencies.push(dependencies.len());
+        }
+
+        // Kahn's algorithm, always picking the earliest inserted transaction that is ready
+        let mut ready: BTreeSet<usize> = (0..self.len())
+            .filter(|i| remaining_dependencies[*i] == 0)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(i) = ready.pop_first() {
+            order.push(self.transactions[i].0);
+            for dependent in &dependents[i] {
+                remaining_dependencies[*dependent] -= 1;
+                if remaining_dependencies[*dependent] == 0 {
+                    ready.insert(*dependent);
+                }
+            }
+        }
+
+        if order.len() < self.len() {
+            return Err(DependencyCycleError {
+                transactions: self
+                    .transactions
+                    .iter()
+                    .zip(remaining_dependencies)
+                    .filter(|(_, remaining)| *remaining > 0)
+                    .map(|((digest, _), _)| *digest)
+                    .collect(),
+            });
+        }
+        Ok(order)
+    }
+}
+
+/// The dependencies of transactions form a cycle, which valid effects never do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DependencyCycleError {
+    /// The transactions which could not be ordered, as they are part of or depend on a cycle.
+    pub transactions: Vec<TransactionDigest>,
+}
+
+impl std::fmt::Display for DependencyCycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} transactions have cyclic dependencies",
+            self.transactions.len()
+        )
+    }
+}
+
+impl std::error::Error for DependencyCycleError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::EpochId;
+    use crate::types::ExecutionStatus;
+    use crate::types::GasCostSummary;
+    use crate::types::TransactionEffectsV2;
+    use crate::types::Version;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn digest(i: u8) -> TransactionDigest {
+        TransactionDigest::new([i; 32])
+    }
+
+    fn effects(transaction: u8, dependencies: &[u8]) -> TransactionEffects {
+        TransactionEffects::V2(Box::new(TransactionEffectsV2 {
+            status: ExecutionStatus::Success,
+            epoch: EpochId::new(0),
+            gas_used: GasCostSummary::default(),
+            transaction_digest: digest(transaction),
+            gas_object_index: None,
+            events_digest: None,
+            dependencies: dependencies.iter().copied().map(digest).collect(),
+            lamport_version: Version::new(1),
+            changed_objects: vec![],
+            unchanged_shared_objects: vec![],
+            auxiliary_data_digest: None,
+        }))
+    }
+
+    #[test]
+    fn causal_order() {
+        // 0 is a transaction of an earlier checkpoint
+        let graph = TransactionDependencyGraph::from_effects(&[
+            effects(4, &[2, 3]),
+            effects(3, &[1]),
+            effects(1, &[0]),
+            effects(2, &[0, 1]),
+            effects(5, &[]),
+        ]);
+        assert_eq!(graph.len(), 5);
+        assert!(!graph.contains(&digest(0)));
+        assert_eq!(
+            graph.dependencies(&digest(2)).collect::<Vec<_>>(),
+            [&digest(1)]
+        );
+        assert_eq!(
+            graph.dependents(&digest(1)).collect::<Vec<_>>(),
+            [&digest(3), &digest(2)]
+        );
+        assert_eq!(graph.dependencies(&digest(0)).count(), 0);
+
+        assert_eq!(
+            graph.topological_order(),
+            Ok(vec![digest(1), digest(3), digest(2), digest(4), digest(5)])
+        );
+    }
+
+    #[test]
+    fn insert_once() {
+        let mut graph = TransactionDependencyGraph::new();
+        assert!(graph.insert(&effects(1, &[])));
+        assert!(!graph.insert(&effects(1, &[2])));
+        assert_eq!(graph.transactions().collect::<Vec<_>>(), [&digest(1)]);
+        assert_eq!(graph.dependencies(&digest(1)).count(), 0);
+    }
+
+    #[test]
+    fn dependency_cycle() {
+        let graph = TransactionDependencyGraph::from_effects(&[
+            effects(1, &[]),
+            effects(2, &[3]),
+            effects(3, &[2]),
+            effects(4, &[1, 3]),
+        ]);
+        assert_eq!(
+            graph.topological_order(),
+            Err(DependencyCycleError {
+                transactions: vec![digest(2), digest(3), digest(4)]
+            })
+        );
+    }
+}
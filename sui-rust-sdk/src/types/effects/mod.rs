@@ -1,6 +1,9 @@
+mod dependency_graph;
 mod v1;
 mod v2;
 
+pub use dependency_graph::DependencyCycleError;
+pub use dependency_graph::TransactionDependencyGraph;
 pub use v1::ModifiedAtVersion;
 pub use v1::ObjectReferenceWithOwner;
 pub use v1::TransactionEffectsV1;
@@ -13,6 +16,13 @@ pub use v2::TransactionEffectsV2;
 pub use v2::UnchangedSharedKind;
 pub use v2::UnchangedSharedObject;
 
+use crate::types::execution_status::ExecutionStatus;
+use crate::types::GasCostSummary;
+use crate::types::ObjectId;
+use crate::types::ObjectReference;
+use crate::types::TransactionDigest;
+use crate::types::Version;
+
 /// The response from processing a transaction or a certified transaction
 #[derive(Eq, PartialEq, Clone, Debug)]
 #[cfg_attr(
@@ -28,6 +38,78 @@ pub enum TransactionEffects {
     V2(Box<TransactionEffectsV2>),
 }
 
+impl TransactionEffects {
+    /// The digest of the transaction that produced these effects.
+    pub fn transaction_digest(&self) -> &TransactionDigest {
+        match self {
+            Self::V1(effects) => effects.transaction_digest(),
+            Self::V2(effects) => &effects.transaction_digest,
+        }
+    }
+
+    /// The set of transaction digests this transaction depends on.
+    pub fn dependencies(&self) -> &[TransactionDigest] {
+        match self {
+            Self::V1(effects) => effects.dependencies(),
+            Self::V2(effects) => &effects.dependencies,
+        }
+    }
+
+    /// The reference of `object_id` as written by the transaction, if it created, mutated or
+    /// unwrapped the object.
+    pub fn written_object(&self, object_id: &ObjectId) -> Option<ObjectReference> {
+        match self {
+            Self::V1(effects) => effects.written_object(object_id),
+            Self::V2(effects) => effects.written_object(object_id),
+        }
+    }
+
+    /// Whether the transaction succeeded, or why it aborted.
+    pub fn status(&self) -> &ExecutionStatus {
+        match self {
+            Self::V1(effects) => effects.status(),
+            Self::V2(effects) => &effects.status,
+        }
+    }
+
+    /// The gas charged for the transaction.
+    pub fn gas_used(&self) -> &GasCostSummary {
+        match self {
+            Self::V1(effects) => effects.gas_used(),
+            Self::V2(effects) => &effects.gas_used,
+        }
+    }
+
+    /// The version each object modified or read by the transaction had before it executed,
+    /// excluding immutable objects and packages, which the effects don't record.
+    pub fn input_versions(&self) -> Box<dyn Iterator<Item = (ObjectId, Version)> + '_> {
+        match self {
+            Self::V1(effects) => Box::new(effects.input_versions()),
+            Self::V2(effects) => Box::new(effects.input_versions()),
+        }
+    }
+
+    /// The ids of the objects changed by the transaction, along with the shared objects it only
+    /// read, in no particular order and possibly with duplicates.
+    pub fn object_ids(&self) -> Box<dyn Iterator<Item = &ObjectId> + '_> {
+        match self {
+            Self::V1(effects) => Box::new(effects.object_ids()),
+            Self::V2(effects) => Box::new(
+                effects
+                    .changed_objects
+                    .iter()
+                    .map(|changed| &changed.object_id)
+                    .chain(
+                        effects
+                            .unchanged_shared_objects
+                            .iter()
+                            .map(|unchanged| &unchanged.object_id),
+                    ),
+            ),
+        }
+    }
+}
+
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 mod serialization {
@@ -1,12 +1,12 @@
 use crate::types::execution_status::ExecutionStatus;
 use crate::types::object::Owner;
-use crate::types::object::Version;
 use crate::types::EpochId;
 use crate::types::GasCostSummary;
 use crate::types::ObjectId;
 use crate::types::ObjectReference;
 use crate::types::TransactionDigest;
 use crate::types::TransactionEventsDigest;
+use crate::types::Version;
 
 /// The response from processing a transaction or a certified transaction
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -61,6 +61,71 @@ pub struct TransactionEffectsV1 {
     dependencies: Vec<TransactionDigest>,
 }
 
+impl TransactionEffectsV1 {
+    /// The digest of the transaction that produced these effects.
+    pub fn transaction_digest(&self) -> &TransactionDigest {
+        &self.transaction_digest
+    }
+
+    /// The set of transaction digests this transaction depends on.
+    pub fn dependencies(&self) -> &[TransactionDigest] {
+        &self.dependencies
+    }
+
+    /// The reference of `object_id` as written by the transaction, if it created, mutated or
+    /// unwrapped the object.
+    pub fn written_object(&self, object_id: &ObjectId) -> Option<ObjectReference> {
+        self.created
+            .iter()
+            .chain(&self.mutated)
+            .chain(&self.unwrapped)
+            .map(|object| &object.reference)
+            .find(|reference| reference.object_id() == object_id)
+            .cloned()
+    }
+
+    pub fn status(&self) -> &ExecutionStatus {
+        &self.status
+    }
+
+    pub fn gas_used(&self) -> &GasCostSummary {
+        &self.gas_used
+    }
+
+    /// The version each object modified or read by the transaction had before it executed.
+    pub fn input_versions(&self) -> impl Iterator<Item = (ObjectId, Version)> + '_ {
+        self.modified_at_versions
+            .iter()
+            .map(|modified| (modified.object_id, modified.version))
+            .chain(
+                self.shared_objects
+                    .iter()
+                    .map(|shared| (*shared.object_id(), shared.version())),
+            )
+    }
+
+    /// The ids of the objects changed by the transaction, along with the shared objects it only
+    /// read, in no particular order and possibly with duplicates.
+    pub fn object_ids(&self) -> impl Iterator<Item = &ObjectId> {
+        self.created
+            .iter()
+            .chain(&self.mutated)
+            .chain(&self.unwrapped)
+            .chain(std::iter::once(&self.gas_object))
+            .map(|object| &object.reference)
+            .chain(&self.shared_objects)
+            .chain(&self.deleted)
+            .chain(&self.unwrapped_then_deleted)
+            .chain(&self.wrapped)
+            .map(ObjectReference::object_id)
+            .chain(
+                self.modified_at_versions
+                    .iter()
+                    .map(|modified| &modified.object_id),
+            )
+    }
+}
+
 #[derive(Eq, PartialEq, Clone, Debug)]
 #[cfg_attr(
     feature = "serde",
@@ -1,13 +1,14 @@
 use crate::types::digest::EffectsAuxiliaryDataDigest;
 use crate::types::execution_status::ExecutionStatus;
 use crate::types::object::Owner;
-use crate::types::object::Version;
 use crate::types::EpochId;
 use crate::types::GasCostSummary;
 use crate::types::ObjectDigest;
 use crate::types::ObjectId;
+use crate::types::ObjectReference;
 use crate::types::TransactionDigest;
 use crate::types::TransactionEventsDigest;
+use crate::types::Version;
 
 /// The response from processing a transaction or a certified transaction
 #[derive(Eq, PartialEq, Clone, Debug)]
@@ -52,6 +53,50 @@ pub struct TransactionEffectsV2 {
     pub auxiliary_data_digest: Option<EffectsAuxiliaryDataDigest>,
 }
 
+impl TransactionEffectsV2 {
+    /// The reference of `object_id` as written by the transaction, if it created, mutated or
+    /// unwrapped the object.
+    pub fn written_object(&self, object_id: &ObjectId) -> Option<ObjectReference> {
+        let changed = self
+            .changed_objects
+            .iter()
+            .find(|changed| &changed.object_id == object_id)?;
+        match &changed.change.output_state {
+            ObjectOut::NotExist => None,
+            ObjectOut::ObjectWrite { digest, .. } => Some(ObjectReference::new(
+                *object_id,
+                self.lamport_version,
+                *digest,
+            )),
+            ObjectOut::PackageWrite { version, digest } => {
+                Some(ObjectReference::new(*object_id, *version, *digest))
+            }
+        }
+    }
+
+    /// The version each object modified or read by the transaction had before it executed.
+    pub fn input_versions(&self) -> impl Iterator<Item = (ObjectId, Version)> + '_ {
+        let changed =
+            self.changed_objects
+                .iter()
+                .filter_map(|changed| match changed.change.input_state {
+                    ObjectIn::NotExist => None,
+                    ObjectIn::Exist { version, .. } => Some((changed.object_id, version)),
+                });
+        let read = self
+            .unchanged_shared_objects
+            .iter()
+            .filter_map(|unchanged| match unchanged.kind {
+                UnchangedSharedKind::ReadOnlyRoot { version, .. } => {
+                    Some((unchanged.object_id, version))
+                }
+                UnchangedSharedKind::MutateDeleted { .. }
+                | UnchangedSharedKind::ReadDeleted { .. } => None,
+            });
+        changed.chain(read)
+    }
+}
+
 //XXX Do we maybe want to just fold "EffectsObjectChange" into this struct?
 #[derive(Eq, PartialEq, Clone, Debug)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
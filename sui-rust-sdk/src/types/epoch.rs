@@ -0,0 +1,305 @@
+//! Mapping epochs to wall-clock time.
+//!
+//! The chain only records when epochs started, in the `ChangeEpoch` transaction, and so in the
+//! [`EpochTransition`], which ended the previous one. The end of the current epoch, and the start
+//! of those after it, are estimated from the epoch duration of the system state, `epoch_duration_ms`,
+//! which the end of an epoch can overrun while validators reconfigure.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use super::CheckpointSummary;
+use super::CheckpointTimestamp;
+use super::EpochId;
+use super::EpochTransition;
+use super::EpochTransitionError;
+use super::TransactionExpiration;
+
+/// When an epoch started, and how long epochs are expected to last.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct EpochInfo {
+    pub epoch: EpochId,
+    /// Unix timestamp when the epoch started, in milliseconds.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub start_timestamp_ms: CheckpointTimestamp,
+    /// The expected duration of epochs, the `epoch_duration_ms` of the system state.
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub duration_ms: u64,
+}
+
+impl EpochInfo {
+    pub fn new(epoch: EpochId, start_timestamp_ms: CheckpointTimestamp, duration_ms: u64) -> Self {
+        Self {
+            epoch,
+            start_timestamp_ms,
+            duration_ms,
+        }
+    }
+
+    /// The epoch started by `transition`.
+    pub fn from_transition(
+        transition: &EpochTransition,
+        duration_ms: u64,
+    ) -> Result<Self, EpochTransitionError> {
+        Ok(Self::new(
+            transition.next_epoch()?,
+            transition.epoch_start_timestamp_ms,
+            duration_ms,
+        ))
+    }
+
+    /// The estimated start of `epoch`, in milliseconds, which is exact for this epoch. `None` for
+    /// earlier epochs, whose durations aren't known.
+    pub fn estimated_start_ms(&self, epoch: EpochId) -> Option<CheckpointTimestamp> {
+        let epochs = epoch.into_inner().checked_sub(self.epoch.into_inner())?;
+        Some(
+            epochs
+                .saturating_mul(self.duration_ms)
+                .saturating_add(self.start_timestamp_ms),
+        )
+    }
+
+    /// The estimated end of the epoch, in milliseconds.
+    pub fn estimated_end_ms(&self) -> CheckpointTimestamp {
+        self.start_timestamp_ms.saturating_add(self.duration_ms)
+    }
+
+    /// The epoch expected to be current at `timestamp_ms`, if it isn't before this epoch.
+    pub fn estimated_epoch_at(&self, timestamp_ms: CheckpointTimestamp) -> Option<EpochId> {
+        let elapsed = timestamp_ms.checked_sub(self.start_timestamp_ms)?;
+        let epochs = elapsed.checked_div(self.duration_ms).unwrap_or(0);
+        self.epoch.checked_add(epochs)
+    }
+}
+
+/// The known start of a range of epochs, as fetched from their [`EpochTransition`]s, to find the
+/// epoch of a timestamp or the time span of an epoch.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EpochTimeline {
+    starts: BTreeMap<EpochId, CheckpointTimestamp>,
+}
+
+impl EpochTimeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record that `epoch` started at `start_timestamp_ms`.
+    pub fn insert(&mut self, epoch: EpochId, start_timestamp_ms: CheckpointTimestamp) {
+        self.starts.insert(epoch, start_timestamp_ms);
+    }
+
+    /// Record the start of the epoch begun by `transition`.
+    pub fn add_transition(
+        &mut self,
+        transition: &EpochTransition,
+    ) -> Result<(), EpochTransitionError> {
+        self.insert(
+            transition.next_epoch()?,
+            transition.epoch_start_timestamp_ms,
+        );
+        Ok(())
+    }
+
+    /// The start of `epoch`, in milliseconds, if known.
+    pub fn start_ms(&self, epoch: EpochId) -> Option<CheckpointTimestamp> {
+        self.starts.get(&epoch).copied()
+    }
+
+    /// The end of `epoch`, in milliseconds, if the start of the next one is known.
+    pub fn end_ms(&self, epoch: EpochId) -> Option<CheckpointTimestamp> {
+        self.start_ms(epoch.checked_add(1)?)
+    }
+
+    /// The epoch current at `timestamp_ms`, e.g. that of a checkpoint: the last known epoch
+    /// started at or before it.
+    ///
+    /// `None` if `timestamp_ms` is before the first known epoch, or if the epoch it falls in may
+    /// be missing, as the epoch after the one found isn't known while later ones are.
+    pub fn epoch_at(&self, timestamp_ms: CheckpointTimestamp) -> Option<EpochId> {
+        let (epoch, _) = self
+            .starts
+            .iter()
+            .rev()
+            .find(|(_, start)| **start <= timestamp_ms)?;
+        let is_last = self.starts.range(*epoch..).nth(1).is_none();
+        (is_last || self.end_ms(*epoch).is_some()).then_some(*epoch)
+    }
+
+    /// The latest known epoch, with `duration_ms` as the expected epoch duration.
+    pub fn latest(&self, duration_ms: u64) -> Option<EpochInfo> {
+        let (epoch, start) = self.starts.last_key_value()?;
+        Some(EpochInfo::new(*epoch, *start, duration_ms))
+    }
+}
+
+impl TransactionExpiration {
+    /// The estimated time, in milliseconds, from which the transaction can no longer be executed,
+    /// the end of its expiration epoch or past its maximum timestamp, whichever comes first.
+    /// `None` if it never expires.
+    ///
+    /// An expiration epoch before that of `info` is already past, and returns the start of `info`.
+    pub fn deadline_ms(&self, info: &EpochInfo) -> Option<CheckpointTimestamp> {
+        let epoch_end = self.max_epoch().map(|epoch| match epoch.checked_add(1) {
+            Some(next) => info
+                .estimated_start_ms(next)
+                .unwrap_or(info.start_timestamp_ms),
+            None => CheckpointTimestamp::MAX,
+        });
+        let timestamp_end = match self {
+            Self::ValidDuring {
+                max_timestamp_seconds: Some(seconds),
+                ..
+            } => Some(seconds.saturating_add(1).saturating_mul(1_000)),
+            _ => None,
+        };
+        epoch_end.into_iter().chain(timestamp_end).min()
+    }
+
+    /// Whether a pending transaction with this expiration can no longer be executed at `now_ms`,
+    /// given the current epoch `info`.
+    ///
+    /// This is an estimate while the expiration epoch is current, as its end can be delayed past
+    /// `info.duration_ms`, but is exact once a later epoch has started.
+    pub fn is_expired(&self, now_ms: CheckpointTimestamp, info: &EpochInfo) -> bool {
+        self.max_epoch().is_some_and(|epoch| epoch < info.epoch)
+            || self.deadline_ms(info).is_some_and(|end| now_ms >= end)
+    }
+}
+
+impl CheckpointSummary {
+    /// The wall-clock time of the checkpoint.
+    pub fn timestamp(&self) -> SystemTime {
+        timestamp_to_system_time(self.timestamp_ms)
+    }
+}
+
+/// The wall-clock time of a Unix timestamp in milliseconds, like those of checkpoints.
+pub fn timestamp_to_system_time(timestamp_ms: CheckpointTimestamp) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_millis(timestamp_ms)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::ChainIdentifier;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn estimates() {
+        let info = EpochInfo::new(EpochId::new(10), 1_000, DAY_MS);
+        assert_eq!(info.estimated_end_ms(), 1_000 + DAY_MS);
+        assert_eq!(info.estimated_start_ms(EpochId::new(10)), Some(1_000));
+        assert_eq!(
+            info.estimated_start_ms(EpochId::new(12)),
+            Some(1_000 + 2 * DAY_MS)
+        );
+        assert_eq!(info.estimated_start_ms(EpochId::new(9)), None);
+
+        assert_eq!(info.estimated_epoch_at(999), None);
+        assert_eq!(info.estimated_epoch_at(1_000), Some(EpochId::new(10)));
+        assert_eq!(info.estimated_epoch_at(DAY_MS), Some(EpochId::new(10)));
+        assert_eq!(
+            info.estimated_epoch_at(1_000 + DAY_MS),
+            Some(EpochId::new(11))
+        );
+
+        assert_eq!(
+            timestamp_to_system_time(1_000)
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap(),
+            Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn expiration() {
+        let info = EpochInfo::new(EpochId::new(10), 1_000, DAY_MS);
+
+        let none = TransactionExpiration::None;
+        assert_eq!(none.deadline_ms(&info), None);
+        assert!(!none.is_expired(u64::MAX, &info));
+
+        let past = TransactionExpiration::Epoch(EpochId::new(9));
+        assert_eq!(past.deadline_ms(&info), Some(1_000));
+        assert!(past.is_expired(0, &info));
+
+        let current = TransactionExpiration::Epoch(EpochId::new(10));
+        assert_eq!(current.deadline_ms(&info), Some(1_000 + DAY_MS));
+        assert!(!current.is_expired(DAY_MS, &info));
+        assert!(current.is_expired(1_000 + DAY_MS, &info));
+
+        let future = TransactionExpiration::Epoch(EpochId::new(12));
+        assert_eq!(future.deadline_ms(&info), Some(1_000 + 3 * DAY_MS));
+        assert!(!future.is_expired(1_000 + 2 * DAY_MS, &info));
+        assert!(future.is_expired(1_000 + 3 * DAY_MS, &info));
+
+        let max = TransactionExpiration::Epoch(EpochId::new(u64::MAX));
+        assert_eq!(max.deadline_ms(&info), Some(u64::MAX));
+        assert!(!max.is_expired(1_000 + 3 * DAY_MS, &info));
+
+        let valid_during = |max_epoch, max_timestamp_seconds| TransactionExpiration::ValidDuring {
+            min_epoch: None,
+            max_epoch,
+            min_timestamp_seconds: None,
+            max_timestamp_seconds,
+            chain: ChainIdentifier::MAINNET,
+            nonce: 0,
+        };
+        let unbounded = valid_during(None, None);
+        assert_eq!(unbounded.deadline_ms(&info), None);
+        assert!(!unbounded.is_expired(u64::MAX, &info));
+
+        let by_epoch = valid_during(Some(EpochId::new(10)), Some(1_000_000));
+        assert_eq!(by_epoch.deadline_ms(&info), Some(1_000 + DAY_MS));
+        assert!(by_epoch.is_expired(1_000 + DAY_MS, &info));
+
+        let by_timestamp = valid_during(Some(EpochId::new(12)), Some(10));
+        assert_eq!(by_timestamp.deadline_ms(&info), Some(11_000));
+        assert!(!by_timestamp.is_expired(10_999, &info));
+        assert!(by_timestamp.is_expired(11_000, &info));
+
+        let past = valid_during(Some(EpochId::new(9)), None);
+        assert!(past.is_expired(0, &info));
+    }
+
+    #[test]
+    fn timeline() {
+        let mut timeline = EpochTimeline::new();
+        assert_eq!(timeline.epoch_at(0), None);
+        assert_eq!(timeline.latest(DAY_MS), None);
+
+        timeline.insert(EpochId::new(1), 100);
+        timeline.insert(EpochId::new(2), 200);
+        timeline.insert(EpochId::new(4), 400);
+
+        assert_eq!(timeline.epoch_at(99), None);
+        assert_eq!(timeline.epoch_at(100), Some(EpochId::new(1)));
+        assert_eq!(timeline.epoch_at(199), Some(EpochId::new(1)));
+        assert_eq!(timeline.epoch_at(250), None);
+        assert_eq!(timeline.epoch_at(1_000), Some(EpochId::new(4)));
+
+        assert_eq!(timeline.end_ms(EpochId::new(1)), Some(200));
+        assert_eq!(timeline.end_ms(EpochId::new(2)), None);
+        assert_eq!(
+            timeline.latest(DAY_MS),
+            Some(EpochInfo::new(EpochId::new(4), 400, DAY_MS))
+        );
+
+        timeline.insert(EpochId::new(3), 300);
+        assert_eq!(timeline.epoch_at(250), Some(EpochId::new(2)));
+    }
+}
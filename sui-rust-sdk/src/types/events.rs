@@ -13,6 +13,20 @@ use super::TypeTag;
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub struct TransactionEvents(Vec<Event>);
 
+impl TransactionEvents {
+    pub fn new(events: Vec<Event>) -> Self {
+        Self(events)
+    }
+
+    pub fn events(&self) -> &[Event] {
+        &self.0
+    }
+
+    pub fn into_events(self) -> Vec<Event> {
+        self.0
+    }
+}
+
 /// Specific type of event
 #[derive(PartialEq, Eq, Debug, Clone)]
 #[cfg_attr(
@@ -0,0 +1,127 @@
+//! The BCS format of the SDK's types, recorded with `serde-reflection` into a YAML registry.
+//!
+//! The registry is checked in at `src/types/fixtures/format.yaml`, providing a machine-readable
+//! spec of the wire format to other SDKs, and the `format` test fails if it drifts from the
+//! code. If a format change is intended, regenerate the registry with:
+//!
+//! ```text
+//! make update-format
+//! ```
+//!
+//! and bump [`crate::schema::SCHEMA_VERSION`], recording the change in the schema changelog and
+//! updating the schema fingerprint to the one reported by the `fingerprint` test.
+
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::Strategy;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+use serde_reflection::ContainerFormat;
+use serde_reflection::Registry;
+use serde_reflection::Samples;
+use serde_reflection::Tracer;
+use serde_reflection::TracerConfig;
+
+use crate::types::*;
+
+const FORMAT_FILE: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/src/types/fixtures/format.yaml"
+);
+
+/// Trace the serialization of `count` arbitrary values of `T`.
+///
+/// Tracing serialization rather than deserialization copes with the many types whose
+/// deserializers validate their input, e.g. signatures or identifiers. Values are generated
+/// deterministically so that every run records the same formats.
+fn trace<T>(tracer: &mut Tracer, samples: &mut Samples, count: usize)
+where
+    T: Arbitrary + serde::Serialize,
+{
+    let mut runner = TestRunner::deterministic();
+    let strategy = T::arbitrary();
+    for _ in 0..count {
+        let value = strategy.new_tree(&mut runner).unwrap().current();
+        tracer
+            .trace_value(samples, &value)
+            .unwrap_or_else(|e| panic!("failed to trace {}: {e}", std::any::type_name::<T>()));
+    }
+}
+
+fn registry() -> Registry {
+    let mut tracer = Tracer::new(TracerConfig::default());
+    let mut samples = Samples::new();
+
+    // The top level types, which cover the format of every other type
+    trace::<Transaction>(&mut tracer, &mut samples, 64);
+    trace::<SignedTransaction>(&mut tracer, &mut samples, 64);
+    trace::<TransactionEffects>(&mut tracer, &mut samples, 64);
+    trace::<TransactionEvents>(&mut tracer, &mut samples, 64);
+    trace::<SignedCheckpointSummary>(&mut tracer, &mut samples, 64);
+    trace::<CheckpointContents>(&mut tracer, &mut samples, 64);
+    trace::<CheckpointData>(&mut tracer, &mut samples, 16);
+    trace::<Object>(&mut tracer, &mut samples, 64);
+    trace::<BridgeMessage>(&mut tracer, &mut samples, 64);
+
+    // Enums need enough samples to hit every variant
+    trace::<TransactionKind>(&mut tracer, &mut samples, 256);
+    trace::<EndOfEpochTransactionKind>(&mut tracer, &mut samples, 256);
+    trace::<Command>(&mut tracer, &mut samples, 256);
+    trace::<ExecutionStatus>(&mut tracer, &mut samples, 2048);
+    trace::<EffectsObjectChange>(&mut tracer, &mut samples, 256);
+    trace::<UnchangedSharedKind>(&mut tracer, &mut samples, 256);
+    trace::<UserSignature>(&mut tracer, &mut samples, 256);
+    trace::<MultisigMember>(&mut tracer, &mut samples, 256);
+    trace::<TypeTag>(&mut tracer, &mut samples, 256);
+    trace::<BridgeEvent>(&mut tracer, &mut samples, 256);
+
+    // Arbitrary type tags are never vectors, nor structs with type parameters
+    let coin: TypeTag = "vector<0x2::coin::Coin<0x2::sui::SUI>>".parse().unwrap();
+    tracer.trace_value(&mut samples, &coin).unwrap();
+
+    tracer.registry().unwrap()
+}
+
+/// Check that no enum variant was missed by sampling, which would show as a gap in its indices.
+fn assert_contiguous_variants(registry: &Registry) {
+    for (name, format) in registry {
+        if let ContainerFormat::Enum(variants) = format {
+            assert!(
+                variants.keys().copied().eq(0..variants.len() as u32),
+                "variants of {name} are missing from the registry"
+            );
+        }
+    }
+}
+
+#[test]
+fn format() {
+    let registry = registry();
+    assert_contiguous_variants(&registry);
+
+    let actual = serde_yaml::to_string(&registry).unwrap();
+    if std::env::var_os("UPDATE_FORMAT").is_some() {
+        std::fs::write(FORMAT_FILE, &actual).unwrap();
+        return;
+    }
+
+    let expected = std::fs::read_to_string(FORMAT_FILE).unwrap();
+    assert!(
+        actual == expected,
+        "the BCS format changed, if this is intended regenerate {FORMAT_FILE} with \
+         `make update-format` and bump the schema version"
+    );
+}
+
+#[cfg(feature = "hash")]
+#[test]
+fn fingerprint() {
+    // JSON keeps the order of the registry and has a single compact layout
+    let canonical = serde_json::to_vec(&registry()).unwrap();
+    let actual = crate::hash::Hasher::digest(canonical);
+    assert!(
+        actual == crate::schema::schema_fingerprint(),
+        "the schema fingerprint changed to {actual} {:?}, if this is intended update it and bump \
+         the schema version",
+        actual.inner()
+    );
+}
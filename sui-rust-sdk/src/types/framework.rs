@@ -1,8 +1,18 @@
 //! Rust definitions of move/sui framework types.
 
+use std::sync::Arc;
+
+use super::Address;
+use super::Argument;
+use super::Command;
+use super::Identifier;
+use super::InputArgument;
+use super::MoveCall;
 use super::Object;
 use super::ObjectId;
 use super::TypeTag;
+use super::UnresolvedInputArgument;
+use super::Version;
 
 #[derive(Debug, Clone)]
 pub struct Coin<'a> {
@@ -48,3 +58,253 @@ impl<'a> Coin<'a> {
         }
     }
 }
+
+/// The `0x2::clock::Clock` shared object, holding the timestamp of the current checkpoint.
+///
+/// Time-dependent Move functions take it by immutable reference, so transactions pass it as a
+/// shared input which isn't mutable, which doesn't need to be sequenced by consensus against
+/// other transactions using it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    id: ObjectId,
+    timestamp_ms: u64,
+}
+
+impl Clock {
+    /// The id of the `0x6` clock.
+    pub const OBJECT_ID: ObjectId = super::well_known::CLOCK_OBJECT_ID;
+
+    /// The version the clock was shared at, at genesis, on every network.
+    pub const INITIAL_SHARED_VERSION: Version = Version::new(1);
+
+    /// The input of the clock, by immutable reference.
+    pub fn input() -> InputArgument {
+        InputArgument::Shared {
+            object_id: Self::OBJECT_ID,
+            initial_shared_version: Self::INITIAL_SHARED_VERSION,
+            mutable: false,
+        }
+    }
+
+    /// The input of the clock, by immutable reference, for an unresolved transaction.
+    pub fn unresolved_input() -> UnresolvedInputArgument {
+        UnresolvedInputArgument::Shared {
+            object_id: Self::OBJECT_ID,
+            initial_shared_version: Some(Self::INITIAL_SHARED_VERSION.into_inner()),
+            mutable: Some(false),
+        }
+    }
+
+    pub fn id(&self) -> &ObjectId {
+        &self.id
+    }
+
+    /// The timestamp of the checkpoint of the last transaction which used the clock, in
+    /// milliseconds since the Unix epoch.
+    pub fn timestamp_ms(&self) -> u64 {
+        self.timestamp_ms
+    }
+
+    pub fn timestamp(&self) -> std::time::SystemTime {
+        super::timestamp_to_system_time(self.timestamp_ms)
+    }
+
+    pub fn try_from_object(object: &Object) -> Option<Self> {
+        match &object.data {
+            super::ObjectData::Struct(move_struct) => {
+                let type_ = &move_struct.type_;
+                let is_clock = type_.address == Address::TWO
+                    && type_.module == *"clock"
+                    && type_.name == *"Clock"
+                    && type_.type_params.is_empty();
+
+                let contents = &move_struct.contents;
+                if !is_clock || contents.len() != ObjectId::LENGTH + std::mem::size_of::<u64>() {
+                    return None;
+                }
+
+                let id = ObjectId::new((&contents[..ObjectId::LENGTH]).try_into().unwrap());
+                let timestamp_ms =
+                    u64::from_le_bytes((&contents[ObjectId::LENGTH..]).try_into().unwrap());
+
+                Some(Self { id, timestamp_ms })
+            }
+            _ => None, // package
+        }
+    }
+}
+
+/// Builders for the `0x2::random` module, which gives access to the on-chain randomness held by
+/// the shared `Random` object.
+///
+/// The protocol only allows `TransferObjects` and `MergeCoins` commands to follow a command using
+/// the `Random` object, so randomness is usually consumed by a single call to a function taking
+/// `&Random`, or by generating values to pass to such transfers.
+#[derive(Debug, Clone, Copy)]
+pub struct Random;
+
+impl Random {
+    /// The id of the shared `0x2::random::Random` object, `0x8`.
+    pub const OBJECT_ID: ObjectId = super::well_known::RANDOM_OBJECT_ID;
+
+    /// The input of the `Random` object, which can only be used by immutable reference.
+    ///
+    /// `initial_shared_version` is the version the object was shared at, as recorded by
+    /// `RandomnessStateUpdate` transactions.
+    pub fn input(initial_shared_version: Version) -> InputArgument {
+        InputArgument::Shared {
+            object_id: Self::OBJECT_ID,
+            initial_shared_version,
+            mutable: false,
+        }
+    }
+
+    /// `new_generator(&Random, &mut TxContext): RandomGenerator`
+    pub fn new_generator(random: Argument) -> Command {
+        Self::call("new_generator", vec![], vec![random])
+    }
+
+    /// `generate_bytes(&mut RandomGenerator, u16): vector<u8>`
+    pub fn generate_bytes(generator: Argument, num_of_bytes: Argument) -> Command {
+        Self::call("generate_bytes", vec![], vec![generator, num_of_bytes])
+    }
+
+    /// `generate_u64(&mut RandomGenerator): u64`
+    pub fn generate_u64(generator: Argument) -> Command {
+        Self::call("generate_u64", vec![], vec![generator])
+    }
+
+    /// `generate_u64_in_range(&mut RandomGenerator, u64, u64): u64`, generating a value in the
+    /// inclusive range `[min, max]`.
+    pub fn generate_u64_in_range(generator: Argument, min: Argument, max: Argument) -> Command {
+        Self::call("generate_u64_in_range", vec![], vec![generator, min, max])
+    }
+
+    /// `generate_bool(&mut RandomGenerator): bool`
+    pub fn generate_bool(generator: Argument) -> Command {
+        Self::call("generate_bool", vec![], vec![generator])
+    }
+
+    /// `shuffle<T>(&mut RandomGenerator, &mut vector<T>)`
+    pub fn shuffle(element_type: TypeTag, generator: Argument, vector: Argument) -> Command {
+        Self::call("shuffle", vec![element_type], vec![generator, vector])
+    }
+
+    fn call(function: &str, type_arguments: Vec<TypeTag>, arguments: Vec<Argument>) -> Command {
+        Command::MoveCall(Arc::new(MoveCall {
+            package: ObjectId::from(Address::TWO),
+            module: Identifier::new("random").unwrap(),
+            function: Identifier::new(function).unwrap(),
+            type_arguments,
+            arguments,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::types::EpochId;
+    use crate::types::ProgrammableTransaction;
+    use crate::types::RandomnessStateUpdate;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn clock_input() {
+        assert_eq!(
+            Clock::input(),
+            InputArgument::Shared {
+                object_id: ObjectId::from_static_hex("0x6"),
+                initial_shared_version: Version::new(1),
+                mutable: false,
+            }
+        );
+        assert_eq!(
+            Clock::unresolved_input(),
+            UnresolvedInputArgument::Shared {
+                object_id: Clock::OBJECT_ID,
+                initial_shared_version: Some(1),
+                mutable: Some(false),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn clock_object() {
+        use crate::types::Owner;
+
+        let contents = bcs::to_bytes(&(Clock::OBJECT_ID, 1_700_000_000_123u64)).unwrap();
+        let object = Object::new_move_object(
+            "0x2::clock::Clock".parse().unwrap(),
+            Version::new(42),
+            contents,
+            Owner::Shared {
+                initial_shared_version: Clock::INITIAL_SHARED_VERSION,
+            },
+        );
+
+        let clock = Clock::try_from_object(&object).unwrap();
+        assert_eq!(clock.id(), &Clock::OBJECT_ID);
+        assert_eq!(clock.timestamp_ms(), 1_700_000_000_123);
+        assert_eq!(
+            clock.timestamp(),
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_000_123)
+        );
+        assert!(Coin::try_from_object(&object).is_none());
+    }
+
+    #[test]
+    fn random_commands() {
+        assert_eq!(
+            Random::OBJECT_ID.to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000008"
+        );
+
+        let update = RandomnessStateUpdate {
+            epoch: EpochId::new(1),
+            randomness_round: 42,
+            random_bytes: vec![7; 32],
+            randomness_obj_initial_shared_version: Version::new(14),
+        };
+        assert_eq!(update.round(), 42);
+        assert_eq!(update.random_bytes(), &[7; 32]);
+
+        let ptb = ProgrammableTransaction {
+            inputs: vec![
+                update.random_object(),
+                InputArgument::Pure {
+                    value: 1u64.to_le_bytes().to_vec(),
+                },
+                InputArgument::Pure {
+                    value: 6u64.to_le_bytes().to_vec(),
+                },
+            ],
+            commands: vec![
+                Random::new_generator(Argument::Input(0)),
+                Random::generate_u64_in_range(
+                    Argument::Result(0),
+                    Argument::Input(1),
+                    Argument::Input(2),
+                ),
+            ],
+        };
+        assert_eq!(
+            ptb.inputs[0],
+            InputArgument::Shared {
+                object_id: Random::OBJECT_ID,
+                initial_shared_version: Version::new(14),
+                mutable: false,
+            }
+        );
+        let Command::MoveCall(call) = &ptb.commands[1] else {
+            panic!("expected a move call");
+        };
+        assert_eq!(call.package, ObjectId::from(Address::TWO));
+        assert_eq!(call.module.as_str(), "random");
+        assert_eq!(call.function.as_str(), "generate_u64_in_range");
+        assert_eq!(call.arguments.len(), 3);
+    }
+}
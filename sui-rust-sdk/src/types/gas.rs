@@ -1,3 +1,10 @@
+use super::framework::Coin;
+use super::GasPayment;
+use super::Object;
+use super::ObjectId;
+use super::ObjectType;
+use super::StructTag;
+
 /// Summary of gas charges.
 ///
 /// Storage is charged independently of computation.
@@ -75,8 +82,125 @@ impl GasCostSummary {
     pub fn net_gas_usage(&self) -> i64 {
         self.gas_used() as i64 - self.storage_rebate as i64
     }
+
+    /// [`gas_used`](Self::gas_used), or `None` if it overflows.
+    pub fn checked_gas_used(&self) -> Option<u64> {
+        self.computation_cost.checked_add(self.storage_cost)
+    }
+
+    /// [`net_gas_usage`](Self::net_gas_usage), or `None` if it overflows.
+    pub fn checked_net_gas_usage(&self) -> Option<i64> {
+        let gas_used = i64::try_from(self.checked_gas_used()?).ok()?;
+        gas_used.checked_sub(i64::try_from(self.storage_rebate).ok()?)
+    }
+
+    /// The amount deducted from the gas coin: the gas used minus the storage rebate, or zero if
+    /// the rebate covers the gas used. `None` if the gas used overflows.
+    pub fn net_charge(&self) -> Option<u64> {
+        Some(self.checked_gas_used()?.saturating_sub(self.storage_rebate))
+    }
+
+    /// The amount added to the gas coin when the storage rebate exceeds the gas used, zero
+    /// otherwise. `None` if the gas used overflows.
+    pub fn net_rebate(&self) -> Option<u64> {
+        Some(self.storage_rebate.saturating_sub(self.checked_gas_used()?))
+    }
+
+    /// The storage cost of the objects deleted or mutated, of which the storage rebate is
+    /// refunded and the non-refundable storage fee is kept by the system.
+    pub fn checked_potential_rebate(&self) -> Option<u64> {
+        self.storage_rebate
+            .checked_add(self.non_refundable_storage_fee)
+    }
+
+    /// Add the costs of `other` to these costs, e.g. to sum the costs of the transactions of an
+    /// epoch. `None` if any of the costs overflows.
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self {
+            computation_cost: self.computation_cost.checked_add(other.computation_cost)?,
+            storage_cost: self.storage_cost.checked_add(other.storage_cost)?,
+            storage_rebate: self.storage_rebate.checked_add(other.storage_rebate)?,
+            non_refundable_storage_fee: self
+                .non_refundable_storage_fee
+                .checked_add(other.non_refundable_storage_fee)?,
+        })
+    }
+}
+
+/// The number of MIST, the smallest unit of SUI, in one SUI.
+pub const MIST_PER_SUI: u64 = 1_000_000_000;
+
+const SUI_DECIMALS: usize = 9;
+
+/// Convert an amount of whole SUI to MIST, or `None` if it overflows.
+pub fn sui_to_mist(sui: u64) -> Option<u64> {
+    sui.checked_mul(MIST_PER_SUI)
+}
+
+/// Format an amount of MIST as a decimal amount of SUI, e.g. `1500000000` as `1.5`.
+pub fn format_sui(mist: u64) -> String {
+    let whole = mist / MIST_PER_SUI;
+    let fraction = mist % MIST_PER_SUI;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+
+    let fraction = format!("{fraction:0SUI_DECIMALS$}");
+    format!("{whole}.{}", fraction.trim_end_matches('0'))
+}
+
+/// Parse a decimal amount of SUI, e.g. `1.5`, into MIST.
+pub fn parse_sui(s: &str) -> Result<u64, ParseSuiError> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    if whole.is_empty() && fraction.is_empty()
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(ParseSuiError::Invalid);
+    }
+    if fraction.len() > SUI_DECIMALS {
+        return Err(ParseSuiError::TooPrecise);
+    }
+
+    let whole = if whole.is_empty() {
+        0
+    } else {
+        whole.parse::<u64>().map_err(|_| ParseSuiError::Overflow)?
+    };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        // At most 9 digits, which fit in a u64
+        fraction.parse::<u64>().unwrap() * 10u64.pow((SUI_DECIMALS - fraction.len()) as u32)
+    };
+
+    sui_to_mist(whole)
+        .and_then(|mist| mist.checked_add(fraction))
+        .ok_or(ParseSuiError::Overflow)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseSuiError {
+    /// The amount is not a non-negative decimal number.
+    Invalid,
+    /// The amount has more decimals than a MIST can represent.
+    TooPrecise,
+    /// The amount of MIST does not fit in a u64.
+    Overflow,
+}
+
+impl std::fmt::Display for ParseSuiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid => write!(f, "invalid SUI amount"),
+            Self::TooPrecise => write!(f, "SUI amount has more than {SUI_DECIMALS} decimals"),
+            Self::Overflow => write!(f, "SUI amount overflows a u64 of MIST"),
+        }
+    }
 }
 
+impl std::error::Error for ParseSuiError {}
+
 impl std::fmt::Display for GasCostSummary {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "computation_cost: {}, ", self.computation_cost)?;
@@ -90,6 +214,122 @@ impl std::fmt::Display for GasCostSummary {
     }
 }
 
+/// The gas coin of a transaction after execution, predicted by [`GasPayment::smash`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SmashedGasCoin {
+    /// The id of the gas coin which the other coins of the payment are merged into, i.e. the
+    /// first coin of the payment.
+    pub id: ObjectId,
+    /// The balance of the gas coin once the gas costs were charged.
+    pub balance: u64,
+    /// The ids of the coins deleted by being merged into the gas coin.
+    pub deleted: Vec<ObjectId>,
+}
+
+impl GasPayment {
+    /// Predict the gas coin resulting from executing a transaction paid for by this payment, and
+    /// charged `gas_used`.
+    ///
+    /// Execution "smashes" all coins of the payment into its first coin, deleting the others, and
+    /// then deducts the net gas usage from, or adds the net rebate to, the merged balance.
+    /// `coins` are the gas coin objects at the versions referenced by the payment.
+    ///
+    /// The prediction assumes the transaction itself does not use the gas coin, e.g. to split
+    /// coins off of it.
+    pub fn smash(
+        &self,
+        coins: &[Object],
+        gas_used: &GasCostSummary,
+    ) -> Result<SmashedGasCoin, GasSmashingError> {
+        let (primary, rest) = self
+            .objects
+            .split_first()
+            .ok_or(GasSmashingError::NoGasCoins)?;
+
+        let gas_coin_type = ObjectType::Struct(StructTag::gas_coin());
+        let mut balance: u64 = 0;
+        for (i, reference) in self.objects.iter().enumerate() {
+            let object_id = *reference.object_id();
+            if self.objects[..i]
+                .iter()
+                .any(|previous| previous.object_id() == &object_id)
+            {
+                return Err(GasSmashingError::DuplicateCoin(object_id));
+            }
+
+            let object = coins
+                .iter()
+                .find(|coin| coin.object_id() == object_id && coin.version() == reference.version())
+                .ok_or(GasSmashingError::MissingCoin(object_id))?;
+            if object.object_type() != gas_coin_type {
+                return Err(GasSmashingError::NotGasCoin(object_id));
+            }
+            let coin =
+                Coin::try_from_object(object).ok_or(GasSmashingError::NotGasCoin(object_id))?;
+            balance = balance
+                .checked_add(coin.balance())
+                .ok_or(GasSmashingError::BalanceOverflow)?;
+        }
+
+        let charge = gas_used.computation_cost as i128 + gas_used.storage_cost as i128
+            - gas_used.storage_rebate as i128;
+        let balance = u64::try_from(balance as i128 - charge).map_err(|_| {
+            if charge > 0 {
+                GasSmashingError::InsufficientBalance { balance, charge }
+            } else {
+                GasSmashingError::BalanceOverflow
+            }
+        })?;
+
+        Ok(SmashedGasCoin {
+            id: *primary.object_id(),
+            balance,
+            deleted: rest
+                .iter()
+                .map(|reference| *reference.object_id())
+                .collect(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GasSmashingError {
+    /// The payment does not reference any gas coin.
+    NoGasCoins,
+    /// The payment references the same coin more than once.
+    DuplicateCoin(ObjectId),
+    /// No object was provided for the coin at the version referenced by the payment.
+    MissingCoin(ObjectId),
+    /// The object is not a `0x2::coin::Coin<0x2::sui::SUI>`.
+    NotGasCoin(ObjectId),
+    BalanceOverflow,
+    /// The balance of the gas coins does not cover the net gas charge.
+    InsufficientBalance {
+        balance: u64,
+        charge: i128,
+    },
+}
+
+impl std::fmt::Display for GasSmashingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoGasCoins => write!(f, "gas payment has no gas coins"),
+            Self::DuplicateCoin(id) => write!(f, "gas coin {id} is used more than once"),
+            Self::MissingCoin(id) => write!(f, "missing gas coin {id}"),
+            Self::NotGasCoin(id) => write!(f, "object {id} is not a gas coin"),
+            Self::BalanceOverflow => write!(f, "gas coin balance overflows a u64"),
+            Self::InsufficientBalance { balance, charge } => {
+                write!(
+                    f,
+                    "gas coin balance {balance} is less than the charge {charge}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for GasSmashingError {}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -110,4 +350,145 @@ mod test {
         println!("{}", serde_json::to_string(&actual).unwrap());
         println!("{:?}", bcs::to_bytes(&actual).unwrap());
     }
+
+    #[cfg(feature = "serde")]
+    fn coin(id: u8, version: u64, balance: u64) -> Object {
+        use base64ct::Encoding;
+
+        let id = ObjectId::new([id; 32]);
+        let mut contents = id.into_inner().to_vec();
+        contents.extend(balance.to_le_bytes());
+        serde_json::from_value(serde_json::json!({
+            "object_id": id,
+            "version": version.to_string(),
+            "owner": { "owner": "address", "address": crate::types::Address::ZERO },
+            "type": StructTag::gas_coin().to_string(),
+            "has_public_transfer": true,
+            "contents": base64ct::Base64::encode_string(&contents),
+            "previous_transaction": crate::types::TransactionDigest::ZERO,
+            "storage_rebate": "0",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn smash_gas_coins() {
+        use crate::types::Address;
+        use crate::types::ObjectDigest;
+        use crate::types::ObjectReference;
+
+        let coins = [coin(1, 5, 1000), coin(2, 7, 500), coin(3, 2, 250)];
+        let payment = |objects: &[&Object]| GasPayment {
+            objects: objects
+                .iter()
+                .map(|object| {
+                    ObjectReference::new(object.object_id(), object.version(), ObjectDigest::ZERO)
+                })
+                .collect(),
+            owner: Address::ZERO,
+            price: 1000,
+            budget: 1000,
+        };
+
+        let smashed = payment(&[&coins[1], &coins[0], &coins[2]])
+            .smash(&coins, &GasCostSummary::new(100, 300, 50, 1))
+            .unwrap();
+        assert_eq!(
+            smashed,
+            SmashedGasCoin {
+                id: coins[1].object_id(),
+                balance: 1750 - 350,
+                deleted: vec![coins[0].object_id(), coins[2].object_id()],
+            }
+        );
+
+        // A net rebate is added to the gas coin
+        let smashed = payment(&[&coins[0]])
+            .smash(&coins, &GasCostSummary::new(10, 0, 100, 1))
+            .unwrap();
+        assert_eq!(smashed.balance, 1090);
+        assert!(smashed.deleted.is_empty());
+
+        assert_eq!(
+            payment(&[&coins[0], &coins[1]]).smash(&coins, &GasCostSummary::new(1000, 1000, 0, 0)),
+            Err(GasSmashingError::InsufficientBalance {
+                balance: 1500,
+                charge: 2000
+            })
+        );
+        assert_eq!(
+            payment(&[]).smash(&coins, &GasCostSummary::default()),
+            Err(GasSmashingError::NoGasCoins)
+        );
+        assert_eq!(
+            payment(&[&coins[0], &coins[0]]).smash(&coins, &GasCostSummary::default()),
+            Err(GasSmashingError::DuplicateCoin(coins[0].object_id()))
+        );
+
+        // The coins must be at the versions referenced by the payment
+        let stale = coin(2, 6, 500);
+        assert_eq!(
+            payment(&[&coins[0], &stale]).smash(&coins, &GasCostSummary::default()),
+            Err(GasSmashingError::MissingCoin(stale.object_id()))
+        );
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        let summary = GasCostSummary::new(100, 300, 50, 1);
+        assert_eq!(summary.checked_gas_used(), Some(400));
+        assert_eq!(summary.checked_net_gas_usage(), Some(350));
+        assert_eq!(summary.net_charge(), Some(350));
+        assert_eq!(summary.net_rebate(), Some(0));
+        assert_eq!(summary.checked_potential_rebate(), Some(51));
+
+        let refund = GasCostSummary::new(10, 0, 100, 1);
+        assert_eq!(refund.checked_net_gas_usage(), Some(-90));
+        assert_eq!(refund.net_charge(), Some(0));
+        assert_eq!(refund.net_rebate(), Some(90));
+
+        assert_eq!(
+            summary.checked_add(&refund),
+            Some(GasCostSummary::new(110, 300, 150, 2))
+        );
+
+        let overflowing = GasCostSummary::new(u64::MAX, 1, 0, 0);
+        assert_eq!(overflowing.checked_gas_used(), None);
+        assert_eq!(overflowing.checked_net_gas_usage(), None);
+        assert_eq!(overflowing.net_charge(), None);
+        assert_eq!(summary.checked_add(&overflowing), None);
+        assert_eq!(
+            GasCostSummary::new(u64::MAX, 0, 0, 0).checked_net_gas_usage(),
+            None
+        );
+    }
+
+    #[test]
+    fn sui_amounts() {
+        assert_eq!(sui_to_mist(2), Some(2_000_000_000));
+        assert_eq!(sui_to_mist(u64::MAX), None);
+
+        for (mist, sui) in [
+            (0, "0"),
+            (1, "0.000000001"),
+            (1_500_000_000, "1.5"),
+            (42 * MIST_PER_SUI, "42"),
+            (u64::MAX, "18446744073.709551615"),
+        ] {
+            assert_eq!(format_sui(mist), sui);
+            assert_eq!(parse_sui(sui), Ok(mist));
+        }
+
+        assert_eq!(parse_sui(".5"), Ok(500_000_000));
+        assert_eq!(parse_sui("1."), Ok(MIST_PER_SUI));
+        assert_eq!(parse_sui("0.0000000001"), Err(ParseSuiError::TooPrecise));
+        assert_eq!(
+            parse_sui("18446744073.709551616"),
+            Err(ParseSuiError::Overflow)
+        );
+        for invalid in ["", ".", "-1", "1.2.3", "1e9", " 1"] {
+            assert_eq!(parse_sui(invalid), Err(ParseSuiError::Invalid));
+        }
+    }
 }
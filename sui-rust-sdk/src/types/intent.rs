@@ -0,0 +1,332 @@
+use super::CheckpointSummary;
+use super::Transaction;
+use super::TransactionEffects;
+
+/// What kind of message a signature commits to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum IntentScope {
+    /// A user signature on transaction data.
+    TransactionData = 0,
+    /// An authority signature on transaction effects.
+    TransactionEffects = 1,
+    /// An authority signature on a checkpoint summary.
+    CheckpointSummary = 2,
+    /// A user signature on a personal message.
+    PersonalMessage = 3,
+    /// An authority signature on a user signed transaction.
+    SenderSignedTransaction = 4,
+    /// An authority's proof of possession of its protocol key.
+    ProofOfPossession = 5,
+    /// A narwhal authority signature on a header digest.
+    HeaderDigest = 6,
+    /// Reserved for the bridge, but not currently included in its messages.
+    BridgeEventUnused = 7,
+    /// A consensus authority signature on a block digest.
+    ConsensusBlock = 8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum IntentVersion {
+    V0 = 0,
+}
+
+/// The application a signature is for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+#[repr(u8)]
+pub enum IntentAppId {
+    Sui = 0,
+    Narwhal = 1,
+    Consensus = 2,
+}
+
+/// The domain separator of a signed message, which prefixes the message as three bytes:
+/// `scope || version || app_id`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct Intent {
+    pub scope: IntentScope,
+    pub version: IntentVersion,
+    pub app_id: IntentAppId,
+}
+
+impl Intent {
+    /// The intent of user signatures on transactions.
+    pub const TRANSACTION_DATA: Self = Self::sui_app(IntentScope::TransactionData);
+    /// The intent of authority signatures on effects.
+    pub const TRANSACTION_EFFECTS: Self = Self::sui_app(IntentScope::TransactionEffects);
+    /// The intent of authority signatures on checkpoint summaries.
+    pub const CHECKPOINT_SUMMARY: Self = Self::sui_app(IntentScope::CheckpointSummary);
+    /// The intent of user signatures on personal messages.
+    pub const PERSONAL_MESSAGE: Self = Self::sui_app(IntentScope::PersonalMessage);
+    /// The intent of authority signatures on user signed transactions.
+    pub const SENDER_SIGNED_TRANSACTION: Self = Self::sui_app(IntentScope::SenderSignedTransaction);
+
+    /// The `V0` intent of `scope` for the `Sui` app.
+    pub const fn sui_app(scope: IntentScope) -> Self {
+        Self {
+            scope,
+            version: IntentVersion::V0,
+            app_id: IntentAppId::Sui,
+        }
+    }
+
+    pub const fn to_bytes(self) -> [u8; 3] {
+        [self.scope as u8, self.version as u8, self.app_id as u8]
+    }
+
+    pub fn from_bytes(bytes: [u8; 3]) -> Result<Self, IntentError> {
+        let [scope, version, app_id] = bytes;
+        let scope = match scope {
+            0 => IntentScope::TransactionData,
+            1 => IntentScope::TransactionEffects,
+            2 => IntentScope::CheckpointSummary,
+            3 => IntentScope::PersonalMessage,
+            4 => IntentScope::SenderSignedTransaction,
+            5 => IntentScope::ProofOfPossession,
+            6 => IntentScope::HeaderDigest,
+            7 => IntentScope::BridgeEventUnused,
+            8 => IntentScope::ConsensusBlock,
+            scope => return Err(IntentError::InvalidScope(scope)),
+        };
+        let version = match version {
+            0 => IntentVersion::V0,
+            version => return Err(IntentError::InvalidVersion(version)),
+        };
+        let app_id = match app_id {
+            0 => IntentAppId::Sui,
+            1 => IntentAppId::Narwhal,
+            2 => IntentAppId::Consensus,
+            app_id => return Err(IntentError::InvalidAppId(app_id)),
+        };
+        Ok(Self {
+            scope,
+            version,
+            app_id,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntentError {
+    InvalidScope(u8),
+    InvalidVersion(u8),
+    InvalidAppId(u8),
+    /// The message was signed with an intent other than the one expected for its value.
+    UnexpectedIntent {
+        expected: Intent,
+        actual: Intent,
+    },
+}
+
+impl std::fmt::Display for IntentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidScope(scope) => write!(f, "invalid intent scope {scope}"),
+            Self::InvalidVersion(version) => write!(f, "invalid intent version {version}"),
+            Self::InvalidAppId(app_id) => write!(f, "invalid intent app id {app_id}"),
+            Self::UnexpectedIntent { expected, actual } => write!(
+                f,
+                "expected intent {:?}, found {:?}",
+                expected.to_bytes(),
+                actual.to_bytes()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for IntentError {}
+
+/// A value together with the intent it is signed with.
+///
+/// Its BCS encoding, the three bytes of the intent followed by the BCS of the value, is what users
+/// sign, hashed, and what authorities sign, followed by the epoch of their signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntentMessage<T> {
+    pub intent: Intent,
+    pub value: T,
+}
+
+impl<T> IntentMessage<T> {
+    pub fn new(intent: Intent, value: T) -> Self {
+        Self { intent, value }
+    }
+
+    /// The value, if it is signed with the `expected` intent.
+    pub fn into_value_with_intent(self, expected: Intent) -> Result<T, IntentError> {
+        if self.intent != expected {
+            return Err(IntentError::UnexpectedIntent {
+                expected,
+                actual: self.intent,
+            });
+        }
+        Ok(self.value)
+    }
+}
+
+impl IntentMessage<Transaction> {
+    pub fn transaction(transaction: Transaction) -> Self {
+        Self::new(Intent::TRANSACTION_DATA, transaction)
+    }
+}
+
+impl IntentMessage<TransactionEffects> {
+    pub fn transaction_effects(effects: TransactionEffects) -> Self {
+        Self::new(Intent::TRANSACTION_EFFECTS, effects)
+    }
+}
+
+impl IntentMessage<CheckpointSummary> {
+    pub fn checkpoint_summary(summary: CheckpointSummary) -> Self {
+        Self::new(Intent::CHECKPOINT_SUMMARY, summary)
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+impl<T: serde::Serialize> IntentMessage<T> {
+    /// The BCS encoding of the message.
+    pub fn to_bcs_bytes(&self) -> Vec<u8> {
+        bcs::to_bytes(self).expect("bcs serialization cannot fail")
+    }
+
+    /// The message an authority signs in `epoch`: the BCS encoding of the message followed by the
+    /// little-endian epoch.
+    pub fn to_authority_message(&self, epoch: super::EpochId) -> Vec<u8> {
+        let mut message = self.to_bcs_bytes();
+        message.extend(epoch.into_inner().to_le_bytes());
+        message
+    }
+}
+
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+mod serialization {
+    use serde::Deserialize;
+    use serde::Deserializer;
+    use serde::Serialize;
+    use serde::Serializer;
+
+    use super::*;
+
+    impl Serialize for Intent {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.to_bytes().serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Intent {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let bytes = <[u8; 3]>::deserialize(deserializer)?;
+            Self::from_bytes(bytes).map_err(serde::de::Error::custom)
+        }
+    }
+
+    impl<T: Serialize> Serialize for IntentMessage<T> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use serde::ser::SerializeTuple;
+
+            let [scope, version, app_id] = self.intent.to_bytes();
+            let mut s = serializer.serialize_tuple(4)?;
+            s.serialize_element(&scope)?;
+            s.serialize_element(&version)?;
+            s.serialize_element(&app_id)?;
+            s.serialize_element(&self.value)?;
+            s.end()
+        }
+    }
+
+    impl<'de, T: Deserialize<'de>> Deserialize<'de> for IntentMessage<T> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let (scope, version, app_id, value): (u8, u8, u8, T) =
+                Deserialize::deserialize(deserializer)?;
+            let intent =
+                Intent::from_bytes([scope, version, app_id]).map_err(serde::de::Error::custom)?;
+            Ok(Self { intent, value })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use test_strategy::proptest;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[proptest]
+    fn roundtrip_intent_bytes(intent: Intent) {
+        assert_eq!(Intent::from_bytes(intent.to_bytes()), Ok(intent));
+    }
+
+    #[test]
+    fn invalid_intents() {
+        assert_eq!(Intent::CHECKPOINT_SUMMARY.to_bytes(), [2, 0, 0]);
+        assert_eq!(
+            Intent::from_bytes([9, 0, 0]),
+            Err(IntentError::InvalidScope(9))
+        );
+        assert_eq!(
+            Intent::from_bytes([0, 1, 0]),
+            Err(IntentError::InvalidVersion(1))
+        );
+        assert_eq!(
+            Intent::from_bytes([0, 0, 3]),
+            Err(IntentError::InvalidAppId(3))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[proptest]
+    fn checkpoint_summary_message(summary: CheckpointSummary) {
+        let epoch = summary.epoch;
+        let message = IntentMessage::checkpoint_summary(summary.clone());
+
+        let mut expected = vec![2, 0, 0];
+        expected.extend(bcs::to_bytes(&summary).unwrap());
+        assert_eq!(message.to_bcs_bytes(), expected);
+        expected.extend(epoch.into_inner().to_le_bytes());
+        assert_eq!(message.to_authority_message(epoch), expected);
+
+        let decoded: IntentMessage<CheckpointSummary> =
+            bcs::from_bytes(&message.to_bcs_bytes()).unwrap();
+        assert_eq!(
+            decoded
+                .clone()
+                .into_value_with_intent(Intent::CHECKPOINT_SUMMARY),
+            Ok(summary)
+        );
+        assert_eq!(
+            decoded.into_value_with_intent(Intent::TRANSACTION_EFFECTS),
+            Err(IntentError::UnexpectedIntent {
+                expected: Intent::TRANSACTION_EFFECTS,
+                actual: Intent::CHECKPOINT_SUMMARY,
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn invalid_intent_message() {
+        let error = bcs::from_bytes::<IntentMessage<u8>>(&[9, 0, 0, 1]).unwrap_err();
+        assert_eq!(error.to_string(), "invalid intent scope 9");
+    }
+}
@@ -0,0 +1,450 @@
+//! Layouts of Move values, describing how the BCS of a value of a type is laid out with the names
+//! of its fields, so that values of types unknown to the SDK can be decoded.
+
+use super::Identifier;
+use super::StructTag;
+use super::TypeTag;
+
+/// The layout of a value of a Move type.
+///
+/// Its readable serialization is the one of the `layout` of a `MoveType` in the GraphQL API, but
+/// for enums, whose layouts also carry their type and the tag of each variant.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum MoveTypeLayout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Vector(Box<MoveTypeLayout>),
+    Struct(Box<MoveStructLayout>),
+    Enum(Box<MoveEnumLayout>),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct MoveStructLayout {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: StructTag,
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct MoveFieldLayout {
+    pub name: Identifier,
+    pub layout: MoveTypeLayout,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct MoveEnumLayout {
+    #[cfg_attr(feature = "serde", serde(rename = "type"))]
+    pub type_: StructTag,
+    pub variants: Vec<MoveVariantLayout>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct MoveVariantLayout {
+    pub name: Identifier,
+    /// The index of the variant in its enum, which prefixes the BCS of its values.
+    pub tag: u16,
+    #[cfg_attr(feature = "serde", serde(rename = "layout"))]
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+impl MoveTypeLayout {
+    /// The type laid out.
+    pub fn type_tag(&self) -> TypeTag {
+        match self {
+            Self::Bool => TypeTag::Bool,
+            Self::U8 => TypeTag::U8,
+            Self::U16 => TypeTag::U16,
+            Self::U32 => TypeTag::U32,
+            Self::U64 => TypeTag::U64,
+            Self::U128 => TypeTag::U128,
+            Self::U256 => TypeTag::U256,
+            Self::Address => TypeTag::Address,
+            Self::Vector(element) => TypeTag::Vector(Box::new(element.type_tag())),
+            Self::Struct(layout) => TypeTag::Struct(Box::new(layout.type_.clone())),
+            Self::Enum(layout) => TypeTag::Struct(Box::new(layout.type_.clone())),
+        }
+    }
+}
+
+impl MoveEnumLayout {
+    pub fn variant(&self, tag: u16) -> Option<&MoveVariantLayout> {
+        self.variants.iter().find(|variant| variant.tag == tag)
+    }
+}
+
+#[cfg(feature = "json")]
+mod json {
+    use serde_json::Map;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::types::well_known::MOVE_STDLIB_ADDRESS;
+    use crate::types::well_known::SUI_FRAMEWORK_ADDRESS;
+    use crate::types::Address;
+
+    impl MoveTypeLayout {
+        /// Decode the BCS of a value laid out by `self` into JSON, the way the JSON-RPC API
+        /// renders Move values.
+        ///
+        /// Integers of up to 32 bits are numbers, wider ones and addresses are strings. Structs are
+        /// objects of their fields, except for the types the JSON-RPC API renders as their
+        /// contents: strings, URLs, IDs, balances and options.
+        pub fn to_json(&self, bcs: &[u8]) -> Result<Value, LayoutDecodeError> {
+            let mut reader = Reader(bcs);
+            let value = reader.value(self)?;
+            if !reader.0.is_empty() {
+                return Err(LayoutDecodeError::TrailingBytes(reader.0.len()));
+            }
+            Ok(value)
+        }
+    }
+
+    impl MoveStructLayout {
+        /// Decode the BCS of a struct into JSON, see [`MoveTypeLayout::to_json`].
+        pub fn to_json(&self, bcs: &[u8]) -> Result<Value, LayoutDecodeError> {
+            let mut reader = Reader(bcs);
+            let value = reader.struct_(self)?;
+            if !reader.0.is_empty() {
+                return Err(LayoutDecodeError::TrailingBytes(reader.0.len()));
+            }
+            Ok(value)
+        }
+    }
+
+    struct Reader<'a>(&'a [u8]);
+
+    impl<'a> Reader<'a> {
+        fn take(&mut self, len: usize) -> Result<&'a [u8], LayoutDecodeError> {
+            if self.0.len() < len {
+                return Err(LayoutDecodeError::UnexpectedEnd);
+            }
+            let (bytes, rest) = self.0.split_at(len);
+            self.0 = rest;
+            Ok(bytes)
+        }
+
+        fn array<const N: usize>(&mut self) -> Result<[u8; N], LayoutDecodeError> {
+            Ok(self.take(N)?.try_into().unwrap())
+        }
+
+        fn uleb128(&mut self) -> Result<u64, LayoutDecodeError> {
+            let mut value = 0u64;
+            for shift in (0..64).step_by(7) {
+                let [byte] = self.array()?;
+                value |= u64::from(byte & 0x7f) << shift;
+                if byte & 0x80 == 0 {
+                    return Ok(value);
+                }
+            }
+            Err(LayoutDecodeError::InvalidLength)
+        }
+
+        fn value(&mut self, layout: &MoveTypeLayout) -> Result<Value, LayoutDecodeError> {
+            Ok(match layout {
+                MoveTypeLayout::Bool => match self.array()? {
+                    [0] => Value::Bool(false),
+                    [1] => Value::Bool(true),
+                    [byte] => return Err(LayoutDecodeError::InvalidBool(byte)),
+                },
+                MoveTypeLayout::U8 => Value::from(u8::from_le_bytes(self.array()?)),
+                MoveTypeLayout::U16 => Value::from(u16::from_le_bytes(self.array()?)),
+                MoveTypeLayout::U32 => Value::from(u32::from_le_bytes(self.array()?)),
+                MoveTypeLayout::U64 => Value::String(u64::from_le_bytes(self.array()?).to_string()),
+                MoveTypeLayout::U128 => {
+                    Value::String(u128::from_le_bytes(self.array()?).to_string())
+                }
+                MoveTypeLayout::U256 => {
+                    Value::String(crate::types::u256::U256::from_digits(self.array()?).to_string())
+                }
+                MoveTypeLayout::Address => Value::String(Address::new(self.array()?).to_string()),
+                MoveTypeLayout::Vector(element) => {
+                    let len = self.uleb128()?;
+                    // Every element takes at least a byte, which bounds the allocation
+                    if len > self.0.len() as u64 {
+                        return Err(LayoutDecodeError::UnexpectedEnd);
+                    }
+                    (0..len)
+                        .map(|_| self.value(element))
+                        .collect::<Result<_, _>>()?
+                }
+                MoveTypeLayout::Struct(layout) => self.struct_(layout)?,
+                MoveTypeLayout::Enum(layout) => {
+                    let tag = self.uleb128()?;
+                    let variant = u16::try_from(tag)
+                        .ok()
+                        .and_then(|tag| layout.variant(tag))
+                        .ok_or(LayoutDecodeError::UnknownVariant(tag))?;
+                    serde_json::json!({
+                        "type": layout.type_.to_string(),
+                        "variant": variant.name.as_str(),
+                        "fields": self.fields(&variant.fields)?,
+                    })
+                }
+            })
+        }
+
+        fn fields(
+            &mut self,
+            fields: &[MoveFieldLayout],
+        ) -> Result<Map<String, Value>, LayoutDecodeError> {
+            fields
+                .iter()
+                .map(|field| Ok((field.name.as_str().to_owned(), self.value(&field.layout)?)))
+                .collect()
+        }
+
+        fn struct_(&mut self, layout: &MoveStructLayout) -> Result<Value, LayoutDecodeError> {
+            let mut fields = self.fields(&layout.fields)?;
+            let type_ = &layout.type_;
+            let unwrapped = match (type_.address, type_.module.as_str(), type_.name.as_str()) {
+                (MOVE_STDLIB_ADDRESS, "string" | "ascii", "String") => {
+                    let bytes = fields.remove("bytes").unwrap_or_default();
+                    let bytes: Option<Vec<u8>> = bytes.as_array().and_then(|bytes| {
+                        bytes
+                            .iter()
+                            .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                            .collect()
+                    });
+                    let string = bytes.and_then(|bytes| String::from_utf8(bytes).ok());
+                    Some(Value::String(string.ok_or(LayoutDecodeError::InvalidUtf8)?))
+                }
+                (MOVE_STDLIB_ADDRESS, "option", "Option") => match fields.remove("vec") {
+                    Some(Value::Array(values)) => {
+                        Some(values.into_iter().next().unwrap_or_default())
+                    }
+                    _ => None,
+                },
+                (SUI_FRAMEWORK_ADDRESS, "url", "Url") => fields.remove("url"),
+                (SUI_FRAMEWORK_ADDRESS, "object", "ID") => fields.remove("bytes"),
+                (SUI_FRAMEWORK_ADDRESS, "balance", "Balance") => fields.remove("value"),
+                _ => None,
+            };
+            Ok(unwrapped.unwrap_or(Value::Object(fields)))
+        }
+    }
+
+    /// The BCS of a value doesn't match its layout.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum LayoutDecodeError {
+        /// The BCS ends before the value.
+        UnexpectedEnd,
+        /// Bytes are left over after the value.
+        TrailingBytes(usize),
+        InvalidBool(u8),
+        /// The length of a vector, or tag of an enum, is not a valid ULEB128.
+        InvalidLength,
+        UnknownVariant(u64),
+        /// The bytes of a string are not UTF-8.
+        InvalidUtf8,
+    }
+
+    impl std::fmt::Display for LayoutDecodeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            match self {
+                Self::UnexpectedEnd => write!(f, "unexpected end of input"),
+                Self::TrailingBytes(len) => write!(f, "{len} trailing bytes"),
+                Self::InvalidBool(byte) => write!(f, "invalid bool {byte}"),
+                Self::InvalidLength => write!(f, "invalid ULEB128 length"),
+                Self::UnknownVariant(tag) => write!(f, "unknown variant {tag}"),
+                Self::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            }
+        }
+    }
+
+    impl std::error::Error for LayoutDecodeError {}
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use json::LayoutDecodeError;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn field(name: &str, layout: MoveTypeLayout) -> MoveFieldLayout {
+        MoveFieldLayout {
+            name: Identifier::new(name).unwrap(),
+            layout,
+        }
+    }
+
+    fn struct_(type_: &str, fields: Vec<MoveFieldLayout>) -> MoveTypeLayout {
+        MoveTypeLayout::Struct(Box::new(MoveStructLayout {
+            type_: type_.parse().unwrap(),
+            fields,
+        }))
+    }
+
+    /// The layout of a `0x2::coin::Coin<0x2::sui::SUI>`.
+    fn coin() -> MoveTypeLayout {
+        let id = struct_(
+            "0x2::object::UID",
+            vec![field(
+                "id",
+                struct_(
+                    "0x2::object::ID",
+                    vec![field("bytes", MoveTypeLayout::Address)],
+                ),
+            )],
+        );
+        let balance = struct_(
+            "0x2::balance::Balance<0x2::sui::SUI>",
+            vec![field("value", MoveTypeLayout::U64)],
+        );
+        struct_(
+            "0x2::coin::Coin<0x2::sui::SUI>",
+            vec![field("id", id), field("balance", balance)],
+        )
+    }
+
+    #[test]
+    fn type_tag() {
+        assert_eq!(
+            coin().type_tag(),
+            "0x2::coin::Coin<0x2::sui::SUI>".parse().unwrap()
+        );
+        assert_eq!(
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)).type_tag(),
+            TypeTag::Vector(Box::new(TypeTag::U8))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json() {
+        let layout = coin();
+        let json = serde_json::to_value(&layout).unwrap();
+        assert_eq!(json["struct"]["type"], layout.type_tag().to_string());
+        assert_eq!(
+            json["struct"]["fields"][1]["layout"]["struct"]["fields"][0]["layout"],
+            "u64"
+        );
+        assert_eq!(
+            serde_json::from_value::<MoveTypeLayout>(json).unwrap(),
+            layout
+        );
+
+        let mut bcs = vec![0xab; 32];
+        bcs.extend(100u64.to_le_bytes());
+        assert_eq!(
+            layout.to_json(&bcs).unwrap(),
+            serde_json::json!({
+                "id": { "id": crate::types::Address::new([0xab; 32]).to_string() },
+                "balance": "100",
+            })
+        );
+        assert_eq!(
+            layout.to_json(&bcs[..39]),
+            Err(LayoutDecodeError::UnexpectedEnd)
+        );
+        bcs.push(0);
+        assert_eq!(
+            layout.to_json(&bcs),
+            Err(LayoutDecodeError::TrailingBytes(1))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_wrappers() {
+        let name = struct_(
+            "0x1::string::String",
+            vec![field(
+                "bytes",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            )],
+        );
+        let option = struct_(
+            "0x1::option::Option<u32>",
+            vec![field(
+                "vec",
+                MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U32)),
+            )],
+        );
+        let status = MoveTypeLayout::Enum(Box::new(MoveEnumLayout {
+            type_: "0x42::example::Status".parse().unwrap(),
+            variants: vec![
+                MoveVariantLayout {
+                    name: Identifier::new("Active").unwrap(),
+                    tag: 0,
+                    fields: vec![],
+                },
+                MoveVariantLayout {
+                    name: Identifier::new("Closed").unwrap(),
+                    tag: 1,
+                    fields: vec![field("at", MoveTypeLayout::U64)],
+                },
+            ],
+        }));
+        let layout = struct_(
+            "0x42::example::Record",
+            vec![
+                field("name", name),
+                field("limit", option.clone()),
+                field("missing", option),
+                field("status", status),
+                field("flag", MoveTypeLayout::Bool),
+            ],
+        );
+
+        let mut bcs = vec![3, b'a', b'b', b'c', 1];
+        bcs.extend(7u32.to_le_bytes());
+        bcs.extend([0, 1]);
+        bcs.extend(5u64.to_le_bytes());
+        bcs.push(1);
+        assert_eq!(
+            layout.to_json(&bcs).unwrap(),
+            serde_json::json!({
+                "name": "abc",
+                "limit": 7,
+                "missing": null,
+                "status": {
+                    "type": "0x0000000000000000000000000000000000000000000000000000000000000042::example::Status",
+                    "variant": "Closed",
+                    "fields": { "at": "5" },
+                },
+                "flag": true,
+            })
+        );
+
+        let last = bcs.len() - 1;
+        bcs[last] = 2;
+        assert_eq!(layout.to_json(&bcs), Err(LayoutDecodeError::InvalidBool(2)));
+        bcs[last - 9] = 2;
+        assert_eq!(
+            layout.to_json(&bcs),
+            Err(LayoutDecodeError::UnknownVariant(2))
+        );
+    }
+}
@@ -1,32 +1,85 @@
 mod address;
+mod bridge;
+mod certificate;
 mod checkpoint;
 mod crypto;
 mod digest;
 mod effects;
+mod epoch;
 mod events;
 mod execution_status;
 pub mod framework;
 mod gas;
+mod intent;
+mod layout;
 mod object;
 mod object_id;
+mod sequence;
 mod transaction;
 mod type_tag;
 mod u256;
+pub mod well_known;
 
 pub use address::Address;
+pub use bridge::BlocklistType;
+pub use bridge::BlocklistValidatorEvent;
+pub use bridge::BridgeAuthoritySignature;
+pub use bridge::BridgeChainId;
+pub use bridge::BridgeCommittee;
+pub use bridge::BridgeCommitteeMember;
+pub use bridge::BridgeEvent;
+pub use bridge::BridgeEventError;
+pub use bridge::BridgeMessage;
+pub use bridge::BridgeMessageError;
+pub use bridge::BridgeMessageKey;
+pub use bridge::BridgeMessagePayload;
+pub use bridge::BridgeMessageType;
+#[cfg(feature = "secp256k1")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "secp256k1")))]
+pub use bridge::BridgeSignatureAggregator;
+pub use bridge::BridgeSignatureError;
+pub use bridge::CertifiedBridgeMessage;
+pub use bridge::CommitteeBlocklistPayload;
+pub use bridge::CommitteeMemberInfo;
+pub use bridge::CommitteeUpdateEntry;
+pub use bridge::CommitteeUpdateEvent;
+pub use bridge::EmergencyOpEvent;
+pub use bridge::EmergencyOpType;
+pub use bridge::EthAddress;
+pub use bridge::EthAddressParseError;
+pub use bridge::LimitUpdatePayload;
+pub use bridge::NewTokenEvent;
+pub use bridge::TokenDepositedEvent;
+pub use bridge::TokenRegistrationEvent;
+pub use bridge::TokenTransferPayload;
+pub use bridge::UpdateRouteLimitEvent;
+pub use bridge::UpdateTokenPriceEvent;
+pub use bridge::BRIDGE_COMMITTEE_MEMBER_ADDRESS_LENGTH;
+pub use bridge::BRIDGE_COMMITTEE_TOTAL_VOTING_POWER;
+pub use bridge::BRIDGE_MESSAGE_PREFIX;
+pub use bridge::BRIDGE_MESSAGE_VERSION;
+pub use bridge::BRIDGE_PACKAGE_ADDRESS;
+pub use certificate::CertifiedTransaction;
+pub use certificate::CertifiedTransactionEffects;
+pub use certificate::SignedTransactionEffects;
 pub use checkpoint::CheckpointCommitment;
 pub use checkpoint::CheckpointContents;
 pub use checkpoint::CheckpointData;
+pub use checkpoint::CheckpointProof;
 pub use checkpoint::CheckpointSequenceNumber;
 pub use checkpoint::CheckpointSummary;
 pub use checkpoint::CheckpointTimestamp;
 pub use checkpoint::CheckpointTransaction;
 pub use checkpoint::CheckpointTransactionInfo;
+pub use checkpoint::CheckpointVerificationError;
 pub use checkpoint::EndOfEpochData;
-pub use checkpoint::EpochId;
+pub use checkpoint::EpochTransition;
+pub use checkpoint::EpochTransitionError;
+pub use checkpoint::ObjectProof;
 pub use checkpoint::ProtocolVersion;
 pub use checkpoint::SignedCheckpointSummary;
 pub use checkpoint::StakeUnit;
+pub use crypto::ActiveJwkSet;
 pub use crypto::Bls12381PrivateKey;
 pub use crypto::Bls12381PublicKey;
 pub use crypto::Bls12381Signature;
@@ -37,8 +90,12 @@ pub use crypto::Claim;
 pub use crypto::Ed25519PrivateKey;
 pub use crypto::Ed25519PublicKey;
 pub use crypto::Ed25519Signature;
+pub use crypto::JsonWebKey;
+pub use crypto::JsonWebKeySet;
 pub use crypto::Jwk;
 pub use crypto::JwkId;
+pub use crypto::JwkParseError;
+pub use crypto::JwkSetDiff;
 pub use crypto::JwtDetails;
 pub use crypto::MultisigAggregatedSignature;
 pub use crypto::MultisigCommittee;
@@ -62,6 +119,8 @@ pub use crypto::ZkLoginAuthenticator;
 pub use crypto::ZkLoginInputs;
 pub use crypto::ZkLoginProof;
 pub use crypto::ZkLoginPublicIdentifier;
+pub use digest::ChainIdentifier;
+pub use digest::ChainMismatchError;
 pub use digest::CheckpointContentsDigest;
 pub use digest::CheckpointDigest;
 pub use digest::ConsensusCommitDigest;
@@ -73,17 +132,22 @@ pub use digest::TransactionDigest;
 pub use digest::TransactionEffectsDigest;
 pub use digest::TransactionEventsDigest;
 pub use effects::ChangedObject;
+pub use effects::DependencyCycleError;
 pub use effects::EffectsObjectChange;
 pub use effects::IdOperation;
 pub use effects::ModifiedAtVersion;
 pub use effects::ObjectIn;
 pub use effects::ObjectOut;
 pub use effects::ObjectReferenceWithOwner;
+pub use effects::TransactionDependencyGraph;
 pub use effects::TransactionEffects;
 pub use effects::TransactionEffectsV1;
 pub use effects::TransactionEffectsV2;
 pub use effects::UnchangedSharedKind;
 pub use effects::UnchangedSharedObject;
+pub use epoch::timestamp_to_system_time;
+pub use epoch::EpochInfo;
+pub use epoch::EpochTimeline;
 pub use events::BalanceChange;
 pub use events::Event;
 pub use events::TransactionEvents;
@@ -93,8 +157,30 @@ pub use execution_status::ExecutionStatus;
 pub use execution_status::MoveLocation;
 pub use execution_status::PackageUpgradeError;
 pub use execution_status::TypeArgumentError;
+pub use gas::format_sui;
+pub use gas::parse_sui;
+pub use gas::sui_to_mist;
 pub use gas::GasCostSummary;
+pub use gas::GasSmashingError;
+pub use gas::ParseSuiError;
+pub use gas::SmashedGasCoin;
+pub use gas::MIST_PER_SUI;
+pub use intent::Intent;
+pub use intent::IntentAppId;
+pub use intent::IntentError;
+pub use intent::IntentMessage;
+pub use intent::IntentScope;
+pub use intent::IntentVersion;
+#[cfg(feature = "json")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
+pub use layout::LayoutDecodeError;
+pub use layout::MoveEnumLayout;
+pub use layout::MoveFieldLayout;
+pub use layout::MoveStructLayout;
+pub use layout::MoveTypeLayout;
+pub use layout::MoveVariantLayout;
 pub use object::GenesisObject;
+pub use object::MovePackage;
 pub use object::Object;
 pub use object::ObjectData;
 pub use object::ObjectReference;
@@ -102,8 +188,9 @@ pub use object::ObjectType;
 pub use object::Owner;
 pub use object::TypeOrigin;
 pub use object::UpgradeInfo;
-pub use object::Version;
 pub use object_id::ObjectId;
+pub use sequence::EpochId;
+pub use sequence::Version;
 pub use transaction::ActiveJwk;
 pub use transaction::Argument;
 pub use transaction::AuthenticatorStateExpire;
@@ -122,27 +209,50 @@ pub use transaction::MoveCall;
 pub use transaction::ProgrammableTransaction;
 pub use transaction::Publish;
 pub use transaction::RandomnessStateUpdate;
+pub use transaction::SenderSignedData;
+pub use transaction::SenderSignedDataError;
+pub use transaction::SenderSignedTransaction;
 pub use transaction::SignedTransaction;
 pub use transaction::SplitCoins;
 pub use transaction::SystemPackage;
 pub use transaction::Transaction;
 pub use transaction::TransactionExpiration;
 pub use transaction::TransactionKind;
+pub use transaction::TransactionKindTag;
+pub use transaction::TransactionSummaryRecord;
 pub use transaction::TransferObjects;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use transaction::UnknownVariantError;
 pub use transaction::UnresolvedGasPayment;
 pub use transaction::UnresolvedInputArgument;
 pub use transaction::UnresolvedObjectReference;
 pub use transaction::UnresolvedProgrammableTransaction;
 pub use transaction::UnresolvedTransaction;
+pub use transaction::UnsupportedExpirationError;
 pub use transaction::Upgrade;
 pub use type_tag::Identifier;
 pub use type_tag::StructTag;
 pub use type_tag::TypeParseError;
 pub use type_tag::TypeTag;
+pub use type_tag::TypeTagPool;
 
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 pub(crate) use transaction::SignedTransactionWithIntentMessage;
 
+#[cfg(all(test, feature = "bls12381"))]
+pub(crate) use crypto::test_committee;
+#[cfg(feature = "bls12381")]
+pub(crate) use crypto::verify_validator_signature;
+#[cfg(feature = "bls12381")]
+pub(crate) use crypto::VALIDATOR_SIGNATURE_DST;
+
 #[cfg(test)]
 mod serialization_proptests;
+
+#[cfg(all(test, feature = "serde", not(target_arch = "wasm32")))]
+mod format;
+
+#[cfg(all(test, feature = "serde", not(target_arch = "wasm32")))]
+mod snapshots;
@@ -6,8 +6,7 @@ use super::ObjectDigest;
 use super::ObjectId;
 use super::StructTag;
 use super::TransactionDigest;
-
-pub type Version = u64;
+use super::Version;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -33,6 +32,28 @@ impl ObjectReference {
         }
     }
 
+    /// Generate a reference to an arbitrary object, with a random id, version and digest.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn generate<R>(mut rng: R) -> Self
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        let object_id = ObjectId::generate(&mut rng);
+        // Versions from `i64::MAX` on are reserved by the protocol
+        let version = Version::new(rng.next_u64() % i64::MAX as u64);
+        let digest = ObjectDigest::generate(&mut rng);
+        Self::new(object_id, version, digest)
+    }
+
+    /// Generate a reference to an arbitrary object using the operating system's random number
+    /// generator.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn random() -> Self {
+        Self::generate(rand_core::OsRng)
+    }
+
     pub fn object_id(&self) -> &ObjectId {
         &self.object_id
     }
@@ -70,6 +91,16 @@ pub enum Owner {
     },
     /// Object is immutable, and hence ownership doesn't matter.
     Immutable,
+    /// Object is exclusively owned by a single address and sequenced via consensus, i.e. a party
+    /// object.
+    ConsensusAddressOwner {
+        /// The version at which the object most recently became consensus object. This serves the
+        /// same function as `initial_shared_version`, except it may change if the object's Owner
+        /// type changes.
+        start_version: Version,
+        /// The owner of the object.
+        owner: Address,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -138,6 +169,29 @@ pub struct MovePackage {
     linkage_table: BTreeMap<ObjectId, UpgradeInfo>,
 }
 
+impl MovePackage {
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+
+    pub fn version(&self) -> Version {
+        self.version
+    }
+
+    /// The bytecode of each module of the package, by module name.
+    pub fn modules(&self) -> &BTreeMap<Identifier, Vec<u8>> {
+        &self.modules
+    }
+
+    pub fn type_origin_table(&self) -> &[TypeOrigin] {
+        &self.type_origin_table
+    }
+
+    pub fn linkage_table(&self) -> &BTreeMap<ObjectId, UpgradeInfo> {
+        &self.linkage_table
+    }
+}
+
 /// Identifies a struct and the module it was defined in
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 #[cfg_attr(
@@ -247,6 +301,32 @@ impl Object {
     pub fn owner(&self) -> &Owner {
         &self.owner
     }
+
+    pub fn data(&self) -> &ObjectData {
+        &self.data
+    }
+
+    /// A Move object of `type_` at `version`, with `contents` and owned by `owner`, for the tests
+    /// of modules decoding objects.
+    #[cfg(all(test, feature = "serde"))]
+    pub(crate) fn new_move_object(
+        type_: StructTag,
+        version: Version,
+        contents: Vec<u8>,
+        owner: Owner,
+    ) -> Self {
+        Self {
+            data: ObjectData::Struct(MoveStruct {
+                type_,
+                has_public_transfer: false,
+                version,
+                contents,
+            }),
+            owner,
+            previous_transaction: TransactionDigest::ZERO,
+            storage_rebate: 0,
+        }
+    }
 }
 
 fn id_opt(contents: &[u8]) -> Option<ObjectId> {
@@ -317,7 +397,7 @@ mod serialization {
                     type_params: Vec::new(),
                 },
                 has_public_transfer: true,
-                version: 12,
+                version: Version::new(12),
                 contents: ObjectId::ZERO.into(),
             }),
             // owner: Owner::Address(Address::ZERO),
@@ -335,7 +415,7 @@ mod serialization {
             "{}",
             serde_json::to_string_pretty(&ObjectReference {
                 object_id: ObjectId::ZERO,
-                version: 1,
+                version: Version::new(1),
                 digest: ObjectDigest::ZERO,
             })
             .unwrap()
@@ -358,6 +438,13 @@ mod serialization {
             initial_shared_version: Version,
         },
         Immutable,
+        ConsensusAddressOwner {
+            #[serde(with = "crate::_serde::ReadableDisplay")]
+            #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+            start_version: Version,
+            // `owner` is taken by the tag
+            address: Address,
+        },
     }
 
     #[cfg(feature = "schemars")]
@@ -375,8 +462,14 @@ mod serialization {
     enum BinaryOwner {
         Address(Address),
         Object(ObjectId),
-        Shared { initial_shared_version: Version },
+        Shared {
+            initial_shared_version: Version,
+        },
         Immutable,
+        ConsensusAddressOwner {
+            start_version: Version,
+            owner: Address,
+        },
     }
 
     impl Serialize for Owner {
@@ -394,6 +487,13 @@ mod serialization {
                         initial_shared_version,
                     },
                     Owner::Immutable => ReadableOwner::Immutable,
+                    Owner::ConsensusAddressOwner {
+                        start_version,
+                        owner,
+                    } => ReadableOwner::ConsensusAddressOwner {
+                        start_version,
+                        address: owner,
+                    },
                 };
                 readable.serialize(serializer)
             } else {
@@ -406,6 +506,13 @@ mod serialization {
                         initial_shared_version,
                     },
                     Owner::Immutable => BinaryOwner::Immutable,
+                    Owner::ConsensusAddressOwner {
+                        start_version,
+                        owner,
+                    } => BinaryOwner::ConsensusAddressOwner {
+                        start_version,
+                        owner,
+                    },
                 };
                 binary.serialize(serializer)
             }
@@ -427,6 +534,13 @@ mod serialization {
                         initial_shared_version,
                     },
                     ReadableOwner::Immutable => Self::Immutable,
+                    ReadableOwner::ConsensusAddressOwner {
+                        start_version,
+                        address,
+                    } => Self::ConsensusAddressOwner {
+                        start_version,
+                        owner: address,
+                    },
                 })
             } else {
                 BinaryOwner::deserialize(deserializer).map(|binary| match binary {
@@ -438,6 +552,13 @@ mod serialization {
                         initial_shared_version,
                     },
                     BinaryOwner::Immutable => Self::Immutable,
+                    BinaryOwner::ConsensusAddressOwner {
+                        start_version,
+                        owner,
+                    } => Self::ConsensusAddressOwner {
+                        start_version,
+                        owner,
+                    },
                 })
             }
         }
@@ -933,6 +1054,9 @@ mod serialization {
     #[cfg(test)]
     mod test {
         use crate::types::object::Object;
+        use crate::types::object::Owner;
+        use crate::types::Address;
+        use crate::types::Version;
 
         #[cfg(target_arch = "wasm32")]
         use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -1048,5 +1172,31 @@ mod serialization {
                 assert_eq!(object, serde_json::from_str(&json).unwrap());
             }
         }
+
+        #[test]
+        fn consensus_address_owner() {
+            let mut bytes = vec![4, 7, 0, 0, 0, 0, 0, 0, 0];
+            bytes.extend([0xaa; 32]);
+
+            let owner: Owner = bcs::from_bytes(&bytes).unwrap();
+            assert_eq!(
+                owner,
+                Owner::ConsensusAddressOwner {
+                    start_version: Version::new(7),
+                    owner: Address::new([0xaa; 32]),
+                }
+            );
+            assert_eq!(bcs::to_bytes(&owner).unwrap(), bytes);
+
+            let json = serde_json::to_value(owner).unwrap();
+            assert_eq!(
+                json,
+                serde_json::json!({
+                    "owner": "consensus_address_owner",
+                    "start_version": "7",
+                    "address": format!("0x{}", "aa".repeat(32)),
+                })
+            );
+        }
     }
 }
@@ -17,6 +17,31 @@ impl ObjectId {
         Self(Address::new(bytes))
     }
 
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn generate<R>(rng: R) -> Self
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        Self(Address::generate(rng))
+    }
+
+    /// Generate a random object id using the operating system's random number generator.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn random() -> Self {
+        Self(Address::random())
+    }
+
+    /// Parse an object id from a hex string in a const context, see [`Address::from_static_hex`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hex` is not a valid object id.
+    pub const fn from_static_hex(hex: &'static str) -> Self {
+        Self(Address::from_static_hex(hex))
+    }
+
     /// Return the underlying byte array of an ObjectId
     pub const fn into_inner(self) -> [u8; Self::LENGTH] {
         self.0.into_inner()
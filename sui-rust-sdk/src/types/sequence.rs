@@ -0,0 +1,117 @@
+//! Sequence numbers of objects and epochs.
+//!
+//! These are distinct types, rather than plain `u64`s, so that an object version can't be passed
+//! where an epoch is expected or vice versa. They serialize exactly like a `u64`.
+
+macro_rules! sequence_number {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde_derive::Serialize, serde_derive::Deserialize),
+            serde(transparent)
+        )]
+        #[cfg_attr(
+            feature = "schemars",
+            derive(schemars::JsonSchema),
+            schemars(transparent)
+        )]
+        #[cfg_attr(test, derive(test_strategy::Arbitrary))]
+        pub struct $name(u64);
+
+        impl $name {
+            pub const fn new(value: u64) -> Self {
+                Self(value)
+            }
+
+            pub const fn into_inner(self) -> u64 {
+                self.0
+            }
+
+            /// Advance by `n`, or `None` if that overflows.
+            pub const fn checked_add(self, n: u64) -> Option<Self> {
+                match self.0.checked_add(n) {
+                    Some(value) => Some(Self(value)),
+                    None => None,
+                }
+            }
+        }
+
+        impl std::ops::Add<u64> for $name {
+            type Output = Self;
+
+            fn add(self, n: u64) -> Self {
+                Self(self.0 + n)
+            }
+        }
+
+        impl From<u64> for $name {
+            fn from(value: u64) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for u64 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = std::num::ParseIntError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                s.parse().map(Self)
+            }
+        }
+    };
+}
+
+sequence_number!(
+    /// The version of an object, incremented each time a transaction writes the object.
+    Version
+);
+
+sequence_number!(
+    /// The number of an epoch, starting at 0 for the genesis epoch.
+    EpochId
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn parse_and_display() {
+        let version: Version = "42".parse().unwrap();
+        assert_eq!(version, Version::new(42));
+        assert_eq!(version.to_string(), "42");
+        assert_eq!(u64::from(version), 42);
+        assert!("-1".parse::<EpochId>().is_err());
+
+        assert_eq!(EpochId::new(1).checked_add(1), Some(EpochId::new(2)));
+        assert_eq!(EpochId::new(u64::MAX).checked_add(1), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serialize_as_u64() {
+        let version = Version::new(7);
+        assert_eq!(
+            bcs::to_bytes(&version).unwrap(),
+            bcs::to_bytes(&7u64).unwrap()
+        );
+        assert_eq!(serde_json::to_string(&version).unwrap(), "7");
+        assert_eq!(serde_json::from_str::<Version>("7").unwrap(), version);
+    }
+}
@@ -67,18 +67,43 @@ where
     let json = serde_json::to_string(instance).unwrap();
     let deser_from_json = serde_json::from_str::<T>(&json).unwrap();
     assert_eq!(instance, &deser_from_json);
+
+    let mut cbor = vec![];
+    ciborium::into_writer(instance, &mut cbor).unwrap();
+    let deser_from_cbor =
+        ciborium::de::from_reader_with_buffer::<T, _>(cbor.as_slice(), &mut vec![0; cbor.len()])
+            .unwrap();
+    assert_eq!(instance, &deser_from_cbor);
+
+    let msgpack = rmp_serde::to_vec(instance).unwrap();
+    let deser_from_msgpack = rmp_serde::from_slice::<T>(&msgpack).unwrap();
+    assert_eq!(instance, &deser_from_msgpack);
 }
 
 serialization_test!(Address);
+serialization_test!(BridgeAuthoritySignature);
+serialization_test!(BridgeCommittee);
+serialization_test!(BridgeCommitteeMember);
+serialization_test!(BridgeEvent);
+serialization_test!(BridgeMessage);
+serialization_test!(BridgeMessageKey);
+serialization_test!(CertifiedBridgeMessage);
+serialization_test!(EthAddress);
+serialization_test!(CertifiedTransaction);
+serialization_test!(CertifiedTransactionEffects);
+serialization_test!(SignedTransactionEffects);
 serialization_test!(CheckpointCommitment);
 serialization_test!(CheckpointContents);
 serialization_test!(CheckpointData);
+serialization_test!(CheckpointProof);
 serialization_test!(CheckpointSequenceNumber);
 serialization_test!(CheckpointSummary);
 serialization_test!(CheckpointTimestamp);
 serialization_test!(CheckpointTransaction);
 serialization_test!(CheckpointTransactionInfo);
 serialization_test!(EndOfEpochData);
+serialization_test!(EpochTransition);
+serialization_test!(ObjectProof);
 serialization_test!(SignedCheckpointSummary);
 serialization_test!(Bls12381PublicKey);
 serialization_test!(Bls12381Signature);
@@ -110,6 +135,7 @@ serialization_test!(ZkLoginProof);
 serialization_test!(ZkLoginPublicIdentifier);
 serialization_test!(CircomG1);
 serialization_test!(CircomG2);
+serialization_test!(ChainIdentifier);
 serialization_test!(CheckpointContentsDigest);
 serialization_test!(CheckpointDigest);
 serialization_test!(ConsensusCommitDigest);
@@ -172,6 +198,8 @@ serialization_test!(SystemPackage);
 serialization_test!(Transaction);
 serialization_test!(TransactionExpiration);
 serialization_test!(TransactionKind);
+serialization_test!(TransactionKindTag);
+serialization_test!(TransactionSummaryRecord);
 serialization_test!(TransferObjects);
 // serialization_test!(UnresolvedGasPayment);
 // serialization_test!(UnresolvedInputArgument);
@@ -0,0 +1,178 @@
+//! Snapshots of the readable JSON of every variant of transaction kinds, commands, effects and
+//! checkpoints.
+//!
+//! The snapshots are checked in under `src/types/fixtures/snapshots`, one file per variant, so a
+//! change to the readable schema shows up as a diff of the snapshots. A missing snapshot fails the
+//! `snapshots` test like a change does, test runs never write to the tree. If a change is
+//! intended, or a variant was added, regenerate the snapshots with:
+//!
+//! ```text
+//! make update-snapshots
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use proptest::arbitrary::Arbitrary;
+use proptest::strategy::Strategy;
+use proptest::strategy::ValueTree;
+use proptest::test_runner::TestRunner;
+
+use crate::types::*;
+
+const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/types/fixtures/snapshots");
+
+/// The number of values of a type sampled to find an instance of each of its variants.
+const MAX_SAMPLES: usize = 4096;
+
+/// The readable JSON of the first value of `T` generated for each variant, keyed by the name of
+/// the variant as given by `variant`.
+///
+/// Values are generated deterministically, so the same value is picked for a variant every run.
+fn sample<T>(variant: impl Fn(&serde_json::Value) -> String) -> BTreeMap<String, String>
+where
+    T: Arbitrary + serde::Serialize,
+{
+    let mut runner = TestRunner::deterministic();
+    let strategy = T::arbitrary();
+    let mut snapshots = BTreeMap::new();
+    for _ in 0..MAX_SAMPLES {
+        let value = strategy.new_tree(&mut runner).unwrap().current();
+        let json = serde_json::to_value(&value).unwrap();
+        snapshots
+            .entry(variant(&json))
+            .or_insert_with(|| serde_json::to_string_pretty(&json).unwrap() + "\n");
+    }
+    snapshots
+}
+
+/// The value of the field tagging the variant of an internally tagged enum.
+fn tag(field: &'static str) -> impl Fn(&serde_json::Value) -> String {
+    move |json: &serde_json::Value| match &json[field] {
+        serde_json::Value::String(tag) => tag.clone(),
+        other => panic!("{field} is not a string tag: {other}"),
+    }
+}
+
+/// Compare the snapshots of the variants of `T` to those under `name`, returning the paths of
+/// the snapshots which differ or are missing.
+///
+/// The snapshots are only written when `UPDATE_SNAPSHOTS` is set.
+fn check<T>(
+    name: &str,
+    variants: &[&str],
+    variant: impl Fn(&serde_json::Value) -> String,
+) -> Vec<String>
+where
+    T: Arbitrary + serde::Serialize,
+{
+    let snapshots = sample::<T>(variant);
+    let mut expected = variants.to_vec();
+    expected.sort();
+    assert_eq!(
+        snapshots.keys().map(String::as_str).collect::<Vec<_>>(),
+        expected,
+        "not every variant of {name} was sampled"
+    );
+
+    let dir = Path::new(SNAPSHOT_DIR).join(name);
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::create_dir_all(&dir).unwrap();
+        for (variant, actual) in snapshots {
+            std::fs::write(dir.join(format!("{variant}.json")), actual).unwrap();
+        }
+        return vec![];
+    }
+
+    let mut changed = vec![];
+    for (variant, actual) in snapshots {
+        let path = dir.join(format!("{variant}.json"));
+        match std::fs::read_to_string(&path) {
+            Ok(expected) if expected == actual => {}
+            Ok(_) => changed.push(path.display().to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                changed.push(format!("{} (missing)", path.display()))
+            }
+            Err(e) => panic!("failed to read {}: {e}", path.display()),
+        }
+    }
+    changed
+}
+
+#[test]
+fn snapshots() {
+    let mut changed = vec![];
+    changed.extend(check::<TransactionKind>(
+        "transaction_kind",
+        &[
+            "programmable_transaction",
+            "change_epoch",
+            "genesis",
+            "consensus_commit_prologue",
+            "authenticator_state_update",
+            "end_of_epoch",
+            "randomness_state_update",
+            "consensus_commit_prologue_v2",
+        ],
+        tag("kind"),
+    ));
+    changed.extend(check::<EndOfEpochTransactionKind>(
+        "end_of_epoch_transaction_kind",
+        &[
+            "change_epoch",
+            "authenticator_state_create",
+            "authenticator_state_expire",
+            "randomness_state_create",
+            "deny_list_state_create",
+            "bridge_state_create",
+            "bridge_committee_init",
+        ],
+        tag("kind"),
+    ));
+    changed.extend(check::<Command>(
+        "command",
+        &[
+            "move_call",
+            "transfer_objects",
+            "split_coins",
+            "merge_coins",
+            "publish",
+            "make_move_vector",
+            "upgrade",
+        ],
+        tag("command"),
+    ));
+    changed.extend(check::<TransactionEffects>(
+        "transaction_effects",
+        &["1", "2"],
+        tag("version"),
+    ));
+    changed.extend(check::<CheckpointCommitment>(
+        "checkpoint_commitment",
+        &["ecmh_live_object_set"],
+        tag("type"),
+    ));
+    changed.extend(check::<CheckpointSummary>(
+        "checkpoint_summary",
+        &["checkpoint", "end_of_epoch"],
+        |json| {
+            if json.get("end_of_epoch_data").is_some() {
+                "end_of_epoch".to_owned()
+            } else {
+                "checkpoint".to_owned()
+            }
+        },
+    ));
+    changed.extend(check::<CheckpointContents>(
+        "checkpoint_contents",
+        &["checkpoint_contents"],
+        |_| "checkpoint_contents".to_owned(),
+    ));
+
+    assert!(
+        changed.is_empty(),
+        "the readable JSON changed or snapshots are missing, if this is intended regenerate the \
+         snapshots with `make update-snapshots`:\n{}",
+        changed.join("\n")
+    );
+}
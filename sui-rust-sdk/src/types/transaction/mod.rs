@@ -1,9 +1,16 @@
+use std::sync::Arc;
+
+use super::framework::Random;
 use super::Address;
+use super::ChainIdentifier;
 use super::CheckpointTimestamp;
 use super::ConsensusCommitDigest;
 use super::EpochId;
 use super::GenesisObject;
 use super::Identifier;
+use super::Intent;
+use super::IntentError;
+use super::IntentMessage;
 use super::Jwk;
 use super::JwkId;
 use super::ObjectId;
@@ -19,6 +26,12 @@ mod serialization;
 #[cfg(feature = "serde")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
 pub(crate) use serialization::SignedTransactionWithIntentMessage;
+#[cfg(feature = "serde")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+pub use serialization::UnknownVariantError;
+
+mod summary;
+pub use summary::TransactionSummaryRecord;
 
 mod unresolved;
 pub use unresolved::UnresolvedGasPayment;
@@ -36,6 +49,81 @@ pub struct Transaction {
     pub expiration: TransactionExpiration,
 }
 
+impl Transaction {
+    /// Generate a transaction fixture, with a random sender transferring a random object to a
+    /// random recipient, paying for gas with a random coin.
+    ///
+    /// The transaction is well formed but references objects which don't exist, so it is only
+    /// useful as a test value.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn generate<R>(mut rng: R) -> Self
+    where
+        R: rand_core::RngCore + rand_core::CryptoRng,
+    {
+        let sender = Address::generate(&mut rng);
+        let recipient = Address::generate(&mut rng);
+        let kind = TransactionKind::ProgrammableTransaction(ProgrammableTransaction {
+            inputs: vec![
+                InputArgument::ImmutableOrOwned(ObjectReference::generate(&mut rng)),
+                InputArgument::Pure {
+                    value: recipient.into(),
+                },
+            ],
+            commands: vec![Command::TransferObjects(TransferObjects {
+                objects: vec![Argument::Input(0)],
+                address: Argument::Input(1),
+            })],
+        });
+
+        Self {
+            kind,
+            sender,
+            gas_payment: GasPayment {
+                objects: vec![ObjectReference::generate(&mut rng)],
+                owner: sender,
+                price: 1_000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    /// Generate a transaction fixture using the operating system's random number generator, see
+    /// [`Transaction::generate`].
+    #[cfg(feature = "rand")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "rand")))]
+    pub fn random() -> Self {
+        Self::generate(rand_core::OsRng)
+    }
+
+    /// The BCS serialization of the transaction, encoded as Base64.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_bcs_base64(&self) -> Result<String, bcs::Error> {
+        crate::encoding::to_bcs_base64(self)
+    }
+
+    /// Deserialize a transaction from its Base64 encoded BCS serialization.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn from_bcs_base64(b64: &str) -> Result<Self, crate::encoding::BcsBase64Error> {
+        crate::encoding::from_bcs_base64(b64)
+    }
+
+    /// The BCS serialization of the transaction, failing if its expiration isn't accepted by
+    /// `protocol_version`.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_bcs_for_protocol_version(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<Vec<u8>, UnsupportedExpirationError> {
+        self.expiration.check_protocol_version(protocol_version)?;
+        Ok(bcs::to_bytes(self).expect("bcs serialization cannot fail"))
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
@@ -45,10 +133,112 @@ pub struct SignedTransaction {
     pub signatures: Vec<UserSignature>,
 }
 
+impl SignedTransaction {
+    /// The BCS serialization of the signed transaction, encoded as Base64.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn to_bcs_base64(&self) -> Result<String, bcs::Error> {
+        crate::encoding::to_bcs_base64(self)
+    }
+
+    /// Deserialize a signed transaction from its Base64 encoded BCS serialization.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn from_bcs_base64(b64: &str) -> Result<Self, crate::encoding::BcsBase64Error> {
+        crate::encoding::from_bcs_base64(b64)
+    }
+}
+
+/// A user signed transaction as exchanged with validators: a sequence of transactions, each
+/// wrapped in its [`IntentMessage`] and followed by its signatures.
+///
+/// The protocol only allows a single transaction, so deserialization rejects any other number of
+/// them. [`SenderSignedData::from_bcs_unchecked`] reads them all regardless, e.g. to inspect a
+/// malformed submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde_derive::Serialize), serde(transparent))]
+pub struct SenderSignedData {
+    pub transactions: Vec<SenderSignedTransaction>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+pub struct SenderSignedTransaction {
+    pub intent_message: IntentMessage<Transaction>,
+    #[cfg_attr(feature = "serde", serde(rename = "tx_signatures"))]
+    pub signatures: Vec<UserSignature>,
+}
+
+impl SenderSignedData {
+    pub fn new(transaction: SignedTransaction) -> Self {
+        let SignedTransaction {
+            transaction,
+            signatures,
+        } = transaction;
+        Self {
+            transactions: vec![SenderSignedTransaction {
+                intent_message: IntentMessage::transaction(transaction),
+                signatures,
+            }],
+        }
+    }
+
+    /// The single transaction of the envelope, if it is signed with the transaction intent.
+    pub fn into_signed_transaction(self) -> Result<SignedTransaction, SenderSignedDataError> {
+        let [entry] = <[SenderSignedTransaction; 1]>::try_from(self.transactions)
+            .map_err(|transactions| SenderSignedDataError::TransactionCount(transactions.len()))?;
+        let transaction = entry
+            .intent_message
+            .into_value_with_intent(Intent::TRANSACTION_DATA)
+            .map_err(SenderSignedDataError::Intent)?;
+        Ok(SignedTransaction {
+            transaction,
+            signatures: entry.signatures,
+        })
+    }
+}
+
+impl From<SignedTransaction> for SenderSignedData {
+    fn from(transaction: SignedTransaction) -> Self {
+        Self::new(transaction)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SenderSignedDataError {
+    /// The envelope doesn't hold exactly one transaction.
+    TransactionCount(usize),
+    Intent(IntentError),
+}
+
+impl std::fmt::Display for SenderSignedDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::TransactionCount(count) => {
+                write!(f, "expected a single signed transaction, found {count}")
+            }
+            Self::Intent(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for SenderSignedDataError {}
+
+/// When a transaction stops being valid.
+///
+/// Protocol upgrades may add ways to expire, e.g. [`TransactionExpiration::ValidDuring`], which
+/// are only accepted from the protocol version enabling them, see
+/// [`TransactionExpiration::check_protocol_version`]. Deserializing one unknown to this crate
+/// fails with an
+/// [`UnknownVariantError`] for BCS, and names the variant for other formats, rather than
+/// misreading the transaction.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[cfg_attr(
     feature = "serde",
-    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    derive(serde_derive::Serialize),
     serde(rename_all = "lowercase")
 )]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -63,8 +253,124 @@ pub enum TransactionExpiration {
         #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
         EpochId,
     ),
+    /// Validators wont sign a transaction outside of the given epochs and timestamps, or for
+    /// another chain. Only accepted from [`TransactionExpiration::VALID_DURING_PROTOCOL_VERSION`].
+    #[cfg_attr(feature = "serde", serde(rename = "valid_during"))]
+    ValidDuring {
+        /// The first epoch the transaction is valid in.
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::_serde::OptionReadableDisplay")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::_schemars::U64>"))]
+        min_epoch: Option<EpochId>,
+        /// The last epoch the transaction is valid in.
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::_serde::OptionReadableDisplay")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::_schemars::U64>"))]
+        max_epoch: Option<EpochId>,
+        /// The earliest time the transaction is valid at, in seconds since the unix epoch.
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::_serde::OptionReadableDisplay")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::_schemars::U64>"))]
+        min_timestamp_seconds: Option<u64>,
+        /// The latest time the transaction is valid at, in seconds since the unix epoch.
+        #[cfg_attr(
+            feature = "serde",
+            serde(with = "crate::_serde::OptionReadableDisplay")
+        )]
+        #[cfg_attr(feature = "schemars", schemars(with = "Option<crate::_schemars::U64>"))]
+        max_timestamp_seconds: Option<u64>,
+        /// The chain the transaction is valid on.
+        chain: ChainIdentifier,
+        /// Distinguishes otherwise identical transactions, which would share a digest.
+        nonce: u32,
+    },
+}
+
+impl TransactionExpiration {
+    /// The first protocol version accepting [`TransactionExpiration::ValidDuring`].
+    pub const VALID_DURING_PROTOCOL_VERSION: ProtocolVersion = 50;
+
+    /// A [`TransactionExpiration::ValidDuring`], if `protocol_version` accepts it.
+    pub fn valid_during(
+        protocol_version: ProtocolVersion,
+        min_epoch: Option<EpochId>,
+        max_epoch: Option<EpochId>,
+        min_timestamp_seconds: Option<u64>,
+        max_timestamp_seconds: Option<u64>,
+        chain: ChainIdentifier,
+        nonce: u32,
+    ) -> Result<Self, UnsupportedExpirationError> {
+        let expiration = Self::ValidDuring {
+            min_epoch,
+            max_epoch,
+            min_timestamp_seconds,
+            max_timestamp_seconds,
+            chain,
+            nonce,
+        };
+        expiration.check_protocol_version(protocol_version)?;
+        Ok(expiration)
+    }
+
+    /// The last epoch the transaction is valid in, if bounded.
+    pub fn max_epoch(&self) -> Option<EpochId> {
+        match self {
+            Self::None => None,
+            Self::Epoch(epoch) => Some(*epoch),
+            Self::ValidDuring { max_epoch, .. } => *max_epoch,
+        }
+    }
+
+    /// The first protocol version accepting this way to expire.
+    pub fn required_protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::None | Self::Epoch(_) => 0,
+            Self::ValidDuring { .. } => Self::VALID_DURING_PROTOCOL_VERSION,
+        }
+    }
+
+    /// Checks that `protocol_version` accepts this way to expire.
+    pub fn check_protocol_version(
+        &self,
+        protocol_version: ProtocolVersion,
+    ) -> Result<(), UnsupportedExpirationError> {
+        let required = self.required_protocol_version();
+        if protocol_version < required {
+            return Err(UnsupportedExpirationError {
+                protocol_version,
+                required,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A [`TransactionExpiration`] isn't accepted by the protocol version a transaction is built for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnsupportedExpirationError {
+    pub protocol_version: ProtocolVersion,
+    /// The first protocol version accepting the expiration.
+    pub required: ProtocolVersion,
 }
 
+impl std::fmt::Display for UnsupportedExpirationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "transaction expiration requires protocol version {}, found {}",
+            self.required, self.protocol_version
+        )
+    }
+}
+
+impl std::error::Error for UnsupportedExpirationError {}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -94,7 +400,7 @@ pub struct RandomnessStateUpdate {
     /// Epoch of the randomness state update transaction
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub epoch: u64,
+    pub epoch: EpochId,
     /// Randomness round of the update
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
@@ -109,11 +415,28 @@ pub struct RandomnessStateUpdate {
     /// The initial version of the randomness object that it was shared at.
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub randomness_obj_initial_shared_version: u64,
+    pub randomness_obj_initial_shared_version: Version,
     // to version this struct, do not add new fields. Instead, add a RandomnessStateUpdateV2 to
     // TransactionKind.
 }
 
+impl RandomnessStateUpdate {
+    /// The randomness round the random bytes were generated in.
+    pub fn round(&self) -> u64 {
+        self.randomness_round
+    }
+
+    pub fn random_bytes(&self) -> &[u8] {
+        &self.random_bytes
+    }
+
+    /// The input of the `Random` object updated by this transaction, to consume randomness in
+    /// programmable transactions.
+    pub fn random_object(&self) -> InputArgument {
+        Random::input(self.randomness_obj_initial_shared_version)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub enum TransactionKind {
@@ -130,7 +453,7 @@ pub enum TransactionKind {
     ///
     /// The ChangeEpoch enumerant is now deprecated (but the ChangeEpoch struct is still used by
     /// EndOfEpochTransaction below).
-    ChangeEpoch(ChangeEpoch),
+    ChangeEpoch(Arc<ChangeEpoch>),
     Genesis(GenesisTransaction),
     ConsensusCommitPrologue(ConsensusCommitPrologue),
     AuthenticatorStateUpdate(AuthenticatorStateUpdate),
@@ -141,10 +464,84 @@ pub enum TransactionKind {
 
     RandomnessStateUpdate(RandomnessStateUpdate),
     // V2 ConsensusCommitPrologue also includes the digest of the current consensus output.
-    ConsensusCommitPrologueV2(ConsensusCommitPrologueV2),
+    ConsensusCommitPrologueV2(Arc<ConsensusCommitPrologueV2>),
     // .. more transaction types go here
 }
 
+impl TransactionKind {
+    /// The epoch change performed by this transaction, either as a deprecated `ChangeEpoch`
+    /// transaction or as part of an `EndOfEpoch` transaction.
+    pub fn change_epoch(&self) -> Option<&ChangeEpoch> {
+        match self {
+            Self::ChangeEpoch(change_epoch) => Some(change_epoch),
+            Self::EndOfEpoch(kinds) => kinds.iter().find_map(|kind| match kind {
+                EndOfEpochTransactionKind::ChangeEpoch(change_epoch) => Some(change_epoch.as_ref()),
+                _ => None,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn tag(&self) -> TransactionKindTag {
+        match self {
+            Self::ProgrammableTransaction(_) => TransactionKindTag::ProgrammableTransaction,
+            Self::ChangeEpoch(_) => TransactionKindTag::ChangeEpoch,
+            Self::Genesis(_) => TransactionKindTag::Genesis,
+            Self::ConsensusCommitPrologue(_) => TransactionKindTag::ConsensusCommitPrologue,
+            Self::AuthenticatorStateUpdate(_) => TransactionKindTag::AuthenticatorStateUpdate,
+            Self::EndOfEpoch(_) => TransactionKindTag::EndOfEpoch,
+            Self::RandomnessStateUpdate(_) => TransactionKindTag::RandomnessStateUpdate,
+            Self::ConsensusCommitPrologueV2(_) => TransactionKindTag::ConsensusCommitPrologueV2,
+        }
+    }
+}
+
+/// The kind of a [`TransactionKind`], without its contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+#[cfg_attr(
+    feature = "schemars",
+    derive(schemars::JsonSchema),
+    schemars(rename_all = "snake_case")
+)]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub enum TransactionKindTag {
+    ProgrammableTransaction,
+    ChangeEpoch,
+    Genesis,
+    ConsensusCommitPrologue,
+    AuthenticatorStateUpdate,
+    EndOfEpoch,
+    RandomnessStateUpdate,
+    ConsensusCommitPrologueV2,
+}
+
+impl TransactionKindTag {
+    /// The name of the kind, as used by the `kind` field of a [`TransactionKind`] in JSON.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ProgrammableTransaction => "programmable_transaction",
+            Self::ChangeEpoch => "change_epoch",
+            Self::Genesis => "genesis",
+            Self::ConsensusCommitPrologue => "consensus_commit_prologue",
+            Self::AuthenticatorStateUpdate => "authenticator_state_update",
+            Self::EndOfEpoch => "end_of_epoch",
+            Self::RandomnessStateUpdate => "randomness_state_update",
+            Self::ConsensusCommitPrologueV2 => "consensus_commit_prologue_v2",
+        }
+    }
+}
+
+impl std::fmt::Display for TransactionKindTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// EndOfEpochTransactionKind
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
@@ -154,17 +551,17 @@ pub enum TransactionKind {
 )]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub enum EndOfEpochTransactionKind {
-    ChangeEpoch(ChangeEpoch),
+    ChangeEpoch(Arc<ChangeEpoch>),
     AuthenticatorStateCreate,
     AuthenticatorStateExpire(AuthenticatorStateExpire),
     RandomnessStateCreate,
     DenyListStateCreate,
     BridgeStateCreate {
-        chain_id: super::CheckpointDigest,
+        chain_id: super::ChainIdentifier,
     },
     BridgeCommitteeInit {
         #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-        bridge_object_version: u64,
+        bridge_object_version: Version,
     },
 }
 
@@ -179,11 +576,11 @@ pub struct AuthenticatorStateExpire {
     /// expire JWKs that have a lower epoch than this
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub min_epoch: u64,
+    pub min_epoch: EpochId,
     /// The initial version of the authenticator object that it was shared at.
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub authenticator_obj_initial_shared_version: u64,
+    pub authenticator_obj_initial_shared_version: Version,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -197,7 +594,7 @@ pub struct AuthenticatorStateUpdate {
     /// Epoch of the authenticator state update transaction
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub epoch: u64,
+    pub epoch: EpochId,
     /// Consensus round of the authenticator state update
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
@@ -207,7 +604,7 @@ pub struct AuthenticatorStateUpdate {
     /// The initial version of the authenticator object that it was shared at.
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub authenticator_obj_initial_shared_version: u64,
+    pub authenticator_obj_initial_shared_version: Version,
     // to version this struct, do not add new fields. Instead, add a AuthenticatorStateUpdateV2 to
     // TransactionKind.
 }
@@ -225,7 +622,7 @@ pub struct ActiveJwk {
     // the most recent epoch in which the jwk was validated
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub epoch: u64,
+    pub epoch: EpochId,
 }
 
 /// Only commit_timestamp_ms is passed to the move call currently.
@@ -241,7 +638,7 @@ pub struct ConsensusCommitPrologue {
     /// Epoch of the commit prologue transaction
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub epoch: u64,
+    pub epoch: EpochId,
     /// Consensus round of the commit
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
@@ -263,7 +660,7 @@ pub struct ConsensusCommitPrologueV2 {
     /// Epoch of the commit prologue transaction
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-    pub epoch: u64,
+    pub epoch: EpochId,
     /// Consensus round of the commit
     #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
     #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
@@ -395,7 +792,7 @@ pub enum InputArgument {
     Shared {
         object_id: ObjectId,
         #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
-        initial_shared_version: u64,
+        initial_shared_version: Version,
         mutable: bool,
     },
     // A Move object that can be received in this transaction.
@@ -412,7 +809,7 @@ pub enum InputArgument {
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub enum Command {
     /// A call to either an entry or a public Move function
-    MoveCall(MoveCall),
+    MoveCall(Arc<MoveCall>),
     /// `(Vec<forall T:key+store. T>, address)`
     /// It sends n-objects to the specified address. These objects must have store
     /// (public transfer) and either the previous owner must be an address or the object must
@@ -426,11 +823,11 @@ pub enum Command {
     MergeCoins(MergeCoins),
     /// Publishes a Move package. It takes the package bytes and a list of the package's transitive
     /// dependencies to link against on-chain.
-    Publish(Publish),
+    Publish(Arc<Publish>),
     /// `forall T: Vec<T> -> vector<T>`
     /// Given n-values of the same type, it constructs a vector. For non objects or an empty vector,
     /// the type tag must be specified.
-    MakeMoveVector(MakeMoveVector),
+    MakeMoveVector(Arc<MakeMoveVector>),
     /// Upgrades a Move package
     /// Takes (in order):
     /// 1. A vector of serialized modules for the package.
@@ -438,9 +835,48 @@ pub enum Command {
     /// 3. The object ID of the package being upgraded.
     /// 4. An argument holding the `UpgradeTicket` that must have been produced from an earlier command in the same
     ///    programmable transaction.
-    Upgrade(Upgrade),
+    Upgrade(Arc<Upgrade>),
+}
+
+/// Conversions of the payloads which enums hold behind an `Arc`, to keep the enums small and their
+/// clones cheap.
+///
+/// A payload already shared behind an `Arc`, e.g. by a cache, is shared by the enum as is, without
+/// a copy.
+macro_rules! impl_from_shared_payload {
+    ($($enum:ident::$variant:ident($payload:ty)),* $(,)?) => {
+        $(
+            impl From<$payload> for $enum {
+                fn from(payload: $payload) -> Self {
+                    Self::$variant(Arc::new(payload))
+                }
+            }
+
+            impl From<Box<$payload>> for $enum {
+                fn from(payload: Box<$payload>) -> Self {
+                    Self::$variant(Arc::from(payload))
+                }
+            }
+
+            impl From<Arc<$payload>> for $enum {
+                fn from(payload: Arc<$payload>) -> Self {
+                    Self::$variant(payload)
+                }
+            }
+        )*
+    };
 }
 
+impl_from_shared_payload!(
+    TransactionKind::ChangeEpoch(ChangeEpoch),
+    TransactionKind::ConsensusCommitPrologueV2(ConsensusCommitPrologueV2),
+    EndOfEpochTransactionKind::ChangeEpoch(ChangeEpoch),
+    Command::MoveCall(MoveCall),
+    Command::Publish(Publish),
+    Command::MakeMoveVector(MakeMoveVector),
+    Command::Upgrade(Upgrade),
+);
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(
     feature = "serde",
@@ -450,8 +886,8 @@ pub enum Command {
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub struct TransferObjects {
     #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
-    objects: Vec<Argument>,
-    address: Argument,
+    pub objects: Vec<Argument>,
+    pub address: Argument,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -462,9 +898,9 @@ pub struct TransferObjects {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub struct SplitCoins {
-    coin: Argument,
+    pub coin: Argument,
     #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
-    amounts: Vec<Argument>,
+    pub amounts: Vec<Argument>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -475,9 +911,9 @@ pub struct SplitCoins {
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub struct MergeCoins {
-    coin: Argument,
+    pub coin: Argument,
     #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
-    coins_to_merge: Vec<Argument>,
+    pub coins_to_merge: Vec<Argument>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -495,8 +931,8 @@ pub struct Publish {
         )
     )]
     #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::_schemars::Base64>"))]
-    modules: Vec<Vec<u8>>,
-    dependencies: Vec<ObjectId>,
+    pub modules: Vec<Vec<u8>>,
+    pub dependencies: Vec<ObjectId>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -508,9 +944,9 @@ pub struct Publish {
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub struct MakeMoveVector {
     #[cfg_attr(feature = "serde", serde(rename = "type"))]
-    type_: Option<TypeTag>,
+    pub type_: Option<TypeTag>,
     #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
-    elements: Vec<Argument>,
+    pub elements: Vec<Argument>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -528,10 +964,10 @@ pub struct Upgrade {
         )
     )]
     #[cfg_attr(feature = "schemars", schemars(with = "Vec<crate::_schemars::Base64>"))]
-    modules: Vec<Vec<u8>>,
-    dependencies: Vec<ObjectId>,
-    package: ObjectId,
-    ticket: Argument,
+    pub modules: Vec<Vec<u8>>,
+    pub dependencies: Vec<ObjectId>,
+    pub package: ObjectId,
+    pub ticket: Argument,
 }
 
 /// An argument to a programmable transaction command
@@ -575,3 +1011,40 @@ pub struct MoveCall {
     #[cfg_attr(test, any(proptest::collection::size_range(0..=2).lift()))]
     pub arguments: Vec<Argument>,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn enum_sizes() {
+        assert!(std::mem::size_of::<Command>() <= 40);
+        assert!(std::mem::size_of::<TransactionKind>() <= 56);
+        assert!(std::mem::size_of::<EndOfEpochTransactionKind>() <= 40);
+    }
+
+    #[test]
+    fn shared_payloads() {
+        let call = Arc::new(MoveCall {
+            package: ObjectId::from(Address::TWO),
+            module: Identifier::new("coin").unwrap(),
+            function: Identifier::new("join").unwrap(),
+            type_arguments: vec![],
+            arguments: vec![Argument::GasCoin, Argument::Input(0)],
+        });
+
+        // The command shares the call, and so do its clones
+        let command = Command::from(Arc::clone(&call));
+        let Command::MoveCall(shared) = command.clone() else {
+            panic!("not a move call: {command:?}");
+        };
+        assert!(Arc::ptr_eq(&shared, &call));
+        assert_eq!(Arc::strong_count(&call), 3);
+
+        assert_eq!(Command::from(Box::new(call.as_ref().clone())), command);
+        assert_eq!(Command::from(call.as_ref().clone()), command);
+    }
+}
@@ -1,3 +1,6 @@
+use std::cell::Cell;
+use std::sync::Arc;
+
 use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
@@ -10,6 +13,51 @@ use crate::types::ObjectReference;
 
 use super::Argument;
 
+/// The variant index of an enum read from BCS is not one known to this crate, as happens when a
+/// newer protocol version adds a transaction kind or command.
+///
+/// Deserialization fails with this as its cause for `TransactionKind`, `Command`,
+/// `EndOfEpochTransactionKind` and `TransactionExpiration`, which
+/// [`from_bcs`](crate::encoding::from_bcs) returns as is, e.g. for an indexer to skip and log
+/// transactions it can't read instead of failing on their whole checkpoint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownVariantError {
+    /// The name of the enum.
+    pub type_name: &'static str,
+    pub index: u32,
+}
+
+thread_local! {
+    /// The unknown variant which last failed a deserialization on this thread.
+    static LAST_UNKNOWN_VARIANT: Cell<Option<UnknownVariantError>> = const { Cell::new(None) };
+}
+
+impl UnknownVariantError {
+    /// The deserialization error of this variant, which is recorded to be taken back by
+    /// [`UnknownVariantError::take_last`] as the formats' errors only keep its message.
+    fn into_error<E: serde::de::Error>(self) -> E {
+        LAST_UNKNOWN_VARIANT.with(|last| last.set(Some(self)));
+        E::custom(self)
+    }
+
+    /// Take the unknown variant which last failed a deserialization on this thread.
+    pub(crate) fn take_last() -> Option<Self> {
+        LAST_UNKNOWN_VARIANT.with(Cell::take)
+    }
+}
+
+impl std::fmt::Display for UnknownVariantError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown variant index {} of {}",
+            self.index, self.type_name
+        )
+    }
+}
+
+impl std::error::Error for UnknownVariantError {}
+
 mod transaction {
     use super::*;
     use crate::types::transaction::GasPayment;
@@ -154,6 +202,127 @@ mod transaction {
     }
 }
 
+mod transaction_expiration {
+    use super::*;
+    use crate::types::transaction::TransactionExpiration;
+    use crate::types::ChainIdentifier;
+    use crate::types::EpochId;
+
+    #[derive(serde_derive::Deserialize)]
+    struct ValidDuring {
+        #[serde(with = "crate::_serde::OptionReadableDisplay")]
+        min_epoch: Option<EpochId>,
+        #[serde(with = "crate::_serde::OptionReadableDisplay")]
+        max_epoch: Option<EpochId>,
+        #[serde(with = "crate::_serde::OptionReadableDisplay")]
+        min_timestamp_seconds: Option<u64>,
+        #[serde(with = "crate::_serde::OptionReadableDisplay")]
+        max_timestamp_seconds: Option<u64>,
+        chain: ChainIdentifier,
+        nonce: u32,
+    }
+
+    impl From<ValidDuring> for TransactionExpiration {
+        fn from(
+            ValidDuring {
+                min_epoch,
+                max_epoch,
+                min_timestamp_seconds,
+                max_timestamp_seconds,
+                chain,
+                nonce,
+            }: ValidDuring,
+        ) -> Self {
+            Self::ValidDuring {
+                min_epoch,
+                max_epoch,
+                min_timestamp_seconds,
+                max_timestamp_seconds,
+                chain,
+                nonce,
+            }
+        }
+    }
+
+    /// Reads a `TransactionExpiration`, failing with an [`UnknownVariantError`] on ways to expire
+    /// added by newer protocol versions, or an error naming them in human readable formats.
+    struct TransactionExpirationVisitor {
+        readable: bool,
+    }
+
+    impl TransactionExpirationVisitor {
+        const VARIANTS: &'static [&'static str] = &["none", "epoch", "valid_during"];
+    }
+
+    impl<'de> serde::de::Visitor<'de> for TransactionExpirationVisitor {
+        type Value = TransactionExpiration;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a transaction expiration")
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::EnumAccess<'de>,
+        {
+            use serde::de::Error;
+            use serde::de::VariantAccess;
+
+            let (index, variant) = if self.readable {
+                let (name, variant) = data.variant::<String>()?;
+                let index = Self::VARIANTS
+                    .iter()
+                    .position(|variant| *variant == name)
+                    .ok_or_else(|| {
+                        A::Error::custom(format!(
+                            "unknown variant `{name}` of TransactionExpiration, \
+                             which may need a newer version of this crate"
+                        ))
+                    })?;
+                (index as u32, variant)
+            } else {
+                data.variant_seed(crate::_serde::VariantIndex(Self::VARIANTS))?
+            };
+            match index {
+                0 => variant
+                    .unit_variant()
+                    .map(|()| TransactionExpiration::None),
+                1 if self.readable => variant
+                    .newtype_variant::<serde_with::de::DeserializeAsWrap<
+                        EpochId,
+                        serde_with::DisplayFromStr,
+                    >>()
+                    .map(|epoch| TransactionExpiration::Epoch(epoch.into_inner())),
+                1 => variant
+                    .newtype_variant()
+                    .map(TransactionExpiration::Epoch),
+                2 => variant
+                    .newtype_variant::<ValidDuring>()
+                    .map(Into::into),
+                index => Err(UnknownVariantError {
+                    type_name: "TransactionExpiration",
+                    index,
+                }
+                .into_error()),
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for TransactionExpiration {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let readable = deserializer.is_human_readable();
+            deserializer.deserialize_enum(
+                "TransactionExpiration",
+                TransactionExpirationVisitor::VARIANTS,
+                TransactionExpirationVisitor { readable },
+            )
+        }
+    }
+}
+
 mod transaction_kind {
     use super::*;
     use crate::types::transaction::AuthenticatorStateUpdate;
@@ -187,7 +356,7 @@ mod transaction_kind {
     #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
     enum ReadableTransactionKind {
         ProgrammableTransaction(ProgrammableTransaction),
-        ChangeEpoch(ChangeEpoch),
+        ChangeEpoch(Box<ChangeEpoch>),
         Genesis(GenesisTransaction),
         ConsensusCommitPrologue(ConsensusCommitPrologue),
         AuthenticatorStateUpdate(AuthenticatorStateUpdate),
@@ -195,7 +364,7 @@ mod transaction_kind {
             commands: Vec<EndOfEpochTransactionKind>,
         },
         RandomnessStateUpdate(RandomnessStateUpdate),
-        ConsensusCommitPrologueV2(ConsensusCommitPrologueV2),
+        ConsensusCommitPrologueV2(Box<ConsensusCommitPrologueV2>),
     }
 
     #[cfg(feature = "schemars")]
@@ -220,16 +389,66 @@ mod transaction_kind {
         RandomnessStateUpdate(&'a RandomnessStateUpdate),
         ConsensusCommitPrologueV2(&'a ConsensusCommitPrologueV2),
     }
-    #[derive(serde_derive::Deserialize)]
-    enum BinaryTransactionKind {
-        ProgrammableTransaction(ProgrammableTransaction),
-        ChangeEpoch(ChangeEpoch),
-        Genesis(GenesisTransaction),
-        ConsensusCommitPrologue(ConsensusCommitPrologue),
-        AuthenticatorStateUpdate(AuthenticatorStateUpdate),
-        EndOfEpoch(Vec<EndOfEpochTransactionKind>),
-        RandomnessStateUpdate(RandomnessStateUpdate),
-        ConsensusCommitPrologueV2(ConsensusCommitPrologueV2),
+    /// Reads the binary encoding of a `TransactionKind`, failing with an [`UnknownVariantError`]
+    /// on kinds added by newer protocol versions.
+    struct BinaryTransactionKindVisitor;
+
+    impl BinaryTransactionKindVisitor {
+        const VARIANTS: &'static [&'static str] = &[
+            "ProgrammableTransaction",
+            "ChangeEpoch",
+            "Genesis",
+            "ConsensusCommitPrologue",
+            "AuthenticatorStateUpdate",
+            "EndOfEpoch",
+            "RandomnessStateUpdate",
+            "ConsensusCommitPrologueV2",
+        ];
+    }
+
+    impl<'de> serde::de::Visitor<'de> for BinaryTransactionKindVisitor {
+        type Value = TransactionKind;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a transaction kind")
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::EnumAccess<'de>,
+        {
+            use serde::de::VariantAccess;
+
+            let (index, variant) =
+                data.variant_seed(crate::_serde::VariantIndex(Self::VARIANTS))?;
+            match index {
+                0 => variant
+                    .newtype_variant()
+                    .map(TransactionKind::ProgrammableTransaction),
+                1 => variant
+                    .newtype_variant()
+                    .map(|k| TransactionKind::ChangeEpoch(Arc::new(k))),
+                2 => variant.newtype_variant().map(TransactionKind::Genesis),
+                3 => variant
+                    .newtype_variant()
+                    .map(TransactionKind::ConsensusCommitPrologue),
+                4 => variant
+                    .newtype_variant()
+                    .map(TransactionKind::AuthenticatorStateUpdate),
+                5 => variant.newtype_variant().map(TransactionKind::EndOfEpoch),
+                6 => variant
+                    .newtype_variant()
+                    .map(TransactionKind::RandomnessStateUpdate),
+                7 => variant
+                    .newtype_variant()
+                    .map(|k| TransactionKind::ConsensusCommitPrologueV2(Arc::new(k))),
+                index => Err(UnknownVariantError {
+                    type_name: "TransactionKind",
+                    index,
+                }
+                .into_error()),
+            }
+        }
     }
 
     impl Serialize for TransactionKind {
@@ -297,7 +516,9 @@ mod transaction_kind {
                     ReadableTransactionKind::ProgrammableTransaction(k) => {
                         TransactionKind::ProgrammableTransaction(k)
                     }
-                    ReadableTransactionKind::ChangeEpoch(k) => TransactionKind::ChangeEpoch(k),
+                    ReadableTransactionKind::ChangeEpoch(k) => {
+                        TransactionKind::ChangeEpoch(k.into())
+                    }
                     ReadableTransactionKind::Genesis(k) => TransactionKind::Genesis(k),
                     ReadableTransactionKind::ConsensusCommitPrologue(k) => {
                         TransactionKind::ConsensusCommitPrologue(k)
@@ -312,30 +533,15 @@ mod transaction_kind {
                         TransactionKind::RandomnessStateUpdate(k)
                     }
                     ReadableTransactionKind::ConsensusCommitPrologueV2(k) => {
-                        TransactionKind::ConsensusCommitPrologueV2(k)
+                        TransactionKind::ConsensusCommitPrologueV2(k.into())
                     }
                 })
             } else {
-                BinaryTransactionKind::deserialize(deserializer).map(|binary| match binary {
-                    BinaryTransactionKind::ProgrammableTransaction(k) => {
-                        TransactionKind::ProgrammableTransaction(k)
-                    }
-                    BinaryTransactionKind::ChangeEpoch(k) => TransactionKind::ChangeEpoch(k),
-                    BinaryTransactionKind::Genesis(k) => TransactionKind::Genesis(k),
-                    BinaryTransactionKind::ConsensusCommitPrologue(k) => {
-                        TransactionKind::ConsensusCommitPrologue(k)
-                    }
-                    BinaryTransactionKind::AuthenticatorStateUpdate(k) => {
-                        TransactionKind::AuthenticatorStateUpdate(k)
-                    }
-                    BinaryTransactionKind::EndOfEpoch(k) => TransactionKind::EndOfEpoch(k),
-                    BinaryTransactionKind::RandomnessStateUpdate(k) => {
-                        TransactionKind::RandomnessStateUpdate(k)
-                    }
-                    BinaryTransactionKind::ConsensusCommitPrologueV2(k) => {
-                        TransactionKind::ConsensusCommitPrologueV2(k)
-                    }
-                })
+                deserializer.deserialize_enum(
+                    "TransactionKind",
+                    BinaryTransactionKindVisitor::VARIANTS,
+                    BinaryTransactionKindVisitor,
+                )
             }
         }
     }
@@ -346,7 +552,8 @@ mod end_of_epoch {
     use crate::types::transaction::AuthenticatorStateExpire;
     use crate::types::transaction::ChangeEpoch;
     use crate::types::transaction::EndOfEpochTransactionKind;
-    use crate::types::CheckpointDigest;
+    use crate::types::ChainIdentifier;
+    use crate::types::Version;
 
     #[derive(serde_derive::Serialize)]
     #[serde(tag = "kind", rename_all = "snake_case")]
@@ -357,28 +564,28 @@ mod end_of_epoch {
         RandomnessStateCreate,
         DenyListStateCreate,
         BridgeStateCreate {
-            chain_id: &'a CheckpointDigest,
+            chain_id: &'a ChainIdentifier,
         },
         BridgeCommitteeInit {
             #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
-            bridge_object_version: u64,
+            bridge_object_version: Version,
         },
     }
 
     #[derive(serde_derive::Deserialize)]
     #[serde(tag = "kind", rename_all = "snake_case")]
     enum ReadableEndOfEpochTransactionKind {
-        ChangeEpoch(ChangeEpoch),
+        ChangeEpoch(Box<ChangeEpoch>),
         AuthenticatorStateCreate,
         AuthenticatorStateExpire(AuthenticatorStateExpire),
         RandomnessStateCreate,
         DenyListStateCreate,
         BridgeStateCreate {
-            chain_id: CheckpointDigest,
+            chain_id: ChainIdentifier,
         },
         BridgeCommitteeInit {
             #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
-            bridge_object_version: u64,
+            bridge_object_version: Version,
         },
     }
 
@@ -389,19 +596,89 @@ mod end_of_epoch {
         AuthenticatorStateExpire(&'a AuthenticatorStateExpire),
         RandomnessStateCreate,
         DenyListStateCreate,
-        BridgeStateCreate { chain_id: &'a CheckpointDigest },
-        BridgeCommitteeInit { bridge_object_version: u64 },
+        BridgeStateCreate { chain_id: &'a ChainIdentifier },
+        BridgeCommitteeInit { bridge_object_version: Version },
     }
 
+    /// Reads the binary encoding of an `EndOfEpochTransactionKind`, failing with an
+    /// [`UnknownVariantError`] on kinds added by newer protocol versions.
+    ///
+    /// Struct variants are read as newtype variants of a struct with the same fields, which every
+    /// format encodes the same way.
+    struct BinaryEndOfEpochTransactionKindVisitor;
+
     #[derive(serde_derive::Deserialize)]
-    enum BinaryEndOfEpochTransactionKind {
-        ChangeEpoch(ChangeEpoch),
-        AuthenticatorStateCreate,
-        AuthenticatorStateExpire(AuthenticatorStateExpire),
-        RandomnessStateCreate,
-        DenyListStateCreate,
-        BridgeStateCreate { chain_id: CheckpointDigest },
-        BridgeCommitteeInit { bridge_object_version: u64 },
+    struct BridgeStateCreate {
+        chain_id: ChainIdentifier,
+    }
+
+    #[derive(serde_derive::Deserialize)]
+    struct BridgeCommitteeInit {
+        bridge_object_version: Version,
+    }
+
+    impl BinaryEndOfEpochTransactionKindVisitor {
+        const VARIANTS: &'static [&'static str] = &[
+            "ChangeEpoch",
+            "AuthenticatorStateCreate",
+            "AuthenticatorStateExpire",
+            "RandomnessStateCreate",
+            "DenyListStateCreate",
+            "BridgeStateCreate",
+            "BridgeCommitteeInit",
+        ];
+    }
+
+    impl<'de> serde::de::Visitor<'de> for BinaryEndOfEpochTransactionKindVisitor {
+        type Value = EndOfEpochTransactionKind;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("an end of epoch transaction kind")
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::EnumAccess<'de>,
+        {
+            use serde::de::VariantAccess;
+
+            let (index, variant) =
+                data.variant_seed(crate::_serde::VariantIndex(Self::VARIANTS))?;
+            match index {
+                0 => variant
+                    .newtype_variant()
+                    .map(|k| EndOfEpochTransactionKind::ChangeEpoch(Arc::new(k))),
+                1 => variant
+                    .unit_variant()
+                    .map(|()| EndOfEpochTransactionKind::AuthenticatorStateCreate),
+                2 => variant
+                    .newtype_variant()
+                    .map(EndOfEpochTransactionKind::AuthenticatorStateExpire),
+                3 => variant
+                    .unit_variant()
+                    .map(|()| EndOfEpochTransactionKind::RandomnessStateCreate),
+                4 => variant
+                    .unit_variant()
+                    .map(|()| EndOfEpochTransactionKind::DenyListStateCreate),
+                5 => variant
+                    .newtype_variant()
+                    .map(|BridgeStateCreate { chain_id }| {
+                        EndOfEpochTransactionKind::BridgeStateCreate { chain_id }
+                    }),
+                6 => variant.newtype_variant().map(
+                    |BridgeCommitteeInit {
+                         bridge_object_version,
+                     }| EndOfEpochTransactionKind::BridgeCommitteeInit {
+                        bridge_object_version,
+                    },
+                ),
+                index => Err(UnknownVariantError {
+                    type_name: "EndOfEpochTransactionKind",
+                    index,
+                }
+                .into_error()),
+            }
+        }
     }
 
     impl Serialize for EndOfEpochTransactionKind {
@@ -471,7 +748,9 @@ mod end_of_epoch {
             if deserializer.is_human_readable() {
                 ReadableEndOfEpochTransactionKind::deserialize(deserializer).map(|readable| {
                     match readable {
-                        ReadableEndOfEpochTransactionKind::ChangeEpoch(k) => Self::ChangeEpoch(k),
+                        ReadableEndOfEpochTransactionKind::ChangeEpoch(k) => {
+                            Self::ChangeEpoch(k.into())
+                        }
                         ReadableEndOfEpochTransactionKind::AuthenticatorStateCreate => {
                             Self::AuthenticatorStateCreate
                         }
@@ -495,30 +774,10 @@ mod end_of_epoch {
                     }
                 })
             } else {
-                BinaryEndOfEpochTransactionKind::deserialize(deserializer).map(
-                    |binary| match binary {
-                        BinaryEndOfEpochTransactionKind::ChangeEpoch(k) => Self::ChangeEpoch(k),
-                        BinaryEndOfEpochTransactionKind::AuthenticatorStateCreate => {
-                            Self::AuthenticatorStateCreate
-                        }
-                        BinaryEndOfEpochTransactionKind::AuthenticatorStateExpire(k) => {
-                            Self::AuthenticatorStateExpire(k)
-                        }
-                        BinaryEndOfEpochTransactionKind::RandomnessStateCreate => {
-                            Self::RandomnessStateCreate
-                        }
-                        BinaryEndOfEpochTransactionKind::DenyListStateCreate => {
-                            Self::DenyListStateCreate
-                        }
-                        BinaryEndOfEpochTransactionKind::BridgeStateCreate { chain_id } => {
-                            Self::BridgeStateCreate { chain_id }
-                        }
-                        BinaryEndOfEpochTransactionKind::BridgeCommitteeInit {
-                            bridge_object_version,
-                        } => Self::BridgeCommitteeInit {
-                            bridge_object_version,
-                        },
-                    },
+                deserializer.deserialize_enum(
+                    "EndOfEpochTransactionKind",
+                    BinaryEndOfEpochTransactionKindVisitor::VARIANTS,
+                    BinaryEndOfEpochTransactionKindVisitor,
                 )
             }
         }
@@ -527,6 +786,7 @@ mod end_of_epoch {
 
 mod input_argument {
     use crate::types::transaction::InputArgument;
+    use crate::types::Version;
 
     use super::*;
 
@@ -534,19 +794,89 @@ mod input_argument {
     #[serde(tag = "type", rename_all = "snake_case")]
     enum ReadableInputArgument {
         Pure {
-            #[serde(with = "::serde_with::As::<crate::_serde::Base64Encoded>")]
+            #[serde(with = "::serde_with::As::<PureValue>")]
             value: Vec<u8>,
         },
         ImmutableOrOwned(ObjectReference),
         Shared {
             object_id: ObjectId,
             #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
-            initial_shared_version: u64,
+            initial_shared_version: Version,
             mutable: bool,
         },
         Receiving(ObjectReference),
     }
 
+    /// The bytes of a pure input, written as Base64 and read from Base64, `0x` prefixed hex or an
+    /// array of bytes, as different ecosystems submit them in different encodings.
+    ///
+    /// Strings made of `0x` followed by hex digits are read as hex, so the rare Base64 which looks
+    /// like that is written as hex instead.
+    struct PureValue;
+
+    impl PureValue {
+        fn hex_digits(s: &str) -> Option<&str> {
+            s.strip_prefix("0x")
+                .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        }
+    }
+
+    impl SerializeAs<Vec<u8>> for PureValue {
+        fn serialize_as<S>(source: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            use base64ct::Encoding;
+
+            let b64 = base64ct::Base64::encode_string(source);
+            if Self::hex_digits(&b64).is_some() {
+                format!("0x{}", hex::encode(source)).serialize(serializer)
+            } else {
+                b64.serialize(serializer)
+            }
+        }
+    }
+
+    impl<'de> DeserializeAs<'de, Vec<u8>> for PureValue {
+        fn deserialize_as<D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_any(PureValue)
+        }
+    }
+
+    impl<'de> serde::de::Visitor<'de> for PureValue {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("Base64, 0x prefixed hex or an array of bytes")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            use base64ct::Encoding;
+
+            match Self::hex_digits(v) {
+                Some(digits) => hex::decode(digits).map_err(E::custom),
+                None => base64ct::Base64::decode_vec(v).map_err(E::custom),
+            }
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+
     #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
     enum CallArg {
         Pure(#[serde(with = "::serde_with::As::<::serde_with::Bytes>")] Vec<u8>),
@@ -558,7 +888,7 @@ mod input_argument {
         ImmutableOrOwned(ObjectReference),
         Shared {
             object_id: ObjectId,
-            initial_shared_version: u64,
+            initial_shared_version: Version,
             mutable: bool,
         },
         Receiving(ObjectReference),
@@ -778,13 +1108,13 @@ mod command {
     #[derive(serde_derive::Deserialize)]
     #[serde(tag = "command", rename_all = "snake_case")]
     enum ReadableCommand {
-        MoveCall(MoveCall),
+        MoveCall(Box<MoveCall>),
         TransferObjects(TransferObjects),
         SplitCoins(SplitCoins),
         MergeCoins(MergeCoins),
-        Publish(Publish),
-        MakeMoveVector(MakeMoveVector),
-        Upgrade(Upgrade),
+        Publish(Box<Publish>),
+        MakeMoveVector(Box<MakeMoveVector>),
+        Upgrade(Box<Upgrade>),
     }
 
     #[derive(serde_derive::Serialize)]
@@ -798,15 +1128,60 @@ mod command {
         Upgrade(&'a Upgrade),
     }
 
-    #[derive(serde_derive::Deserialize)]
-    enum BinaryCommand {
-        MoveCall(MoveCall),
-        TransferObjects(TransferObjects),
-        SplitCoins(SplitCoins),
-        MergeCoins(MergeCoins),
-        Publish(Publish),
-        MakeMoveVector(MakeMoveVector),
-        Upgrade(Upgrade),
+    /// Reads the binary encoding of a `Command`, failing with an [`UnknownVariantError`] on
+    /// commands added by newer protocol versions.
+    struct BinaryCommandVisitor;
+
+    impl BinaryCommandVisitor {
+        const VARIANTS: &'static [&'static str] = &[
+            "MoveCall",
+            "TransferObjects",
+            "SplitCoins",
+            "MergeCoins",
+            "Publish",
+            "MakeMoveVector",
+            "Upgrade",
+        ];
+    }
+
+    impl<'de> serde::de::Visitor<'de> for BinaryCommandVisitor {
+        type Value = Command;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a command")
+        }
+
+        fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+        where
+            A: serde::de::EnumAccess<'de>,
+        {
+            use serde::de::VariantAccess;
+
+            let (index, variant) =
+                data.variant_seed(crate::_serde::VariantIndex(Self::VARIANTS))?;
+            match index {
+                0 => variant
+                    .newtype_variant()
+                    .map(|c| Command::MoveCall(Arc::new(c))),
+                1 => variant.newtype_variant().map(Command::TransferObjects),
+                2 => variant.newtype_variant().map(Command::SplitCoins),
+                3 => variant.newtype_variant().map(Command::MergeCoins),
+                4 => variant
+                    .newtype_variant()
+                    .map(|c| Command::Publish(Arc::new(c))),
+                5 => variant
+                    .newtype_variant()
+                    .map(|c| Command::MakeMoveVector(Arc::new(c))),
+                6 => variant
+                    .newtype_variant()
+                    .map(|c| Command::Upgrade(Arc::new(c))),
+                index => Err(UnknownVariantError {
+                    type_name: "Command",
+                    index,
+                }
+                .into_error()),
+            }
+        }
     }
 
     impl Serialize for Command {
@@ -847,24 +1222,20 @@ mod command {
         {
             if deserializer.is_human_readable() {
                 ReadableCommand::deserialize(deserializer).map(|readable| match readable {
-                    ReadableCommand::MoveCall(c) => Command::MoveCall(c),
+                    ReadableCommand::MoveCall(c) => Command::MoveCall(c.into()),
                     ReadableCommand::TransferObjects(c) => Command::TransferObjects(c),
                     ReadableCommand::SplitCoins(c) => Command::SplitCoins(c),
                     ReadableCommand::MergeCoins(c) => Command::MergeCoins(c),
-                    ReadableCommand::Publish(c) => Command::Publish(c),
-                    ReadableCommand::MakeMoveVector(c) => Command::MakeMoveVector(c),
-                    ReadableCommand::Upgrade(c) => Command::Upgrade(c),
+                    ReadableCommand::Publish(c) => Command::Publish(c.into()),
+                    ReadableCommand::MakeMoveVector(c) => Command::MakeMoveVector(c.into()),
+                    ReadableCommand::Upgrade(c) => Command::Upgrade(c.into()),
                 })
             } else {
-                BinaryCommand::deserialize(deserializer).map(|binary| match binary {
-                    BinaryCommand::MoveCall(c) => Command::MoveCall(c),
-                    BinaryCommand::TransferObjects(c) => Command::TransferObjects(c),
-                    BinaryCommand::SplitCoins(c) => Command::SplitCoins(c),
-                    BinaryCommand::MergeCoins(c) => Command::MergeCoins(c),
-                    BinaryCommand::Publish(c) => Command::Publish(c),
-                    BinaryCommand::MakeMoveVector(c) => Command::MakeMoveVector(c),
-                    BinaryCommand::Upgrade(c) => Command::Upgrade(c),
-                })
+                deserializer.deserialize_enum(
+                    "Command",
+                    BinaryCommandVisitor::VARIANTS,
+                    BinaryCommandVisitor,
+                )
             }
         }
     }
@@ -876,8 +1247,13 @@ mod signed_transaction {
     use serde::ser::SerializeSeq;
 
     use super::*;
+    use crate::types::transaction::SenderSignedData;
+    use crate::types::transaction::SenderSignedDataError;
+    use crate::types::transaction::SenderSignedTransaction;
     use crate::types::transaction::SignedTransaction;
     use crate::types::transaction::Transaction;
+    use crate::types::Intent;
+    use crate::types::IntentMessage;
     use crate::types::UserSignature;
 
     #[derive(serde_derive::Serialize)]
@@ -960,39 +1336,7 @@ mod signed_transaction {
         }
     }
 
-    /// Intents are defined as:
-    ///
-    /// ```
-    /// struct Intent {
-    ///     scope: IntentScope,
-    ///     version: IntentVersion,
-    ///     app_id: AppId,
-    /// }
-    ///
-    /// enum IntentVersion {
-    ///     V0 = 0,
-    /// }
-    ///
-    /// enum AppId {
-    ///     Sui = 0,
-    ///     Narwhal = 1,
-    ///     Consensus = 2,
-    /// }
-    ///
-    /// enum IntentScope {
-    ///     TransactionData = 0,         // Used for a user signature on a transaction data.
-    ///     TransactionEffects = 1,      // Used for an authority signature on transaction effects.
-    ///     CheckpointSummary = 2,       // Used for an authority signature on a checkpoint summary.
-    ///     PersonalMessage = 3,         // Used for a user signature on a personal message.
-    ///     SenderSignedTransaction = 4, // Used for an authority signature on a user signed transaction.
-    ///     ProofOfPossession = 5, // Used as a signature representing an authority's proof of possession of its authority protocol key.
-    ///     HeaderDigest = 6,      // Used for narwhal authority signature on header digest.
-    ///     BridgeEventUnused = 7, // for bridge purposes but it's currently not included in messages.
-    ///     ConsensusBlock = 8,    // Used for consensus authority signature on block's digest
-    /// }
-    /// ```
-    ///
-    /// So we need to serialize Transaction as (0, 0, 0, Transaction)
+    /// Serializes a `Transaction` as the `IntentMessage` signed by users, `(0, 0, 0, Transaction)`.
     struct IntentMessageWrappedTransaction;
 
     impl SerializeAs<Transaction> for IntentMessageWrappedTransaction {
@@ -1000,51 +1344,45 @@ mod signed_transaction {
         where
             S: Serializer,
         {
-            use serde::ser::SerializeTuple;
-
-            let mut s = serializer.serialize_tuple(4)?;
-            s.serialize_element(&0u8)?;
-            s.serialize_element(&0u8)?;
-            s.serialize_element(&0u8)?;
-            s.serialize_element(transaction)?;
-            s.end()
+            IntentMessage::new(Intent::TRANSACTION_DATA, transaction).serialize(serializer)
         }
     }
 
-    impl<'de> DeserializeAs<'de, Transaction> for IntentMessageWrappedTransaction {
-        fn deserialize_as<D>(deserializer: D) -> Result<Transaction, D::Error>
+    pub(crate) struct SignedTransactionWithIntentMessage;
+
+    /// Serializes a `SignedTransaction` as the single entry of a `SenderSignedData`.
+    #[derive(serde_derive::Serialize)]
+    #[serde(rename = "SenderSignedTransaction")]
+    struct SenderSignedTransactionRef<'a> {
+        #[serde(with = "::serde_with::As::<IntentMessageWrappedTransaction>")]
+        intent_message: &'a Transaction,
+        tx_signatures: &'a Vec<UserSignature>,
+    }
+
+    impl<'de> Deserialize<'de> for SenderSignedData {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
         where
             D: Deserializer<'de>,
         {
-            let (scope, version, app, transaction): (u8, u8, u8, Transaction) =
+            let transactions: Vec<SenderSignedTransaction> =
                 Deserialize::deserialize(deserializer)?;
-            match (scope, version, app) {
-                (0, 0, 0) => {}
-                _ => {
-                    return Err(serde::de::Error::custom(format!(
-                        "invalid intent message ({scope}, {version}, {app})"
-                    )))
-                }
+            if transactions.len() != 1 {
+                return Err(serde::de::Error::custom(
+                    SenderSignedDataError::TransactionCount(transactions.len()),
+                ));
             }
-
-            Ok(transaction)
+            Ok(Self { transactions })
         }
     }
 
-    pub(crate) struct SignedTransactionWithIntentMessage;
-
-    #[derive(serde_derive::Serialize)]
-    struct BinarySignedTransactionWithIntentMessageRef<'a> {
-        #[serde(with = "::serde_with::As::<IntentMessageWrappedTransaction>")]
-        transaction: &'a Transaction,
-        signatures: &'a Vec<UserSignature>,
-    }
-
-    #[derive(serde_derive::Deserialize)]
-    struct BinarySignedTransactionWithIntentMessage {
-        #[serde(with = "::serde_with::As::<IntentMessageWrappedTransaction>")]
-        transaction: Transaction,
-        signatures: Vec<UserSignature>,
+    impl SenderSignedData {
+        /// Deserialize an envelope from its BCS serialization, holding any number of transactions,
+        /// which the protocol rejects, e.g. to inspect a malformed submission.
+        pub fn from_bcs_unchecked(bytes: &[u8]) -> Result<Self, bcs::Error> {
+            Ok(Self {
+                transactions: bcs::from_bytes(bytes)?,
+            })
+        }
     }
 
     impl SerializeAs<SignedTransaction> for SignedTransactionWithIntentMessage {
@@ -1066,9 +1404,9 @@ mod signed_transaction {
                 };
                 readable.serialize(serializer)
             } else {
-                let binary = BinarySignedTransactionWithIntentMessageRef {
-                    transaction,
-                    signatures,
+                let binary = SenderSignedTransactionRef {
+                    intent_message: transaction,
+                    tx_signatures: signatures,
                 };
 
                 let mut s = serializer.serialize_seq(Some(1))?;
@@ -1094,38 +1432,9 @@ mod signed_transaction {
                     signatures,
                 })
             } else {
-                struct V;
-                impl<'de> serde::de::Visitor<'de> for V {
-                    type Value = SignedTransaction;
-
-                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                        formatter.write_str("expected a sequence with length 1")
-                    }
-
-                    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
-                    where
-                        A: serde::de::SeqAccess<'de>,
-                    {
-                        if seq.size_hint().is_some_and(|size| size != 1) {
-                            return Err(serde::de::Error::custom(
-                                "expected a sequence with length 1",
-                            ));
-                        }
-
-                        let BinarySignedTransactionWithIntentMessage {
-                            transaction,
-                            signatures,
-                        } = seq.next_element()?.ok_or_else(|| {
-                            serde::de::Error::custom("expected a sequence with length 1")
-                        })?;
-                        Ok(SignedTransaction {
-                            transaction,
-                            signatures,
-                        })
-                    }
-                }
-
-                deserializer.deserialize_seq(V)
+                SenderSignedData::deserialize(deserializer)?
+                    .into_signed_transaction()
+                    .map_err(serde::de::Error::custom)
             }
         }
     }
@@ -1136,12 +1445,28 @@ mod test {
     use base64ct::Base64;
     use base64ct::Encoding;
 
+    use crate::encoding::from_bcs;
+    use crate::encoding::BcsDecodeError;
     use crate::types::transaction::Argument;
+    use crate::types::transaction::Command;
+    use crate::types::transaction::EndOfEpochTransactionKind;
     use crate::types::transaction::InputArgument;
+    use crate::types::transaction::SenderSignedData;
+    use crate::types::transaction::SenderSignedDataError;
+    use crate::types::transaction::SignedTransaction;
+    use crate::types::transaction::SignedTransactionWithIntentMessage;
     use crate::types::transaction::Transaction;
+    use crate::types::transaction::TransactionExpiration;
+    use crate::types::transaction::UnknownVariantError;
+    use crate::types::transaction::UnsupportedExpirationError;
+    use crate::types::ChainIdentifier;
+    use crate::types::Intent;
+    use crate::types::IntentError;
+    use crate::types::IntentMessage;
     use crate::types::ObjectDigest;
     use crate::types::ObjectId;
     use crate::types::ObjectReference;
+    use crate::types::Version;
 
     #[cfg(target_arch = "wasm32")]
     use wasm_bindgen_test::wasm_bindgen_test as test;
@@ -1189,7 +1514,7 @@ mod test {
             (
                 InputArgument::ImmutableOrOwned(ObjectReference::new(
                     ObjectId::ZERO,
-                    1,
+                    Version::new(1),
                     ObjectDigest::ZERO,
                 )),
                 serde_json::json!({
@@ -1202,7 +1527,7 @@ mod test {
             (
                 InputArgument::Shared {
                     object_id: ObjectId::ZERO,
-                    initial_shared_version: 1,
+                    initial_shared_version: Version::new(1),
                     mutable: true,
                 },
                 serde_json::json!({
@@ -1215,7 +1540,7 @@ mod test {
             (
                 InputArgument::Receiving(ObjectReference::new(
                     ObjectId::ZERO,
-                    1,
+                    Version::new(1),
                     ObjectDigest::ZERO,
                 )),
                 serde_json::json!({
@@ -1264,4 +1589,260 @@ mod test {
             assert_eq!(tx, serde_json::from_str(&json).unwrap());
         }
     }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn random_transaction() {
+        let tx = Transaction::random();
+        assert_ne!(tx, Transaction::random());
+        assert_eq!(tx.gas_payment.owner, tx.sender);
+        assert!(tx.gas_payment.objects[0].version() < Version::new(i64::MAX as u64));
+
+        let bytes = bcs::to_bytes(&tx).unwrap();
+        assert_eq!(bcs::from_bytes::<Transaction>(&bytes).unwrap(), tx);
+        let json = serde_json::to_string(&tx).unwrap();
+        assert_eq!(serde_json::from_str::<Transaction>(&json).unwrap(), tx);
+    }
+
+    #[test]
+    fn pure_value_encodings() {
+        let pure = InputArgument::Pure {
+            value: vec![1, 2, 255],
+        };
+        for json in [
+            serde_json::json!({"type": "pure", "value": "AQL/"}),
+            serde_json::json!({"type": "pure", "value": "0x0102ff"}),
+            serde_json::json!({"type": "pure", "value": "0x0102FF"}),
+            serde_json::json!({"type": "pure", "value": [1, 2, 255]}),
+        ] {
+            assert_eq!(serde_json::from_value::<InputArgument>(json).unwrap(), pure);
+        }
+        assert_eq!(
+            serde_json::to_value(&pure).unwrap(),
+            serde_json::json!({"type": "pure", "value": "AQL/"})
+        );
+
+        for invalid in [
+            serde_json::json!("0x012"),
+            serde_json::json!("not base64"),
+            serde_json::json!([1, 256]),
+        ] {
+            serde_json::from_value::<InputArgument>(
+                serde_json::json!({"type": "pure", "value": invalid}),
+            )
+            .unwrap_err();
+        }
+
+        // Base64 which would be read as hex is written as hex
+        let pure = InputArgument::Pure {
+            value: vec![0xd3, 0x1d, 0xb7],
+        };
+        let json = serde_json::to_value(&pure).unwrap();
+        assert_eq!(json["value"], "0xd31db7");
+        assert_eq!(serde_json::from_value::<InputArgument>(json).unwrap(), pure);
+    }
+
+    fn unknown_variant<T: serde::de::DeserializeOwned + std::fmt::Debug>(
+        bytes: &[u8],
+    ) -> UnknownVariantError {
+        match from_bcs::<T>(bytes) {
+            Err(BcsDecodeError::UnknownVariant(unknown)) => unknown,
+            result => panic!("expected an unknown variant, got {result:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_variants() {
+        const PTB: &str = "AAADAQFEBbUNeR/TNGdU6Bcaqra8LtJsLEbv3QM8FLMK5QesMyx96QEAAAAAAQAIVsakAAAAAAABALyyokbZ/8ynfWQer6UyP1DpeCnPU1NC7AyFNJSaTztnQF40BQAAAAAgffPXh5XuG6TWjHk6qC5w9k2a+41oTWfm0sC1FOYRqsEBAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAN7pB2Nsb2JfdjIMY2FuY2VsX29yZGVyAgcAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAgNzdWkDU1VJAAddSzAlBmRcN/8TO5jEtQpa4UhBZZc41tcz1Z0NIXqTvwRjb2luBENPSU4AAwEAAAEBAAECAPgh00g/x3Jeuvqlo9Ejc9SZAb384UhPIZ2qcGajDfd9ASXQjpFOD6mfycbzwD1wc+IOkCXQ8rHQo/Vi5SDOGMR/Jl40BQAAAAAgV7P1E0IMKon5uI82R/0arWLt+dc1ng/4VwKDqpTCxHT4IdNIP8dyXrr6paPRI3PUmQG9/OFITyGdqnBmow33fe4CAAAAAAAAAMqaOwAAAAAA";
+
+        // The kind of a transaction follows its version
+        let mut transaction = Base64::decode_vec(PTB).unwrap();
+        transaction[1] = 42;
+        assert_eq!(
+            unknown_variant::<Transaction>(&transaction),
+            UnknownVariantError {
+                type_name: "TransactionKind",
+                index: 42
+            }
+        );
+
+        assert_eq!(
+            unknown_variant::<Command>(&[7]),
+            UnknownVariantError {
+                type_name: "Command",
+                index: 7
+            }
+        );
+        assert_eq!(
+            bcs::from_bytes::<Command>(&[7]).unwrap_err().to_string(),
+            "unknown variant index 7 of Command"
+        );
+
+        assert_eq!(
+            unknown_variant::<EndOfEpochTransactionKind>(&[7]),
+            UnknownVariantError {
+                type_name: "EndOfEpochTransactionKind",
+                index: 7
+            }
+        );
+        assert_eq!(
+            bcs::from_bytes::<EndOfEpochTransactionKind>(&[3]).unwrap(),
+            EndOfEpochTransactionKind::RandomnessStateCreate
+        );
+
+        assert_eq!(
+            unknown_variant::<TransactionExpiration>(&[3]),
+            UnknownVariantError {
+                type_name: "TransactionExpiration",
+                index: 3
+            }
+        );
+        assert_eq!(
+            bcs::from_bytes::<TransactionExpiration>(&[1, 5, 0, 0, 0, 0, 0, 0, 0]).unwrap(),
+            TransactionExpiration::Epoch(5.into())
+        );
+
+        // Truncated input is not an unknown variant, even after one
+        assert!(matches!(
+            from_bcs::<Transaction>(&transaction[..1]),
+            Err(BcsDecodeError::Bcs(_))
+        ));
+    }
+
+    #[test]
+    fn transaction_expiration_json() {
+        let cases = [
+            (TransactionExpiration::None, serde_json::json!("none")),
+            (
+                TransactionExpiration::Epoch(7.into()),
+                serde_json::json!({"epoch": "7"}),
+            ),
+            (
+                TransactionExpiration::ValidDuring {
+                    min_epoch: Some(7.into()),
+                    max_epoch: Some(8.into()),
+                    min_timestamp_seconds: None,
+                    max_timestamp_seconds: Some(1_700_000_000),
+                    chain: ChainIdentifier::MAINNET,
+                    nonce: 3,
+                },
+                serde_json::json!({"valid_during": {
+                    "min_epoch": "7",
+                    "max_epoch": "8",
+                    "min_timestamp_seconds": null,
+                    "max_timestamp_seconds": "1700000000",
+                    "chain": ChainIdentifier::MAINNET.to_string(),
+                    "nonce": 3,
+                }}),
+            ),
+        ];
+        for (expiration, json) in cases {
+            assert_eq!(serde_json::to_value(expiration).unwrap(), json);
+            assert_eq!(
+                serde_json::from_value::<TransactionExpiration>(json).unwrap(),
+                expiration
+            );
+            let bytes = bcs::to_bytes(&expiration).unwrap();
+            assert_eq!(
+                bcs::from_bytes::<TransactionExpiration>(&bytes).unwrap(),
+                expiration
+            );
+        }
+
+        let error = serde_json::from_value::<TransactionExpiration>(
+            serde_json::json!({"timestamp": {"min": "1", "max": "2"}}),
+        )
+        .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("unknown variant `timestamp` of TransactionExpiration"));
+    }
+
+    #[test]
+    fn expiration_protocol_version() {
+        let version = TransactionExpiration::VALID_DURING_PROTOCOL_VERSION;
+        let valid_during = |protocol_version| {
+            TransactionExpiration::valid_during(
+                protocol_version,
+                None,
+                Some(8.into()),
+                None,
+                None,
+                ChainIdentifier::MAINNET,
+                0,
+            )
+        };
+        assert_eq!(
+            valid_during(version - 1),
+            Err(UnsupportedExpirationError {
+                protocol_version: version - 1,
+                required: version,
+            })
+        );
+        let expiration = valid_during(version).unwrap();
+
+        let fixture = include_str!("fixtures/wormhole-pyth-transaction");
+        let bytes = Base64::decode_vec(fixture.trim()).unwrap();
+        let mut transaction: Transaction = bcs::from_bytes(&bytes).unwrap();
+        transaction.expiration = expiration;
+        assert_eq!(
+            transaction.to_bcs_for_protocol_version(version - 1),
+            Err(UnsupportedExpirationError {
+                protocol_version: version - 1,
+                required: version,
+            })
+        );
+        let bytes = transaction.to_bcs_for_protocol_version(version).unwrap();
+        assert_eq!(bcs::from_bytes::<Transaction>(&bytes).unwrap(), transaction);
+
+        transaction.expiration = TransactionExpiration::Epoch(8.into());
+        assert!(transaction.to_bcs_for_protocol_version(1).is_ok());
+    }
+
+    #[test_strategy::proptest]
+    fn sender_signed_data(transaction: SignedTransaction) {
+        let data = SenderSignedData::new(transaction.clone());
+        let bytes = bcs::to_bytes(&data).unwrap();
+        assert_eq!(
+            bytes,
+            bcs::to_bytes(&serde_with::ser::SerializeAsWrap::<
+                SignedTransaction,
+                SignedTransactionWithIntentMessage,
+            >::new(&transaction))
+            .unwrap()
+        );
+        let decoded: SenderSignedData = bcs::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(decoded.into_signed_transaction(), Ok(transaction.clone()));
+
+        // The protocol rejects envelopes holding several transactions, but they can be inspected
+        let mut data = data;
+        data.transactions.push(data.transactions[0].clone());
+        let bytes = bcs::to_bytes(&data).unwrap();
+        let error = bcs::from_bytes::<SenderSignedData>(&bytes).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "expected a single signed transaction, found 2"
+        );
+        let decoded = SenderSignedData::from_bcs_unchecked(&bytes).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(
+            decoded.into_signed_transaction(),
+            Err(SenderSignedDataError::TransactionCount(2))
+        );
+
+        // Only the transaction intent is accepted
+        let mut data = SenderSignedData::new(transaction.clone());
+        data.transactions[0].intent_message =
+            IntentMessage::new(Intent::PERSONAL_MESSAGE, transaction.transaction);
+        assert_eq!(
+            data.into_signed_transaction(),
+            Err(SenderSignedDataError::Intent(
+                IntentError::UnexpectedIntent {
+                    expected: Intent::TRANSACTION_DATA,
+                    actual: Intent::PERSONAL_MESSAGE,
+                }
+            ))
+        );
+    }
 }
@@ -0,0 +1,140 @@
+use std::collections::BTreeSet;
+
+use super::InputArgument;
+use super::Transaction;
+use super::TransactionKind;
+use super::TransactionKindTag;
+use crate::types::Address;
+use crate::types::GasCostSummary;
+use crate::types::ObjectId;
+use crate::types::TransactionDigest;
+use crate::types::TransactionEffects;
+
+/// The metadata of an executed transaction which indexers typically query by, as a flat record.
+///
+/// Every field is a scalar, or a list of them, so records map directly to the columns of a
+/// columnar store without keeping the full BCS of the transaction and its effects around.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_derive::Serialize, serde_derive::Deserialize)
+)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(test, derive(test_strategy::Arbitrary))]
+pub struct TransactionSummaryRecord {
+    pub digest: TransactionDigest,
+    pub sender: Address,
+    pub kind: TransactionKindTag,
+    /// The objects the transaction took as input, paid gas with, or changed, sorted and without
+    /// duplicates.
+    #[cfg_attr(test, any(proptest::collection::size_range(0..=5).lift()))]
+    pub touched_objects: Vec<ObjectId>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub computation_cost: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub storage_cost: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub storage_rebate: u64,
+    #[cfg_attr(feature = "serde", serde(with = "crate::_serde::ReadableDisplay"))]
+    #[cfg_attr(feature = "schemars", schemars(with = "crate::_schemars::U64"))]
+    pub non_refundable_storage_fee: u64,
+}
+
+impl TransactionSummaryRecord {
+    /// The record of `transaction`, as executed with `effects`.
+    ///
+    /// The digest is taken from `effects`, which must be the effects of `transaction`.
+    pub fn new(transaction: &Transaction, effects: &TransactionEffects) -> Self {
+        let mut touched_objects: BTreeSet<ObjectId> = effects.object_ids().copied().collect();
+        touched_objects.extend(
+            transaction
+                .gas_payment
+                .objects
+                .iter()
+                .map(|object| *object.object_id()),
+        );
+        if let TransactionKind::ProgrammableTransaction(ptb) = &transaction.kind {
+            touched_objects.extend(ptb.inputs.iter().filter_map(|input| match input {
+                InputArgument::Pure { .. } => None,
+                InputArgument::ImmutableOrOwned(reference)
+                | InputArgument::Receiving(reference) => Some(*reference.object_id()),
+                InputArgument::Shared { object_id, .. } => Some(*object_id),
+            }));
+        }
+
+        let GasCostSummary {
+            computation_cost,
+            storage_cost,
+            storage_rebate,
+            non_refundable_storage_fee,
+        } = *effects.gas_used();
+        Self {
+            digest: *effects.transaction_digest(),
+            sender: transaction.sender,
+            kind: transaction.kind.tag(),
+            touched_objects: touched_objects.into_iter().collect(),
+            computation_cost,
+            storage_cost,
+            storage_rebate,
+            non_refundable_storage_fee,
+        }
+    }
+
+    /// The gas used by the transaction, as recorded by its effects.
+    pub fn gas_used(&self) -> GasCostSummary {
+        GasCostSummary::new(
+            self.computation_cost,
+            self.storage_cost,
+            self.storage_rebate,
+            self.non_refundable_storage_fee,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn pyth_wormhole() {
+        use base64ct::Base64;
+        use base64ct::Encoding;
+
+        let transaction: Transaction = bcs::from_bytes(
+            &Base64::decode_vec(include_str!("fixtures/wormhole-pyth-transaction").trim()).unwrap(),
+        )
+        .unwrap();
+        let effects: TransactionEffects = bcs::from_bytes(
+            &Base64::decode_vec(include_str!("../effects/fixtures/pyth-wormhole-v2").trim())
+                .unwrap(),
+        )
+        .unwrap();
+
+        let record = TransactionSummaryRecord::new(&transaction, &effects);
+        assert_eq!(record.digest, *effects.transaction_digest());
+        assert_eq!(record.sender, transaction.sender);
+        assert_eq!(record.kind, TransactionKindTag::ProgrammableTransaction);
+        assert_eq!(record.kind.to_string(), "programmable_transaction");
+        assert_eq!(&record.gas_used(), effects.gas_used());
+        #[cfg(feature = "json")]
+        assert_eq!(
+            serde_json::to_value(&record).unwrap()["kind"],
+            "programmable_transaction"
+        );
+
+        assert!(record.touched_objects.windows(2).all(|w| w[0] < w[1]));
+        for object in &transaction.gas_payment.objects {
+            assert!(record.touched_objects.contains(object.object_id()));
+        }
+        for object_id in effects.object_ids() {
+            assert!(record.touched_objects.contains(object_id));
+        }
+    }
+}
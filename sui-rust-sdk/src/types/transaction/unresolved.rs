@@ -1,7 +1,9 @@
-use crate::types::object::Version;
 use crate::types::Address;
+use crate::types::ChainIdentifier;
+use crate::types::ChainMismatchError;
 use crate::types::ObjectDigest;
 use crate::types::ObjectId;
+use crate::types::Version;
 
 use super::Command;
 use super::TransactionExpiration;
@@ -17,6 +19,24 @@ pub struct UnresolvedTransaction {
     pub sender: Address,
     pub gas_payment: Option<UnresolvedGasPayment>,
     pub expiration: TransactionExpiration,
+    /// The chain the transaction is built for, if bound to one. Resolvers refuse to resolve it
+    /// against another chain, see [`UnresolvedTransaction::verify_chain`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub chain: Option<ChainIdentifier>,
+}
+
+impl UnresolvedTransaction {
+    /// Check that the transaction can be resolved against `chain`: that it is bound to it, or
+    /// to no chain at all.
+    pub fn verify_chain(&self, chain: ChainIdentifier) -> Result<(), ChainMismatchError> {
+        match self.chain {
+            Some(bound) => chain.verify(bound),
+            None => Ok(()),
+        }
+    }
 }
 
 #[cfg_attr(
@@ -0,0 +1,286 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+
+use super::parse;
+use super::Identifier;
+use super::StructTag;
+use super::TypeParseError;
+use super::TypeTag;
+
+/// A pool of interned [`Identifier`]s and [`StructTag`]s.
+///
+/// Identifiers handed out by a pool share the allocation of every equal identifier previously
+/// interned into it, so pipelines which decode the same handful of type tags millions of times
+/// keep a single copy of each string around instead of re-allocating it for every value.
+///
+/// Cloning a pool is cheap and yields a handle to the same pool. Use [`TypeTagPool::global`] for
+/// a process-wide pool, or [`TypeTagPool::new`] for one whose lifetime is bounded, e.g. by a
+/// single ingestion job.
+///
+/// ```
+/// use sui_sdk::types::TypeTagPool;
+///
+/// let pool = TypeTagPool::new();
+/// let a = pool.identifier("coin").unwrap();
+/// let b = pool.identifier("coin").unwrap();
+/// assert!(std::ptr::eq(a.as_str(), b.as_str()));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypeTagPool {
+    inner: Arc<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    identifiers: Mutex<HashSet<Arc<str>>>,
+    struct_tags: Mutex<HashSet<Arc<StructTag>>>,
+}
+
+/// Lock `mutex`, ignoring poisoning: the sets are never left half-updated by a panic.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl TypeTagPool {
+    /// Create a new, empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The process-wide pool.
+    ///
+    /// Nothing is ever removed from it unless [`TypeTagPool::clear`] is called, so prefer a
+    /// dedicated pool when the set of type tags seen is unbounded.
+    pub fn global() -> &'static Self {
+        static GLOBAL: OnceLock<TypeTagPool> = OnceLock::new();
+        GLOBAL.get_or_init(Self::new)
+    }
+
+    /// Parse `identifier`, sharing its allocation with any equal identifier already in the pool.
+    ///
+    /// Identifiers already in the pool are not validated again, so this is also cheaper than
+    /// [`Identifier::new`] for repeated identifiers.
+    pub fn identifier(&self, identifier: &str) -> Result<Identifier, TypeParseError> {
+        let mut identifiers = lock(&self.inner.identifiers);
+        if let Some(interned) = identifiers.get(identifier) {
+            return Ok(Identifier(interned.clone()));
+        }
+
+        parse::parse_identifier(identifier).map_err(|_| TypeParseError)?;
+        let interned: Arc<str> = identifier.into();
+        identifiers.insert(interned.clone());
+        Ok(Identifier(interned))
+    }
+
+    /// Intern `identifier`, returning an equal identifier sharing the pool's allocation.
+    pub fn intern_identifier(&self, identifier: &Identifier) -> Identifier {
+        let mut identifiers = lock(&self.inner.identifiers);
+        if let Some(interned) = identifiers.get(identifier.as_str()) {
+            return Identifier(interned.clone());
+        }
+
+        identifiers.insert(identifier.0.clone());
+        identifier.clone()
+    }
+
+    /// Intern `type_tag`, returning an equal type tag whose identifiers, including those of its
+    /// type parameters, are shared with the pool.
+    pub fn intern_type_tag(&self, type_tag: &TypeTag) -> TypeTag {
+        match type_tag {
+            TypeTag::Vector(type_tag) => TypeTag::Vector(Box::new(self.intern_type_tag(type_tag))),
+            TypeTag::Struct(struct_tag) => {
+                TypeTag::Struct(Box::new(self.rebuild_struct_tag(struct_tag)))
+            }
+            primitive => primitive.clone(),
+        }
+    }
+
+    /// Intern `struct_tag`, returning a shared handle to the pool's copy of it.
+    ///
+    /// Callers keeping many copies of the same struct tag, e.g. as the type of every object or
+    /// event they index, can hold on to the returned `Arc` instead.
+    pub fn intern_struct_tag(&self, struct_tag: &StructTag) -> Arc<StructTag> {
+        if let Some(interned) = lock(&self.inner.struct_tags).get(struct_tag) {
+            return interned.clone();
+        }
+
+        // Rebuild the tag before taking the lock again, as interning its identifiers locks the
+        // identifier set.
+        let interned = Arc::new(self.rebuild_struct_tag(struct_tag));
+        let mut struct_tags = lock(&self.inner.struct_tags);
+        // Another thread may have interned the same tag in the meantime.
+        if let Some(existing) = struct_tags.get(struct_tag) {
+            return existing.clone();
+        }
+        struct_tags.insert(interned.clone());
+        interned
+    }
+
+    fn rebuild_struct_tag(&self, struct_tag: &StructTag) -> StructTag {
+        StructTag {
+            address: struct_tag.address,
+            module: self.intern_identifier(&struct_tag.module),
+            name: self.intern_identifier(&struct_tag.name),
+            type_params: struct_tag
+                .type_params
+                .iter()
+                .map(|type_tag| self.intern_type_tag(type_tag))
+                .collect(),
+        }
+    }
+
+    /// The number of distinct identifiers in the pool.
+    pub fn len(&self) -> usize {
+        lock(&self.inner.identifiers).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove everything from the pool.
+    ///
+    /// Values handed out before remain valid; they simply stop being shared with those handed out
+    /// after.
+    pub fn clear(&self) {
+        lock(&self.inner.identifiers).clear();
+        lock(&self.inner.struct_tags).clear();
+    }
+
+    /// Run `f` with this pool as the current thread's decoding pool.
+    ///
+    /// Every [`Identifier`] deserialized on this thread while `f` runs, including those nested in
+    /// the [`StructTag`]s of events, objects or transactions, is interned into this pool.
+    ///
+    /// ```
+    /// use sui_sdk::types::StructTag;
+    /// use sui_sdk::types::TypeTagPool;
+    ///
+    /// let bytes = bcs::to_bytes(&StructTag::gas_coin()).unwrap();
+    /// let pool = TypeTagPool::new();
+    /// let (a, b): (StructTag, StructTag) = pool.scope(|| {
+    ///     (bcs::from_bytes(&bytes).unwrap(), bcs::from_bytes(&bytes).unwrap())
+    /// });
+    /// assert!(std::ptr::eq(a.name.as_str(), b.name.as_str()));
+    /// ```
+    #[cfg(feature = "serde")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "serde")))]
+    pub fn scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Restore(Option<TypeTagPool>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                CURRENT.with(|current| *current.borrow_mut() = self.0.take());
+            }
+        }
+
+        let _restore = Restore(CURRENT.with(|current| current.replace(Some(self.clone()))));
+        f()
+    }
+}
+
+#[cfg(feature = "serde")]
+thread_local! {
+    static CURRENT: std::cell::RefCell<Option<TypeTagPool>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Parse `identifier`, interning it into the current thread's decoding pool, if any.
+#[cfg(feature = "serde")]
+pub(super) fn decode_identifier(identifier: &str) -> Result<Identifier, TypeParseError> {
+    CURRENT.with(|current| match &*current.borrow() {
+        Some(pool) => pool.identifier(identifier),
+        None => identifier.parse(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    fn shared(a: &Identifier, b: &Identifier) -> bool {
+        std::ptr::eq(a.as_str(), b.as_str())
+    }
+
+    #[test]
+    fn identifiers() {
+        let pool = TypeTagPool::new();
+        let a = pool.identifier("coin").unwrap();
+        let b = pool.identifier("coin").unwrap();
+        let c = pool.intern_identifier(&Identifier::new("coin").unwrap());
+        assert!(shared(&a, &b));
+        assert!(shared(&a, &c));
+        assert_eq!(a, Identifier::new("coin").unwrap());
+        assert_eq!(pool.len(), 1);
+
+        assert_eq!(pool.identifier("0coin"), Err(TypeParseError));
+        assert_eq!(pool.len(), 1);
+
+        // Pools don't share with each other.
+        let other = TypeTagPool::new().identifier("coin").unwrap();
+        assert!(!shared(&a, &other));
+
+        pool.clear();
+        assert!(pool.is_empty());
+        assert!(!shared(&a, &pool.identifier("coin").unwrap()));
+    }
+
+    #[test]
+    fn struct_tags() {
+        let pool = TypeTagPool::new();
+        let a = pool.intern_struct_tag(&StructTag::gas_coin());
+        let b = pool.intern_struct_tag(&StructTag::gas_coin());
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(*a, StructTag::gas_coin());
+
+        let staked = pool.intern_type_tag(&TypeTag::Vector(Box::new(TypeTag::Struct(Box::new(
+            StructTag::coin(TypeTag::Struct(Box::new(StructTag::staked_sui()))),
+        )))));
+        let TypeTag::Vector(inner) = staked else {
+            panic!("expected a vector");
+        };
+        let TypeTag::Struct(coin) = *inner else {
+            panic!("expected a struct");
+        };
+        assert!(shared(&coin.module, &a.module));
+        assert!(shared(&coin.name, &a.name));
+        // coin, Coin, sui, SUI, staking_pool and StakedSui
+        assert_eq!(pool.len(), 6);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn decoding_scope() {
+        let bytes = bcs::to_bytes(&StructTag::gas_coin()).unwrap();
+        let decode = || bcs::from_bytes::<StructTag>(&bytes).unwrap();
+
+        let pool = TypeTagPool::new();
+        let (a, b) = pool.scope(|| {
+            let a = decode();
+            // Nested scopes take over until they end.
+            let nested = TypeTagPool::new();
+            let n = nested.scope(decode);
+            assert!(!shared(&a.name, &n.name));
+            assert_eq!(nested.len(), 4);
+            (a, decode())
+        });
+        assert!(shared(&a.module, &b.module));
+        assert!(shared(&a.name, &b.name));
+        assert_eq!(pool.len(), 4);
+
+        let c = decode();
+        assert_eq!(a, c);
+        assert!(!shared(&a.name, &c.name));
+        assert_eq!(pool.len(), 4);
+
+        assert!(pool
+            .scope(|| bcs::from_bytes::<Identifier>(&bcs::to_bytes("0coin").unwrap()))
+            .is_err());
+    }
+}
@@ -1,3 +1,4 @@
+mod intern;
 mod parse;
 
 #[cfg(feature = "serde")]
@@ -6,6 +7,8 @@ mod serialization;
 
 use super::Address;
 
+pub use intern::TypeTagPool;
+
 #[derive(Eq, PartialEq, PartialOrd, Ord, Debug, Clone, Hash)]
 #[cfg_attr(test, derive(test_strategy::Arbitrary))]
 pub enum TypeTag {
@@ -73,7 +76,7 @@ pub struct Identifier(
             Into::into
         ))
     )]
-    Box<str>,
+    std::sync::Arc<str>,
 );
 
 impl Identifier {
@@ -84,7 +87,7 @@ impl Identifier {
     }
 
     pub fn into_inner(self) -> Box<str> {
-        self.0
+        self.0.as_ref().into()
     }
 
     pub fn as_str(&self) -> &str {
@@ -21,7 +21,24 @@ impl<'de> Deserialize<'de> for Identifier {
     where
         D: Deserializer<'de>,
     {
-        serde_with::DisplayFromStr::deserialize_as(deserializer)
+        struct IdentifierVisitor;
+
+        impl Visitor<'_> for IdentifierVisitor {
+            type Value = Identifier;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a Move identifier")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                super::intern::decode_identifier(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(IdentifierVisitor)
     }
 }
 
@@ -138,17 +155,19 @@ impl<'de> Deserialize<'de> for TypeTag {
         if deserializer.is_human_readable() {
             serde_with::DisplayFromStr::deserialize_as(deserializer)
         } else {
-            let variants = &[
-                "bool", "u8", "u64", "u128", "address", "signer", "vector", "struct", "u16", "u32",
-                "u256",
-            ];
-            deserializer.deserialize_enum("TypeTag", variants, TypeTagVisitor)
+            deserializer.deserialize_enum("TypeTag", TypeTagVisitor::VARIANTS, TypeTagVisitor)
         }
     }
 }
 
 struct TypeTagVisitor;
 
+impl TypeTagVisitor {
+    const VARIANTS: &'static [&'static str] = &[
+        "bool", "u8", "u64", "u128", "address", "signer", "vector", "struct", "u16", "u32", "u256",
+    ];
+}
+
 impl<'de> Visitor<'de> for TypeTagVisitor {
     type Value = TypeTag;
 
@@ -162,7 +181,8 @@ impl<'de> Visitor<'de> for TypeTagVisitor {
     {
         use serde::de::VariantAccess;
 
-        let (variant, deserializer) = data.variant::<u32>()?;
+        let (variant, deserializer) =
+            data.variant_seed(crate::_serde::VariantIndex(Self::VARIANTS))?;
         match SerializedTypeTagVariant::new(variant)
             .map_err(|e| serde::de::Error::custom(format!("unknown variant {e}")))?
         {
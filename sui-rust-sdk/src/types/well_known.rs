@@ -0,0 +1,62 @@
+//! Addresses of the system packages and ids of the system objects created at genesis.
+
+use super::Address;
+use super::ObjectId;
+
+/// The `0x1` Move standard library package.
+pub const MOVE_STDLIB_ADDRESS: Address = Address::from_static_hex("0x1");
+
+/// The `0x2` Sui framework package.
+pub const SUI_FRAMEWORK_ADDRESS: Address = Address::TWO;
+
+/// The `0x3` Sui system package.
+pub const SUI_SYSTEM_ADDRESS: Address = Address::THREE;
+
+/// The `0xb` Sui Bridge package.
+pub const BRIDGE_ADDRESS: Address = Address::from_static_hex("0xb");
+
+/// The `0xdee9` DeepBook package.
+pub const DEEPBOOK_ADDRESS: Address = Address::from_static_hex("0xdee9");
+
+/// The `0x3::sui_system::SuiSystemState` shared object.
+pub const SUI_SYSTEM_STATE_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x5");
+
+/// The `0x2::clock::Clock` shared object, which can only be used by immutable reference.
+pub const CLOCK_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x6");
+
+/// The `0x2::authenticator_state::AuthenticatorState` shared object.
+pub const AUTHENTICATOR_STATE_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x7");
+
+/// The `0x2::random::Random` shared object, which can only be used by immutable reference.
+pub const RANDOM_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x8");
+
+/// The `0xb::bridge::Bridge` shared object.
+pub const BRIDGE_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x9");
+
+/// The `0x2::deny_list::DenyList` shared object.
+pub const DENY_LIST_OBJECT_ID: ObjectId = ObjectId::from_static_hex("0x403");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test as test;
+
+    #[test]
+    fn system_object_ids() {
+        assert_eq!(
+            CLOCK_OBJECT_ID.to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000006"
+        );
+        assert_eq!(
+            DENY_LIST_OBJECT_ID.to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000403"
+        );
+        assert_eq!(
+            SUI_SYSTEM_STATE_OBJECT_ID,
+            "0x5".parse::<Address>().unwrap().into()
+        );
+        assert_eq!(BRIDGE_ADDRESS, crate::types::BRIDGE_PACKAGE_ADDRESS);
+    }
+}
@@ -2383,6 +2383,9 @@ impl ProtocolConfig {
                 .reject_mutable_random_on_entry_functions(),
             bytecode_version: self.move_binary_format_version(),
             max_variants_in_enum: self.max_move_enum_variants_as_option(),
+            max_struct_definitions: None,
+            max_enum_definitions: None,
+            max_fields_in_variant: None,
         }
     }
 
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use invalid_mutations::{
+    abilities::ability_mutants,
     bounds::{
         ApplyCodeUnitBoundsContext, ApplyOutOfBoundsContext, CodeUnitBoundsMutation,
         OutOfBoundsMutation,
@@ -31,6 +32,18 @@ proptest! {
         prop_assert!(ability_field_requirements::verify_module(&module).is_ok());
     }
 
+    #[test]
+    fn invalid_ability_mutants(module in CompiledModule::valid_strategy(20)) {
+        for mutant in ability_mutants(&module) {
+            prop_assert_eq!(
+                ability_field_requirements::verify_module(&mutant.module).map_err(|e| e.major_status()),
+                Err(mutant.expected_status),
+                "{:?}",
+                mutant.mutation
+            );
+        }
+    }
+
     #[test]
     fn valid_bounds(_module in CompiledModule::valid_strategy(20)) {
         // valid_strategy will panic if there are any bounds check issues.
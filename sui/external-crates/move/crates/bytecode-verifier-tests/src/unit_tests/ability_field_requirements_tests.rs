@@ -0,0 +1,200 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::ability_field_requirements;
+use move_core_types::{identifier::Identifier, vm_status::StatusCode};
+
+fn phantom_param() -> DatatypeTyParameter {
+    DatatypeTyParameter {
+        constraints: AbilitySet::EMPTY,
+        is_phantom: true,
+    }
+}
+
+/// module <SELF> {
+///     struct Box<phantom T> has copy, drop { f: u64 }
+///     struct S<phantom T> has copy { f: <field> }
+/// }
+fn phantom_module(field: SignatureToken) -> CompiledModule {
+    let mut m = empty_module();
+    let first_ident = m.identifiers.len() as TableIndex;
+    m.identifiers.extend([
+        Identifier::new("Box").unwrap(),
+        Identifier::new("S").unwrap(),
+        Identifier::new("f").unwrap(),
+    ]);
+    m.datatype_handles = vec![
+        DatatypeHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(first_ident),
+            abilities: AbilitySet::EMPTY | Ability::Copy | Ability::Drop,
+            type_parameters: vec![phantom_param()],
+        },
+        DatatypeHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(first_ident + 1),
+            abilities: AbilitySet::EMPTY | Ability::Copy,
+            type_parameters: vec![phantom_param()],
+        },
+    ];
+    m.struct_defs = vec![
+        StructDefinition {
+            struct_handle: DatatypeHandleIndex(0),
+            field_information: StructFieldInformation::Declared(vec![FieldDefinition {
+                name: IdentifierIndex(first_ident + 2),
+                signature: TypeSignature(SignatureToken::U64),
+            }]),
+        },
+        StructDefinition {
+            struct_handle: DatatypeHandleIndex(1),
+            field_information: StructFieldInformation::Declared(vec![FieldDefinition {
+                name: IdentifierIndex(first_ident + 2),
+                signature: TypeSignature(field),
+            }]),
+        },
+    ];
+    m
+}
+
+#[test]
+fn phantom_parameter_in_phantom_position() {
+    let m = phantom_module(SignatureToken::DatatypeInstantiation(Box::new((
+        DatatypeHandleIndex(0),
+        vec![SignatureToken::TypeParameter(0)],
+    ))));
+    ability_field_requirements::verify_module(&m).unwrap();
+}
+
+#[test]
+fn phantom_parameter_in_non_phantom_position() {
+    let m = phantom_module(SignatureToken::Vector(Box::new(
+        SignatureToken::TypeParameter(0),
+    )));
+    assert_eq!(
+        ability_field_requirements::verify_module(&m)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::FIELD_MISSING_TYPE_ABILITY
+    );
+}
+
+#[test]
+fn instantiation_constraints() {
+    let mut m = phantom_module(SignatureToken::U64);
+    m.datatype_handles[1]
+        .type_parameters
+        .push(DatatypeTyParameter {
+            constraints: AbilitySet::EMPTY | Ability::Drop,
+            is_phantom: false,
+        });
+
+    let copy = AbilitySet::EMPTY | Ability::Copy;
+    assert_eq!(
+        ability_field_requirements::instantiation_constraints(&m, DatatypeHandleIndex(1), copy),
+        Some(vec![
+            AbilitySet::EMPTY,
+            AbilitySet::EMPTY | Ability::Copy | Ability::Drop
+        ])
+    );
+    assert_eq!(
+        ability_field_requirements::instantiation_constraints(
+            &m,
+            DatatypeHandleIndex(1),
+            AbilitySet::EMPTY | Ability::Key
+        ),
+        None
+    );
+}
+
+/// module 0x0::A { struct X has drop { f: u64 } }
+/// module 0x0::B { struct S has <abilities> { x: A::X } }
+/// where B's handle for `A::X` claims `copy, drop`.
+fn dependent_modules(abilities: AbilitySet) -> (CompiledModule, CompiledModule) {
+    let mut a = empty_module();
+    a.identifiers = vec![
+        Identifier::new("A").unwrap(),
+        Identifier::new("X").unwrap(),
+        Identifier::new("f").unwrap(),
+    ];
+    a.datatype_handles = vec![DatatypeHandle {
+        module: ModuleHandleIndex(0),
+        name: IdentifierIndex(1),
+        abilities: AbilitySet::EMPTY | Ability::Drop,
+        type_parameters: vec![],
+    }];
+    a.struct_defs = vec![StructDefinition {
+        struct_handle: DatatypeHandleIndex(0),
+        field_information: StructFieldInformation::Declared(vec![FieldDefinition {
+            name: IdentifierIndex(2),
+            signature: TypeSignature(SignatureToken::U64),
+        }]),
+    }];
+
+    let mut b = empty_module();
+    b.identifiers = vec![
+        Identifier::new("B").unwrap(),
+        Identifier::new("A").unwrap(),
+        Identifier::new("X").unwrap(),
+        Identifier::new("S").unwrap(),
+        Identifier::new("x").unwrap(),
+    ];
+    b.module_handles.push(ModuleHandle {
+        address: AddressIdentifierIndex(0),
+        name: IdentifierIndex(1),
+    });
+    b.datatype_handles = vec![
+        DatatypeHandle {
+            module: ModuleHandleIndex(1),
+            name: IdentifierIndex(2),
+            abilities: AbilitySet::EMPTY | Ability::Copy | Ability::Drop,
+            type_parameters: vec![],
+        },
+        DatatypeHandle {
+            module: ModuleHandleIndex(0),
+            name: IdentifierIndex(3),
+            abilities,
+            type_parameters: vec![],
+        },
+    ];
+    b.struct_defs = vec![StructDefinition {
+        struct_handle: DatatypeHandleIndex(1),
+        field_information: StructFieldInformation::Declared(vec![FieldDefinition {
+            name: IdentifierIndex(4),
+            signature: TypeSignature(SignatureToken::Datatype(DatatypeHandleIndex(0))),
+        }]),
+    }];
+    (a, b)
+}
+
+#[test]
+fn imported_handle_abilities_not_in_definition() {
+    let (a, b) = dependent_modules(AbilitySet::EMPTY | Ability::Copy);
+    // The module-local pass trusts the imported handle's abilities.
+    ability_field_requirements::verify_module(&b).unwrap();
+    assert_eq!(
+        ability_field_requirements::verify_module_with_dependencies(&b, [&a])
+            .unwrap_err()
+            .major_status(),
+        StatusCode::TYPE_MISMATCH
+    );
+}
+
+#[test]
+fn imported_handle_missing_dependency() {
+    let (_, b) = dependent_modules(AbilitySet::EMPTY | Ability::Drop);
+    assert_eq!(
+        ability_field_requirements::verify_module_with_dependencies(&b, [])
+            .unwrap_err()
+            .major_status(),
+        StatusCode::MISSING_DEPENDENCY
+    );
+}
+
+#[test]
+fn imported_handle_consistent_with_definition() {
+    let (mut a, mut b) = dependent_modules(AbilitySet::EMPTY | Ability::Drop);
+    a.datatype_handles[0].abilities = AbilitySet::EMPTY | Ability::Copy | Ability::Drop;
+    b.datatype_handles[0].abilities = AbilitySet::EMPTY | Ability::Drop;
+    ability_field_requirements::verify_module_with_dependencies(&b, [&a]).unwrap();
+}
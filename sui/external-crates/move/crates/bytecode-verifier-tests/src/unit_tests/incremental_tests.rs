@@ -0,0 +1,94 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::{
+    incremental::{
+        verify_module_incremental_metered, verify_module_incremental_unmetered, ModuleDiff,
+    },
+    verify_module_with_config_metered,
+};
+use move_bytecode_verifier_meter::{bound::BoundMeter, Scope};
+use move_core_types::vm_status::StatusCode;
+use move_vm_config::verifier::{MeterConfig, VerifierConfig};
+
+#[test]
+fn unchanged_module() {
+    let m = basic_test_module();
+    assert_eq!(ModuleDiff::new(&m, &m), ModuleDiff::default());
+    verify_module_incremental_unmetered(&VerifierConfig::default(), &m, &m).unwrap();
+}
+
+#[test]
+fn changed_function_body() {
+    let old = basic_test_module();
+    let mut new = basic_test_module();
+    new.function_defs[0].code.as_mut().unwrap().code = vec![Bytecode::Pop, Bytecode::Ret];
+
+    let diff = ModuleDiff::new(&old, &new);
+    assert!(!diff.datatypes_changed);
+    assert!(!diff.signatures_changed);
+    assert_eq!(
+        diff.changed_functions.into_iter().collect::<Vec<_>>(),
+        vec![FunctionDefinitionIndex(0)]
+    );
+    assert_eq!(
+        verify_module_incremental_unmetered(&VerifierConfig::default(), &old, &new)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::NEGATIVE_STACK_SIZE_WITHIN_BLOCK
+    );
+}
+
+#[test]
+fn changed_datatype() {
+    let old = basic_test_module();
+    let mut new = basic_test_module();
+    new.datatype_handles[0].abilities = AbilitySet::EMPTY | Ability::Copy;
+    new.struct_defs[0].field_information =
+        StructFieldInformation::Declared(vec![FieldDefinition {
+            name: IdentifierIndex(3),
+            signature: TypeSignature(SignatureToken::Signer),
+        }]);
+
+    let diff = ModuleDiff::new(&old, &new);
+    assert!(diff.datatypes_changed);
+    assert!(diff.changed_functions.is_empty());
+    assert_eq!(
+        verify_module_incremental_unmetered(&VerifierConfig::default(), &old, &new)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::FIELD_MISSING_TYPE_ABILITY
+    );
+}
+
+#[test]
+fn module_meter_limit_only_charges_changed_functions() {
+    let m = basic_test_module();
+    let config = VerifierConfig::default();
+    // Units are only counted in the scopes of a `BoundMeter` which have a limit
+    let meter_config = |max_per_mod_meter_units| MeterConfig {
+        max_per_fun_meter_units: Some(u128::MAX),
+        max_per_mod_meter_units: Some(max_per_mod_meter_units),
+        max_per_pkg_meter_units: None,
+    };
+
+    let mut meter = BoundMeter::new(meter_config(u128::MAX));
+    verify_module_with_config_metered(&config, &m, &mut meter).unwrap();
+    let used = meter.get_usage(Scope::Module);
+    assert!(used > 0);
+
+    // A full verification exceeds a module limit below its usage...
+    let mut meter = BoundMeter::new(meter_config(used - 1));
+    assert_eq!(
+        verify_module_with_config_metered(&config, &m, &mut meter)
+            .unwrap_err()
+            .major_status(),
+        StatusCode::CONSTRAINT_NOT_SATISFIED
+    );
+
+    // ...which an incremental one doesn't charge for unchanged functions
+    let mut meter = BoundMeter::new(meter_config(used - 1));
+    verify_module_incremental_metered(&config, &m, &m, &mut meter).unwrap();
+    assert_eq!(meter.get_usage(Scope::Module), 0);
+}
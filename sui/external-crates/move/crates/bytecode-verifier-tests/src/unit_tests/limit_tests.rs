@@ -823,3 +823,106 @@ fn leaf_module(name: &str) -> CompiledModule {
     module.address_identifiers[0] = AccountAddress::ONE;
     module
 }
+
+fn multi_enum(module: &mut CompiledModule, count: usize, variant_fields: usize) {
+    for i in 0..count {
+        module
+            .identifiers
+            .push(Identifier::new(format!("E_{}", i)).unwrap());
+        module.datatype_handles.push(DatatypeHandle {
+            module: module.self_module_handle_idx,
+            name: IdentifierIndex((module.identifiers.len() - 1) as u16),
+            abilities: AbilitySet::EMPTY,
+            type_parameters: vec![],
+        });
+        let fields = (0..variant_fields)
+            .map(|_| FieldDefinition {
+                name: Default::default(),
+                signature: TypeSignature(SignatureToken::U8),
+            })
+            .collect();
+        module.enum_defs.push(EnumDefinition {
+            enum_handle: DatatypeHandleIndex((module.datatype_handles.len() - 1) as u16),
+            variants: vec![VariantDefinition {
+                variant_name: Default::default(),
+                fields,
+            }],
+        });
+    }
+}
+
+#[test]
+fn max_datatype_kinds_test() {
+    const MAX_ENUMS: usize = 10;
+    const MAX_VARIANT_FIELDS: usize = 5;
+    let config = VerifierConfig {
+        max_struct_definitions: Some(MAX_STRUCTS),
+        max_enum_definitions: Some(MAX_ENUMS),
+        max_fields_in_variant: Some(MAX_VARIANT_FIELDS),
+        ..Default::default()
+    };
+
+    let mut module = leaf_module("M");
+    multi_struct(&mut module, MAX_STRUCTS);
+    multi_enum(&mut module, MAX_ENUMS, MAX_VARIANT_FIELDS);
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(res, Ok(()));
+
+    let mut module = leaf_module("M");
+    multi_struct(&mut module, MAX_STRUCTS + 1);
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(
+        res.unwrap_err().major_status(),
+        StatusCode::MAX_STRUCT_ONLY_DEFINITIONS_REACHED,
+    );
+
+    let mut module = leaf_module("M");
+    multi_enum(&mut module, MAX_ENUMS + 1, 0);
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(
+        res.unwrap_err().major_status(),
+        StatusCode::MAX_ENUM_DEFINITIONS_REACHED,
+    );
+
+    let mut module = leaf_module("M");
+    multi_enum(&mut module, 1, MAX_VARIANT_FIELDS + 1);
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(
+        res.unwrap_err().major_status(),
+        StatusCode::MAX_VARIANT_FIELDS_REACHED,
+    );
+}
+
+#[test]
+fn max_variants_in_enum_needs_max_fields_in_struct() {
+    const MAX_VARIANTS: u64 = 3;
+
+    let mut module = leaf_module("M");
+    multi_enum(&mut module, 1, 0);
+    module.enum_defs[0].variants = (0..=MAX_VARIANTS)
+        .map(|_| VariantDefinition {
+            variant_name: Default::default(),
+            fields: vec![],
+        })
+        .collect();
+
+    // The variant count is only checked along with the field counts
+    let config = VerifierConfig {
+        max_fields_in_struct: None,
+        max_variants_in_enum: Some(MAX_VARIANTS),
+        ..Default::default()
+    };
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(res, Ok(()));
+
+    let config = VerifierConfig {
+        max_fields_in_struct: Some(MAX_FIELDS),
+        max_variants_in_enum: Some(MAX_VARIANTS),
+        ..Default::default()
+    };
+    let res = LimitsVerifier::verify_module(&config, &module);
+    assert_eq!(
+        res.unwrap_err().major_status(),
+        StatusCode::MAX_VARIANTS_REACHED,
+    );
+}
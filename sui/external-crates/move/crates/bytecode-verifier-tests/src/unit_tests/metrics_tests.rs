@@ -0,0 +1,86 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::{file_format::*, IndexKind};
+use move_bytecode_verifier::metrics::module_metrics;
+use move_core_types::{account_address::AccountAddress, identifier::Identifier};
+
+#[test]
+fn empty_module_metrics() {
+    let metrics = module_metrics(&empty_module());
+    assert_eq!(metrics.instruction_count, 0);
+    assert_eq!(metrics.max_function_instructions, 0);
+    assert_eq!(metrics.max_struct_fields, 0);
+    assert_eq!(metrics.max_enum_variants, 0);
+    assert_eq!(metrics.table_sizes[&IndexKind::ModuleHandle], 1);
+    assert_eq!(metrics.table_sizes[&IndexKind::AddressIdentifier], 1);
+    assert!(metrics.ability_distribution.values().all(|n| *n == 0));
+    assert!(metrics.dependency_fan_out.is_empty());
+}
+
+#[test]
+fn basic_module_metrics() {
+    let mut m = basic_test_module_with_enum();
+    m.function_defs[0].code.as_mut().unwrap().code =
+        vec![Bytecode::LdU64(0), Bytecode::Pop, Bytecode::Ret];
+    m.datatype_handles[0].abilities = AbilitySet::EMPTY | Ability::Copy | Ability::Drop;
+    m.datatype_handles[1].abilities = AbilitySet::EMPTY | Ability::Drop;
+    m.enum_defs[0].variants[0].fields = vec![
+        FieldDefinition {
+            name: IdentifierIndex(0),
+            signature: TypeSignature(SignatureToken::Bool),
+        },
+        FieldDefinition {
+            name: IdentifierIndex(0),
+            signature: TypeSignature(SignatureToken::U8),
+        },
+    ];
+
+    let metrics = module_metrics(&m);
+    assert_eq!(metrics.instruction_count, 3);
+    assert_eq!(metrics.max_function_instructions, 3);
+    assert_eq!(metrics.table_sizes[&IndexKind::FunctionDefinition], 1);
+    assert_eq!(metrics.table_sizes[&IndexKind::EnumDefinition], 1);
+    assert_eq!(metrics.max_struct_fields, 1);
+    assert_eq!(metrics.max_enum_variants, 1);
+    assert_eq!(metrics.max_variant_fields, 2);
+    assert_eq!(metrics.ability_distribution[&Ability::Copy], 1);
+    assert_eq!(metrics.ability_distribution[&Ability::Drop], 2);
+    assert_eq!(metrics.ability_distribution[&Ability::Key], 0);
+}
+
+#[test]
+fn dependency_fan_out() {
+    let mut m = basic_test_module();
+    let dep_name = IdentifierIndex(m.identifiers.len() as TableIndex);
+    m.identifiers.extend([
+        Identifier::new("dep").unwrap(),
+        Identifier::new("Coin").unwrap(),
+        Identifier::new("mint").unwrap(),
+    ]);
+    m.address_identifiers.push(AccountAddress::ONE);
+    m.module_handles.push(ModuleHandle {
+        address: AddressIdentifierIndex(1),
+        name: dep_name,
+    });
+    m.datatype_handles.push(DatatypeHandle {
+        module: ModuleHandleIndex(1),
+        name: IdentifierIndex(dep_name.0 + 1),
+        abilities: AbilitySet::EMPTY,
+        type_parameters: vec![],
+    });
+    m.function_handles.push(FunctionHandle {
+        module: ModuleHandleIndex(1),
+        name: IdentifierIndex(dep_name.0 + 2),
+        parameters: SignatureIndex(0),
+        return_: SignatureIndex(0),
+        type_parameters: vec![],
+    });
+
+    let metrics = module_metrics(&m);
+    let dep = m.module_id_for_handle(&m.module_handles[1]);
+    assert_eq!(metrics.dependency_fan_out.len(), 1);
+    assert_eq!(metrics.dependency_fan_out[&dep], 2);
+    // Handles into the module itself are not dependencies.
+    assert!(!metrics.dependency_fan_out.contains_key(&m.self_id()));
+}
@@ -8,6 +8,7 @@ use move_vm_config::verifier::{
     DEFAULT_MAX_VARIANTS,
 };
 
+pub mod ability_field_requirements_tests;
 pub mod binary_samples;
 pub mod bounds_tests;
 pub mod code_unit_tests;
@@ -15,14 +16,19 @@ pub mod constants_tests;
 pub mod control_flow_tests;
 pub mod duplication_tests;
 pub mod generic_ops_tests;
+pub mod incremental_tests;
 pub mod large_type_test;
 pub mod limit_tests;
 pub mod locals;
 pub mod loop_summary_tests;
 pub mod many_back_edges;
+pub mod metrics_tests;
+pub mod module_graph_tests;
 pub mod negative_stack_size_tests;
 pub mod reference_safety_tests;
 pub mod signature_tests;
+pub mod source_locations_tests;
+pub mod type_display_tests;
 pub mod vec_pack_tests;
 
 /// Configuration used in production.
@@ -52,6 +58,9 @@ pub(crate) fn production_config() -> (VerifierConfig, MeterConfig) {
             reject_mutable_random_on_entry_functions: true,
             bytecode_version: VERSION_MAX,
             max_variants_in_enum: Some(DEFAULT_MAX_VARIANTS),
+            max_struct_definitions: None,
+            max_enum_definitions: None,
+            max_fields_in_variant: None,
         },
         MeterConfig::default(),
     )
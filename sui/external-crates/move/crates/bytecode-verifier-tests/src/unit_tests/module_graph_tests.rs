@@ -0,0 +1,129 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::module_graph::{DependencyKind, ModuleGraph};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    vm_status::StatusCode,
+};
+
+/// module 0x0::<name> {
+///     use 0x0::<dep> for each of `deps`, referring to a `T` datatype of the dependency
+///     friend 0x0::<friend> for each of `friends`
+/// }
+fn module(name: &str, deps: &[&str], friends: &[&str]) -> CompiledModule {
+    let mut m = empty_module();
+    m.identifiers[0] = Identifier::new(name).unwrap();
+    let datatype_name = IdentifierIndex(m.identifiers.len() as TableIndex);
+    m.identifiers.push(Identifier::new("T").unwrap());
+    for dep in deps {
+        m.datatype_handles.push(DatatypeHandle {
+            module: ModuleHandleIndex(m.module_handles.len() as TableIndex),
+            name: datatype_name,
+            abilities: AbilitySet::EMPTY,
+            type_parameters: vec![],
+        });
+        m.module_handles.push(ModuleHandle {
+            address: AddressIdentifierIndex(0),
+            name: IdentifierIndex(m.identifiers.len() as TableIndex),
+        });
+        m.identifiers.push(Identifier::new(*dep).unwrap());
+    }
+    for friend in friends {
+        m.friend_decls.push(ModuleHandle {
+            address: AddressIdentifierIndex(0),
+            name: IdentifierIndex(m.identifiers.len() as TableIndex),
+        });
+        m.identifiers.push(Identifier::new(*friend).unwrap());
+    }
+    m
+}
+
+fn id(name: &str) -> ModuleId {
+    ModuleId::new(AccountAddress::ZERO, Identifier::new(name).unwrap())
+}
+
+fn names(modules: &[&CompiledModule]) -> Vec<String> {
+    modules.iter().map(|m| m.name().to_string()).collect()
+}
+
+#[test]
+fn typed_dependencies() {
+    let a = module("a", &[], &["c"]);
+    let b = module("b", &["a", "ext"], &[]);
+    let c = module("c", &["b"], &[]);
+    let graph = ModuleGraph::new([&a, &b, &c]).unwrap();
+
+    let b_deps = graph.dependencies(&id("b"));
+    assert_eq!(b_deps.len(), 1);
+    assert_eq!(b_deps[0].0, id("a"));
+    assert!(b_deps[0].1.kinds.contains(&DependencyKind::ModuleHandle));
+    assert!(b_deps[0].1.kinds.contains(&DependencyKind::Datatype));
+    assert!(b_deps[0]
+        .1
+        .datatypes
+        .contains(&Identifier::new("T").unwrap()));
+
+    // `c` is a friend of `a`, so it depends on it.
+    let c_deps = graph.dependencies(&id("c"));
+    assert_eq!(c_deps.len(), 2);
+    assert_eq!(c_deps[0].0, id("a"));
+    assert_eq!(
+        c_deps[0].1.kinds.iter().copied().collect::<Vec<_>>(),
+        vec![DependencyKind::Friend]
+    );
+
+    assert!(graph.dependencies(&id("a")).is_empty());
+    assert_eq!(
+        graph
+            .external_dependencies()
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>(),
+        vec![id("ext")]
+    );
+}
+
+#[test]
+fn topological_order() {
+    let a = module("a", &[], &["c"]);
+    let b = module("b", &["a"], &[]);
+    let c = module("c", &["b"], &[]);
+    let d = module("d", &[], &[]);
+    let graph = ModuleGraph::new([&c, &b, &d, &a]).unwrap();
+    assert!(graph.find_cycle().is_none());
+    assert_eq!(
+        names(&graph.topological_order().unwrap()),
+        vec!["d", "a", "b", "c"]
+    );
+}
+
+#[test]
+fn dependency_cycles() {
+    let a = module("a", &["c"], &[]);
+    let b = module("b", &["a"], &[]);
+    let c = module("c", &["b"], &[]);
+    let d = module("d", &[], &[]);
+    let graph = ModuleGraph::new([&a, &b, &c, &d]).unwrap();
+    assert_eq!(graph.find_cycle(), Some(vec![id("a"), id("b"), id("c")]));
+    assert_eq!(
+        graph.topological_order().unwrap_err().major_status(),
+        StatusCode::CYCLIC_MODULE_DEPENDENCY
+    );
+
+    // A friend that is also a dependency of the declaring module forms a cycle.
+    let a = module("a", &["b"], &["b"]);
+    let b = module("b", &[], &[]);
+    let graph = ModuleGraph::new([&a, &b]).unwrap();
+    assert_eq!(graph.find_cycle(), Some(vec![id("a"), id("b")]));
+}
+
+#[test]
+fn duplicate_modules() {
+    let a = module("a", &[], &[]);
+    assert_eq!(
+        ModuleGraph::new([&a, &a]).err().unwrap().major_status(),
+        StatusCode::DUPLICATE_ELEMENT
+    );
+}
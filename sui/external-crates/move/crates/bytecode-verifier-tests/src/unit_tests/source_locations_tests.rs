@@ -0,0 +1,72 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_source_map::source_map::SourceMap;
+use move_bytecode_verifier::source_locations::{
+    line_column, verify_module_with_source_map, LineColumn,
+};
+use move_command_line_common::files::FileHash;
+use move_core_types::{account_address::AccountAddress, vm_status::StatusCode};
+use move_ir_types::{
+    ast::{ModuleIdent, ModuleName},
+    location::Loc,
+};
+use move_vm_config::verifier::VerifierConfig;
+
+const SOURCE: &str =
+    "module 0x0::M {\n    struct Bar has copy {\n        x: u64,\n        s: signer,\n    }\n}\n";
+
+fn loc_of(needle: &str) -> Loc {
+    let start = SOURCE.find(needle).unwrap();
+    Loc::new(
+        FileHash::new(SOURCE),
+        start as u32,
+        (start + needle.len()) as u32,
+    )
+}
+
+#[test]
+fn missing_ability_mapped_to_field() {
+    // struct Bar has copy { x: u64, s: signer }
+    let mut m = basic_test_module();
+    m.datatype_handles[0].abilities = AbilitySet::EMPTY | Ability::Copy;
+    if let StructFieldInformation::Declared(fields) = &mut m.struct_defs[0].field_information {
+        fields.push(FieldDefinition {
+            name: IdentifierIndex(0),
+            signature: TypeSignature(SignatureToken::Signer),
+        });
+    }
+
+    let mut source_map = SourceMap::new(
+        loc_of("module"),
+        ModuleIdent::new(ModuleName::module_self(), AccountAddress::ZERO),
+    );
+    let bar = StructDefinitionIndex(0);
+    source_map
+        .add_top_level_struct_mapping(bar, loc_of("struct Bar"))
+        .unwrap();
+    source_map
+        .add_struct_field_mapping(bar, loc_of("x: u64"))
+        .unwrap();
+    source_map
+        .add_struct_field_mapping(bar, loc_of("s: signer"))
+        .unwrap();
+
+    let located = verify_module_with_source_map(&VerifierConfig::default(), &m, Some(&source_map))
+        .unwrap_err();
+    assert_eq!(
+        located.error.major_status(),
+        StatusCode::FIELD_MISSING_TYPE_ABILITY
+    );
+    let loc = located.loc.unwrap();
+    assert_eq!(loc, loc_of("s: signer"));
+    assert_eq!(
+        line_column(SOURCE, loc.start()),
+        LineColumn { line: 4, column: 9 }
+    );
+
+    let unlocated =
+        verify_module_with_source_map(&VerifierConfig::default(), &m, None).unwrap_err();
+    assert!(unlocated.loc.is_none());
+}
@@ -0,0 +1,96 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::file_format::*;
+use move_bytecode_verifier::type_display::SignatureTokenDisplay;
+use move_core_types::identifier::Identifier;
+
+/// module <SELF> {
+///     struct Bar has copy, drop, store { x: u64 }
+///     native struct Box<T> has copy, drop, store
+/// }
+fn module() -> CompiledModule {
+    let mut m = basic_test_module();
+    m.datatype_handles[0].abilities = AbilitySet::PRIMITIVES;
+    m.datatype_handles.push(DatatypeHandle {
+        module: ModuleHandleIndex(0),
+        name: IdentifierIndex(m.identifiers.len() as TableIndex),
+        abilities: AbilitySet::PRIMITIVES,
+        type_parameters: vec![DatatypeTyParameter {
+            constraints: AbilitySet::EMPTY,
+            is_phantom: false,
+        }],
+    });
+    m.identifiers.push(Identifier::new("Box").unwrap());
+    m
+}
+
+fn display(m: &CompiledModule, token: &SignatureToken) -> String {
+    SignatureTokenDisplay::new(m, token).to_string()
+}
+
+fn display_with_abilities(
+    m: &CompiledModule,
+    token: &SignatureToken,
+    type_parameters: &[AbilitySet],
+) -> String {
+    SignatureTokenDisplay::new(m, token)
+        .with_abilities(type_parameters)
+        .to_string()
+}
+
+#[test]
+fn display_primitive_types() {
+    let m = module();
+    use SignatureToken::*;
+    assert_eq!(display(&m, &U8), "u8");
+    assert_eq!(display(&m, &U256), "u256");
+    assert_eq!(display(&m, &Address), "address");
+    assert_eq!(display(&m, &Vector(Box::new(Bool))), "vector<bool>");
+    assert_eq!(display(&m, &Reference(Box::new(Signer))), "&signer");
+    assert_eq!(
+        display(&m, &MutableReference(Box::new(TypeParameter(1)))),
+        "&mut T1"
+    );
+}
+
+#[test]
+fn display_datatypes() {
+    let m = module();
+    let name = format!("0x{}::{}", m.address().short_str_lossless(), m.name());
+    let bar = SignatureToken::Datatype(DatatypeHandleIndex(0));
+    assert_eq!(display(&m, &bar), format!("{}::Bar", name));
+
+    let inst = SignatureToken::DatatypeInstantiation(Box::new((
+        DatatypeHandleIndex(1),
+        vec![SignatureToken::Vector(Box::new(bar))],
+    )));
+    assert_eq!(
+        display(&m, &inst),
+        format!("{}::Box<vector<{}::Bar>>", name, name)
+    );
+}
+
+#[test]
+fn display_abilities() {
+    let m = module();
+    use SignatureToken::*;
+    assert_eq!(
+        display_with_abilities(&m, &U64, &[]),
+        "u64 has copy, drop, store"
+    );
+    assert_eq!(display_with_abilities(&m, &Signer, &[]), "signer has drop");
+    // The abilities of an instantiation depend on its type arguments.
+    let inst = DatatypeInstantiation(Box::new((DatatypeHandleIndex(1), vec![TypeParameter(0)])));
+    assert!(
+        display_with_abilities(&m, &inst, &[AbilitySet::EMPTY | Ability::Copy])
+            .ends_with("<T0> has copy")
+    );
+    assert!(display_with_abilities(&m, &inst, &[AbilitySet::EMPTY]).ends_with("<T0>"));
+    // Unresolvable type parameters and references are displayed without abilities.
+    assert!(display_with_abilities(&m, &inst, &[]).ends_with("<T0>"));
+    assert_eq!(
+        display_with_abilities(&m, &Reference(Box::new(U8)), &[]),
+        "&u8"
+    );
+}
@@ -0,0 +1,186 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! Targeted mutations that break the ability requirements of otherwise valid modules.
+//!
+//! Unlike the other mutations in this crate, these are not driven by proptest indices: given a
+//! module, `ability_mutants` systematically generates every mutant it knows how to derive from it,
+//! each paired with the status code the ability field requirements pass is expected to report.
+
+use move_binary_format::file_format::{
+    Ability, AbilitySet, CompiledModule, DatatypeHandleIndex, FieldDefinition, IdentifierIndex,
+    SignatureToken, StructFieldInformation, TableIndex, TypeSignature,
+};
+use move_core_types::{identifier::Identifier, vm_status::StatusCode};
+
+/// The kind of mutation applied to a module.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AbilityMutation {
+    /// Remove an ability required by a struct from the datatype handle of one of its fields.
+    DropFieldTypeAbility {
+        struct_def: TableIndex,
+        field: TableIndex,
+        ability: Ability,
+    },
+    /// Add a `signer` field, which has neither `copy` nor `store`, to a struct declared with
+    /// `copy` or `store`.
+    AddNonCopyableField { struct_def: TableIndex },
+    /// Replace the first field of a variant of an enum declared with `copy` or `store` with a
+    /// `signer`, adding the field if the variant has none.
+    CorruptVariantField {
+        enum_def: TableIndex,
+        variant: TableIndex,
+    },
+}
+
+/// A module derived from a valid module by applying a single `AbilityMutation`.
+#[derive(Clone, Debug)]
+pub struct AbilityMutant {
+    pub mutation: AbilityMutation,
+    pub module: CompiledModule,
+    /// The status code expected from the ability field requirements pass on `module`.
+    pub expected_status: StatusCode,
+}
+
+/// Generates all ability mutants of `module`, which is expected to pass the ability field
+/// requirements check.
+pub fn ability_mutants(module: &CompiledModule) -> Vec<AbilityMutant> {
+    let mut mutants = vec![];
+    mutants.extend(drop_field_type_ability(module));
+    mutants.extend(add_non_copyable_field(module));
+    mutants.extend(corrupt_variant_field(module));
+    mutants
+}
+
+/// The abilities every field of a datatype with `abilities` must have.
+fn required_field_abilities(abilities: AbilitySet) -> AbilitySet {
+    abilities
+        .into_iter()
+        .map(|a| a.requires())
+        .fold(AbilitySet::EMPTY, |acc, required| acc | required)
+}
+
+/// Whether a `signer` field violates the requirements of a datatype with `abilities`.
+fn rejects_signer(abilities: AbilitySet) -> bool {
+    !required_field_abilities(abilities).is_subset(AbilitySet::SIGNER)
+}
+
+fn field_datatype(token: &SignatureToken) -> Option<DatatypeHandleIndex> {
+    match token {
+        SignatureToken::Datatype(idx) => Some(*idx),
+        SignatureToken::DatatypeInstantiation(inst) => Some(inst.0),
+        _ => None,
+    }
+}
+
+fn drop_field_type_ability(module: &CompiledModule) -> Vec<AbilityMutant> {
+    let mut mutants = vec![];
+    for (s_idx, struct_def) in module.struct_defs.iter().enumerate() {
+        let fields = match &struct_def.field_information {
+            StructFieldInformation::Native => continue,
+            StructFieldInformation::Declared(fields) => fields,
+        };
+        let required = required_field_abilities(
+            module.datatype_handles[struct_def.struct_handle.0 as usize].abilities,
+        );
+        for (f_idx, field) in fields.iter().enumerate() {
+            let field_handle = match field_datatype(&field.signature.0) {
+                Some(field_handle) => field_handle,
+                None => continue,
+            };
+            for ability in required {
+                let mut mutated = module.clone();
+                let handle = &mut mutated.datatype_handles[field_handle.0 as usize];
+                if !handle.abilities.has_ability(ability) {
+                    continue;
+                }
+                handle.abilities = handle.abilities.remove(ability);
+                mutants.push(AbilityMutant {
+                    mutation: AbilityMutation::DropFieldTypeAbility {
+                        struct_def: s_idx as TableIndex,
+                        field: f_idx as TableIndex,
+                        ability,
+                    },
+                    module: mutated,
+                    expected_status: StatusCode::FIELD_MISSING_TYPE_ABILITY,
+                });
+            }
+        }
+    }
+    mutants
+}
+
+fn add_non_copyable_field(module: &CompiledModule) -> Vec<AbilityMutant> {
+    let mut mutants = vec![];
+    for (s_idx, struct_def) in module.struct_defs.iter().enumerate() {
+        let abilities = module.datatype_handles[struct_def.struct_handle.0 as usize].abilities;
+        if !rejects_signer(abilities) {
+            continue;
+        }
+        let mut mutated = module.clone();
+        let name = fresh_identifier(&mut mutated);
+        match &mut mutated.struct_defs[s_idx].field_information {
+            StructFieldInformation::Native => continue,
+            StructFieldInformation::Declared(fields) => fields.push(FieldDefinition {
+                name,
+                signature: TypeSignature(SignatureToken::Signer),
+            }),
+        }
+        mutants.push(AbilityMutant {
+            mutation: AbilityMutation::AddNonCopyableField {
+                struct_def: s_idx as TableIndex,
+            },
+            module: mutated,
+            expected_status: StatusCode::FIELD_MISSING_TYPE_ABILITY,
+        });
+    }
+    mutants
+}
+
+fn corrupt_variant_field(module: &CompiledModule) -> Vec<AbilityMutant> {
+    let mut mutants = vec![];
+    for (e_idx, enum_def) in module.enum_defs.iter().enumerate() {
+        let abilities = module.datatype_handles[enum_def.enum_handle.0 as usize].abilities;
+        if !rejects_signer(abilities) {
+            continue;
+        }
+        for v_idx in 0..enum_def.variants.len() {
+            let mut mutated = module.clone();
+            let signer = TypeSignature(SignatureToken::Signer);
+            if enum_def.variants[v_idx].fields.is_empty() {
+                let name = fresh_identifier(&mut mutated);
+                mutated.enum_defs[e_idx].variants[v_idx]
+                    .fields
+                    .push(FieldDefinition {
+                        name,
+                        signature: signer,
+                    });
+            } else {
+                mutated.enum_defs[e_idx].variants[v_idx].fields[0].signature = signer;
+            }
+            mutants.push(AbilityMutant {
+                mutation: AbilityMutation::CorruptVariantField {
+                    enum_def: e_idx as TableIndex,
+                    variant: v_idx as TableIndex,
+                },
+                module: mutated,
+                expected_status: StatusCode::FIELD_MISSING_TYPE_ABILITY,
+            });
+        }
+    }
+    mutants
+}
+
+/// Adds an identifier that does not already exist in `module` and returns its index.
+fn fresh_identifier(module: &mut CompiledModule) -> IdentifierIndex {
+    let mut suffix = 0;
+    let ident = loop {
+        let candidate = Identifier::new(format!("mutant_field_{}", suffix)).unwrap();
+        if !module.identifiers.contains(&candidate) {
+            break candidate;
+        }
+        suffix += 1;
+    };
+    module.identifiers.push(ident);
+    IdentifierIndex((module.identifiers.len() - 1) as TableIndex)
+}
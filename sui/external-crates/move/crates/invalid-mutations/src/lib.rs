@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+pub mod abilities;
 pub mod bounds;
 mod helpers;
 pub mod signature;
@@ -4,9 +4,16 @@
 
 //! This module implements a checker for verifying that all of the struct's fields satisfy the
 //! abilities required by the struct's abilities
+//!
+//! Only modules are checked: this binary format has no `CompiledScript`, and scripts could not
+//! declare datatypes in the first place, so there are no script fields to hold to any abilities.
+use crate::dependencies;
 use move_binary_format::{
     errors::{verification_error, Location, PartialVMResult, VMResult},
-    file_format::{AbilitySet, CompiledModule, StructFieldInformation, TableIndex},
+    file_format::{
+        AbilitySet, CompiledModule, DatatypeHandle, DatatypeHandleIndex, MemberCount,
+        StructDefinitionIndex, StructFieldInformation, TableIndex,
+    },
     IndexKind,
 };
 use move_core_types::vm_status::StatusCode;
@@ -15,52 +22,122 @@ pub fn verify_module(module: &CompiledModule) -> VMResult<()> {
     verify_module_impl(module).map_err(|e| e.finish(Location::Module(module.self_id())))
 }
 
-fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
-    for (idx, struct_def) in module.struct_defs().iter().enumerate() {
-        let sh = module.datatype_handle_at(struct_def.struct_handle);
-        let fields = match &struct_def.field_information {
-            StructFieldInformation::Native => continue,
-            StructFieldInformation::Declared(fields) => fields,
-        };
-        let required_abilities = sh
-            .abilities
-            .into_iter()
-            .map(|a| a.requires())
-            .fold(AbilitySet::EMPTY, |acc, required| acc | required);
-        // Assume type parameters have all abilities, as the struct's abilities will be dependent on
-        // them
-        let type_parameter_abilities = sh
-            .type_parameters
+/// Verifies the ability field requirements of `module` in the context of its `dependencies`.
+///
+/// The module-local pass computes the abilities of field types from the datatype handles declared
+/// in `module`, trusting them for imported types. This variant additionally resolves every imported
+/// datatype handle to its definition in `dependencies` and checks that the abilities and type
+/// parameters declared on the handle are consistent with the definition, so a handle claiming
+/// abilities its definition lacks cannot be used to satisfy a field requirement.
+pub fn verify_module_with_dependencies<'a>(
+    module: &CompiledModule,
+    dependencies: impl IntoIterator<Item = &'a CompiledModule>,
+) -> VMResult<()> {
+    dependencies::verify_imported_datatypes(module, dependencies)
+        .and_then(|()| verify_module_impl(module))
+        .map_err(|e| e.finish(Location::Module(module.self_id())))
+}
+
+/// Returns the abilities that a type argument must have, for each type parameter of the datatype
+/// at `handle`, in order for an instantiation of that datatype to have `abilities`.
+///
+/// Every type argument must satisfy the declared constraints of its parameter. Non-phantom
+/// parameters must additionally have `a.requires()` for every requested ability `a`, while phantom
+/// parameters never carry a requirement beyond their declared constraints, since they do not
+/// participate in the abilities of the instantiation.
+///
+/// Returns `None` if the datatype does not declare all of `abilities`, in which case no
+/// instantiation can have them.
+pub fn instantiation_constraints(
+    module: &CompiledModule,
+    handle: DatatypeHandleIndex,
+    abilities: AbilitySet,
+) -> Option<Vec<AbilitySet>> {
+    let sh = module.datatype_handle_at(handle);
+    if !abilities.is_subset(sh.abilities) {
+        return None;
+    }
+    let required = required_field_abilities(abilities);
+    Some(
+        sh.type_parameters
             .iter()
-            .map(|_| AbilitySet::ALL)
-            .collect::<Vec<_>>();
-        for field in fields {
-            let field_abilities =
-                module.abilities(&field.signature.0, &type_parameter_abilities)?;
-            if !required_abilities.is_subset(field_abilities) {
-                return Err(verification_error(
-                    StatusCode::FIELD_MISSING_TYPE_ABILITY,
-                    IndexKind::StructDefinition,
-                    idx as TableIndex,
-                ));
+            .map(|param| {
+                if param.is_phantom {
+                    param.constraints
+                } else {
+                    param.constraints | required
+                }
+            })
+            .collect(),
+    )
+}
+
+/// The abilities every field of a datatype with `abilities` must have.
+fn required_field_abilities(abilities: AbilitySet) -> AbilitySet {
+    abilities
+        .into_iter()
+        .map(|a| a.requires())
+        .fold(AbilitySet::EMPTY, |acc, required| acc | required)
+}
+
+/// The abilities assumed for the type parameters of `sh` when checking its fields.
+///
+/// Non-phantom type parameters are assumed to have all abilities, as the datatype's abilities
+/// will be dependent on them. Phantom type parameters are assumed to have none: they may only
+/// appear in phantom positions, which are ignored when computing abilities, so any field that
+/// relies on an ability of a phantom parameter is using it in a non-phantom position.
+fn type_parameter_abilities(sh: &DatatypeHandle) -> Vec<AbilitySet> {
+    sh.type_parameters
+        .iter()
+        .map(|param| {
+            if param.is_phantom {
+                AbilitySet::EMPTY
+            } else {
+                AbilitySet::ALL
             }
+        })
+        .collect()
+}
+
+/// Returns the index of the first field of the struct at `idx` whose type does not have the
+/// abilities required by the struct's abilities, if any.
+pub fn struct_field_missing_abilities(
+    module: &CompiledModule,
+    idx: StructDefinitionIndex,
+) -> PartialVMResult<Option<MemberCount>> {
+    let struct_def = module.struct_def_at(idx);
+    let sh = module.datatype_handle_at(struct_def.struct_handle);
+    let fields = match &struct_def.field_information {
+        StructFieldInformation::Native => return Ok(None),
+        StructFieldInformation::Declared(fields) => fields,
+    };
+    let required_abilities = required_field_abilities(sh.abilities);
+    let type_parameter_abilities = type_parameter_abilities(sh);
+    for (fi, field) in fields.iter().enumerate() {
+        let field_abilities = module.abilities(&field.signature.0, &type_parameter_abilities)?;
+        if !required_abilities.is_subset(field_abilities) {
+            return Ok(Some(fi as MemberCount));
+        }
+    }
+    Ok(None)
+}
+
+fn verify_module_impl(module: &CompiledModule) -> PartialVMResult<()> {
+    for idx in 0..module.struct_defs().len() {
+        let idx = StructDefinitionIndex(idx as TableIndex);
+        if struct_field_missing_abilities(module, idx)?.is_some() {
+            return Err(verification_error(
+                StatusCode::FIELD_MISSING_TYPE_ABILITY,
+                IndexKind::StructDefinition,
+                idx.0,
+            ));
         }
     }
 
     for (idx, enum_def) in module.enum_defs().iter().enumerate() {
         let sh = module.datatype_handle_at(enum_def.enum_handle);
-        let required_abilities = sh
-            .abilities
-            .into_iter()
-            .map(|a| a.requires())
-            .fold(AbilitySet::EMPTY, |acc, required| acc | required);
-        // Assume type parameters have all abilities, as the enum's abilities will be dependent on
-        // them
-        let type_parameter_abilities = sh
-            .type_parameters
-            .iter()
-            .map(|_| AbilitySet::ALL)
-            .collect::<Vec<_>>();
+        let required_abilities = required_field_abilities(sh.abilities);
+        let type_parameter_abilities = type_parameter_abilities(sh);
         for (i, variant) in enum_def.variants.iter().enumerate() {
             for (fi, field) in variant.fields.iter().enumerate() {
                 let field_abilities =
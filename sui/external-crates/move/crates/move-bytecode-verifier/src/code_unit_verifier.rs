@@ -20,7 +20,7 @@ use move_binary_format::{
 use move_bytecode_verifier_meter::{Meter, Scope};
 use move_core_types::vm_status::StatusCode;
 use move_vm_config::verifier::VerifierConfig;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 
 pub struct CodeUnitVerifier<'a> {
     module: &'a CompiledModule,
@@ -38,11 +38,48 @@ impl<'a> CodeUnitVerifier<'a> {
             .map_err(|e| e.finish(Location::Module(module.self_id())))
     }
 
+    /// Verifies only the bodies of the given `functions` of `module`.
+    ///
+    /// The module-wide back edge limit cannot be checked from a subset of the functions, so it is
+    /// not enforced here.
+    pub fn verify_functions(
+        verifier_config: &VerifierConfig,
+        module: &'a CompiledModule,
+        functions: &BTreeSet<FunctionDefinitionIndex>,
+        meter: &mut (impl Meter + ?Sized),
+    ) -> VMResult<()> {
+        Self::verify_functions_impl(
+            verifier_config,
+            module,
+            |index| functions.contains(&index),
+            meter,
+        )
+        .map(|_| ())
+        .map_err(|e| e.finish(Location::Module(module.self_id())))
+    }
+
     fn verify_module_impl(
         verifier_config: &VerifierConfig,
         module: &CompiledModule,
         meter: &mut (impl Meter + ?Sized),
     ) -> PartialVMResult<()> {
+        let total_back_edges =
+            Self::verify_functions_impl(verifier_config, module, |_| true, meter)?;
+        if let Some(limit) = verifier_config.max_back_edges_per_module {
+            if total_back_edges > limit {
+                return Err(PartialVMError::new(StatusCode::TOO_MANY_BACK_EDGES));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies the functions selected by `filter`, returning their total number of back edges.
+    fn verify_functions_impl(
+        verifier_config: &VerifierConfig,
+        module: &CompiledModule,
+        filter: impl Fn(FunctionDefinitionIndex) -> bool,
+        meter: &mut (impl Meter + ?Sized),
+    ) -> PartialVMResult<usize> {
         let mut name_def_map = HashMap::new();
         for (idx, func_def) in module.function_defs().iter().enumerate() {
             let fh = module.function_handle_at(func_def.function);
@@ -51,6 +88,9 @@ impl<'a> CodeUnitVerifier<'a> {
         let mut total_back_edges = 0;
         for (idx, function_definition) in module.function_defs().iter().enumerate() {
             let index = FunctionDefinitionIndex(idx as TableIndex);
+            if !filter(index) {
+                continue;
+            }
             let num_back_edges = Self::verify_function(
                 verifier_config,
                 index,
@@ -62,12 +102,7 @@ impl<'a> CodeUnitVerifier<'a> {
             .map_err(|err| err.at_index(IndexKind::FunctionDefinition, index.0))?;
             total_back_edges += num_back_edges;
         }
-        if let Some(limit) = verifier_config.max_back_edges_per_module {
-            if total_back_edges > limit {
-                return Err(PartialVMError::new(StatusCode::TOO_MANY_BACK_EDGES));
-            }
-        }
-        Ok(())
+        Ok(total_back_edges)
     }
 
     fn verify_function(
@@ -173,6 +173,18 @@ fn verify_module_impl<'a>(
     verify_all_script_visibility_usage(context)
 }
 
+/// Verifies that the datatypes `module` imports are defined in `dependencies` with the abilities
+/// and type parameters its handles declare, leaving its other imports unchecked.
+pub(crate) fn verify_imported_datatypes<'a>(
+    module: &CompiledModule,
+    dependencies: impl IntoIterator<Item = &'a CompiledModule>,
+) -> PartialVMResult<()> {
+    let context = &Context::module(module, dependencies);
+
+    verify_imported_modules(context)?;
+    verify_imported_structs(context)
+}
+
 fn verify_imported_modules(context: &Context) -> PartialVMResult<()> {
     let self_module = context.module.self_handle_idx();
     for (idx, module_handle) in context.module.module_handles().iter().enumerate() {
@@ -0,0 +1,229 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements incremental verification of a module against a previous version of it
+//! that is already known to verify, as happens when validating a package upgrade.
+//!
+//! The cheap, module-wide passes are always re-run on the new module. The passes over datatype
+//! definitions are only re-run if a datatype changed, and function bodies are only re-verified if
+//! they changed, unless a change to a datatype or signature they may depend on forces all of them
+//! to be re-verified.
+use crate::{
+    ability_field_requirements, check_duplication::DuplicationChecker,
+    code_unit_verifier::CodeUnitVerifier, constants, data_defs::RecursiveDataDefChecker, friends,
+    instantiation_loops::InstantiationLoopChecker, instruction_consistency::InstructionConsistency,
+    limits::LimitsVerifier, script_signature,
+    script_signature::no_additional_script_signature_checks, signature::SignatureChecker,
+    verifier::verify_module_with_config_metered,
+};
+use move_binary_format::{
+    check_bounds::BoundsChecker,
+    errors::{Location, VMResult},
+    file_format::{
+        AbilitySet, CompiledModule, DatatypeTyParameter, FunctionDefinition,
+        FunctionDefinitionIndex, TableIndex,
+    },
+    normalized,
+};
+use move_bytecode_verifier_meter::{dummy::DummyMeter, Meter};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_vm_config::verifier::VerifierConfig;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The differences between two versions of a module that determine which verification passes need
+/// to be re-run on the new version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleDiff {
+    /// Whether any struct or enum definition, or the declaration of any datatype used by the
+    /// module, changed.
+    pub datatypes_changed: bool,
+    /// Whether any function declaration, constant, dependency or friend changed, or a function was
+    /// removed.
+    pub signatures_changed: bool,
+    /// The functions of the new module whose body changed or that are not present in the old
+    /// module.
+    pub changed_functions: BTreeSet<FunctionDefinitionIndex>,
+}
+
+impl ModuleDiff {
+    /// Computes the difference between `old` and `new`.
+    ///
+    /// Both modules must have passed at least the bounds, signature and instruction consistency
+    /// checks, as normalizing a malformed module may panic.
+    pub fn new(old: &CompiledModule, new: &CompiledModule) -> Self {
+        let old_normalized = normalized::Module::new(old);
+        let new_normalized = normalized::Module::new(new);
+
+        let datatypes_changed = old_normalized.structs != new_normalized.structs
+            || old_normalized.enums != new_normalized.enums
+            || datatype_declarations(old) != datatype_declarations(new);
+
+        let signatures_changed = old_normalized.dependencies != new_normalized.dependencies
+            || old_normalized.friends != new_normalized.friends
+            || old_normalized.constants != new_normalized.constants
+            || function_declarations(old) != function_declarations(new)
+            || old_normalized.functions.iter().any(|(name, old_fun)| {
+                new_normalized.functions.get(name).map_or(true, |new_fun| {
+                    old_fun.visibility != new_fun.visibility || old_fun.is_entry != new_fun.is_entry
+                })
+            });
+
+        let old_bodies = function_bodies(old);
+        let changed_functions = new
+            .function_defs()
+            .iter()
+            .enumerate()
+            .filter(|(_, def)| {
+                let (name, body) = function_body(new, def);
+                old_bodies.get(&name) != Some(&body)
+            })
+            .map(|(idx, _)| FunctionDefinitionIndex(idx as TableIndex))
+            .collect();
+
+        Self {
+            datatypes_changed,
+            signatures_changed,
+            changed_functions,
+        }
+    }
+}
+
+/// Incrementally verifies `new` given that `old` is a previous version of it that verifies.
+///
+/// Produces the same result as `verify_module_with_config_metered(config, new, meter)` under an
+/// unbounded meter, provided that `old` verifies under `config`. If `config` bounds the number of
+/// back edges per module, which cannot be checked from a subset of the functions, `new` is
+/// verified in full.
+///
+/// Only the functions that are re-verified are charged to `meter`, so a module or package limit
+/// that a full verification of `new` would exceed may not be hit here. Callers enforcing such
+/// limits must verify `new` in full instead.
+pub fn verify_module_incremental_metered(
+    config: &VerifierConfig,
+    old: &CompiledModule,
+    new: &CompiledModule,
+    meter: &mut (impl Meter + ?Sized),
+) -> VMResult<()> {
+    if config.max_back_edges_per_module.is_some() {
+        return verify_module_with_config_metered(config, new, meter);
+    }
+
+    BoundsChecker::verify_module(new).map_err(|e| {
+        // We can't point the error at the module, because if bounds-checking
+        // failed, we cannot safely index into module's handle to itself.
+        e.finish(Location::Undefined)
+    })?;
+    LimitsVerifier::verify_module(config, new)?;
+    DuplicationChecker::verify_module(new)?;
+    SignatureChecker::verify_module(new)?;
+    InstructionConsistency::verify_module(new)?;
+    constants::verify_module(new)?;
+    friends::verify_module(new)?;
+
+    let diff = ModuleDiff::new(old, new);
+    if diff.datatypes_changed {
+        ability_field_requirements::verify_module(new)?;
+        RecursiveDataDefChecker::verify_module(new)?;
+    }
+    InstantiationLoopChecker::verify_module(new)?;
+    if diff.datatypes_changed || diff.signatures_changed {
+        CodeUnitVerifier::verify_module(config, new, meter)?;
+    } else {
+        CodeUnitVerifier::verify_functions(config, new, &diff.changed_functions, meter)?;
+    }
+
+    script_signature::verify_module(new, no_additional_script_signature_checks)
+}
+
+pub fn verify_module_incremental_unmetered(
+    config: &VerifierConfig,
+    old: &CompiledModule,
+    new: &CompiledModule,
+) -> VMResult<()> {
+    verify_module_incremental_metered(config, old, new, &mut DummyMeter)
+}
+
+type QualifiedName = (ModuleId, Identifier);
+
+/// The type parameters, parameters and return types of a function.
+type FunctionDeclaration = (
+    Vec<AbilitySet>,
+    Vec<normalized::Type>,
+    Vec<normalized::Type>,
+);
+
+/// The body of a function: its locals and its code.
+type FunctionBody = (Vec<normalized::Type>, normalized::Function);
+
+/// The declarations of all datatypes referenced by `module`, including imported ones, as the
+/// abilities and type parameters of those affect the verification of function bodies.
+fn datatype_declarations(
+    module: &CompiledModule,
+) -> BTreeMap<QualifiedName, (AbilitySet, Vec<DatatypeTyParameter>)> {
+    module
+        .datatype_handles()
+        .iter()
+        .map(|handle| {
+            let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+            let name = module.identifier_at(handle.name).to_owned();
+            (
+                (module_id, name),
+                (handle.abilities, handle.type_parameters.clone()),
+            )
+        })
+        .collect()
+}
+
+/// The declarations of all functions referenced by `module`, including imported ones, as function
+/// bodies are verified against the declarations of the functions they call.
+fn function_declarations(module: &CompiledModule) -> BTreeMap<QualifiedName, FunctionDeclaration> {
+    let types = |idx| {
+        module
+            .signature_at(idx)
+            .0
+            .iter()
+            .map(|ty| normalized::Type::new(module, ty))
+            .collect::<Vec<_>>()
+    };
+    module
+        .function_handles()
+        .iter()
+        .map(|handle| {
+            let module_id = module.module_id_for_handle(module.module_handle_at(handle.module));
+            let name = module.identifier_at(handle.name).to_owned();
+            (
+                (module_id, name),
+                (
+                    handle.type_parameters.clone(),
+                    types(handle.parameters),
+                    types(handle.return_),
+                ),
+            )
+        })
+        .collect()
+}
+
+fn function_bodies(module: &CompiledModule) -> BTreeMap<Identifier, FunctionBody> {
+    module
+        .function_defs()
+        .iter()
+        .map(|def| function_body(module, def))
+        .collect()
+}
+
+fn function_body(module: &CompiledModule, def: &FunctionDefinition) -> (Identifier, FunctionBody) {
+    let locals = def
+        .code
+        .as_ref()
+        .map(|code| {
+            module
+                .signature_at(code.locals)
+                .0
+                .iter()
+                .map(|ty| normalized::Type::new(module, ty))
+                .collect()
+        })
+        .unwrap_or_default();
+    let (name, function) = normalized::Function::new(module, def);
+    (name, (locals, function))
+}
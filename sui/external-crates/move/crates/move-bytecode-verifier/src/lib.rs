@@ -17,12 +17,18 @@ pub mod cyclic_dependencies;
 pub mod data_defs;
 pub mod dependencies;
 pub mod friends;
+pub mod incremental;
 pub mod instantiation_loops;
 pub mod instruction_consistency;
 pub mod limits;
 pub mod loop_summary;
+pub mod metrics;
+pub mod module_graph;
 pub mod script_signature;
 pub mod signature;
+#[cfg(feature = "source-map")]
+pub mod source_locations;
+pub mod type_display;
 pub mod verifier;
 
 pub use check_duplication::DuplicationChecker;
@@ -33,9 +39,10 @@ pub use script_signature::{
     legacy_script_signature_checks, no_additional_script_signature_checks, FnCheckScriptSignature,
 };
 pub use signature::SignatureChecker;
+#[cfg(not(target_arch = "wasm32"))]
+pub use verifier::verify_module_with_config_for_test;
 pub use verifier::{
-    verify_module_unmetered, verify_module_with_config_for_test, verify_module_with_config_metered,
-    verify_module_with_config_unmetered,
+    verify_module_unmetered, verify_module_with_config_metered, verify_module_with_config_unmetered,
 };
 
 mod acquires_list_verifier;
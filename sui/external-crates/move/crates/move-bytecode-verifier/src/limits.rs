@@ -136,6 +136,20 @@ impl<'a> LimitsVerifier<'a> {
                 ));
             }
         }
+        if let Some(max_struct_definitions) = config.max_struct_definitions {
+            if self.module.struct_defs().len() > max_struct_definitions {
+                return Err(PartialVMError::new(
+                    StatusCode::MAX_STRUCT_ONLY_DEFINITIONS_REACHED,
+                ));
+            }
+        }
+        if let Some(max_enum_definitions) = config.max_enum_definitions {
+            if self.module.enum_defs().len() > max_enum_definitions {
+                return Err(PartialVMError::new(
+                    StatusCode::MAX_ENUM_DEFINITIONS_REACHED,
+                ));
+            }
+        }
 
         if let Some(max_fields_in_struct) = config.max_fields_in_struct {
             for def in self.module.struct_defs() {
@@ -172,6 +186,19 @@ impl<'a> LimitsVerifier<'a> {
                 }
             }
         }
+
+        // Number of fields in each variant is less than the number of fields allowed in a variant.
+        if let Some(max_fields_in_variant) = config.max_fields_in_variant {
+            for (idx, def) in self.module.enum_defs().iter().enumerate() {
+                for (tag, variant) in def.variants.iter().enumerate() {
+                    if variant.fields.len() > max_fields_in_variant {
+                        return Err(PartialVMError::new(StatusCode::MAX_VARIANT_FIELDS_REACHED)
+                            .at_index(IndexKind::EnumDefinition, idx as TableIndex)
+                            .at_index(IndexKind::VariantTag, tag as TableIndex));
+                    }
+                }
+            }
+        }
         Ok(())
     }
 
@@ -0,0 +1,139 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module computes size and shape metrics for a module, for tooling that estimates
+//! publishing costs or budgets module sizes ahead of verification.
+//!
+//! The metrics are computed directly from the module's tables and are meant to be run on modules
+//! that have passed the bounds checker.
+use move_binary_format::{
+    file_format::{Ability, AbilitySet, CompiledModule, StructFieldInformation},
+    IndexKind,
+};
+use move_core_types::language_storage::ModuleId;
+use std::collections::BTreeMap;
+
+/// The index kinds that correspond to a table of the module.
+const TABLE_KINDS: &[IndexKind] = &[
+    IndexKind::ModuleHandle,
+    IndexKind::DatatypeHandle,
+    IndexKind::FunctionHandle,
+    IndexKind::FieldHandle,
+    IndexKind::FriendDeclaration,
+    IndexKind::StructDefInstantiation,
+    IndexKind::FunctionInstantiation,
+    IndexKind::FieldInstantiation,
+    IndexKind::StructDefinition,
+    IndexKind::FunctionDefinition,
+    IndexKind::Signature,
+    IndexKind::Identifier,
+    IndexKind::AddressIdentifier,
+    IndexKind::ConstantPool,
+    IndexKind::EnumDefinition,
+    IndexKind::EnumDefInstantiation,
+    IndexKind::VariantHandle,
+    IndexKind::VariantInstantiationHandle,
+];
+
+/// Size and shape metrics of a module.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModuleMetrics {
+    /// The total number of instructions across all function bodies.
+    pub instruction_count: usize,
+    /// The largest number of instructions in a single function body.
+    pub max_function_instructions: usize,
+    /// The number of entries in each table of the module.
+    pub table_sizes: BTreeMap<IndexKind, usize>,
+    /// The largest number of fields declared by a single struct, ignoring native structs.
+    pub max_struct_fields: usize,
+    /// The largest number of variants declared by a single enum.
+    pub max_enum_variants: usize,
+    /// The largest number of fields declared by a single enum variant.
+    pub max_variant_fields: usize,
+    /// For each ability, the number of datatypes defined in the module that declare it.
+    pub ability_distribution: BTreeMap<Ability, usize>,
+    /// For each module the module depends on, the number of datatype and function handles that
+    /// refer to it.
+    pub dependency_fan_out: BTreeMap<ModuleId, usize>,
+}
+
+/// Computes the metrics of `module`.
+pub fn module_metrics(module: &CompiledModule) -> ModuleMetrics {
+    let function_sizes = module
+        .function_defs()
+        .iter()
+        .filter_map(|def| def.code.as_ref())
+        .map(|code| code.code.len());
+    let (instruction_count, max_function_instructions) =
+        function_sizes.fold((0, 0), |(total, max), size| (total + size, max.max(size)));
+
+    let table_sizes = TABLE_KINDS
+        .iter()
+        .map(|kind| (*kind, module.kind_count(*kind)))
+        .collect();
+
+    let max_struct_fields = module
+        .struct_defs()
+        .iter()
+        .map(|def| match &def.field_information {
+            StructFieldInformation::Native => 0,
+            StructFieldInformation::Declared(fields) => fields.len(),
+        })
+        .max()
+        .unwrap_or(0);
+    let max_enum_variants = module
+        .enum_defs()
+        .iter()
+        .map(|def| def.variants.len())
+        .max()
+        .unwrap_or(0);
+    let max_variant_fields = module
+        .enum_defs()
+        .iter()
+        .flat_map(|def| def.variants.iter())
+        .map(|variant| variant.fields.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut ability_distribution: BTreeMap<Ability, usize> =
+        AbilitySet::ALL.into_iter().map(|a| (a, 0)).collect();
+    let defined_handles = module
+        .struct_defs()
+        .iter()
+        .map(|def| def.struct_handle)
+        .chain(module.enum_defs().iter().map(|def| def.enum_handle));
+    for handle in defined_handles {
+        for ability in module.datatype_handle_at(handle).abilities {
+            *ability_distribution.entry(ability).or_default() += 1;
+        }
+    }
+
+    let self_handle = module.self_handle_idx();
+    let mut dependency_fan_out: BTreeMap<ModuleId, usize> = BTreeMap::new();
+    let handle_modules = module
+        .datatype_handles()
+        .iter()
+        .map(|handle| handle.module)
+        .chain(module.function_handles().iter().map(|handle| handle.module));
+    for module_handle in handle_modules {
+        if module_handle == self_handle {
+            continue;
+        }
+        let id = module.module_id_for_handle(module.module_handle_at(module_handle));
+        *dependency_fan_out.entry(id).or_default() += 1;
+    }
+    for id in module.immediate_dependencies() {
+        dependency_fan_out.entry(id).or_default();
+    }
+
+    ModuleMetrics {
+        instruction_count,
+        max_function_instructions,
+        table_sizes,
+        max_struct_fields,
+        max_enum_variants,
+        max_variant_fields,
+        ability_distribution,
+        dependency_fan_out,
+    }
+}
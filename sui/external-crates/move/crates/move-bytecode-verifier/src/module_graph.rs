@@ -0,0 +1,210 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module builds the dependency graph of a set of modules, e.g. the modules of a package, for
+//! ordering them for publishing and for auditing how they refer to each other.
+//!
+//! A module depends on every module it has a handle to. A friend declaration is treated as a
+//! dependency of the friend on the declaring module, since the friend is the one calling into the
+//! declaring module: the declaring module has to be published first, and a module can not be both
+//! a friend and a (transitive) dependency of another module.
+use move_binary_format::{
+    errors::{Location, PartialVMError, VMResult},
+    file_format::CompiledModule,
+};
+use move_core_types::{identifier::Identifier, language_storage::ModuleId, vm_status::StatusCode};
+use petgraph::{algo::tarjan_scc, graphmap::DiGraphMap};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// How a module depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DependencyKind {
+    /// The module has a handle to the other module.
+    ModuleHandle,
+    /// The other module declares the module as a friend.
+    Friend,
+    /// The module refers to datatypes declared in the other module.
+    Datatype,
+}
+
+/// The ways in which a module depends on another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DependencyEdge {
+    pub kinds: BTreeSet<DependencyKind>,
+    /// The datatypes of the other module the module refers to.
+    pub datatypes: BTreeSet<Identifier>,
+}
+
+/// The dependency graph of a set of modules.
+///
+/// Dependencies on modules outside the set are not part of the graph, they are reported by
+/// `external_dependencies` instead.
+pub struct ModuleGraph<'a> {
+    modules: Vec<&'a CompiledModule>,
+    indices: BTreeMap<ModuleId, usize>,
+    /// The edges of the graph, from a module to each module of the set it depends on.
+    edges: BTreeMap<usize, BTreeMap<usize, DependencyEdge>>,
+    external_dependencies: BTreeSet<ModuleId>,
+}
+
+impl<'a> ModuleGraph<'a> {
+    /// Builds the graph of `modules`, failing with `DUPLICATE_ELEMENT` if a module appears twice.
+    pub fn new(modules: impl IntoIterator<Item = &'a CompiledModule>) -> VMResult<Self> {
+        let modules: Vec<_> = modules.into_iter().collect();
+        let mut indices = BTreeMap::new();
+        for (idx, module) in modules.iter().enumerate() {
+            let self_id = module.self_id();
+            if indices.insert(self_id.clone(), idx).is_some() {
+                return Err(PartialVMError::new(StatusCode::DUPLICATE_ELEMENT)
+                    .with_message("module appears more than once in the module set".to_string())
+                    .finish(Location::Module(self_id)));
+            }
+        }
+
+        let mut graph = Self {
+            modules,
+            indices,
+            edges: BTreeMap::new(),
+            external_dependencies: BTreeSet::new(),
+        };
+        for idx in 0..graph.modules.len() {
+            let module = graph.modules[idx];
+            graph.edges.entry(idx).or_default();
+            for dep in module.immediate_dependencies() {
+                graph.add_edge(idx, dep, DependencyKind::ModuleHandle, None);
+            }
+            for handle in module.datatype_handles() {
+                if handle.module == module.self_handle_idx() {
+                    continue;
+                }
+                let dep = module.module_id_for_handle(module.module_handle_at(handle.module));
+                let name = module.identifier_at(handle.name).to_owned();
+                graph.add_edge(idx, dep, DependencyKind::Datatype, Some(name));
+            }
+        }
+        // A friend depends on the module declaring it, which is the reverse direction.
+        for idx in 0..graph.modules.len() {
+            let module = graph.modules[idx];
+            for friend in module.immediate_friends() {
+                match graph.indices.get(&friend) {
+                    Some(friend_idx) => {
+                        let self_id = module.self_id();
+                        graph.add_edge(*friend_idx, self_id, DependencyKind::Friend, None)
+                    }
+                    None => {
+                        graph.external_dependencies.insert(friend);
+                    }
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn add_edge(
+        &mut self,
+        from: usize,
+        to: ModuleId,
+        kind: DependencyKind,
+        datatype: Option<Identifier>,
+    ) {
+        let to = match self.indices.get(&to) {
+            Some(to) => *to,
+            None => {
+                self.external_dependencies.insert(to);
+                return;
+            }
+        };
+        let edge = self.edges.entry(from).or_default().entry(to).or_default();
+        edge.kinds.insert(kind);
+        edge.datatypes.extend(datatype);
+    }
+
+    /// Returns the modules of the set, in the order they were given.
+    pub fn modules(&self) -> &[&'a CompiledModule] {
+        &self.modules
+    }
+
+    /// Returns the modules outside the set that modules of the set depend on or declare as friends.
+    pub fn external_dependencies(&self) -> &BTreeSet<ModuleId> {
+        &self.external_dependencies
+    }
+
+    /// Returns the modules of the set that `module` depends on, with how it depends on them.
+    pub fn dependencies(&self, module: &ModuleId) -> Vec<(ModuleId, &DependencyEdge)> {
+        let idx = match self.indices.get(module) {
+            Some(idx) => idx,
+            None => return vec![],
+        };
+        self.edges[idx]
+            .iter()
+            .map(|(to, edge)| (self.modules[*to].self_id(), edge))
+            .collect()
+    }
+
+    /// Returns the modules involved in a dependency cycle, if there is any.
+    pub fn find_cycle(&self) -> Option<Vec<ModuleId>> {
+        tarjan_scc(&self.petgraph())
+            .into_iter()
+            .find(|component| match component.as_slice() {
+                [idx] => self.edges[idx].contains_key(idx),
+                _ => true,
+            })
+            .map(|mut component| {
+                component.sort();
+                component
+                    .into_iter()
+                    .map(|idx| self.modules[idx].self_id())
+                    .collect()
+            })
+    }
+
+    /// Returns the modules of the set ordered so that every module comes after the modules it
+    /// depends on, failing with `CYCLIC_MODULE_DEPENDENCY` if there is a dependency cycle.
+    ///
+    /// Among modules that do not depend on each other, the order they were given in is preserved.
+    pub fn topological_order(&self) -> VMResult<Vec<&'a CompiledModule>> {
+        if let Some(cycle) = self.find_cycle() {
+            let names = cycle
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(PartialVMError::new(StatusCode::CYCLIC_MODULE_DEPENDENCY)
+                .with_message(format!("dependency cycle between modules {}", names))
+                .finish(Location::Module(cycle[0].clone())));
+        }
+
+        let mut remaining: BTreeMap<usize, usize> = self
+            .edges
+            .iter()
+            .map(|(idx, deps)| (*idx, deps.len()))
+            .collect();
+        let mut order = Vec::with_capacity(self.modules.len());
+        while let Some(next) = remaining
+            .iter()
+            .find(|(_, deps)| **deps == 0)
+            .map(|(idx, _)| *idx)
+        {
+            remaining.remove(&next);
+            order.push(self.modules[next]);
+            for (idx, deps) in remaining.iter_mut() {
+                if self.edges[idx].contains_key(&next) {
+                    *deps -= 1;
+                }
+            }
+        }
+        debug_assert!(remaining.is_empty());
+        Ok(order)
+    }
+
+    fn petgraph(&self) -> DiGraphMap<usize, ()> {
+        let mut graph = DiGraphMap::new();
+        for (from, deps) in &self.edges {
+            graph.add_node(*from);
+            for to in deps.keys() {
+                graph.add_edge(*from, *to, ());
+            }
+        }
+        graph
+    }
+}
@@ -0,0 +1,127 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module maps verification errors back to locations in the source code a module was
+//! compiled from, using the module's source map, so that tooling can report them against the
+//! offending declaration rather than a table index.
+use crate::{ability_field_requirements, verifier::verify_module_with_config_unmetered};
+use move_binary_format::{
+    errors::VMError,
+    file_format::{
+        CompiledModule, EnumDefinitionIndex, FunctionDefinitionIndex, StructDefinitionIndex,
+        TableIndex,
+    },
+    IndexKind,
+};
+use move_bytecode_source_map::source_map::SourceMap;
+use move_core_types::vm_status::StatusCode;
+use move_ir_types::location::{ByteIndex, Loc};
+use move_vm_config::verifier::VerifierConfig;
+
+/// A verification error, along with the source location it was mapped to, if any.
+#[derive(Debug)]
+pub struct LocatedVMError {
+    pub error: VMError,
+    pub loc: Option<Loc>,
+}
+
+/// A 1-based line and column within a source file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Verifies `module` and, if verification fails and a `source_map` is provided, maps the error to
+/// a location in the module's source.
+pub fn verify_module_with_source_map(
+    config: &VerifierConfig,
+    module: &CompiledModule,
+    source_map: Option<&SourceMap>,
+) -> Result<(), LocatedVMError> {
+    verify_module_with_config_unmetered(config, module).map_err(|error| {
+        let loc = source_map.and_then(|source_map| error_location(module, source_map, &error));
+        LocatedVMError { error, loc }
+    })
+}
+
+/// Returns the most precise source location `error` can be mapped to, using `source_map`.
+///
+/// Errors pointing into a function body are mapped to the offending instruction. Errors pointing
+/// at a struct or enum are mapped to the offending field when the error identifies it, falling
+/// back to the datatype's declaration otherwise.
+pub fn error_location(
+    module: &CompiledModule,
+    source_map: &SourceMap,
+    error: &VMError,
+) -> Option<Loc> {
+    if let Some((fdef_idx, offset)) = error.offsets().first() {
+        return source_map.get_code_location(*fdef_idx, *offset).ok();
+    }
+
+    let indices = error.indices();
+    let index_of = |kind: IndexKind| {
+        indices
+            .iter()
+            .find(|(k, _)| *k == kind)
+            .map(|(_, idx)| *idx)
+    };
+
+    if let Some(idx) = index_of(IndexKind::StructDefinition) {
+        let idx = StructDefinitionIndex(idx);
+        let struct_map = source_map.get_struct_source_map(idx).ok()?;
+        let field = index_of(IndexKind::FieldDefinition).or_else(|| {
+            // The ability requirements pass only reports the struct, find the field it rejected.
+            if error.major_status() == StatusCode::FIELD_MISSING_TYPE_ABILITY {
+                ability_field_requirements::struct_field_missing_abilities(module, idx)
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            }
+        });
+        return Some(
+            field
+                .and_then(|field| struct_map.get_field_location(field))
+                .unwrap_or(struct_map.definition_location),
+        );
+    }
+
+    if let Some(idx) = index_of(IndexKind::EnumDefinition) {
+        let enum_map = source_map
+            .get_enum_source_map(EnumDefinitionIndex(idx))
+            .ok()?;
+        let field_loc = index_of(IndexKind::VariantTag).and_then(|tag| {
+            let (_, field_locs) = enum_map.get_variant_location(tag)?;
+            let field = index_of(IndexKind::FieldDefinition)?;
+            field_locs.get(field as usize).copied()
+        });
+        return Some(field_loc.unwrap_or(enum_map.definition_location));
+    }
+
+    if let Some(idx) = index_of(IndexKind::FunctionDefinition) {
+        return source_map
+            .get_function_source_map(FunctionDefinitionIndex(idx as TableIndex))
+            .ok()
+            .map(|function_map| function_map.definition_location);
+    }
+
+    Some(source_map.definition_location)
+}
+
+/// Converts a byte offset into `file_contents` into a line and column, counting columns in
+/// characters.
+pub fn line_column(file_contents: &str, offset: ByteIndex) -> LineColumn {
+    let offset = (offset as usize).min(file_contents.len());
+    let prefix = &file_contents.as_bytes()[..offset];
+    let line_start = prefix
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map_or(0, |pos| pos + 1);
+    let line = prefix.iter().filter(|b| **b == b'\n').count() + 1;
+    let column = String::from_utf8_lossy(&prefix[line_start..])
+        .chars()
+        .count()
+        + 1;
+    LineColumn { line, column }
+}
@@ -0,0 +1,150 @@
+// Copyright (c) The Move Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module renders `SignatureToken`s as Move source types, resolving datatype handles to
+//! their fully qualified names, e.g. `vector<0x2::coin::Coin<T0>>`.
+//!
+//! The rendering is meant for diagnostics and previews: it is not guaranteed to be stable and
+//! should not be parsed back.
+use move_binary_format::file_format::{
+    Ability, AbilitySet, CompiledModule, DatatypeHandleIndex, SignatureToken,
+};
+use std::fmt;
+
+/// Displays a `SignatureToken` of `module` as a Move type.
+///
+/// Type parameters are rendered as `T<index>`. If abilities are requested, the abilities of the
+/// whole type are appended as a `has` clause, computed with the type parameter constraints given
+/// to `with_abilities`.
+pub struct SignatureTokenDisplay<'a> {
+    module: &'a CompiledModule,
+    token: &'a SignatureToken,
+    type_parameters: Option<&'a [AbilitySet]>,
+}
+
+impl<'a> SignatureTokenDisplay<'a> {
+    pub fn new(module: &'a CompiledModule, token: &'a SignatureToken) -> Self {
+        Self {
+            module,
+            token,
+            type_parameters: None,
+        }
+    }
+
+    /// Appends the abilities of the type, given the constraints of the type parameters in scope.
+    pub fn with_abilities(self, type_parameters: &'a [AbilitySet]) -> Self {
+        Self {
+            type_parameters: Some(type_parameters),
+            ..self
+        }
+    }
+
+    fn fmt_token(&self, f: &mut fmt::Formatter, token: &SignatureToken) -> fmt::Result {
+        use SignatureToken::*;
+
+        match token {
+            Bool => write!(f, "bool"),
+            U8 => write!(f, "u8"),
+            U16 => write!(f, "u16"),
+            U32 => write!(f, "u32"),
+            U64 => write!(f, "u64"),
+            U128 => write!(f, "u128"),
+            U256 => write!(f, "u256"),
+            Address => write!(f, "address"),
+            Signer => write!(f, "signer"),
+            TypeParameter(idx) => write!(f, "T{}", idx),
+            Vector(inner) => {
+                write!(f, "vector<")?;
+                self.fmt_token(f, inner)?;
+                write!(f, ">")
+            }
+            Reference(inner) => {
+                write!(f, "&")?;
+                self.fmt_token(f, inner)
+            }
+            MutableReference(inner) => {
+                write!(f, "&mut ")?;
+                self.fmt_token(f, inner)
+            }
+            Datatype(idx) => self.fmt_datatype(f, *idx, &[]),
+            DatatypeInstantiation(inst) => {
+                let (idx, type_args) = &**inst;
+                self.fmt_datatype(f, *idx, type_args)
+            }
+        }
+    }
+
+    fn fmt_datatype(
+        &self,
+        f: &mut fmt::Formatter,
+        idx: DatatypeHandleIndex,
+        type_args: &[SignatureToken],
+    ) -> fmt::Result {
+        let handle = self.module.datatype_handle_at(idx);
+        let module_id = self
+            .module
+            .module_id_for_handle(self.module.module_handle_at(handle.module));
+        write!(
+            f,
+            "0x{}::{}::{}",
+            module_id.address().short_str_lossless(),
+            module_id.name(),
+            self.module.identifier_at(handle.name)
+        )?;
+        if type_args.is_empty() {
+            return Ok(());
+        }
+        write!(f, "<")?;
+        for (i, arg) in type_args.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            self.fmt_token(f, arg)?;
+        }
+        write!(f, ">")
+    }
+}
+
+impl fmt::Display for SignatureTokenDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_token(f, self.token)?;
+        let type_parameters = match self.type_parameters {
+            Some(type_parameters) => type_parameters,
+            None => return Ok(()),
+        };
+        // References have no abilities worth reporting, and type parameters without a constraint
+        // in scope cannot be resolved.
+        let unresolved = self.token.preorder_traversal().any(|token| match token {
+            SignatureToken::TypeParameter(idx) => *idx as usize >= type_parameters.len(),
+            _ => false,
+        });
+        if unresolved || self.token.is_reference() {
+            return Ok(());
+        }
+        let abilities = match self.module.abilities(self.token, type_parameters) {
+            Ok(abilities) => abilities,
+            Err(_) => return Ok(()),
+        };
+        write_abilities(f, abilities)
+    }
+}
+
+fn write_abilities(f: &mut fmt::Formatter, abilities: AbilitySet) -> fmt::Result {
+    if abilities == AbilitySet::EMPTY {
+        return Ok(());
+    }
+    write!(f, " has ")?;
+    for (i, ability) in abilities.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        let name = match ability {
+            Ability::Copy => "copy",
+            Ability::Drop => "drop",
+            Ability::Store => "store",
+            Ability::Key => "key",
+        };
+        write!(f, "{}", name)?;
+    }
+    Ok(())
+}
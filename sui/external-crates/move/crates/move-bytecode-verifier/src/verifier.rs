@@ -14,10 +14,10 @@ use move_binary_format::{
     check_bounds::BoundsChecker,
     errors::{Location, VMResult},
     file_format::CompiledModule,
-    file_format_common::VERSION_6,
 };
 use move_bytecode_verifier_meter::{dummy::DummyMeter, Meter};
 use move_vm_config::verifier::VerifierConfig;
+#[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 
 /// Helper for a "canonical" verification of a module.
@@ -34,6 +34,9 @@ pub fn verify_module_unmetered(module: &CompiledModule) -> VMResult<()> {
     verify_module_with_config_unmetered(&VerifierConfig::default(), module)
 }
 
+/// Verifies `module` and reports timing and size to stderr. Not available on
+/// `wasm32`, where `std::time::Instant` is unsupported.
+#[cfg(not(target_arch = "wasm32"))]
 pub fn verify_module_with_config_for_test(
     name: &str,
     config: &VerifierConfig,
@@ -42,6 +45,7 @@ pub fn verify_module_with_config_for_test(
 ) -> VMResult<()> {
     const MAX_MODULE_SIZE: usize = 65355;
     let mut bytes = vec![];
+    use move_binary_format::file_format_common::VERSION_6;
     let version = if config.bytecode_version > VERSION_6 {
         module.version
     } else {
@@ -313,6 +313,11 @@ pub enum StatusCode {
     INVALID_ENUM_SWITCH = 1133,
     ZERO_SIZED_ENUM = 1134,
     MAX_VARIANTS_REACHED = 1135,
+    MAX_ENUM_DEFINITIONS_REACHED = 1136,
+    MAX_VARIANT_FIELDS_REACHED = 1137,
+    // Too many struct definitions, as opposed to structs and enums together, which is reported
+    // as MAX_STRUCT_DEFINITIONS_REACHED.
+    MAX_STRUCT_ONLY_DEFINITIONS_REACHED = 1138,
 
     // These are errors that the VM might raise if a violation of internal
     // invariants takes place.
@@ -29,6 +29,9 @@ pub struct VerifierConfig {
     pub reject_mutable_random_on_entry_functions: bool,
     pub bytecode_version: u32,
     pub max_variants_in_enum: Option<u64>,
+    pub max_struct_definitions: Option<usize>,
+    pub max_enum_definitions: Option<usize>,
+    pub max_fields_in_variant: Option<usize>,
 }
 
 #[derive(Debug, Clone)]
@@ -73,6 +76,12 @@ impl Default for VerifierConfig {
             reject_mutable_random_on_entry_functions: true,
             bytecode_version: VERSION_MAX,
             max_variants_in_enum: Some(DEFAULT_MAX_VARIANTS),
+            // Max count of structs in a module, on top of `max_data_definitions`
+            max_struct_definitions: None,
+            // Max count of enums in a module, on top of `max_data_definitions`
+            max_enum_definitions: None,
+            // Max count of fields in a single enum variant
+            max_fields_in_variant: None,
         }
     }
 }
@@ -52,6 +52,9 @@ pub(crate) fn production_config() -> (VerifierConfig, MeterConfig) {
             reject_mutable_random_on_entry_functions: true,
             bytecode_version: VERSION_6,
             max_variants_in_enum: Some(VARIANT_COUNT_MAX),
+            max_struct_definitions: None,
+            max_enum_definitions: None,
+            max_fields_in_variant: None,
         },
         MeterConfig::default(),
     )